@@ -0,0 +1,170 @@
+//! Exporting of Typst documents into accessible linearizations.
+//!
+//! Two formats are supported: Plain text for speech synthesis, in which
+//! formulas are spoken in the style of MathSpeak, and a simple XML structure
+//! for Braille transcription pipelines, in which formulas are written in
+//! Typst's linear math syntax. Both are built from the document's semantic
+//! [structure](typst::model::Structure), the same one that the DOCX exporter
+//! uses, which is flattened into headings, paragraphs, formulas, and images.
+
+mod math;
+mod tree;
+
+use typst::diag::SourceResult;
+use typst::model::{Document, DocumentExporter};
+use xmlwriter::{Indent, Options, XmlWriter};
+
+use crate::tree::{Block, Formula, Inline};
+
+pub use self::math::{linearize, speak};
+
+/// Export a document into plain text for speech synthesis.
+///
+/// Blocks are separated by blank lines and headings are announced with their
+/// level.
+#[typst_macros::time(name = "speech")]
+pub fn speech(document: &Document) -> String {
+    let tree = tree::tree(document);
+    let mut out = String::new();
+    for block in &tree.blocks {
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        match block {
+            Block::Heading(level, inlines) => {
+                out.push_str(&format!("Heading level {level}: "));
+                out.push_str(&join(inlines));
+            }
+            Block::Paragraph(inlines) => {
+                out.push_str(&join(inlines));
+            }
+            Block::Equation(formula) => out.push_str(&formula.speech),
+            Block::Image(alt) => match alt {
+                Some(alt) => out.push_str(&format!("Image: {alt}")),
+                None => out.push_str("Image"),
+            },
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// Export a document into an XML structure for Braille transcription.
+///
+/// The root `document` element contains `heading`, `paragraph`, `equation`,
+/// and `image` elements in reading order. Formulas are written in Typst's
+/// linear math syntax, with their spoken form in the `speech` attribute.
+#[typst_macros::time(name = "braille")]
+pub fn braille(document: &Document) -> String {
+    let tree = tree::tree(document);
+    let mut xml = XmlWriter::new(Options { indent: Indent::None, ..Options::default() });
+    xml.write_declaration();
+    xml.start_element("document");
+    if let Some(lang) = tree.lang {
+        xml.write_attribute("xml:lang", lang.as_str());
+    }
+    if let Some(title) = &document.title {
+        xml.write_attribute("title", title);
+    }
+
+    for block in &tree.blocks {
+        match block {
+            Block::Heading(level, inlines) => {
+                xml.start_element("heading");
+                xml.write_attribute("level", level);
+                write_inlines(&mut xml, inlines);
+                xml.end_element();
+            }
+            Block::Paragraph(inlines) => {
+                xml.start_element("paragraph");
+                write_inlines(&mut xml, inlines);
+                xml.end_element();
+            }
+            Block::Equation(formula) => write_formula(&mut xml, "equation", formula),
+            Block::Image(alt) => {
+                xml.start_element("image");
+                if let Some(alt) = alt {
+                    xml.write_attribute("alt", alt);
+                }
+                xml.end_element();
+            }
+        }
+    }
+
+    xml.end_document()
+}
+
+/// Exports documents into plain text for speech synthesis, like [`speech`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SpeechExporter;
+
+impl DocumentExporter for SpeechExporter {
+    type Output = String;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        Ok(speech(document))
+    }
+}
+
+/// Exports documents into an XML structure for Braille transcription, like
+/// [`braille`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct BrailleExporter;
+
+impl DocumentExporter for BrailleExporter {
+    type Output = String;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        Ok(braille(document))
+    }
+}
+
+/// Join text and spoken formulas, separating them with spaces.
+fn join(inlines: &[Inline]) -> String {
+    let mut out = String::new();
+    for inline in inlines {
+        let piece = match inline {
+            Inline::Text(text) => text,
+            Inline::Formula(formula) => &formula.speech,
+        };
+        if needs_space(&out, piece) {
+            out.push(' ');
+        }
+        out.push_str(piece);
+    }
+    out.trim().into()
+}
+
+/// Write the contents of a block.
+fn write_inlines(xml: &mut XmlWriter, inlines: &[Inline]) {
+    let mut prev: &str = "";
+    for inline in inlines {
+        let next = match inline {
+            Inline::Text(text) => text.as_str(),
+            Inline::Formula(formula) => formula.linear.as_str(),
+        };
+        if needs_space(prev, next) {
+            xml.write_text(" ");
+        }
+        match inline {
+            Inline::Text(text) => xml.write_text(text),
+            Inline::Formula(formula) => write_formula(xml, "math", formula),
+        }
+        prev = next;
+    }
+}
+
+/// Whether a space must be inserted between two consecutive pieces of text.
+fn needs_space(prev: &str, next: &str) -> bool {
+    !prev.is_empty()
+        && !prev.ends_with(char::is_whitespace)
+        && !next.starts_with(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
+}
+
+/// Write a formula as an element with the given name.
+fn write_formula(xml: &mut XmlWriter, name: &str, formula: &Formula) {
+    xml.start_element(name);
+    xml.write_attribute("speech", &formula.speech);
+    xml.write_text(&formula.linear);
+    xml.end_element();
+}
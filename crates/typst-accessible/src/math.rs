@@ -0,0 +1,663 @@
+//! Linearization of formulas.
+
+use ecow::{eco_format, EcoString};
+use typst::foundations::{
+    Content, IntoValue, SequenceElem, StyleChain, StyledElem, Value,
+};
+use typst::layout::HElem;
+use typst::math::{
+    AccentElem, AlignPointElem, AttachElem, BinomElem, CancelElem, CasesElem, ClassElem,
+    EquationElem, FracElem, Limits, LimitsElem, LrElem, MatElem, MathSize, MidElem,
+    OpElem, OverbraceElem, OverbracketElem, OverlineElem, PrimesElem, RootElem,
+    ScriptsElem, UnderbraceElem, UnderbracketElem, UnderlineElem, VecElem,
+};
+use typst::text::{LinebreakElem, SpaceElem, TextElem};
+
+/// Speak a formula in the style of MathSpeak, e.g. `StartFraction a Over b
+/// EndFraction` for a fraction.
+pub fn speak(content: &Content, styles: StyleChain) -> EcoString {
+    let mut writer = Writer { mode: Mode::Speech, out: EcoString::new() };
+    writer.content(content, styles);
+    writer.out.trim().into()
+}
+
+/// Write a formula as linear text in Typst's math syntax, e.g. `(a)/(b)` for
+/// a fraction.
+pub fn linearize(content: &Content, styles: StyleChain) -> EcoString {
+    let mut writer = Writer { mode: Mode::Linear, out: EcoString::new() };
+    writer.content(content, styles);
+    writer.out.trim().into()
+}
+
+/// Which linearization to produce.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Mode {
+    Speech,
+    Linear,
+}
+
+/// Writes the linearization of a formula.
+struct Writer {
+    /// Which linearization to produce.
+    mode: Mode,
+    /// The text written so far.
+    out: EcoString,
+}
+
+impl Writer {
+    /// Write a piece of content.
+    fn content(&mut self, content: &Content, styles: StyleChain) {
+        if let Some(sequence) = content.to_packed::<SequenceElem>() {
+            for child in &sequence.children {
+                self.content(child, styles);
+            }
+        } else if let Some(styled) = content.to_packed::<StyledElem>() {
+            self.content(&styled.child, styles.chain(&styled.styles));
+        } else if let Some(elem) = content.to_packed::<EquationElem>() {
+            self.content(elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<TextElem>() {
+            self.text(elem.text());
+        } else if content.is::<SpaceElem>() {
+            self.space();
+        } else if content.is::<HElem>() || content.is::<AlignPointElem>() {
+            // Spacing and alignment points are not linearized.
+        } else if content.is::<LinebreakElem>() {
+            self.pick("NewLine", " \\ ");
+        } else if let Some(elem) = content.to_packed::<FracElem>() {
+            match self.mode {
+                Mode::Speech => {
+                    self.word("StartFraction");
+                    self.content(elem.num(), styles);
+                    self.word("Over");
+                    self.content(elem.denom(), styles);
+                    self.word("EndFraction");
+                }
+                Mode::Linear => {
+                    self.group(elem.num(), styles);
+                    self.out.push('/');
+                    self.group(elem.denom(), styles);
+                }
+            }
+        } else if let Some(elem) = content.to_packed::<BinomElem>() {
+            self.pick("StartBinomial", "binom(");
+            self.content(elem.upper(), styles);
+            self.pick("Choose", ", ");
+            self.list(elem.lower(), styles);
+            self.pick("EndBinomial", ")");
+        } else if let Some(elem) = content.to_packed::<AttachElem>() {
+            self.attach(elem, styles);
+        } else if let Some(elem) = content.to_packed::<PrimesElem>() {
+            self.primes(*elem.count());
+        } else if let Some(elem) = content.to_packed::<RootElem>() {
+            match (self.mode, elem.index(styles)) {
+                (Mode::Speech, index) => {
+                    if let Some(index) = index {
+                        self.word("RootIndex");
+                        self.content(&index, styles);
+                    }
+                    self.word("StartRoot");
+                    self.content(elem.radicand(), styles);
+                    self.word("EndRoot");
+                }
+                (Mode::Linear, Some(index)) => {
+                    self.out.push_str("root(");
+                    self.content(&index, styles);
+                    self.out.push_str(", ");
+                    self.content(elem.radicand(), styles);
+                    self.out.push(')');
+                }
+                (Mode::Linear, None) => {
+                    self.out.push_str("sqrt(");
+                    self.content(elem.radicand(), styles);
+                    self.out.push(')');
+                }
+            }
+        } else if let Some(elem) = content.to_packed::<MatElem>() {
+            let rows = elem.rows();
+            let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+            self.matrix("mat", rows.len(), columns, rows, styles);
+        } else if let Some(elem) = content.to_packed::<VecElem>() {
+            let rows: Vec<_> = elem.children().iter().map(|c| vec![c.clone()]).collect();
+            self.matrix("vec", rows.len(), 1, &rows, styles);
+        } else if let Some(elem) = content.to_packed::<CasesElem>() {
+            match self.mode {
+                Mode::Speech => {
+                    self.word("StartLayout Enlarged left-brace");
+                    for (i, child) in elem.children().iter().enumerate() {
+                        self.word(&eco_format!("{} Row", ordinal(i + 1)));
+                        self.content(child, styles);
+                    }
+                    self.word("EndLayout");
+                }
+                Mode::Linear => {
+                    self.out.push_str("cases(");
+                    self.list(elem.children(), styles);
+                    self.out.push(')');
+                }
+            }
+        } else if let Some(elem) = content.to_packed::<AccentElem>() {
+            let accent = match (*elem.accent()).into_value() {
+                Value::Str(s) => s.chars().next(),
+                _ => None,
+            };
+            let (name, func) = accent.map_or(("accent", "accent"), accent_name);
+            self.modified(elem.base(), true, name, func, None, styles);
+        } else if let Some(elem) = content.to_packed::<UnderlineElem>() {
+            self.modified(elem.body(), false, "bar", "underline", None, styles);
+        } else if let Some(elem) = content.to_packed::<OverlineElem>() {
+            self.modified(elem.body(), true, "bar", "overline", None, styles);
+        } else if let Some(elem) = content.to_packed::<UnderbraceElem>() {
+            let annotation = elem.annotation(styles);
+            self.modified(
+                elem.body(),
+                false,
+                "bottom-brace",
+                "underbrace",
+                annotation,
+                styles,
+            );
+        } else if let Some(elem) = content.to_packed::<OverbraceElem>() {
+            let annotation = elem.annotation(styles);
+            self.modified(
+                elem.body(),
+                true,
+                "top-brace",
+                "overbrace",
+                annotation,
+                styles,
+            );
+        } else if let Some(elem) = content.to_packed::<UnderbracketElem>() {
+            let annotation = elem.annotation(styles);
+            self.modified(
+                elem.body(),
+                false,
+                "bottom-bracket",
+                "underbracket",
+                annotation,
+                styles,
+            );
+        } else if let Some(elem) = content.to_packed::<OverbracketElem>() {
+            let annotation = elem.annotation(styles);
+            self.modified(
+                elem.body(),
+                true,
+                "top-bracket",
+                "overbracket",
+                annotation,
+                styles,
+            );
+        } else if let Some(elem) = content.to_packed::<CancelElem>() {
+            self.pick("StartCrossOut", "cancel(");
+            self.content(elem.body(), styles);
+            self.pick("EndCrossOut", ")");
+        } else if let Some(elem) = content.to_packed::<OpElem>() {
+            self.word(&elem.text().plain_text());
+        } else if let Some(elem) = content.to_packed::<LrElem>() {
+            self.content(elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<MidElem>() {
+            self.content(elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<ClassElem>() {
+            self.content(elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<ScriptsElem>() {
+            self.wrapped("scripts", elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<LimitsElem>() {
+            self.wrapped("limits", elem.body(), styles);
+        } else {
+            self.text(&content.plain_text());
+        }
+    }
+
+    /// Write a base with attachments.
+    fn attach(&mut self, elem: &AttachElem, styles: StyleChain) {
+        // Like in layout, top and bottom attachments are scripts unless the
+        // base displays limits or the corner is taken.
+        let limits = limits(elem.base(), styles).active(styles);
+        let [t, b, tl, bl, tr, br] = [
+            elem.t(styles),
+            elem.b(styles),
+            elem.tl(styles),
+            elem.bl(styles),
+            elem.tr(styles),
+            elem.br(styles),
+        ];
+        let (t, tr) = if limits || tr.is_some() { (t, tr) } else { (None, t) };
+        let (b, br) = if limits || br.is_some() { (b, br) } else { (None, b) };
+
+        let script = EquationElem::set_size(MathSize::Script).wrap();
+        let inner = styles.chain(&script);
+
+        if self.mode == Mode::Linear {
+            // The shorthand `base_b^t` only works if each attachment ends up
+            // where the shorthand would put it.
+            let (above, below) = if limits { (&t, &b) } else { (&tr, &br) };
+            let unused = if limits { [&tr, &br] } else { [&t, &b] };
+            let shorthand = [&tl, &bl].into_iter().chain(unused).all(Option::is_none);
+            if !shorthand {
+                self.out.push_str("attach(");
+                self.content(elem.base(), styles);
+                let keys = ["t", "b", "tl", "bl", "tr", "br"];
+                for (key, value) in keys.into_iter().zip([&t, &b, &tl, &bl, &tr, &br]) {
+                    if let Some(value) = value {
+                        self.out.push_str(&eco_format!(", {key}: "));
+                        self.content(value, inner);
+                    }
+                }
+                self.out.push(')');
+                return;
+            }
+
+            self.content(elem.base(), styles);
+            let (primes, above) = match above {
+                Some(above) if above.is::<PrimesElem>() => (Some(above), &None),
+                above => (None, above),
+            };
+            if let Some(primes) = primes {
+                self.content(primes, inner);
+            }
+            for (marker, value) in [('_', below), ('^', above)] {
+                if let Some(value) = value {
+                    self.out.push(marker);
+                    self.group(value, inner);
+                }
+            }
+            return;
+        }
+
+        if tl.is_some() || bl.is_some() {
+            for (word, value) in [("Presubscript", &bl), ("Presuperscript", &tl)] {
+                if let Some(value) = value {
+                    self.word(word);
+                    self.content(value, inner);
+                }
+            }
+            self.word("Base");
+        }
+
+        self.content(elem.base(), styles);
+
+        if b.is_some() || t.is_some() {
+            for (word, value) in [("Underscript", &b), ("Overscript", &t)] {
+                if let Some(value) = value {
+                    self.word(word);
+                    self.content(value, inner);
+                }
+            }
+            self.word("Endscripts");
+        }
+
+        // Primes are spoken directly, e.g. "f prime".
+        let (primes, tr) = match tr {
+            Some(tr) if tr.is::<PrimesElem>() => (Some(tr), None),
+            tr => (None, tr),
+        };
+
+        if let Some(primes) = &primes {
+            self.content(primes, inner);
+        }
+
+        if br.is_some() || tr.is_some() {
+            for (word, value) in [("Subscript", &br), ("Superscript", &tr)] {
+                if let Some(value) = value {
+                    self.word(word);
+                    self.content(value, inner);
+                }
+            }
+            self.word("Baseline");
+        }
+    }
+
+    /// Write a number of primes.
+    fn primes(&mut self, count: usize) {
+        match self.mode {
+            Mode::Speech => match count {
+                1 => self.word("prime"),
+                2 => self.word("double-prime"),
+                3 => self.word("triple-prime"),
+                n => self.word(&eco_format!("{n} primes")),
+            },
+            Mode::Linear => {
+                for _ in 0..count {
+                    self.out.push('\'');
+                }
+            }
+        }
+    }
+
+    /// Write a matrix with the given dimensions.
+    fn matrix(
+        &mut self,
+        func: &str,
+        rows: usize,
+        columns: usize,
+        cells: &[Vec<Content>],
+        styles: StyleChain,
+    ) {
+        match self.mode {
+            Mode::Speech => {
+                self.word(&eco_format!("Start {rows} By {columns} Matrix"));
+                for (i, row) in cells.iter().enumerate() {
+                    self.word(&eco_format!("{} Row", ordinal(i + 1)));
+                    for (j, cell) in row.iter().enumerate() {
+                        if row.len() > 1 {
+                            self.word(&eco_format!("{} Column", ordinal(j + 1)));
+                        }
+                        self.content(cell, styles);
+                    }
+                }
+                self.word("EndMatrix");
+            }
+            Mode::Linear => {
+                self.out.push_str(func);
+                self.out.push('(');
+                for (i, row) in cells.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(if func == "vec" { ", " } else { "; " });
+                    }
+                    self.list(row, styles);
+                }
+                self.out.push(')');
+            }
+        }
+    }
+
+    /// Write content that is modified above or below, like an accent or an
+    /// underbrace.
+    fn modified(
+        &mut self,
+        body: &Content,
+        above: bool,
+        name: &str,
+        func: &str,
+        annotation: Option<Content>,
+        styles: StyleChain,
+    ) {
+        match self.mode {
+            Mode::Speech => {
+                self.word(if above { "ModifyingAbove" } else { "ModifyingBelow" });
+                self.content(body, styles);
+                self.word("With");
+                self.word(name);
+                if let Some(annotation) = annotation {
+                    self.word(if above { "Overscript" } else { "Underscript" });
+                    self.content(&annotation, styles);
+                    self.word("Endscripts");
+                }
+            }
+            Mode::Linear => {
+                self.out.push_str(func);
+                self.out.push('(');
+                self.content(body, styles);
+                if let Some(annotation) = annotation {
+                    self.out.push_str(", ");
+                    self.content(&annotation, styles);
+                }
+                self.out.push(')');
+            }
+        }
+    }
+
+    /// Write content that is wrapped in a function call in linear mode only,
+    /// as it is spoken the same either way.
+    fn wrapped(&mut self, func: &str, body: &Content, styles: StyleChain) {
+        match self.mode {
+            Mode::Speech => self.content(body, styles),
+            Mode::Linear => {
+                self.out.push_str(func);
+                self.out.push('(');
+                self.content(body, styles);
+                self.out.push(')');
+            }
+        }
+    }
+
+    /// Write a comma-separated list of content.
+    fn list(&mut self, children: &[Content], styles: StyleChain) {
+        for (i, child) in children.iter().enumerate() {
+            if i > 0 {
+                self.pick("comma", ", ");
+            }
+            self.content(child, styles);
+        }
+    }
+
+    /// Write content, parenthesized in linear mode unless it is a single
+    /// number or identifier.
+    fn group(&mut self, content: &Content, styles: StyleChain) {
+        let mut inner = Writer { mode: self.mode, out: EcoString::new() };
+        inner.content(content, styles);
+        let text = inner.out.trim();
+        if text.chars().all(char::is_alphanumeric) && !text.is_empty() {
+            self.out.push_str(text);
+        } else {
+            self.out.push('(');
+            self.out.push_str(text);
+            self.out.push(')');
+        }
+    }
+
+    /// Write text, naming its symbols in speech mode.
+    fn text(&mut self, text: &str) {
+        if self.mode == Mode::Linear {
+            self.out.push_str(text);
+            return;
+        }
+
+        let mut run = String::new();
+        for c in text.chars() {
+            if c.is_ascii_alphanumeric() {
+                run.push(c);
+                continue;
+            }
+
+            self.run(&mut run);
+            if let Some(name) = symbol_name(c) {
+                self.word(name);
+            } else if !c.is_whitespace() {
+                self.word(c.encode_utf8(&mut [0; 4]));
+            }
+        }
+        self.run(&mut run);
+    }
+
+    /// Write and clear a run of letters and digits. A single capital letter
+    /// is spoken as "upper".
+    fn run(&mut self, run: &mut String) {
+        if run.is_empty() {
+            return;
+        }
+
+        let mut chars = run.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if c.is_ascii_uppercase() {
+                self.word("upper");
+            }
+        }
+
+        self.word(run);
+        run.clear();
+    }
+
+    /// Write the first string in speech mode and the second one in linear
+    /// mode.
+    fn pick(&mut self, speech: &str, linear: &str) {
+        match self.mode {
+            Mode::Speech => self.word(speech),
+            Mode::Linear => self.out.push_str(linear),
+        }
+    }
+
+    /// Write a word, separated by a space in speech mode.
+    fn word(&mut self, word: &str) {
+        if self.mode == Mode::Speech && !self.out.is_empty() && !self.out.ends_with(' ') {
+            self.out.push(' ');
+        }
+        self.out.push_str(word);
+    }
+
+    /// Write a space from the source in linear mode.
+    fn space(&mut self) {
+        if self.mode == Mode::Linear && !self.out.ends_with(' ') {
+            self.out.push(' ');
+        }
+    }
+}
+
+/// How the base of attachments displays its top and bottom attachments,
+/// mirroring math layout.
+fn limits(base: &Content, styles: StyleChain) -> Limits {
+    if let Some(styled) = base.to_packed::<StyledElem>() {
+        limits(&styled.child, styles.chain(&styled.styles))
+    } else if let Some(elem) = base.to_packed::<LimitsElem>() {
+        if elem.inline(styles) {
+            Limits::Always
+        } else {
+            Limits::Display
+        }
+    } else if let Some(elem) = base.to_packed::<OpElem>() {
+        if elem.limits(styles) {
+            Limits::Display
+        } else {
+            Limits::Never
+        }
+    } else if let Some(elem) = base.to_packed::<ClassElem>() {
+        Limits::for_class(*elem.class())
+    } else if let Some(elem) = base.to_packed::<TextElem>() {
+        let mut chars = elem.text().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Limits::for_char(c),
+            _ => Limits::Never,
+        }
+    } else {
+        Limits::Never
+    }
+}
+
+/// The spoken name and the Typst function of an accent.
+fn accent_name(c: char) -> (&'static str, &'static str) {
+    match c {
+        '\u{0300}' => ("grave", "grave"),
+        '\u{0301}' => ("acute", "acute"),
+        '\u{0302}' => ("caret", "hat"),
+        '\u{0303}' => ("tilde", "tilde"),
+        '\u{0304}' | '\u{0305}' => ("bar", "macron"),
+        '\u{0306}' => ("breve", "breve"),
+        '\u{0307}' => ("dot", "dot"),
+        '\u{0308}' => ("double-dot", "dot.double"),
+        '\u{030A}' => ("ring", "circle"),
+        '\u{030C}' => ("caron", "caron"),
+        '\u{20D6}' => ("left-arrow", "arrow.l"),
+        '\u{20D7}' => ("right-arrow", "arrow"),
+        _ => ("accent", "accent"),
+    }
+}
+
+/// The spoken name of a symbol.
+fn symbol_name(c: char) -> Option<&'static str> {
+    Some(match c {
+        '+' => "plus",
+        '-' | '−' => "minus",
+        '=' => "equals",
+        '<' => "less-than",
+        '>' => "greater-than",
+        '≤' => "less-than-or-equal-to",
+        '≥' => "greater-than-or-equal-to",
+        '≠' => "not-equals",
+        '≈' => "almost-equals",
+        '≡' => "identical-to",
+        '×' => "times",
+        '·' | '⋅' => "dot",
+        '÷' => "divided-by",
+        '±' => "plus-or-minus",
+        '∓' => "minus-or-plus",
+        '∞' => "infinity",
+        '∑' => "sigma-summation",
+        '∏' => "product",
+        '∫' => "integral",
+        '∮' => "contour-integral",
+        '∂' => "partial-differential",
+        '∇' => "nabla",
+        '→' => "right-arrow",
+        '←' => "left-arrow",
+        '↔' => "left-right-arrow",
+        '⇒' => "right-double-arrow",
+        '⇔' => "left-right-double-arrow",
+        '↦' => "maps-to",
+        '∈' => "element-of",
+        '∉' => "not-an-element-of",
+        '⊂' => "subset-of",
+        '⊆' => "subset-of-or-equal-to",
+        '∪' => "union",
+        '∩' => "intersection",
+        '∅' => "empty-set",
+        '∀' => "for-all",
+        '∃' => "there-exists",
+        '¬' => "not",
+        '∧' => "logical-and",
+        '∨' => "logical-or",
+        '(' => "left-parenthesis",
+        ')' => "right-parenthesis",
+        '[' => "left-bracket",
+        ']' => "right-bracket",
+        '{' => "left-brace",
+        '}' => "right-brace",
+        '⟨' => "left-angle-bracket",
+        '⟩' => "right-angle-bracket",
+        '|' => "vertical-bar",
+        '‖' => "double-vertical-bar",
+        ',' => "comma",
+        ';' => "semicolon",
+        ':' => "colon",
+        '!' => "factorial",
+        '/' => "slash",
+        '\'' | '′' => "prime",
+        '…' | '⋯' => "ellipsis",
+        '°' => "degree",
+        'α' => "alpha",
+        'β' => "beta",
+        'γ' => "gamma",
+        'δ' => "delta",
+        'ε' | 'ϵ' => "epsilon",
+        'ζ' => "zeta",
+        'η' => "eta",
+        'θ' | 'ϑ' => "theta",
+        'ι' => "iota",
+        'κ' => "kappa",
+        'λ' => "lamda",
+        'μ' => "mu",
+        'ν' => "nu",
+        'ξ' => "xi",
+        'ο' => "omicron",
+        'π' => "pi",
+        'ρ' => "rho",
+        'σ' => "sigma",
+        'τ' => "tau",
+        'υ' => "upsilon",
+        'φ' | 'ϕ' => "phi",
+        'χ' => "chi",
+        'ψ' => "psi",
+        'ω' => "omega",
+        'Γ' => "upper Gamma",
+        'Δ' => "upper Delta",
+        'Θ' => "upper Theta",
+        'Λ' => "upper Lamda",
+        'Ξ' => "upper Xi",
+        'Π' => "upper Pi",
+        'Σ' => "upper Sigma",
+        'Φ' => "upper Phi",
+        'Ψ' => "upper Psi",
+        'Ω' => "upper Omega",
+        _ => return None,
+    })
+}
+
+/// The English ordinal of a number, e.g. `2nd`.
+fn ordinal(n: usize) -> EcoString {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    eco_format!("{n}{suffix}")
+}
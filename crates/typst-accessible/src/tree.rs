@@ -0,0 +1,167 @@
+//! Flattening of the document's semantic structure into linear blocks.
+
+use ecow::EcoString;
+use typst::foundations::{Packed, ShowSet, StyleChain, Styles};
+use typst::math::EquationElem;
+use typst::model::{Document, StructBlock, StructInline};
+use typst::text::Lang;
+
+use crate::math;
+
+/// The structure of a document in reading order.
+pub struct Tree {
+    /// The top-level blocks.
+    pub blocks: Vec<Block>,
+    /// The language of the document's first text.
+    pub lang: Option<Lang>,
+}
+
+/// A top-level block.
+pub enum Block {
+    /// A heading with its level.
+    Heading(usize, Vec<Inline>),
+    /// A paragraph.
+    Paragraph(Vec<Inline>),
+    /// A formula that is displayed as its own block.
+    Equation(Formula),
+    /// An image with its alternative description.
+    Image(Option<EcoString>),
+}
+
+/// A piece of block content.
+pub enum Inline {
+    /// Plain text.
+    Text(EcoString),
+    /// An inline formula.
+    Formula(Formula),
+}
+
+/// The linearizations of a formula.
+pub struct Formula {
+    /// How the formula is spoken.
+    pub speech: EcoString,
+    /// The formula in Typst's linear math syntax.
+    pub linear: EcoString,
+}
+
+/// Flatten the structure of a document into blocks in reading order.
+///
+/// List items become paragraphs starting with their marker, table cells and
+/// figure captions become paragraphs of their own, and footnotes are read
+/// right after the block that references them.
+pub fn tree(document: &Document) -> Tree {
+    let mut tree = Tree { blocks: vec![], lang: None };
+    blocks(&mut tree, &document.structure.blocks);
+    tree
+}
+
+/// Add structured blocks.
+fn blocks(tree: &mut Tree, blocks: &[StructBlock]) {
+    for block in blocks {
+        match block {
+            StructBlock::Heading(level, inlines) => {
+                let mut footnotes = vec![];
+                let inlines = self::inlines(tree, inlines, &mut footnotes);
+                tree.blocks.push(Block::Heading(level.get(), inlines));
+                self::footnotes(tree, &footnotes);
+            }
+            StructBlock::Paragraph(inlines) => paragraph(tree, inlines),
+            StructBlock::ListItem(marker, children) => {
+                let start = tree.blocks.len();
+                self::blocks(tree, children);
+                let marker = Inline::Text(marker.clone());
+                match tree.blocks.get_mut(start) {
+                    Some(Block::Paragraph(inlines)) => inlines.insert(0, marker),
+                    _ => tree.blocks.insert(start, Block::Paragraph(vec![marker])),
+                }
+            }
+            StructBlock::Table(table) => {
+                for cell in table.rows.iter().flatten() {
+                    self::blocks(tree, &cell.blocks);
+                }
+            }
+            StructBlock::Figure(children, caption) => {
+                self::blocks(tree, children);
+                if let Some(caption) = caption {
+                    paragraph(tree, caption);
+                }
+            }
+            StructBlock::Equation(equation, styles) => {
+                tree.blocks.push(Block::Equation(formula(equation, styles)));
+            }
+            StructBlock::Image(image) => {
+                tree.blocks.push(Block::Image(image.image.alt().map(Into::into)));
+            }
+            StructBlock::Only(_, children) => self::blocks(tree, children),
+        }
+    }
+}
+
+/// Add a paragraph followed by its footnotes.
+fn paragraph(tree: &mut Tree, inlines: &[StructInline]) {
+    let mut footnotes = vec![];
+    let inlines = self::inlines(tree, inlines, &mut footnotes);
+    tree.blocks.push(Block::Paragraph(inlines));
+    self::footnotes(tree, &footnotes);
+}
+
+/// Add the footnotes referenced by the previous block.
+fn footnotes(tree: &mut Tree, footnotes: &[Vec<StructBlock>]) {
+    for footnote in footnotes {
+        let start = tree.blocks.len();
+        blocks(tree, footnote);
+        let label = Inline::Text("Footnote:".into());
+        match tree.blocks.get_mut(start) {
+            Some(Block::Paragraph(inlines)) => inlines.insert(0, label),
+            _ => tree.blocks.insert(start, Block::Paragraph(vec![label])),
+        }
+    }
+}
+
+/// Convert structured inlines, collecting the footnotes they reference.
+fn inlines(
+    tree: &mut Tree,
+    inlines: &[StructInline],
+    footnotes: &mut Vec<Vec<StructBlock>>,
+) -> Vec<Inline> {
+    let mut out = vec![];
+    for inline in inlines {
+        match inline {
+            StructInline::Text(text, style) => {
+                tree.lang.get_or_insert(style.lang);
+                match out.last_mut() {
+                    Some(Inline::Text(prev)) => prev.push_str(text),
+                    _ => out.push(Inline::Text(text.clone())),
+                }
+            }
+            StructInline::Equation(equation, styles) => {
+                out.push(Inline::Formula(formula(equation, styles)));
+            }
+            StructInline::Footnote(blocks) => footnotes.push(blocks.clone()),
+            StructInline::Only(_, children) => {
+                for inline in self::inlines(tree, children, footnotes) {
+                    match (out.last_mut(), inline) {
+                        (Some(Inline::Text(prev)), Inline::Text(text)) => {
+                            prev.push_str(&text)
+                        }
+                        (_, inline) => out.push(inline),
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Linearize a formula in the styles it appears in.
+fn formula(equation: &Packed<EquationElem>, styles: &Styles) -> Formula {
+    // Apply the same styles as the equation's show rule, in particular the
+    // math size, which decides whether attachments are limits.
+    let outer = StyleChain::new(styles);
+    let local = equation.show_set(outer);
+    let styles = outer.chain(&local);
+    Formula {
+        speech: math::speak(equation.body(), styles),
+        linear: math::linearize(equation.body(), styles),
+    }
+}
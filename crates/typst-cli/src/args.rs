@@ -8,6 +8,9 @@ use chrono::{DateTime, Utc};
 use clap::builder::ValueParser;
 use clap::{ArgAction, Args, ColorChoice, Parser, Subcommand, ValueEnum};
 use semver::Version;
+use typst::layout::Paper;
+use typst::text::{Lang, Region};
+use typst::visualize::Color;
 
 /// The character typically used to separate path components
 /// in environment variables.
@@ -109,6 +112,63 @@ pub struct CompileCommand {
     #[arg(long = "ppi", default_value_t = 144.0)]
     pub ppi: f32,
 
+    /// A stable identifier of the document for PDF export
+    ///
+    /// It is hashed into the PDF's document ID and should not change between
+    /// compilations of the same document.
+    #[arg(long = "pdf-ident", value_name = "IDENT")]
+    pub pdf_ident: Option<String>,
+
+    /// Makes PDF output reproducible by not embedding the current time
+    ///
+    /// Combined with `--creation-timestamp`, the given date is embedded
+    /// instead. Compiling the same inputs then yields identical files.
+    #[arg(long = "reproducible")]
+    pub reproducible: bool,
+
+    /// Embeds complete fonts into PDFs instead of subsets
+    ///
+    /// This makes files larger, but allows editing their text downstream.
+    #[arg(long = "pdf-full-fonts")]
+    pub pdf_full_fonts: bool,
+
+    /// Refuses to embed fonts into PDFs whose license forbids it
+    ///
+    /// Fonts whose license forbids subsetting are then embedded in full.
+    #[arg(long = "pdf-respect-font-licenses")]
+    pub pdf_respect_font_licenses: bool,
+
+    /// Output a JSON manifest of all files, fonts, and packages the document
+    /// accessed, with SHA-256 digests of their contents
+    #[arg(long = "manifest", value_name = "PATH")]
+    pub manifest: Option<PathBuf>,
+
+    /// Attaches the resource manifest to the PDF as `manifest.json`
+    #[arg(long = "pdf-embed-manifest")]
+    pub pdf_embed_manifest: bool,
+
+    /// Output a JSON file that maps the IDs of all translatable segments to
+    /// their texts
+    #[arg(long = "extract-translations", value_name = "PATH")]
+    pub extract_translations: Option<PathBuf>,
+
+    /// Output a JSON report with the estimated CMYK ink coverage of each page
+    #[arg(long = "ink-report", value_name = "PATH")]
+    pub ink_report: Option<PathBuf>,
+
+    /// Converts all colors to luminance-preserving shades of gray
+    #[arg(long = "grayscale", conflicts_with = "palette")]
+    pub grayscale: bool,
+
+    /// Replaces all colors with the closest of the given hex colors
+    #[arg(
+        long = "palette",
+        value_name = "COLORS",
+        value_delimiter = ',',
+        value_parser = parse_color
+    )]
+    pub palette: Option<Vec<Color>>,
+
     /// Produces performance timings of the compilation process (experimental)
     ///
     /// The resulting JSON file can be loaded into a tracing tool such as
@@ -116,6 +176,14 @@ pub struct CompileCommand {
     /// apart from file names and line numbers.
     #[arg(long = "timings", value_name = "OUTPUT_JSON")]
     pub timings: Option<Option<PathBuf>>,
+
+    /// The format of the recorded timings
+    ///
+    /// A `chrome` trace contains every recorded event, a `summary` lists the
+    /// time spent per element, show rule, and layout step, and `folded` stacks
+    /// can be turned into a flamegraph.
+    #[arg(long = "timings-format", default_value_t = TimingsFormat::Chrome)]
+    pub timings_format: TimingsFormat,
 }
 
 /// Initializes a new project from a template
@@ -145,7 +213,13 @@ pub struct QueryCommand {
     pub common: SharedArgs,
 
     /// Defines which elements to retrieve
-    pub selector: String,
+    #[clap(required_unless_present = "front_matter")]
+    pub selector: Option<String>,
+
+    /// Retrieves the document's front matter instead of elements: its title,
+    /// authors, keywords, date, and the values set with `document(extra: ..)`
+    #[clap(long = "front-matter", conflicts_with_all = ["selector", "field", "one"])]
+    pub front_matter: bool,
 
     /// Extracts just one field from all retrieved elements
     #[clap(long = "field")]
@@ -164,6 +238,26 @@ pub struct QueryCommand {
     pub pretty: bool,
 }
 
+/// The format of recorded timings.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum TimingsFormat {
+    /// The Chrome trace event format.
+    Chrome,
+    /// A JSON summary of the time spent per scope.
+    Summary,
+    /// Folded stacks for flamegraph tools.
+    Folded,
+}
+
+impl Display for TimingsFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
 // Output file format for query command
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
 pub enum SerializationFormat {
@@ -195,6 +289,10 @@ pub struct SharedArgs {
     #[clap(flatten)]
     pub font_args: FontArgs,
 
+    /// Regional defaults of the environment
+    #[clap(flatten)]
+    pub locale_args: LocaleArgs,
+
     /// The document's creation date formatted as a UNIX timestamp.
     ///
     /// For more information, see <https://reproducible-builds.org/specs/source-date-epoch/>.
@@ -351,6 +449,11 @@ impl FromStr for PageRangeArgument {
     }
 }
 
+/// Parses a hex color like `#a0b1c2`.
+fn parse_color(value: &str) -> Result<Color, &'static str> {
+    Color::from_str(value)
+}
+
 fn parse_page_number(value: &str) -> Result<NonZeroUsize, &'static str> {
     if value == "0" {
         Err("page numbers start at one")
@@ -389,6 +492,30 @@ pub struct FontArgs {
     pub ignore_system_fonts: bool,
 }
 
+/// Arguments to configure the regional defaults visible through `sys.locale`
+#[derive(Debug, Clone, Parser)]
+pub struct LocaleArgs {
+    /// The default text language (ISO 639-1/2/3 code)
+    #[clap(long = "lang", env = "TYPST_LANG", value_name = "LANG")]
+    pub lang: Option<Lang>,
+
+    /// The default text region (ISO 3166-1 alpha-2 code)
+    #[clap(long = "region", env = "TYPST_REGION", value_name = "REGION")]
+    pub region: Option<Region>,
+
+    /// The default paper size (e.g. `us-letter`)
+    #[clap(long = "paper", env = "TYPST_PAPER", value_name = "PAPER")]
+    pub paper: Option<Paper>,
+
+    /// The customary decimal separator
+    #[clap(
+        long = "decimal-separator",
+        env = "TYPST_DECIMAL_SEPARATOR",
+        value_name = "CHAR"
+    )]
+    pub decimal_separator: Option<char>,
+}
+
 /// Which format to use for diagnostics.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
 pub enum DiagnosticFormat {
@@ -437,6 +564,9 @@ pub enum OutputFormat {
     Pdf,
     Png,
     Svg,
+    Docx,
+    Speech,
+    Braille,
 }
 
 impl Display for OutputFormat {
@@ -7,15 +7,17 @@ use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::term;
 use ecow::{eco_format, eco_vec, EcoString, EcoVec};
 use parking_lot::RwLock;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use typst::diag::{bail, FileError, Severity, SourceDiagnostic, StrResult};
+use typst::engine::Manifest;
 use typst::eval::Tracer;
-use typst::foundations::{Datetime, Smart};
+use typst::foundations::{Bytes, Datetime, Smart};
 use typst::layout::{Frame, PageRanges};
-use typst::model::Document;
+use typst::model::{Document, TranslatableSegment};
 use typst::syntax::{FileId, Source, Span};
-use typst::visualize::Color;
+use typst::visualize::{Color, Recolor};
 use typst::{World, WorldExt};
+use typst_pdf::{FontPolicy, PdfAttachment, PdfOptions};
 
 use crate::args::{
     CompileCommand, DiagnosticFormat, Input, Output, OutputFormat, PageRangeArgument,
@@ -40,6 +42,9 @@ impl CompileCommand {
                     OutputFormat::Pdf => "pdf",
                     OutputFormat::Png => "png",
                     OutputFormat::Svg => "svg",
+                    OutputFormat::Docx => "docx",
+                    OutputFormat::Speech => "txt",
+                    OutputFormat::Braille => "xml",
                 },
             ))
         })
@@ -56,6 +61,7 @@ impl CompileCommand {
                 Some(ext) if ext.eq_ignore_ascii_case("pdf") => OutputFormat::Pdf,
                 Some(ext) if ext.eq_ignore_ascii_case("png") => OutputFormat::Png,
                 Some(ext) if ext.eq_ignore_ascii_case("svg") => OutputFormat::Svg,
+                Some(ext) if ext.eq_ignore_ascii_case("docx") => OutputFormat::Docx,
                 _ => bail!("could not infer output format for path {}.\nconsider providing the format manually with `--format/-f`", output.display()),
             }
         } else {
@@ -63,6 +69,21 @@ impl CompileCommand {
         })
     }
 
+    /// The color conversion to apply before exporting, if any.
+    pub fn recolor(&self) -> Option<Recolor> {
+        if self.grayscale {
+            Some(Recolor::Grayscale)
+        } else {
+            self.palette.clone().map(Recolor::Palette)
+        }
+    }
+
+    /// Whether a resource manifest should be built, either to be written out
+    /// or to be embedded in the PDF.
+    pub fn wants_manifest(&self) -> bool {
+        self.manifest.is_some() || self.pdf_embed_manifest
+    }
+
     /// The ranges of the pages to be exported as specified by the user.
     ///
     /// This returns `None` if all pages should be exported.
@@ -113,13 +134,21 @@ pub fn compile_once(
     }
 
     let mut tracer = Tracer::new();
+    if command.wants_manifest() {
+        tracer = tracer.with_digests();
+    }
     let result = typst::compile(world, &mut tracer);
+    let manifest = Manifest::new(&tracer);
     let warnings = tracer.warnings();
 
     match result {
         // Export the PDF / PNG.
         Ok(document) => {
-            export(world, &document, command, watching)?;
+            let document = match command.recolor() {
+                Some(recolor) => recolor.document(&document),
+                None => document,
+            };
+            export(world, &document, manifest.as_ref(), command, watching)?;
             let duration = start.elapsed();
 
             if watching {
@@ -134,6 +163,9 @@ pub fn compile_once(
                 .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
 
             write_make_deps(world, command)?;
+            write_manifest(manifest.as_ref(), command)?;
+            write_ink_report(&document, command)?;
+            write_translations(&document, command)?;
 
             if let Some(open) = command.open.take() {
                 if let Output::Path(file) = command.output() {
@@ -167,6 +199,7 @@ pub fn compile_once(
 fn export(
     world: &mut SystemWorld,
     document: &Document,
+    manifest: Option<&Manifest>,
     command: &CompileCommand,
     watching: bool,
 ) -> StrResult<()> {
@@ -177,17 +210,63 @@ fn export(
         OutputFormat::Svg => {
             export_image(world, document, command, watching, ImageExportFormat::Svg)
         }
-        OutputFormat::Pdf => export_pdf(document, command),
+        OutputFormat::Pdf => export_pdf(document, manifest, command),
+        OutputFormat::Docx => export_docx(document, command),
+        OutputFormat::Speech => export_text(typst_accessible::speech(document), command),
+        OutputFormat::Braille => {
+            export_text(typst_accessible::braille(document), command)
+        }
     }
 }
 
 /// Export to a PDF.
-fn export_pdf(document: &Document, command: &CompileCommand) -> StrResult<()> {
-    let timestamp = convert_datetime(
-        command.common.creation_timestamp.unwrap_or_else(chrono::Utc::now),
-    );
-    let exported_page_ranges = command.exported_page_ranges();
-    let buffer = typst_pdf::pdf(document, Smart::Auto, timestamp, exported_page_ranges);
+fn export_pdf(
+    document: &Document,
+    manifest: Option<&Manifest>,
+    command: &CompileCommand,
+) -> StrResult<()> {
+    let timestamp = match command.common.creation_timestamp {
+        Some(timestamp) => convert_datetime(timestamp),
+        None if command.reproducible => None,
+        None => convert_datetime(chrono::Utc::now()),
+    };
+    let ident = match &command.pdf_ident {
+        Some(ident) => Smart::Custom(ident.as_str()),
+        None => Smart::Auto,
+    };
+    let font_policy = FontPolicy {
+        full: command.pdf_full_fonts,
+        respect_licenses: command.pdf_respect_font_licenses,
+    };
+    let mut attachments = vec![];
+    if let Some(manifest) = manifest.filter(|_| command.pdf_embed_manifest) {
+        attachments.push(PdfAttachment {
+            name: "manifest.json".into(),
+            mime_type: Some("application/json".into()),
+            description: Some("Resources accessed during compilation".into()),
+            data: Bytes::from(manifest.to_json().into_bytes()),
+        });
+    }
+    let options = PdfOptions {
+        ident,
+        timestamp,
+        page_ranges: command.exported_page_ranges(),
+        font_policy,
+        attachments,
+    };
+    let buffer = typst_pdf::pdf(document, &options).map_err(|errors| {
+        let messages: Vec<_> = errors
+            .iter()
+            .map(|error| {
+                let mut message = error.message.clone();
+                for hint in &error.hints {
+                    message.push_str(&eco_format!(" (hint: {hint})"));
+                }
+                message
+            })
+            .collect();
+        eco_format!("failed to export PDF ({})", messages.join("; "))
+    })?;
     command
         .output()
         .write(&buffer)
@@ -195,6 +274,25 @@ fn export_pdf(document: &Document, command: &CompileCommand) -> StrResult<()> {
     Ok(())
 }
 
+/// Export to a DOCX file.
+fn export_docx(document: &Document, command: &CompileCommand) -> StrResult<()> {
+    let buffer = typst_docx::docx(document);
+    command
+        .output()
+        .write(&buffer)
+        .map_err(|err| eco_format!("failed to write DOCX file ({err})"))?;
+    Ok(())
+}
+
+/// Export to a text file for speech synthesis or Braille transcription.
+fn export_text(text: String, command: &CompileCommand) -> StrResult<()> {
+    command
+        .output()
+        .write(text.as_bytes())
+        .map_err(|err| eco_format!("failed to write text file ({err})"))?;
+    Ok(())
+}
+
 /// Convert [`chrono::DateTime`] to [`Datetime`]
 fn convert_datetime(date_time: chrono::DateTime<chrono::Utc>) -> Option<Datetime> {
     Datetime::from_ymd_hms(
@@ -394,6 +492,60 @@ impl ExportCache {
     }
 }
 
+/// Writes the resource manifest, if requested.
+fn write_manifest(
+    manifest: Option<&Manifest>,
+    command: &CompileCommand,
+) -> StrResult<()> {
+    let (Some(path), Some(manifest)) = (&command.manifest, manifest) else {
+        return Ok(());
+    };
+    fs::write(path, manifest.to_json())
+        .map_err(|err| eco_format!("failed to write manifest ({err})"))
+}
+
+/// Writes the estimated ink coverage of each page, if requested.
+fn write_ink_report(document: &Document, command: &CompileCommand) -> StrResult<()> {
+    let Some(path) = &command.ink_report else { return Ok(()) };
+    let percent = |fraction: f64| (fraction * 1000.0).round() / 10.0;
+    let pages: Vec<_> = document
+        .pages
+        .par_iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let coverage = typst_render::ink_coverage(&page.frame, 0.5);
+            serde_json::json!({
+                "page": i + 1,
+                "cyan": percent(coverage.cyan),
+                "magenta": percent(coverage.magenta),
+                "yellow": percent(coverage.yellow),
+                "black": percent(coverage.black),
+                "total": percent(coverage.total()),
+                "max-total": percent(coverage.max_total),
+            })
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&pages).unwrap();
+    fs::write(path, json).map_err(|err| eco_format!("failed to write ink report ({err})"))
+}
+
+/// Writes the translatable segments of the document, if requested.
+fn write_translations(document: &Document, command: &CompileCommand) -> StrResult<()> {
+    let Some(path) = &command.extract_translations else { return Ok(()) };
+    let segments = typst::model::translatable_segments(&document.introspector);
+    fs::write(path, translations_json(segments))
+        .map_err(|err| eco_format!("failed to write translations ({err})"))
+}
+
+/// Serializes translatable segments into a JSON object from IDs to texts.
+fn translations_json(segments: Vec<TranslatableSegment>) -> String {
+    let segments: serde_json::Map<_, _> = segments
+        .into_iter()
+        .map(|segment| (segment.id.to_string(), segment.text.as_str().into()))
+        .collect();
+    serde_json::to_string_pretty(&segments).unwrap()
+}
+
 /// Writes a Makefile rule describing the relationship between the output and
 /// its dependencies to the path specified by the --make-deps argument, if it
 /// was provided.
@@ -642,3 +794,42 @@ impl<'a> codespan_reporting::files::Files<'a> for SystemWorld {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::args::{CliArguments, Command};
+
+    #[test]
+    fn test_extract_translations() {
+        let args = CliArguments::parse_from([
+            "typst",
+            "compile",
+            "main.typ",
+            "--extract-translations",
+            "segments.json",
+        ]);
+        let Command::Compile(command) = args.command else { panic!("expected compile") };
+        assert_eq!(
+            command.extract_translations.as_deref(),
+            Some(Path::new("segments.json"))
+        );
+
+        let segments = vec![
+            TranslatableSegment { id: "title".into(), text: "Introduction".into() },
+            TranslatableSegment {
+                id: "greeting".into(),
+                text: "Hello \"world\"".into(),
+            },
+        ];
+        assert_eq!(
+            translations_json(segments),
+            r#"{
+  "greeting": "Hello \"world\"",
+  "title": "Introduction"
+}"#
+        );
+    }
+}
@@ -28,8 +28,12 @@ pub fn query(command: &QueryCommand) -> HintedStrResult<()> {
     match result {
         // Retrieve and print query results.
         Ok(document) => {
-            let data = retrieve(&world, command, &document)?;
-            let serialized = format(data, command)?;
+            let serialized = if command.front_matter {
+                serialize(&document.front_matter(), command.format, command.pretty)?
+            } else {
+                let data = retrieve(&world, command, &document)?;
+                format(data, command)?
+            };
             println!("{serialized}");
             print_diagnostics(&world, &[], &warnings, command.common.diagnostic_format)
                 .map_err(|err| eco_format!("failed to print diagnostics ({err})"))?;
@@ -59,7 +63,7 @@ fn retrieve(
 ) -> HintedStrResult<Vec<Content>> {
     let selector = eval_string(
         world.track(),
-        &command.selector,
+        command.selector.as_deref().unwrap_or_default(),
         Span::detached(),
         EvalMode::Code,
         Scope::default(),
@@ -124,3 +128,40 @@ fn serialize(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use typst::foundations::{dict, Smart};
+
+    use super::*;
+    use crate::args::{CliArguments, Command};
+
+    #[test]
+    fn test_query_front_matter() {
+        let args = CliArguments::parse_from([
+            "typst",
+            "query",
+            "main.typ",
+            "--front-matter",
+            "--format",
+            "json",
+        ]);
+        let Command::Query(command) = args.command else { panic!("expected query") };
+        assert!(command.front_matter);
+
+        let document = Document {
+            title: Some("Hello".into()),
+            author: vec!["Jane".into()],
+            date: Smart::Custom(None),
+            extra: dict! { "title" => "Slug", "draft" => false },
+            ..Document::default()
+        };
+        let serialized =
+            serialize(&document.front_matter(), command.format, command.pretty).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"title":"Hello","author":["Jane"],"keywords":[],"date":null,"extra":{"title":"Slug","draft":false}}"#
+        );
+    }
+}
@@ -6,13 +6,15 @@ use typst::diag::{bail, StrResult};
 use typst::syntax::Span;
 use typst::World;
 
-use crate::args::{CliArguments, Command};
+use crate::args::{CliArguments, Command, TimingsFormat};
 use crate::world::SystemWorld;
 
 /// Allows to record timings of function executions.
 pub struct Timer {
     /// Where to save the recorded timings of each compilation step.
     path: Option<PathBuf>,
+    /// The format in which to save the timings.
+    format: TimingsFormat,
     /// The current watch iteration.
     index: usize,
 }
@@ -21,10 +23,11 @@ impl Timer {
     /// Initializes the timing system and returns a timer that can be used to
     /// record timings for a specific function invocation.
     pub fn new(args: &CliArguments) -> Timer {
-        let record = match &args.command {
-            Command::Compile(command) => command.timings.clone(),
-            Command::Watch(command) => command.timings.clone(),
-            _ => None,
+        let (record, format) = match &args.command {
+            Command::Compile(command) | Command::Watch(command) => {
+                (command.timings.clone(), command.timings_format)
+            }
+            _ => (None, TimingsFormat::Chrome),
         };
 
         // Enable event collection.
@@ -35,7 +38,7 @@ impl Timer {
         let path =
             record.map(|path| path.unwrap_or_else(|| PathBuf::from("record-{n}.json")));
 
-        Timer { path, index: 0 }
+        Timer { path, format, index: 0 }
     }
 
     /// Records all timings in `f` and writes them to disk.
@@ -71,9 +74,14 @@ impl Timer {
             File::create(path).map_err(|e| format!("failed to create file: {e}"))?;
         let writer = BufWriter::with_capacity(1 << 20, file);
 
-        typst_timing::export_json(writer, |span| {
+        let source = |span| {
             resolve_span(world, span).unwrap_or_else(|| ("unknown".to_string(), 0))
-        })?;
+        };
+        match self.format {
+            TimingsFormat::Chrome => typst_timing::export_json(writer, source)?,
+            TimingsFormat::Summary => typst_timing::export_summary(writer, source)?,
+            TimingsFormat::Folded => typst_timing::export_folded(writer, source)?,
+        }
 
         Ok(output)
     }
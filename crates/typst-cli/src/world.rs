@@ -9,6 +9,7 @@ use ecow::{eco_format, EcoString};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use typst::diag::{FileError, FileResult};
+use typst::foundations::sys::Locale;
 use typst::foundations::{Bytes, Datetime, Dict, IntoValue};
 use typst::syntax::{FileId, Source, VirtualPath};
 use typst::text::{Font, FontBook};
@@ -102,7 +103,14 @@ impl SystemWorld {
                 .map(|(k, v)| (k.as_str().into(), v.as_str().into_value()))
                 .collect();
 
-            Library::builder().with_inputs(inputs).build()
+            let locale = Locale {
+                lang: command.locale_args.lang,
+                region: command.locale_args.region,
+                paper: command.locale_args.paper,
+                decimal_separator: command.locale_args.decimal_separator,
+            };
+
+            Library::builder().with_inputs(inputs).with_locale(locale).build()
         };
 
         let mut searcher = FontSearcher::new();
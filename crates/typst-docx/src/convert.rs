@@ -0,0 +1,310 @@
+//! Conversion of the document's semantic structure into DOCX blocks.
+
+use ecow::{eco_format, EcoString};
+use typst::layout::{Abs, Frame, FrameItem, Point, Sides, Size};
+use typst::model::{
+    Document, StructBlock, StructImage, StructInline, StructStyle, StructTable,
+};
+use typst::syntax::Span;
+use typst::text::TextElem;
+use typst::visualize::{Color, Image, ImageFormat, Paint, RasterFormat};
+
+/// The structure of a document, as far as DOCX can represent it.
+pub struct Body {
+    /// The top-level blocks, in reading order.
+    pub blocks: Vec<Block>,
+    /// The plain text of each footnote.
+    pub footnotes: Vec<EcoString>,
+    /// The images referenced by the blocks.
+    pub media: Vec<Media>,
+    /// The distinct markers of list items. Each one becomes a Word numbering
+    /// definition.
+    pub markers: Vec<EcoString>,
+    /// The size of the first page.
+    pub page: Size,
+    /// The margins of the first page.
+    pub margin: Sides<Abs>,
+}
+
+/// A top-level block.
+pub enum Block {
+    Paragraph(Paragraph),
+    Table(Table),
+}
+
+/// A paragraph with an optional Word style.
+#[derive(Default)]
+pub struct Paragraph {
+    /// The name of the paragraph style, e.g. `Heading1`.
+    pub style: Option<EcoString>,
+    /// How deeply the paragraph is nested in list items, zero outside of
+    /// lists.
+    pub depth: usize,
+    /// The index of the marker the paragraph starts with if it is the first
+    /// paragraph of a list item.
+    pub marker: Option<usize>,
+    /// The paragraph's contents.
+    pub inlines: Vec<Inline>,
+}
+
+/// A piece of paragraph content.
+pub enum Inline {
+    /// A run of uniformly styled text.
+    Text(EcoString, RunProps),
+    /// A reference to the footnote with the given index.
+    Footnote(usize),
+    /// The media with the given index, displayed at the given size.
+    Image(usize, Size, Option<EcoString>),
+}
+
+/// The character formatting of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunProps {
+    /// The font family.
+    pub family: EcoString,
+    /// The font size.
+    pub size: Abs,
+    /// Whether the run is bold.
+    pub bold: bool,
+    /// Whether the run is italic.
+    pub italic: bool,
+    /// The text color as `RRGGBB`, if it isn't black.
+    pub color: Option<EcoString>,
+}
+
+/// A table whose cells contain blocks.
+pub struct Table {
+    /// The number of columns.
+    pub columns: usize,
+    /// The cells in each row.
+    pub rows: Vec<Vec<Cell>>,
+}
+
+/// A table cell.
+pub struct Cell {
+    /// The cell's contents.
+    pub blocks: Vec<Block>,
+    /// The number of columns the cell spans.
+    pub colspan: usize,
+}
+
+/// An image file that is embedded into the package.
+pub struct Media {
+    /// The encoded image.
+    pub data: Vec<u8>,
+    /// The file extension matching the encoding.
+    pub extension: &'static str,
+}
+
+/// Convert the structure of a document into DOCX blocks.
+pub fn convert(document: &Document) -> Body {
+    let mut body = Body {
+        blocks: vec![],
+        footnotes: vec![],
+        media: vec![],
+        markers: vec![],
+        page: document
+            .pages
+            .first()
+            .map(|page| page.frame.size())
+            .unwrap_or_default(),
+        margin: document.structure.margin,
+    };
+
+    blocks(&mut body, &document.structure.blocks, None);
+    body
+}
+
+/// Add blocks, giving their paragraphs the style if they don't have a more
+/// specific one.
+fn blocks(body: &mut Body, blocks: &[StructBlock], style: Option<&str>) {
+    for block in blocks {
+        match block {
+            StructBlock::Heading(level, inlines) => {
+                let style = eco_format!("Heading{}", level.get().min(9));
+                paragraph(body, inlines, Some(style));
+            }
+            StructBlock::Paragraph(inlines) => {
+                paragraph(body, inlines, style.map(Into::into));
+            }
+            StructBlock::ListItem(marker, children) => {
+                let start = body.blocks.len();
+                self::blocks(body, children, Some("ListParagraph"));
+
+                // Nested items were converted first, so each paragraph ends up
+                // one level deeper for every item that contains it.
+                for block in &mut body.blocks[start..] {
+                    if let Block::Paragraph(par) = block {
+                        par.depth += 1;
+                    }
+                }
+
+                let index = match body.markers.iter().position(|m| m == marker) {
+                    Some(index) => index,
+                    None => {
+                        body.markers.push(marker.clone());
+                        body.markers.len() - 1
+                    }
+                };
+
+                // The marker goes onto the item's first paragraph, unless the
+                // item starts with something else, like a nested list.
+                match body.blocks.get_mut(start) {
+                    Some(Block::Paragraph(par)) if par.marker.is_none() => {
+                        par.marker = Some(index);
+                    }
+                    _ => body.blocks.insert(
+                        start,
+                        Block::Paragraph(Paragraph {
+                            style: Some("ListParagraph".into()),
+                            depth: 1,
+                            marker: Some(index),
+                            inlines: vec![],
+                        }),
+                    ),
+                }
+            }
+            StructBlock::Table(table) => {
+                let table = self::table(body, table);
+                body.blocks.push(Block::Table(table));
+            }
+            StructBlock::Figure(children, caption) => {
+                self::blocks(body, children, style);
+                if let Some(caption) = caption {
+                    paragraph(body, caption, Some("Caption".into()));
+                }
+            }
+            StructBlock::Equation(..) => {
+                let text = block.plain_text();
+                let par = Paragraph {
+                    style: style.map(Into::into),
+                    inlines: vec![Inline::Text(text, RunProps::default())],
+                    ..Paragraph::default()
+                };
+                body.blocks.push(Block::Paragraph(par));
+            }
+            StructBlock::Image(image) => self::image(body, image),
+            StructBlock::Only(_, children) => self::blocks(body, children, style),
+        }
+    }
+}
+
+/// Add a paragraph.
+fn paragraph(body: &mut Body, inlines: &[StructInline], style: Option<EcoString>) {
+    let mut par = Paragraph { style, ..Paragraph::default() };
+    self::inlines(body, &mut par, inlines);
+    body.blocks.push(Block::Paragraph(par));
+}
+
+/// Add inline content to a paragraph.
+fn inlines(body: &mut Body, par: &mut Paragraph, inlines: &[StructInline]) {
+    for inline in inlines {
+        match inline {
+            StructInline::Text(text, style) => {
+                push_text(par, text.clone(), RunProps::new(style));
+            }
+            StructInline::Equation(..) => {
+                push_text(par, inline.plain_text(), RunProps::default());
+            }
+            StructInline::Footnote(blocks) => {
+                let texts: Vec<_> = blocks.iter().map(StructBlock::plain_text).collect();
+                body.footnotes.push(texts.join(" ").into());
+                par.inlines.push(Inline::Footnote(body.footnotes.len() - 1));
+            }
+            StructInline::Only(_, children) => self::inlines(body, par, children),
+        }
+    }
+}
+
+/// Add a run of text, merging it with the previous run if their formatting
+/// matches.
+fn push_text(par: &mut Paragraph, text: EcoString, props: RunProps) {
+    if let Some(Inline::Text(prev, prev_props)) = par.inlines.last_mut() {
+        if *prev_props == props {
+            prev.push_str(&text);
+            return;
+        }
+    }
+
+    par.inlines.push(Inline::Text(text, props));
+}
+
+/// Convert a table, converting the contents of its cells like top-level
+/// blocks.
+fn table(body: &mut Body, table: &StructTable) -> Table {
+    let mut rows = vec![];
+    for row in &table.rows {
+        let mut cells = vec![];
+        for cell in row {
+            let outer = std::mem::take(&mut body.blocks);
+            blocks(body, &cell.blocks, None);
+            let blocks = std::mem::replace(&mut body.blocks, outer);
+            cells.push(Cell { blocks, colspan: cell.colspan });
+        }
+        rows.push(cells);
+    }
+
+    Table { columns: table.columns, rows }
+}
+
+/// Add an image as its own paragraph.
+fn image(body: &mut Body, image: &StructImage) {
+    let Some(media) = encode_image(&image.image, image.size) else { return };
+    body.media.push(media);
+    let alt = image.image.alt().map(Into::into);
+    let inline = Inline::Image(body.media.len() - 1, image.size, alt);
+    let par = Paragraph { inlines: vec![inline], ..Paragraph::default() };
+    body.blocks.push(Block::Paragraph(par));
+}
+
+impl RunProps {
+    /// The formatting of a run of text.
+    fn new(style: &StructStyle) -> Self {
+        let color = match &style.fill {
+            Some(Paint::Solid(color)) if *color != Color::BLACK => {
+                let [r, g, b, _] = color.to_rgb().to_vec4_u8();
+                Some(eco_format!("{r:02X}{g:02X}{b:02X}"))
+            }
+            _ => None,
+        };
+
+        Self {
+            family: style.family.clone(),
+            size: style.size,
+            bold: style.bold,
+            italic: style.italic,
+            color,
+        }
+    }
+}
+
+impl Default for RunProps {
+    fn default() -> Self {
+        Self {
+            family: EcoString::new(),
+            size: TextElem::size_in(Default::default()),
+            bold: false,
+            italic: false,
+            color: None,
+        }
+    }
+}
+
+/// Encode an image in a format Word understands. Vector graphics are
+/// rasterized.
+fn encode_image(image: &Image, size: Size) -> Option<Media> {
+    if let ImageFormat::Raster(format) = image.format() {
+        let extension = match format {
+            RasterFormat::Png => "png",
+            RasterFormat::Jpg => "jpeg",
+            RasterFormat::Gif => "gif",
+        };
+        return Some(Media { data: image.data().to_vec(), extension });
+    }
+
+    let mut frame = Frame::soft(size);
+    frame.push(Point::zero(), FrameItem::Image(image.clone(), size, Span::detached()));
+    let pixmap = typst_render::render(&frame, 4.0, Color::WHITE.with_alpha(0.0));
+    let data = pixmap.encode_png().ok()?;
+    Some(Media { data, extension: "png" })
+}
@@ -0,0 +1,69 @@
+//! Exporting of Typst documents into DOCX files.
+//!
+//! Word documents are flowing text rather than fixed pages, so the exporter
+//! works with the document's semantic [structure](typst::model::Structure)
+//! instead of its frames: Headings are mapped to Word's heading styles, list
+//! items become numbered paragraphs that display their marker, and footnotes
+//! become Word footnotes. Tables keep the formatted contents of their cells. Images
+//! are embedded, with vector graphics being rasterized.
+
+mod convert;
+mod write;
+
+use std::io::{Cursor, Write};
+
+use typst::diag::SourceResult;
+use typst::layout::ExportTarget;
+use typst::model::{Document, DocumentExporter};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Export a document into a DOCX file.
+///
+/// Returns the raw bytes making up the DOCX file.
+#[typst_macros::time(name = "docx")]
+pub fn docx(document: &Document) -> Vec<u8> {
+    let document = &document.for_target(ExportTarget::Docx);
+    let body = convert::convert(document);
+
+    let mut parts = vec![
+        ("[Content_Types].xml".to_string(), write::content_types(&body).into_bytes()),
+        ("_rels/.rels".into(), write::package_rels().into_bytes()),
+        ("docProps/core.xml".into(), write::core_props(document).into_bytes()),
+        ("word/_rels/document.xml.rels".into(), write::document_rels(&body).into_bytes()),
+        ("word/document.xml".into(), write::document(&body).into_bytes()),
+        ("word/styles.xml".into(), write::styles().into_bytes()),
+        ("word/footnotes.xml".into(), write::footnotes(&body).into_bytes()),
+        ("word/numbering.xml".into(), write::numbering(&body).into_bytes()),
+    ];
+
+    for (i, media) in body.media.into_iter().enumerate() {
+        let path = format!("word/{}", write::media_path(i, media.extension));
+        parts.push((path, media.data));
+    }
+
+    package(parts).expect("writing to memory should not fail")
+}
+
+/// Exports documents into DOCX files, like [`docx`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DocxExporter;
+
+impl DocumentExporter for DocxExporter {
+    type Output = Vec<u8>;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        Ok(docx(document))
+    }
+}
+
+/// Zip the parts into a package.
+fn package(parts: Vec<(String, Vec<u8>)>) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (path, data) in parts {
+        zip.start_file(path, options)?;
+        zip.write_all(&data)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
@@ -0,0 +1,720 @@
+//! Writing of the XML parts of a DOCX package.
+
+use ecow::EcoString;
+use typst::foundations::Smart;
+use typst::layout::{Abs, Size};
+use typst::model::Document;
+use xmlwriter::{Indent, Options, XmlWriter};
+
+use crate::convert::{Block, Body, Inline, Paragraph, RunProps, Table};
+
+const NS_W: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const NS_R: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const NS_WP: &str =
+    "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing";
+const NS_A: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+const NS_PIC: &str = "http://schemas.openxmlformats.org/drawingml/2006/picture";
+const NS_RELS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+const REL_IMAGE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+
+/// Write `[Content_Types].xml`.
+pub fn content_types(body: &Body) -> String {
+    let mut xml = writer();
+    xml.start_element("Types");
+    xml.write_attribute(
+        "xmlns",
+        "http://schemas.openxmlformats.org/package/2006/content-types",
+    );
+
+    let mut defaults = vec![
+        ("rels", "application/vnd.openxmlformats-package.relationships+xml"),
+        ("xml", "application/xml"),
+    ];
+    for (extension, mime) in
+        [("png", "image/png"), ("jpeg", "image/jpeg"), ("gif", "image/gif")]
+    {
+        if body.media.iter().any(|media| media.extension == extension) {
+            defaults.push((extension, mime));
+        }
+    }
+
+    for (extension, mime) in defaults {
+        xml.start_element("Default");
+        xml.write_attribute("Extension", extension);
+        xml.write_attribute("ContentType", mime);
+        xml.end_element();
+    }
+
+    let main = "application/vnd.openxmlformats-officedocument.wordprocessingml";
+    for (part, kind) in [
+        ("/word/document.xml", format!("{main}.document.main+xml")),
+        ("/word/styles.xml", format!("{main}.styles+xml")),
+        ("/word/footnotes.xml", format!("{main}.footnotes+xml")),
+        ("/word/numbering.xml", format!("{main}.numbering+xml")),
+        (
+            "/docProps/core.xml",
+            "application/vnd.openxmlformats-package.core-properties+xml".into(),
+        ),
+    ] {
+        xml.start_element("Override");
+        xml.write_attribute("PartName", part);
+        xml.write_attribute("ContentType", &kind);
+        xml.end_element();
+    }
+
+    xml.end_document()
+}
+
+/// Write `_rels/.rels`, which points to the main document and its metadata.
+pub fn package_rels() -> String {
+    relationships(&[
+        (
+            "rId1".into(),
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument",
+            "word/document.xml".into(),
+        ),
+        (
+            "rId2".into(),
+            "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties",
+            "docProps/core.xml".into(),
+        ),
+    ])
+}
+
+/// Write `word/_rels/document.xml.rels`, which points to the styles, the
+/// footnotes, the numberings, and the images.
+pub fn document_rels(body: &Body) -> String {
+    let mut rels = vec![
+        (
+            "rIdStyles".into(),
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles",
+            "styles.xml".into(),
+        ),
+        (
+            "rIdFootnotes".into(),
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes",
+            "footnotes.xml".into(),
+        ),
+        (
+            "rIdNumbering".into(),
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/numbering",
+            "numbering.xml".into(),
+        ),
+    ];
+
+    for (i, media) in body.media.iter().enumerate() {
+        rels.push((image_id(i), REL_IMAGE, media_path(i, media.extension)));
+    }
+
+    relationships(&rels)
+}
+
+/// Write `docProps/core.xml` with the document's metadata.
+pub fn core_props(document: &Document) -> String {
+    let mut xml = writer();
+    xml.start_element("cp:coreProperties");
+    xml.write_attribute(
+        "xmlns:cp",
+        "http://schemas.openxmlformats.org/package/2006/metadata/core-properties",
+    );
+    xml.write_attribute("xmlns:dc", "http://purl.org/dc/elements/1.1/");
+    xml.write_attribute("xmlns:dcterms", "http://purl.org/dc/terms/");
+    xml.write_attribute("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance");
+
+    if let Some(title) = &document.title {
+        text_element(&mut xml, "dc:title", title);
+    }
+
+    if !document.author.is_empty() {
+        text_element(&mut xml, "dc:creator", &document.author.join(", "));
+    }
+
+    if !document.keywords.is_empty() {
+        text_element(&mut xml, "cp:keywords", &document.keywords.join(", "));
+    }
+
+    if let Smart::Custom(Some(date)) = document.date {
+        if let (Some(year), Some(month), Some(day)) =
+            (date.year(), date.month(), date.day())
+        {
+            let hour = date.hour().unwrap_or(0);
+            let minute = date.minute().unwrap_or(0);
+            let second = date.second().unwrap_or(0);
+            xml.start_element("dcterms:created");
+            xml.write_attribute("xsi:type", "dcterms:W3CDTF");
+            xml.write_text(&format!(
+                "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z"
+            ));
+            xml.end_element();
+        }
+    }
+
+    xml.end_document()
+}
+
+/// Write `word/document.xml` with the document's body.
+pub fn document(body: &Body) -> String {
+    let mut xml = writer();
+    xml.start_element("w:document");
+    xml.write_attribute("xmlns:w", NS_W);
+    xml.write_attribute("xmlns:r", NS_R);
+    xml.write_attribute("xmlns:wp", NS_WP);
+    xml.write_attribute("xmlns:a", NS_A);
+    xml.write_attribute("xmlns:pic", NS_PIC);
+    xml.start_element("w:body");
+
+    blocks(&mut xml, &body.blocks);
+
+    // The header and footer sit halfway into the top and bottom margins.
+    let Size { x: width, y: height } = body.page;
+    let margin = body.margin;
+    xml.start_element("w:sectPr");
+    xml.start_element("w:pgSz");
+    xml.write_attribute("w:w", &twips(width));
+    xml.write_attribute("w:h", &twips(height));
+    xml.end_element();
+    xml.start_element("w:pgMar");
+    xml.write_attribute("w:top", &twips(margin.top));
+    xml.write_attribute("w:right", &twips(margin.right));
+    xml.write_attribute("w:bottom", &twips(margin.bottom));
+    xml.write_attribute("w:left", &twips(margin.left));
+    xml.write_attribute("w:header", &twips(margin.top / 2.0));
+    xml.write_attribute("w:footer", &twips(margin.bottom / 2.0));
+    xml.write_attribute("w:gutter", "0");
+    xml.end_element();
+    xml.end_element();
+
+    xml.end_document()
+}
+
+/// Write `word/footnotes.xml` with the document's footnotes.
+pub fn footnotes(body: &Body) -> String {
+    let mut xml = writer();
+    xml.start_element("w:footnotes");
+    xml.write_attribute("xmlns:w", NS_W);
+
+    // Word requires the separators to be defined.
+    for (id, kind) in [(-1, "separator"), (0, "continuationSeparator")] {
+        xml.start_element("w:footnote");
+        xml.write_attribute("w:type", kind);
+        xml.write_attribute("w:id", &id);
+        xml.start_element("w:p");
+        xml.start_element("w:r");
+        xml.start_element(&format!("w:{kind}"));
+        xml.end_element();
+        xml.end_element();
+        xml.end_element();
+        xml.end_element();
+    }
+
+    for (i, text) in body.footnotes.iter().enumerate() {
+        xml.start_element("w:footnote");
+        xml.write_attribute("w:id", &footnote_id(i));
+        xml.start_element("w:p");
+        paragraph_style(&mut xml, "FootnoteText");
+        xml.start_element("w:r");
+        run_style(&mut xml, "FootnoteReference");
+        xml.start_element("w:footnoteRef");
+        xml.end_element();
+        xml.end_element();
+        xml.start_element("w:r");
+        text_element(&mut xml, "w:t", &format!(" {text}"));
+        xml.end_element();
+        xml.end_element();
+        xml.end_element();
+    }
+
+    xml.end_document()
+}
+
+/// Write `word/numbering.xml`, which defines a numbering for each distinct
+/// list marker.
+///
+/// The markers were already resolved by Typst, so each numbering displays its
+/// marker literally on every level and only determines the indentation.
+pub fn numbering(body: &Body) -> String {
+    let mut xml = writer();
+    xml.start_element("w:numbering");
+    xml.write_attribute("xmlns:w", NS_W);
+
+    for (i, marker) in body.markers.iter().enumerate() {
+        xml.start_element("w:abstractNum");
+        xml.write_attribute("w:abstractNumId", &i);
+        for level in 0..9 {
+            xml.start_element("w:lvl");
+            xml.write_attribute("w:ilvl", &level);
+            xml.start_element("w:numFmt");
+            xml.write_attribute("w:val", "bullet");
+            xml.end_element();
+            xml.start_element("w:lvlText");
+            xml.write_attribute("w:val", marker);
+            xml.end_element();
+            xml.start_element("w:pPr");
+            indentation(&mut xml, level + 1, true);
+            xml.end_element();
+            xml.end_element();
+        }
+        xml.end_element();
+    }
+
+    for i in 0..body.markers.len() {
+        xml.start_element("w:num");
+        xml.write_attribute("w:numId", &numbering_id(i));
+        xml.start_element("w:abstractNumId");
+        xml.write_attribute("w:val", &i);
+        xml.end_element();
+        xml.end_element();
+    }
+
+    xml.end_document()
+}
+
+/// Write `word/styles.xml`, which defines the styles the body refers to.
+pub fn styles() -> String {
+    let mut xml = writer();
+    xml.start_element("w:styles");
+    xml.write_attribute("xmlns:w", NS_W);
+
+    style(&mut xml, "paragraph", "Normal", "Normal", None, |xml| {
+        xml.start_element("w:pPr");
+        xml.start_element("w:spacing");
+        xml.write_attribute("w:after", "160");
+        xml.end_element();
+        xml.end_element();
+    });
+
+    for level in 1..=9 {
+        let id = format!("Heading{level}");
+        let name = format!("heading {level}");
+        style(&mut xml, "paragraph", &id, &name, Some("Normal"), |xml| {
+            xml.start_element("w:pPr");
+            xml.start_element("w:keepNext");
+            xml.end_element();
+            xml.start_element("w:spacing");
+            xml.write_attribute("w:before", "240");
+            xml.write_attribute("w:after", "120");
+            xml.end_element();
+            xml.start_element("w:outlineLvl");
+            xml.write_attribute("w:val", &(level - 1));
+            xml.end_element();
+            xml.end_element();
+            xml.start_element("w:rPr");
+            xml.start_element("w:b");
+            xml.end_element();
+            xml.end_element();
+        });
+    }
+
+    style(
+        &mut xml,
+        "paragraph",
+        "ListParagraph",
+        "List Paragraph",
+        Some("Normal"),
+        |xml| {
+            xml.start_element("w:pPr");
+            xml.start_element("w:ind");
+            xml.write_attribute("w:left", "720");
+            xml.write_attribute("w:hanging", "360");
+            xml.end_element();
+            xml.end_element();
+        },
+    );
+
+    style(&mut xml, "paragraph", "Caption", "caption", Some("Normal"), |xml| {
+        xml.start_element("w:rPr");
+        xml.start_element("w:i");
+        xml.end_element();
+        xml.end_element();
+    });
+
+    style(
+        &mut xml,
+        "paragraph",
+        "FootnoteText",
+        "footnote text",
+        Some("Normal"),
+        |xml| {
+            xml.start_element("w:pPr");
+            xml.start_element("w:spacing");
+            xml.write_attribute("w:after", "0");
+            xml.end_element();
+            xml.end_element();
+            xml.start_element("w:rPr");
+            xml.start_element("w:sz");
+            xml.write_attribute("w:val", "18");
+            xml.end_element();
+            xml.end_element();
+        },
+    );
+
+    style(
+        &mut xml,
+        "character",
+        "FootnoteReference",
+        "footnote reference",
+        None,
+        |xml| {
+            xml.start_element("w:rPr");
+            xml.start_element("w:vertAlign");
+            xml.write_attribute("w:val", "superscript");
+            xml.end_element();
+            xml.end_element();
+        },
+    );
+
+    style(&mut xml, "table", "TableGrid", "Table Grid", None, |xml| {
+        xml.start_element("w:tblPr");
+        xml.start_element("w:tblBorders");
+        for side in ["top", "left", "bottom", "right", "insideH", "insideV"] {
+            xml.start_element(&format!("w:{side}"));
+            xml.write_attribute("w:val", "single");
+            xml.write_attribute("w:sz", "4");
+            xml.write_attribute("w:space", "0");
+            xml.write_attribute("w:color", "auto");
+            xml.end_element();
+        }
+        xml.end_element();
+        xml.end_element();
+    });
+
+    xml.end_document()
+}
+
+/// Write blocks.
+fn blocks(xml: &mut XmlWriter, blocks: &[Block]) {
+    for block in blocks {
+        match block {
+            Block::Paragraph(par) => paragraph(xml, par),
+            Block::Table(table) => write_table(xml, table),
+        }
+    }
+}
+
+/// Write a paragraph.
+fn paragraph(xml: &mut XmlWriter, par: &Paragraph) {
+    xml.start_element("w:p");
+    if par.style.is_some() || par.depth > 0 {
+        xml.start_element("w:pPr");
+        if let Some(style) = &par.style {
+            xml.start_element("w:pStyle");
+            xml.write_attribute("w:val", style);
+            xml.end_element();
+        }
+
+        // The first paragraph of a list item is numbered, which indents it.
+        // Further paragraphs are indented to the item's text.
+        if let Some(marker) = par.marker {
+            xml.start_element("w:numPr");
+            xml.start_element("w:ilvl");
+            xml.write_attribute("w:val", &(par.depth - 1));
+            xml.end_element();
+            xml.start_element("w:numId");
+            xml.write_attribute("w:val", &numbering_id(marker));
+            xml.end_element();
+            xml.end_element();
+        } else if par.depth > 0 {
+            indentation(xml, par.depth, false);
+        }
+        xml.end_element();
+    }
+
+    for inline in &par.inlines {
+        match inline {
+            Inline::Text(text, props) => run(xml, text, props),
+            Inline::Footnote(i) => {
+                xml.start_element("w:r");
+                run_style(xml, "FootnoteReference");
+                xml.start_element("w:footnoteReference");
+                xml.write_attribute("w:id", &footnote_id(*i));
+                xml.end_element();
+                xml.end_element();
+            }
+            Inline::Image(i, size, alt) => image(xml, *i, *size, alt.as_ref()),
+        }
+    }
+
+    xml.end_element();
+}
+
+/// Write a run of text with direct formatting.
+fn run(xml: &mut XmlWriter, text: &str, props: &RunProps) {
+    xml.start_element("w:r");
+    xml.start_element("w:rPr");
+
+    if !props.family.is_empty() {
+        xml.start_element("w:rFonts");
+        for attr in ["w:ascii", "w:hAnsi", "w:eastAsia", "w:cs"] {
+            xml.write_attribute(attr, &props.family);
+        }
+        xml.end_element();
+    }
+
+    if props.bold {
+        xml.start_element("w:b");
+        xml.end_element();
+    }
+
+    if props.italic {
+        xml.start_element("w:i");
+        xml.end_element();
+    }
+
+    if let Some(color) = &props.color {
+        xml.start_element("w:color");
+        xml.write_attribute("w:val", color);
+        xml.end_element();
+    }
+
+    // Sizes are given in half-points.
+    xml.start_element("w:sz");
+    xml.write_attribute("w:val", &((2.0 * props.size.to_pt()).round() as u32));
+    xml.end_element();
+
+    xml.end_element();
+    text_element(xml, "w:t", text);
+    xml.end_element();
+}
+
+/// Write an inline image.
+fn image(xml: &mut XmlWriter, i: usize, size: Size, alt: Option<&EcoString>) {
+    let (cx, cy) = (emu(size.x), emu(size.y));
+    let id = i + 1;
+    let name = format!("Image {id}");
+
+    xml.start_element("w:r");
+    xml.start_element("w:drawing");
+    xml.start_element("wp:inline");
+
+    xml.start_element("wp:extent");
+    xml.write_attribute("cx", &cx);
+    xml.write_attribute("cy", &cy);
+    xml.end_element();
+
+    xml.start_element("wp:docPr");
+    xml.write_attribute("id", &id);
+    xml.write_attribute("name", &name);
+    if let Some(alt) = alt {
+        xml.write_attribute("descr", alt);
+    }
+    xml.end_element();
+
+    xml.start_element("a:graphic");
+    xml.start_element("a:graphicData");
+    xml.write_attribute("uri", NS_PIC);
+    xml.start_element("pic:pic");
+
+    xml.start_element("pic:nvPicPr");
+    xml.start_element("pic:cNvPr");
+    xml.write_attribute("id", &id);
+    xml.write_attribute("name", &name);
+    xml.end_element();
+    xml.start_element("pic:cNvPicPr");
+    xml.end_element();
+    xml.end_element();
+
+    xml.start_element("pic:blipFill");
+    xml.start_element("a:blip");
+    xml.write_attribute("r:embed", &image_id(i));
+    xml.end_element();
+    xml.start_element("a:stretch");
+    xml.start_element("a:fillRect");
+    xml.end_element();
+    xml.end_element();
+    xml.end_element();
+
+    xml.start_element("pic:spPr");
+    xml.start_element("a:xfrm");
+    xml.start_element("a:off");
+    xml.write_attribute("x", "0");
+    xml.write_attribute("y", "0");
+    xml.end_element();
+    xml.start_element("a:ext");
+    xml.write_attribute("cx", &cx);
+    xml.write_attribute("cy", &cy);
+    xml.end_element();
+    xml.end_element();
+    xml.start_element("a:prstGeom");
+    xml.write_attribute("prst", "rect");
+    xml.start_element("a:avLst");
+    xml.end_element();
+    xml.end_element();
+    xml.end_element();
+
+    xml.end_element();
+    xml.end_element();
+    xml.end_element();
+    xml.end_element();
+    xml.end_element();
+    xml.end_element();
+}
+
+/// Write a table.
+fn write_table(xml: &mut XmlWriter, table: &Table) {
+    xml.start_element("w:tbl");
+
+    xml.start_element("w:tblPr");
+    xml.start_element("w:tblStyle");
+    xml.write_attribute("w:val", "TableGrid");
+    xml.end_element();
+    xml.start_element("w:tblW");
+    xml.write_attribute("w:w", "0");
+    xml.write_attribute("w:type", "auto");
+    xml.end_element();
+    xml.end_element();
+
+    xml.start_element("w:tblGrid");
+    for _ in 0..table.columns {
+        xml.start_element("w:gridCol");
+        xml.end_element();
+    }
+    xml.end_element();
+
+    for row in &table.rows {
+        xml.start_element("w:tr");
+        for cell in row {
+            xml.start_element("w:tc");
+            if cell.colspan > 1 {
+                xml.start_element("w:tcPr");
+                xml.start_element("w:gridSpan");
+                xml.write_attribute("w:val", &cell.colspan);
+                xml.end_element();
+                xml.end_element();
+            }
+
+            blocks(xml, &cell.blocks);
+
+            // Each cell must end with a paragraph.
+            if !matches!(cell.blocks.last(), Some(Block::Paragraph(_))) {
+                xml.start_element("w:p");
+                xml.end_element();
+            }
+            xml.end_element();
+        }
+        xml.end_element();
+    }
+
+    xml.end_element();
+}
+
+/// Write a relationships part.
+fn relationships(rels: &[(String, &str, String)]) -> String {
+    let mut xml = writer();
+    xml.start_element("Relationships");
+    xml.write_attribute("xmlns", NS_RELS);
+    for (id, kind, target) in rels {
+        xml.start_element("Relationship");
+        xml.write_attribute("Id", id);
+        xml.write_attribute("Type", kind);
+        xml.write_attribute("Target", target);
+        xml.end_element();
+    }
+    xml.end_document()
+}
+
+/// Write a style definition.
+fn style(
+    xml: &mut XmlWriter,
+    kind: &str,
+    id: &str,
+    name: &str,
+    based_on: Option<&str>,
+    props: impl FnOnce(&mut XmlWriter),
+) {
+    xml.start_element("w:style");
+    xml.write_attribute("w:type", kind);
+    xml.write_attribute("w:styleId", id);
+    xml.start_element("w:name");
+    xml.write_attribute("w:val", name);
+    xml.end_element();
+    if let Some(based_on) = based_on {
+        xml.start_element("w:basedOn");
+        xml.write_attribute("w:val", based_on);
+        xml.end_element();
+        xml.start_element("w:next");
+        xml.write_attribute("w:val", based_on);
+        xml.end_element();
+    }
+    props(xml);
+    xml.end_element();
+}
+
+/// Write the properties of a paragraph with the given style.
+fn paragraph_style(xml: &mut XmlWriter, style: &str) {
+    xml.start_element("w:pPr");
+    xml.start_element("w:pStyle");
+    xml.write_attribute("w:val", style);
+    xml.end_element();
+    xml.end_element();
+}
+
+/// Write the indentation of a paragraph in a list item at the given depth,
+/// with a hanging marker if `hanging` is set.
+fn indentation(xml: &mut XmlWriter, depth: usize, hanging: bool) {
+    xml.start_element("w:ind");
+    xml.write_attribute("w:left", &(720 * depth));
+    if hanging {
+        xml.write_attribute("w:hanging", "360");
+    } else {
+        xml.write_attribute("w:firstLine", "0");
+    }
+    xml.end_element();
+}
+
+/// Write the properties of a run with the given style.
+fn run_style(xml: &mut XmlWriter, style: &str) {
+    xml.start_element("w:rPr");
+    xml.start_element("w:rStyle");
+    xml.write_attribute("w:val", style);
+    xml.end_element();
+    xml.end_element();
+}
+
+/// Write an element that only contains text.
+fn text_element(xml: &mut XmlWriter, name: &str, text: &str) {
+    xml.start_element(name);
+    if name == "w:t" {
+        xml.write_attribute("xml:space", "preserve");
+    }
+    xml.write_text(text);
+    xml.end_element();
+}
+
+/// Create a writer that doesn't add whitespace, which would be significant in
+/// text runs.
+fn writer() -> XmlWriter {
+    let mut xml = XmlWriter::new(Options { indent: Indent::None, ..Options::default() });
+    xml.write_declaration();
+    xml
+}
+
+/// The id of a footnote. The ids 0 and -1 are reserved for the separators.
+fn footnote_id(i: usize) -> usize {
+    i + 1
+}
+
+/// The id of the numbering for a list marker. Zero means no numbering.
+fn numbering_id(i: usize) -> usize {
+    i + 1
+}
+
+/// The relationship id of an image.
+fn image_id(i: usize) -> String {
+    format!("rIdImage{}", i + 1)
+}
+
+/// The path of an image relative to the `word` directory.
+pub fn media_path(i: usize, extension: &str) -> String {
+    format!("media/image{}.{extension}", i + 1)
+}
+
+/// Convert a length to twentieths of a point.
+fn twips(abs: Abs) -> i64 {
+    (abs.to_pt() * 20.0).round() as i64
+}
+
+/// Convert a length to English Metric Units.
+fn emu(abs: Abs) -> i64 {
+    (abs.to_pt() * 12700.0).round() as i64
+}
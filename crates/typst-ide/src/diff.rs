@@ -0,0 +1,396 @@
+use ecow::EcoString;
+use typst::layout::{Frame, FrameItem, Point, Size, Transform};
+use typst::model::Document;
+use typst::syntax::Span;
+use typst::utils::hash128;
+
+/// The differences between two compilations of a document.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentDiff {
+    /// The pages that differ, in order.
+    pub pages: Vec<PageDiff>,
+}
+
+impl DocumentDiff {
+    /// Whether the documents look the same.
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+}
+
+/// The differences between a page of the old and the new document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageDiff {
+    /// The zero-based index of the page.
+    pub index: usize,
+    /// How the page as a whole changed.
+    pub kind: PageChange,
+    /// The changed items on the page. For added and removed pages, these are
+    /// all of their items.
+    pub changes: Vec<ItemChange>,
+}
+
+/// How a page as a whole changed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PageChange {
+    /// The page only exists in the new document.
+    Added,
+    /// The page only exists in the old document.
+    Removed,
+    /// The page exists in both documents, but its size changed.
+    Resized,
+    /// The page exists in both documents with the same size, but its
+    /// contents changed.
+    Modified,
+}
+
+/// A change of a single visible item on a page.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemChange {
+    /// The item only exists in the new document.
+    Added(DiffItem),
+    /// The item only exists in the old document.
+    Removed(DiffItem),
+    /// The item exists in both documents, but at a different position or with
+    /// a different size.
+    Moved { old: DiffItem, new: DiffItem },
+}
+
+/// A visible item on a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffItem {
+    /// What kind of item this is.
+    pub kind: DiffItemKind,
+    /// The item's position on the page.
+    pub pos: Point,
+    /// The item's size.
+    pub size: Size,
+    /// The span of the item's source, if known.
+    pub span: Span,
+}
+
+/// The kind of a [`DiffItem`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DiffItemKind {
+    /// A run of text.
+    Text(EcoString),
+    /// A geometric shape.
+    Shape,
+    /// An image.
+    Image,
+}
+
+/// Compute the visual differences between two compilations of a document.
+///
+/// Pages are compared by their index. On each page, the visible items are
+/// matched by their contents and in reading order. Matched items that moved
+/// or resized are reported as moved, the remaining ones as added or removed.
+/// Text is compared in runs, so a changed word shows up as a removed and an
+/// added run. If a page changed too much, its differing items are reported as
+/// removed and added without matching them.
+pub fn diff_documents(old: &Document, new: &Document) -> DocumentDiff {
+    let mut pages = vec![];
+    for index in 0..old.pages.len().max(new.pages.len()) {
+        let old_frame = old.pages.get(index).map(|page| &page.frame);
+        let new_frame = new.pages.get(index).map(|page| &page.frame);
+        let diff = match (old_frame, new_frame) {
+            (Some(old), Some(new)) => {
+                let changes = diff_items(&flatten(old), &flatten(new));
+                let kind = if old.size() != new.size() {
+                    PageChange::Resized
+                } else if !changes.is_empty() {
+                    PageChange::Modified
+                } else {
+                    continue;
+                };
+                PageDiff { index, kind, changes }
+            }
+            (Some(old), None) => PageDiff {
+                index,
+                kind: PageChange::Removed,
+                changes: flatten(old)
+                    .into_iter()
+                    .map(|keyed| ItemChange::Removed(keyed.item))
+                    .collect(),
+            },
+            (None, Some(new)) => PageDiff {
+                index,
+                kind: PageChange::Added,
+                changes: flatten(new)
+                    .into_iter()
+                    .map(|keyed| ItemChange::Added(keyed.item))
+                    .collect(),
+            },
+            (None, None) => unreachable!(),
+        };
+        pages.push(diff);
+    }
+    DocumentDiff { pages }
+}
+
+/// An item with a key that identifies its contents.
+struct Keyed {
+    key: u128,
+    item: DiffItem,
+}
+
+/// Collect the visible items of a frame in order.
+fn flatten(frame: &Frame) -> Vec<Keyed> {
+    let mut items = vec![];
+    collect(frame, Transform::identity(), &mut items);
+    items
+}
+
+/// Collect the visible items of a frame, transformed with `ts`.
+fn collect(frame: &Frame, ts: Transform, items: &mut Vec<Keyed>) {
+    for (pos, item) in frame.items() {
+        let (kind, size, span, key) = match item {
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                collect(&group.frame, ts, items);
+                continue;
+            }
+            FrameItem::Text(text) => {
+                let span = text.glyphs.first().map(|g| g.span.0);
+                let size = Size::new(text.width(), text.size);
+                let key = hash128(&(&text.text, &text.font, text.size, &text.fill));
+                (DiffItemKind::Text(text.text.clone()), size, span, key)
+            }
+            FrameItem::Shape(shape, span) => {
+                let size = shape.geometry.bbox_size();
+                (DiffItemKind::Shape, size, Some(*span), hash128(shape))
+            }
+            FrameItem::Image(image, size, span) => {
+                (DiffItemKind::Image, *size, Some(*span), hash128(image))
+            }
+            FrameItem::Link(..) | FrameItem::Tag(_) => continue,
+        };
+
+        let item = DiffItem {
+            kind,
+            pos: pos.transform(ts),
+            size,
+            span: span.unwrap_or_else(Span::detached),
+        };
+        items.push(Keyed { key, item });
+    }
+}
+
+/// Match two sequences of items and report their differences.
+fn diff_items(old: &[Keyed], new: &[Keyed]) -> Vec<ItemChange> {
+    // Skip the common prefix and suffix to keep the table small.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a.key == b.key).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a.key == b.key)
+        .count();
+
+    let mut changes = vec![];
+    for (a, b) in old.iter().zip(new).take(prefix) {
+        matched(&mut changes, a, b);
+    }
+
+    // Find the shortest edit script for the remaining items. If the items
+    // differ too much, the page has changed as a whole.
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    match edit_script(old_mid, new_mid) {
+        Some(edits) => {
+            for edit in edits {
+                match edit {
+                    Edit::Keep(i, j) => matched(&mut changes, &old_mid[i], &new_mid[j]),
+                    Edit::Add(j) => {
+                        changes.push(ItemChange::Added(new_mid[j].item.clone()))
+                    }
+                    Edit::Remove(i) => {
+                        changes.push(ItemChange::Removed(old_mid[i].item.clone()))
+                    }
+                }
+            }
+        }
+        None => {
+            changes.extend(old_mid.iter().map(|a| ItemChange::Removed(a.item.clone())));
+            changes.extend(new_mid.iter().map(|b| ItemChange::Added(b.item.clone())));
+        }
+    }
+
+    for (a, b) in old[old.len() - suffix..].iter().zip(&new[new.len() - suffix..]) {
+        matched(&mut changes, a, b);
+    }
+
+    changes
+}
+
+/// The maximum number of added and removed items for which a page is diffed
+/// item by item.
+const MAX_EDITS: usize = 1000;
+
+/// A step of an edit script.
+enum Edit {
+    /// Keep the old item at the first index as the new one at the second.
+    Keep(usize, usize),
+    /// Add the new item at the index.
+    Add(usize),
+    /// Remove the old item at the index.
+    Remove(usize),
+}
+
+/// Find the shortest edit script that turns `old` into `new` with Myers'
+/// algorithm.
+///
+/// Returns `None` if the script would add and remove more than [`MAX_EDITS`]
+/// items. This bounds the time to `O((n + m) · MAX_EDITS)` and the memory to
+/// `O(MAX_EDITS²)`.
+fn edit_script(old: &[Keyed], new: &[Keyed]) -> Option<Vec<Edit>> {
+    let (n, m) = (old.len(), new.len());
+    let max = (n + m).min(MAX_EDITS) as isize;
+
+    // The furthest reaching x-position on each diagonal `k = x - y`, offset
+    // by `max + 1`. After each round `d`, the positions on the diagonals
+    // `-d, -d + 2, ..., d` are saved for backtracking.
+    let mut v = vec![0; 2 * max as usize + 3];
+    let mut trace: Vec<Vec<usize>> = vec![];
+    let index = |k: isize| (k + max + 1) as usize;
+    let mut done = false;
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+                v[index(k + 1)]
+            } else {
+                v[index(k - 1)] + 1
+            };
+            let mut y = (x as isize - k) as usize;
+            while x < n && y < m && old[x].key == new[y].key {
+                x += 1;
+                y += 1;
+            }
+            v[index(k)] = x;
+            done |= x >= n && y >= m;
+        }
+        trace.push((-d..=d).step_by(2).map(|k| v[index(k)]).collect());
+        if done {
+            break;
+        }
+    }
+
+    if !done {
+        return None;
+    }
+
+    // Walk back from the end, taking the same steps as above.
+    let at = |d: isize, k: isize| trace[d as usize][((k + d) / 2) as usize];
+    let mut edits = vec![];
+    let (mut x, mut y) = (n, m);
+    for d in (1..trace.len() as isize).rev() {
+        let k = x as isize - y as isize;
+        let down = k == -d || (k != d && at(d - 1, k - 1) < at(d - 1, k + 1));
+        let prev_k = if down { k + 1 } else { k - 1 };
+        let prev_x = at(d - 1, prev_k);
+        let prev_y = (prev_x as isize - prev_k) as usize;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Keep(x, y));
+        }
+        edits.push(if down { Edit::Add(prev_y) } else { Edit::Remove(prev_x) });
+        (x, y) = (prev_x, prev_y);
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        edits.push(Edit::Keep(x, y));
+    }
+
+    edits.reverse();
+    Some(edits)
+}
+
+/// Report a pair of matched items if it moved or resized.
+fn matched(changes: &mut Vec<ItemChange>, a: &Keyed, b: &Keyed) {
+    let (old, new) = (&a.item, &b.item);
+    if !old.pos.x.approx_eq(new.pos.x)
+        || !old.pos.y.approx_eq(new.pos.y)
+        || !old.size.x.approx_eq(new.size.x)
+        || !old.size.y.approx_eq(new.size.y)
+    {
+        changes.push(ItemChange::Moved { old: old.clone(), new: new.clone() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use typst::eval::Tracer;
+    use typst::model::Document;
+
+    use super::{diff_documents, DiffItemKind, ItemChange, PageChange};
+    use crate::tests::TestWorld;
+
+    fn compile(text: &str) -> Document {
+        let world = TestWorld::new(text);
+        typst::compile(&world, &mut Tracer::new()).unwrap()
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let doc = compile("Hello #box(width: 10pt, height: 10pt, fill: red)");
+        assert!(diff_documents(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn test_diff_text() {
+        let old = compile("Hello world");
+        let new = compile("Hello there");
+        let diff = diff_documents(&old, &new);
+        assert_eq!(diff.pages.len(), 1);
+        assert_eq!(diff.pages[0].kind, PageChange::Modified);
+        let texts: Vec<_> = diff.pages[0]
+            .changes
+            .iter()
+            .map(|change| match change {
+                ItemChange::Added(item) => ("added", &item.kind),
+                ItemChange::Removed(item) => ("removed", &item.kind),
+                ItemChange::Moved { new, .. } => ("moved", &new.kind),
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                ("added", &DiffItemKind::Text("Hello there".into())),
+                ("removed", &DiffItemKind::Text("Hello world".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_moved_and_pages() {
+        let old = compile("#rect()");
+        let new = compile("#v(1cm) #rect() #pagebreak() New");
+        let diff = diff_documents(&old, &new);
+        assert_eq!(diff.pages.len(), 2);
+        assert!(diff.pages[0]
+            .changes
+            .iter()
+            .any(|change| matches!(change, ItemChange::Moved { .. })));
+        assert_eq!(diff.pages[1].kind, PageChange::Added);
+    }
+
+    #[test]
+    fn test_diff_too_many_changes() {
+        let old = compile("#for i in range(600) { place(rect(width: 1pt)) }");
+        let new = compile("#for i in range(600) { place(rect(width: 2pt)) }");
+        let diff = diff_documents(&old, &new);
+        assert_eq!(diff.pages.len(), 1);
+        let changes = &diff.pages[0].changes;
+        assert_eq!(changes.len(), 1200);
+        assert!(changes[..600]
+            .iter()
+            .all(|change| matches!(change, ItemChange::Removed(_))));
+        assert!(changes[600..]
+            .iter()
+            .all(|change| matches!(change, ItemChange::Added(_))));
+    }
+}
@@ -2,12 +2,21 @@
 
 mod analyze;
 mod complete;
+mod diff;
 mod jump;
+mod structure;
 mod tooltip;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::{autocomplete, Completion, CompletionKind};
+pub use self::diff::{
+    diff_documents, DiffItem, DiffItemKind, DocumentDiff, ItemChange, PageChange,
+    PageDiff,
+};
 pub use self::jump::{jump_from_click, jump_from_cursor, Jump};
+pub use self::structure::{
+    document_structure, source_structure, DocumentSymbol, SymbolKind,
+};
 pub use self::tooltip::{tooltip, Tooltip};
 
 use std::fmt::Write;
@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+
+use comemo::Track;
+use ecow::EcoString;
+use typst::foundations::{Label, StyleChain};
+use typst::model::{
+    BibliographyElem, CiteGroup, Document, FigureElem, HeadingElem, RefElem,
+};
+use typst::syntax::{ast, LinkedNode, Source, Span, SyntaxKind};
+
+/// An entry in the logical structure of a document.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DocumentSymbol {
+    /// What kind of entry this is.
+    pub kind: SymbolKind,
+    /// A short, human-readable name for the entry.
+    pub name: EcoString,
+    /// The span of the entry in the source.
+    pub span: Span,
+    /// The label attached to the entry, if any.
+    pub label: Option<Label>,
+}
+
+/// The kind of a [`DocumentSymbol`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SymbolKind {
+    /// A heading with its level.
+    Heading(NonZeroUsize),
+    /// A figure.
+    Figure,
+    /// A labelled element that is not a heading or figure.
+    Label,
+    /// A reference to a label.
+    Reference,
+    /// A reference to a label that the document does not contain, for
+    /// example because the document is outdated.
+    UnresolvedReference,
+    /// A citation of a bibliography key.
+    Citation,
+}
+
+/// Extract the logical structure of a compiled document.
+///
+/// The symbols are in document order. Headings can be nested by their levels
+/// to build an outline.
+pub fn document_structure(document: &Document) -> Vec<DocumentSymbol> {
+    let introspector = &document.introspector;
+    let keys: HashSet<EcoString> = BibliographyElem::keys(introspector.track())
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+
+    let mut symbols = vec![];
+    for elem in introspector.all() {
+        let label = elem.label();
+        let mut push = |kind, name| {
+            symbols.push(DocumentSymbol { kind, name, span: elem.span(), label })
+        };
+
+        if let Some(heading) = elem.to_packed::<HeadingElem>() {
+            let level = heading.resolve_level(StyleChain::default());
+            push(SymbolKind::Heading(level), heading.body().plain_text());
+        } else if let Some(figure) = elem.to_packed::<FigureElem>() {
+            let name = figure
+                .caption(StyleChain::default())
+                .map(|caption| caption.body().plain_text())
+                .unwrap_or_else(|| "figure".into());
+            push(SymbolKind::Figure, name);
+        } else if let Some(group) = elem.to_packed::<CiteGroup>() {
+            for cite in group.children() {
+                push(SymbolKind::Citation, cite.key().as_str().into());
+            }
+        } else if let Some(reference) = elem.to_packed::<RefElem>() {
+            // References to bibliography keys become citations, which are
+            // reported through their citation group.
+            let target = *reference.target();
+            if introspector.query_label(target).is_ok() {
+                push(SymbolKind::Reference, target.as_str().into());
+            } else if !keys.contains(target.as_str()) {
+                push(SymbolKind::UnresolvedReference, target.as_str().into());
+            }
+        } else if let Some(label) = label {
+            push(SymbolKind::Label, label.as_str().into());
+        }
+    }
+    symbols
+}
+
+/// Extract the logical structure of a source file without compiling it.
+///
+/// This works purely on the syntax tree and is thus fast, but it only finds
+/// what is written directly in the file. For example, headings produced by
+/// functions are missed. Since references and citations look the same in
+/// markup, all of them are reported as [references](SymbolKind::Reference).
+pub fn source_structure(source: &Source) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+    let mut attached = HashSet::new();
+    walk(&LinkedNode::new(source.root()), &mut symbols, &mut attached);
+    symbols
+}
+
+/// Collect the symbols in a syntax tree.
+fn walk(
+    node: &LinkedNode,
+    symbols: &mut Vec<DocumentSymbol>,
+    attached: &mut HashSet<Span>,
+) {
+    match node.kind() {
+        SyntaxKind::Heading => {
+            let heading = node.cast::<ast::Heading>().unwrap();
+            let body = node.children().find(|child| child.kind() == SyntaxKind::Markup);
+            let label = body.as_ref().and_then(|body| {
+                let last =
+                    body.children().rev().find(|child| !child.kind().is_trivia())?;
+                attached_label(&last, attached)
+            });
+            symbols.push(DocumentSymbol {
+                kind: SymbolKind::Heading(heading.depth()),
+                name: body.as_ref().map(plain).unwrap_or_default(),
+                span: node.span(),
+                label,
+            });
+        }
+        SyntaxKind::FuncCall if is_figure(node) => {
+            let caption = find_named_arg(node, "caption")
+                .map(|caption| plain(&caption))
+                .filter(|caption| !caption.is_empty());
+            let label =
+                node.next_sibling().and_then(|next| attached_label(&next, attached));
+            symbols.push(DocumentSymbol {
+                kind: SymbolKind::Figure,
+                name: caption.unwrap_or_else(|| "figure".into()),
+                span: node.span(),
+                label,
+            });
+        }
+        SyntaxKind::Label if !attached.contains(&node.span()) => {
+            let label = node.cast::<ast::Label>().unwrap().get();
+            symbols.push(DocumentSymbol {
+                kind: SymbolKind::Label,
+                name: label.into(),
+                span: node.span(),
+                label: Some(Label::new(label)),
+            });
+        }
+        SyntaxKind::Ref => {
+            let target = node.cast::<ast::Ref>().unwrap().target();
+            symbols.push(DocumentSymbol {
+                kind: SymbolKind::Reference,
+                name: target.into(),
+                span: node.span(),
+                label: None,
+            });
+        }
+        _ => {}
+    }
+
+    for child in node.children() {
+        walk(&child, symbols, attached);
+    }
+}
+
+/// Mark the node as an attached label if it is one.
+fn attached_label(node: &LinkedNode, attached: &mut HashSet<Span>) -> Option<Label> {
+    let label = node.cast::<ast::Label>()?;
+    attached.insert(node.span());
+    Some(Label::new(label.get()))
+}
+
+/// Whether the node is a call to the `figure` function.
+fn is_figure(node: &LinkedNode) -> bool {
+    node.cast::<ast::FuncCall>().is_some_and(|call| {
+        matches!(call.callee(), ast::Expr::Ident(ident) if ident.as_str() == "figure")
+    })
+}
+
+/// Extract the plain text of markup, without labels and brackets.
+fn plain(node: &LinkedNode) -> EcoString {
+    fn collect(node: &LinkedNode, text: &mut String) {
+        match node.kind() {
+            SyntaxKind::Label | SyntaxKind::LeftBracket | SyntaxKind::RightBracket => {}
+            _ if node.get().children().len() == 0 => text.push_str(node.get().text()),
+            _ => node.children().for_each(|child| collect(&child, text)),
+        }
+    }
+
+    let mut text = String::new();
+    collect(node, &mut text);
+    text.split_whitespace().collect::<Vec<_>>().join(" ").into()
+}
+
+/// Find the value of a named argument of a function call.
+fn find_named_arg<'a>(call: &LinkedNode<'a>, name: &str) -> Option<LinkedNode<'a>> {
+    let args = call.children().find(|child| child.kind() == SyntaxKind::Args)?;
+    args.children().find_map(|arg| {
+        let named = arg.cast::<ast::Named>()?;
+        (named.name().as_str() == name).then(|| arg.children().last())?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use typst::eval::Tracer;
+
+    use super::{document_structure, source_structure, SymbolKind};
+    use crate::tests::TestWorld;
+
+    const TEXT: &str = "\
+        #set heading(numbering: \"1.\")\n\
+        = Intro <intro>\n\
+        == Details\n\
+        #figure([A], caption: [A cat]) <cat>\n\
+        See @intro and @cat. #box[x] <misc>";
+
+    #[track_caller]
+    fn test(
+        symbols: Vec<super::DocumentSymbol>,
+        expected: &[(SymbolKind, &str, Option<&str>)],
+    ) {
+        let actual: Vec<_> = symbols
+            .iter()
+            .map(|symbol| {
+                (
+                    symbol.kind,
+                    symbol.name.as_str(),
+                    symbol.label.map(|label| label.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    fn level(level: usize) -> SymbolKind {
+        SymbolKind::Heading(NonZeroUsize::new(level).unwrap())
+    }
+
+    #[test]
+    fn test_source_structure() {
+        let world = TestWorld::new(TEXT);
+        test(
+            source_structure(&world.main),
+            &[
+                (level(1), "Intro", Some("intro")),
+                (level(2), "Details", None),
+                (SymbolKind::Figure, "A cat", Some("cat")),
+                (SymbolKind::Reference, "intro", None),
+                (SymbolKind::Reference, "cat", None),
+                (SymbolKind::Label, "misc", Some("misc")),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let world = TestWorld::new(TEXT);
+        let doc = typst::compile(&world, &mut Tracer::new()).unwrap();
+        test(
+            document_structure(&doc),
+            &[
+                (level(1), "Intro", Some("intro")),
+                (level(2), "Details", None),
+                (SymbolKind::Figure, "A cat", Some("cat")),
+                (SymbolKind::Reference, "intro", None),
+                (SymbolKind::Reference, "cat", None),
+                (SymbolKind::Label, "misc", Some("misc")),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_document_structure_unresolved_reference() {
+        let world = TestWorld::new("= Intro <intro>\n#pagebreak()\nSee @intro.");
+        let mut doc = typst::compile(&world, &mut Tracer::new()).unwrap();
+
+        // Drop the page with the heading, as if the document was outdated.
+        doc.pages.remove(0);
+        doc.introspector.rebuild(&doc.pages);
+        test(
+            document_structure(&doc),
+            &[(SymbolKind::UnresolvedReference, "intro", None)],
+        );
+    }
+}
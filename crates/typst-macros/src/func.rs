@@ -456,7 +456,8 @@ fn bind(param: &Param) -> TokenStream {
     }
 }
 
-/// Removes attributes and so on from the native function.
+/// Removes attributes and so on from the native function. Lint attributes are
+/// kept.
 fn rewrite_fn_item(item: &syn::ItemFn) -> syn::ItemFn {
     let inputs = item.sig.inputs.iter().cloned().filter_map(|mut input| {
         if let syn::FnArg::Typed(typed) = &mut input {
@@ -468,7 +469,7 @@ fn rewrite_fn_item(item: &syn::ItemFn) -> syn::ItemFn {
         Some(input)
     });
     let mut item = item.clone();
-    item.attrs.clear();
+    item.attrs.retain(|attr| attr.path().is_ident("allow"));
     item.sig.inputs = parse_quote! { #(#inputs),* };
     item
 }
@@ -0,0 +1,51 @@
+use ecow::EcoString;
+use pdf_writer::{Finish, Name, Pdf, Ref, Str, TextStr};
+use typst::foundations::Bytes;
+
+/// A file to attach to an exported PDF.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct PdfAttachment {
+    /// The file name under which the file is attached.
+    pub name: EcoString,
+    /// The MIME type of the file, if known.
+    pub mime_type: Option<EcoString>,
+    /// A human-readable description of the file.
+    pub description: Option<EcoString>,
+    /// The contents of the file.
+    pub data: Bytes,
+}
+
+/// Write the embedded files and their file specifications.
+///
+/// Returns the names and file specification references, sorted by name as
+/// required for the embedded files name tree.
+pub fn write_attachments<'a>(
+    pdf: &mut Pdf,
+    alloc: &mut Ref,
+    attachments: &'a [PdfAttachment],
+) -> Vec<(&'a str, Ref)> {
+    let mut refs = vec![];
+    for attachment in attachments {
+        let file_ref = alloc.bump();
+        let mut file = pdf.embedded_file(file_ref, attachment.data.as_slice());
+        if let Some(mime_type) = &attachment.mime_type {
+            file.subtype(Name(mime_type.as_bytes()));
+        }
+        file.finish();
+
+        let spec_ref = alloc.bump();
+        let mut spec = pdf.file_spec(spec_ref);
+        spec.path(Str(attachment.name.as_bytes()));
+        spec.unic_file(TextStr(&attachment.name));
+        spec.embedded_file(file_ref);
+        if let Some(description) = &attachment.description {
+            spec.description(TextStr(description));
+        }
+        spec.finish();
+
+        refs.push((attachment.name.as_str(), spec_ref));
+    }
+
+    refs.sort_by_key(|&(name, _)| name);
+    refs
+}
@@ -10,6 +10,7 @@ use typst::foundations::{Datetime, Smart};
 use typst::layout::Dir;
 use typst::text::Lang;
 
+use crate::attachment::{write_attachments, PdfAttachment};
 use crate::WithEverything;
 use crate::{hash_base64, outline, page::PdfPageLabel};
 
@@ -18,6 +19,7 @@ pub fn write_catalog(
     ctx: WithEverything,
     ident: Smart<&str>,
     timestamp: Option<Datetime>,
+    attachments: &[PdfAttachment],
     pdf: &mut Pdf,
     alloc: &mut Ref,
 ) {
@@ -40,6 +42,9 @@ pub fn write_catalog(
     // Write the page labels.
     let page_labels = write_page_labels(pdf, alloc, &ctx);
 
+    // Write the attached files.
+    let attachments = write_attachments(pdf, alloc, attachments);
+
     // Write the document information.
     let info_ref = alloc.bump();
     let mut info = pdf.document_info(info_ref);
@@ -148,6 +153,18 @@ pub fn write_catalog(
     }
     names.finish();
     dests_name_tree.finish();
+
+    // Write the embedded files name tree.
+    if !attachments.is_empty() {
+        let mut files_name_tree = name_dict.embedded_files();
+        let mut names = files_name_tree.names();
+        for &(name, spec_ref) in &attachments {
+            names.insert(Str(name.as_bytes()), spec_ref);
+        }
+        names.finish();
+        files_name_tree.finish();
+    }
+
     name_dict.finish();
 
     // Insert the page labels.
@@ -130,7 +130,7 @@ pub fn write_color_fonts(
 
             // Write the font descriptor.
             gids.sort();
-            let subset_tag = subset_tag(&gids);
+            let subset_tag = subset_tag(&font_slice.font, &gids);
             let postscript_name = font_slice
                 .font
                 .find_name(name_id::POST_SCRIPT_NAME)
@@ -4,26 +4,32 @@
 //!
 //! See also [`pdf_writer::Content`].
 
-use ecow::eco_format;
+use std::fmt::Write;
+
+use ecow::{eco_format, EcoString};
 use pdf_writer::{
     types::{ColorSpaceOperand, LineCapStyle, LineJoinStyle, TextRenderingMode},
-    Content, Finish, Name, Rect, Str,
+    Content, Finish, Name, Rect, Str, TextStr,
 };
+use typst::foundations::Bytes;
 use typst::layout::{
     Abs, Em, Frame, FrameItem, GroupItem, Point, Ratio, Size, Transform,
 };
 use typst::model::Destination;
+use typst::syntax::Span;
 use typst::text::{color::is_color_glyph, Font, TextItem, TextItemView};
 use typst::utils::{Deferred, Numeric, SliceExt};
 use typst::visualize::{
-    FixedStroke, Geometry, Image, LineCap, LineJoin, Paint, Path, PathItem, Shape,
+    BlendMode, Color, FixedStroke, Geometry, Image, ImageFormat, LineCap, LineJoin,
+    Paint, Path, PathItem, RatioOrAngle, Shape, VectorFormat,
 };
 
 use crate::color_font::ColorFontMap;
 use crate::extg::ExtGState;
+use crate::group::{GroupRemapper, PdfGroup};
 use crate::image::deferred_image;
 use crate::{color::PaintEncode, resources::Resources};
-use crate::{deflate_deferred, AbsExt, EmExt};
+use crate::{deflate_deferred, transform_to_array, AbsExt, EmExt};
 
 /// Encode a [`Frame`] into a content stream.
 ///
@@ -96,6 +102,8 @@ pub struct Builder<'a, R = ()> {
     uses_opacities: bool,
     /// All clickable links that are present in this content.
     links: Vec<(Destination, Rect)>,
+    /// The size of the content stream's own coordinate space.
+    base_size: Size,
 }
 
 impl<'a, R> Builder<'a, R> {
@@ -108,6 +116,7 @@ impl<'a, R> Builder<'a, R> {
             state: State::new(size),
             saves: vec![],
             links: vec![],
+            base_size: size,
         }
     }
 }
@@ -224,7 +233,11 @@ impl Builder<'_, ()> {
                 color.alpha().map_or(255, |v| (v * 255.0).round() as u8)
             })
             .unwrap_or(255);
-        self.set_external_graphics_state(&ExtGState { stroke_opacity, fill_opacity });
+        self.set_external_graphics_state(&ExtGState {
+            stroke_opacity,
+            fill_opacity,
+            blend_mode: BlendMode::Normal,
+        });
     }
 
     pub fn transform(&mut self, transform: Transform) {
@@ -348,7 +361,9 @@ pub(crate) fn write_frame(ctx: &mut Builder, frame: &Frame) {
             FrameItem::Group(group) => write_group(ctx, pos, group),
             FrameItem::Text(text) => write_text(ctx, pos, text),
             FrameItem::Shape(shape, _) => write_shape(ctx, pos, shape),
-            FrameItem::Image(image, size, _) => write_image(ctx, x, y, image, *size),
+            FrameItem::Image(image, size, span) => {
+                write_image(ctx, x, y, image, *size, *span)
+            }
             FrameItem::Link(dest, size) => write_link(ctx, pos, dest, *size),
             FrameItem::Tag(_) => {}
         }
@@ -365,7 +380,7 @@ fn write_group(ctx: &mut Builder, pos: Point, group: &GroupItem) {
         ctx.group_transform(
             ctx.state
                 .transform
-                .post_concat(ctx.state.container_transform.invert().unwrap())
+                .post_concat(ctx.state.container_transform.invert().unwrap_or_default())
                 .pre_concat(translation)
                 .pre_concat(group.transform),
         );
@@ -379,10 +394,75 @@ fn write_group(ctx: &mut Builder, pos: Point, group: &GroupItem) {
         ctx.content.end_path();
     }
 
-    write_frame(ctx, &group.frame);
+    if let Some(alt) = &group.alt {
+        begin_alt(ctx, alt);
+    }
+
+    if group.opacity.is_one() && group.blend_mode == BlendMode::Normal {
+        write_frame(ctx, &group.frame);
+    } else {
+        write_transparency_group(ctx, group);
+    }
+
+    if group.alt.is_some() {
+        ctx.content.end_marked_content();
+    }
+
     ctx.restore_state();
 }
 
+/// Encode a group with non-default opacity or blend mode into the content
+/// stream as a transparency group.
+///
+/// The group's content is written in the coordinate space of the surrounding
+/// content stream, so that link annotations, gradients, and patterns inside
+/// the group are positioned just like outside of it.
+fn write_transparency_group(ctx: &mut Builder, group: &GroupItem) {
+    // A degenerate transform makes the group invisible.
+    let Some(inverse) = ctx.state.transform.invert() else {
+        return;
+    };
+
+    let size = ctx.base_size;
+    let transform = ctx.state.transform;
+    let container_transform = ctx.state.container_transform;
+    let container_size = ctx.state.size;
+
+    let groups = ctx
+        .resources
+        .groups
+        .get_or_insert_with(|| Box::new(GroupRemapper::new()));
+
+    // Render the body.
+    let mut inner = Builder::new(&mut groups.resources, size);
+    inner.state.transform = transform;
+    inner.state.container_transform = container_transform;
+    inner.state.size = container_size;
+    inner.content.transform(transform_to_array(transform));
+    write_frame(&mut inner, &group.frame);
+
+    let uses_opacities = inner.uses_opacities;
+    let links = std::mem::take(&mut inner.links);
+    let content = deflate_deferred(inner.content.finish());
+    let index = groups
+        .remapper
+        .insert(PdfGroup { size, content: content.wait().clone() });
+
+    ctx.links.extend(links);
+    ctx.uses_opacities |= uses_opacities;
+
+    let alpha = (group.opacity.get() * 255.0).round() as u8;
+    ctx.set_external_graphics_state(&ExtGState {
+        stroke_opacity: alpha,
+        fill_opacity: alpha,
+        blend_mode: group.blend_mode,
+    });
+
+    let name = eco_format!("Xg{index}");
+    ctx.content.transform(transform_to_array(inverse));
+    ctx.content.x_object(Name(name.as_bytes()));
+}
+
 /// Encode a text run into the content stream.
 fn write_text(ctx: &mut Builder, pos: Point, text: &TextItem) {
     let ttf = text.font.ttf();
@@ -439,6 +519,9 @@ fn write_normal_text(ctx: &mut Builder, pos: Point, text: TextItemView) {
 
     *ctx.resources.languages.entry(text.item.lang).or_insert(0) += text.glyph_range.len();
 
+    let span = text.glyphs().next().map_or(Span::detached(), |glyph| glyph.span.0);
+    ctx.resources.font_spans.entry(text.item.font.clone()).or_insert(span);
+
     let glyph_set = ctx.resources.glyph_sets.entry(text.item.font.clone()).or_default();
     for g in text.glyphs() {
         let t = text.text();
@@ -447,7 +530,9 @@ fn write_normal_text(ctx: &mut Builder, pos: Point, text: TextItemView) {
     }
 
     let fill_transform = ctx.state.transforms(Size::zero(), pos);
-    ctx.set_fill(&text.item.fill, true, fill_transform);
+    if let Some(fill) = &text.item.fill {
+        ctx.set_fill(fill, true, fill_transform);
+    }
 
     let stroke = text.item.stroke.as_ref().and_then(|stroke| {
         if stroke.thickness.to_f32() > 0.0 {
@@ -459,13 +544,17 @@ fn write_normal_text(ctx: &mut Builder, pos: Point, text: TextItemView) {
 
     if let Some(stroke) = stroke {
         ctx.set_stroke(stroke, true, fill_transform);
-        ctx.set_text_rendering_mode(TextRenderingMode::FillStroke);
-    } else {
-        ctx.set_text_rendering_mode(TextRenderingMode::Fill);
     }
 
+    ctx.set_text_rendering_mode(match (&text.item.fill, stroke) {
+        (Some(_), Some(_)) => TextRenderingMode::FillStroke,
+        (Some(_), None) => TextRenderingMode::Fill,
+        (None, Some(_)) => TextRenderingMode::Stroke,
+        (None, None) => TextRenderingMode::Invisible,
+    });
+
     ctx.set_font(&text.item.font, text.item.size);
-    ctx.set_opacities(text.item.stroke.as_ref(), Some(&text.item.fill));
+    ctx.set_opacities(text.item.stroke.as_ref(), text.item.fill.as_ref());
     ctx.content.begin_text();
 
     // Position the text.
@@ -552,6 +641,11 @@ fn write_color_glyphs(ctx: &mut Builder, pos: Point, text: TextItemView) {
 
 /// Encode a geometrical shape into the content stream.
 fn write_shape(ctx: &mut Builder, pos: Point, shape: &Shape) {
+    if shape.blur > Abs::zero() {
+        write_blurred_shape(ctx, pos, shape);
+        return;
+    }
+
     let x = pos.x.to_f32();
     let y = pos.y.to_f32();
 
@@ -572,11 +666,15 @@ fn write_shape(ctx: &mut Builder, pos: Point, shape: &Shape) {
     }
 
     if let Some(stroke) = stroke {
-        ctx.set_stroke(
-            stroke,
-            false,
-            ctx.state.transforms(shape.geometry.bbox_size(), pos),
-        );
+        if let Some((stroke, origin, size)) = shape.stroke_along_path() {
+            ctx.set_stroke(&stroke, false, ctx.state.transforms(size, pos + origin));
+        } else {
+            ctx.set_stroke(
+                stroke,
+                false,
+                ctx.state.transforms(shape.geometry.bbox_size(), pos),
+            );
+        }
     }
 
     ctx.set_opacities(stroke, shape.fill.as_ref());
@@ -608,6 +706,147 @@ fn write_shape(ctx: &mut Builder, pos: Point, shape: &Shape) {
     };
 }
 
+/// Encode a blurred shape into the content stream.
+///
+/// PDF has no blur filters, so the shape is converted into an SVG with a
+/// gaussian blur, which is rasterized when the image is embedded.
+fn write_blurred_shape(ctx: &mut Builder, pos: Point, shape: &Shape) {
+    let (min, max) = shape.blur_bounds();
+    let size = Size::new(max.x - min.x, max.y - min.y);
+    if !size.is_finite() || size.x <= Abs::zero() || size.y <= Abs::zero() {
+        return;
+    }
+
+    let svg = blurred_shape_svg(shape, min, size);
+    let Ok(image) = Image::new(
+        Bytes::from(svg.as_bytes()),
+        ImageFormat::Vector(VectorFormat::Svg),
+        None,
+    ) else {
+        return;
+    };
+
+    let origin = pos + min;
+    let (x, y) = (origin.x.to_f32(), origin.y.to_f32());
+    write_image(ctx, x, y, &image, size, Span::detached());
+}
+
+/// Build an SVG document that contains the shape with a gaussian blur. The
+/// document's view box starts at `min` in the shape's coordinate system.
+///
+/// Gradient and pattern paints are approximated by a solid color since a
+/// blurred shape rarely shows their detail.
+fn blurred_shape_svg(shape: &Shape, min: Point, size: Size) -> EcoString {
+    fn color(paint: &Paint) -> Color {
+        match paint {
+            Paint::Solid(color) => *color,
+            Paint::Gradient(gradient) => {
+                gradient.sample(RatioOrAngle::Ratio(Ratio::new(0.5)))
+            }
+            Paint::Pattern(_) => Color::BLACK,
+        }
+    }
+
+    fn write_paint(svg: &mut EcoString, attr: &str, paint: &Paint) {
+        let [r, g, b, a] = color(paint).to_rgb().to_vec4_u8();
+        let opacity = a as f32 / 255.0;
+        write!(svg, r#" {attr}="rgb({r},{g},{b})" {attr}-opacity="{opacity}""#).ok();
+    }
+
+    let (w, h) = (size.x.to_pt(), size.y.to_pt());
+    let mut svg = EcoString::new();
+    write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="{} {} {w} {h}">"#,
+        min.x.to_pt(),
+        min.y.to_pt(),
+    )
+    .ok();
+    write!(
+        svg,
+        r#"<filter id="blur" filterUnits="userSpaceOnUse" x="{}" y="{}" width="{w}" height="{h}"><feGaussianBlur stdDeviation="{}"/></filter>"#,
+        min.x.to_pt(),
+        min.y.to_pt(),
+        shape.blur.to_pt() / 2.0,
+    )
+    .ok();
+
+    svg.push_str(r#"<path filter="url(#blur)""#);
+    match &shape.fill {
+        Some(fill) => write_paint(&mut svg, "fill", fill),
+        None => svg.push_str(r#" fill="none""#),
+    }
+
+    if let Some(stroke) = &shape.stroke {
+        write_paint(&mut svg, "stroke", &stroke.paint);
+        let cap = match stroke.cap {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        };
+        let join = match stroke.join {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel",
+        };
+        write!(
+            svg,
+            r#" stroke-width="{}" stroke-linecap="{cap}" stroke-linejoin="{join}" stroke-miterlimit="{}""#,
+            stroke.thickness.to_pt(),
+            stroke.miter_limit.get(),
+        )
+        .ok();
+        if let Some(dash) = &stroke.dash {
+            let array: Vec<_> =
+                dash.array.iter().map(|l| l.to_pt().to_string()).collect();
+            write!(
+                svg,
+                r#" stroke-dasharray="{}" stroke-dashoffset="{}""#,
+                array.join(" "),
+                dash.phase.to_pt(),
+            )
+            .ok();
+        }
+    }
+
+    svg.push_str(r#" d=""#);
+    match &shape.geometry {
+        Geometry::Line(target) => {
+            write!(svg, "M 0 0 L {} {}", target.x.to_pt(), target.y.to_pt()).ok();
+        }
+        Geometry::Rect(size) => {
+            let (w, h) = (size.x.to_pt(), size.y.to_pt());
+            write!(svg, "M 0 0 H {w} V {h} H 0 Z").ok();
+        }
+        Geometry::Path(path) => {
+            for item in &path.0 {
+                match item {
+                    PathItem::MoveTo(p) => {
+                        write!(svg, "M {} {} ", p.x.to_pt(), p.y.to_pt())
+                    }
+                    PathItem::LineTo(p) => {
+                        write!(svg, "L {} {} ", p.x.to_pt(), p.y.to_pt())
+                    }
+                    PathItem::CubicTo(p1, p2, p3) => write!(
+                        svg,
+                        "C {} {} {} {} {} {} ",
+                        p1.x.to_pt(),
+                        p1.y.to_pt(),
+                        p2.x.to_pt(),
+                        p2.y.to_pt(),
+                        p3.x.to_pt(),
+                        p3.y.to_pt(),
+                    ),
+                    PathItem::ClosePath => write!(svg, "Z "),
+                }
+                .ok();
+            }
+        }
+    }
+    svg.push_str(r#""/></svg>"#);
+    svg
+}
+
 /// Encode a bezier path into the content stream.
 fn write_path(ctx: &mut Builder, x: f32, y: f32, path: &Path) {
     for elem in &path.0 {
@@ -632,14 +871,14 @@ fn write_path(ctx: &mut Builder, x: f32, y: f32, path: &Path) {
 }
 
 /// Encode a vector or raster image into the content stream.
-fn write_image(ctx: &mut Builder, x: f32, y: f32, image: &Image, size: Size) {
+fn write_image(ctx: &mut Builder, x: f32, y: f32, image: &Image, size: Size, span: Span) {
     let index = ctx.resources.images.insert(image.clone());
     ctx.resources.deferred_images.entry(index).or_insert_with(|| {
         let (image, color_space) = deferred_image(image.clone());
         if let Some(color_space) = color_space {
             ctx.resources.colors.mark_as_used(color_space);
         }
-        image
+        (image, span)
     });
 
     let name = eco_format!("Im{index}");
@@ -649,13 +888,7 @@ fn write_image(ctx: &mut Builder, x: f32, y: f32, image: &Image, size: Size) {
     ctx.content.transform([w, 0.0, 0.0, -h, x, y + h]);
 
     if let Some(alt) = image.alt() {
-        let mut image_span =
-            ctx.content.begin_marked_content_with_properties(Name(b"Span"));
-        let mut image_alt = image_span.properties();
-        image_alt.pair(Name(b"Alt"), pdf_writer::Str(alt.as_bytes()));
-        image_alt.finish();
-        image_span.finish();
-
+        begin_alt(ctx, alt);
         ctx.content.x_object(Name(name.as_bytes()));
         ctx.content.end_marked_content();
     } else {
@@ -665,6 +898,14 @@ fn write_image(ctx: &mut Builder, x: f32, y: f32, image: &Image, size: Size) {
     ctx.content.restore_state();
 }
 
+/// Begin a marked-content sequence that carries an alternative description.
+///
+/// Must be closed with `end_marked_content`.
+fn begin_alt(ctx: &mut Builder, alt: &str) {
+    let mut span = ctx.content.begin_marked_content_with_properties(Name(b"Span"));
+    span.properties().pair(Name(b"Alt"), TextStr(alt));
+}
+
 /// Save a link for later writing in the annotations dictionary.
 fn write_link(ctx: &mut Builder, pos: Point, dest: &Destination, size: Size) {
     let mut min_x = Abs::inf();
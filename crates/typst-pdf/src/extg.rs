@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use pdf_writer::Ref;
+use typst::visualize::BlendMode;
 
+use crate::group::to_pdf_blend_mode;
 use crate::{PdfChunk, WithGlobalRefs};
 
 /// A PDF external graphics state.
@@ -11,17 +13,25 @@ pub struct ExtGState {
     pub stroke_opacity: u8,
     // In the range 0-255, needs to be divided before being written into the graphics state!
     pub fill_opacity: u8,
+    /// How painted content is blended with the content beneath it.
+    pub blend_mode: BlendMode,
 }
 
 impl Default for ExtGState {
     fn default() -> Self {
-        Self { stroke_opacity: 255, fill_opacity: 255 }
+        Self {
+            stroke_opacity: 255,
+            fill_opacity: 255,
+            blend_mode: BlendMode::Normal,
+        }
     }
 }
 
 impl ExtGState {
     pub fn uses_opacities(&self) -> bool {
-        self.stroke_opacity != 255 || self.fill_opacity != 255
+        self.stroke_opacity != 255
+            || self.fill_opacity != 255
+            || self.blend_mode != BlendMode::Normal
     }
 }
 
@@ -39,10 +49,13 @@ pub fn write_graphic_states(
 
             let id = chunk.alloc();
             out.insert(*external_gs, id);
-            chunk
-                .ext_graphics(id)
+            let mut ext_graphics = chunk.ext_graphics(id);
+            ext_graphics
                 .non_stroking_alpha(external_gs.fill_opacity as f32 / 255.0)
                 .stroking_alpha(external_gs.stroke_opacity as f32 / 255.0);
+            if external_gs.blend_mode != BlendMode::Normal {
+                ext_graphics.blend_mode(to_pdf_blend_mode(external_gs.blend_mode));
+            }
         }
     });
 
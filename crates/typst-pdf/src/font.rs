@@ -1,19 +1,21 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::Hash;
 use std::sync::Arc;
 
-use ecow::{eco_format, EcoString};
+use ecow::{eco_format, EcoString, EcoVec};
 use pdf_writer::{
     types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap},
     writers::FontDescriptor,
     Chunk, Filter, Finish, Name, Rect, Ref, Str,
 };
-use ttf_parser::{name_id, GlyphId, Tag};
+use ttf_parser::{name_id, GlyphId, Permissions, Tag};
+use typst::diag::{error, SourceResult};
+use typst::syntax::Span;
 use typst::text::Font;
 use typst::utils::SliceExt;
 use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
 
-use crate::{deflate, EmExt, PdfChunk, WithGlobalRefs};
+use crate::{deflate, EmExt, PdfChunk, WithGlobalRefs, WithResources};
 
 const CFF: Tag = Tag::from_bytes(b"CFF ");
 const CFF2: Tag = Tag::from_bytes(b"CFF2");
@@ -24,6 +26,57 @@ pub(crate) const SYSTEM_INFO: SystemInfo = SystemInfo {
     supplement: 0,
 };
 
+/// How much of each font is embedded into the PDF.
+///
+/// Fonts are always embedded as CID-keyed Type 0 fonts, with CFF outlines as
+/// `CIDFontType0` and TrueType outlines as `CIDFontType2`. An option to force
+/// simple Type 1 or TrueType fonts instead is deferred: those address glyphs
+/// through one-byte codes and the font's own encoding or `cmap`, which would
+/// require rewriting the embedded font programs and is not supported by the
+/// subsetter yet.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FontPolicy {
+    /// Whether to embed complete fonts instead of subsets with just the used
+    /// glyphs. This makes the file larger, but allows editing its text
+    /// downstream.
+    pub full: bool,
+    /// Whether to refuse embedding fonts whose license forbids it. Fonts whose
+    /// license forbids subsetting are then also embedded in full.
+    pub respect_licenses: bool,
+}
+
+/// Check that the licenses of all used fonts allow embedding them, if the
+/// policy requests it.
+pub fn check_font_licenses(context: &WithResources) -> SourceResult<()> {
+    if !context.font_policy.respect_licenses {
+        return Ok(());
+    }
+
+    let mut seen = HashSet::new();
+    let mut errors = EcoVec::new();
+    context.resources.traverse(&mut |resources| {
+        for font in resources.fonts.items() {
+            if seen.insert(font.clone())
+                && font.ttf().permissions() == Some(Permissions::Restricted)
+            {
+                let span = resources.font_spans.get(font).copied();
+                errors.push(error!(
+                    span.unwrap_or_else(Span::detached),
+                    "the license of font `{}` does not allow embedding it",
+                    font.info().family;
+                    hint: "choose a different font or disable license checks",
+                ));
+            }
+        }
+    });
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(())
+}
+
 /// Embed all used fonts into the PDF.
 #[typst_macros::time(name = "write fonts")]
 pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
@@ -59,8 +112,19 @@ pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
                 .find_name(name_id::POST_SCRIPT_NAME)
                 .unwrap_or_else(|| "unknown".to_string());
 
-            let subset_tag = subset_tag(glyph_set);
-            let base_font = eco_format!("{subset_tag}+{postscript_name}");
+            // Embed the font in full if requested or if its license forbids
+            // subsetting.
+            let policy = context.font_policy;
+            let full =
+                policy.full || (policy.respect_licenses && !ttf.is_subsetting_allowed());
+
+            // Only subsets are tagged.
+            let base_font = if full {
+                EcoString::from(postscript_name.as_str())
+            } else {
+                let subset_tag = subset_tag(font, glyph_set);
+                eco_format!("{subset_tag}+{postscript_name}")
+            };
             let base_font_type0 = if is_cff {
                 eco_format!("{base_font}-Identity-H")
             } else {
@@ -86,9 +150,16 @@ pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
                 cid.cid_to_gid_map_predefined(Name(b"Identity"));
             }
 
+            // The glyphs to embed.
+            let glyphs: Vec<_> = if full {
+                (0..ttf.number_of_glyphs()).collect()
+            } else {
+                glyph_set.keys().copied().collect()
+            };
+
             // Extract the widths of all glyphs.
             let mut widths = vec![];
-            for gid in std::iter::once(0).chain(glyph_set.keys().copied()) {
+            for gid in std::iter::once(0).chain(glyphs.iter().copied()) {
                 let width = ttf.glyph_hor_advance(GlyphId(gid)).unwrap_or(0);
                 let units = font.to_em(width).to_font_units();
                 let cid = glyph_cid(font, gid);
@@ -119,8 +190,7 @@ pub fn write_fonts(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Font, Ref>) {
             chunk.cmap(cmap_ref, &cmap.finish());
 
             // Subset and write the font's bytes.
-            let glyphs: Vec<_> = glyph_set.keys().copied().collect();
-            let data = subset_font(font, &glyphs);
+            let data = if full { full_font(font) } else { subset_font(font, &glyphs) };
 
             let mut stream = chunk.stream(data_ref, &data);
             stream.filter(Filter::FlateDecode);
@@ -192,6 +262,28 @@ pub fn write_font_descriptor<'a>(
     font_descriptor
 }
 
+/// Extract a complete font for embedding.
+///
+/// - For a font with TrueType outlines, this returns the whole OpenType font.
+/// - For a font with CFF outlines, this returns just the CFF font program.
+#[comemo::memoize]
+fn full_font(font: &Font) -> Arc<Vec<u8>> {
+    let data = font.data();
+    let raw = ttf_parser::RawFace::parse(data, font.index()).unwrap();
+    if let Some(cff) = raw.table(CFF) {
+        return Arc::new(deflate(cff));
+    }
+
+    // A font in a collection must be extracted from it, which the subsetter
+    // does for us when keeping all glyphs.
+    if font.index() != 0 {
+        let glyphs: Vec<_> = (0..font.ttf().number_of_glyphs()).collect();
+        return subset_font(font, &glyphs);
+    }
+
+    Arc::new(deflate(data))
+}
+
 /// Subset a font to the given glyphs.
 ///
 /// - For a font with TrueType outlines, this returns the whole OpenType font.
@@ -213,11 +305,14 @@ fn subset_font(font: &Font, glyphs: &[u16]) -> Arc<Vec<u8>> {
     Arc::new(deflate(data))
 }
 
-/// Produce a unique 6 letter tag for a glyph set.
-pub(crate) fn subset_tag<T: Hash>(glyphs: &T) -> EcoString {
+/// Produce a unique 6 letter tag for a glyph set of a font.
+///
+/// The tag only depends on the font's data and the glyphs, so that it is the
+/// same across compilations.
+pub(crate) fn subset_tag<T: Hash>(font: &Font, glyphs: &T) -> EcoString {
     const LEN: usize = 6;
     const BASE: u128 = 26;
-    let mut hash = typst::utils::hash128(&glyphs);
+    let mut hash = typst::utils::hash128(&(font, glyphs));
     let mut letter = [b'A'; LEN];
     for l in letter.iter_mut() {
         *l = b'A' + (hash % BASE) as u8;
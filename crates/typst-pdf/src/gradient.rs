@@ -12,7 +12,8 @@ use pdf_writer::{
 use typst::layout::{Abs, Angle, Point, Quadrant, Ratio, Transform};
 use typst::utils::Numeric;
 use typst::visualize::{
-    Color, ColorSpace, Gradient, RatioOrAngle, RelativeTo, WeightedColor,
+    Color, ColorSpace, Gradient, MeshGradient, MeshPatch, RatioOrAngle, RelativeTo,
+    WeightedColor,
 };
 
 use crate::color::{self, ColorSpaceExt, PaintEncode, QuantizedColor};
@@ -154,6 +155,39 @@ pub fn write_gradients(
 
                     stream_shading.finish();
 
+                    let mut shading_pattern = chunk.shading_pattern(shading);
+                    shading_pattern.shading_ref(stream_shading_id);
+                    shading_pattern
+                }
+                Gradient::Mesh(mesh) => {
+                    let vertices = compute_mesh_stream(mesh);
+
+                    let stream_shading_id = chunk.alloc();
+                    let mut stream_shading =
+                        chunk.chunk.stream_shading(stream_shading_id, &vertices);
+
+                    color::write(
+                        color_space,
+                        stream_shading.color_space(),
+                        &context.globals.color_functions,
+                    );
+
+                    let [lo, hi] = MESH_RANGE;
+                    let range = color_space.range();
+                    stream_shading
+                        .bits_per_coordinate(16)
+                        .bits_per_component(16)
+                        .bits_per_flag(8)
+                        .shading_type(StreamShadingType::TensorProductPatch)
+                        .decode([
+                            lo, hi, lo, hi, range[0], range[1], range[2], range[3],
+                            range[4], range[5],
+                        ])
+                        .anti_alias(gradient.anti_alias())
+                        .filter(Filter::FlateDecode);
+
+                    stream_shading.finish();
+
                     let mut shading_pattern = chunk.shading_pattern(shading);
                     shading_pattern.shading_ref(stream_shading_id);
                     shading_pattern
@@ -294,7 +328,7 @@ fn register_gradient(
         transforms.size.y = Abs::pt(1.0);
     }
     let size = match gradient.unwrap_relative(on_text) {
-        RelativeTo::Self_ => transforms.size,
+        RelativeTo::Self_ | RelativeTo::Stroke => transforms.size,
         RelativeTo::Parent => transforms.container_size,
     };
 
@@ -309,7 +343,7 @@ fn register_gradient(
     let rotation = gradient.angle().unwrap_or_else(Angle::zero);
 
     let transform = match gradient.unwrap_relative(on_text) {
-        RelativeTo::Self_ => transforms.transform,
+        RelativeTo::Self_ | RelativeTo::Stroke => transforms.transform,
         RelativeTo::Parent => transforms.container_transform,
     };
 
@@ -321,6 +355,7 @@ fn register_gradient(
     let pdf_gradient = PdfGradient {
         aspect_ratio: size.aspect_ratio(),
         transform: transform
+            .pre_concat(gradient.transform())
             .pre_concat(Transform::translate(
                 offset_x * scale_offset,
                 offset_y * scale_offset,
@@ -495,6 +530,77 @@ fn compute_vertex_stream(gradient: &Gradient, aspect_ratio: Ratio) -> Arc<Vec<u8
     Arc::new(deflate(&vertices))
 }
 
+/// The range of the coordinates of mesh patches. The patches lie in the unit
+/// square, but the inner control points of curved patches can lie outside of
+/// it.
+const MESH_RANGE: [f32; 2] = [-1.0, 2.0];
+
+/// Writes a single tensor-product patch of a mesh gradient.
+///
+/// The control points are written in the order that the PDF specification
+/// prescribes: first the twelve points of the boundary, starting at the
+/// corner `points[0][0]` and running through `points[0][3]`, `points[3][3]`
+/// and `points[3][0]`, then the four inner points.
+fn write_mesh_patch(target: &mut Vec<u8>, patch: &MeshPatch, space: ColorSpace) {
+    const ORDER: [(usize, usize); 16] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (3, 2),
+        (3, 1),
+        (3, 0),
+        (2, 0),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (2, 2),
+        (2, 1),
+    ];
+
+    // Push the flag
+    target.push(0);
+
+    // Push the points.
+    let points = ORDER.map(|(i, j)| {
+        let point = patch.points[i][j];
+        [
+            u16::quantize(point.x.get() as f32, MESH_RANGE).to_be(),
+            u16::quantize(point.y.get() as f32, MESH_RANGE).to_be(),
+        ]
+    });
+    target.extend_from_slice(bytemuck::cast_slice(&points));
+
+    // Push the colors, in the same order as their corners.
+    let [[c00, c01], [c10, c11]] = patch.colors;
+    let colors = [c00, c01, c11, c10].map(|c| space.convert::<u16>(c).map(u16::to_be));
+    target.extend_from_slice(bytemuck::cast_slice(&colors));
+}
+
+#[comemo::memoize]
+fn compute_mesh_stream(mesh: &MeshGradient) -> Arc<Vec<u8>> {
+    // Generated vertices for the tensor-product patches
+    let mut vertices = Vec::new();
+
+    // Colors in hue-based spaces are encoded as Oklab, so we need to subdivide
+    // the patches for the interpolation to match.
+    for patch in &mesh.patches {
+        match mesh.space.hue_index() {
+            Some(_) => {
+                for part in patch.split(8, mesh.space) {
+                    write_mesh_patch(&mut vertices, &part, ColorSpace::Oklab);
+                }
+            }
+            None => write_mesh_patch(&mut vertices, patch, mesh.space),
+        }
+    }
+
+    Arc::new(deflate(&vertices))
+}
+
 fn color_space_of(gradient: &Gradient) -> ColorSpace {
     if gradient.space().hue_index().is_some() {
         ColorSpace::Oklab
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use pdf_writer::{Filter, Name, Rect, Ref};
+use typst::layout::Size;
+use typst::visualize::BlendMode;
+
+use crate::resources::{Remapper, ResourcesRefs};
+use crate::{AbsExt, PdfChunk, Resources, WithGlobalRefs};
+
+/// Writes the transparency groups to the PDF.
+/// This is performed once after writing all pages.
+pub fn write_groups(context: &WithGlobalRefs) -> (PdfChunk, HashMap<PdfGroup, Ref>) {
+    let mut chunk = PdfChunk::new();
+    let mut out = HashMap::new();
+    context.resources.traverse(&mut |resources| {
+        let Some(groups) = &resources.groups else {
+            return;
+        };
+
+        for pdf_group in groups.remapper.items() {
+            let PdfGroup { size, content } = pdf_group;
+            if out.contains_key(pdf_group) {
+                continue;
+            }
+
+            let id = chunk.alloc();
+            out.insert(pdf_group.clone(), id);
+
+            let mut form = chunk.form_xobject(id, content);
+            form.bbox(Rect::new(0.0, 0.0, size.x.to_f32(), size.y.to_f32()));
+            form.group()
+                .transparency()
+                .isolated(false)
+                .knockout(false)
+                .color_space()
+                .srgb();
+
+            // The actual resource dict will be written in a later step
+            form.pair(Name(b"Resources"), groups.resources.reference);
+            form.filter(Filter::FlateDecode);
+        }
+    });
+
+    (chunk, out)
+}
+
+/// A group of content that is composited as a whole.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PdfGroup {
+    /// The size of the coordinate space the group is drawn in.
+    pub size: Size,
+    /// The rendered group.
+    pub content: Vec<u8>,
+}
+
+/// Converts a blend mode into its PDF counterpart.
+pub(crate) fn to_pdf_blend_mode(blend_mode: BlendMode) -> pdf_writer::types::BlendMode {
+    use pdf_writer::types::BlendMode as Pdf;
+    match blend_mode {
+        BlendMode::Normal => Pdf::Normal,
+        BlendMode::Multiply => Pdf::Multiply,
+        BlendMode::Screen => Pdf::Screen,
+        BlendMode::Overlay => Pdf::Overlay,
+        BlendMode::Darken => Pdf::Darken,
+        BlendMode::Lighten => Pdf::Lighten,
+        BlendMode::ColorDodge => Pdf::ColorDodge,
+        BlendMode::ColorBurn => Pdf::ColorBurn,
+        BlendMode::HardLight => Pdf::HardLight,
+        BlendMode::SoftLight => Pdf::SoftLight,
+        BlendMode::Difference => Pdf::Difference,
+        BlendMode::Exclusion => Pdf::Exclusion,
+        BlendMode::Hue => Pdf::Hue,
+        BlendMode::Saturation => Pdf::Saturation,
+        BlendMode::Color => Pdf::Color,
+        BlendMode::Luminosity => Pdf::Luminosity,
+    }
+}
+
+/// De-duplicate transparency groups and the resources they require to be
+/// drawn.
+pub struct GroupRemapper<R> {
+    /// Group de-duplicator.
+    pub remapper: Remapper<PdfGroup>,
+    /// PDF resources that are used by these groups.
+    pub resources: Resources<R>,
+}
+
+impl GroupRemapper<()> {
+    pub fn new() -> Self {
+        Self {
+            remapper: Remapper::new("Xg"),
+            resources: Resources::default(),
+        }
+    }
+
+    /// Allocate a reference to the resource dictionary of these groups.
+    pub fn with_refs(self, refs: &ResourcesRefs) -> GroupRemapper<Ref> {
+        GroupRemapper {
+            remapper: self.remapper,
+            resources: self.resources.with_refs(refs),
+        }
+    }
+}
@@ -1,14 +1,35 @@
 use std::collections::HashMap;
 use std::io::Cursor;
 
+use ecow::{eco_format, EcoVec};
 use image::{DynamicImage, GenericImageView, Rgba};
-use pdf_writer::{Chunk, Filter, Finish, Ref};
+use pdf_writer::{Chunk, Filter, Finish, Name, Obj, Rect, Ref, Str};
+use typst::diag::{error, SourceResult, StrResult};
+use typst::layout::{Abs, Ratio, Transform};
 use typst::utils::Deferred;
 use typst::visualize::{
-    ColorSpace, Image, ImageKind, RasterFormat, RasterImage, SvgImage,
+    ColorSpace, Image, ImageKind, PdfImage, RasterFormat, RasterImage, SvgImage,
 };
 
-use crate::{color, deflate, PdfChunk, WithGlobalRefs};
+use crate::{color, deflate, PdfChunk, WithGlobalRefs, WithResources};
+
+/// Check that all used images could be encoded.
+pub fn check_images(context: &WithResources) -> SourceResult<()> {
+    let mut errors = EcoVec::new();
+    context.resources.traverse(&mut |resources| {
+        for (handle, span) in resources.deferred_images.values() {
+            if let Err(err) = handle.wait() {
+                errors.push(error!(*span, "failed to embed image ({err})"));
+            }
+        }
+    });
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(())
+}
 
 /// Embed all used images into the PDF.
 #[typst_macros::time(name = "write images")]
@@ -21,8 +42,10 @@ pub fn write_images(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Image, Ref>)
                 continue;
             }
 
-            let handle = resources.deferred_images.get(&i).unwrap();
-            match handle.wait() {
+            // Images that failed to encode were reported by `check_images`.
+            let (handle, _) = resources.deferred_images.get(&i).unwrap();
+            let Ok(encoded) = handle.wait() else { continue };
+            match encoded {
                 EncodedImage::Raster {
                     data,
                     filter,
@@ -90,9 +113,10 @@ pub fn write_images(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Image, Ref>)
                         }
                     }
                 }
-                EncodedImage::Svg(svg_chunk, id) => {
+                EncodedImage::Svg(vector_chunk, id)
+                | EncodedImage::Pdf(vector_chunk, id) => {
                     let mut map = HashMap::new();
-                    svg_chunk.renumber_into(&mut chunk.chunk, |old| {
+                    vector_chunk.renumber_into(&mut chunk.chunk, |old| {
                         *map.entry(old).or_insert_with(|| chunk.alloc.bump())
                     });
                     out.insert(image.clone(), map[&id]);
@@ -108,7 +132,9 @@ pub fn write_images(context: &WithGlobalRefs) -> (PdfChunk, HashMap<Image, Ref>)
 ///
 /// Also starts the deferred encoding of the image.
 #[comemo::memoize]
-pub fn deferred_image(image: Image) -> (Deferred<EncodedImage>, Option<ColorSpace>) {
+pub fn deferred_image(
+    image: Image,
+) -> (Deferred<StrResult<EncodedImage>>, Option<ColorSpace>) {
     let color_space = match image.kind() {
         ImageKind::Raster(raster) if raster.icc().is_none() => {
             if raster.dynamic().color().channel_count() > 2 {
@@ -130,11 +156,23 @@ pub fn deferred_image(image: Image) -> (Deferred<EncodedImage>, Option<ColorSpac
             let alpha =
                 raster.dynamic().color().has_alpha().then(|| encode_alpha(&raster));
 
-            EncodedImage::Raster { data, filter, has_color, width, height, icc, alpha }
+            Ok(EncodedImage::Raster {
+                data,
+                filter,
+                has_color,
+                width,
+                height,
+                icc,
+                alpha,
+            })
         }
         ImageKind::Svg(svg) => {
             let (chunk, id) = encode_svg(svg);
-            EncodedImage::Svg(chunk, id)
+            Ok(EncodedImage::Svg(chunk, id))
+        }
+        ImageKind::Pdf(pdf) => {
+            let (chunk, id) = encode_pdf(pdf)?;
+            Ok(EncodedImage::Pdf(chunk, id))
         }
     });
 
@@ -183,6 +221,163 @@ fn encode_svg(svg: &SvgImage) -> (Chunk, Ref) {
     svg2pdf::to_chunk(svg.tree(), svg2pdf::ConversionOptions::default())
 }
 
+/// Encode a page of a PDF document into a chunk of PDF objects.
+///
+/// The page becomes a form XObject that maps the visible page onto the unit
+/// square, just like an image XObject. All objects its resources depend on are
+/// copied along with it.
+fn encode_pdf(pdf: &PdfImage) -> StrResult<(Chunk, Ref)> {
+    let document = lopdf::Document::load_mem(pdf.data())
+        .map_err(|err| eco_format!("failed to parse PDF ({err})"))?;
+    let page_id = *document
+        .get_pages()
+        .get(&(pdf.page().get() as u32))
+        .ok_or("PDF page does not exist")?;
+
+    let mut chunk = Chunk::new();
+    let mut copier = Copier {
+        document: &document,
+        next: Ref::new(1),
+        refs: HashMap::new(),
+        queue: vec![],
+    };
+
+    let form_ref = copier.next.bump();
+    let mut resources_ref = None;
+    if let Some(resources) = page_resources(&document, page_id) {
+        let id = copier.next.bump();
+        copy_object(
+            chunk.indirect(id),
+            &lopdf::Object::Dictionary(resources.clone()),
+            &mut copier,
+        )?;
+        resources_ref = Some(id);
+    }
+
+    // Copy everything the resources refer to, including indirect references
+    // of the copied objects.
+    while let Some((id, new)) = copier.queue.pop() {
+        match copier.document.get_object(id) {
+            Ok(lopdf::Object::Stream(stream)) => {
+                let mut writer = chunk.stream(new, &stream.content);
+                for (key, value) in stream.dict.iter() {
+                    if key.as_slice() != b"Length" {
+                        copy_object(writer.insert(Name(key)), value, &mut copier)?;
+                    }
+                }
+            }
+            Ok(object) => copy_object(chunk.indirect(new), object, &mut copier)?,
+            Err(_) => chunk.indirect(new).primitive(pdf_writer::Null),
+        }
+    }
+
+    // Map the page onto the unit square with the origin in the bottom-left
+    // corner, which is how image XObjects are placed.
+    let unit = Transform {
+        sx: Ratio::new(1.0 / pdf.width()),
+        sy: Ratio::new(-1.0 / pdf.height()),
+        ty: Abs::pt(1.0),
+        ..Transform::identity()
+    };
+    let ts = unit.pre_concat(pdf.transform());
+
+    let [x0, y0, x1, y1] = pdf.bbox();
+    let content = document.get_page_content(page_id).unwrap_or_default();
+    let data = deflate(&content);
+    let mut form = chunk.form_xobject(form_ref, &data);
+    form.filter(Filter::FlateDecode);
+    form.bbox(Rect::new(x0 as f32, y0 as f32, x1 as f32, y1 as f32));
+    form.matrix([
+        ts.sx.get() as f32,
+        ts.ky.get() as f32,
+        ts.kx.get() as f32,
+        ts.sy.get() as f32,
+        ts.tx.to_pt() as f32,
+        ts.ty.to_pt() as f32,
+    ]);
+    if let Some(resources_ref) = resources_ref {
+        form.pair(Name(b"Resources"), resources_ref);
+    }
+    form.finish();
+
+    Ok((chunk, form_ref))
+}
+
+/// The resource dictionary of a page, which may be inherited from the page
+/// tree.
+fn page_resources(
+    document: &lopdf::Document,
+    mut id: lopdf::ObjectId,
+) -> Option<&lopdf::Dictionary> {
+    for _ in 0..64 {
+        let dict = document.get_dictionary(id).ok()?;
+        if let Ok(object) = dict.get(b"Resources") {
+            return match object {
+                lopdf::Object::Reference(id) => document.get_dictionary(*id).ok(),
+                object => object.as_dict().ok(),
+            };
+        }
+        id = dict.get(b"Parent").and_then(lopdf::Object::as_reference).ok()?;
+    }
+    None
+}
+
+/// Assigns new references to the objects of an embedded PDF document.
+struct Copier<'a> {
+    /// The document the objects are copied from.
+    document: &'a lopdf::Document,
+    /// The next reference to assign.
+    next: Ref,
+    /// The references assigned to the objects of the document so far.
+    refs: HashMap<lopdf::ObjectId, Ref>,
+    /// Objects that have been assigned a reference, but not yet written.
+    queue: Vec<(lopdf::ObjectId, Ref)>,
+}
+
+impl Copier<'_> {
+    /// The new reference of an object, which is scheduled to be written if
+    /// it is seen for the first time.
+    fn map(&mut self, id: lopdf::ObjectId) -> Ref {
+        *self.refs.entry(id).or_insert_with(|| {
+            let new = self.next.bump();
+            self.queue.push((id, new));
+            new
+        })
+    }
+}
+
+/// Write an object of an embedded PDF document, replacing its references.
+fn copy_object(obj: Obj, object: &lopdf::Object, copier: &mut Copier) -> StrResult<()> {
+    use lopdf::Object;
+    match object {
+        Object::Null | Object::Stream(_) => obj.primitive(pdf_writer::Null),
+        Object::Boolean(v) => obj.primitive(*v),
+        Object::Integer(v) => {
+            let v = i32::try_from(*v).map_err(|_| {
+                eco_format!("PDF page contains an integer that is out of range ({v})")
+            })?;
+            obj.primitive(v)
+        }
+        Object::Real(v) => obj.primitive(*v),
+        Object::Name(name) => obj.primitive(Name(name)),
+        Object::String(string, _) => obj.primitive(Str(string)),
+        Object::Reference(id) => obj.primitive(copier.map(*id)),
+        Object::Array(items) => {
+            let mut array = obj.array();
+            for item in items {
+                copy_object(array.push(), item, copier)?;
+            }
+        }
+        Object::Dictionary(dict) => {
+            let mut writer = obj.dict();
+            for (key, value) in dict.iter() {
+                copy_object(writer.insert(Name(key)), value, copier)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// A pre-encoded image.
 pub enum EncodedImage {
     /// A pre-encoded rasterized image.
@@ -206,4 +401,8 @@ pub enum EncodedImage {
     ///
     /// The chunk is the SVG converted to PDF objects.
     Svg(Chunk, Ref),
+    /// A page of a PDF document.
+    ///
+    /// The chunk contains the page as a form XObject and all objects it uses.
+    Pdf(Chunk, Ref),
 }
@@ -1,5 +1,6 @@
 //! Exporting of Typst documents into PDFs.
 
+mod attachment;
 mod catalog;
 mod color;
 mod color_font;
@@ -7,6 +8,7 @@ mod content;
 mod extg;
 mod font;
 mod gradient;
+mod group;
 mod image;
 mod named_destination;
 mod outline;
@@ -14,15 +16,19 @@ mod page;
 mod pattern;
 mod resources;
 
+pub use self::attachment::PdfAttachment;
+pub use self::font::FontPolicy;
+
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::ops::{Deref, DerefMut};
 
 use base64::Engine;
 use pdf_writer::{Chunk, Pdf, Ref};
+use typst::diag::SourceResult;
 use typst::foundations::{Datetime, Smart};
-use typst::layout::{Abs, Em, PageRanges, Transform};
-use typst::model::Document;
+use typst::layout::{Abs, Em, ExportTarget, PageRanges, Transform};
+use typst::model::{Document, DocumentExporter};
 use typst::text::Font;
 use typst::utils::Deferred;
 use typst::visualize::Image;
@@ -31,9 +37,10 @@ use crate::catalog::write_catalog;
 use crate::color::{alloc_color_functions_refs, ColorFunctionRefs};
 use crate::color_font::{write_color_fonts, ColorFontSlice};
 use crate::extg::{write_graphic_states, ExtGState};
-use crate::font::write_fonts;
+use crate::font::{check_font_licenses, write_fonts};
 use crate::gradient::{write_gradients, PdfGradient};
-use crate::image::write_images;
+use crate::group::{write_groups, PdfGroup};
+use crate::image::{check_images, write_images};
 use crate::named_destination::{write_named_destinations, NamedDestinations};
 use crate::page::{alloc_page_refs, traverse_pages, write_page_tree, EncodedPage};
 use crate::pattern::{write_patterns, PdfPattern};
@@ -45,33 +52,19 @@ use crate::resources::{
 ///
 /// Returns the raw bytes making up the PDF file.
 ///
-/// The `ident` parameter, if given, shall be a string that uniquely and stably
-/// identifies the document. It should not change between compilations of the
-/// same document.  **If you cannot provide such a stable identifier, just pass
-/// `Smart::Auto` rather than trying to come up with one.** The CLI, for
-/// example, does not have a well-defined notion of a long-lived project and as
-/// such just passes `Smart::Auto`.
-///
-/// If an `ident` is given, the hash of it will be used to create a PDF document
-/// identifier (the identifier itself is not leaked). If `ident` is `Auto`, a
-/// hash of the document's title and author is used instead (which is reasonably
-/// unique and stable).
-///
-/// The `timestamp`, if given, is expected to be the creation date of the
-/// document as a UTC datetime. It will only be used if `set document(date: ..)`
-/// is `auto`.
-///
-/// The `page_ranges` option specifies which ranges of pages should be exported
-/// in the PDF. When `None`, all pages should be exported.
+/// The output only depends on the document and the options: Objects are
+/// written in a stable order and font subset names are derived from the fonts
+/// and glyphs. Thus, passing the same `ident` and `timestamp` (or no timestamp
+/// at all) yields byte-for-byte identical files across compilations.
 #[typst_macros::time(name = "pdf")]
-pub fn pdf(
-    document: &Document,
-    ident: Smart<&str>,
-    timestamp: Option<Datetime>,
-    page_ranges: Option<PageRanges>,
-) -> Vec<u8> {
-    PdfBuilder::new(document, page_ranges)
-        .phase(|builder| builder.run(traverse_pages))
+pub fn pdf(document: &Document, options: &PdfOptions) -> SourceResult<Vec<u8>> {
+    let document = document.for_target(ExportTarget::Pdf);
+    let builder =
+        PdfBuilder::new(&document, options.page_ranges.clone(), options.font_policy)
+            .phase(|builder| builder.run(traverse_pages));
+    check_font_licenses(&builder.state)?;
+    check_images(&builder.state)?;
+    Ok(builder
         .phase(|builder| GlobalRefs {
             color_functions: builder.run(alloc_color_functions_refs),
             pages: builder.run(alloc_page_refs),
@@ -84,11 +77,58 @@ pub fn pdf(
             images: builder.run(write_images),
             gradients: builder.run(write_gradients),
             patterns: builder.run(write_patterns),
+            groups: builder.run(write_groups),
             ext_gs: builder.run(write_graphic_states),
         })
         .phase(|builder| builder.run(write_page_tree))
         .phase(|builder| builder.run(write_resource_dictionaries))
-        .export_with(ident, timestamp, write_catalog)
+        .export_with(
+            options.ident,
+            options.timestamp,
+            |ctx, ident, timestamp, pdf, alloc| {
+                write_catalog(ctx, ident, timestamp, &options.attachments, pdf, alloc)
+            },
+        ))
+}
+
+/// Settings for PDF export.
+///
+/// The options also serve as a [`DocumentExporter`] that exports with them.
+#[derive(Debug, Default, Clone)]
+pub struct PdfOptions<'a> {
+    /// A string that uniquely and stably identifies the document, if any.
+    ///
+    /// It should not change between compilations of the same document.  **If
+    /// you cannot provide such a stable identifier, just pass `Smart::Auto`
+    /// rather than trying to come up with one.** The CLI, for example, does
+    /// not have a well-defined notion of a long-lived project and as such
+    /// passes `Smart::Auto` unless an identifier is explicitly provided.
+    ///
+    /// If an `ident` is given, the hash of it will be used to create a PDF
+    /// document identifier (the identifier itself is not leaked). If `ident`
+    /// is `Auto`, a hash of the document's title and author is used instead
+    /// (which is reasonably unique and stable).
+    pub ident: Smart<&'a str>,
+    /// The creation date of the document as a UTC datetime, if any.
+    ///
+    /// It will only be used if `set document(date: ..)` is `auto`.
+    pub timestamp: Option<Datetime>,
+    /// Which ranges of pages should be exported. When `None`, all pages are
+    /// exported.
+    pub page_ranges: Option<PageRanges>,
+    /// How fonts are embedded. Export fails if the policy respects font
+    /// licenses and a font's license forbids embedding.
+    pub font_policy: FontPolicy,
+    /// Files to embed into the PDF, which viewers list as attachments.
+    pub attachments: Vec<PdfAttachment>,
+}
+
+impl DocumentExporter for PdfOptions<'_> {
+    type Output = Vec<u8>;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        pdf(document, self)
+    }
 }
 
 /// A struct to build a PDF following a fixed succession of phases.
@@ -127,6 +167,8 @@ struct WithDocument<'a> {
     /// Page ranges to export.
     /// When `None`, all pages are exported.
     exported_pages: Option<PageRanges>,
+    /// How fonts are embedded.
+    font_policy: FontPolicy,
 }
 
 /// At this point, resources were listed, but they don't have any reference
@@ -136,6 +178,7 @@ struct WithDocument<'a> {
 struct WithResources<'a> {
     document: &'a Document,
     exported_pages: Option<PageRanges>,
+    font_policy: FontPolicy,
     /// The content of the pages encoded as PDF content streams.
     ///
     /// The pages are at the index corresponding to their page number, but they
@@ -171,6 +214,7 @@ impl<'a> From<(WithDocument<'a>, (Vec<Option<EncodedPage>>, Resources<()>))>
         Self {
             document: previous.document,
             exported_pages: previous.exported_pages,
+            font_policy: previous.font_policy,
             pages,
             resources,
         }
@@ -185,6 +229,7 @@ impl<'a> From<(WithDocument<'a>, (Vec<Option<EncodedPage>>, Resources<()>))>
 struct WithGlobalRefs<'a> {
     document: &'a Document,
     exported_pages: Option<PageRanges>,
+    font_policy: FontPolicy,
     pages: Vec<Option<EncodedPage>>,
     /// Resources are the same as in previous phases, but each dictionary now has a reference.
     resources: Resources,
@@ -197,6 +242,7 @@ impl<'a> From<(WithResources<'a>, GlobalRefs)> for WithGlobalRefs<'a> {
         Self {
             document: previous.document,
             exported_pages: previous.exported_pages,
+            font_policy: previous.font_policy,
             pages: previous.pages,
             resources: previous.resources.with_refs(&globals.resources),
             globals,
@@ -218,6 +264,8 @@ struct References {
     gradients: HashMap<PdfGradient, Ref>,
     /// The IDs of written patterns.
     patterns: HashMap<PdfPattern, Ref>,
+    /// The IDs of written transparency groups.
+    groups: HashMap<PdfGroup, Ref>,
     /// The IDs of written external graphics states.
     ext_gs: HashMap<ExtGState, Ref>,
 }
@@ -284,11 +332,15 @@ impl<'a> From<(WithRefs<'a>, Ref)> for WithEverything<'a> {
 
 impl<'a> PdfBuilder<WithDocument<'a>> {
     /// Start building a PDF for a Typst document.
-    fn new(document: &'a Document, exported_pages: Option<PageRanges>) -> Self {
+    fn new(
+        document: &'a Document,
+        exported_pages: Option<PageRanges>,
+        font_policy: FontPolicy,
+    ) -> Self {
         Self {
             alloc: Ref::new(1),
             pdf: Pdf::new(),
-            state: WithDocument { document, exported_pages },
+            state: WithDocument { document, exported_pages, font_policy },
         }
     }
 }
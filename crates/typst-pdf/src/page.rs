@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
 
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 use pdf_writer::{
     types::{ActionType, AnnotationFlags, AnnotationType, NumberingStyle},
     Filter, Finish, Name, Rect, Ref, Str,
@@ -10,7 +10,7 @@ use typst::foundations::Label;
 use typst::introspection::Location;
 use typst::layout::{Abs, Frame};
 use typst::model::{Destination, Numbering};
-use typst::text::Case;
+use typst::text::{Case, Lang};
 
 use crate::{content, AbsExt, PdfChunk, WithDocument, WithRefs, WithResources};
 use crate::{font::improve_glyph_sets, Resources};
@@ -34,10 +34,13 @@ pub fn traverse_pages(
             skipped_pages += 1;
         } else {
             let mut encoded = construct_page(&mut resources, &page.frame);
+            encoded.bleed = page.bleed;
             encoded.label = page
                 .numbering
                 .as_ref()
-                .and_then(|num| PdfPageLabel::generate(num, page.number))
+                .and_then(|num| {
+                    PdfPageLabel::generate(num, page.section, page.number, page.lang)
+                })
                 .or_else(|| {
                     // When some pages were ignored from export, we show a page label with
                     // the correct real (not logical) page number.
@@ -62,7 +65,7 @@ pub fn traverse_pages(
 fn construct_page(out: &mut Resources<()>, frame: &Frame) -> EncodedPage {
     let content = content::build(out, frame, None);
 
-    EncodedPage { content, label: None }
+    EncodedPage { content, label: None, bleed: Abs::zero() }
 }
 
 /// Allocate a reference for each exported page.
@@ -169,6 +172,11 @@ fn write_page(
     let w = page.content.size.x.to_f32();
     let h = page.content.size.y.to_f32();
     page_writer.media_box(Rect::new(0.0, 0.0, w, h));
+    if page.bleed > Abs::zero() {
+        let b = page.bleed.to_f32();
+        page_writer.bleed_box(Rect::new(0.0, 0.0, w, h));
+        page_writer.trim_box(Rect::new(b, b, w - b, h - b));
+    }
     page_writer.contents(content_id);
     page_writer.pair(Name(b"Resources"), ctx.resources.reference);
 
@@ -226,13 +234,22 @@ pub enum PdfPageLabelStyle {
 
 impl PdfPageLabel {
     /// Create a new `PdfNumbering` from a `Numbering` applied to a page
-    /// number.
-    fn generate(numbering: &Numbering, number: usize) -> Option<PdfPageLabel> {
+    /// number, which may be preceded by the number of its section. Localized
+    /// patterns are applied in the page's language.
+    fn generate(
+        numbering: &Numbering,
+        section: Option<usize>,
+        number: usize,
+        lang: Lang,
+    ) -> Option<PdfPageLabel> {
         let Numbering::Pattern(pat) = numbering else {
             return None;
         };
 
-        let (prefix, kind, case) = pat.pieces.first()?;
+        // The page number is counted by the piece after the section's.
+        let numbers: Vec<_> = section.into_iter().chain([number]).collect();
+        let (prefix, kind, case) =
+            pat.pieces.get(numbers.len() - 1).or(pat.pieces.last())?;
 
         // If there is a suffix, we cannot use the common style optimisation,
         // since PDF does not provide a suffix field.
@@ -257,7 +274,9 @@ impl PdfPageLabel {
         // spec, we use the given prefix and an offset. Otherwise, everything
         // goes into prefix.
         let prefix = if style.is_none() {
-            Some(pat.apply(&[number]))
+            Some(pat.apply(&numbers, lang))
+        } else if let Some(section) = section {
+            Some(eco_format!("{}{prefix}", pat.apply(&[section], lang)))
         } else {
             (!prefix.is_empty()).then(|| prefix.clone())
         };
@@ -294,4 +313,6 @@ impl PdfPageLabelStyle {
 pub struct EncodedPage {
     pub content: content::Encoded,
     pub label: Option<PdfPageLabel>,
+    /// How far the page extends beyond its trim box on each side.
+    pub bleed: Abs,
 }
@@ -98,9 +98,10 @@ fn register_pattern(
     }
 
     let transform = match pattern.unwrap_relative(on_text) {
-        RelativeTo::Self_ => transforms.transform,
+        RelativeTo::Self_ | RelativeTo::Stroke => transforms.transform,
         RelativeTo::Parent => transforms.container_transform,
-    };
+    }
+    .pre_concat(pattern.transform());
 
     // Render the body.
     let content = content::build(&mut patterns.resources, pattern.frame(), None);
@@ -11,13 +11,15 @@ use std::hash::Hash;
 
 use ecow::{eco_format, EcoString};
 use pdf_writer::{Dict, Finish, Name, Ref};
+use typst::diag::StrResult;
+use typst::syntax::Span;
 use typst::text::Lang;
 use typst::{text::Font, utils::Deferred, visualize::Image};
 
 use crate::{
     color::ColorSpaces, color_font::ColorFontMap, extg::ExtGState, gradient::PdfGradient,
-    image::EncodedImage, pattern::PatternRemapper, PdfChunk, Renumber, WithEverything,
-    WithResources,
+    group::GroupRemapper, image::EncodedImage, pattern::PatternRemapper, PdfChunk,
+    Renumber, WithEverything, WithResources,
 };
 
 /// All the resources that have been collected when traversing the document.
@@ -25,12 +27,13 @@ use crate::{
 /// This does not allocate references to resources, only track what was used
 /// and deduplicate what can be deduplicated.
 ///
-/// You may notice that this structure is a tree: [`PatternRemapper`] and
-/// [`ColorFontMap`] (that are present in the fields of [`Resources`]),
-/// themselves contain [`Resources`] (that will be called "sub-resources" from
-/// now on). Because color glyphs and patterns are defined using content
-/// streams, just like pages, they can refer to resources too, which are tracked
-/// by the respective sub-resources.
+/// You may notice that this structure is a tree: [`PatternRemapper`],
+/// [`GroupRemapper`] and [`ColorFontMap`] (that are present in the fields of
+/// [`Resources`]), themselves contain [`Resources`] (that will be called
+/// "sub-resources" from now on). Because color glyphs, patterns and
+/// transparency groups are defined using content streams, just like pages,
+/// they can refer to resources too, which are tracked by the respective
+/// sub-resources.
 ///
 /// Each instance of this structure will become a `/Resources` dictionary in
 /// the final PDF. It is not possible to use a single shared dictionary for all
@@ -56,12 +59,15 @@ pub struct Resources<R = Ref> {
     pub fonts: Remapper<Font>,
     /// Deduplicates images used across the document.
     pub images: Remapper<Image>,
-    /// Handles to deferred image conversions.
-    pub deferred_images: HashMap<usize, Deferred<EncodedImage>>,
+    /// Handles to deferred image conversions, along with the span of the
+    /// first use of the image.
+    pub deferred_images: HashMap<usize, (Deferred<StrResult<EncodedImage>>, Span)>,
     /// Deduplicates gradients used across the document.
     pub gradients: Remapper<PdfGradient>,
     /// Deduplicates patterns used across the document.
     pub patterns: Option<Box<PatternRemapper<R>>>,
+    /// Deduplicates transparency groups used across the document.
+    pub groups: Option<Box<GroupRemapper<R>>>,
     /// Deduplicates external graphics states used across the document.
     pub ext_gs: Remapper<ExtGState>,
     /// Deduplicates color glyphs.
@@ -82,6 +88,8 @@ pub struct Resources<R = Ref> {
     /// PDF's /ToUnicode map for glyphs that don't have an entry in the font's
     /// cmap. This is important for copy-paste and searching.
     pub glyph_sets: HashMap<Font, BTreeMap<u16, EcoString>>,
+    /// The span of the first glyph that was set in each font.
+    pub font_spans: HashMap<Font, Span>,
 }
 
 impl<R: Renumber> Renumber for Resources<R> {
@@ -95,6 +103,10 @@ impl<R: Renumber> Renumber for Resources<R> {
         if let Some(patterns) = &mut self.patterns {
             patterns.resources.renumber(offset);
         }
+
+        if let Some(groups) = &mut self.groups {
+            groups.resources.renumber(offset);
+        }
     }
 }
 
@@ -108,10 +120,12 @@ impl Default for Resources<()> {
             deferred_images: HashMap::new(),
             gradients: Remapper::new("Gr"),
             patterns: None,
+            groups: None,
             ext_gs: Remapper::new("Gs"),
             color_fonts: None,
             languages: BTreeMap::new(),
             glyph_sets: HashMap::new(),
+            font_spans: HashMap::new(),
         }
     }
 }
@@ -131,6 +145,10 @@ impl Resources<()> {
                 .patterns
                 .zip(refs.patterns.as_ref())
                 .map(|(p, r)| Box::new(p.with_refs(r))),
+            groups: self
+                .groups
+                .zip(refs.groups.as_ref())
+                .map(|(g, r)| Box::new(g.with_refs(r))),
             ext_gs: self.ext_gs,
             color_fonts: self
                 .color_fonts
@@ -138,6 +156,7 @@ impl Resources<()> {
                 .map(|(c, r)| Box::new(c.with_refs(r))),
             languages: self.languages,
             glyph_sets: self.glyph_sets,
+            font_spans: self.font_spans,
         }
     }
 }
@@ -156,6 +175,9 @@ impl<R> Resources<R> {
         if let Some(patterns) = &self.patterns {
             patterns.resources.traverse(process)
         }
+        if let Some(groups) = &self.groups {
+            groups.resources.traverse(process)
+        }
     }
 }
 
@@ -167,6 +189,7 @@ pub struct ResourcesRefs {
     pub reference: Ref,
     pub color_fonts: Option<Box<ResourcesRefs>>,
     pub patterns: Option<Box<ResourcesRefs>>,
+    pub groups: Option<Box<ResourcesRefs>>,
 }
 
 impl Renumber for ResourcesRefs {
@@ -178,6 +201,9 @@ impl Renumber for ResourcesRefs {
         if let Some(patterns) = &mut self.patterns {
             patterns.renumber(offset);
         }
+        if let Some(groups) = &mut self.groups {
+            groups.renumber(offset);
+        }
     }
 }
 
@@ -196,6 +222,10 @@ pub fn alloc_resources_refs(context: &WithResources) -> (PdfChunk, ResourcesRefs
                 .patterns
                 .as_ref()
                 .map(|p| Box::new(refs_for(&p.resources, chunk))),
+            groups: resources
+                .groups
+                .as_ref()
+                .map(|g| Box::new(refs_for(&g.resources, chunk))),
         }
     }
 
@@ -209,7 +239,8 @@ pub fn alloc_resources_refs(context: &WithResources) -> (PdfChunk, ResourcesRefs
 /// to the root node of the page tree because using the resource inheritance
 /// feature breaks PDF merging with Apple Preview.
 ///
-/// Also write resource dictionaries for Type3 fonts and patterns.
+/// Also write resource dictionaries for Type3 fonts, patterns and transparency
+/// groups.
 pub fn write_resource_dictionaries(ctx: &WithEverything) -> (PdfChunk, ()) {
     let mut chunk = PdfChunk::new();
     let mut used_color_spaces = ColorSpaces::default();
@@ -236,9 +267,12 @@ pub fn write_resource_dictionaries(ctx: &WithEverything) -> (PdfChunk, ()) {
             to_items: color_font_slices,
         };
 
-        resources
-            .images
-            .write(&ctx.references.images, &mut chunk.indirect(images_ref).dict());
+        let mut x_objects_dict = chunk.indirect(images_ref).dict();
+        resources.images.write(&ctx.references.images, &mut x_objects_dict);
+        if let Some(g) = &resources.groups {
+            g.remapper.write(&ctx.references.groups, &mut x_objects_dict);
+        }
+        x_objects_dict.finish();
 
         let mut patterns_dict = chunk.indirect(patterns_ref).dict();
         resources
@@ -4,16 +4,18 @@ use image::imageops::FilterType;
 use image::{GenericImageView, Rgba};
 use tiny_skia as sk;
 use typst::layout::Size;
+use typst::syntax::Span;
 use typst::visualize::{Image, ImageKind};
 
 use crate::{AbsExt, State};
 
-/// Render a raster or SVG image into the canvas.
+/// Render a raster, SVG, or PDF image into the canvas.
 pub fn render_image(
     canvas: &mut sk::Pixmap,
     state: State,
     image: &Image,
     size: Size,
+    span: Span,
 ) -> Option<()> {
     let ts = state.transform;
     let view_width = size.x.to_f32();
@@ -34,7 +36,7 @@ pub fn render_image(
     let w = (scale_x * view_width.max(aspect * view_height)).ceil() as u32;
     let h = ((w as f32) / aspect).ceil() as u32;
 
-    let pixmap = scaled_texture(image, w, h)?;
+    let pixmap = scaled_texture(image, w, h, state.preview, span)?;
     let paint_scale_x = view_width / pixmap.width() as f32;
     let paint_scale_y = view_height / pixmap.height() as f32;
 
@@ -56,14 +58,25 @@ pub fn render_image(
 }
 
 /// Prepare a texture for an image at a scaled size.
+///
+/// In preview mode, raster images are resized with a cheap filter.
 #[comemo::memoize]
-fn scaled_texture(image: &Image, w: u32, h: u32) -> Option<Arc<sk::Pixmap>> {
+fn scaled_texture(
+    image: &Image,
+    w: u32,
+    h: u32,
+    preview: bool,
+    span: Span,
+) -> Option<Arc<sk::Pixmap>> {
     let mut pixmap = sk::Pixmap::new(w, h)?;
     match image.kind() {
         ImageKind::Raster(raster) => {
             let downscale = w < raster.width();
-            let filter =
-                if downscale { FilterType::Lanczos3 } else { FilterType::CatmullRom };
+            let filter = match (preview, downscale) {
+                (true, _) => FilterType::Triangle,
+                (false, true) => FilterType::Lanczos3,
+                (false, false) => FilterType::CatmullRom,
+            };
             let buf = raster.dynamic().resize(w, h, filter);
             for ((_, _, src), dest) in buf.pixels().zip(pixmap.pixels_mut()) {
                 let Rgba([r, g, b, a]) = src;
@@ -80,6 +93,15 @@ fn scaled_texture(image: &Image, w: u32, h: u32) -> Option<Arc<sk::Pixmap>> {
             );
             resvg::render(tree, ts, &mut pixmap.as_mut())
         }
+        ImageKind::Pdf(pdf) => {
+            let frame = pdf.frame(span);
+            let scale_x = w as f32 / frame.width().to_f32();
+            let scale_y = h as f32 / frame.height().to_f32();
+            let ts = sk::Transform::from_scale(scale_x, scale_y);
+            let state =
+                State::new(frame.size(), ts, scale_x.max(scale_y)).with_preview(preview);
+            crate::render_frame(&mut pixmap, state, &frame);
+        }
     }
     Some(Arc::new(pixmap))
 }
@@ -0,0 +1,65 @@
+use typst::layout::{ExportTarget, Frame};
+use typst::visualize::{Color, Ink, Recolor};
+
+/// The estimated ink coverage of a page.
+///
+/// All values are fractions of the page's area, where `1.0` means that the
+/// whole page is covered with the channel at full strength.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct InkCoverage {
+    /// The average coverage of the cyan channel.
+    pub cyan: f64,
+    /// The average coverage of the magenta channel.
+    pub magenta: f64,
+    /// The average coverage of the yellow channel.
+    pub yellow: f64,
+    /// The average coverage of the black channel.
+    pub black: f64,
+    /// The highest total coverage of all four channels at any point, between
+    /// `0.0` and `4.0`. Presses usually limit this to around `3.0`.
+    pub max_total: f64,
+}
+
+impl InkCoverage {
+    /// The average total coverage of all four channels.
+    pub fn total(&self) -> f64 {
+        self.cyan + self.magenta + self.yellow + self.black
+    }
+}
+
+/// Estimate the ink coverage of a frame.
+///
+/// Each paint in the frame is [separated](Recolor::Separation) into the
+/// amounts of the four inks, so that CMYK colors contribute exactly the ink
+/// they specify and other colors are converted the same way as elsewhere in
+/// Typst. The separations are then rendered in
+/// [preview mode](crate::render_preview) onto white paper at the given
+/// resolution to find out which areas they cover. Content is included as in
+/// PDF exports, since those are what is printed. The actual coverage depends
+/// on the press's color profile, so this is only an estimate.
+#[typst_macros::time(name = "ink coverage")]
+pub fn ink_coverage(frame: &Frame, pixel_per_pt: f32) -> InkCoverage {
+    let frame = frame.for_target(ExportTarget::Pdf);
+    let inks = [Ink::Cyan, Ink::Magenta, Ink::Yellow, Ink::Black];
+
+    let mut sums = [0.0; 4];
+    let mut totals = vec![];
+    for (ink, sum) in inks.into_iter().zip(&mut sums) {
+        let plate = Recolor::Separation(ink).frame(&frame);
+        let canvas = crate::render_impl(&plate, pixel_per_pt, Color::WHITE, true);
+        totals.resize(canvas.pixels().len(), 0.0);
+
+        // The plate is gray and opaque, so any channel of a pixel tells how
+        // much ink it receives.
+        for (pixel, total) in canvas.pixels().iter().zip(&mut totals) {
+            let amount = 1.0 - f64::from(pixel.red()) / 255.0;
+            *sum += amount;
+            *total += amount;
+        }
+    }
+
+    let count = totals.len().max(1) as f64;
+    let max_total = totals.iter().copied().fold(0.0, f64::max);
+    let [cyan, magenta, yellow, black] = sums.map(|sum| sum / count);
+    InkCoverage { cyan, magenta, yellow, black, max_total }
+}
@@ -1,16 +1,21 @@
 //! Rendering of Typst documents into raster images.
 
 mod image;
+mod ink;
 mod paint;
 mod shape;
 mod text;
 
+pub use self::ink::{ink_coverage, InkCoverage};
+
 use tiny_skia as sk;
+use typst::diag::SourceResult;
 use typst::layout::{
-    Abs, Axes, Frame, FrameItem, FrameKind, GroupItem, Point, Size, Transform,
+    Abs, Axes, ExportTarget, Frame, FrameItem, FrameKind, GroupItem, Point, Ratio, Size,
+    Transform,
 };
-use typst::model::Document;
-use typst::visualize::Color;
+use typst::model::{Document, DocumentExporter};
+use typst::visualize::{BlendMode, Color};
 
 /// Export a frame into a raster image.
 ///
@@ -18,6 +23,39 @@ use typst::visualize::Color;
 /// the resulting `tiny-skia` pixel buffer.
 #[typst_macros::time(name = "render")]
 pub fn render(frame: &Frame, pixel_per_pt: f32, fill: Color) -> sk::Pixmap {
+    render_impl(&frame.for_target(ExportTarget::Png), pixel_per_pt, fill, false)
+}
+
+/// Export a frame into a raster image in fast preview mode.
+///
+/// Works like [`render`], but skips expensive effects: Gradients are
+/// approximated by a single color, images are downscaled with a cheap filter,
+/// and blurs are omitted. This is meant for low resolutions, where these
+/// effects are barely visible anyway.
+#[typst_macros::time(name = "render preview")]
+pub fn render_preview(frame: &Frame, pixel_per_pt: f32, fill: Color) -> sk::Pixmap {
+    render_impl(&frame.for_target(ExportTarget::Png), pixel_per_pt, fill, true)
+}
+
+/// Export a frame into a thumbnail.
+///
+/// The frame is rendered in [preview mode](render_preview) and scaled such
+/// that its longer side spans `max_size` pixels, for use in file-manager
+/// previews and editor sidebars.
+pub fn render_thumbnail(frame: &Frame, max_size: u32, fill: Color) -> sk::Pixmap {
+    let size = frame.size();
+    let longest = size.x.max(size.y).to_f32();
+    let pixel_per_pt = if longest > 0.0 { max_size as f32 / longest } else { 1.0 };
+    render_preview(frame, pixel_per_pt, fill)
+}
+
+/// Shared implementation of [`render`] and [`render_preview`].
+fn render_impl(
+    frame: &Frame,
+    pixel_per_pt: f32,
+    fill: Color,
+    preview: bool,
+) -> sk::Pixmap {
     let size = frame.size();
     let pxw = (pixel_per_pt * size.x.to_f32()).round().max(1.0) as u32;
     let pxh = (pixel_per_pt * size.y.to_f32()).round().max(1.0) as u32;
@@ -26,7 +64,8 @@ pub fn render(frame: &Frame, pixel_per_pt: f32, fill: Color) -> sk::Pixmap {
     canvas.fill(paint::to_sk_color(fill));
 
     let ts = sk::Transform::from_scale(pixel_per_pt, pixel_per_pt);
-    render_frame(&mut canvas, State::new(size, ts, pixel_per_pt), frame);
+    let state = State::new(size, ts, pixel_per_pt).with_preview(preview);
+    render_frame(&mut canvas, state, frame);
 
     canvas
 }
@@ -40,11 +79,25 @@ pub fn render_merged(
     frame_fill: Color,
     gap: Abs,
     gap_fill: Color,
+) -> sk::Pixmap {
+    render_merged_impl(document, pixel_per_pt, frame_fill, gap, gap_fill, false)
+}
+
+/// Shared implementation of [`render_merged`] and the preview mode of the
+/// [`RasterExporter`].
+fn render_merged_impl(
+    document: &Document,
+    pixel_per_pt: f32,
+    frame_fill: Color,
+    gap: Abs,
+    gap_fill: Color,
+    preview: bool,
 ) -> sk::Pixmap {
     let pixmaps: Vec<_> = document
         .pages
         .iter()
-        .map(|page| render(&page.frame, pixel_per_pt, frame_fill))
+        .map(|page| page.frame.for_target(ExportTarget::Png))
+        .map(|frame| render_impl(&frame, pixel_per_pt, frame_fill, preview))
         .collect();
 
     let gap = (pixel_per_pt * gap.to_f32()).round() as u32;
@@ -72,6 +125,36 @@ pub fn render_merged(
     canvas
 }
 
+/// Exports documents into single raster images, like [`render_merged`].
+#[derive(Debug, Copy, Clone)]
+pub struct RasterExporter {
+    /// The number of pixels per point.
+    pub pixel_per_pt: f32,
+    /// The background of the individual pages.
+    pub fill: Color,
+    /// The gap between the individual pages.
+    pub gap: Abs,
+    /// The color of the gap between the individual pages.
+    pub gap_fill: Color,
+    /// Whether to render in fast [preview mode](render_preview).
+    pub preview: bool,
+}
+
+impl DocumentExporter for RasterExporter {
+    type Output = sk::Pixmap;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        Ok(render_merged_impl(
+            document,
+            self.pixel_per_pt,
+            self.fill,
+            self.gap,
+            self.gap_fill,
+            self.preview,
+        ))
+    }
+}
+
 /// Additional metadata carried through the rendering process.
 #[derive(Clone, Copy, Default)]
 struct State<'a> {
@@ -85,6 +168,8 @@ struct State<'a> {
     pixel_per_pt: f32,
     /// The size of the first hard frame in the hierarchy.
     size: Size,
+    /// Whether to skip expensive effects.
+    preview: bool,
 }
 
 impl<'a> State<'a> {
@@ -131,6 +216,11 @@ impl<'a> State<'a> {
         }
     }
 
+    /// Sets whether to skip expensive effects.
+    fn with_preview(self, preview: bool) -> Self {
+        Self { preview, ..self }
+    }
+
     /// Sets the size of the first hard frame in the hierarchy.
     fn with_size(self, size: Size) -> Self {
         Self { size, ..self }
@@ -158,8 +248,14 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
             FrameItem::Shape(shape, _) => {
                 shape::render_shape(canvas, state.pre_translate(*pos), shape);
             }
-            FrameItem::Image(image, size, _) => {
-                image::render_image(canvas, state.pre_translate(*pos), image, *size);
+            FrameItem::Image(image, size, span) => {
+                image::render_image(
+                    canvas,
+                    state.pre_translate(*pos),
+                    image,
+                    *size,
+                    *span,
+                );
             }
             FrameItem::Link(_, _) => {}
             FrameItem::Tag(_) => {}
@@ -178,7 +274,7 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
             .pre_concat_container(
                 state
                     .transform
-                    .post_concat(state.container_transform.invert().unwrap()),
+                    .post_concat(state.container_transform.invert().unwrap_or_default()),
             )
             .pre_concat_container(to_sk_transform(&Transform::translate(pos.x, pos.y)))
             .pre_concat_container(sk_transform)
@@ -222,7 +318,47 @@ fn render_group(canvas: &mut sk::Pixmap, state: State, pos: Point, group: &Group
         }
     }
 
-    render_frame(canvas, state.with_mask(mask), &group.frame);
+    if group.opacity == Ratio::one() && group.blend_mode == BlendMode::Normal {
+        render_frame(canvas, state.with_mask(mask), &group.frame);
+        return;
+    }
+
+    // Render the group into a separate layer so that it is composited as a
+    // whole.
+    let Some(mut layer) = sk::Pixmap::new(canvas.width(), canvas.height()) else {
+        return;
+    };
+
+    render_frame(&mut layer, state.with_mask(mask), &group.frame);
+
+    let paint = sk::PixmapPaint {
+        opacity: group.opacity.get() as f32,
+        blend_mode: to_sk_blend_mode(group.blend_mode),
+        quality: sk::FilterQuality::Nearest,
+    };
+
+    canvas.draw_pixmap(0, 0, layer.as_ref(), &paint, sk::Transform::identity(), None);
+}
+
+fn to_sk_blend_mode(blend_mode: BlendMode) -> sk::BlendMode {
+    match blend_mode {
+        BlendMode::Normal => sk::BlendMode::SourceOver,
+        BlendMode::Multiply => sk::BlendMode::Multiply,
+        BlendMode::Screen => sk::BlendMode::Screen,
+        BlendMode::Overlay => sk::BlendMode::Overlay,
+        BlendMode::Darken => sk::BlendMode::Darken,
+        BlendMode::Lighten => sk::BlendMode::Lighten,
+        BlendMode::ColorDodge => sk::BlendMode::ColorDodge,
+        BlendMode::ColorBurn => sk::BlendMode::ColorBurn,
+        BlendMode::HardLight => sk::BlendMode::HardLight,
+        BlendMode::SoftLight => sk::BlendMode::SoftLight,
+        BlendMode::Difference => sk::BlendMode::Difference,
+        BlendMode::Exclusion => sk::BlendMode::Exclusion,
+        BlendMode::Hue => sk::BlendMode::Hue,
+        BlendMode::Saturation => sk::BlendMode::Saturation,
+        BlendMode::Color => sk::BlendMode::Color,
+        BlendMode::Luminosity => sk::BlendMode::Luminosity,
+    }
 }
 
 fn to_sk_transform(transform: &Transform) -> sk::Transform {
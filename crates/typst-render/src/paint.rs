@@ -1,10 +1,10 @@
 use std::sync::Arc;
 
 use tiny_skia as sk;
-use typst::layout::{Axes, Point, Ratio, Size};
+use typst::layout::{Abs, Axes, Point, Ratio, Size, Transform};
 use typst::visualize::{Color, Gradient, Paint, Pattern, RelativeTo};
 
-use crate::{AbsExt, State};
+use crate::{to_sk_transform, AbsExt, State};
 
 /// Trait for sampling of a paint, used as a generic
 /// abstraction over solid colors and gradients.
@@ -39,14 +39,15 @@ impl<'a> GradientSampler<'a> {
     ) -> Self {
         let relative = gradient.unwrap_relative(on_text);
         let container_size = match relative {
-            RelativeTo::Self_ => item_size,
+            RelativeTo::Self_ | RelativeTo::Stroke => item_size,
             RelativeTo::Parent => state.size,
         };
 
         let fill_transform = match relative {
-            RelativeTo::Self_ => sk::Transform::identity(),
-            RelativeTo::Parent => state.container_transform.invert().unwrap(),
-        };
+            RelativeTo::Self_ | RelativeTo::Stroke => sk::Transform::identity(),
+            RelativeTo::Parent => state.container_transform.invert().unwrap_or_default(),
+        }
+        .post_concat(to_sk_transform(&gradient.transform().invert().unwrap_or_default()));
 
         Self {
             gradient,
@@ -93,8 +94,8 @@ impl<'a> PatternSampler<'a> {
     ) -> Self {
         let relative = pattern.unwrap_relative(on_text);
         let fill_transform = match relative {
-            RelativeTo::Self_ => sk::Transform::identity(),
-            RelativeTo::Parent => state.container_transform.invert().unwrap(),
+            RelativeTo::Self_ | RelativeTo::Stroke => sk::Transform::identity(),
+            RelativeTo::Parent => state.container_transform.invert().unwrap_or_default(),
         };
 
         Self {
@@ -144,17 +145,20 @@ pub fn to_sk_paint<'a>(
         width: u32,
         height: u32,
         gradient_map: Option<(Point, Axes<Ratio>)>,
+        inverse: Transform,
     ) -> Arc<sk::Pixmap> {
         let (offset, scale) =
             gradient_map.unwrap_or_else(|| (Point::zero(), Axes::splat(Ratio::one())));
         let mut pixmap = sk::Pixmap::new(width.max(1), height.max(1)).unwrap();
         for x in 0..width {
             for y in 0..height {
+                let point = Point::new(
+                    Abs::pt((x as f64 + offset.x.to_pt()) * scale.x.get()),
+                    Abs::pt((y as f64 + offset.y.to_pt()) * scale.y.get()),
+                )
+                .transform(inverse);
                 let color = gradient.sample_at(
-                    (
-                        (x as f32 + offset.x.to_f32()) * scale.x.get() as f32,
-                        (y as f32 + offset.y.to_f32()) * scale.y.get() as f32,
-                    ),
+                    (point.x.to_f32(), point.y.to_f32()),
                     (width as f32, height as f32),
                 );
 
@@ -168,6 +172,10 @@ pub fn to_sk_paint<'a>(
 
     let mut sk_paint: sk::Paint<'_> = sk::Paint::default();
     match paint {
+        Paint::Gradient(gradient) if state.preview => {
+            sk_paint.set_color(to_sk_color(approximate(gradient)));
+            sk_paint.anti_alias = true;
+        }
         Paint::Solid(color) => {
             sk_paint.set_color(to_sk_color(*color));
             sk_paint.anti_alias = true;
@@ -175,26 +183,37 @@ pub fn to_sk_paint<'a>(
         Paint::Gradient(gradient) => {
             let relative = gradient.unwrap_relative(on_text);
             let container_size = match relative {
-                RelativeTo::Self_ => item_size,
+                RelativeTo::Self_ | RelativeTo::Stroke => item_size,
                 RelativeTo::Parent => state.size,
             };
 
             let fill_transform = match relative {
-                RelativeTo::Self_ => fill_transform.unwrap_or_default(),
+                RelativeTo::Self_ | RelativeTo::Stroke => {
+                    fill_transform.unwrap_or_default()
+                }
                 RelativeTo::Parent => state
                     .container_transform
-                    .post_concat(state.transform.invert().unwrap()),
+                    .post_concat(state.transform.invert().unwrap_or_default()),
             };
             let width =
                 (container_size.x.to_f32().abs() * state.pixel_per_pt).ceil() as u32;
             let height =
                 (container_size.y.to_f32().abs() * state.pixel_per_pt).ceil() as u32;
 
+            // The gradient's own transform is given in points, but the pixmap
+            // is sampled in pixels.
+            let ppp = Ratio::new(state.pixel_per_pt as f64);
+            let pt_per_pixel = Ratio::new(1.0 / state.pixel_per_pt as f64);
+            let inverse = Transform::scale(ppp, ppp)
+                .pre_concat(gradient.transform().invert().unwrap_or_default())
+                .pre_concat(Transform::scale(pt_per_pixel, pt_per_pixel));
+
             *pixmap = Some(cached(
                 gradient,
                 width.max(state.pixel_per_pt.ceil() as u32),
                 height.max(state.pixel_per_pt.ceil() as u32),
                 gradient_map,
+                inverse,
             ));
 
             // We can use FilterQuality::Nearest here because we're
@@ -216,11 +235,14 @@ pub fn to_sk_paint<'a>(
             let relative = pattern.unwrap_relative(on_text);
 
             let fill_transform = match relative {
-                RelativeTo::Self_ => fill_transform.unwrap_or_default(),
+                RelativeTo::Self_ | RelativeTo::Stroke => {
+                    fill_transform.unwrap_or_default()
+                }
                 RelativeTo::Parent => state
                     .container_transform
-                    .post_concat(state.transform.invert().unwrap()),
-            };
+                    .post_concat(state.transform.invert().unwrap_or_default()),
+            }
+            .pre_concat(to_sk_transform(&pattern.transform()));
 
             let canvas = render_pattern_frame(&state, pattern);
             *pixmap = Some(Arc::new(canvas));
@@ -240,6 +262,11 @@ pub fn to_sk_paint<'a>(
     sk_paint
 }
 
+/// Approximate a gradient by a single color, for preview mode.
+pub fn approximate(gradient: &Gradient) -> Color {
+    gradient.sample_at((0.5, 0.5), (1.0, 1.0))
+}
+
 pub fn to_sk_color(color: Color) -> sk::Color {
     let [r, g, b, a] = color.to_rgb().to_vec4();
     sk::Color::from_rgba(r, g, b, a)
@@ -261,7 +288,8 @@ pub fn render_pattern_frame(state: &State, pattern: &Pattern) -> sk::Pixmap {
 
     // Render the pattern into a new canvas.
     let ts = sk::Transform::from_scale(state.pixel_per_pt, state.pixel_per_pt);
-    let temp_state = State::new(pattern.size(), ts, state.pixel_per_pt);
+    let temp_state =
+        State::new(pattern.size(), ts, state.pixel_per_pt).with_preview(state.preview);
     crate::render_frame(&mut canvas, temp_state, pattern.frame());
     canvas
 }
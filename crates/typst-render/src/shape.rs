@@ -8,6 +8,15 @@ use crate::{paint, AbsExt, State};
 
 /// Render a geometrical shape into the canvas.
 pub fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Option<()> {
+    // Blurs are skipped in preview mode.
+    if shape.blur > Abs::zero() {
+        if state.preview {
+            let sharp = Shape { blur: Abs::zero(), ..shape.clone() };
+            return render_shape(canvas, state, &sharp);
+        }
+        return render_blurred_shape(canvas, state, shape);
+    }
+
     let ts = state.transform;
     let path = match shape.geometry {
         Geometry::Line(target) => {
@@ -91,6 +100,21 @@ pub fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Opt
                     )
                 });
 
+            // A gradient along the stroke is laid out in its own rectangle.
+            let along = shape.stroke_along_path();
+            let (paint, offset_bbox, fill_transform, gradient_map) = match &along {
+                Some((stroke, origin, size)) => (
+                    &stroke.paint,
+                    *size,
+                    Some(sk::Transform::from_translate(
+                        origin.x.to_f32(),
+                        origin.y.to_f32(),
+                    )),
+                    None,
+                ),
+                None => (paint, offset_bbox, fill_transform, gradient_map),
+            };
+
             let mut pixmap = None;
             let paint = paint::to_sk_paint(
                 paint,
@@ -115,6 +139,140 @@ pub fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Opt
     Some(())
 }
 
+/// Render a shape with a gaussian blur.
+///
+/// The shape is drawn sharply into an offscreen pixmap that covers the
+/// shape's blur region, which is then blurred and composited onto the canvas.
+fn render_blurred_shape(
+    canvas: &mut sk::Pixmap,
+    state: State,
+    shape: &Shape,
+) -> Option<()> {
+    let ts = state.transform;
+    let (min, max) = shape.blur_bounds();
+    let rect = sk::Rect::from_ltrb(
+        min.x.to_f32(),
+        min.y.to_f32(),
+        max.x.to_f32(),
+        max.y.to_f32(),
+    )?
+    .transform(ts)?;
+
+    // Only the part of the region near the canvas can affect it.
+    let scale = (ts.sx * ts.sy - ts.kx * ts.ky).abs().sqrt();
+    let std_dev = shape.blur.to_f32() / 2.0 * scale;
+    let margin = 3.0 * std_dev;
+    let x0 = rect.left().max(-margin).floor();
+    let y0 = rect.top().max(-margin).floor();
+    let x1 = rect.right().min(canvas.width() as f32 + margin).ceil();
+    let y1 = rect.bottom().min(canvas.height() as f32 + margin).ceil();
+    if x1 <= x0 || y1 <= y0 {
+        return Some(());
+    }
+
+    let mut pixmap = sk::Pixmap::new((x1 - x0) as u32, (y1 - y0) as u32)?;
+    let offset = sk::Transform::from_translate(-x0, -y0);
+    let sharp = Shape { blur: Abs::zero(), ..shape.clone() };
+    let offscreen = State {
+        transform: offset.pre_concat(state.transform),
+        container_transform: offset.pre_concat(state.container_transform),
+        mask: None,
+        ..state
+    };
+    render_shape(&mut pixmap, offscreen, &sharp)?;
+    blur(&mut pixmap, std_dev);
+
+    canvas.draw_pixmap(
+        x0 as i32,
+        y0 as i32,
+        pixmap.as_ref(),
+        &sk::PixmapPaint::default(),
+        sk::Transform::identity(),
+        state.mask,
+    );
+
+    Some(())
+}
+
+/// Approximate a gaussian blur with the given standard deviation (in pixels)
+/// by three successive box blurs.
+fn blur(pixmap: &mut sk::Pixmap, std_dev: f32) {
+    let width = pixmap.width() as usize;
+    let height = pixmap.height() as usize;
+    let data = pixmap.data_mut();
+    let mut buf = vec![0; data.len()];
+    for radius in box_radii(std_dev) {
+        if radius == 0 {
+            continue;
+        }
+        box_blur(data, &mut buf, width, height, 4, width * 4, radius);
+        box_blur(&buf, data, height, width, width * 4, 4, radius);
+    }
+}
+
+/// Compute the radii of three box blurs that together approximate a gaussian.
+///
+/// See <https://www.peterkovesi.com/papers/FastGaussianSmoothing.pdf>.
+fn box_radii(std_dev: f32) -> [usize; 3] {
+    let n = 3.0;
+    let ideal = (12.0 * std_dev * std_dev / n + 1.0).sqrt();
+    let mut lower = ideal.floor() as i32;
+    if lower % 2 == 0 {
+        lower -= 1;
+    }
+    let lower = lower.max(1);
+    let upper = lower + 2;
+    let wl = lower as f32;
+    let m = ((12.0 * std_dev * std_dev - n * wl * wl - 4.0 * n * wl - 3.0 * n)
+        / (-4.0 * wl - 4.0))
+        .round() as i32;
+    std::array::from_fn(|i| {
+        let size = if (i as i32) < m { lower } else { upper };
+        (size as usize - 1) / 2
+    })
+}
+
+/// Blur the RGBA pixels in `src` along one axis with a box of the given
+/// radius and write the result to `dst`.
+///
+/// The pixmap consists of `lines` lines with `len` pixels each. `step` is the
+/// byte distance between neighbouring pixels on a line and `stride` the one
+/// between neighbouring lines.
+fn box_blur(
+    src: &[u8],
+    dst: &mut [u8],
+    len: usize,
+    lines: usize,
+    step: usize,
+    stride: usize,
+    radius: usize,
+) {
+    let size = (2 * radius + 1) as u32;
+    for line in 0..lines {
+        let base = line * stride;
+        let at = |i: isize| -> Option<usize> {
+            (i >= 0 && (i as usize) < len).then(|| base + i as usize * step)
+        };
+
+        for channel in 0..4 {
+            // Pixels outside of the pixmap are transparent.
+            let mut sum: u32 = (0..=radius as isize)
+                .filter_map(at)
+                .map(|i| src[i + channel] as u32)
+                .sum();
+            for i in 0..len as isize {
+                dst[base + i as usize * step + channel] = ((sum + size / 2) / size) as u8;
+                if let Some(j) = at(i + radius as isize + 1) {
+                    sum += src[j + channel] as u32;
+                }
+                if let Some(j) = at(i - radius as isize) {
+                    sum -= src[j + channel] as u32;
+                }
+            }
+        }
+    }
+}
+
 /// Convert a Typst path into a tiny-skia path.
 pub fn convert_path(path: &Path) -> Option<sk::Path> {
     let mut builder = sk::PathBuilder::new();
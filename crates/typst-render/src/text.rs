@@ -73,16 +73,18 @@ fn render_outline_glyph(
         // system is Y-up.
         let ts = ts.pre_scale(scale, -scale);
         let state_ts = state.pre_concat(sk::Transform::from_scale(scale, -scale));
-        let paint = paint::to_sk_paint(
-            &text.fill,
-            state_ts,
-            Size::zero(),
-            true,
-            None,
-            &mut pixmap,
-            None,
-        );
-        canvas.fill_path(&path, &paint, rule, ts, state.mask);
+        if let Some(fill) = &text.fill {
+            let paint = paint::to_sk_paint(
+                fill,
+                state_ts,
+                Size::zero(),
+                true,
+                None,
+                &mut pixmap,
+                None,
+            );
+            canvas.fill_path(&path, &paint, rule, ts, state.mask);
+        }
 
         if let Some(FixedStroke { paint, thickness, cap, join, dash, miter_limit }) =
             &text.stroke
@@ -130,11 +132,18 @@ fn render_outline_glyph(
         )))
     }
 
+    // Without a fill or stroke, there is nothing to draw.
+    let fill = text.fill.as_ref()?;
+
     // Try to retrieve a prepared glyph or prepare it from scratch if it
     // doesn't exist, yet.
     let bitmap =
         rasterize(&text.font, id, ts.tx.to_bits(), ts.ty.to_bits(), ppem.to_bits())?;
-    match &text.fill {
+    match fill {
+        Paint::Gradient(gradient) if state.preview => {
+            let color = paint::to_sk_color_u8(paint::approximate(gradient));
+            write_bitmap(canvas, &bitmap, &state, color.premultiply())?;
+        }
         Paint::Gradient(gradient) => {
             let sampler = GradientSampler::new(gradient, &state, Size::zero(), true);
             write_bitmap(canvas, &bitmap, &state, sampler)?;
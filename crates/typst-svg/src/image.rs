@@ -1,13 +1,32 @@
 use base64::Engine;
 use ecow::{eco_format, EcoString};
-use typst::layout::{Abs, Axes};
-use typst::visualize::{Image, ImageFormat, RasterFormat, VectorFormat};
+use typst::layout::{Abs, Axes, Ratio, Transform};
+use typst::syntax::Span;
+use typst::visualize::{Image, ImageFormat, ImageKind, RasterFormat, VectorFormat};
 
-use crate::SVGRenderer;
+use crate::{SVGRenderer, State};
 
 impl SVGRenderer {
     /// Render an image element.
-    pub(super) fn render_image(&mut self, image: &Image, size: &Axes<Abs>) {
+    pub(super) fn render_image(
+        &mut self,
+        state: State,
+        image: &Image,
+        size: &Axes<Abs>,
+        span: Span,
+    ) {
+        // Pages of PDF documents can't be referenced from SVG, so they are
+        // converted into a frame instead.
+        if let ImageKind::Pdf(pdf) = image.kind() {
+            let frame = pdf.frame(span);
+            let ts = Transform::scale(
+                Ratio::new(size.x / frame.width()),
+                Ratio::new(size.y / frame.height()),
+            );
+            self.render_frame(state.pre_concat(ts).with_size(frame.size()), ts, &frame);
+            return;
+        }
+
         let url = convert_image_to_base64_url(image);
         self.xml.start_element("image");
         self.xml.write_attribute("xlink:href", &url);
@@ -30,6 +49,7 @@ pub fn convert_image_to_base64_url(image: &Image) -> EcoString {
         },
         ImageFormat::Vector(f) => match f {
             VectorFormat::Svg => "svg+xml",
+            VectorFormat::Pdf => "pdf",
         },
     };
 
@@ -10,20 +10,24 @@ use std::fmt::{self, Display, Formatter, Write};
 
 use ecow::EcoString;
 use ttf_parser::OutlineBuilder;
+use typst::diag::SourceResult;
 use typst::layout::{
-    Abs, Frame, FrameItem, FrameKind, GroupItem, Point, Ratio, Size, Transform,
+    Abs, ExportTarget, Frame, FrameItem, FrameKind, GroupItem, Point, Ratio, Size,
+    Transform,
 };
-use typst::model::Document;
+use typst::model::{Document, DocumentExporter};
 use typst::utils::hash128;
-use typst::visualize::{Gradient, Pattern};
+use typst::visualize::{BlendMode, Gradient, Pattern};
 use xmlwriter::XmlWriter;
 
 use crate::paint::{GradientRef, PatternRef, SVGSubGradient};
+use crate::shape::SVGBlur;
 use crate::text::RenderedGlyph;
 
 /// Export a frame into a SVG file.
 #[typst_macros::time(name = "svg")]
 pub fn svg(frame: &Frame) -> String {
+    let frame = &frame.for_target(ExportTarget::Svg);
     let mut renderer = SVGRenderer::new();
     renderer.write_header(frame.size());
 
@@ -36,6 +40,7 @@ pub fn svg(frame: &Frame) -> String {
 ///
 /// The padding will be added around and between the individual frames.
 pub fn svg_merged(document: &Document, padding: Abs) -> String {
+    let document = &document.for_target(ExportTarget::Svg);
     let width = 2.0 * padding
         + document
             .pages
@@ -64,6 +69,21 @@ pub fn svg_merged(document: &Document, padding: Abs) -> String {
     renderer.finalize()
 }
 
+/// Exports documents into single SVG files, like [`svg_merged`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SvgExporter {
+    /// The padding around and between the individual pages.
+    pub padding: Abs,
+}
+
+impl DocumentExporter for SvgExporter {
+    type Output = String;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        Ok(svg_merged(document, self.padding))
+    }
+}
+
 /// Renders one or multiple frames to an SVG file.
 struct SVGRenderer {
     /// The internal XML writer.
@@ -102,6 +122,8 @@ struct SVGRenderer {
     patterns: Deduplicator<Pattern>,
     /// These are the gradients that compose a conic gradient.
     conic_subgradients: Deduplicator<SVGSubGradient>,
+    /// Gaussian blur filters applied to shapes.
+    blurs: Deduplicator<SVGBlur>,
 }
 
 /// Contextual information for rendering.
@@ -152,6 +174,7 @@ impl SVGRenderer {
             gradient_refs: Deduplicator::new('g'),
             gradients: Deduplicator::new('f'),
             conic_subgradients: Deduplicator::new('s'),
+            blurs: Deduplicator::new('b'),
             pattern_refs: Deduplicator::new('p'),
             patterns: Deduplicator::new('t'),
         }
@@ -206,7 +229,9 @@ impl SVGRenderer {
                 FrameItem::Shape(shape, _) => {
                     self.render_shape(state.pre_translate(*pos), shape)
                 }
-                FrameItem::Image(image, size, _) => self.render_image(image, size),
+                FrameItem::Image(image, size, span) => {
+                    self.render_image(state.pre_translate(*pos), image, size, *span)
+                }
                 FrameItem::Link(_, _) => unreachable!(),
                 FrameItem::Tag(_) => unreachable!(),
             };
@@ -236,6 +261,22 @@ impl SVGRenderer {
             self.xml.write_attribute_fmt("clip-path", format_args!("url(#{id})"));
         }
 
+        if group.opacity != Ratio::one() {
+            self.xml.write_attribute("opacity", &group.opacity.get());
+        }
+
+        if group.blend_mode != BlendMode::Normal {
+            self.xml.write_attribute_fmt(
+                "style",
+                format_args!("mix-blend-mode: {}", css_blend_mode(group.blend_mode)),
+            );
+        }
+
+        if let Some(alt) = &group.alt {
+            self.xml.write_attribute("role", "img");
+            self.xml.write_attribute("aria-label", alt.as_str());
+        }
+
         self.render_frame(state, group.transform, &group.frame);
         self.xml.end_element();
     }
@@ -244,6 +285,7 @@ impl SVGRenderer {
     fn finalize(mut self) -> String {
         self.write_glyph_defs();
         self.write_clip_path_defs();
+        self.write_blurs();
         self.write_gradients();
         self.write_gradient_refs();
         self.write_subgradients();
@@ -274,6 +316,28 @@ impl SVGRenderer {
     }
 }
 
+/// The name of a blend mode in CSS.
+fn css_blend_mode(blend_mode: BlendMode) -> &'static str {
+    match blend_mode {
+        BlendMode::Normal => "normal",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Overlay => "overlay",
+        BlendMode::Darken => "darken",
+        BlendMode::Lighten => "lighten",
+        BlendMode::ColorDodge => "color-dodge",
+        BlendMode::ColorBurn => "color-burn",
+        BlendMode::HardLight => "hard-light",
+        BlendMode::SoftLight => "soft-light",
+        BlendMode::Difference => "difference",
+        BlendMode::Exclusion => "exclusion",
+        BlendMode::Hue => "hue",
+        BlendMode::Saturation => "saturation",
+        BlendMode::Color => "color",
+        BlendMode::Luminosity => "luminosity",
+    }
+}
+
 /// Deduplicates its elements. It is used to deduplicate glyphs and clip paths.
 /// The `H` is the hash type, and `T` is the value type. The `PREFIX` is the
 /// prefix of the index. This is used to distinguish between glyphs and clip
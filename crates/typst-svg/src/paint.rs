@@ -1,11 +1,12 @@
 use std::f32::consts::TAU;
 
+use base64::Engine;
 use ecow::{eco_format, EcoString};
 use ttf_parser::OutlineBuilder;
 use typst::foundations::Repr;
 use typst::layout::{Angle, Axes, Frame, Quadrant, Ratio, Size, Transform};
 use typst::utils::hash128;
-use typst::visualize::{Color, Gradient, Paint, Pattern, RatioOrAngle};
+use typst::visualize::{Color, Gradient, MeshGradient, Paint, Pattern, RatioOrAngle};
 use xmlwriter::XmlWriter;
 
 use crate::{Id, SVGRenderer, State, SvgMatrix, SvgPathBuilder};
@@ -15,6 +16,9 @@ use crate::{Id, SVGRenderer, State, SvgMatrix, SvgPathBuilder};
 /// Smaller values could be interesting for optimization.
 const CONIC_SEGMENT: usize = 360;
 
+/// The number of pixels along the longer side of a rasterized mesh gradient.
+const MESH_RESOLUTION: f64 = 512.0;
+
 impl SVGRenderer {
     /// Render a frame to a string.
     pub(super) fn render_pattern_frame(
@@ -93,6 +97,7 @@ impl SVGRenderer {
         );
 
         let pattern_id = self.patterns.insert_with(hash128(pattern), || pattern.clone());
+        let ts = ts.pre_concat(pattern.transform());
         self.pattern_refs
             .insert_with(hash128(&(pattern_id, ts)), || PatternRef {
                 id: pattern_id,
@@ -227,6 +232,28 @@ impl SVGRenderer {
                         self.xml.end_element();
                     }
 
+                    // We skip the default stop generation code.
+                    self.xml.end_element();
+                    continue;
+                }
+                Gradient::Mesh(mesh) => {
+                    self.xml.start_element("pattern");
+                    self.xml.write_attribute("id", &id);
+                    self.xml.write_attribute("viewBox", "0 0 1 1");
+                    self.xml.write_attribute("preserveAspectRatio", "none");
+                    self.xml.write_attribute("patternUnits", "userSpaceOnUse");
+                    self.xml.write_attribute("width", "1");
+                    self.xml.write_attribute("height", "1");
+
+                    // SVG has no mesh gradients, so we embed a rasterized
+                    // rendition of the mesh.
+                    self.xml.start_element("image");
+                    self.xml.write_attribute("width", "1");
+                    self.xml.write_attribute("height", "1");
+                    self.xml.write_attribute("preserveAspectRatio", "none");
+                    self.xml.write_attribute("xlink:href", &rasterize_mesh(mesh, *ratio));
+                    self.xml.end_element();
+
                     // We skip the default stop generation code.
                     self.xml.end_element();
                     continue;
@@ -310,6 +337,7 @@ impl SVGRenderer {
 
             self.xml.end_element();
         }
+
         self.xml.end_element();
     }
 
@@ -336,7 +364,7 @@ impl SVGRenderer {
                         &SvgMatrix(gradient_ref.transform),
                     );
                 }
-                GradientKind::Conic => {
+                GradientKind::Conic | GradientKind::Mesh => {
                     self.xml.start_element("pattern");
                     self.xml.write_attribute(
                         "patternTransform",
@@ -476,6 +504,8 @@ enum GradientKind {
     Radial,
     /// A conic gradient.
     Conic,
+    /// A mesh gradient.
+    Mesh,
 }
 
 impl From<&Gradient> for GradientKind {
@@ -484,6 +514,7 @@ impl From<&Gradient> for GradientKind {
             Gradient::Linear { .. } => GradientKind::Linear,
             Gradient::Radial { .. } => GradientKind::Radial,
             Gradient::Conic { .. } => GradientKind::Conic,
+            Gradient::Mesh { .. } => GradientKind::Mesh,
         }
     }
 }
@@ -581,3 +612,43 @@ impl ColorEncode for Color {
 pub fn correct_pattern_pos(x: f32) -> f32 {
     (x + 0.5) / 2.0
 }
+
+/// Rasterizes a mesh gradient into a PNG image, encoded as a data URL.
+///
+/// The image is stretched over the unit square, so its resolution follows
+/// the aspect ratio of the shape it is drawn on.
+#[comemo::memoize]
+fn rasterize_mesh(mesh: &MeshGradient, ratio: Ratio) -> EcoString {
+    let ratio = Some(ratio.get()).filter(|r| r.is_finite() && *r > 0.0).unwrap_or(1.0);
+    let (width, height) = if ratio >= 1.0 {
+        (MESH_RESOLUTION, MESH_RESOLUTION / ratio)
+    } else {
+        (MESH_RESOLUTION * ratio, MESH_RESOLUTION)
+    };
+    let width = width.ceil().max(1.0) as u32;
+    let height = height.ceil().max(1.0) as u32;
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let color = mesh.sample(
+                (x as f64 + 0.5) / width as f64,
+                (y as f64 + 0.5) / height as f64,
+            );
+            pixels.extend(color.to_rgb().to_vec4_u8());
+        }
+    }
+
+    // Encoding into memory can't fail.
+    let mut data = vec![];
+    let mut encoder = png::Encoder::new(&mut data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    writer.finish().unwrap();
+
+    let mut url = EcoString::from("data:image/png;base64,");
+    url.push_str(&base64::engine::general_purpose::STANDARD.encode(&data));
+    url
+}
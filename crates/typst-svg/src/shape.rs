@@ -1,6 +1,7 @@
 use ecow::EcoString;
 use ttf_parser::OutlineBuilder;
-use typst::layout::{Abs, Ratio, Size, Transform};
+use typst::layout::{Abs, Point, Ratio, Size, Transform};
+use typst::utils::hash128;
 use typst::visualize::{
     FixedStroke, Geometry, LineCap, LineJoin, Paint, Path, PathItem, RelativeTo, Shape,
 };
@@ -24,7 +25,13 @@ impl SVGRenderer {
             self.xml.write_attribute("fill", "none");
         }
 
-        if let Some(stroke) = &shape.stroke {
+        if let Some((stroke, origin, size)) = shape.stroke_along_path() {
+            // A gradient along the stroke is laid out in its own rectangle.
+            let ts =
+                Transform::scale(Ratio::new(size.x.to_pt()), Ratio::new(size.y.to_pt()))
+                    .post_concat(Transform::translate(origin.x, origin.y));
+            self.write_stroke(&stroke, size, ts);
+        } else if let Some(stroke) = &shape.stroke {
             self.write_stroke(
                 stroke,
                 self.shape_fill_size(state, &stroke.paint, shape),
@@ -32,11 +39,44 @@ impl SVGRenderer {
             );
         }
 
+        if shape.blur > Abs::zero() {
+            let (min, max) = shape.blur_bounds();
+            let blur = SVGBlur { std_dev: shape.blur / 2.0, min, max };
+            let id = self.blurs.insert_with(hash128(&blur), || blur);
+            self.xml.write_attribute_fmt("filter", format_args!("url(#{id})"));
+        }
+
         let path = convert_geometry_to_path(&shape.geometry);
         self.xml.write_attribute("d", &path);
         self.xml.end_element();
     }
 
+    /// Build the blur filter definitions.
+    pub(super) fn write_blurs(&mut self) {
+        if self.blurs.is_empty() {
+            return;
+        }
+
+        self.xml.start_element("defs");
+        self.xml.write_attribute("id", "blurs");
+
+        for (id, blur) in self.blurs.iter() {
+            self.xml.start_element("filter");
+            self.xml.write_attribute("id", &id);
+            self.xml.write_attribute("filterUnits", "userSpaceOnUse");
+            self.xml.write_attribute("x", &blur.min.x.to_pt());
+            self.xml.write_attribute("y", &blur.min.y.to_pt());
+            self.xml.write_attribute("width", &(blur.max.x - blur.min.x).to_pt());
+            self.xml.write_attribute("height", &(blur.max.y - blur.min.y).to_pt());
+            self.xml.start_element("feGaussianBlur");
+            self.xml.write_attribute("stdDeviation", &blur.std_dev.to_pt());
+            self.xml.end_element();
+            self.xml.end_element();
+        }
+
+        self.xml.end_element();
+    }
+
     /// Calculate the transform of the shape's fill or stroke.
     fn shape_paint_transform(
         &self,
@@ -56,20 +96,22 @@ impl SVGRenderer {
 
         if let Paint::Gradient(gradient) = paint {
             match gradient.unwrap_relative(false) {
-                RelativeTo::Self_ => Transform::scale(
+                RelativeTo::Self_ | RelativeTo::Stroke => Transform::scale(
                     Ratio::new(shape_size.x.to_pt()),
                     Ratio::new(shape_size.y.to_pt()),
-                ),
+                )
+                .post_concat(gradient.transform()),
                 RelativeTo::Parent => Transform::scale(
                     Ratio::new(state.size.x.to_pt()),
                     Ratio::new(state.size.y.to_pt()),
                 )
-                .post_concat(state.transform.invert().unwrap()),
+                .post_concat(gradient.transform())
+                .post_concat(state.transform.invert().unwrap_or_default()),
             }
         } else if let Paint::Pattern(pattern) = paint {
             match pattern.unwrap_relative(false) {
-                RelativeTo::Self_ => Transform::identity(),
-                RelativeTo::Parent => state.transform.invert().unwrap(),
+                RelativeTo::Self_ | RelativeTo::Stroke => Transform::identity(),
+                RelativeTo::Parent => state.transform.invert().unwrap_or_default(),
             }
         } else {
             Transform::identity()
@@ -90,7 +132,7 @@ impl SVGRenderer {
 
         if let Paint::Gradient(gradient) = paint {
             match gradient.unwrap_relative(false) {
-                RelativeTo::Self_ => shape_size,
+                RelativeTo::Self_ | RelativeTo::Stroke => shape_size,
                 RelativeTo::Parent => state.size,
             }
         } else {
@@ -193,3 +235,16 @@ pub fn convert_path(path: &Path) -> EcoString {
     }
     builder.0
 }
+
+/// A gaussian blur filter. Since shapes are drawn in their local coordinate
+/// system, the filter region is given in user space, relative to the shape's
+/// origin.
+#[derive(Debug, Clone, Hash)]
+pub struct SVGBlur {
+    /// The standard deviation of the blur.
+    std_dev: Abs,
+    /// The top-left corner of the filter region.
+    min: Point,
+    /// The bottom-right corner of the filter region.
+    max: Point,
+}
@@ -136,11 +136,15 @@ impl SVGRenderer {
         self.xml.start_element("use");
         self.xml.write_attribute_fmt("xlink:href", format_args!("#{id}"));
         self.xml.write_attribute_fmt("x", format_args!("{x_offset}"));
-        self.write_fill(
-            &text.fill,
-            Size::new(Abs::pt(width), Abs::pt(height)),
-            self.text_paint_transform(state, &text.fill),
-        );
+        if let Some(fill) = &text.fill {
+            self.write_fill(
+                fill,
+                Size::new(Abs::pt(width), Abs::pt(height)),
+                self.text_paint_transform(state, fill),
+            );
+        } else {
+            self.xml.write_attribute("fill", "none");
+        }
         if let Some(stroke) = &text.stroke {
             self.write_stroke(
                 stroke,
@@ -157,16 +161,17 @@ impl SVGRenderer {
         match paint {
             Paint::Solid(_) => Transform::identity(),
             Paint::Gradient(gradient) => match gradient.unwrap_relative(true) {
-                RelativeTo::Self_ => Transform::identity(),
+                RelativeTo::Self_ | RelativeTo::Stroke => Transform::identity(),
                 RelativeTo::Parent => Transform::scale(
                     Ratio::new(state.size.x.to_pt()),
                     Ratio::new(state.size.y.to_pt()),
                 )
-                .post_concat(state.transform.invert().unwrap()),
+                .post_concat(gradient.transform())
+                .post_concat(state.transform.invert().unwrap_or_default()),
             },
             Paint::Pattern(pattern) => match pattern.unwrap_relative(true) {
-                RelativeTo::Self_ => Transform::identity(),
-                RelativeTo::Parent => state.transform.invert().unwrap(),
+                RelativeTo::Self_ | RelativeTo::Stroke => Transform::identity(),
+                RelativeTo::Parent => state.transform.invert().unwrap_or_default(),
             },
         }
     }
@@ -1,5 +1,6 @@
 //! Performance timing for Typst.
 
+use std::collections::HashMap;
 use std::hash::Hash;
 use std::io::Write;
 use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
@@ -45,6 +46,8 @@ struct Event {
     name: &'static str,
     /// The span of code that this event was recorded in.
     span: Option<Span>,
+    /// Whether summaries should list this scope separately for each span.
+    by_span: bool,
     /// The thread ID of this event.
     thread_id: ThreadId,
 }
@@ -62,6 +65,12 @@ pub fn enable() {
     ENABLED.store(true, Relaxed);
 }
 
+/// Disable the timer.
+#[inline]
+pub fn disable() {
+    ENABLED.store(false, Relaxed);
+}
+
 /// Whether the timer is enabled.
 #[inline]
 pub fn is_enabled() -> bool {
@@ -78,6 +87,7 @@ pub fn clear() {
 pub struct TimingScope {
     name: &'static str,
     span: Option<Span>,
+    by_span: bool,
     id: u64,
     thread_id: ThreadId,
 }
@@ -85,6 +95,20 @@ pub struct TimingScope {
 impl TimingScope {
     /// Create a new scope if timing is enabled.
     pub fn new(name: &'static str, span: Option<Span>) -> Option<Self> {
+        Self::new_impl(name, span, false)
+    }
+
+    /// Create a new scope if timing is enabled, which summaries and folded
+    /// stacks list separately for each span instead of lumping all scopes
+    /// with the same name together.
+    ///
+    /// This is meant for scopes whose span identifies what runs, like the
+    /// definition of a show rule, rather than where it runs.
+    pub fn with_span(name: &'static str, span: Span) -> Option<Self> {
+        Self::new_impl(name, Some(span), true)
+    }
+
+    fn new_impl(name: &'static str, span: Option<Span>, by_span: bool) -> Option<Self> {
         if !is_enabled() {
             return None;
         }
@@ -101,10 +125,11 @@ impl TimingScope {
             id,
             name,
             span,
+            by_span,
             thread_id,
         });
 
-        Some(TimingScope { name, span, id, thread_id })
+        Some(TimingScope { name, span, by_span, id, thread_id })
     }
 }
 
@@ -116,6 +141,7 @@ impl Drop for TimingScope {
             id: self.id,
             name: self.name,
             span: self.span,
+            by_span: self.by_span,
             thread_id: self.thread_id,
         };
 
@@ -224,3 +250,175 @@ pub fn export_json<W: Write>(
 
     Ok(())
 }
+
+/// Export data as folded stacks for flamegraph tools.
+///
+/// Each line consists of a stack of scope names separated by semicolons,
+/// followed by the time spent in the innermost scope in microseconds. This is
+/// the input format of tools like [inferno] and [speedscope]. Scopes that are
+/// [listed per span](TimingScope::with_span) carry the source location the
+/// `source` function returns for their span.
+///
+/// [inferno]: https://github.com/jonhoo/inferno
+/// [speedscope]: https://www.speedscope.app
+pub fn export_folded<W: Write>(
+    mut writer: W,
+    source: impl FnMut(Span) -> (String, u32),
+) -> Result<(), String> {
+    let mut stacks: HashMap<String, Duration> = HashMap::new();
+    for span in spans(&RECORDER.lock().events, source) {
+        *stacks.entry(span.stack).or_default() += span.exclusive;
+    }
+
+    let mut stacks: Vec<_> = stacks.into_iter().collect();
+    stacks.sort();
+    for (stack, time) in stacks {
+        writeln!(writer, "{stack} {}", time.as_micros())
+            .map_err(|e| format!("failed to write stack: {e}"))?;
+    }
+
+    Ok(())
+}
+
+/// Export a summary of the time spent per scope name as JSON.
+///
+/// For each name, the summary contains how often a scope with that name was
+/// entered, the total time spent in such scopes, and the time spent in them
+/// excluding nested scopes, all in microseconds. The entries are sorted by
+/// the latter, in descending order. Scopes that are
+/// [listed per span](TimingScope::with_span) get an entry for each source
+/// location the `source` function returns.
+pub fn export_summary<W: Write>(
+    writer: W,
+    source: impl FnMut(Span) -> (String, u32),
+) -> Result<(), String> {
+    #[derive(Serialize)]
+    struct Entry {
+        name: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        line: Option<u32>,
+        calls: u64,
+        total: u128,
+        exclusive: u128,
+    }
+
+    let mut entries: HashMap<(&'static str, Option<Location>), Entry> = HashMap::new();
+    for span in spans(&RECORDER.lock().events, source) {
+        let location = span.location.clone();
+        let entry = entries.entry((span.name, location)).or_insert_with(|| Entry {
+            name: span.name,
+            file: span.location.as_ref().map(|(file, _)| file.clone()),
+            line: span.location.as_ref().map(|&(_, line)| line),
+            calls: 0,
+            total: 0,
+            exclusive: 0,
+        });
+        entry.calls += 1;
+        entry.exclusive += span.exclusive.as_micros();
+
+        // Don't count recursive scopes twice.
+        if !span.recursive {
+            entry.total += span.inclusive.as_micros();
+        }
+    }
+
+    let mut entries: Vec<_> = entries.into_values().collect();
+    entries.sort_by(|a, b| {
+        b.exclusive
+            .cmp(&a.exclusive)
+            .then(a.name.cmp(b.name))
+            .then(a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+    });
+    serde_json::to_writer_pretty(writer, &entries)
+        .map_err(|e| format!("failed to serialize summary: {e}"))
+}
+
+/// A file name and line number.
+type Location = (String, u32);
+
+/// A completed scope, reconstructed from its start and end events.
+struct CompletedSpan {
+    /// The name of the scope.
+    name: &'static str,
+    /// The source location of the scope, if it is listed per span.
+    location: Option<Location>,
+    /// The labels of the scope and its ancestors, separated by semicolons.
+    stack: String,
+    /// Whether an ancestor has the same name.
+    recursive: bool,
+    /// The time spent in the scope.
+    inclusive: Duration,
+    /// The time spent in the scope, excluding nested scopes.
+    exclusive: Duration,
+}
+
+/// Match start and end events per thread into completed spans.
+fn spans(
+    events: &[Event],
+    mut source: impl FnMut(Span) -> (String, u32),
+) -> Vec<CompletedSpan> {
+    struct Open {
+        id: u64,
+        name: &'static str,
+        location: Option<Location>,
+        label: String,
+        start: SystemTime,
+        children: Duration,
+    }
+
+    let mut open: HashMap<ThreadId, Vec<Open>> = HashMap::new();
+    let mut spans = vec![];
+    for event in events {
+        let stack = open.entry(event.thread_id).or_default();
+        match event.kind {
+            EventKind::Start => {
+                let location = event.span.filter(|_| event.by_span).map(&mut source);
+                let label = match &location {
+                    Some((file, line)) => format!("{} ({file}:{line})", event.name),
+                    None => event.name.into(),
+                };
+                stack.push(Open {
+                    id: event.id,
+                    name: event.name,
+                    location,
+                    label,
+                    start: event.timestamp,
+                    children: Duration::ZERO,
+                });
+            }
+            EventKind::End => {
+                let Some(pos) = stack.iter().rposition(|scope| scope.id == event.id)
+                else {
+                    continue;
+                };
+
+                // Scopes that weren't closed properly end with their parent.
+                stack.truncate(pos + 1);
+                let scope = stack.pop().unwrap();
+                let inclusive =
+                    event.timestamp.duration_since(scope.start).unwrap_or(Duration::ZERO);
+                if let Some(parent) = stack.last_mut() {
+                    parent.children += inclusive;
+                }
+
+                let recursive = stack.iter().any(|open| open.name == scope.name);
+                let mut labels: Vec<_> =
+                    stack.iter().map(|open| open.label.as_str()).collect();
+                labels.push(&scope.label);
+                spans.push(CompletedSpan {
+                    name: scope.name,
+                    stack: labels.join(";"),
+                    location: scope.location,
+                    recursive,
+                    inclusive,
+                    exclusive: inclusive.saturating_sub(scope.children),
+                });
+            }
+        }
+    }
+
+    spans
+}
@@ -3,11 +3,15 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
+use sha2::{Digest, Sha256};
 
-use crate::diag::SourceResult;
+use crate::diag::{FileError, FileResult, SourceResult};
 use crate::eval::Tracer;
+use crate::foundations::Bytes;
 use crate::introspection::{Introspector, Locator};
-use crate::syntax::FileId;
+use crate::syntax::package::PackageSpec;
+use crate::syntax::{FileId, Source};
+use crate::text::Font;
 use crate::World;
 
 /// Holds all data needed during compilation.
@@ -42,6 +46,159 @@ impl Engine<'_> {
             }
         }
     }
+
+    /// Emits a structured event to the tracer's log sink, if any.
+    pub fn log(&mut self, event: LogEvent) {
+        self.tracer.log(event);
+    }
+
+    /// Reads a file from the world and logs the access.
+    ///
+    /// Fails for untrusted code, that is, if the library marks the whole
+    /// document as untrusted or the code is evaluated within `untrusted`.
+    pub fn file(&mut self, id: FileId) -> FileResult<Bytes> {
+        self.check_trusted()?;
+        let data = self.world.file(id)?;
+        self.log(LogEvent::FileRead(id));
+        if self.tracer.digesting() {
+            self.tracer.digest(id, data.clone());
+        }
+        Ok(data)
+    }
+
+    /// Loads a source file from the world and logs the access.
+    ///
+    /// Subject to the same restrictions as [`file`](Self::file), so that
+    /// untrusted code cannot include or import other files.
+    pub fn source(&mut self, id: FileId) -> FileResult<Source> {
+        self.check_trusted()?;
+        let source = self.world.source(id)?;
+        self.log(LogEvent::FileRead(id));
+        if self.tracer.digesting() {
+            self.tracer.digest(id, raw(self.world.file(id), &source));
+        }
+        Ok(source)
+    }
+
+    /// Whether the code evaluated by this engine is untrusted, that is, if
+    /// the library marks the whole document as untrusted or the code is
+    /// evaluated within `untrusted`.
+    pub fn is_untrusted(&self) -> bool {
+        self.world.library().untrusted || self.route.untrusted()
+    }
+
+    /// Fails if the code evaluated by this engine is untrusted.
+    fn check_trusted(&self) -> FileResult<()> {
+        if self.is_untrusted() {
+            return Err(FileError::Other(Some(
+                "untrusted content may not read files".into(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The raw bytes a source file was decoded from, so that it is digested in
+/// the same way no matter whether it was read as bytes or as a source. Falls
+/// back to the text for sources that don't exist as files.
+pub(crate) fn raw(file: FileResult<Bytes>, source: &Source) -> Bytes {
+    file.unwrap_or_else(|_| Bytes::from(source.text().as_bytes()))
+}
+
+/// A structured event emitted during compilation.
+///
+/// Embedders can receive these events by attaching a log sink to the tracer
+/// with [`Tracer::with_log`], for instance to show progress or to audit which
+/// resources a document touched. Each distinct event is emitted at most once
+/// per tracer.
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum LogEvent {
+    /// A file was read from the world.
+    FileRead(FileId),
+    /// A package was imported.
+    PackageImported(PackageSpec),
+    /// A font was used in the laid out document.
+    FontLoaded(Font),
+    /// A layout pass started. Passes are counted from one.
+    LayoutPass(usize),
+}
+
+/// A manifest of the files, fonts, and packages a compilation accessed.
+///
+/// Useful for reproducibility audits and for dependency tracking in build
+/// systems.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct Manifest {
+    /// The files that were read, with the SHA-256 digest of their raw bytes
+    /// at the time they were read.
+    pub files: Vec<(FileId, [u8; 32])>,
+    /// The fonts used in the document, with the SHA-256 digest of their data.
+    pub fonts: Vec<(Font, [u8; 32])>,
+    /// The packages that were imported.
+    pub packages: Vec<PackageSpec>,
+}
+
+impl Manifest {
+    /// Build the manifest from the events and digests recorded by a tracer.
+    ///
+    /// Returns `None` if the tracer wasn't created
+    /// [with digests](Tracer::with_digests).
+    pub fn new(tracer: &Tracer) -> Option<Self> {
+        let mut manifest = Self {
+            files: tracer.digests()?.to_vec(),
+            ..Self::default()
+        };
+        for event in tracer.events() {
+            match event {
+                LogEvent::FontLoaded(font) => {
+                    let digest = Sha256::digest(font.data()).into();
+                    manifest.fonts.push((font.clone(), digest));
+                }
+                LogEvent::PackageImported(spec) => {
+                    manifest.packages.push(spec.clone());
+                }
+                LogEvent::FileRead(_) | LogEvent::LayoutPass(_) => {}
+            }
+        }
+        Some(manifest)
+    }
+
+    /// Serialize the manifest into JSON.
+    pub fn to_json(&self) -> String {
+        let hex = |digest: &[u8; 32]| -> String {
+            digest.iter().map(|byte| format!("{byte:02x}")).collect()
+        };
+        let files: Vec<_> = self
+            .files
+            .iter()
+            .map(|(id, digest)| {
+                serde_json::json!({
+                    "package": id.package().map(ToString::to_string),
+                    "path": id.vpath().as_rootless_path().to_string_lossy(),
+                    "sha256": hex(digest),
+                })
+            })
+            .collect();
+        let fonts: Vec<_> = self
+            .fonts
+            .iter()
+            .map(|(font, digest)| {
+                serde_json::json!({
+                    "family": font.info().family,
+                    "index": font.index(),
+                    "sha256": hex(digest),
+                })
+            })
+            .collect();
+        let packages: Vec<_> = self.packages.iter().map(ToString::to_string).collect();
+        let manifest = serde_json::json!({
+            "files": files,
+            "fonts": fonts,
+            "packages": packages,
+        });
+        serde_json::to_string_pretty(&manifest).unwrap()
+    }
 }
 
 /// The route the engine took during compilation. This is used to detect
@@ -69,6 +226,8 @@ pub struct Route<'a> {
     /// because it would prevent cache reuse of some computation at different,
     /// non-exceeding depths).
     upper: AtomicUsize,
+    /// Whether code evaluated on this route segment is untrusted.
+    untrusted: bool,
 }
 
 /// The maximum nesting depths. They are different so that even if show rule and
@@ -94,6 +253,7 @@ impl<'a> Route<'a> {
             outer: None,
             len: 0,
             upper: AtomicUsize::new(0),
+            untrusted: false,
         }
     }
 
@@ -104,6 +264,7 @@ impl<'a> Route<'a> {
             id: None,
             len: 1,
             upper: AtomicUsize::new(usize::MAX),
+            untrusted: false,
         }
     }
 
@@ -123,7 +284,7 @@ impl<'a> Route<'a> {
     /// if it does not contribute anything.
     pub fn track(&self) -> Tracked<'_, Self> {
         match self.outer {
-            Some(outer) if self.id.is_none() && self.len == 0 => outer,
+            Some(outer) if self.id.is_none() && self.len == 0 && !self.untrusted => outer,
             _ => Track::track(self),
         }
     }
@@ -137,6 +298,11 @@ impl<'a> Route<'a> {
     pub fn decrease(&mut self) {
         self.len -= 1;
     }
+
+    /// Set whether code evaluated on this route segment is untrusted.
+    pub fn set_untrusted(&mut self, untrusted: bool) {
+        self.untrusted = untrusted;
+    }
 }
 
 #[comemo::track]
@@ -146,6 +312,12 @@ impl<'a> Route<'a> {
         self.id == Some(id) || self.outer.is_some_and(|outer| outer.contains(id))
     }
 
+    /// Whether code on this route is untrusted because it is evaluated within
+    /// `untrusted`.
+    pub fn untrusted(&self) -> bool {
+        self.untrusted || self.outer.is_some_and(|outer| outer.untrusted())
+    }
+
     /// Whether the route's depth is less than or equal to the given depth.
     pub fn within(&self, depth: usize) -> bool {
         use Ordering::Relaxed;
@@ -186,6 +358,7 @@ impl Clone for Route<'_> {
             // The ordering doesn't really matter since it's the upper bound
             // is only an optimization.
             upper: AtomicUsize::new(self.upper.load(Ordering::Relaxed)),
+            untrusted: self.untrusted,
         }
     }
 }
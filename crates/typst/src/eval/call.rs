@@ -63,6 +63,9 @@ impl Eval for ast::FuncCall<'_> {
 
             // Handle plugins.
             if let Value::Plugin(plugin) = &target {
+                if vm.engine.is_untrusted() {
+                    bail!(span, "untrusted content may not call plugins");
+                }
                 let bytes = args.all::<Bytes>()?;
                 args.finish()?;
                 return Ok(plugin.call(&field, bytes).at(span)?.into_value());
@@ -126,7 +129,18 @@ impl Eval for ast::FuncCall<'_> {
                 bail!(error);
             }
         } else {
-            (callee.eval(vm)?, args.eval(vm)?.spanned(span))
+            let callee = callee.eval(vm)?;
+            let args = if is_untrusted(&callee) && !vm.engine.route.untrusted() {
+                // Everything evaluated within `untrusted` is restricted,
+                // including closures defined there, which remember it.
+                vm.engine.route.set_untrusted(true);
+                let args = args.eval(vm);
+                vm.engine.route.set_untrusted(false);
+                args?
+            } else {
+                args.eval(vm)?
+            };
+            (callee, args.spanned(span))
         };
 
         // Handle math special cases for non-functions:
@@ -261,6 +275,7 @@ impl Eval for ast::Closure<'_> {
                 .children()
                 .filter(|p| matches!(p, ast::Param::Pos(_)))
                 .count(),
+            untrusted: vm.engine.route.untrusted(),
         };
 
         Ok(Value::Func(Func::from(closure).spanned(self.params().span())))
@@ -293,10 +308,12 @@ pub(crate) fn call_closure(
 
     // Prepare the engine.
     let mut locator = Locator::chained(locator);
+    let mut route = Route::extend(route);
+    route.set_untrusted(closure.untrusted);
     let engine = Engine {
         world,
         introspector,
-        route: Route::extend(route),
+        route,
         locator: &mut locator,
         tracer,
     };
@@ -379,6 +396,11 @@ fn in_math(expr: ast::Expr) -> bool {
     }
 }
 
+/// Whether the value is the `untrusted` function.
+fn is_untrusted(callee: &Value) -> bool {
+    matches!(callee, Value::Func(func) if func.is_untrusted())
+}
+
 /// A visitor that determines which variables to capture for a closure.
 pub struct CapturesVisitor<'a> {
     external: Option<&'a Scopes<'a>>,
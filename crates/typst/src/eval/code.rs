@@ -1,7 +1,7 @@
 use ecow::{eco_vec, EcoVec};
 
 use crate::diag::{bail, error, At, SourceResult};
-use crate::eval::{ops, CapturesVisitor, Eval, Vm};
+use crate::eval::{eval_tail, ops, CapturesVisitor, Eval, Vm};
 use crate::foundations::{
     Array, Capturer, Closure, Content, ContextElem, Dict, Func, NativeElement, Str, Value,
 };
@@ -32,7 +32,8 @@ fn eval_code<'a>(
                     break;
                 }
 
-                let tail = eval_code(vm, exprs)?.display();
+                let tail = eval_code(vm, exprs)?;
+                let tail = vm.display(tail);
                 Value::Content(tail.styled_with_map(styles))
             }
             ast::Expr::Show(show) => {
@@ -41,7 +42,8 @@ fn eval_code<'a>(
                     break;
                 }
 
-                let tail = eval_code(vm, exprs)?.display();
+                let tail = eval_tail(vm, &recipe, |vm| eval_code(vm, exprs))?;
+                let tail = vm.display(tail);
                 Value::Content(tail.styled_with_recipe(
                     &mut vm.engine,
                     vm.context,
@@ -350,6 +352,7 @@ impl Eval for ast::Contextual<'_> {
             defaults: vec![],
             captured,
             num_pos_params: 0,
+            untrusted: vm.engine.route.untrusted(),
         };
 
         let func = Func::from(closure).spanned(body.span());
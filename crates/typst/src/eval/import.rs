@@ -2,12 +2,12 @@ use comemo::TrackedMut;
 use ecow::{eco_format, eco_vec, EcoString};
 
 use crate::diag::{bail, error, warning, At, FileError, SourceResult, Trace, Tracepoint};
+use crate::engine::LogEvent;
 use crate::eval::{eval, Eval, Vm};
 use crate::foundations::{Content, Module, Value};
 use crate::syntax::ast::{self, AstNode};
 use crate::syntax::package::{PackageManifest, PackageSpec};
 use crate::syntax::{FileId, Span, VirtualPath};
-use crate::World;
 
 impl Eval for ast::ModuleImport<'_> {
     type Output = Value;
@@ -172,7 +172,8 @@ pub fn import(
 fn import_package(vm: &mut Vm, spec: PackageSpec, span: Span) -> SourceResult<Module> {
     // Evaluate the manifest.
     let manifest_id = FileId::new(Some(spec.clone()), VirtualPath::new("typst.toml"));
-    let bytes = vm.world().file(manifest_id).at(span)?;
+    vm.engine.log(LogEvent::PackageImported(spec.clone()));
+    let bytes = vm.engine.file(manifest_id).at(span)?;
     let string = std::str::from_utf8(&bytes).map_err(FileError::from).at(span)?;
     let manifest: PackageManifest = toml::from_str(string)
         .map_err(|err| eco_format!("package manifest is malformed ({})", err.message()))
@@ -181,7 +182,7 @@ fn import_package(vm: &mut Vm, spec: PackageSpec, span: Span) -> SourceResult<Mo
 
     // Evaluate the entry point.
     let entrypoint_id = manifest_id.join(&manifest.package.entrypoint);
-    let source = vm.world().source(entrypoint_id).at(span)?;
+    let source = vm.engine.source(entrypoint_id).at(span)?;
     let point = || Tracepoint::Import;
     Ok(eval(
         vm.world(),
@@ -198,7 +199,7 @@ fn import_file(vm: &mut Vm, path: &str, span: Span) -> SourceResult<Module> {
     // Load the source file.
     let world = vm.world();
     let id = span.resolve_path(path).at(span)?;
-    let source = world.source(id).at(span)?;
+    let source = vm.engine.source(id).at(span)?;
 
     // Prevent cyclic importing.
     if vm.engine.route.contains(source.id()) {
@@ -1,5 +1,5 @@
 use crate::diag::{warning, SourceResult};
-use crate::eval::{Eval, Vm};
+use crate::eval::{eval_tail, Eval, Vm};
 use crate::foundations::{Content, Label, NativeElement, Smart, Unlabellable, Value};
 use crate::math::EquationElem;
 use crate::model::{
@@ -44,7 +44,7 @@ fn eval_markup<'a>(
                     break;
                 }
 
-                let tail = eval_markup(vm, exprs)?;
+                let tail = eval_tail(vm, &recipe, |vm| eval_markup(vm, exprs))?;
                 seq.push(tail.styled_with_recipe(&mut vm.engine, vm.context, recipe)?)
             }
             expr => match expr.eval(vm)? {
@@ -55,7 +55,7 @@ fn eval_markup<'a>(
                         *elem = std::mem::take(elem).labelled(label);
                     }
                 }
-                value => seq.push(value.display().spanned(expr.span())),
+                value => seq.push(vm.display(value).spanned(expr.span())),
             },
         }
 
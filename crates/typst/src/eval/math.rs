@@ -100,6 +100,7 @@ trait ExprExt {
 
 impl ExprExt for ast::Expr<'_> {
     fn eval_display(&self, vm: &mut Vm) -> SourceResult<Content> {
-        Ok(self.eval(vm)?.display().spanned(self.span()))
+        let value = self.eval(vm)?;
+        Ok(vm.display(value).spanned(self.span()))
     }
 }
@@ -22,15 +22,22 @@ pub use self::vm::*;
 pub(crate) use self::access::*;
 pub(crate) use self::binding::*;
 pub(crate) use self::flow::*;
+pub(crate) use self::rules::*;
 
 use comemo::{Track, Tracked, TrackedMut};
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::{Engine, Route};
-use crate::foundations::{Cast, Context, Module, NativeElement, Scope, Scopes, Value};
+use crate::foundations::{
+    Cast, Content, Context, Module, NativeElement, Scope, Scopes, SequenceElem, Style,
+    StyledElem, Styles, Transformation, Value,
+};
 use crate::introspection::{Introspector, Locator};
 use crate::math::EquationElem;
-use crate::syntax::{ast, parse, parse_code, parse_math, Source, Span};
+use crate::model::ParbreakElem;
+use crate::syntax::ast::{self, AstNode};
+use crate::syntax::{parse, parse_code, parse_math, Source, Span};
+use crate::text::SpaceElem;
 use crate::World;
 
 /// Evaluate a source file and return the resulting module.
@@ -91,16 +98,126 @@ pub fn eval(
     Ok(Module::new(name, vm.scopes.top).with_content(output))
 }
 
+/// Evaluate a style bundle into the styles of its top-level set and show
+/// rules.
+///
+/// Bindings and imports may prepare the rules. Blocks are evaluated as usual,
+/// so the rules within them stay scoped to the block and don't end up in the
+/// styles. Anything that produces content is an error.
+#[typst_macros::time(name = "eval styles", span = source.root().span())]
+pub fn eval_styles(
+    world: Tracked<dyn World + '_>,
+    route: Tracked<Route>,
+    tracer: TrackedMut<Tracer>,
+    source: &Source,
+) -> SourceResult<Styles> {
+    // Prepare the engine.
+    let mut locator = Locator::new();
+    let introspector = Introspector::default();
+    let engine = Engine {
+        world,
+        route: Route::extend(route).with_id(source.id()),
+        introspector: introspector.track(),
+        locator: &mut locator,
+        tracer,
+    };
+
+    // Prepare VM.
+    let context = Context::none();
+    let scopes = Scopes::new(Some(world.library()));
+    let root = source.root();
+    let mut vm = Vm::new(engine, context.track(), scopes, root.span());
+
+    // Check for well-formedness.
+    let errors = root.errors();
+    if !errors.is_empty() {
+        return Err(errors.into_iter().map(Into::into).collect());
+    }
+
+    // Collect the rules, each applying inside the previous ones.
+    let mut styles = Styles::new();
+    let markup = root.cast::<ast::Markup>().unwrap();
+    for expr in markup.exprs() {
+        let mut inner = match expr {
+            ast::Expr::Set(set) => set.eval(&mut vm)?,
+            ast::Expr::Show(show) => {
+                let recipe = show.eval(&mut vm)?;
+                match (&recipe.selector, &recipe.transform) {
+                    (Some(_), _) => Styles::from(Style::Recipe(recipe)),
+                    (None, Transformation::Style(inner)) => inner.clone(),
+                    (None, _) => bail!(
+                        show.span(),
+                        "style bundle cannot contain show rules without a selector";
+                        hint: "these rules transform the whole document instead of styling it"
+                    ),
+                }
+            }
+            expr => {
+                let value = expr.eval(&mut vm)?;
+                if !is_styles_only(&value) {
+                    bail!(
+                        expr.span(),
+                        "style bundle must only contain set and show rules"
+                    );
+                }
+                Styles::new()
+            }
+        };
+
+        if let Some(flow) = vm.flow {
+            bail!(flow.forbidden());
+        }
+
+        inner.apply(std::mem::take(&mut styles));
+        styles = inner;
+    }
+
+    Ok(styles)
+}
+
+/// Whether a value produces no content, apart from whitespace and styles that
+/// only apply to it.
+fn is_styles_only(value: &Value) -> bool {
+    fn empty(content: &Content) -> bool {
+        if let Some(styled) = content.to_packed::<StyledElem>() {
+            empty(&styled.child)
+        } else if let Some(sequence) = content.to_packed::<SequenceElem>() {
+            sequence.children.iter().all(empty)
+        } else {
+            content.is::<SpaceElem>() || content.is::<ParbreakElem>()
+        }
+    }
+
+    match value {
+        Value::None => true,
+        Value::Content(content) => empty(content),
+        _ => false,
+    }
+}
+
 /// Evaluate a string as code and return the resulting value.
 ///
 /// Everything in the output is associated with the given `span`.
-#[comemo::memoize]
 pub fn eval_string(
     world: Tracked<dyn World + '_>,
     string: &str,
     span: Span,
     mode: EvalMode,
     scope: Scope,
+) -> SourceResult<Value> {
+    eval_string_in(world, string, span, mode, scope, false)
+}
+
+/// Evaluate a string as code, optionally with the restrictions of
+/// [`untrusted`](crate::model::UntrustedElem) content.
+#[comemo::memoize]
+pub(crate) fn eval_string_in(
+    world: Tracked<dyn World + '_>,
+    string: &str,
+    span: Span,
+    mode: EvalMode,
+    scope: Scope,
+    untrusted: bool,
 ) -> SourceResult<Value> {
     let mut root = match mode {
         EvalMode::Code => parse_code(string),
@@ -120,10 +237,12 @@ pub fn eval_string(
     let mut tracer = Tracer::new();
     let mut locator = Locator::new();
     let introspector = Introspector::default();
+    let mut route = Route::default();
+    route.set_untrusted(untrusted);
     let engine = Engine {
         world,
         introspector: introspector.track(),
-        route: Route::default(),
+        route,
         locator: &mut locator,
         tracer: tracer.track_mut(),
     };
@@ -7,7 +7,7 @@ use ecow::eco_format;
 use crate::diag::{bail, At, HintedStrResult, SourceResult, StrResult};
 use crate::eval::{access_dict, Access, Eval, Vm};
 use crate::foundations::{format_str, Datetime, IntoValue, Regex, Repr, Value};
-use crate::layout::{Alignment, Length, Rel};
+use crate::layout::{Alignment, Length, Rel, Vec2};
 use crate::syntax::ast::{self, AstNode};
 use crate::text::TextElem;
 use crate::utils::Numeric;
@@ -171,6 +171,7 @@ pub fn neg(value: Value) -> HintedStrResult<Value> {
         Fraction(v) => Fraction(-v),
         Duration(v) => Duration(-v),
         Datetime(_) => mismatch!("cannot apply unary '-' to {}", value),
+        Dyn(d) if d.is::<Vec2>() => (-*d.downcast::<Vec2>().unwrap()).into_value(),
         v => mismatch!("cannot apply '-' to {}", v),
     })
 }
@@ -244,6 +245,11 @@ pub fn add(lhs: Value, rhs: Value) -> HintedStrResult<Value> {
                 return Ok((a + b)?.into_value());
             }
 
+            // Vectors can be summed.
+            if let (Some(&a), Some(&b)) = (a.downcast::<Vec2>(), b.downcast::<Vec2>()) {
+                return Ok((a + b).into_value());
+            }
+
             mismatch!("cannot add {} and {}", a, b);
         }
 
@@ -280,6 +286,15 @@ pub fn sub(lhs: Value, rhs: Value) -> HintedStrResult<Value> {
         (Datetime(a), Duration(b)) => Datetime(a - b),
         (Datetime(a), Datetime(b)) => Duration((a - b)?),
 
+        (Dyn(a), Dyn(b)) => {
+            // Vectors can be subtracted.
+            if let (Some(&a), Some(&b)) = (a.downcast::<Vec2>(), b.downcast::<Vec2>()) {
+                return Ok((a - b).into_value());
+            }
+
+            mismatch!("cannot subtract {1} from {0}", a, b);
+        }
+
         (a, b) => mismatch!("cannot subtract {1} from {0}", a, b),
     })
 }
@@ -339,6 +354,19 @@ pub fn mul(lhs: Value, rhs: Value) -> HintedStrResult<Value> {
         (Duration(a), Int(b)) => Duration(a * (b as f64)),
         (Duration(a), Float(b)) => Duration(a * b),
 
+        (Dyn(a), Int(b)) if a.is::<Vec2>() => {
+            (*a.downcast::<Vec2>().unwrap() * b as f64).into_value()
+        }
+        (Dyn(a), Float(b)) if a.is::<Vec2>() => {
+            (*a.downcast::<Vec2>().unwrap() * b).into_value()
+        }
+        (Int(a), Dyn(b)) if b.is::<Vec2>() => {
+            (*b.downcast::<Vec2>().unwrap() * a as f64).into_value()
+        }
+        (Float(a), Dyn(b)) if b.is::<Vec2>() => {
+            (*b.downcast::<Vec2>().unwrap() * a).into_value()
+        }
+
         (a, b) => mismatch!("cannot multiply {} with {}", a, b),
     })
 }
@@ -384,6 +412,13 @@ pub fn div(lhs: Value, rhs: Value) -> HintedStrResult<Value> {
         (Duration(a), Float(b)) => Duration(a / b),
         (Duration(a), Duration(b)) => Float(a / b),
 
+        (Dyn(a), Int(b)) if a.is::<Vec2>() => {
+            (*a.downcast::<Vec2>().unwrap() / b as f64).into_value()
+        }
+        (Dyn(a), Float(b)) if a.is::<Vec2>() => {
+            (*a.downcast::<Vec2>().unwrap() / b).into_value()
+        }
+
         (a, b) => mismatch!("cannot divide {} by {}", a, b),
     })
 }
@@ -49,3 +49,21 @@ impl Eval for ast::ShowRule<'_> {
         Ok(Recipe { span, selector, transform })
     }
 }
+
+/// Evaluates the content following a show rule without a selector.
+///
+/// After `show: untrusted`, the rest of the scope is restricted just like the
+/// body of an explicit `untrusted` call.
+pub(crate) fn eval_tail<T>(
+    vm: &mut Vm,
+    recipe: &Recipe,
+    f: impl FnOnce(&mut Vm) -> SourceResult<T>,
+) -> SourceResult<T> {
+    if !recipe.is_untrusted() || vm.engine.route.untrusted() {
+        return f(vm);
+    }
+    vm.engine.route.set_untrusted(true);
+    let output = f(vm);
+    vm.engine.route.set_untrusted(false);
+    output
+}
@@ -1,11 +1,18 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
-use ecow::EcoVec;
+use ecow::{EcoString, EcoVec};
+use sha2::{Digest, Sha256};
 
 use crate::diag::SourceDiagnostic;
-use crate::foundations::{Styles, Value};
+use crate::engine::LogEvent;
+use crate::foundations::{Bytes, Styles, Value};
 use crate::syntax::{FileId, Span};
 use crate::utils::hash128;
+use crate::World;
+
+/// A sink that receives the structured events emitted during compilation.
+type LogSink = Arc<dyn Fn(&LogEvent) + Send + Sync>;
 
 /// Traces warnings and which values existed for an expression at a span.
 #[derive(Default, Clone)]
@@ -15,6 +22,11 @@ pub struct Tracer {
     warnings_set: HashSet<u128>,
     delayed: EcoVec<SourceDiagnostic>,
     values: EcoVec<(Value, Option<Styles>)>,
+    suppressions: EcoVec<(Span, EcoVec<EcoString>)>,
+    log: Option<LogSink>,
+    logged: HashSet<u128>,
+    events: EcoVec<LogEvent>,
+    digests: Option<EcoVec<(FileId, [u8; 32])>>,
 }
 
 impl Tracer {
@@ -26,6 +38,34 @@ impl Tracer {
         Self::default()
     }
 
+    /// Attach a sink that receives the structured events emitted during
+    /// compilation.
+    pub fn with_log(self, sink: impl Fn(&LogEvent) + Send + Sync + 'static) -> Self {
+        Self { log: Some(Arc::new(sink)), ..self }
+    }
+
+    /// Capture a SHA-256 digest of the raw bytes of each file when it is
+    /// first read, for use in a [`Manifest`](crate::engine::Manifest).
+    pub fn with_digests(self) -> Self {
+        Self { digests: Some(EcoVec::new()), ..self }
+    }
+
+    /// Whether a log sink is attached.
+    pub fn logging(&self) -> bool {
+        self.log.is_some()
+    }
+
+    /// Get the distinct structured events emitted so far, in order.
+    pub fn events(&self) -> &[LogEvent] {
+        &self.events
+    }
+
+    /// Get the digests of the files read so far, in order, if they are
+    /// [captured](Self::with_digests).
+    pub fn digests(&self) -> Option<&[(FileId, [u8; 32])]> {
+        self.digests.as_deref()
+    }
+
     /// Get the stored delayed errors.
     pub fn delayed(&mut self) -> EcoVec<SourceDiagnostic> {
         std::mem::take(&mut self.delayed)
@@ -46,6 +86,42 @@ impl Tracer {
     pub fn values(self) -> EcoVec<(Value, Option<Styles>)> {
         self.values
     }
+
+    /// Remove the warnings that stem from a region in which they were
+    /// suppressed.
+    ///
+    /// A warning stems from a region if its span lies within the region's
+    /// span in the same file. Warnings with detached spans or spans in other
+    /// files are always kept.
+    pub fn apply_suppressions(&mut self, world: &dyn World) {
+        if self.suppressions.is_empty() {
+            return;
+        }
+
+        let within = |region: Span, span: Span| {
+            let Some(id) = span.id().filter(|&id| region.id() == Some(id)) else {
+                return false;
+            };
+            let Ok(source) = world.source(id) else { return false };
+            match (source.range(region), source.range(span)) {
+                (Some(outer), Some(inner)) => {
+                    outer.start <= inner.start && inner.end <= outer.end
+                }
+                _ => false,
+            }
+        };
+
+        let suppressions = std::mem::take(&mut self.suppressions);
+        self.warnings.retain(|warning| {
+            !suppressions.iter().any(|(region, patterns)| {
+                within(*region, warning.span)
+                    && (patterns.is_empty()
+                        || patterns
+                            .iter()
+                            .any(|pattern| warning.message.contains(pattern.as_str())))
+            })
+        });
+    }
 }
 
 #[comemo::track]
@@ -64,6 +140,16 @@ impl Tracer {
         }
     }
 
+    /// Suppress warnings that stem from the region of the given span and
+    /// whose messages contain one of the patterns. Without patterns, all
+    /// warnings in the region are suppressed.
+    pub fn suppress(&mut self, span: Span, patterns: EcoVec<EcoString>) {
+        let suppression = (span, patterns);
+        if !self.suppressions.contains(&suppression) {
+            self.suppressions.push(suppression);
+        }
+    }
+
     /// The inspected span if it is part of the given source file.
     pub fn inspected(&self, id: FileId) -> Option<Span> {
         if self.inspected.and_then(Span::id) == Some(id) {
@@ -73,6 +159,32 @@ impl Tracer {
         }
     }
 
+    /// Record a structured event and emit it to the log sink, unless it was
+    /// already recorded.
+    pub fn log(&mut self, event: LogEvent) {
+        if self.logged.insert(hash128(&event)) {
+            if let Some(sink) = &self.log {
+                sink(&event);
+            }
+            self.events.push(event);
+        }
+    }
+
+    /// Whether digests of the read files are captured.
+    pub fn digesting(&self) -> bool {
+        self.digests.is_some()
+    }
+
+    /// Record the digest of a file's raw bytes, unless it is already known
+    /// or digests aren't captured.
+    pub fn digest(&mut self, id: FileId, data: Bytes) {
+        if let Some(digests) = &mut self.digests {
+            if !digests.iter().any(|&(known, _)| known == id) {
+                digests.push((id, Sha256::digest(&data).into()));
+            }
+        }
+    }
+
     /// Trace a value for the span.
     pub fn value(&mut self, value: Value, styles: Option<Styles>) {
         if self.values.len() < Self::MAX_VALUES {
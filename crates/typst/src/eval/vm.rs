@@ -2,9 +2,10 @@ use comemo::Tracked;
 
 use crate::engine::Engine;
 use crate::eval::FlowEvent;
-use crate::foundations::{Context, IntoValue, Scopes, Value};
+use crate::foundations::{repr, Content, Context, IntoValue, Scopes, Value};
 use crate::syntax::ast::{self, AstNode};
 use crate::syntax::Span;
+use crate::text::TextElem;
 use crate::World;
 
 /// A virtual machine.
@@ -41,6 +42,22 @@ impl<'a> Vm<'a> {
         self.engine.world
     }
 
+    /// Display a value as content, with floats written with the decimal
+    /// separator of the [locale](crate::foundations::sys::Locale).
+    ///
+    /// This only applies to floats that are placed into markup or math
+    /// directly. Strings, including those from `str` and `repr`, as well as
+    /// floats within other values, always use a period.
+    pub fn display(&self, value: Value) -> Content {
+        match (value, self.world().library().locale.decimal_separator) {
+            (Value::Float(v), Some(separator)) => {
+                let separator = separator.encode_utf8(&mut [0; 4]);
+                TextElem::packed(repr::display_float(v).replace('.', separator))
+            }
+            (value, _) => value.display(),
+        }
+    }
+
     /// Define a variable in the current scope.
     pub fn define(&mut self, var: ast::Ident, value: impl IntoValue) {
         let value = value.into_value();
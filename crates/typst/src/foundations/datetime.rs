@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::hash::Hash;
 use std::ops::{Add, Sub};
 
+use comemo::Tracked;
 use ecow::{eco_format, EcoString, EcoVec};
 use time::error::{Format, InvalidFormatDescription};
 use time::macros::format_description;
@@ -10,8 +11,9 @@ use time::{format_description, Month, PrimitiveDateTime};
 use crate::diag::{bail, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, func, repr, scope, ty, Dict, Duration, Repr, Smart, Str, Value,
+    cast, func, repr, scope, ty, Context, Dict, Duration, Repr, Smart, Str, Value,
 };
+use crate::text::TextElem;
 use crate::World;
 
 /// Represents a date, a time, or a combination of both.
@@ -229,6 +231,24 @@ impl Datetime {
             Datetime::Time(_) => "time",
         }
     }
+
+    /// The default format for this kind of datetime from the user-provided
+    /// [translations](TextElem::translations_in), if any.
+    fn local_format(
+        &self,
+        context: Tracked<Context>,
+    ) -> StrResult<Option<format_description::OwnedFormatItem>> {
+        let Ok(styles) = context.styles() else { return Ok(None) };
+        let translations = TextElem::translations_in(styles);
+        let lang = TextElem::lang_in(styles);
+        let region = TextElem::region_in(styles);
+        let Some(pattern) = translations.get(lang, region, self.kind()) else {
+            return Ok(None);
+        };
+        format_description::parse_owned::<2>(pattern)
+            .map(Some)
+            .map_err(format_time_invalid_format_description_error)
+    }
 }
 
 #[scope]
@@ -338,19 +358,29 @@ impl Datetime {
     /// default format will be different. If you specified a date, it will be
     /// `[[year]-[month]-[day]]`. If you specified a time, it will be
     /// `[[hour]:[minute]:[second]]`. In the case of a datetime, it will be
-    /// `[[year]-[month]-[day] [hour]:[minute]:[second]]`.
+    /// `[[year]-[month]-[day] [hour]:[minute]:[second]]`. Within
+    /// [context], these defaults can be overridden per language through the
+    /// `date`, `time`, and `datetime` keys of the
+    /// [text translations]($text.translations).
     ///
     /// See the [format syntax]($datetime/#format) for more information.
     #[func]
     pub fn display(
         &self,
+        /// The callsite context.
+        context: Tracked<Context>,
         /// The format used to display the datetime.
         #[default]
         pattern: Smart<DisplayPattern>,
     ) -> StrResult<EcoString> {
         let pat = |s| format_description::parse_borrowed::<2>(s).unwrap();
-        let result = match pattern {
-            Smart::Auto => match self {
+        let format = match pattern {
+            Smart::Auto => self.local_format(context)?,
+            Smart::Custom(DisplayPattern(_, format)) => Some(format),
+        };
+
+        let result = match format {
+            None => match self {
                 Self::Date(date) => date.format(&pat("[year]-[month]-[day]")),
                 Self::Time(time) => time.format(&pat("[hour]:[minute]:[second]")),
                 Self::Datetime(datetime) => {
@@ -358,7 +388,7 @@ impl Datetime {
                 }
             },
 
-            Smart::Custom(DisplayPattern(_, format)) => match self {
+            Some(format) => match self {
                 Self::Date(date) => date.format(&format),
                 Self::Time(time) => time.format(&format),
                 Self::Datetime(datetime) => datetime.format(&format),
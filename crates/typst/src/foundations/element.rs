@@ -14,7 +14,6 @@ use crate::foundations::{
     cast, Args, Content, Dict, Func, ParamInfo, Repr, Scope, Selector, StyleChain,
     Styles, Value,
 };
-use crate::text::{Lang, Region};
 use crate::utils::Static;
 
 #[doc(inline)]
@@ -129,8 +128,8 @@ impl Element {
     }
 
     /// The element's local name, if any.
-    pub fn local_name(&self, lang: Lang, region: Option<Region>) -> Option<&'static str> {
-        (self.0).0.local_name.map(|f| f(lang, region))
+    pub fn local_name(&self, styles: StyleChain) -> Option<EcoString> {
+        (self.0).0.local_name.map(|f| f(styles))
     }
 }
 
@@ -284,7 +283,7 @@ pub struct NativeElementData {
     /// Get the field with the given ID in the presence of styles (see [`Fields`]).
     pub field_from_styles: fn(u8, StyleChain) -> Option<Value>,
     /// Gets the localized name for this element (see [`LocalName`][crate::text::LocalName]).
-    pub local_name: Option<fn(Lang, Option<Region>) -> &'static str>,
+    pub local_name: Option<fn(StyleChain) -> EcoString>,
     pub scope: Lazy<Scope>,
     /// A list of parameter information for each field.
     pub params: Lazy<Vec<ParamInfo>>,
@@ -4,8 +4,8 @@ use ecow::{eco_format, EcoString};
 
 use crate::diag::StrResult;
 use crate::foundations::{IntoValue, Type, Value, Version};
-use crate::layout::{Alignment, Length, Rel};
-use crate::visualize::Stroke;
+use crate::layout::{Alignment, Length, Rel, Transform, Vec2};
+use crate::visualize::{Shadow, Stroke};
 
 /// Try to access a field on a value.
 ///
@@ -45,6 +45,30 @@ pub(crate) fn field(value: &Value, field: &str) -> StrResult<Value> {
                     }
                     _ => return missing(),
                 }
+            } else if let Some(shadow) = dynamic.downcast::<Shadow>() {
+                match field {
+                    "dx" => shadow.dx.into_value(),
+                    "dy" => shadow.dy.into_value(),
+                    "blur" => shadow.blur.into_value(),
+                    "color" => shadow.color.into_value(),
+                    _ => return missing(),
+                }
+            } else if let Some(vec) = dynamic.downcast::<Vec2>() {
+                match field {
+                    "x" => vec.x.into_value(),
+                    "y" => vec.y.into_value(),
+                    _ => return missing(),
+                }
+            } else if let Some(ts) = dynamic.downcast::<Transform>() {
+                match field {
+                    "sx" => ts.sx.into_value(),
+                    "ky" => ts.ky.into_value(),
+                    "kx" => ts.kx.into_value(),
+                    "sy" => ts.sy.into_value(),
+                    "tx" => ts.tx.into_value(),
+                    "ty" => ts.ty.into_value(),
+                    _ => return missing(),
+                }
             } else if let Some(align) = dynamic.downcast::<Alignment>() {
                 match field {
                     "x" => align.x().into_value(),
@@ -83,8 +107,12 @@ pub fn fields_on(ty: Type) -> &'static [&'static str] {
         &["ratio", "length"]
     } else if ty == Type::of::<Stroke>() {
         &["paint", "thickness", "cap", "join", "dash", "miter-limit"]
-    } else if ty == Type::of::<Alignment>() {
+    } else if ty == Type::of::<Shadow>() {
+        &["dx", "dy", "blur", "color"]
+    } else if ty == Type::of::<Vec2>() || ty == Type::of::<Alignment>() {
         &["x", "y"]
+    } else if ty == Type::of::<Transform>() {
+        &["sx", "ky", "kx", "sy", "tx", "ty"]
     } else {
         &[]
     }
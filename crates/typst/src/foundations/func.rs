@@ -8,9 +8,10 @@ use once_cell::sync::Lazy;
 use crate::diag::{bail, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, repr, scope, ty, Args, CastInfo, Content, Context, Element, IntoArgs, Scope,
-    Selector, Type, Value,
+    cast, repr, scope, ty, Args, CastInfo, Content, Context, Element, IntoArgs,
+    NativeElement, Scope, Selector, Type, Value,
 };
+use crate::model::UntrustedElem;
 use crate::syntax::{ast, Span, SyntaxNode};
 use crate::utils::{LazyHash, Static};
 
@@ -263,6 +264,16 @@ impl Func {
         }
     }
 
+    /// Whether this is the `untrusted` function, possibly with pre-applied
+    /// arguments.
+    pub(crate) fn is_untrusted(&self) -> bool {
+        match &self.repr {
+            Repr::Element(elem) => *elem == UntrustedElem::elem(),
+            Repr::With(with) => with.0.is_untrusted(),
+            _ => false,
+        }
+    }
+
     /// Call the function with the given context and arguments.
     pub fn call<A: IntoArgs>(
         &self,
@@ -515,6 +526,9 @@ pub struct Closure {
     pub captured: Scope,
     /// The number of positional parameters in the closure.
     pub num_pos_params: usize,
+    /// Whether the closure was defined within `untrusted`. If so, its body is
+    /// restricted wherever it is called.
+    pub untrusted: bool,
 }
 
 impl Closure {
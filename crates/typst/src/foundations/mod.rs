@@ -70,12 +70,12 @@ pub use {
     once_cell::sync::Lazy,
 };
 
-use ecow::EcoString;
+use ecow::{EcoString, EcoVec};
 
-use crate::diag::{bail, SourceResult, StrResult};
+use crate::diag::{bail, HintedStrResult, SourceDiagnostic, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::eval::EvalMode;
-use crate::syntax::Spanned;
+use crate::syntax::{Span, Spanned};
 
 /// Foundational types and functions.
 ///
@@ -85,7 +85,7 @@ use crate::syntax::Spanned;
 pub static FOUNDATIONS: Category;
 
 /// Hook up all `foundations` definitions.
-pub(super) fn define(global: &mut Scope, inputs: Dict) {
+pub(super) fn define(global: &mut Scope, inputs: Dict, locale: sys::Locale) {
     global.category(FOUNDATIONS);
     global.define_type::<bool>();
     global.define_type::<i64>();
@@ -102,6 +102,7 @@ pub(super) fn define(global: &mut Scope, inputs: Dict) {
     global.define_type::<Module>();
     global.define_type::<Regex>();
     global.define_type::<Selector>();
+    global.define_type::<Styles>();
     global.define_type::<Datetime>();
     global.define_type::<Duration>();
     global.define_type::<Version>();
@@ -109,10 +110,12 @@ pub(super) fn define(global: &mut Scope, inputs: Dict) {
     global.define_func::<repr::repr>();
     global.define_func::<panic>();
     global.define_func::<assert>();
+    global.define_func::<warn>();
+    global.define_func::<suppress_warnings>();
     global.define_func::<eval>();
     global.define_func::<style>();
     global.define_module(calc::module());
-    global.define_module(sys::module(inputs));
+    global.define_module(sys::module(inputs, locale));
 }
 
 /// Fails with an error.
@@ -243,6 +246,89 @@ impl assert {
     }
 }
 
+/// Emits a compiler warning.
+///
+/// The warning points at the call site and does not stop compilation. This
+/// way, templates and packages can alert their users to questionable input
+/// without failing like [`panic`] does.
+///
+/// # Example
+/// ```typ
+/// #let title(body) = {
+///   if body == [] {
+///     warn("the title is empty")
+///   }
+///   heading(body)
+/// }
+/// ```
+#[func]
+pub fn warn(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite span.
+    span: Span,
+    /// The warning message.
+    message: EcoString,
+    /// Additional hints that explain how to avoid the warning.
+    #[named]
+    #[default]
+    hints: Vec<EcoString>,
+) -> NoneValue {
+    engine
+        .tracer
+        .warn(SourceDiagnostic::warning(span, message).with_hints(hints));
+    NoneValue
+}
+
+/// Suppresses compiler warnings in a region of the document.
+///
+/// All warnings that stem from source code within the call, including its
+/// body, are suppressed if their messages contain one of the given patterns.
+/// Errors cannot be suppressed.
+///
+/// Warnings are attributed to the code that causes them, not to the place
+/// where their result ends up. A warning about a function or set rule that is
+/// written outside of the call is thus not suppressed, even if the function
+/// is called or the set rule applies within it. Moreover, warnings have no
+/// stable names, so they are recognized by their messages, which may change
+/// between versions of Typst.
+///
+/// # Example
+/// ```example
+/// #suppress-warnings(only: "unknown font family")[
+///   #set text(font: ("Fancy Font", "Libertinus Serif"))
+///   Falls back silently.
+/// ]
+/// ```
+#[func]
+pub fn suppress_warnings(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite span.
+    span: Span,
+    /// The content in which warnings are suppressed.
+    body: Content,
+    /// One or multiple parts of the messages of the warnings to suppress. If
+    /// empty, all warnings are suppressed.
+    #[named]
+    #[default]
+    only: WarningPatterns,
+) -> Content {
+    engine.tracer.suppress(span, only.0);
+    body
+}
+
+/// Parts of the messages of warnings.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct WarningPatterns(EcoVec<EcoString>);
+
+cast! {
+    WarningPatterns,
+    self => self.0.into_iter().map(IntoValue::into_value).collect::<Array>().into_value(),
+    v: EcoString => Self(eco_vec![v]),
+    v: Array => Self(v.into_iter().map(Value::cast).collect::<HintedStrResult<_>>()?),
+}
+
 /// Evaluates a string as Typst code.
 ///
 /// This function should only be used as a last resort.
@@ -292,5 +378,6 @@ pub fn eval(
     for (key, value) in dict {
         scope.define(key, value);
     }
-    crate::eval::eval_string(engine.world, &text, span, mode, scope)
+    let untrusted = engine.route.untrusted();
+    crate::eval::eval_string_in(engine.world, &text, span, mode, scope, untrusted)
 }
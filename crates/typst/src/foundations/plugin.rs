@@ -9,7 +9,6 @@ use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{func, repr, scope, ty, Bytes};
 use crate::syntax::Spanned;
-use crate::World;
 
 /// A WebAssembly plugin.
 ///
@@ -159,7 +158,7 @@ impl Plugin {
     ) -> SourceResult<Plugin> {
         let Spanned { v: path, span } = path;
         let id = span.resolve_path(&path).at(span)?;
-        let data = engine.world.file(id).at(span)?;
+        let data = engine.file(id).at(span)?;
         Plugin::new(data).at(span)
     }
 }
@@ -7,11 +7,11 @@ use comemo::{Track, Tracked};
 use ecow::{eco_vec, EcoString, EcoVec};
 use smallvec::SmallVec;
 
-use crate::diag::{SourceResult, Trace, Tracepoint};
+use crate::diag::{bail, SourceResult, Trace, Tracepoint};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, func, ty, Content, Context, Element, Func, NativeElement, Packed, Repr,
-    Selector, Show,
+    cast, elem, func, scope, ty, Content, Context, Element, Func, NativeElement, Packed,
+    Repr, Selector, Show, StyledElem,
 };
 use crate::introspection::Locatable;
 use crate::syntax::Span;
@@ -65,11 +65,45 @@ impl Show for Packed<StyleElem> {
     }
 }
 
-/// A list of style properties.
-#[ty(cast)]
+/// A list of style properties and show rules.
+///
+/// Styles can be passed to [`measure`] to measure content as if the rules were
+/// in effect.
+///
+/// ```example
+/// #let big = styles[#set text(16pt)]
+/// #context {
+///   let (normal, large) = (
+///     measure[Hello],
+///     measure([Hello], big),
+///   )
+///   [Grows by #(large.width - normal.width)]
+/// }
+/// ```
+#[ty(scope, cast)]
 #[derive(Default, PartialEq, Clone, Hash)]
 pub struct Styles(EcoVec<LazyHash<Style>>);
 
+#[scope]
+impl Styles {
+    /// Collects the rules of a content block into styles.
+    #[func(constructor)]
+    pub fn construct(
+        /// The call site span.
+        span: Span,
+        /// Content consisting only of set and show rules.
+        rules: Content,
+    ) -> SourceResult<Styles> {
+        if rules.is_empty() {
+            return Ok(Styles::new());
+        }
+        match rules.to_packed::<StyledElem>() {
+            Some(styled) if styled.child.is_empty() => Ok(styled.styles.clone()),
+            _ => bail!(span, "expected content consisting only of set and show rules"),
+        }
+    }
+}
+
 impl Styles {
     /// Create a new, empty style list.
     pub const fn new() -> Self {
@@ -375,6 +409,16 @@ impl Recipe {
         }
     }
 
+    /// Whether this is a `show: untrusted` rule, which restricts the code
+    /// evaluated after it.
+    pub fn is_untrusted(&self) -> bool {
+        self.selector.is_none()
+            && matches!(
+                &self.transform,
+                Transformation::Func(func) if func.is_untrusted()
+            )
+    }
+
     /// Whether the recipe is applicable to the target.
     pub fn applicable(&self, target: &Content, styles: StyleChain) -> bool {
         self.selector
@@ -1,9 +1,11 @@
 //! System-related things.
 
-use crate::foundations::{Dict, Module, Scope, Version};
+use crate::foundations::{dict, Dict, Module, Scope, Smart, Styles, Version};
+use crate::layout::{PageElem, Paper};
+use crate::text::{Lang, Region, TextElem};
 
 /// A module with system-related things.
-pub fn module(inputs: Dict) -> Module {
+pub fn module(inputs: Dict, locale: Locale) -> Module {
     let mut scope = Scope::deduplicating();
     scope.define(
         "version",
@@ -14,5 +16,54 @@ pub fn module(inputs: Dict) -> Module {
         ]),
     );
     scope.define("inputs", inputs);
+    scope.define("locale", locale.to_dict());
     Module::new("sys", scope)
 }
+
+/// Regional defaults of the environment a document is compiled in.
+///
+/// The language and region become the defaults of the `text` function and the
+/// paper becomes the default page size. Floats placed into markup or math are
+/// written with the decimal separator, while strings, including the results of
+/// `str` and `repr`, keep a period. Templates can read all of them through
+/// `sys.locale`. Fields that are not set keep Typst's usual defaults,
+/// so compiling with the default locale yields the same output everywhere.
+#[derive(Debug, Default, Copy, Clone, Hash)]
+pub struct Locale {
+    /// The default text language.
+    pub lang: Option<Lang>,
+    /// The default text region.
+    pub region: Option<Region>,
+    /// The default paper size.
+    pub paper: Option<Paper>,
+    /// The decimal separator customary in the region.
+    pub decimal_separator: Option<char>,
+}
+
+impl Locale {
+    /// The default styles implied by the locale.
+    pub fn styles(&self) -> Styles {
+        let mut styles = Styles::new();
+        if let Some(lang) = self.lang {
+            styles.set(TextElem::set_lang(lang));
+        }
+        if let Some(region) = self.region {
+            styles.set(TextElem::set_region(Some(region)));
+        }
+        if let Some(paper) = self.paper {
+            styles.set(PageElem::set_width(Smart::Custom(paper.width().into())));
+            styles.set(PageElem::set_height(Smart::Custom(paper.height().into())));
+        }
+        styles
+    }
+
+    /// The locale as a dictionary, with the defaults filled in.
+    fn to_dict(self) -> Dict {
+        dict! {
+            "lang" => self.lang.unwrap_or(Lang::ENGLISH),
+            "region" => self.region,
+            "paper" => self.paper.unwrap_or(Paper::A4),
+            "decimal-separator" => self.decimal_separator.unwrap_or('.'),
+        }
+    }
+}
@@ -18,7 +18,7 @@ use crate::layout::{Frame, FrameItem, PageElem};
 use crate::math::EquationElem;
 use crate::model::{FigureElem, HeadingElem, Numbering, NumberingPattern};
 use crate::syntax::Span;
-use crate::utils::NonZeroExt;
+use crate::utils::{hash128, NonZeroExt};
 use crate::World;
 
 /// Counts through pages, elements, and more.
@@ -223,7 +223,8 @@ impl Counter {
         location: Location,
     ) -> SourceResult<CounterState> {
         let sequence = self.sequence(engine)?;
-        let offset = engine.introspector.query_count_before(&self.selector(), location);
+        let selector = self.selector(engine.introspector);
+        let offset = engine.introspector.query_count_before(&selector, location);
         let (mut at_state, at_page) = sequence[offset].clone();
         let (mut final_state, final_page) = sequence.last().unwrap().clone();
         if self.is_page() {
@@ -245,7 +246,8 @@ impl Counter {
         location: Location,
     ) -> SourceResult<CounterState> {
         let sequence = self.sequence(engine)?;
-        let offset = engine.introspector.query_count_before(&self.selector(), location);
+        let selector = self.selector(engine.introspector);
+        let offset = engine.introspector.query_count_before(&selector, location);
         let (mut state, page) = sequence[offset].clone();
         if self.is_page() {
             let delta =
@@ -255,6 +257,130 @@ impl Counter {
         Ok(state)
     }
 
+    /// Gets the numbers to display at the given location with the given
+    /// numbering.
+    ///
+    /// For a [dependent]($dependent) numbering, the parent counter's numbers
+    /// come first and the counter's own number restarts whenever they change.
+    /// Similarly, the page counter's number is preceded by the page's section
+    /// in a [sectioned]($page.section) page numbering.
+    fn at_loc_numbered(
+        &self,
+        engine: &mut Engine,
+        location: Location,
+        numbering: &Numbering,
+    ) -> SourceResult<CounterState> {
+        let mut state = self.at_loc(engine, location)?;
+        if self.is_page() {
+            if let Some(section) = engine.introspector.page_section(location) {
+                state.0.insert(0, section);
+                return Ok(state);
+            }
+        }
+
+        let Numbering::Dependent(dependent) = numbering else { return Ok(state) };
+
+        let depth = dependent.depth();
+        let parent = &dependent.parent;
+        let base = match parent.section_start(engine, location, depth)? {
+            Some(start) => self.at_loc(engine, start)?.first(),
+            None => 0,
+        };
+
+        let mut numbers = parent.at_loc(engine, location)?.prefix(depth);
+        numbers.push(state.first().saturating_sub(base));
+        Ok(CounterState(numbers))
+    }
+
+    /// Finds the last update before the given location that changed any of
+    /// the counter's first `depth` numbers.
+    fn section_start(
+        &self,
+        engine: &mut Engine,
+        location: Location,
+        depth: usize,
+    ) -> SourceResult<Option<Location>> {
+        if depth == 0 {
+            return Ok(None);
+        }
+
+        let starts = self.section_starts_impl(
+            depth,
+            engine.world,
+            engine.introspector,
+            engine.route.track(),
+            engine.locator.track(),
+            TrackedMut::reborrow_mut(&mut engine.tracer),
+        )?;
+        let selector = self.selector(engine.introspector);
+        let offset = engine.introspector.query_count_before(&selector, location);
+        let Some(i) = starts[offset] else { return Ok(None) };
+        let elems = engine.introspector.query(&selector);
+        Ok(elems[i].location())
+    }
+
+    /// Memoized computation of the update that started the section of each
+    /// state in the counter's sequence, for `section_start`.
+    ///
+    /// Computing them all at once keeps displaying dependent numberings
+    /// linear in the number of updates.
+    #[comemo::memoize]
+    fn section_starts_impl(
+        &self,
+        depth: usize,
+        world: Tracked<dyn World + '_>,
+        introspector: Tracked<Introspector>,
+        route: Tracked<Route>,
+        locator: Tracked<Locator>,
+        tracer: TrackedMut<Tracer>,
+    ) -> SourceResult<EcoVec<Option<usize>>> {
+        let mut locator = Locator::chained(locator);
+        let mut engine = Engine {
+            world,
+            introspector,
+            route: Route::extend(route).unnested(),
+            locator: &mut locator,
+            tracer,
+        };
+
+        let sequence = self.sequence(&mut engine)?;
+        let mut start = None;
+        let mut starts = eco_vec![None];
+        for (i, pair) in sequence.windows(2).enumerate() {
+            if pair[0].0.prefix(depth) != pair[1].0.prefix(depth) {
+                start = Some(i);
+            }
+            starts.push(start);
+        }
+        Ok(starts)
+    }
+
+    /// Determines the section of a page when this counter divides the pages
+    /// into [sections]($page.section).
+    ///
+    /// This is the counter's first number after the first update on the page
+    /// or, if there is none, at the end of the previous page.
+    pub fn page_section(
+        &self,
+        engine: &mut Engine,
+        page: NonZeroUsize,
+    ) -> SourceResult<usize> {
+        let sequence = self.sequence(engine)?;
+        let elems = engine.introspector.query(&self.selector(engine.introspector));
+        let mut section = sequence[0].0.first();
+        for (elem, (state, _)) in elems.iter().zip(sequence.iter().skip(1)) {
+            let at = engine.introspector.page(elem.location().unwrap());
+            if at > page {
+                break;
+            }
+            section = state.first();
+            if at == page {
+                break;
+            }
+        }
+        Ok(section)
+    }
+
     /// Displays the value of the counter at the given location.
     pub fn display_at_loc(
         &self,
@@ -265,7 +391,7 @@ impl Counter {
     ) -> SourceResult<Content> {
         let context = Context::new(Some(loc), Some(styles));
         Ok(self
-            .at_loc(engine, loc)?
+            .at_loc_numbered(engine, loc, numbering)?
             .display(engine, context.track(), numbering)?
             .display())
     }
@@ -310,7 +436,7 @@ impl Counter {
         let mut page = NonZeroUsize::ONE;
         let mut stops = eco_vec![(state.clone(), page)];
 
-        for elem in introspector.query(&self.selector()) {
+        for elem in introspector.query(&self.selector(introspector)) {
             if self.is_page() {
                 let prev = page;
                 page = introspector.page(elem.location().unwrap());
@@ -322,7 +448,10 @@ impl Counter {
             }
 
             if let Some(update) = match elem.with::<dyn Count>() {
-                Some(countable) => countable.update(),
+                Some(countable) => match countable.shared_counter() {
+                    Some(shared) if shared != *self => None,
+                    _ => countable.update(),
+                },
                 None => Some(CounterUpdate::Step(NonZeroUsize::ONE)),
             } {
                 state.update(&mut engine, update)?;
@@ -335,18 +464,36 @@ impl Counter {
     }
 
     /// The selector relevant for this counter's updates.
-    fn selector(&self) -> Selector {
+    fn selector(&self, introspector: Tracked<Introspector>) -> Selector {
         let mut selector = select_where!(CounterUpdateElem, Key => self.0.clone());
 
         if let CounterKey::Selector(key) = &self.0 {
             selector = Selector::Or(eco_vec![selector, key.clone()]);
         }
 
+        // Equations can opt into stepping another counter than their own. We
+        // only select them for counters that some equation actually steps.
+        if self.is_shared(introspector) {
+            let shared =
+                select_where!(EquationElem, Counter => Smart::Custom(self.clone()));
+            selector = Selector::Or(eco_vec![selector, shared]);
+        }
+
         selector
     }
 
+    /// Whether any equation steps this counter instead of its own.
+    fn is_shared(&self, introspector: Tracked<Introspector>) -> bool {
+        !self.is_page()
+            && introspector.query(&EquationElem::elem().select()).iter().any(|elem| {
+                elem.with::<dyn Count>()
+                    .and_then(|countable| countable.shared_counter())
+                    .is_some_and(|shared| shared == *self)
+            })
+    }
+
     /// Whether this is the page counter.
-    fn is_page(&self) -> bool {
+    pub(crate) fn is_page(&self) -> bool {
         self.0 == CounterKey::Page
     }
 
@@ -383,7 +530,7 @@ impl Counter {
         let state = if both {
             self.both(engine, location)?
         } else {
-            self.at_loc(engine, location)?
+            self.at_loc_numbered(engine, location, &numbering)?
         };
 
         let context = Context::new(Some(location), styles);
@@ -637,6 +784,12 @@ cast! {
 pub trait Count {
     /// Get the counter update for this element.
     fn update(&self) -> Option<CounterUpdate>;
+
+    /// The counter this element steps instead of the one selecting it, if
+    /// any. Counters selecting the element otherwise ignore its update.
+    fn shared_counter(&self) -> Option<Counter> {
+        None
+    }
 }
 
 /// Counts through elements with different levels.
@@ -691,6 +844,14 @@ impl CounterState {
         self.0.first().copied().unwrap_or(1)
     }
 
+    /// Get the first `depth` numbers of the state, padded with zeros.
+    fn prefix(&self, depth: usize) -> SmallVec<[usize; 3]> {
+        let mut numbers: SmallVec<[usize; 3]> =
+            self.0.iter().copied().take(depth).collect();
+        numbers.resize(depth, 0);
+        numbers
+    }
+
     /// Display the counter state with a numbering.
     pub fn display(
         &self,
@@ -792,12 +953,17 @@ impl Show for Packed<CounterDisplayElem> {
 pub struct ManualPageCounter {
     physical: NonZeroUsize,
     logical: usize,
+    section: Option<usize>,
 }
 
 impl ManualPageCounter {
     /// Create a new fast page counter, starting at 1.
     pub fn new() -> Self {
-        Self { physical: NonZeroUsize::ONE, logical: 1 }
+        Self {
+            physical: NonZeroUsize::ONE,
+            logical: 1,
+            section: None,
+        }
     }
 
     /// Get the current physical page counter state.
@@ -832,6 +998,27 @@ impl ManualPageCounter {
         Ok(())
     }
 
+    /// Enter the section of the next page. Returns whether it differs from
+    /// the section of the previous page, in which case the page counter should
+    /// be [restarted](Self::restart).
+    pub fn enter_section(&mut self, section: Option<usize>) -> bool {
+        let changed = section.is_some() && section != self.section;
+        self.section = section;
+        changed
+    }
+
+    /// Create a located update that restarts the page counter at one, to be
+    /// placed at the top of a page.
+    pub fn restart(&self, engine: &mut Engine) -> Content {
+        let mut update = CounterUpdateElem::new(
+            CounterKey::Page,
+            CounterUpdate::Set(CounterState(smallvec![1])),
+        )
+        .pack();
+        update.set_location(engine.locator.locate(hash128(&(&update, self.physical))));
+        update
+    }
+
     /// Step past a page _boundary._
     pub fn step(&mut self) {
         self.physical = self.physical.saturating_add(1);
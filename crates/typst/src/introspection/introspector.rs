@@ -25,8 +25,13 @@ pub struct Introspector {
     /// Maps labels to their indices in the element list. We use a smallvec such
     /// that if the label is unique, we don't need to allocate.
     labels: HashMap<Label, SmallVec<[usize; 1]>>,
+    /// The last pages of elements spanning multiple pages.
+    ends: HashMap<Location, NonZeroUsize>,
     /// The page numberings, indexed by page number minus 1.
     page_numberings: Vec<Option<Numbering>>,
+    /// The sections of sectioned page numberings, indexed by page number
+    /// minus 1.
+    page_sections: Vec<Option<usize>>,
     /// Caches queries done on the introspector. This is important because
     /// even if all top-level queries are distinct, they often have shared
     /// subqueries. Example: Individual counter queries with `before` that
@@ -41,13 +46,16 @@ impl Introspector {
         self.pages = pages.len();
         self.elems.clear();
         self.labels.clear();
+        self.ends.clear();
         self.page_numberings.clear();
+        self.page_sections.clear();
         self.queries.clear();
 
         for (i, page) in pages.iter().enumerate() {
             let page_nr = NonZeroUsize::new(1 + i).unwrap();
             self.extract(&page.frame, page_nr, Transform::identity());
             self.page_numberings.push(page.numbering.clone());
+            self.page_sections.push(page.section);
         }
     }
 
@@ -61,14 +69,19 @@ impl Introspector {
                         .pre_concat(group.transform);
                     self.extract(&group.frame, page, ts);
                 }
-                FrameItem::Tag(elem)
-                    if !self.elems.contains_key(&elem.location().unwrap()) =>
-                {
+                FrameItem::Tag(elem) => {
+                    let loc = elem.location().unwrap();
+                    if self.elems.contains_key(&loc) {
+                        // Elements spanning multiple pages repeat their tag on
+                        // each page they continue on.
+                        self.ends.insert(loc, page);
+                        continue;
+                    }
+
                     let pos = pos.transform(ts);
-                    let ret = self.elems.insert(
-                        elem.location().unwrap(),
-                        (elem.clone(), Position { page, point: pos }),
-                    );
+                    let ret = self
+                        .elems
+                        .insert(loc, (elem.clone(), Position { page, point: pos }));
                     assert!(ret.is_none(), "duplicate locations");
 
                     // Build the label cache.
@@ -271,11 +284,25 @@ impl Introspector {
             .and_then(|slot| slot.as_ref())
     }
 
+    /// Gets the section of a sectioned page numbering for the given location,
+    /// if any.
+    pub fn page_section(&self, location: Location) -> Option<usize> {
+        let page = self.page(location);
+        self.page_sections.get(page.get() - 1).copied().flatten()
+    }
+
     /// Find the page number for the given location.
     pub fn page(&self, location: Location) -> NonZeroUsize {
         self.position(location).page
     }
 
+    /// Find the first and last page of the element at the given location.
+    pub fn page_span(&self, location: Location) -> (NonZeroUsize, NonZeroUsize) {
+        let start = self.page(location);
+        let end = self.ends.get(&location).copied().unwrap_or(start);
+        (start, end.max(start))
+    }
+
     /// Find the position for the given location.
     pub fn position(&self, location: Location) -> Position {
         self.elems
@@ -291,7 +318,9 @@ impl Default for Introspector {
             pages: 0,
             elems: IndexMap::new(),
             labels: HashMap::new(),
+            ends: HashMap::new(),
             page_numberings: vec![],
+            page_sections: vec![],
             queries: QueryCache::default(),
         }
     }
@@ -69,7 +69,9 @@ impl Location {
 
     /// Returns a dictionary with the page number and the x, y position for this
     /// location. The page number starts at one and the coordinates are measured
-    /// from the top-left of the page.
+    /// from the top-left of the page. On pages with a [bleed]($page.bleed),
+    /// this is the top-left of the bleed, so the coordinates are larger by
+    /// the bleed than they would be on the trimmed page.
     ///
     /// If you only need the page number, use `page()` instead as it allows
     /// Typst to skip unnecessary work.
@@ -0,0 +1,139 @@
+use std::num::NonZeroUsize;
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, Content, NativeElement, Packed, Repr, Resolve, Show, Smart, StyleChain,
+};
+use crate::layout::{Abs, Axes, BlockElem, Frame, Length, Region, Regions, Rel, Size};
+use crate::utils::Numeric;
+
+/// Asserts that the laid-out document satisfies constraints.
+///
+/// The constraints are checked after layout. If one is violated, an error is
+/// reported at the assertion. This way, templates for documents with a fixed
+/// length, like a résumé or a one-pager, can fail instead of silently spilling
+/// onto additional pages.
+///
+/// The assertion itself is invisible.
+///
+/// # Example
+/// ```example
+/// #assert-layout(max-pages: 1)
+/// = Jane Doe
+/// Typesetter with a passion for
+/// microtypography.
+/// ```
+#[elem(Show)]
+pub struct AssertLayoutElem {
+    /// The maximum number of pages the document may have.
+    pub max_pages: Option<NonZeroUsize>,
+}
+
+impl Show for Packed<AssertLayoutElem> {
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        // The page count is only known once layout has converged, so a
+        // violation is reported as a delayed error.
+        let pages = engine.introspector.pages();
+        let max = self.max_pages(styles);
+        engine.delayed(|_| {
+            if let Some(max) = max {
+                if pages > max {
+                    bail!(
+                        self.span(),
+                        "document has {pages} pages, but at most {max} {} allowed",
+                        if max.get() == 1 { "is" } else { "are" };
+                        hint: "try shortening the content or reducing its size"
+                    );
+                }
+            }
+            Ok(())
+        });
+        Ok(Content::empty())
+    }
+}
+
+/// Asserts that content fits into the space that is available for it.
+///
+/// The content is laid out as usual, but its natural size is checked against
+/// the given limits. If the content is too large, an error is reported at
+/// the assertion. The content is kept together in a single region.
+///
+/// # Example
+/// ```example
+/// #assert-fits(width: 100%)[
+///   #box(width: 40pt, fill: aqua)
+///   #box(width: 40pt, fill: teal)
+/// ]
+/// ```
+#[elem(Show)]
+pub struct AssertFitsElem {
+    /// The maximum width of the content.
+    ///
+    /// Relative lengths are resolved relative to the width of the region the
+    /// content is in. If `{auto}`, the content may not be wider than the
+    /// region.
+    pub width: Smart<Rel<Length>>,
+
+    /// The maximum height of the content.
+    ///
+    /// Relative lengths are resolved relative to the full height of the
+    /// region the content is in. If `{auto}`, the content may not be taller
+    /// than the region, i.e. it must fit onto a single page or column.
+    pub height: Smart<Rel<Length>>,
+
+    /// The content that must fit.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<AssertFitsElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_assert_fits).pack())
+    }
+}
+
+/// Layout the content and check its size.
+#[typst_macros::time(span = elem.span())]
+fn layout_assert_fits(
+    elem: &Packed<AssertFitsElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let pod = Regions::one(Size::new(region.size.x, Abs::inf()), Axes::splat(false));
+    let frame = elem.body().layout(engine, styles, pod)?.into_frame();
+
+    let limit = |sizing: Smart<Rel<Length>>, base: Abs| match sizing {
+        Smart::Auto => base,
+        Smart::Custom(rel) => rel.resolve(styles).relative_to(base),
+    };
+
+    // The size may still change while layout converges, so violations are
+    // reported as delayed errors.
+    let width = limit(elem.width(styles), region.size.x);
+    let height = limit(elem.height(styles), region.size.y);
+    engine.delayed(|_| {
+        if !width.fits(frame.width()) {
+            bail!(
+                elem.span(),
+                "content is {} wide, but at most {} fit",
+                frame.width().repr(),
+                width.repr(),
+            );
+        }
+
+        if height.is_finite() && !height.fits(frame.height()) {
+            bail!(
+                elem.span(),
+                "content is {} tall, but at most {} fit",
+                frame.height().repr(),
+                height.repr(),
+            );
+        }
+
+        Ok(())
+    });
+
+    Ok(frame)
+}
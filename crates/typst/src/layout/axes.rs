@@ -4,7 +4,7 @@ use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Deref, Not};
 
 use crate::diag::bail;
 use crate::foundations::{array, cast, Array, Resolve, Smart, StyleChain};
-use crate::layout::{Abs, Dir, Length, Ratio, Rel, Size};
+use crate::layout::{Abs, Dir, Length, Ratio, Rel, Size, Vec2};
 use crate::utils::Get;
 
 /// A container with a horizontal and vertical component.
@@ -284,6 +284,7 @@ cast! {
             _ => bail!("point array must contain exactly two entries"),
         }
     },
+    vec: Vec2 => vec.into(),
 }
 
 cast! {
@@ -308,6 +309,7 @@ cast! {
             _ => bail!("length array must contain exactly two entries"),
         }
     },
+    vec: Vec2 => vec.into(),
 }
 
 impl<T: Resolve> Resolve for Axes<T> {
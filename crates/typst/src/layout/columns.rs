@@ -101,6 +101,7 @@ fn layout_columns(
         last: regions.last,
         expand: Axes::new(true, regions.expand.y),
         root: regions.root,
+        contour: None,
     };
 
     // Layout the children.
@@ -1,3 +1,4 @@
+use ecow::EcoString;
 use once_cell::unsync::Lazy;
 use smallvec::SmallVec;
 
@@ -8,11 +9,15 @@ use crate::foundations::{
     Smart, StyleChain, Value,
 };
 use crate::layout::{
-    Abs, Axes, Corners, Em, Fr, Fragment, Frame, FrameKind, Length, Region, Regions, Rel,
-    Sides, Size, Spacing, VElem,
+    Abs, Axes, Corners, Em, Fr, Fragment, Frame, FrameItem, FrameKind, Length, Point,
+    Ratio, Region, Regions, Rel, Sides, Size, Spacing, Transform, VAlignment, VElem,
 };
-use crate::utils::Numeric;
-use crate::visualize::{clip_rect, Paint, Stroke};
+use crate::text::{families, variant, TextElem};
+use crate::utils::{Numeric, Scalar};
+use crate::visualize::{
+    clip_rect, BlendMode, Paint, PathElem, PathVertex, PolygonElem, Shadow, Stroke,
+};
+use crate::World;
 
 /// An inline-level container that sizes content.
 ///
@@ -49,13 +54,44 @@ pub struct BoxElem {
     /// The height of the box.
     pub height: Smart<Rel<Length>>,
 
-    /// An amount to shift the box's baseline by.
+    /// The ratio of the box's width to its height.
+    ///
+    /// If only one of `width` and `height` is set, the other one is derived
+    /// from it. If neither is set, the box takes the full available width. If
+    /// both are set, the ratio has no effect.
+    ///
+    /// ```example
+    /// #box(width: 3cm, ratio: 16/9, fill: aqua)
+    /// #box(height: 1cm, ratio: 1, fill: teal)
+    /// ```
+    pub ratio: Option<AspectRatio>,
+
+    /// How to determine the box's baseline.
+    ///
+    /// - A relative length shifts the box's baseline up by that amount.
+    /// - `{top}` uses the first baseline of the box's text.
+    /// - `{bottom}` uses the last baseline of the box's text.
+    /// - `{horizon}` centers the box on the math axis of the current font,
+    ///   like a fraction. For fonts without math support, the axis is at half
+    ///   of the font's x-height.
+    ///
+    /// If the box contains no text, `{top}` and `{bottom}` keep the box's
+    /// default baseline.
     ///
     /// ```example
     /// Image: #box(baseline: 40%, image("tiger.jpg", width: 2cm)).
+    ///
+    /// Stack: #box(baseline: top, stack[First][Second]) and
+    /// #box(baseline: bottom, stack[First][Second]).
+    ///
+    /// Frac: #box(baseline: horizon, stack(
+    ///   spacing: 2pt,
+    ///   [1],
+    ///   line(length: 1em),
+    ///   [2],
+    /// ))
     /// ```
-    #[resolve]
-    pub baseline: Rel<Length>,
+    pub baseline: BoxBaseline,
 
     /// The box's background color. See the
     /// [rectangle's documentation]($rect.fill) for more details.
@@ -104,10 +140,40 @@ pub struct BoxElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A drop shadow behind the box. See the [shadow] type for more details.
+    ///
+    /// The shadow follows the box's outline, including its radius and
+    /// outset, and does not affect the layout.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// Whether to clip the content inside the box.
     #[default(false)]
     pub clip: bool,
 
+    /// The opacity of the box, including its fill, stroke, shadow, and
+    /// contents.
+    ///
+    /// The box is made transparent as a whole, so overlapping parts of its
+    /// contents don't show through each other.
+    ///
+    /// ```example
+    /// #box(opacity: 50%, fill: aqua, inset: 4pt)[
+    ///   #box(fill: red, inset: 4pt)[Nested]
+    /// ]
+    /// ```
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// How the colors of the box are blended with the content beneath it.
+    ///
+    /// ```example
+    /// #set text(size: 16pt)
+    /// #box(fill: yellow)[Highlight]
+    /// #box(fill: yellow, blend-mode: "multiply")[Highlight]
+    /// ```
+    pub blend_mode: BlendMode,
+
     /// The contents of the box.
     #[positional]
     #[borrowed]
@@ -128,8 +194,10 @@ impl Packed<BoxElem> {
         let height = self.height(styles);
         let inset = self.inset(styles).unwrap_or_default();
 
+        let ratio = self.ratio(styles);
+
         // Build the pod region.
-        let pod = Self::pod(&width, &height, &inset, styles, region);
+        let pod = Self::pod(&width, &height, ratio, &inset, styles, region);
 
         // Layout the body.
         let mut frame = match self.body(styles) {
@@ -154,12 +222,27 @@ impl Packed<BoxElem> {
             crate::layout::grow(&mut frame, &inset);
         }
 
-        // Apply baseline shift. Do this after setting the size and applying the
+        // Apply the baseline. Do this after setting the size and applying the
         // inset, so that a relative shift is resolved relative to the final
-        // height.
-        let shift = self.baseline(styles).relative_to(frame.height());
-        if !shift.is_zero() {
-            frame.set_baseline(frame.baseline() - shift);
+        // height and the text baselines include the inset.
+        match self.baseline(styles) {
+            BoxBaseline::Shift(shift) => {
+                let shift = shift.resolve(styles).relative_to(frame.height());
+                if !shift.is_zero() {
+                    frame.set_baseline(frame.baseline() - shift);
+                }
+            }
+            BoxBaseline::Align(VAlignment::Horizon) => {
+                let axis = math_axis(engine, styles).at(TextElem::size_in(styles));
+                frame.set_baseline(frame.height() / 2.0 + axis);
+            }
+            BoxBaseline::Align(align) => {
+                if let Some((first, last)) = text_baselines(&frame, Transform::identity())
+                {
+                    let last_line = align == VAlignment::Bottom;
+                    frame.set_baseline(if last_line { last } else { first });
+                }
+            }
         }
 
         // Prepare fill and stroke.
@@ -169,6 +252,8 @@ impl Packed<BoxElem> {
             .unwrap_or_default()
             .map(|s| s.map(Stroke::unwrap_or_default));
 
+        let shadow = self.shadow(styles);
+
         // Only fetch these if necessary (for clipping or filling/stroking).
         let outset = Lazy::new(|| self.outset(styles).unwrap_or_default());
         let radius = Lazy::new(|| self.radius(styles).unwrap_or_default());
@@ -184,6 +269,15 @@ impl Packed<BoxElem> {
             frame.fill_and_stroke(fill, &stroke, &outset, &radius, self.span());
         }
 
+        // Add the shadow below everything else.
+        if let Some(shadow) = &shadow {
+            frame.shadow(shadow, &outset, &radius, self.span());
+        }
+
+        // Composite the box as a whole.
+        let opacity = self.opacity(styles).clamp(Ratio::zero(), Ratio::one());
+        frame.composite(opacity, self.blend_mode(styles));
+
         Ok(frame)
     }
 
@@ -191,6 +285,7 @@ impl Packed<BoxElem> {
     fn pod(
         width: &Sizing,
         height: &Smart<Rel>,
+        ratio: Option<AspectRatio>,
         inset: &Sides<Rel<Abs>>,
         styles: StyleChain,
         region: Size,
@@ -213,15 +308,23 @@ impl Packed<BoxElem> {
             },
         );
 
+        // If the child is not auto-sized, the size is forced and we should
+        // enable expansion.
+        let mut expand = Axes::new(*width != Sizing::Auto, *height != Smart::Auto);
+
+        // Derive the missing dimension from the aspect ratio.
+        if let Some(derived) = ratio.and_then(|ratio| {
+            ratio.derive(expand.x.then_some(size.x), expand.y.then_some(size.y), region.x)
+        }) {
+            size = derived;
+            expand = Axes::splat(true);
+        }
+
         // Take the inset, if any, into account.
         if !inset.is_zero() {
             size = crate::layout::shrink(size, inset);
         }
 
-        // If the child is not auto-sized, the size is forced and we should
-        // enable expansion.
-        let expand = Axes::new(*width != Sizing::Auto, *height != Smart::Auto);
-
         Region::new(size, expand)
     }
 }
@@ -339,6 +442,23 @@ pub struct BlockElem {
     /// ```
     pub height: Smart<Rel<Length>>,
 
+    /// The ratio of the block's width to its height.
+    ///
+    /// Works like the [box's ratio]($box.ratio): Without an explicit size,
+    /// the block takes the full available width and derives its height from
+    /// it. This is useful for placeholders that should keep their shape in
+    /// columns of any width.
+    ///
+    /// ```example
+    /// #grid(
+    ///   columns: (1fr, 2fr),
+    ///   gutter: 6pt,
+    ///   block(ratio: 16/9, fill: luma(200)),
+    ///   block(ratio: 16/9, fill: luma(200)),
+    /// )
+    /// ```
+    pub ratio: Option<AspectRatio>,
+
     /// Whether the block can be broken and continue on the next page.
     ///
     /// ```example
@@ -381,6 +501,13 @@ pub struct BlockElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// A drop shadow behind the block. See the [shadow] type for more details.
+    ///
+    /// The shadow follows the block's outline, including its radius and
+    /// outset, and does not affect the layout.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The spacing around this block. This is shorthand to set `above` and
     /// `below` to the same value.
     ///
@@ -426,10 +553,48 @@ pub struct BlockElem {
     #[default(VElem::block_spacing(Em::new(1.2).into()))]
     pub below: VElem,
 
+    /// A [polygon] or [path] that bounds the lines of the block's paragraphs.
+    ///
+    /// The contour's coordinates are relative to the block's content area,
+    /// that is, inside of its inset. Each line of text only extends over the
+    /// horizontal span of the contour at its height, which makes it possible
+    /// to pour text into a circle or set it along a diagonal cut. Curves are
+    /// approximated by straight segments. Lines above and below the contour
+    /// use the full width.
+    ///
+    /// Paragraphs set into a contour are always broken into lines in
+    /// [simple]($par.linebreaks) style. The contour applies to the
+    /// paragraphs directly in the block, but not to nested containers.
+    ///
+    /// ```example
+    /// #block(
+    ///   width: 100%,
+    ///   height: 90pt,
+    ///   contour: polygon(
+    ///     (50%, 0%),
+    ///     (100%, 100%),
+    ///     (0%, 100%),
+    ///   ),
+    /// )[
+    ///   #set align(center)
+    ///   #lorem(24)
+    /// ]
+    /// ```
+    pub contour: Option<Contour>,
+
     /// Whether to clip the content inside the block.
     #[default(false)]
     pub clip: bool,
 
+    /// The opacity of the block, including its fill, stroke, shadow, and
+    /// contents. See the [box's documentation]($box.opacity) for more details.
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// How the colors of the block are blended with the content beneath it.
+    /// See the [box's documentation]($box.blend-mode) for more details.
+    pub blend_mode: BlendMode,
+
     /// Whether this block must stick to the following one.
     ///
     /// Use this to prevent page breaks between e.g. a heading and its body.
@@ -444,6 +609,12 @@ pub struct BlockElem {
     #[parse(None)]
     pub rootable: bool,
 
+    /// A textual description of the block's contents for assistive
+    /// technology.
+    #[internal]
+    #[parse(None)]
+    pub alt: Option<EcoString>,
+
     /// The contents of the block.
     #[positional]
     #[borrowed]
@@ -502,6 +673,19 @@ impl Packed<BlockElem> {
         let inset = self.inset(styles).unwrap_or_default();
         let breakable = self.breakable(styles);
 
+        // Derive the missing dimension from the aspect ratio. The derived
+        // size is then treated as if it was set explicitly.
+        let (width, height) = match self.ratio(styles).and_then(|ratio| {
+            let base = regions.base();
+            let resolve = |sizing: Smart<Rel>, base: Abs| {
+                sizing.custom().map(|rel| rel.resolve(styles).relative_to(base))
+            };
+            ratio.derive(resolve(width, base.x), resolve(height, base.y), regions.size.x)
+        }) {
+            Some(size) => (Smart::Custom(size.x.into()), Smart::Custom(size.y.into())),
+            None => (width, height),
+        };
+
         // Allocate a small vector for backlogs.
         let mut buf = SmallVec::<[Abs; 2]>::new();
 
@@ -509,6 +693,11 @@ impl Packed<BlockElem> {
         let pod =
             Self::pod(&width, &height, &inset, breakable, styles, regions, &mut buf);
 
+        // Resolve the contour relative to the content area.
+        let contour = self
+            .contour(styles)
+            .map(|contour| contour.resolve(styles, pod.base()));
+
         // Layout the body.
         let body = self.body(styles);
         let mut fragment = match body {
@@ -530,6 +719,7 @@ impl Packed<BlockElem> {
 
             // If we have content as our body, just layout it.
             Some(BlockChild::Content(body)) => {
+                let pod = Regions { contour: contour.as_deref(), ..pod };
                 let mut fragment = body.measure(engine, styles, pod)?;
 
                 // If the body is automatically sized and produced more than one
@@ -587,6 +777,7 @@ impl Packed<BlockElem> {
             .stroke(styles)
             .unwrap_or_default()
             .map(|s| s.map(Stroke::unwrap_or_default));
+        let shadow = self.shadow(styles);
 
         // Only fetch these if necessary (for clipping or filling/stroking).
         let outset = Lazy::new(|| self.outset(styles).unwrap_or_default());
@@ -595,6 +786,10 @@ impl Packed<BlockElem> {
         // Fetch/compute these outside of the loop.
         let clip = self.clip(styles);
         let has_fill_or_stroke = fill.is_some() || stroke.iter().any(Option::is_some);
+        let has_background = has_fill_or_stroke || shadow.is_some();
+        let opacity = self.opacity(styles).clamp(Ratio::zero(), Ratio::one());
+        let blend_mode = self.blend_mode(styles);
+        let alt = self.alt(styles);
         let has_inset = !inset.is_zero();
         let is_explicit = matches!(body, None | Some(BlockChild::Content(_)));
 
@@ -602,7 +797,7 @@ impl Packed<BlockElem> {
         // one follows.
         let mut skip_first = false;
         if let [first, rest @ ..] = fragment.as_slice() {
-            skip_first = has_fill_or_stroke
+            skip_first = has_background
                 && first.is_empty()
                 && rest.iter().any(|frame| !frame.is_empty());
         }
@@ -639,6 +834,21 @@ impl Packed<BlockElem> {
                     self.span(),
                 );
             }
+
+            // Add the shadow below everything else.
+            if let Some(shadow) = &shadow {
+                if i > 0 || !skip_first {
+                    frame.shadow(shadow, &outset, &radius, self.span());
+                }
+            }
+
+            // Composite the block as a whole.
+            frame.composite(opacity, blend_mode);
+
+            // Describe the block's contents.
+            if let Some(alt) = &alt {
+                frame.describe(alt.clone());
+            }
         }
 
         Ok(fragment)
@@ -751,6 +961,7 @@ impl Packed<BlockElem> {
             // `rootable`. It is important that we propagate this, so that
             // columns can hold footnotes.
             root: regions.root,
+            contour: None,
         }
     }
 }
@@ -783,6 +994,105 @@ cast! {
     v: Content => Self::Content(v),
 }
 
+/// The ratio of a container's width to its height.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct AspectRatio(Scalar);
+
+impl AspectRatio {
+    /// The ratio as a number.
+    pub fn get(self) -> f64 {
+        self.0.get()
+    }
+
+    /// Derive a container's size from its resolved width and height, if they
+    /// are set, and the available width.
+    ///
+    /// Returns `None` if both dimensions are set or the derived size would be
+    /// infinite.
+    fn derive(
+        self,
+        width: Option<Abs>,
+        height: Option<Abs>,
+        available: Abs,
+    ) -> Option<Size> {
+        let ratio = self.get();
+        let size = match (width, height) {
+            (Some(_), Some(_)) => return None,
+            (Some(width), None) => Size::new(width, width / ratio),
+            (None, Some(height)) => Size::new(height * ratio, height),
+            (None, None) => Size::new(available, available / ratio),
+        };
+        size.all(|v| v.is_finite()).then_some(size)
+    }
+}
+
+cast! {
+    AspectRatio,
+    self => self.get().into_value(),
+    v: f64 => {
+        if !v.is_finite() || v <= 0.0 {
+            bail!("aspect ratio must be positive");
+        }
+        Self(Scalar::new(v))
+    },
+}
+
+/// A polygon or path that bounds lines of text.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Contour(Vec<PathVertex>);
+
+impl Contour {
+    /// Resolve the contour into a polygon within a region of the given size.
+    fn resolve(&self, styles: StyleChain, size: Size) -> Vec<Point> {
+        /// Into how many segments each curve is split.
+        const STEPS: usize = 8;
+
+        let resolve = |axes: Axes<Rel<Length>>| {
+            axes.resolve(styles).zip_map(size, Rel::relative_to).to_point()
+        };
+
+        let mut points = vec![];
+        for (i, vertex) in self.0.iter().enumerate() {
+            let next = &self.0[(i + 1) % self.0.len()];
+            let from = resolve(vertex.vertex());
+            let to = resolve(next.vertex());
+            let c1 = from + resolve(vertex.control_point_from());
+            let c2 = to + resolve(next.control_point_to());
+            points.push(from);
+            if c1 == from && c2 == to {
+                continue;
+            }
+
+            for step in 1..STEPS {
+                let t = step as f64 / STEPS as f64;
+                let u = 1.0 - t;
+                points.push(
+                    from * (u * u * u)
+                        + c1 * (3.0 * u * u * t)
+                        + c2 * (3.0 * u * t * t)
+                        + to * (t * t * t),
+                );
+            }
+        }
+
+        points
+    }
+}
+
+cast! {
+    Contour,
+    self => PathElem::new(self.0).with_closed(true).pack().into_value(),
+    v: Content => {
+        if let Some(polygon) = v.to_packed::<PolygonElem>() {
+            Self(polygon.vertices().iter().copied().map(PathVertex::Vertex).collect())
+        } else if let Some(path) = v.to_packed::<PathElem>() {
+            Self(path.vertices().clone())
+        } else {
+            bail!("expected polygon or path");
+        }
+    },
+}
+
 /// Defines how to size something along an axis.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Sizing {
@@ -843,6 +1153,81 @@ cast! {
     v: Fr => Self::Fr(v),
 }
 
+/// Defines how to determine the baseline of a box.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BoxBaseline {
+    /// Shift the baseline up by an amount.
+    Shift(Rel<Length>),
+    /// Use the first or last baseline of the contents or the math axis.
+    Align(VAlignment),
+}
+
+impl Default for BoxBaseline {
+    fn default() -> Self {
+        Self::Shift(Rel::zero())
+    }
+}
+
+cast! {
+    BoxBaseline,
+    self => match self {
+        Self::Shift(shift) => shift.into_value(),
+        Self::Align(align) => align.into_value(),
+    },
+    v: Rel<Length> => Self::Shift(v),
+    v: VAlignment => Self::Align(v),
+}
+
+/// The height of the math axis above the baseline in the current text font.
+///
+/// Fonts without a math table have no axis, so half of their x-height is used
+/// instead, which is where they place the bar of a minus sign.
+fn math_axis(engine: &Engine, styles: StyleChain) -> Em {
+    let world = engine.world;
+    let Some(font) = families(styles).find_map(|family| {
+        world
+            .book()
+            .select(family, variant(styles))
+            .and_then(|id| world.font(id))
+    }) else {
+        return Em::new(0.25);
+    };
+
+    font.ttf()
+        .tables()
+        .math
+        .and_then(|math| math.constants)
+        .map(|constants| font.to_em(constants.axis_height().value))
+        .unwrap_or(font.metrics().x_height / 2.0)
+}
+
+/// Finds the vertical positions of the topmost and bottommost text baselines
+/// in a frame.
+pub(crate) fn text_baselines(frame: &Frame, ts: Transform) -> Option<(Abs, Abs)> {
+    let mut bounds: Option<(Abs, Abs)> = None;
+    let mut extend = |y: Abs| {
+        bounds = Some(bounds.map_or((y, y), |(min, max)| (min.min(y), max.max(y))));
+    };
+
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Text(_) => extend(pos.transform(ts).y),
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                if let Some((first, last)) = text_baselines(&group.frame, ts) {
+                    extend(first);
+                    extend(last);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    bounds
+}
+
 /// Distribute a fixed height spread over existing regions into a new first
 /// height and a new backlog.
 fn distribute<'a>(
@@ -14,11 +14,14 @@ use crate::foundations::{
 use crate::introspection::TagElem;
 use crate::layout::{
     Abs, AlignElem, Axes, BlockElem, ColbreakElem, FixedAlignment, FlushElem, Fr,
-    Fragment, Frame, FrameItem, PlaceElem, Point, Regions, Rel, Size, Spacing, VElem,
+    Fragment, Frame, FrameItem, PageElem, PlaceElem, Point, Regions, Rel, Size, Spacing,
+    VElem,
 };
 use crate::model::{FootnoteElem, FootnoteEntry, ParElem};
 use crate::realize::StyleVec;
+use crate::text::{families, variant, TextElem};
 use crate::utils::Numeric;
+use crate::World;
 
 /// Arranges spacing, paragraphs and block-level elements into a flow.
 ///
@@ -111,6 +114,8 @@ struct FlowLayouter<'a> {
     styles: StyleChain<'a>,
     /// Whether the flow should expand to fill the region.
     expand: Axes<bool>,
+    /// Whether full regions are justified vertically.
+    justify: bool,
     /// The initial size of `regions.size` that was available before we started
     /// subtracting.
     initial: Size,
@@ -144,6 +149,9 @@ struct FootnoteConfig {
 enum FlowItem {
     /// Spacing between other items and whether it is weak.
     Absolute(Abs, bool),
+    /// Leading between the lines of a paragraph. It is weak, but doesn't
+    /// stretch when a region is justified.
+    Leading(Abs),
     /// Fractional spacing between other items.
     Fractional(Fr),
     /// A frame for a layouted block.
@@ -207,6 +215,7 @@ impl<'a> FlowLayouter<'a> {
             regions,
             styles,
             expand,
+            justify: root && PageElem::justify_in(styles),
             initial: regions.size,
             last_was_par: false,
             items: vec![],
@@ -256,6 +265,10 @@ impl<'a> FlowLayouter<'a> {
         let align = AlignElem::alignment_in(styles).resolve(styles);
         let leading = ParElem::leading_in(styles);
         let consecutive = self.last_was_par;
+        let contour = self
+            .regions
+            .contour
+            .map(|contour| (contour, self.initial.y - self.regions.size.y));
         let lines = par
             .layout(
                 engine,
@@ -263,6 +276,7 @@ impl<'a> FlowLayouter<'a> {
                 consecutive,
                 self.regions.base(),
                 self.regions.expand.x,
+                contour,
             )?
             .into_frames();
 
@@ -273,7 +287,7 @@ impl<'a> FlowLayouter<'a> {
                 let mut sticky = self.items.len();
                 for (i, item) in self.items.iter().enumerate().rev() {
                     match *item {
-                        FlowItem::Absolute(_, _) => {}
+                        FlowItem::Absolute(_, _) | FlowItem::Leading(_) => {}
                         FlowItem::Frame { sticky: true, .. } => sticky = i,
                         _ => break,
                     }
@@ -295,7 +309,7 @@ impl<'a> FlowLayouter<'a> {
 
         for (i, mut frame) in lines.into_iter().enumerate() {
             if i > 0 {
-                self.layout_item(engine, FlowItem::Absolute(leading, true))?;
+                self.layout_item(engine, FlowItem::Leading(leading))?;
             }
 
             self.drain_tag(&mut frame);
@@ -335,6 +349,17 @@ impl<'a> FlowLayouter<'a> {
         // How to align the block.
         let align = AlignElem::alignment_in(styles).resolve(styles);
 
+        // The block itself spans all of its regions. Other pending tags, like
+        // page counter updates, must not be repeated.
+        let spanning: Vec<_> = self
+            .pending_tags
+            .iter()
+            .filter(|elem| {
+                elem.location().is_some() && elem.location() == block.location()
+            })
+            .cloned()
+            .collect();
+
         let mut notes = Vec::new();
         for (i, mut frame) in fragment.into_iter().enumerate() {
             // Find footnotes in the frame.
@@ -344,6 +369,16 @@ impl<'a> FlowLayouter<'a> {
 
             if i > 0 {
                 self.finish_region(engine, false)?;
+
+                // Repeat the tags so that the introspector knows on which
+                // page the elements end.
+                if !frame.is_empty() {
+                    frame.prepend_multiple(
+                        spanning
+                            .iter()
+                            .map(|elem| (Point::zero(), FrameItem::Tag(elem.clone()))),
+                    );
+                }
             }
 
             self.drain_tag(&mut frame);
@@ -413,6 +448,12 @@ impl<'a> FlowLayouter<'a> {
                 }
                 self.regions.size.y -= v
             }
+            FlowItem::Leading(v) => {
+                if !self.items.iter().any(|item| matches!(item, FlowItem::Frame { .. })) {
+                    return Ok(());
+                }
+                self.regions.size.y -= v
+            }
             FlowItem::Fractional(..) => {}
             FlowItem::Frame { ref frame, movable, .. } => {
                 let height = frame.height();
@@ -493,6 +534,21 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// How far the last in-flow frame extends below the last baseline of its
+    /// text. Frames without text end at their last baseline.
+    fn last_depth(&self) -> Abs {
+        self.items
+            .iter()
+            .rev()
+            .find_map(|item| match item {
+                FlowItem::Frame { frame, .. } if !item.is_out_of_flow() => {
+                    Some(last_baseline(frame).map_or(Abs::zero(), |y| frame.height() - y))
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
     /// Finish the frame for one region.
     ///
     /// Set `force` to `true` to allow creating a frame for out-of-flow elements
@@ -510,11 +566,9 @@ impl<'a> FlowLayouter<'a> {
         }
 
         // Trim weak spacing.
-        while self
-            .items
-            .last()
-            .is_some_and(|item| matches!(item, FlowItem::Absolute(_, true)))
-        {
+        while self.items.last().is_some_and(|item| {
+            matches!(item, FlowItem::Absolute(_, true) | FlowItem::Leading(_))
+        }) {
             self.items.pop();
         }
 
@@ -525,9 +579,16 @@ impl<'a> FlowLayouter<'a> {
         let mut float_top_height = Abs::zero();
         let mut float_bottom_height = Abs::zero();
         let mut first_footnote = true;
+        let mut flexible = Abs::zero();
         for item in &self.items {
             match item {
-                FlowItem::Absolute(v, _) => used.y += *v,
+                FlowItem::Absolute(v, weak) => {
+                    used.y += *v;
+                    if *weak {
+                        flexible += *v;
+                    }
+                }
+                FlowItem::Leading(v) => used.y += *v,
                 FlowItem::Fractional(v) => fr += *v,
                 FlowItem::Frame { frame, .. } => {
                     used.y += frame.height();
@@ -555,6 +616,25 @@ impl<'a> FlowLayouter<'a> {
         }
         used.y += footnote_height + float_top_height + float_bottom_height;
 
+        // If the region is full and should be justified, stretch the weak
+        // spacing proportionally so that the last baseline lands where the
+        // baseline of a line of body text ending flush with the region would.
+        // This way, the last baselines of all full regions line up.
+        let mut stretch = 0.0;
+        if self.justify
+            && !force
+            && fr.is_zero()
+            && flexible > Abs::zero()
+            && self.initial.y.is_finite()
+        {
+            let gap = self.initial.y - used.y + self.last_depth()
+                - text_depth(engine, self.styles);
+            if gap > Abs::zero() {
+                stretch = gap / flexible;
+                used.y += gap;
+            }
+        }
+
         // Determine the size of the flow in this region depending on whether
         // the region expands. Also account for fractional spacing and
         // footnotes.
@@ -573,7 +653,13 @@ impl<'a> FlowLayouter<'a> {
         // Place all frames.
         for item in self.items.drain(..) {
             match item {
-                FlowItem::Absolute(v, _) => {
+                FlowItem::Absolute(v, weak) => {
+                    offset += v;
+                    if weak {
+                        offset += v * stretch;
+                    }
+                }
+                FlowItem::Leading(v) => {
                     offset += v;
                 }
                 FlowItem::Fractional(v) => {
@@ -813,3 +899,37 @@ fn find_footnotes(notes: &mut Vec<Packed<FootnoteElem>>, frame: &Frame) {
         }
     }
 }
+
+/// How far a line of text in the given style extends below its baseline, as
+/// determined by the text's bottom edge and the first available font.
+fn text_depth(engine: &Engine, styles: StyleChain) -> Abs {
+    let world = engine.world;
+    let variant = variant(styles);
+    families(styles)
+        .find_map(|family| {
+            world.book().select(family, variant).and_then(|id| world.font(id))
+        })
+        .map(|font| {
+            -TextElem::bottom_edge_in(styles).resolve(
+                TextElem::size_in(styles),
+                &font,
+                None,
+            )
+        })
+        .unwrap_or_default()
+        .max(Abs::zero())
+}
+
+/// The position of the lowest baseline of the text in a frame, if any.
+fn last_baseline(frame: &Frame) -> Option<Abs> {
+    frame
+        .items()
+        .filter_map(|(pos, item)| match item {
+            FrameItem::Text(_) => Some(pos.y),
+            FrameItem::Group(group) if group.transform.is_identity() => {
+                last_baseline(&group.frame).map(|y| pos.y + y)
+            }
+            _ => None,
+        })
+        .reduce(Abs::max)
+}
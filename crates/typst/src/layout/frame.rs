@@ -4,19 +4,21 @@ use std::fmt::{self, Debug, Formatter};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
+use ecow::EcoString;
 use smallvec::SmallVec;
 
 use crate::foundations::{cast, dict, Content, Dict, StyleChain, Value};
 use crate::layout::{
-    Abs, Axes, Corners, FixedAlignment, HideElem, Length, Point, Rel, Sides, Size,
-    Transform,
+    Abs, Axes, Corners, ExportOnlyElem, ExportTarget, FixedAlignment, HideElem, Length,
+    Point, Ratio, Rel, Sides, Size, Transform,
 };
 use crate::model::{Destination, LinkElem};
 use crate::syntax::Span;
 use crate::text::TextItem;
 use crate::utils::{LazyHash, Numeric};
 use crate::visualize::{
-    ellipse, styled_rect, Color, FixedStroke, Geometry, Image, Paint, Path, Shape,
+    ellipse, styled_rect, BlendMode, Color, FixedStroke, Geometry, Image, Paint, Path,
+    Shadow, Shape,
 };
 
 /// A finished layout with items at fixed positions.
@@ -324,12 +326,18 @@ impl Frame {
             self.post_process_raw(
                 LinkElem::dests_in(styles),
                 HideElem::hidden_in(styles),
+                ExportOnlyElem::restriction_in(styles),
             );
         }
     }
 
     /// Apply raw late-stage properties from the raw data.
-    pub fn post_process_raw(&mut self, dests: SmallVec<[Destination; 1]>, hide: bool) {
+    pub fn post_process_raw(
+        &mut self,
+        dests: SmallVec<[Destination; 1]>,
+        hide: bool,
+        restriction: Option<Vec<ExportTarget>>,
+    ) {
         if !self.is_empty() {
             let size = self.size;
             self.push_multiple(
@@ -340,6 +348,144 @@ impl Frame {
             if hide {
                 self.hide();
             }
+            if let Some(targets) = restriction {
+                self.group(|group| group.targets = Some(targets));
+            }
+        }
+    }
+
+    /// Remove all content that is restricted to other export targets.
+    ///
+    /// The frame's size and the position of the remaining content stay the
+    /// same.
+    pub fn for_target(&self, target: ExportTarget) -> Frame {
+        if !self.is_restricted() {
+            return self.clone();
+        }
+
+        let mut frame = self.clone();
+        frame.retain_target(target);
+        frame
+    }
+
+    /// Whether the frame contains content restricted to some export targets.
+    fn is_restricted(&self) -> bool {
+        self.items().any(|(_, item)| match item {
+            FrameItem::Group(group) => {
+                group.targets.is_some() || group.frame.is_restricted()
+            }
+            _ => false,
+        })
+    }
+
+    /// Remove all content that is restricted to other export targets in place.
+    fn retain_target(&mut self, target: ExportTarget) {
+        Arc::make_mut(&mut self.items).retain_mut(|(_, item)| match item {
+            FrameItem::Group(group) => {
+                if group
+                    .targets
+                    .as_ref()
+                    .is_some_and(|targets| !targets.contains(&target))
+                {
+                    return false;
+                }
+                if group.frame.is_restricted() {
+                    group.frame.retain_target(target);
+                }
+                true
+            }
+            _ => true,
+        });
+    }
+
+    /// Visit the text items of the frame and its untransformed groups with
+    /// their positions relative to the frame.
+    pub fn visit_text(&self, f: &mut impl FnMut(Point, &TextItem)) {
+        self.visit_text_at(Point::zero(), f);
+    }
+
+    fn visit_text_at(&self, offset: Point, f: &mut impl FnMut(Point, &TextItem)) {
+        for (pos, item) in self.items() {
+            match item {
+                FrameItem::Group(group) if group.transform.is_identity() => {
+                    group.frame.visit_text_at(offset + *pos, f)
+                }
+                FrameItem::Text(text) => f(offset + *pos, text),
+                _ => {}
+            }
+        }
+    }
+
+    /// Replace runs of consecutive text items in the frame and its
+    /// untransformed groups.
+    ///
+    /// A run consists of text items for which `key` returns the same value.
+    /// The closure receives each run with the items' positions relative to the
+    /// frame and returns a replacement, also positioned relative to the frame.
+    pub fn map_text_runs<K: PartialEq>(
+        &mut self,
+        key: &mut impl FnMut(&TextItem) -> Option<K>,
+        f: &mut impl FnMut(Vec<(Point, TextItem)>) -> (Point, FrameItem),
+    ) {
+        self.map_text_runs_at(Point::zero(), key, f);
+    }
+
+    fn map_text_runs_at<K: PartialEq>(
+        &mut self,
+        offset: Point,
+        key: &mut impl FnMut(&TextItem) -> Option<K>,
+        f: &mut impl FnMut(Vec<(Point, TextItem)>) -> (Point, FrameItem),
+    ) {
+        let items = Arc::make_mut(&mut self.items);
+        let mut output = Vec::with_capacity(items.len());
+        let mut run = vec![];
+        let mut run_key = None;
+
+        for (pos, mut item) in std::mem::take(&mut **items) {
+            if let FrameItem::Group(group) = &mut item {
+                if group.transform.is_identity() {
+                    group.frame.map_text_runs_at(offset + pos, key, f);
+                }
+            }
+
+            let item_key = match &item {
+                FrameItem::Text(text) => key(text),
+                _ => None,
+            };
+
+            if !run.is_empty() && item_key != run_key {
+                let (run_pos, run_item) = f(std::mem::take(&mut run));
+                output.push((run_pos - offset, run_item));
+            }
+
+            match item {
+                FrameItem::Text(text) if item_key.is_some() => {
+                    run.push((offset + pos, text));
+                    run_key = item_key;
+                }
+                item => output.push((pos, item)),
+            }
+        }
+
+        if !run.is_empty() {
+            let (run_pos, run_item) = f(run);
+            output.push((run_pos - offset, run_item));
+        }
+
+        **items = output;
+    }
+
+    /// Visit the frames of the transformed groups in the frame and its
+    /// untransformed groups.
+    pub fn visit_transformed_mut(&mut self, f: &mut impl FnMut(&mut Frame)) {
+        for (_, item) in Arc::make_mut(&mut self.items).iter_mut() {
+            if let FrameItem::Group(group) = item {
+                if group.transform.is_identity() {
+                    group.frame.visit_transformed_mut(f);
+                } else {
+                    f(&mut group.frame);
+                }
+            }
         }
     }
 
@@ -355,11 +501,11 @@ impl Frame {
         });
     }
 
-    /// Add a background fill.
-    pub fn fill(&mut self, fill: Paint) {
+    /// Add a background fill that originates from the given span.
+    pub fn fill(&mut self, fill: Paint, span: Span) {
         self.prepend(
             Point::zero(),
-            FrameItem::Shape(Geometry::Rect(self.size()).filled(fill), Span::detached()),
+            FrameItem::Shape(Geometry::Rect(self.size()).filled(fill), span),
         );
     }
 
@@ -382,6 +528,29 @@ impl Frame {
         )
     }
 
+    /// Add a drop shadow with optional radius and outset below the frame's
+    /// contents.
+    pub fn shadow(
+        &mut self,
+        shadow: &Shadow<Abs>,
+        outset: &Sides<Rel<Abs>>,
+        radius: &Corners<Rel<Abs>>,
+        span: Span,
+    ) {
+        let outset = outset.relative_to(self.size());
+        let size = self.size() + outset.sum_by_axis();
+        let pos = Point::new(shadow.dx - outset.left, shadow.dy - outset.top);
+        let fill = Some(Paint::Solid(shadow.color));
+        self.prepend_multiple(
+            styled_rect(size, radius, fill, &Sides::splat(None)).into_iter().map(
+                |mut shape| {
+                    shape.blur = shadow.blur;
+                    (pos, FrameItem::Shape(shape, span))
+                },
+            ),
+        )
+    }
+
     /// Arbitrarily transform the contents of the frame.
     pub fn transform(&mut self, transform: Transform) {
         if !self.is_empty() {
@@ -400,6 +569,27 @@ impl Frame {
         }
     }
 
+    /// Composite the contents of the frame as a whole onto the content beneath
+    /// it with an opacity and blend mode.
+    pub fn composite(&mut self, opacity: Ratio, blend_mode: BlendMode) {
+        if !self.is_empty()
+            && (opacity != Ratio::one() || blend_mode != BlendMode::Normal)
+        {
+            self.group(|g| {
+                g.opacity = opacity;
+                g.blend_mode = blend_mode;
+            });
+        }
+    }
+
+    /// Attach a textual description of the frame's contents for assistive
+    /// technology.
+    pub fn describe(&mut self, alt: EcoString) {
+        if !self.is_empty() {
+            self.group(|g| g.alt = Some(alt));
+        }
+    }
+
     /// Wrap the frame's contents in a group and modify that group with `f`.
     fn group<F>(&mut self, f: F)
     where
@@ -547,6 +737,16 @@ pub struct GroupItem {
     pub transform: Transform,
     /// Whether the frame should be a clipping boundary.
     pub clip_path: Option<Path>,
+    /// The opacity with which the group is composited onto the content
+    /// beneath it.
+    pub opacity: Ratio,
+    /// How the group's colors are blended with the content beneath it.
+    pub blend_mode: BlendMode,
+    /// A textual description of the group's contents for assistive
+    /// technology.
+    pub alt: Option<EcoString>,
+    /// The export targets the group is restricted to, if any.
+    pub targets: Option<Vec<ExportTarget>>,
 }
 
 impl GroupItem {
@@ -556,6 +756,10 @@ impl GroupItem {
             frame,
             transform: Transform::identity(),
             clip_path: None,
+            opacity: Ratio::one(),
+            blend_mode: BlendMode::Normal,
+            alt: None,
+            targets: None,
         }
     }
 }
@@ -2,7 +2,7 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use comemo::Track;
-use ecow::eco_format;
+use ecow::{eco_format, EcoString};
 
 use super::lines::Line;
 use super::repeated::{Footer, Header, Repeatable};
@@ -12,7 +12,9 @@ use crate::foundations::{
     Array, CastInfo, Content, Context, Fold, FromValue, Func, IntoValue, Reflect,
     Resolve, Smart, StyleChain, Value,
 };
-use crate::layout::{Abs, Alignment, Axes, Length, LinePosition, Rel, Sides, Sizing};
+use crate::layout::{
+    Abs, Alignment, Axes, GridAreas, Length, LinePosition, Rel, Sides, Sizing,
+};
 use crate::syntax::Span;
 use crate::utils::NonZeroExt;
 use crate::visualize::{Paint, Stroke};
@@ -264,15 +266,17 @@ pub enum ResolvableGridChild<T: ResolvableCell, I> {
 /// Used for cell-like elements which are aware of their final properties in
 /// the table, and may have property overrides.
 pub trait ResolvableCell {
-    /// Resolves the cell's fields, given its coordinates and default grid-wide
-    /// fill, align, inset and stroke properties, plus the expected value of
-    /// the `breakable` field.
+    /// Resolves the cell's fields, given its coordinates and spans and default
+    /// grid-wide fill, align, inset and stroke properties, plus the expected
+    /// value of the `breakable` field.
     /// Returns a final Cell.
     #[allow(clippy::too_many_arguments)]
     fn resolve_cell(
         self,
         x: usize,
         y: usize,
+        colspan: NonZeroUsize,
+        rowspan: NonZeroUsize,
         fill: &Option<Paint>,
         align: Smart<Alignment>,
         inset: Sides<Option<Rel<Length>>>,
@@ -293,6 +297,9 @@ pub trait ResolvableCell {
     /// The amount of rows spanned by this cell.
     fn rowspan(&self, styles: StyleChain) -> NonZeroUsize;
 
+    /// The name of the grid area this cell is placed in, if any.
+    fn area(&self, styles: StyleChain) -> Option<EcoString>;
+
     /// The cell's span, for errors.
     fn span(&self) -> Span;
 }
@@ -347,6 +354,7 @@ impl CellGrid {
         align: &Celled<Smart<Alignment>>,
         inset: &Celled<Sides<Option<Rel<Length>>>>,
         stroke: &ResolvedCelled<Sides<Option<Option<Arc<Stroke>>>>>,
+        areas: &GridAreas,
         engine: &mut Engine,
         styles: StyleChain,
         span: Span,
@@ -606,13 +614,38 @@ impl CellGrid {
                     ResolvableGridItem::Cell(cell) => cell,
                 };
                 let cell_span = cell.span();
-                let colspan = cell.colspan(styles).get();
-                let rowspan = cell.rowspan(styles).get();
+                let cell_x = cell.x(styles);
+                let cell_y = cell.y(styles);
+                let mut spans = Axes::new(cell.colspan(styles), cell.rowspan(styles));
+
+                // A cell placed in a named area occupies exactly that area.
+                let area = match cell.area(styles) {
+                    Some(name) => {
+                        if cell_x.is_custom() || cell_y.is_custom() {
+                            bail!(
+                                cell_span,
+                                "cell cannot be placed in an area and at a position";
+                                hint: "remove either the `area` or the `x` and `y` fields"
+                            );
+                        }
+                        let Some((pos, size)) = areas.get(&name) else {
+                            bail!(cell_span, "grid area \"{name}\" does not exist");
+                        };
+                        spans = size;
+                        Some(pos)
+                    }
+                    None => None,
+                };
+                let colspan = spans.x.get();
+                let rowspan = spans.y.get();
+
                 // Let's calculate the cell's final position based on its
                 // requested position.
                 let resolved_index = {
-                    let cell_x = cell.x(styles);
-                    let cell_y = cell.y(styles);
+                    let (cell_x, cell_y) = match area {
+                        Some(pos) => (Smart::Custom(pos.x), Smart::Custom(pos.y)),
+                        None => (cell_x, cell_y),
+                    };
                     resolve_cell_position(
                         cell_x,
                         cell_y,
@@ -655,6 +688,8 @@ impl CellGrid {
                 let cell = cell.resolve_cell(
                     x,
                     y,
+                    spans.x,
+                    spans.y,
                     &fill.resolve(engine, styles, x, y)?,
                     align.resolve(engine, styles, x, y)?,
                     inset.resolve(engine, styles, x, y)?,
@@ -916,6 +951,8 @@ impl CellGrid {
                     let new_cell = T::default().resolve_cell(
                         x,
                         y,
+                        NonZeroUsize::ONE,
+                        NonZeroUsize::ONE,
                         &fill.resolve(engine, styles, x, y)?,
                         align.resolve(engine, styles, x, y)?,
                         inset.resolve(engine, styles, x, y)?,
@@ -10,13 +10,16 @@ pub use self::cells::{
 pub use self::layout::GridLayouter;
 pub use self::lines::LinePosition;
 
+use std::borrow::Cow;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
-use ecow::eco_format;
+use ecow::{eco_format, EcoString};
 use smallvec::{smallvec, SmallVec};
 
-use crate::diag::{bail, HintedStrResult, HintedString, SourceResult, Trace, Tracepoint};
+use crate::diag::{
+    bail, HintedStrResult, HintedString, SourceResult, StrResult, Trace, Tracepoint,
+};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, scope, Array, Content, Fold, NativeElement, Packed, Show, Smart,
@@ -186,6 +189,35 @@ pub struct GridElem {
     #[borrowed]
     pub row_gutter: TrackSizings,
 
+    /// Named areas that cells can be placed in.
+    ///
+    /// Each inner array is a row and each string in it names the area the
+    /// column belongs to in that row. Unnamed positions are marked with
+    /// `{none}`. The positions sharing a name must form a rectangle. A
+    /// [cell]($grid.cell) with a matching [`area`]($grid.cell.area) is placed
+    /// in the area's top-left corner and spans all of its columns and rows.
+    ///
+    /// If no `columns` are specified, the grid has as many `{auto}`-sized
+    /// columns as the areas.
+    ///
+    /// ```example
+    /// #grid(
+    ///   areas: (
+    ///     ("head", "head"),
+    ///     ("nav", "main"),
+    ///   ),
+    ///   columns: (3em, 1fr),
+    ///   inset: 4pt,
+    ///   fill: luma(230),
+    ///   gutter: 2pt,
+    ///   grid.cell(area: "main")[Main],
+    ///   grid.cell(area: "nav")[Nav],
+    ///   grid.cell(area: "head")[Head],
+    /// )
+    /// ```
+    #[borrowed]
+    pub areas: GridAreas,
+
     /// How to fill the cells.
     ///
     /// This can be a color or a function that returns a color. The function
@@ -352,7 +384,8 @@ fn layout_grid(
 ) -> SourceResult<Fragment> {
     let inset = elem.inset(styles);
     let align = elem.align(styles);
-    let columns = elem.columns(styles);
+    let areas = elem.areas(styles);
+    let columns = areas.columns_or(elem.columns(styles));
     let rows = elem.rows(styles);
     let column_gutter = elem.column_gutter(styles);
     let row_gutter = elem.row_gutter(styles);
@@ -385,6 +418,7 @@ fn layout_grid(
         align,
         &inset,
         &stroke,
+        areas,
         engine,
         styles,
         elem.span(),
@@ -409,6 +443,88 @@ cast! {
     values: Array => Self(values.into_iter().map(Value::cast).collect::<HintedStrResult<_>>()?),
 }
 
+/// Named areas of a grid, given as rows of area names.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct GridAreas(Vec<Vec<Option<EcoString>>>);
+
+impl GridAreas {
+    /// Create areas from rows of names, ensuring that all rows are equally
+    /// long and that each area is a rectangle.
+    pub fn new(rows: Vec<Vec<Option<EcoString>>>) -> StrResult<Self> {
+        let areas = Self(rows);
+        if areas.0.iter().any(|row| row.len() != areas.columns()) {
+            bail!("all rows of the grid areas must have the same length");
+        }
+
+        for name in areas.0.iter().flatten().flatten() {
+            let (pos, size) = areas.get(name).expect("area exists");
+            let rectangular = areas.0[pos.y..pos.y + size.y.get()].iter().all(|row| {
+                row[pos.x..pos.x + size.x.get()]
+                    .iter()
+                    .all(|area| area.as_ref() == Some(name))
+            });
+            if !rectangular {
+                bail!("grid area \"{name}\" must be a rectangle");
+            }
+        }
+
+        Ok(areas)
+    }
+
+    /// The number of columns covered by the areas.
+    pub fn columns(&self) -> usize {
+        self.0.first().map_or(0, Vec::len)
+    }
+
+    /// The position of the top-left corner of the area with the given name
+    /// and the number of columns and rows it spans.
+    pub fn get(&self, name: &str) -> Option<(Axes<usize>, Axes<NonZeroUsize>)> {
+        let mut min = Axes::splat(usize::MAX);
+        let mut max = Axes::splat(0);
+        for (y, row) in self.0.iter().enumerate() {
+            for (x, _) in row
+                .iter()
+                .enumerate()
+                .filter(|(_, area)| area.as_deref() == Some(name))
+            {
+                min = Axes::new(min.x.min(x), min.y.min(y));
+                max = Axes::new(max.x.max(x), max.y.max(y));
+            }
+        }
+
+        let size = Axes::new(
+            NonZeroUsize::new(max.x.checked_sub(min.x)? + 1)?,
+            NonZeroUsize::new(max.y.checked_sub(min.y)? + 1)?,
+        );
+        Some((min, size))
+    }
+
+    /// The given columns or, if there are none, an auto-sized column for each
+    /// column of the areas.
+    pub(crate) fn columns_or<'a>(
+        &self,
+        columns: &'a TrackSizings,
+    ) -> Cow<'a, TrackSizings> {
+        if columns.0.is_empty() && self.columns() > 0 {
+            Cow::Owned(TrackSizings(smallvec![Sizing::Auto; self.columns()]))
+        } else {
+            Cow::Borrowed(columns)
+        }
+    }
+}
+
+cast! {
+    GridAreas,
+    self => self.0.into_value(),
+    rows: Array => Self::new(
+        rows.into_iter()
+            .map(|row| -> HintedStrResult<Vec<Option<EcoString>>> {
+                row.cast::<Array>()?.into_iter().map(Value::cast).collect()
+            })
+            .collect::<HintedStrResult<_>>()?,
+    )?,
+}
+
 /// Any child of a grid element.
 #[derive(Debug, PartialEq, Clone, Hash)]
 pub enum GridChild {
@@ -821,6 +937,12 @@ pub struct GridCell {
     #[default(NonZeroUsize::ONE)]
     pub rowspan: NonZeroUsize,
 
+    /// The named [area]($grid.areas) this cell is placed in.
+    ///
+    /// The cell then spans all columns and rows of the area, regardless of its
+    /// `colspan` and `rowspan`. It must not also specify an `x` or `y`.
+    pub area: Option<EcoString>,
+
     /// The cell's [fill]($grid.fill) override.
     pub fill: Smart<Option<Paint>>,
 
@@ -858,6 +980,8 @@ impl ResolvableCell for Packed<GridCell> {
         mut self,
         x: usize,
         y: usize,
+        colspan: NonZeroUsize,
+        rowspan: NonZeroUsize,
         fill: &Option<Paint>,
         align: Smart<Alignment>,
         inset: Sides<Option<Rel<Length>>>,
@@ -866,8 +990,6 @@ impl ResolvableCell for Packed<GridCell> {
         styles: StyleChain,
     ) -> Cell {
         let cell = &mut *self;
-        let colspan = cell.colspan(styles);
-        let rowspan = cell.rowspan(styles);
         let breakable = cell.breakable(styles).unwrap_or(breakable);
         let fill = cell.fill(styles).unwrap_or_else(|| fill.clone());
 
@@ -887,6 +1009,8 @@ impl ResolvableCell for Packed<GridCell> {
         let stroke = cell_stroke.fold(stroke).map(Option::flatten);
         cell.push_x(Smart::Custom(x));
         cell.push_y(Smart::Custom(y));
+        cell.push_colspan(colspan);
+        cell.push_rowspan(rowspan);
         cell.push_fill(Smart::Custom(fill.clone()));
         cell.push_align(match align {
             Smart::Custom(align) => {
@@ -941,6 +1065,10 @@ impl ResolvableCell for Packed<GridCell> {
         (**self).rowspan(styles)
     }
 
+    fn area(&self, styles: StyleChain) -> Option<EcoString> {
+        (**self).area(styles)
+    }
+
     fn span(&self) -> Span {
         Packed::span(self)
     }
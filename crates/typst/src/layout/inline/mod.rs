@@ -1,32 +1,40 @@
 mod linebreak;
 mod shaping;
 
-use comemo::{Tracked, TrackedMut};
+use std::collections::HashMap;
+
+use comemo::{Track, Tracked, TrackedMut};
+use typst_timing::TimingScope;
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
+use unicode_segmentation::UnicodeSegmentation;
 
 use self::linebreak::{breakpoints, Breakpoint};
 use self::shaping::{
-    cjk_punct_style, is_of_cj_script, shape, ShapedGlyph, ShapedText, BEGIN_PUNCT_PAT,
-    END_PUNCT_PAT,
+    cjk_punct_style, find_caps, is_of_cj_script, shape, ShapedGlyph, ShapedText,
+    BEGIN_PUNCT_PAT, END_PUNCT_PAT,
 };
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, At, SourceResult};
 use crate::engine::{Engine, Route};
 use crate::eval::Tracer;
-use crate::foundations::{Packed, Resolve, Smart, StyleChain};
+use crate::foundations::{
+    Content, Context, Func, NativeElement, Packed, Resolve, Smart, StyleChain, Value,
+};
 use crate::introspection::{Introspector, Locator, TagElem};
 use crate::layout::{
-    Abs, AlignElem, BoxElem, Dir, Em, FixedAlignment, Fr, Fragment, Frame, FrameItem,
-    HElem, InlineElem, InlineItem, Point, Size, Sizing, Spacing,
+    contour_span, Abs, AlignElem, BoxElem, Dir, Em, FixedAlignment, Fr, Fragment, Frame,
+    FrameItem, GroupItem, HElem, InlineElem, InlineItem, Point, RepeatElem, Size, Sizing,
+    Spacing,
 };
-use crate::model::{Linebreaks, ParElem};
+use crate::model::{Linebreaks, ParElem, TabElem, TabStop};
 use crate::realize::StyleVec;
 use crate::syntax::Span;
 use crate::text::{
-    Costs, Lang, LinebreakElem, SmartQuoteElem, SmartQuoter, SmartQuotes, SpaceElem,
-    TextElem,
+    Costs, GlyphStyle, Lang, LinebreakElem, SmartQuoteElem, SmartQuoter, SmartQuotes,
+    SpaceElem, TextElem, TextItem,
 };
 use crate::utils::Numeric;
+use crate::visualize::{Paint, RelativeTo};
 use crate::World;
 
 /// Layouts content inline.
@@ -37,6 +45,7 @@ pub(crate) fn layout_inline(
     consecutive: bool,
     region: Size,
     expand: bool,
+    contour: Option<(&[Point], Abs)>,
 ) -> SourceResult<Fragment> {
     #[comemo::memoize]
     #[allow(clippy::too_many_arguments)]
@@ -51,7 +60,10 @@ pub(crate) fn layout_inline(
         consecutive: bool,
         region: Size,
         expand: bool,
+        contour: Option<(&[Point], Abs)>,
     ) -> SourceResult<Fragment> {
+        // Only runs when the layout isn't cached.
+        let _scope = TimingScope::new("layout paragraph", None);
         let mut locator = Locator::chained(locator);
         let mut engine = Engine {
             world,
@@ -70,12 +82,16 @@ pub(crate) fn layout_inline(
         // each and every line from scratch.
         let p = prepare(&mut engine, children, &text, segments, spans, styles)?;
 
+        // Track the position of lines within the contour, if any.
+        let contour =
+            contour.map(|(contour, y)| ContourCursor { contour, y, width: region.x });
+
         // Break the paragraph into lines.
-        let lines = linebreak(&engine, &p, region.x - p.hang);
+        let lines = linebreak(&engine, &p, region.x - p.hang, contour);
 
         // Stack the lines into one frame per region.
         let shrink = ParElem::shrink_in(styles);
-        finalize(&mut engine, &p, &lines, region, expand, shrink)
+        finalize(&mut engine, &p, &lines, region, expand, shrink, contour)
     }
 
     let fragment = cached(
@@ -89,6 +105,7 @@ pub(crate) fn layout_inline(
         consecutive,
         region,
         expand,
+        contour,
     )?;
 
     engine.locator.visit_frames(&fragment);
@@ -137,6 +154,8 @@ struct Preparation<'a> {
     justify: bool,
     /// The paragraph's hanging indent.
     hang: Abs,
+    /// The paragraph's tab stops.
+    tabs: Vec<TabStop>,
     /// Whether to add spacing between CJK and Latin characters.
     cjk_latin_spacing: bool,
     /// Whether font fallback is enabled for this paragraph.
@@ -222,8 +241,10 @@ enum Item<'a> {
     Text(ShapedText<'a>),
     /// Absolute spacing between other items, and whether it is weak.
     Absolute(Abs, bool),
-    /// Fractional spacing between other items.
-    Fractional(Fr, Option<(&'a Packed<BoxElem>, StyleChain<'a>)>),
+    /// Fractional spacing between other items, with its weight.
+    Fractional(Fr, usize, Option<FrBody<'a>>),
+    /// A tab that advances to the next tab stop.
+    Tab(StyleChain<'a>),
     /// Layouted inline-level content.
     Frame(Frame, StyleChain<'a>),
     /// A tag.
@@ -255,7 +276,7 @@ impl<'a> Item<'a> {
     fn textual(&self) -> &str {
         match self {
             Self::Text(shaped) => shaped.text,
-            Self::Absolute(_, _) | Self::Fractional(_, _) => SPACING_REPLACE,
+            Self::Absolute(_, _) | Self::Fractional(..) | Self::Tab(_) => SPACING_REPLACE,
             Self::Frame(_, _) => OBJ_REPLACE,
             Self::Tag(_) => "",
             Self::Skip(s) => s,
@@ -268,17 +289,29 @@ impl<'a> Item<'a> {
     }
 
     /// The natural layouted width of the item.
+    ///
+    /// Tabs depend on their position in the line and are measured separately
+    /// by [`tab_widths`].
     fn width(&self) -> Abs {
         match self {
             Self::Text(shaped) => shaped.width,
             Self::Absolute(v, _) => *v,
             Self::Frame(frame, _) => frame.width(),
-            Self::Fractional(_, _) | Self::Tag(_) => Abs::zero(),
+            Self::Fractional(..) | Self::Tab(_) | Self::Tag(_) => Abs::zero(),
             Self::Skip(_) => Abs::zero(),
         }
     }
 }
 
+/// What is drawn into fractional spacing.
+#[derive(Debug)]
+enum FrBody<'a> {
+    /// A box with a fractional width.
+    Box(&'a Packed<BoxElem>, StyleChain<'a>),
+    /// A fill of horizontal spacing.
+    Fill(&'a Content, StyleChain<'a>),
+}
+
 /// Maps byte offsets back to spans.
 #[derive(Default)]
 struct SpanMapper(Vec<(usize, Span)>);
@@ -415,17 +448,89 @@ impl<'a> Line<'a> {
         self.items().filter_map(Item::text).map(|s| s.shrinkability()).sum()
     }
 
-    /// The sum of fractions in the line.
+    /// How far the tabs in the line advance in total.
+    fn tabs(&self, p: &Preparation, width: Abs) -> Abs {
+        let items: Vec<_> = self.items().collect();
+        tab_widths(p, &items, p.hang, width)
+            .iter()
+            .map(|&(amount, _)| amount)
+            .sum()
+    }
+
+    /// The width of the line including how far its tabs advance when it is
+    /// set into a region of the given width.
+    fn measure(&self, p: &Preparation, width: Abs) -> Abs {
+        self.width + self.tabs(p, width)
+    }
+
+    /// The highest weight of the fractions in the line. Only fractions of
+    /// this weight receive space.
+    fn weight(&self) -> usize {
+        self.items()
+            .filter_map(|item| match item {
+                Item::Fractional(_, weight, _) => Some(*weight),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The sum of fractions in the line that receive space.
     fn fr(&self) -> Fr {
+        let weight = self.weight();
         self.items()
             .filter_map(|item| match item {
-                Item::Fractional(fr, _) => Some(*fr),
+                Item::Fractional(fr, w, _) if *w == weight => Some(*fr),
                 _ => None,
             })
             .sum()
     }
 }
 
+/// The vertical position of the next line of a paragraph that is set into a
+/// contour.
+#[derive(Copy, Clone)]
+struct ContourCursor<'a> {
+    /// The contour, relative to the top-left corner of the region.
+    contour: &'a [Point],
+    /// The top edge of the next line.
+    y: Abs,
+    /// The full width of the region.
+    width: Abs,
+}
+
+impl ContourCursor<'_> {
+    /// The horizontal offset and width available to the next line.
+    ///
+    /// The line is assumed to be about as tall as the text size.
+    fn span(&self, p: &Preparation) -> (Abs, Abs) {
+        match contour_span(self.contour, self.y, self.y + p.size) {
+            Some((left, right)) => {
+                let left = left.max(Abs::zero()).min(self.width);
+                let right = right.max(left).min(self.width);
+                (left, right - left)
+            }
+            None => (Abs::zero(), self.width),
+        }
+    }
+
+    /// Move past a line and the leading after it.
+    fn advance(&mut self, engine: &Engine, p: &Preparation, line: &Line) {
+        let mut height = Abs::zero();
+        for item in line.items() {
+            match item {
+                Item::Text(shaped) => {
+                    let (top, bottom) = shaped.measure(engine);
+                    height.set_max(top + bottom);
+                }
+                Item::Frame(frame, _) => height.set_max(frame.height()),
+                _ => {}
+            }
+        }
+        self.y += height + p.leading;
+    }
+}
+
 /// Collect all text of the paragraph into one string and layout equations. This
 /// also performs string-level preprocessing like case transformations.
 fn collect<'a>(
@@ -491,23 +596,41 @@ fn collect<'a>(
             }
 
             collector.push_item(match amount {
-                Spacing::Fr(fr) => Item::Fractional(*fr, None),
+                Spacing::Fr(fr) => Item::Fractional(
+                    *fr,
+                    elem.weight(styles),
+                    elem.fill(styles).as_ref().map(|fill| FrBody::Fill(fill, styles)),
+                ),
                 Spacing::Rel(rel) => Item::Absolute(
                     rel.resolve(styles).relative_to(region.x),
                     elem.weak(styles),
                 ),
             });
+        } else if child.is::<TabElem>() {
+            collector.push_item(Item::Tab(styles));
         } else if let Some(elem) = child.to_packed::<LinebreakElem>() {
             collector
                 .push_text(if elem.justify(styles) { "\u{2028}" } else { "\n" }, styles);
         } else if let Some(elem) = child.to_packed::<SmartQuoteElem>() {
             let double = elem.double(styles);
-            if elem.enabled(styles) {
+            let elided = elem.enabled(styles)
+                && iter
+                    .peek()
+                    .and_then(|(child, _)| child.to_packed::<TextElem>())
+                    .is_some_and(|text| {
+                        let elisions = elem.elisions(styles);
+                        collector.quoter.is_elision(elisions, double, text.text())
+                    });
+
+            if elided {
+                collector.push_text("’", styles);
+            } else if elem.enabled(styles) {
                 let quotes = SmartQuotes::new(
                     elem.quotes(styles),
                     TextElem::lang_in(styles),
                     TextElem::region_in(styles),
                     elem.alternative(styles),
+                    elem.nesting(styles),
                 );
                 let peeked = iter.peek().and_then(|(child, _)| {
                     if let Some(elem) = child.to_packed::<TextElem>() {
@@ -516,6 +639,7 @@ fn collect<'a>(
                         Some('"')
                     } else if child.is::<SpaceElem>()
                         || child.is::<HElem>()
+                        || child.is::<TabElem>()
                         || child.is::<LinebreakElem>()
                         // This is a temporary hack. We should rather skip these
                         // and peek at the next child.
@@ -549,7 +673,11 @@ fn collect<'a>(
             collector.push_item(Item::Skip(POP_ISOLATE));
         } else if let Some(elem) = child.to_packed::<BoxElem>() {
             if let Sizing::Fr(v) = elem.width(styles) {
-                collector.push_item(Item::Fractional(v, Some((elem, styles))));
+                collector.push_item(Item::Fractional(
+                    v,
+                    1,
+                    Some(FrBody::Box(elem, styles)),
+                ));
             } else {
                 let frame = elem.layout(engine, styles, region)?;
                 collector.push_item(Item::Frame(frame, styles));
@@ -677,6 +805,7 @@ fn prepare<'a>(
         align: AlignElem::alignment_in(styles).resolve(styles).x,
         justify: ParElem::justify_in(styles),
         hang: ParElem::hanging_indent_in(styles),
+        tabs: ParElem::tabs_in(styles),
         cjk_latin_spacing,
         fallback: TextElem::fallback_in(styles),
         leading: ParElem::leading_in(styles),
@@ -744,6 +873,7 @@ fn shape_range<'a>(
     let script = TextElem::script_in(styles);
     let lang = TextElem::lang_in(styles);
     let region = TextElem::region_in(styles);
+    let caps = find_caps(&bidi.text[range.clone()], range.start, styles);
     let mut process = |range: Range, level: BidiLevel| {
         let dir = if level.is_ltr() { Dir::LTR } else { Dir::RTL };
         let shaped = shape(
@@ -755,6 +885,7 @@ fn shape_range<'a>(
             dir,
             lang,
             region,
+            caps.clone(),
         );
         items.push(Item::Text(shaped));
     };
@@ -805,7 +936,18 @@ fn is_compatible(a: Script, b: Script) -> bool {
 }
 
 /// Find suitable linebreaks.
-fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
+fn linebreak<'a>(
+    engine: &Engine,
+    p: &'a Preparation<'a>,
+    width: Abs,
+    contour: Option<ContourCursor>,
+) -> Vec<Line<'a>> {
+    // Within a contour, each line has its own width, which the optimized
+    // line breaker doesn't support.
+    if contour.is_some() {
+        return linebreak_simple(engine, p, width, contour);
+    }
+
     let linebreaks = p.linebreaks.unwrap_or_else(|| {
         if p.justify {
             Linebreaks::Optimized
@@ -815,7 +957,7 @@ fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Lin
     });
 
     match linebreaks {
-        Linebreaks::Simple => linebreak_simple(engine, p, width),
+        Linebreaks::Simple => linebreak_simple(engine, p, width, None),
         Linebreaks::Optimized => linebreak_optimized(engine, p, width),
     }
 }
@@ -826,12 +968,26 @@ fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Lin
 fn linebreak_simple<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
-    width: Abs,
+    mut width: Abs,
+    mut contour: Option<ContourCursor>,
 ) -> Vec<Line<'a>> {
     let mut lines = Vec::with_capacity(16);
     let mut start = 0;
     let mut last = None;
 
+    // Within a contour, the width changes from line to line.
+    let hang = p.hang;
+    let advance = |contour: &mut Option<ContourCursor>, line: &Line| {
+        contour.as_mut().map(|cursor| {
+            cursor.advance(engine, p, line);
+            cursor.span(p).1 - hang
+        })
+    };
+
+    if let Some(cursor) = &contour {
+        width = cursor.span(p).1 - hang;
+    }
+
     breakpoints(p, |end, breakpoint| {
         let prepend_hyphen = lines.last().map(should_repeat_hyphen).unwrap_or(false);
 
@@ -841,8 +997,9 @@ fn linebreak_simple<'a>(
         // If the line doesn't fit anymore, we push the last fitting attempt
         // into the stack and rebuild the line from the attempt's end. The
         // resulting line cannot be broken up further.
-        if !width.fits(attempt.width) {
+        if !width.fits(attempt.measure(p, p.hang + width)) {
             if let Some((last_attempt, last_end)) = last.take() {
+                width = advance(&mut contour, &last_attempt).unwrap_or(width);
                 lines.push(last_attempt);
                 start = last_end;
                 attempt = line(engine, p, start..end, breakpoint, prepend_hyphen);
@@ -852,7 +1009,10 @@ fn linebreak_simple<'a>(
         // Finish the current line if there is a mandatory line break (i.e.
         // due to "\n") or if the line doesn't fit horizontally already
         // since then no shorter line will be possible.
-        if breakpoint == Breakpoint::Mandatory || !width.fits(attempt.width) {
+        if breakpoint == Breakpoint::Mandatory
+            || !width.fits(attempt.measure(p, p.hang + width))
+        {
+            width = advance(&mut contour, &attempt).unwrap_or(width);
             lines.push(attempt);
             start = end;
             last = None;
@@ -935,7 +1095,7 @@ fn linebreak_optimized<'a>(
 
             // Determine how much the line's spaces would need to be stretched
             // to make it the desired width.
-            let delta = width - attempt.width;
+            let delta = width - attempt.measure(p, p.hang + width);
             // Determine how much stretch are permitted.
             let adjust = if delta >= Abs::zero() {
                 attempt.stretchability()
@@ -1264,25 +1424,48 @@ fn finalize(
     region: Size,
     expand: bool,
     shrink: bool,
+    mut contour: Option<ContourCursor>,
 ) -> SourceResult<Fragment> {
     // Determine the paragraph's width: Full width of the region if we
-    // should expand or there's fractional spacing, fit-to-width otherwise.
+    // should expand, there's fractional spacing or the lines are set into a
+    // contour, fit-to-width otherwise.
     let width = if !region.x.is_finite()
-        || (!expand && lines.iter().all(|line| line.fr().is_zero()))
+        || (!expand && contour.is_none() && lines.iter().all(|line| line.fr().is_zero()))
     {
-        region
-            .x
-            .min(p.hang + lines.iter().map(|line| line.width).max().unwrap_or_default())
+        let widest = lines
+            .iter()
+            .map(|line| line.measure(p, region.x))
+            .max()
+            .unwrap_or_default();
+        region.x.min(p.hang + widest)
     } else {
         region.x
     };
 
+    // Style the glyphs once for the whole paragraph.
+    let glyph_styles = style_glyphs(engine, p)?;
+
     // Stack the lines into one frame per region.
     let mut frames: Vec<Frame> = lines
         .iter()
-        .map(|line| commit(engine, p, line, width, region.y, shrink))
+        .map(|line| match &mut contour {
+            Some(cursor) => {
+                // Commit to the line's span and place it within the full width.
+                let (start, span) = cursor.span(p);
+                let frame =
+                    commit(engine, p, line, span, region.y, shrink, &glyph_styles)?;
+                let mut output = Frame::soft(Size::new(width, frame.height()));
+                output.set_baseline(frame.baseline());
+                output.push_frame(Point::with_x(start), frame);
+                cursor.advance(engine, p, line);
+                Ok(output)
+            }
+            None => commit(engine, p, line, width, region.y, shrink, &glyph_styles),
+        })
         .collect::<SourceResult<_>>()?;
 
+    relate_fills(&mut frames, p.leading);
+
     // Positive ratios enable prevention, while zero and negative ratios disable it.
     if p.costs.orphan().get() > 0.0 {
         // Prevent orphans.
@@ -1305,6 +1488,131 @@ fn finalize(
     Ok(Fragment::frames(frames))
 }
 
+/// Call the glyph stylers of the paragraph's text for each of their grapheme
+/// clusters.
+///
+/// Clusters are counted from the start of each contiguous run of text with
+/// the same styler. The styles are keyed by the clusters' start in the
+/// paragraph.
+fn style_glyphs(
+    engine: &mut Engine,
+    p: &Preparation,
+) -> SourceResult<HashMap<usize, GlyphStyle>> {
+    let mut styles = HashMap::new();
+    let mut run: Option<(Func, i64)> = None;
+    for item in &p.items {
+        let text = match item {
+            Item::Tag(_) | Item::Skip(_) => continue,
+            Item::Text(text) => text,
+            _ => {
+                run = None;
+                continue;
+            }
+        };
+
+        let Some(styler) = TextElem::glyph_styler_in(text.styles) else {
+            run = None;
+            continue;
+        };
+
+        let mut i = match &run {
+            Some((prev, i)) if *prev == styler => *i,
+            _ => 0,
+        };
+
+        let context = Context::new(None, Some(text.styles));
+        for (offset, cluster) in text.text.grapheme_indices(true) {
+            let args = [Value::Int(i), Value::Str(cluster.into())];
+            let value = styler.call(engine, context.track(), args)?;
+            if let Some(style) = value.cast::<Option<GlyphStyle>>().at(styler.span())? {
+                styles.insert(text.base + offset, style);
+            }
+            i += 1;
+        }
+
+        run = Some((styler, i));
+    }
+
+    Ok(styles)
+}
+
+/// Lay out self-relative gradient and pattern fills of text across the whole
+/// paragraph.
+///
+/// All text with the same fill shares one coordinate space: the bounding box
+/// of that text across the frames, as if they were stacked with the leading
+/// in between. Each run of such text is wrapped in a hard group of that size,
+/// so that the fill can be relative to its parent. Text in transformed groups
+/// gets its own coordinate space.
+pub(crate) fn relate_fills(frames: &mut [Frame], leading: Abs) {
+    if !frames.iter().any(has_self_fill) {
+        return;
+    }
+
+    for frame in frames.iter_mut() {
+        frame.visit_transformed_mut(&mut |frame| {
+            relate_fills(std::slice::from_mut(frame), Abs::zero())
+        });
+    }
+
+    // Determine the bounding box of each fill.
+    let mut boxes: Vec<(Paint, Point, Point)> = vec![];
+    let mut y = Abs::zero();
+    for frame in frames.iter() {
+        frame.visit_text(&mut |pos, text| {
+            let Some(fill) = self_fill(text) else { return };
+            let metrics = text.font.metrics();
+            let min = pos + Point::new(Abs::zero(), y - metrics.ascender.at(text.size));
+            let max = pos + Point::new(text.width(), y - metrics.descender.at(text.size));
+            match boxes.iter_mut().find(|(paint, ..)| paint == fill) {
+                Some((_, lo, hi)) => {
+                    *lo = lo.min(min);
+                    *hi = hi.max(max);
+                }
+                None => boxes.push((fill.clone(), min, max)),
+            }
+        });
+        y += frame.height() + leading;
+    }
+
+    if boxes.is_empty() {
+        return;
+    }
+
+    // Wrap the runs of text in groups spanning their fill's bounding box.
+    let mut y = Abs::zero();
+    for frame in frames.iter_mut() {
+        frame.map_text_runs(&mut |text| self_fill(text).cloned(), &mut |run| {
+            let fill = run[0].1.fill.clone().unwrap();
+            let (_, min, max) = boxes.iter().find(|(paint, ..)| *paint == fill).unwrap();
+            let origin = *min - Point::with_y(y);
+            let mut group = Frame::hard((*max - *min).to_size());
+            for (pos, text) in run {
+                let fill = Some(fill.clone().with_relative(RelativeTo::Parent));
+                group.push(pos - origin, FrameItem::Text(TextItem { fill, ..text }));
+            }
+            (origin, FrameItem::Group(GroupItem::new(group)))
+        });
+        y += frame.height() + leading;
+    }
+}
+
+/// The fill of a text item if it is relative to the text itself.
+fn self_fill(text: &TextItem) -> Option<&Paint> {
+    text.fill
+        .as_ref()
+        .filter(|fill| fill.relative() == Smart::Custom(RelativeTo::Self_))
+}
+
+/// Whether the frame contains text with a self-relative fill anywhere.
+fn has_self_fill(frame: &Frame) -> bool {
+    frame.items().any(|(_, item)| match item {
+        FrameItem::Group(group) => has_self_fill(&group.frame),
+        FrameItem::Text(text) => self_fill(text).is_some(),
+        _ => false,
+    })
+}
+
 /// Merge two line frames
 fn merge(first: &mut Frame, second: Frame, leading: Abs) {
     let offset = first.height() + leading;
@@ -1321,6 +1629,7 @@ fn commit(
     width: Abs,
     full: Abs,
     shrink: bool,
+    glyph_styles: &HashMap<usize, GlyphStyle>,
 ) -> SourceResult<Frame> {
     let mut remaining = width - line.width - p.hang;
     let mut offset = Abs::zero();
@@ -1358,12 +1667,18 @@ fn commit(
         }
     }
 
+    // Determine how far the tabs advance. They take up some of the remaining
+    // space.
+    let tabs = tab_widths(p, &reordered, offset, width);
+    remaining -= tabs.iter().map(|&(amount, _)| amount).sum::<Abs>();
+
     // Determine how much additional space is needed.
     // The justification_ratio is for the first step justification,
     // extra_justification is for the last step.
     // For more info on multi-step justification, see Procedures for Inter-
     // Character Space Expansion in W3C document Chinese Layout Requirements.
     let fr = line.fr();
+    let weight = line.weight();
     let mut justification_ratio = 0.0;
     let mut extra_justification = Abs::zero();
 
@@ -1373,7 +1688,7 @@ fn commit(
         // Attempt to reduce the length of the line, using shrinkability.
         justification_ratio = (remaining / shrinkability).max(-1.0);
         remaining = (remaining + shrinkability).min(Abs::zero());
-    } else if line.justify && fr.is_zero() {
+    } else if line.justify && fr.is_zero() && tabs.is_empty() {
         // Attempt to increase the length of the line, using stretchability.
         if stretch > Abs::zero() {
             justification_ratio = (remaining / stretch).min(1.0);
@@ -1393,6 +1708,7 @@ fn commit(
 
     // Build the frames and determine the height and baseline.
     let mut frames = vec![];
+    let mut tabs = tabs.into_iter();
     for item in reordered {
         let mut push = |offset: &mut Abs, frame: Frame| {
             let width = frame.width();
@@ -1406,9 +1722,41 @@ fn commit(
             Item::Absolute(v, _) => {
                 offset += *v;
             }
-            Item::Fractional(v, elem) => {
-                let amount = v.share(fr, remaining);
-                if let Some((elem, styles)) = elem {
+            Item::Fractional(v, w, body) => {
+                let amount =
+                    if *w == weight { v.share(fr, remaining) } else { Abs::zero() };
+                match body {
+                    Some(FrBody::Box(elem, styles)) => {
+                        let region = Size::new(amount, full);
+                        let mut frame = elem.layout(engine, *styles, region)?;
+                        frame.post_process(*styles);
+                        frame.translate(Point::with_y(TextElem::baseline_in(*styles)));
+                        push(&mut offset, frame);
+                    }
+                    Some(FrBody::Fill(fill, styles)) if amount > Abs::zero() => {
+                        let elem = Packed::new(
+                            BoxElem::new()
+                                .with_width(Sizing::Rel(amount.into()))
+                                .with_body(Some((*fill).clone())),
+                        );
+                        let region = Size::new(amount, full);
+                        let mut frame = elem.layout(engine, *styles, region)?;
+                        frame.post_process(*styles);
+                        frame.translate(Point::with_y(TextElem::baseline_in(*styles)));
+                        push(&mut offset, frame);
+                    }
+                    _ => offset += amount,
+                }
+            }
+            Item::Tab(styles) => {
+                let (amount, stop) = tabs.next().unwrap();
+                let leader = stop.and_then(|stop| stop.leader.as_ref());
+                if let Some(leader) = leader.filter(|_| amount > Abs::zero()) {
+                    let elem = Packed::new(
+                        BoxElem::new()
+                            .with_width(Sizing::Rel(amount.into()))
+                            .with_body(Some(RepeatElem::new(leader.clone()).pack())),
+                    );
                     let region = Size::new(amount, full);
                     let mut frame = elem.layout(engine, *styles, region)?;
                     frame.post_process(*styles);
@@ -1419,8 +1767,15 @@ fn commit(
                 }
             }
             Item::Text(shaped) => {
-                let mut frame =
-                    shaped.build(engine, justification_ratio, extra_justification);
+                let glyph_styles = TextElem::glyph_styler_in(shaped.styles)
+                    .is_some()
+                    .then_some(glyph_styles);
+                let mut frame = shaped.build(
+                    engine,
+                    justification_ratio,
+                    extra_justification,
+                    glyph_styles,
+                );
                 frame.post_process(shaped.styles);
                 push(&mut offset, frame);
             }
@@ -1458,6 +1813,53 @@ fn commit(
     Ok(output)
 }
 
+/// Determine how far each tab in a line advances, given the line's items in
+/// visual order, and which tab stop it advances to.
+fn tab_widths<'a>(
+    p: &'a Preparation,
+    items: &[&Item],
+    start: Abs,
+    width: Abs,
+) -> Vec<(Abs, Option<&'a TabStop>)> {
+    let mut tabs = vec![];
+    let mut offset = start;
+    for (i, item) in items.iter().enumerate() {
+        let Item::Tab(styles) = item else {
+            offset += item.width();
+            continue;
+        };
+
+        // The width of the items up to the next tab, which are aligned at
+        // the stop.
+        let next: Abs = items[i + 1..]
+            .iter()
+            .take_while(|item| !matches!(item, Item::Tab(_)))
+            .map(|item| item.width())
+            .sum();
+
+        let tab = p
+            .tabs
+            .iter()
+            .find_map(|stop| {
+                let pos = stop.pos.resolve(*styles).relative_to(width);
+                if !pos.is_finite() {
+                    return None;
+                }
+                let amount = match stop.align.resolve(*styles) {
+                    FixedAlignment::Start => pos - offset,
+                    FixedAlignment::Center => pos - offset - next / 2.0,
+                    FixedAlignment::End => pos - offset - next,
+                };
+                (pos > offset && amount >= Abs::zero()).then_some((amount, Some(stop)))
+            })
+            .unwrap_or_else(|| (Em::new(0.25).resolve(*styles), None));
+
+        offset += tab.0;
+        tabs.push(tab);
+    }
+    tabs
+}
+
 /// Return a line's items in visual order.
 fn reorder<'a>(line: &'a Line<'a>) -> (Vec<&Item<'a>>, bool) {
     let mut reordered = vec![];
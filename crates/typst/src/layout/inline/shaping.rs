@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::Range;
 use std::str::FromStr;
@@ -12,14 +13,16 @@ use unicode_script::{Script, UnicodeScript};
 
 use super::SpanMapper;
 use crate::engine::Engine;
-use crate::foundations::StyleChain;
-use crate::layout::{Abs, Dir, Em, Frame, FrameItem, Point, Size};
+use crate::foundations::{Resolve, StyleChain};
+use crate::layout::{
+    Abs, Dir, Em, Frame, FrameItem, GroupItem, Point, Ratio, Size, Transform,
+};
 use crate::syntax::Span;
 use crate::text::{
-    decorate, families, features, variant, Font, FontVariant, Glyph, Lang, Region,
-    TextElem, TextItem,
+    caps_runs, decorate, families, features, variant, FamilyFeatures, Font, FontFamily,
+    FontVariant, Glyph, GlyphStyle, Lang, Region, TextElem, TextItem, TrackCaps,
 };
-use crate::utils::SliceExt;
+use crate::utils::{Numeric, SliceExt};
 use crate::World;
 
 /// The result of shaping text.
@@ -48,6 +51,10 @@ pub(super) struct ShapedText<'a> {
     pub width: Abs,
     /// The shaped glyphs.
     pub glyphs: Cow<'a, [ShapedGlyph]>,
+    /// The byte ranges in the full paragraph of the text that is tracked as
+    /// [capitals](TextElem::track_caps). They are found once for the whole
+    /// text run, so that reshaping a part of it tracks the same letters.
+    pub caps: Arc<[Range<usize>]>,
 }
 
 /// A single glyph resulting from shaping.
@@ -63,6 +70,9 @@ pub(super) struct ShapedGlyph {
     pub x_offset: Em,
     /// The vertical offset of the glyph.
     pub y_offset: Em,
+    /// The glyph's size relative to the text size. The advance and offsets
+    /// are already scaled accordingly.
+    pub scale: Ratio,
     /// The adjustability of the glyph.
     pub adjustability: Adjustability,
     /// The byte range of this glyph's cluster in the full paragraph. A cluster
@@ -199,6 +209,22 @@ impl ShapedGlyph {
     }
 }
 
+/// Rotate a text item around the middle of its baseline if its glyph style
+/// asks for it.
+fn rotated(item: TextItem, style: Option<&GlyphStyle>) -> FrameItem {
+    let Some(style) = style.filter(|style| !style.rotate.is_zero()) else {
+        return FrameItem::Text(item);
+    };
+
+    let mid = item.width() / 2.0;
+    let transform = Transform::translate(mid, Abs::zero())
+        .pre_concat(Transform::rotate(style.rotate))
+        .pre_concat(Transform::translate(-mid, Abs::zero()));
+    let mut frame = Frame::soft(Size::zero());
+    frame.push(Point::zero(), FrameItem::Text(item));
+    FrameItem::Group(GroupItem { transform, ..GroupItem::new(frame) })
+}
+
 /// A side you can go toward.
 enum Side {
     /// To the left-hand side.
@@ -212,11 +238,15 @@ impl<'a> ShapedText<'a> {
     ///
     /// The `justification` defines how much extra advance width each
     /// [justifiable glyph](ShapedGlyph::is_justifiable) will get.
+    ///
+    /// The `glyph_styles` map the start of grapheme clusters in the paragraph
+    /// to the styles of their glyphs.
     pub fn build(
         &self,
         engine: &Engine,
         justification_ratio: f64,
         extra_justification: Abs,
+        glyph_styles: Option<&HashMap<usize, GlyphStyle>>,
     ) -> Frame {
         let (top, bottom) = self.measure(engine);
         let size = Size::new(self.width, top + bottom);
@@ -231,8 +261,12 @@ impl<'a> ShapedText<'a> {
         let stroke = TextElem::stroke_in(self.styles);
         let span_offset = TextElem::span_offset_in(self.styles);
 
-        for ((font, y_offset), group) in
-            self.glyphs.as_ref().group_by_key(|g| (g.font.clone(), g.y_offset))
+        // Styled clusters get their own text items.
+        for ((font, y_offset, scale, _), group) in
+            self.glyphs.as_ref().group_by_key(|g| {
+                let cluster = glyph_styles.map(|_| g.range.start);
+                (g.font.clone(), g.y_offset, g.scale, cluster)
+            })
         {
             let mut range = group[0].range.clone();
             for glyph in group {
@@ -292,10 +326,11 @@ impl<'a> ShapedText<'a> {
                     // A+B+C+D: Glyph's x_advance
                     Glyph {
                         id: shaped.glyph_id,
-                        x_advance: shaped.x_advance
+                        x_advance: (shaped.x_advance
                             + justification_left
-                            + justification_right,
-                        x_offset: shaped.x_offset + justification_left,
+                            + justification_right)
+                            / scale.get(),
+                        x_offset: (shaped.x_offset + justification_left) / scale.get(),
                         range: (shaped.range.start - range.start).saturating_as()
                             ..(shaped.range.end - range.start).saturating_as(),
                         span,
@@ -303,23 +338,34 @@ impl<'a> ShapedText<'a> {
                 })
                 .collect();
 
+            let style = glyph_styles.and_then(|styles| styles.get(&group[0].range.start));
             let item = TextItem {
                 font,
-                size: self.size,
+                size: self.size * scale.get(),
                 lang: self.lang,
                 region: self.region,
-                fill: fill.clone(),
+                fill: style.and_then(|style| style.fill.clone()).or_else(|| fill.clone()),
                 stroke: stroke.clone().map(|s| s.unwrap_or_default()),
                 text: self.text[range.start - self.base..range.end - self.base].into(),
                 glyphs,
             };
 
             let width = item.width();
+            let pos = match style {
+                Some(style) => {
+                    pos + Point::new(
+                        style.dx.resolve(self.styles),
+                        style.dy.resolve(self.styles),
+                    )
+                }
+                None => pos,
+            };
+
             if decos.is_empty() {
-                frame.push(pos, FrameItem::Text(item));
+                frame.push(pos, rotated(item, style));
             } else {
                 // Apply line decorations.
-                frame.push(pos, FrameItem::Text(item.clone()));
+                frame.push(pos, rotated(item.clone(), style));
                 for deco in &decos {
                     decorate(&mut frame, deco, &item, width, shift, pos);
                 }
@@ -332,7 +378,7 @@ impl<'a> ShapedText<'a> {
     }
 
     /// Measure the top and bottom extent of this text.
-    fn measure(&self, engine: &Engine) -> (Abs, Abs) {
+    pub fn measure(&self, engine: &Engine) -> (Abs, Abs) {
         let mut top = Abs::zero();
         let mut bottom = Abs::zero();
 
@@ -431,6 +477,7 @@ impl<'a> ShapedText<'a> {
                 variant: self.variant,
                 width: glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(self.size),
                 glyphs: Cow::Borrowed(glyphs),
+                caps: self.caps.clone(),
             }
         } else {
             shape(
@@ -442,6 +489,7 @@ impl<'a> ShapedText<'a> {
                 self.dir,
                 self.lang,
                 self.region,
+                self.caps.clone(),
             )
         }
     }
@@ -490,6 +538,7 @@ impl<'a> ShapedText<'a> {
                 x_advance,
                 x_offset: Em::zero(),
                 y_offset: Em::zero(),
+                scale: Ratio::one(),
                 adjustability: Adjustability::default(),
                 range,
                 safe_to_break: true,
@@ -599,6 +648,10 @@ struct ShapingContext<'a, 'v> {
     size: Abs,
     variant: FontVariant,
     features: Vec<rustybuzz::Feature>,
+    family_features: FamilyFeatures,
+    acronyms: bool,
+    track_caps: Option<TrackCaps>,
+    caps: Arc<[Range<usize>]>,
     fallback: bool,
     dir: Dir,
 }
@@ -614,6 +667,7 @@ pub(super) fn shape<'a>(
     dir: Dir,
     lang: Lang,
     region: Option<Region>,
+    caps: Arc<[Range<usize>]>,
 ) -> ShapedText<'a> {
     let size = TextElem::size_in(styles);
     let mut ctx = ShapingContext {
@@ -625,6 +679,10 @@ pub(super) fn shape<'a>(
         styles,
         variant: variant(styles),
         features: features(styles),
+        family_features: TextElem::family_features_in(styles),
+        acronyms: TextElem::profile_in(styles).acronyms,
+        track_caps: TextElem::track_caps_in(styles),
+        caps,
         fallback: TextElem::fallback_in(styles),
         dir,
     };
@@ -652,6 +710,24 @@ pub(super) fn shape<'a>(
         size,
         width: ctx.glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(size),
         glyphs: Cow::Owned(ctx.glyphs),
+        caps: ctx.caps,
+    }
+}
+
+/// Find the byte ranges in the full paragraph of the text to track as
+/// [capitals](TextElem::track_caps) in a run of text that starts at `base`.
+pub(super) fn find_caps(
+    text: &str,
+    base: usize,
+    styles: StyleChain,
+) -> Arc<[Range<usize>]> {
+    match TextElem::track_caps_in(styles) {
+        Some(caps) => caps
+            .find(text)
+            .into_iter()
+            .map(|range| base + range.start..base + range.end)
+            .collect(),
+        None => Vec::new().into(),
     }
 }
 
@@ -719,7 +795,7 @@ fn shape_segment<'a>(
         buffer.direction(),
         buffer.script(),
         buffer.language().as_ref(),
-        &ctx.features,
+        &segment_features(ctx, &font, text),
     );
 
     // Shape!
@@ -754,6 +830,7 @@ fn shape_segment<'a>(
                 x_advance,
                 x_offset: font.to_em(pos[i].x_offset),
                 y_offset: font.to_em(pos[i].y_offset),
+                scale: Ratio::one(),
                 adjustability: Adjustability::default(),
                 range: start..end,
                 safe_to_break: !info.unsafe_to_break(),
@@ -814,6 +891,37 @@ fn shape_segment<'a>(
     ctx.used.pop();
 }
 
+/// Add the defaults of the font's family and small capitals for acronyms to
+/// the features of the shaping context.
+fn segment_features<'a>(
+    ctx: &'a ShapingContext,
+    font: &Font,
+    text: &str,
+) -> Cow<'a, [rustybuzz::Feature]> {
+    let family = FontFamily::new(&font.info().family);
+    let mut defaults = ctx.family_features.get(&family).peekable();
+    let acronyms = if ctx.acronyms { caps_runs(text) } else { vec![] };
+    if defaults.peek().is_none() && acronyms.is_empty() {
+        return Cow::Borrowed(&ctx.features);
+    }
+
+    // The family's defaults come first so that the other features take
+    // precedence over them.
+    let mut features: Vec<_> = defaults
+        .map(|(tag, value)| rustybuzz::Feature::new(tag, value, ..))
+        .collect();
+    features.extend(ctx.features.iter().cloned());
+    // The range is built by hand because `Feature::new` turns an exclusive
+    // end into an inclusive one, while shaping treats it as exclusive.
+    features.extend(acronyms.into_iter().map(|range| rustybuzz::Feature {
+        tag: Tag::from_bytes(b"c2sc"),
+        value: 1,
+        start: range.start as u32,
+        end: range.end as u32,
+    }));
+    Cow::Owned(features)
+}
+
 /// Create a shape plan.
 #[comemo::memoize]
 fn create_shape_plan(
@@ -845,6 +953,7 @@ fn shape_tofus(ctx: &mut ShapingContext, base: usize, text: &str, font: Font) {
             x_advance,
             x_offset: Em::zero(),
             y_offset: Em::zero(),
+            scale: Ratio::one(),
             adjustability: Adjustability::default(),
             range: start..end,
             safe_to_break: true,
@@ -872,8 +981,28 @@ fn track_and_space(ctx: &mut ShapingContext) {
     let spacing =
         TextElem::spacing_in(ctx.styles).map(|abs| Em::from_length(abs, ctx.size));
 
+    // The runs of capitals to track, if any.
+    let (caps_tracking, caps_size) = match &ctx.track_caps {
+        Some(caps) => {
+            (Em::from_length(caps.tracking.resolve(ctx.styles), ctx.size), caps.size)
+        }
+        None => (Em::zero(), Ratio::one()),
+    };
+    let caps = ctx.caps.clone();
+    let run = |glyph: &ShapedGlyph| {
+        caps.iter().position(|range| range.contains(&glyph.range.start))
+    };
+
     let mut glyphs = ctx.glyphs.iter_mut().peekable();
     while let Some(glyph) = glyphs.next() {
+        let caps_run = run(glyph);
+        if caps_run.is_some() && caps_size != Ratio::one() {
+            glyph.x_advance *= caps_size.get();
+            glyph.x_offset *= caps_size.get();
+            glyph.y_offset *= caps_size.get();
+            glyph.scale = caps_size;
+        }
+
         // Make non-breaking space same width as normal space.
         if glyph.c == '\u{00A0}' {
             glyph.x_advance -= nbsp_delta(&glyph.font).unwrap_or_default();
@@ -888,6 +1017,10 @@ fn track_and_space(ctx: &mut ShapingContext) {
             .is_some_and(|next| glyph.range.start != next.range.start)
         {
             glyph.x_advance += tracking;
+            if caps_run.is_some() && glyphs.peek().and_then(|next| run(next)) == caps_run
+            {
+                glyph.x_advance += caps_tracking;
+            }
         }
     }
 }
@@ -0,0 +1,159 @@
+use ecow::{eco_format, EcoVec};
+
+use crate::diag::{SourceDiagnostic, SourceResult};
+use crate::layout::{Frame, FrameItem};
+use crate::syntax::Span;
+use crate::visualize::{Geometry, Path, PathItem};
+
+/// Limits on the complexity of each page of a document.
+///
+/// Renderers and exporters need memory proportional to the contents of a
+/// page. Services that compile untrusted documents can set these limits to
+/// reject pathological pages before they reach the renderer. All limits are
+/// unset by default.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Limits {
+    /// The maximum number of frame items on a page, including the items of
+    /// nested groups.
+    pub frame_items: Option<usize>,
+    /// The maximum number of path segments of all shapes and clip paths on a
+    /// page.
+    pub path_segments: Option<usize>,
+    /// The maximum number of image pixels on a page, summed across all
+    /// images.
+    pub image_pixels: Option<u64>,
+}
+
+impl Limits {
+    /// Whether no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that the frames of all pages stay within the limits.
+    ///
+    /// Fails with one error per exceeded limit and page. The errors point at
+    /// the first content on the offending page.
+    pub fn check<'a>(
+        &self,
+        frames: impl IntoIterator<Item = &'a Frame>,
+    ) -> SourceResult<()> {
+        if self.is_unlimited() {
+            return Ok(());
+        }
+
+        let mut errors = EcoVec::new();
+        for (i, frame) in frames.into_iter().enumerate() {
+            let mut usage = Usage::new();
+            usage.visit(frame);
+            self.report(i + 1, &usage, &mut errors);
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+
+    /// Report the limits that the usage of a page exceeds.
+    fn report(
+        &self,
+        number: usize,
+        usage: &Usage,
+        errors: &mut EcoVec<SourceDiagnostic>,
+    ) {
+        let mut exceeded = |count: u64, limit: Option<u64>, what: &str| {
+            if let Some(limit) = limit.filter(|&limit| count > limit) {
+                errors.push(
+                    SourceDiagnostic::error(
+                        usage.span,
+                        eco_format!(
+                            "page {number} contains {count} {what}, \
+                             which exceeds the limit of {limit}"
+                        ),
+                    )
+                    .with_hint("try splitting the content across more pages"),
+                );
+            }
+        };
+
+        exceeded(
+            usage.frame_items as u64,
+            self.frame_items.map(|v| v as u64),
+            "frame items",
+        );
+        exceeded(
+            usage.path_segments as u64,
+            self.path_segments.map(|v| v as u64),
+            "path segments",
+        );
+        exceeded(usage.image_pixels, self.image_pixels, "image pixels");
+    }
+}
+
+/// The resources used by a page.
+struct Usage {
+    frame_items: usize,
+    path_segments: usize,
+    image_pixels: u64,
+    /// The span of the first content on the page.
+    span: Span,
+}
+
+impl Usage {
+    /// Start with no usage.
+    fn new() -> Self {
+        Self {
+            frame_items: 0,
+            path_segments: 0,
+            image_pixels: 0,
+            span: Span::detached(),
+        }
+    }
+
+    /// Accumulate the usage of a frame and its subframes.
+    fn visit(&mut self, frame: &Frame) {
+        for (_, item) in frame.items() {
+            self.frame_items += 1;
+            let span = match item {
+                FrameItem::Group(group) => {
+                    if let Some(clip) = &group.clip_path {
+                        self.path_segments += segments(clip);
+                    }
+                    self.visit(&group.frame);
+                    Span::detached()
+                }
+                FrameItem::Text(text) => {
+                    text.glyphs.first().map_or(Span::detached(), |glyph| glyph.span.0)
+                }
+                FrameItem::Shape(shape, span) => {
+                    self.path_segments += match &shape.geometry {
+                        Geometry::Line(_) => 1,
+                        Geometry::Rect(_) => 4,
+                        Geometry::Path(path) => segments(path),
+                    };
+                    *span
+                }
+                FrameItem::Image(image, _, span) => {
+                    self.image_pixels += (image.width() * image.height()) as u64;
+                    *span
+                }
+                FrameItem::Link(..) | FrameItem::Tag(_) => Span::detached(),
+            };
+
+            if self.span.is_detached() {
+                self.span = span;
+            }
+        }
+    }
+}
+
+/// The number of segments in a path. Moving the pen and closing the path
+/// don't draw segments of their own.
+fn segments(path: &Path) -> usize {
+    path.0
+        .iter()
+        .filter(|item| matches!(item, PathItem::LineTo(_) | PathItem::CubicTo(..)))
+        .count()
+}
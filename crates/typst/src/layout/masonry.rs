@@ -0,0 +1,202 @@
+use std::num::NonZeroUsize;
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{elem, Cast, Content, NativeElement, Packed, Show, StyleChain};
+use crate::layout::{
+    Abs, Axes, BlockElem, Dir, Fragment, Frame, Length, Point, Ratio, Regions, Rel, Size,
+};
+use crate::text::TextElem;
+use crate::utils::Numeric;
+
+/// Packs items of varying heights into columns.
+///
+/// Each item is placed below the previous item in its column, without leaving
+/// the gaps a [grid]($grid) would leave between rows of unequally tall items.
+/// This makes it suitable for photo sheets and card galleries. When an item
+/// doesn't fit into the remaining space of its column, the masonry continues
+/// in the next region, for instance on the next page.
+///
+/// # Example
+/// ```example
+/// #masonry(
+///   columns: 3,
+///   gutter: 4pt,
+///   ..(28pt, 16pt, 40pt, 20pt, 12pt, 32pt).map(h => rect(
+///     width: 100%,
+///     height: h,
+///     fill: aqua,
+///   )),
+/// )
+/// ```
+#[elem(Show)]
+pub struct MasonryElem {
+    /// The number of columns.
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub columns: NonZeroUsize,
+
+    /// The size of the gaps between the columns and between the items in a
+    /// column.
+    #[resolve]
+    #[default(Ratio::new(0.04).into())]
+    pub gutter: Rel<Length>,
+
+    /// In which column each item is placed.
+    ///
+    /// ```example
+    /// #let cards = (24pt, 12pt, 12pt, 12pt).enumerate().map(((i, h)) => rect(
+    ///   width: 100%,
+    ///   height: h,
+    ///   fill: luma(220),
+    /// )[#(i + 1)])
+    ///
+    /// #masonry(columns: 2, gutter: 4pt, ..cards)
+    /// #masonry(columns: 2, gutter: 4pt, order: "sequential", ..cards)
+    /// ```
+    #[default(MasonryOrder::Shortest)]
+    pub order: MasonryOrder,
+
+    /// The items to arrange.
+    #[variadic]
+    pub children: Vec<Content>,
+}
+
+impl Show for Packed<MasonryElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::multi_layouter(self.clone(), layout_masonry).pack())
+    }
+}
+
+/// How the items of a masonry are distributed over its columns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum MasonryOrder {
+    /// Each item is placed into the column that currently ends the highest,
+    /// which keeps the columns balanced.
+    Shortest,
+    /// The items are placed into the columns in turn, so that they read in
+    /// source order from left to right.
+    Sequential,
+}
+
+/// Layout the masonry.
+#[typst_macros::time(span = elem.span())]
+fn layout_masonry(
+    elem: &Packed<MasonryElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    mut regions: Regions,
+) -> SourceResult<Fragment> {
+    let count = elem.columns(styles).get();
+    let order = elem.order(styles);
+    let dir = TextElem::dir_in(styles);
+
+    // Relative gutters make no sense in an infinitely wide region.
+    let base = regions.base().x;
+    let gutter = elem.gutter(styles).relative_to(if base.is_finite() {
+        base
+    } else {
+        Abs::zero()
+    });
+
+    // With a finite width, all columns are equally wide. Otherwise, they are
+    // as wide as the widest item.
+    let finite = regions.size.x.is_finite();
+    let available = if finite {
+        (regions.size.x - gutter * (count - 1) as f64).max(Abs::zero()) / count as f64
+    } else {
+        Abs::inf()
+    };
+
+    let pod = Regions::one(Size::new(available, regions.base().y), Axes::splat(false));
+    let frames = elem
+        .children()
+        .iter()
+        .map(|child| Ok(child.layout(engine, styles, pod)?.into_frame()))
+        .collect::<SourceResult<Vec<_>>>()?;
+
+    let width = if finite {
+        available
+    } else {
+        frames.iter().map(Frame::width).max().unwrap_or_default()
+    };
+    let total = width * count as f64 + gutter * (count - 1) as f64;
+
+    let mut finished = vec![];
+    let mut packing = Packing::new(count, gutter);
+    for (i, frame) in frames.into_iter().enumerate() {
+        let mut col = packing.pick(order, i);
+        let height = frame.height();
+
+        // Continue in the next region if the item doesn't fit.
+        if !packing.is_empty()
+            && !regions.in_last()
+            && !regions.size.y.fits(packing.next_y(col) + height)
+        {
+            finished.push(packing.finish(total, regions));
+            regions.next();
+            packing = Packing::new(count, gutter);
+            col = packing.pick(order, i);
+        }
+
+        let offset = (width + gutter) * col as f64;
+        let x = if dir == Dir::RTL { total - offset - width } else { offset };
+        let y = packing.next_y(col);
+        packing.bottoms[col] = Some(y + height);
+        packing.items.push((Point::new(x, y), frame));
+    }
+
+    finished.push(packing.finish(total, regions));
+    Ok(Fragment::frames(finished))
+}
+
+/// The columns of a masonry in one region.
+struct Packing {
+    /// Where each column ends, if it contains any items.
+    bottoms: Vec<Option<Abs>>,
+    /// The placed items.
+    items: Vec<(Point, Frame)>,
+    /// The gap between items.
+    gutter: Abs,
+}
+
+impl Packing {
+    /// Create empty columns.
+    fn new(count: usize, gutter: Abs) -> Self {
+        Self { bottoms: vec![None; count], items: vec![], gutter }
+    }
+
+    /// Whether no items were placed yet.
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Where the next item in the given column would be placed.
+    fn next_y(&self, col: usize) -> Abs {
+        self.bottoms[col].map_or(Abs::zero(), |bottom| bottom + self.gutter)
+    }
+
+    /// The column the `i`-th item is placed in.
+    fn pick(&self, order: MasonryOrder, i: usize) -> usize {
+        match order {
+            MasonryOrder::Shortest => (0..self.bottoms.len())
+                .min_by_key(|&col| self.next_y(col))
+                .unwrap_or(0),
+            MasonryOrder::Sequential => i % self.bottoms.len(),
+        }
+    }
+
+    /// Finish the columns into a frame.
+    fn finish(self, width: Abs, regions: Regions) -> Frame {
+        let height = if regions.expand.y {
+            regions.size.y
+        } else {
+            self.bottoms.iter().flatten().copied().max().unwrap_or_default()
+        };
+
+        let mut output = Frame::hard(Size::new(width, height));
+        for (pos, frame) in self.items {
+            output.push_frame(pos, frame);
+        }
+        output
+    }
+}
@@ -3,7 +3,8 @@ use comemo::Tracked;
 use crate::diag::{At, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    dict, func, Content, Context, Dict, Resolve, Smart, StyleChain, Styles,
+    cast, dict, func, Array, Content, Context, Dict, IntoValue, Resolve, Smart,
+    StyleChain, Styles, Value,
 };
 use crate::layout::{Abs, Axes, Length, Regions, Size};
 use crate::syntax::Span;
@@ -42,6 +43,22 @@ use crate::syntax::Span;
 ///
 /// The measure function returns a dictionary with the entries `width` and
 /// `height`, both of type [`length`].
+///
+/// # Measuring candidates
+/// To pick the best of several variants, for instance the largest font size
+/// at which a title still fits, you can pass an array of candidates. They
+/// are all measured in the same context and an array with one dictionary per
+/// candidate is returned.
+///
+/// ```example
+/// #context {
+///   let sizes = (24pt, 18pt, 14pt, 10pt)
+///   let candidates = sizes.map(size => text(size)[Fitting title])
+///   let widths = measure(candidates).map(d => d.width)
+///   let i = widths.position(w => w <= 80pt)
+///   candidates.at(i)
+/// }
+/// ```
 #[func(contextual)]
 pub fn measure(
     /// The engine.
@@ -73,16 +90,26 @@ pub fn measure(
     #[named]
     #[default(Smart::Auto)]
     height: Smart<Length>,
-    /// The content whose size to measure.
-    content: Content,
-    /// _Compatibility:_ This argument only exists for compatibility with
-    /// Typst 0.10 and lower and shouldn't be used anymore.
+    /// The content whose size to measure or an array of candidates to
+    /// measure all at once.
+    content: Measurable,
+    /// Styles to apply on top of the context's styles while measuring, for
+    /// example to try out a font size. Create them with [`styles`].
+    ///
+    /// If no context is available, only these styles are used. Styles
+    /// obtained from the deprecated [`style`] function are accepted for
+    /// compatibility with Typst 0.10 and lower.
     #[default]
     styles: Option<Styles>,
-) -> SourceResult<Dict> {
-    let styles = match &styles {
-        Some(styles) => StyleChain::new(styles),
-        None => context.styles().at(span)?,
+) -> SourceResult<Value> {
+    let outer = context.styles();
+    let styles = match (&styles, &outer) {
+        // Styles obtained from `style` already contain the context's styles,
+        // whose folded properties must not be applied twice.
+        (Some(overrides), Ok(outer)) if *overrides == outer.to_map() => *outer,
+        (Some(overrides), Ok(outer)) => outer.chain(overrides),
+        (Some(overrides), Err(_)) => StyleChain::new(overrides),
+        (None, outer) => outer.clone().at(span)?,
     };
 
     let available = Axes::new(
@@ -91,7 +118,32 @@ pub fn measure(
     );
 
     let pod = Regions::one(available, Axes::splat(false));
-    let frame = content.measure(engine, styles, pod)?.into_frame();
-    let Size { x, y } = frame.size();
-    Ok(dict! { "width" => x, "height" => y })
+    let mut measure = |content: &Content| -> SourceResult<Dict> {
+        let frame = content.measure(engine, styles, pod)?.into_frame();
+        let Size { x, y } = frame.size();
+        Ok(dict! { "width" => x, "height" => y })
+    };
+
+    Ok(match content {
+        Measurable::Single(content) => measure(&content)?.into_value(),
+        Measurable::Candidates(candidates) => candidates
+            .iter()
+            .map(|content| measure(content).map(IntoValue::into_value))
+            .collect::<SourceResult<Array>>()?
+            .into_value(),
+    })
+}
+
+/// Content to measure.
+pub enum Measurable {
+    /// A single content, whose size is returned as a dictionary.
+    Single(Content),
+    /// Multiple candidates, whose sizes are returned as an array.
+    Candidates(Vec<Content>),
+}
+
+cast! {
+    Measurable,
+    v: Content => Self::Single(v),
+    v: Vec<Content> => Self::Candidates(v),
 }
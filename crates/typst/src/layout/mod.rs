@@ -3,6 +3,7 @@
 mod abs;
 mod align;
 mod angle;
+mod assert;
 mod axes;
 mod columns;
 mod container;
@@ -19,6 +20,8 @@ mod inline;
 #[path = "layout.rs"]
 mod layout_;
 mod length;
+mod limits;
+mod masonry;
 #[path = "measure.rs"]
 mod measure_;
 mod pad;
@@ -33,11 +36,14 @@ mod sides;
 mod size;
 mod spacing;
 mod stack;
+mod target;
 mod transform;
+mod vec2;
 
 pub use self::abs::*;
 pub use self::align::*;
 pub use self::angle::*;
+pub use self::assert::*;
 pub use self::axes::*;
 pub use self::columns::*;
 pub use self::container::*;
@@ -52,6 +58,8 @@ pub use self::grid::*;
 pub use self::hide::*;
 pub use self::layout_::*;
 pub use self::length::*;
+pub use self::limits::*;
+pub use self::masonry::*;
 pub use self::measure_::*;
 pub use self::pad::*;
 pub use self::page::*;
@@ -65,11 +73,14 @@ pub use self::sides::*;
 pub use self::size::*;
 pub use self::spacing::*;
 pub use self::stack::*;
+pub use self::target::*;
 pub use self::transform::*;
+pub use self::vec2::*;
 
 pub(crate) use self::inline::*;
 
 use comemo::{Tracked, TrackedMut};
+use typst_timing::TimingScope;
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::{Engine, Route};
@@ -93,6 +104,8 @@ pub fn define(global: &mut Scope) {
     global.define_type::<Angle>();
     global.define_type::<Ratio>();
     global.define_type::<Rel<Length>>();
+    global.define_type::<Vec2>();
+    global.define_type::<Transform>();
     global.define_type::<Fr>();
     global.define_type::<Dir>();
     global.define_type::<Alignment>();
@@ -106,6 +119,7 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<GridElem>();
     global.define_elem::<ColumnsElem>();
     global.define_elem::<ColbreakElem>();
+    global.define_elem::<MasonryElem>();
     global.define_elem::<PlaceElem>();
     global.define_elem::<FlushElem>();
     global.define_elem::<AlignElem>();
@@ -114,7 +128,12 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<MoveElem>();
     global.define_elem::<ScaleElem>();
     global.define_elem::<RotateElem>();
+    global.define_elem::<SkewElem>();
     global.define_elem::<HideElem>();
+    global.define_elem::<ExportOnlyElem>();
+    global.define_elem::<ScreenOnlyElem>();
+    global.define_elem::<AssertLayoutElem>();
+    global.define_elem::<AssertFitsElem>();
     global.define_func::<measure>();
     global.define_func::<layout>();
 }
@@ -201,6 +220,8 @@ impl Content {
             styles: StyleChain,
             regions: Regions,
         ) -> SourceResult<Fragment> {
+            // Only runs when the layout isn't cached.
+            let _scope = TimingScope::new("layout content", Some(content.span()));
             let mut locator = Locator::chained(locator);
             let mut engine = Engine {
                 world,
@@ -10,19 +10,19 @@ use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, AutoValue, Cast, Content, Context, Dict, Fold, Func, NativeElement,
-    Packed, Resolve, Smart, StyleChain, Value,
+    Packed, Resolve, Smart, StyleChain, Styles, Value,
 };
 use crate::introspection::{Counter, CounterDisplayElem, CounterKey, ManualPageCounter};
 use crate::layout::{
-    Abs, AlignElem, Alignment, Axes, ColumnsElem, Dir, Frame, HAlignment, Length,
-    OuterVAlignment, Point, Ratio, Regions, Rel, Sides, Size, SpecificAlignment,
+    Abs, AlignElem, Alignment, Axes, ColumnsElem, Dir, Frame, FrameItem, HAlignment,
+    Length, OuterVAlignment, Point, Ratio, Regions, Rel, Sides, Size, SpecificAlignment,
     VAlignment,
 };
 
 use crate::model::Numbering;
-use crate::text::TextElem;
+use crate::text::{Lang, TextElem};
 use crate::utils::{NonZeroExt, Numeric, Scalar};
-use crate::visualize::Paint;
+use crate::visualize::{ImageElem, Paint};
 
 /// Layouts its child onto one or multiple pages.
 ///
@@ -176,6 +176,28 @@ pub struct PageElem {
     #[default(NonZeroUsize::ONE)]
     pub columns: NonZeroUsize,
 
+    /// Whether to justify the page's content vertically.
+    ///
+    /// When enabled, the weak spacing on each page that is filled up by its
+    /// content stretches so that the last baseline lands where the baseline
+    /// of a line of body text ending flush with the bottom of the page (or
+    /// column) would. This way, the last baselines of all full pages line up.
+    /// This includes the spacing around paragraphs and other blocks and weak
+    /// [`v`] spacing, but not the leading between the lines of a paragraph. The last page of a flow and pages with
+    /// [fractional spacing]($fraction) are left as they are.
+    ///
+    /// ```example
+    /// #set page(height: 120pt, justify: true)
+    /// = Introduction
+    /// #lorem(12)
+    ///
+    /// #lorem(10)
+    ///
+    /// #lorem(8)
+    /// ```
+    #[default(false)]
+    pub justify: bool,
+
     /// The page's background color.
     ///
     /// This instructs the printer to color the complete page with the given
@@ -208,6 +230,40 @@ pub struct PageElem {
     #[borrowed]
     pub numbering: Option<Numbering>,
 
+    /// A counter that divides the pages into sections with their own page
+    /// numbers.
+    ///
+    /// Whenever the counter's first number changes from one page to the next,
+    /// the page counter restarts at one. Page numbers are then displayed with
+    /// the section's number in front, so that with a numbering of `{"1-1"}`,
+    /// the twelfth page of the third chapter is numbered "3-12". This applies
+    /// wherever a page number is shown: in the header or footer, in the
+    /// [outline], in [references]($ref), in `{counter(page).display()}`, and
+    /// in the page labels of exported PDFs. The value of the page counter
+    /// itself, as returned by `{counter(page).get()}`, remains just the page
+    /// number; the section's number is the value of the section counter.
+    ///
+    /// A page belongs to the first section that starts on it or, if no
+    /// section starts on it, to the section of the previous page.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 100pt,
+    ///   numbering: "1-1",
+    ///   section: counter(heading),
+    /// )
+    /// #set heading(numbering: "1.")
+    ///
+    /// = Introduction
+    /// #lorem(20)
+    ///
+    /// #pagebreak()
+    /// = Method
+    /// #lorem(10)
+    /// ```
+    #[borrowed]
+    pub section: Option<Counter>,
+
     /// The alignment of the page numbering.
     ///
     /// If the vertical component is `top`, the numbering is placed into the
@@ -291,6 +347,31 @@ pub struct PageElem {
     #[default(Ratio::new(0.3).into())]
     pub footer_descent: Rel<Length>,
 
+    /// What happens when the header or footer is taller than the space that
+    /// is reserved for it in the margin.
+    ///
+    /// By default, a header or footer that is too tall overflows into the
+    /// page's body. Set this to `{"shrink"}` to first shrink the header ascent
+    /// or footer descent, or to `{"grow"}` to additionally grow the margin on
+    /// the pages where the header or footer is too tall.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 100pt,
+    ///   margin: (top: 32pt, bottom: 16pt),
+    ///   marginal-fit: "grow",
+    ///   header: [
+    ///     _Chapter 1_ \
+    ///     _Introduction to the Theory_ \
+    ///     _of Everything_
+    ///   ],
+    /// )
+    ///
+    /// #lorem(20)
+    /// ```
+    #[default(MarginalFit::Overflow)]
+    pub marginal_fit: MarginalFit,
+
     /// Content in the page's background.
     ///
     /// This content will be placed behind the page's body. It can be
@@ -310,6 +391,47 @@ pub struct PageElem {
     #[borrowed]
     pub background: Option<Content>,
 
+    /// An image or other content that covers the whole page, including its
+    /// [bleed]($page.bleed).
+    ///
+    /// The content is laid out in the size of the page plus bleed, so an
+    /// image with `{fit: "cover"}` fills the page regardless of the paper size
+    /// and the image's [`focus`]($image.focus) determines which part of it
+    /// stays visible. It is placed behind the page's `background`.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 120pt,
+    ///   height: 80pt,
+    ///   background-image: image("tiger.jpg", focus: (50%, 30%)),
+    /// )
+    /// ```
+    #[borrowed]
+    pub background_image: Option<Content>,
+
+    /// How far the page extends beyond its trimmed size on each side.
+    ///
+    /// Printers need backgrounds to extend a few millimeters beyond the
+    /// page's edges so that no white strip remains after trimming. The bleed
+    /// grows the exported page on every side without changing the area the
+    /// margins and the page's content are laid out in. Exported PDFs mark
+    /// the trimmed area as the page's trim box. Like the exported page,
+    /// [positions]($location.position) are measured from the outer edge of
+    /// the bleed.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 120pt,
+    ///   height: 80pt,
+    ///   bleed: 3mm,
+    ///   fill: aqua,
+    /// )
+    /// Trimmed later.
+    /// ```
+    #[resolve]
+    #[default(Length::zero())]
+    pub bleed: Length,
+
     /// Content in the page's foreground.
     ///
     /// This content will overlay the page's body.
@@ -395,37 +517,30 @@ impl Packed<PageElem> {
                 .spanned(self.span());
         }
 
-        let area = size - margin.sum_by_axis();
-        let mut regions = Regions::repeat(area, area.map(Abs::is_finite));
-        regions.root = true;
-
-        // Layout the child.
-        let mut frames = child.layout(engine, styles, regions)?.into_frames();
-
-        // Align the child to the pagebreak's parity.
-        // Check for page count after adding the pending frames
-        if extend_to
-            .is_some_and(|p| !p.matches(page_counter.physical().get() + frames.len()))
-        {
-            // Insert empty page after the current pages.
-            let size = area.map(Abs::is_finite).select(area, Size::zero());
-            frames.push(Frame::hard(size));
-        }
-
         let fill = self.fill(styles);
         let foreground = self.foreground(styles);
         let background = self.background(styles);
+        let background_image = self.background_image(styles);
+        let bleed = self.bleed(styles);
         let header_ascent = self.header_ascent(styles);
         let footer_descent = self.footer_descent(styles);
+        let marginal_fit = self.marginal_fit(styles);
         let numbering = self.numbering(styles);
         let number_align = self.number_align(styles);
+        let section = self.section(styles);
+        if section.as_ref().is_some_and(|counter| counter.is_page()) {
+            bail!(self.span(), "pages cannot be sectioned by the page counter");
+        }
 
-        // Construct the numbering (for header or footer).
+        // Construct the numbering (for header or footer). In sectioned
+        // numberings, the second number is the page's and not the total.
         let numbering_marginal = numbering.as_ref().map(|numbering| {
-            let both = match numbering {
-                Numbering::Pattern(pattern) => pattern.pieces() >= 2,
-                Numbering::Func(_) => true,
-            };
+            let both = section.is_none()
+                && match numbering {
+                    Numbering::Pattern(pattern) => pattern.pieces() >= 2,
+                    Numbering::Func(_) => true,
+                    Numbering::Dependent(_) => false,
+                };
 
             let mut counter = CounterDisplayElem::new(
                 Counter::new(CounterKey::Page),
@@ -458,9 +573,66 @@ impl Packed<PageElem> {
             )
         };
 
+        let area = size - margin.sum_by_axis();
+        let mut regions = Regions::repeat(area, area.map(Abs::is_finite));
+        regions.root = true;
+
+        // Layout the child. If the marginals may grow into the body, they are
+        // laid out page by page before the body, so that their contextual
+        // content sees the page it ends up on. The body is only laid out
+        // again if later pages need a different amount of space than the
+        // earlier ones.
+        let mut marginals = vec![];
+        let mut growth = vec![];
+        let mut frames = if marginal_fit == MarginalFit::Grow && area.is_finite() {
+            marginals.push(layout_marginals(engine, styles, header, footer, area.x)?);
+            growth.push(grow_marginals(&marginals[0], &margin));
+            loop {
+                let heights: Vec<_> =
+                    growth.iter().map(|&(top, bottom)| area.y - top - bottom).collect();
+                let pod = Regions {
+                    size: Size::new(area.x, heights[0]),
+                    backlog: &heights[1..],
+                    last: heights.last().copied(),
+                    ..regions
+                };
+
+                let fragment = child.measure(engine, styles, pod)?;
+                let known = growth.len();
+                let assumed = growth[known - 1];
+                while marginals.len() < fragment.len() {
+                    let sub = layout_marginals(engine, styles, header, footer, area.x)?;
+                    growth.push(grow_marginals(&sub, &margin));
+                    marginals.push(sub);
+                }
+
+                if growth[known..].iter().all(|&g| g == assumed) {
+                    marginals.truncate(fragment.len());
+                    growth.truncate(fragment.len().max(1));
+                    engine.locator.visit_frames(&fragment);
+                    break fragment.into_frames();
+                }
+            }
+        } else {
+            child.layout(engine, styles, regions)?.into_frames()
+        };
+
+        // Align the child to the pagebreak's parity.
+        // Check for page count after adding the pending frames
+        if extend_to
+            .is_some_and(|p| !p.matches(page_counter.physical().get() + frames.len()))
+        {
+            // Insert empty page after the current pages.
+            let mut size = area.map(Abs::is_finite).select(area, Size::zero());
+            if let Some(&(top, bottom)) = growth.last() {
+                size.y -= top + bottom;
+            }
+            frames.push(Frame::hard(size));
+        }
+
         // Post-process pages.
         let mut pages = Vec::with_capacity(frames.len());
-        for mut frame in frames {
+        for (i, mut frame) in frames.into_iter().enumerate() {
             // The padded width of the page's content without margins.
             let pw = frame.width();
 
@@ -472,6 +644,23 @@ impl Packed<PageElem> {
                 std::mem::swap(&mut margin.left, &mut margin.right);
             }
 
+            // Make room for marginals that grow into the body. A page that
+            // was added for parity grows like the last page of the body.
+            if let Some(&(top, bottom)) = growth.get(i).or(growth.last()) {
+                margin.top += top;
+                margin.bottom += bottom;
+            }
+
+            // Unless the marginals may overflow, they are laid out at their
+            // natural height and then fitted into their margins.
+            let [mut top, mut bottom] = match marginals.get_mut(i) {
+                Some(laid_out) => std::mem::take(laid_out),
+                None if marginal_fit != MarginalFit::Overflow => {
+                    layout_marginals(engine, styles, header, footer, pw)?
+                }
+                None => [None, None],
+            };
+
             // Realize margins.
             frame.set_size(frame.size() + margin.sum_by_axis());
             frame.translate(Point::new(margin.left, margin.top));
@@ -486,11 +675,22 @@ impl Packed<PageElem> {
                 let (pos, area, align);
                 if ptr::eq(marginal, header) {
                     let ascent = header_ascent.relative_to(margin.top);
+                    if let Some(sub) = top.take() {
+                        let height = fit_marginal(sub.height(), margin.top, ascent);
+                        let y = (height - sub.height()).max(Abs::zero());
+                        frame.prepend_frame(Point::new(margin.left, y), sub);
+                        continue;
+                    }
                     pos = Point::with_x(margin.left);
                     area = Size::new(pw, margin.top - ascent);
                     align = Alignment::BOTTOM;
                 } else if ptr::eq(marginal, footer) {
                     let descent = footer_descent.relative_to(margin.bottom);
+                    if let Some(sub) = bottom.take() {
+                        let height = fit_marginal(sub.height(), margin.bottom, descent);
+                        frame.push_frame(Point::new(margin.left, size.y - height), sub);
+                        continue;
+                    }
                     pos = Point::new(margin.left, size.y - margin.bottom + descent);
                     area = Size::new(pw, margin.bottom - descent);
                     align = Alignment::TOP;
@@ -514,8 +714,41 @@ impl Packed<PageElem> {
                 }
             }
 
+            // Extend the page by the bleed and cover it with the background
+            // image.
+            if bleed > Abs::zero() {
+                frame.set_size(frame.size() + Size::splat(2.0 * bleed));
+                frame.translate(Point::new(bleed, bleed));
+            }
+
+            if let Some(image) = background_image {
+                // Images are sized to the whole area unless they have a size
+                // of their own, so that their fit applies to it.
+                let full = Smart::Custom(Rel::from(Ratio::one()));
+                let mut map = Styles::new();
+                map.set(ImageElem::set_width(full));
+                map.set(ImageElem::set_height(full));
+                let pod = Regions::one(frame.size(), Axes::splat(true));
+                let sub = image.layout(engine, styles.chain(&map), pod)?.into_frame();
+                frame.prepend_frame(Point::zero(), sub);
+            }
+
             if let Some(fill) = fill {
-                frame.fill(fill.clone());
+                frame.fill(fill.clone(), self.span());
+            }
+
+            // Restart the page counter when a new section begins. The update
+            // goes first so that it applies to everything on the page.
+            let section = match section {
+                Some(counter) => {
+                    let physical = page_counter.physical();
+                    Some(counter.page_section(engine, physical)?)
+                }
+                None => None,
+            };
+            if page_counter.enter_section(section) {
+                frame
+                    .prepend(Point::zero(), FrameItem::Tag(page_counter.restart(engine)));
             }
 
             page_counter.visit(engine, &frame)?;
@@ -523,6 +756,10 @@ impl Packed<PageElem> {
                 frame,
                 numbering: numbering.clone(),
                 number: page_counter.logical(),
+                section,
+                lang: TextElem::lang_in(styles),
+                bleed,
+                margin,
             });
 
             page_counter.step();
@@ -532,6 +769,64 @@ impl Packed<PageElem> {
     }
 }
 
+/// Determine the height of the area a header or footer is placed in, given
+/// its natural height, the size of its margin and the ascent or descent into
+/// the margin.
+///
+/// The ascent or descent shrinks as far as necessary for the marginal to fit
+/// into the margin.
+fn fit_marginal(height: Abs, margin: Abs, offset: Abs) -> Abs {
+    (margin - offset).max(height.min(margin))
+}
+
+/// Lay out the header and footer of a page at their natural heights.
+fn layout_marginals(
+    engine: &mut Engine,
+    styles: StyleChain,
+    header: &Option<Content>,
+    footer: &Option<Content>,
+    width: Abs,
+) -> SourceResult<[Option<Frame>; 2]> {
+    let mut layout = |marginal: &Option<Content>, align: Alignment| {
+        let Some(content) = marginal else { return Ok(None) };
+        let pod = Regions::one(Size::new(width, Abs::inf()), Axes::new(true, false));
+        let frame = content
+            .clone()
+            .styled(AlignElem::set_alignment(align))
+            .layout(engine, styles, pod)?
+            .into_frame();
+        SourceResult::Ok(Some(frame))
+    };
+
+    Ok([layout(header, Alignment::BOTTOM)?, layout(footer, Alignment::TOP)?])
+}
+
+/// How far the laid out header and footer of a page grow beyond their
+/// margins.
+fn grow_marginals(marginals: &[Option<Frame>; 2], margin: &Sides<Abs>) -> (Abs, Abs) {
+    let grow = |frame: &Option<Frame>, margin: Abs| {
+        frame
+            .as_ref()
+            .map_or(Abs::zero(), |frame| (frame.height() - margin).max(Abs::zero()))
+    };
+
+    let [header, footer] = marginals;
+    (grow(header, margin.top), grow(footer, margin.bottom))
+}
+
+/// What happens when a header or footer doesn't fit into its margin.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum MarginalFit {
+    /// The header or footer overflows into the page's body.
+    Overflow,
+    /// The header ascent or footer descent shrinks so that the header or
+    /// footer fits into its margin.
+    Shrink,
+    /// Like `shrink`, but if the header or footer is taller than its whole
+    /// margin, the margin grows on that page and the body gets less space.
+    Grow,
+}
+
 /// A finished page.
 #[derive(Debug, Clone)]
 pub struct Page {
@@ -542,6 +837,16 @@ pub struct Page {
     /// The logical page number (controlled by `counter(page)` and may thus not
     /// match the physical number).
     pub number: usize,
+    /// The number of the section the page belongs to, if the page numbering
+    /// is sectioned.
+    pub section: Option<usize>,
+    /// The text language of the page, in which its number is displayed.
+    pub lang: Lang,
+    /// How far the frame extends beyond the trimmed page on each side.
+    pub bleed: Abs,
+    /// The page's resolved margins, with `inside` and `outside` swapped into
+    /// place and including the room made for growing marginals.
+    pub margin: Sides<Abs>,
 }
 
 /// Specification of the page's margins.
@@ -665,7 +970,7 @@ pub enum Binding {
 
 impl Binding {
     /// Whether to swap left and right margin for the page with this number.
-    fn swap(self, number: NonZeroUsize) -> bool {
+    pub(crate) fn swap(self, number: NonZeroUsize) -> bool {
         match self {
             // Left-bound must swap on even pages
             // (because it is correct on the first page).
@@ -730,6 +1035,7 @@ cast! {
 /// A list of page ranges to be exported. The ranges are one-indexed.
 /// For example, `1..=3` indicates the first, second and third pages should be
 /// exported.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct PageRanges(Vec<PageRange>);
 
 pub type PageRange = RangeInclusive<Option<NonZeroUsize>>;
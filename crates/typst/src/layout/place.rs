@@ -2,8 +2,9 @@ use crate::diag::{bail, At, Hint, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{elem, scope, Content, Packed, Smart, StyleChain, Unlabellable};
 use crate::layout::{
-    Alignment, Axes, Em, Fragment, Length, Regions, Rel, Size, VAlignment,
+    Alignment, Axes, Em, Fragment, Length, Regions, Rel, Size, VAlignment, Vec2,
 };
+use crate::model::UntrustedElem;
 use crate::realize::{Behave, Behaviour};
 
 /// Places content at an absolute position.
@@ -70,6 +71,19 @@ pub struct PlaceElem {
     #[resolve]
     pub clearance: Length,
 
+    /// The displacement of the placed content as a [vector]($vec2). This is
+    /// shorthand to set `dx` and `dy` at once. Both take precedence over it.
+    ///
+    /// ```example
+    /// #set page(height: 60pt)
+    /// #let step = vec2(12pt, 6pt)
+    /// #for i in range(4) {
+    ///   place(offset: step * i)[#i]
+    /// }
+    /// ```
+    #[external]
+    pub offset: Vec2,
+
     /// The horizontal displacement of the placed content.
     ///
     /// ```example
@@ -83,6 +97,10 @@ pub struct PlaceElem {
     /// This does not affect the layout of in-flow content.
     /// In other words, the placed content is treated as if it
     /// were wrapped in a [`move`] element.
+    #[parse(
+        let offset = args.named::<Vec2>("offset")?;
+        args.named("dx")?.or(offset.map(|offset| offset.x.into()))
+    )]
     pub dx: Rel<Length>,
 
     /// The vertical displacement of the placed content.
@@ -90,6 +108,7 @@ pub struct PlaceElem {
     /// This does not affect the layout of in-flow content.
     /// In other words, the placed content is treated as if it
     /// were wrapped in a [`move`] element.
+    #[parse(args.named("dy")?.or(offset.map(|offset| offset.y.into())))]
     pub dy: Rel<Length>,
 
     /// The content to place.
@@ -116,6 +135,10 @@ impl Packed<PlaceElem> {
         let float = self.float(styles);
         let alignment = self.alignment(styles);
 
+        if !float && UntrustedElem::is_active(engine, styles) {
+            bail!(self.span(), "untrusted content may not use absolute placement");
+        }
+
         if float
             && alignment.is_custom_and(|align| {
                 matches!(align.y(), None | Some(VAlignment::Horizon))
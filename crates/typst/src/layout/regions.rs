@@ -1,6 +1,7 @@
 use std::fmt::{self, Debug, Formatter};
 
-use crate::layout::{Abs, Axes, Size};
+use crate::layout::{Abs, Axes, Point, Size};
+use crate::utils::Numeric;
 
 /// A single region to layout into.
 #[derive(Debug, Copy, Clone, Hash)]
@@ -28,9 +29,9 @@ impl Region {
 ///
 /// A *region* is a contiguous rectangular space in which elements
 /// can be laid out. All regions within a `Regions` object have the
-/// same width, namely `self.size.x`. This means that it is not
-/// currently possible to, for instance, have content wrap to the
-/// side of a floating element.
+/// same width, namely `self.size.x`. Within this rectangle, an optional
+/// [contour](Self::contour) can restrict the horizontal extent of each line
+/// of text.
 #[derive(Copy, Clone, Hash)]
 pub struct Regions<'a> {
     /// The remaining size of the first region.
@@ -50,6 +51,12 @@ pub struct Regions<'a> {
     /// True for the padded page regions and columns directly in the page,
     /// false otherwise.
     pub root: bool,
+    /// A polygon that bounds the lines of paragraphs laid out directly into
+    /// these regions, relative to the top-left corner of each region.
+    ///
+    /// This is not passed on to nested regions since their coordinate systems
+    /// differ.
+    pub contour: Option<&'a [Point]>,
 }
 
 impl Regions<'_> {
@@ -62,6 +69,7 @@ impl Regions<'_> {
             last: None,
             expand,
             root: false,
+            contour: None,
         }
     }
 
@@ -74,6 +82,7 @@ impl Regions<'_> {
             last: Some(size.y),
             expand,
             root: false,
+            contour: None,
         }
     }
 
@@ -103,6 +112,7 @@ impl Regions<'_> {
             last: self.last.map(|y| f(Size::new(x, y)).y),
             expand: self.expand,
             root: self.root,
+            contour: None,
         }
     }
 
@@ -151,6 +161,61 @@ impl Regions<'_> {
     }
 }
 
+/// Determine the horizontal span of a contour that is available to a line of
+/// text between `top` and `bottom`.
+///
+/// Where the contour is crossed multiple times, the widest span at each height
+/// is used. Returns `None` if the line is entirely above or below the contour,
+/// in which case it is not restricted.
+pub(crate) fn contour_span(
+    contour: &[Point],
+    top: Abs,
+    bottom: Abs,
+) -> Option<(Abs, Abs)> {
+    let (min, max) = contour
+        .iter()
+        .fold((Abs::inf(), -Abs::inf()), |(min, max), p| (min.min(p.y), max.max(p.y)));
+    if contour.len() < 3 || bottom < min || top > max {
+        return None;
+    }
+
+    // Sample the contour at the line's edges and at all vertices in between.
+    let samples = [top.max(min), bottom.min(max)]
+        .into_iter()
+        .chain(contour.iter().map(|p| p.y).filter(|&y| y > top && y < bottom));
+
+    let mut span = (-Abs::inf(), Abs::inf());
+    for (left, right) in samples.filter_map(|y| widest_crossing(contour, y)) {
+        span.0.set_max(left);
+        span.1.set_min(right);
+    }
+
+    if !span.0.is_finite() {
+        return None;
+    }
+
+    span.1.set_max(span.0);
+    Some(span)
+}
+
+/// The widest span inside the contour at a given height, if the contour
+/// crosses this height.
+fn widest_crossing(contour: &[Point], y: Abs) -> Option<(Abs, Abs)> {
+    let mut xs = vec![];
+    for (i, &a) in contour.iter().enumerate() {
+        let b = contour[(i + 1) % contour.len()];
+        let (lo, hi) = if a.y <= b.y { (a, b) } else { (b, a) };
+        if lo.y <= y && y < hi.y {
+            xs.push(lo.x + (hi.x - lo.x) * ((y - lo.y) / (hi.y - lo.y)));
+        }
+    }
+
+    xs.sort();
+    xs.chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .max_by_key(|&(left, right)| right - left)
+}
+
 impl Debug for Regions<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str("Regions ")?;
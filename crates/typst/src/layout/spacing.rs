@@ -16,6 +16,15 @@ use crate::utils::Numeric;
 /// First #h(2fr) Second #h(1fr) Third
 /// ```
 ///
+/// # Filled spacing
+/// Fractional spacing can be [filled]($h.fill) with content, for instance to
+/// draw lines into a form:
+///
+/// ```example
+/// Name #h(2fr, fill: line(length: 100%)) \
+/// Date #h(1fr, fill: line(length: 100%))
+/// ```
+///
 /// # Mathematical Spacing { #math-spacing }
 /// In [mathematical formulas]($category/math), you can additionally use these
 /// constants to add spacing between elements: `thin`, `med`, `thick`, `quad`, `wide`.
@@ -52,6 +61,32 @@ pub struct HElem {
     /// ```
     #[default(false)]
     pub weak: bool,
+
+    /// The weight of fractional spacing.
+    ///
+    /// If a line contains fractional spacings of different weights, only
+    /// those with the highest weight receive the remaining space, while the
+    /// others collapse to nothing. This way, a spacing can take precedence
+    /// over others, for instance over a fill that would otherwise share the
+    /// space. Has no effect on absolute or relative spacing.
+    ///
+    /// ```example
+    /// A #h(1fr) B #h(1fr, weight: 2) C
+    /// ```
+    #[default(1)]
+    pub weight: usize,
+
+    /// Content to draw into fractional spacing.
+    ///
+    /// The content is laid out in a box as wide as the spacing, so a
+    /// [line] with a length of `{100%}` spans the whole spacing. Has no effect
+    /// on absolute or relative spacing.
+    ///
+    /// ```example
+    /// Name #h(1fr, fill: line(length: 100%, stroke: 0.5pt))
+    /// ```
+    #[borrowed]
+    pub fill: Option<Content>,
 }
 
 impl HElem {
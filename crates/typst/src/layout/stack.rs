@@ -7,8 +7,8 @@ use crate::foundations::{
     cast, elem, Content, NativeElement, Packed, Resolve, Show, StyleChain, StyledElem,
 };
 use crate::layout::{
-    Abs, AlignElem, Axes, Axis, BlockElem, Dir, FixedAlignment, Fr, Fragment, Frame,
-    HElem, Point, Regions, Size, Spacing, VElem,
+    text_baselines, Abs, AlignElem, Axes, Axis, BlockElem, Dir, FixedAlignment, Fr,
+    Fragment, Frame, HElem, Point, Regions, Size, Spacing, Transform, VElem,
 };
 use crate::utils::{Get, Numeric};
 
@@ -49,6 +49,27 @@ pub struct StackElem {
     /// Spacing to insert between items where no explicit spacing was provided.
     pub spacing: Option<Spacing>,
 
+    /// Whether to align the first baselines of the children in a horizontal
+    /// stack.
+    ///
+    /// When enabled, the children are shifted along the cross axis so that
+    /// the first lines of text in all of them sit on a shared baseline.
+    /// Children without text are aligned at their baseline if they have one
+    /// (like a box) or at their bottom edge otherwise. This overrides the
+    /// children's vertical alignment. In vertical stacks, it has no effect.
+    ///
+    /// ```example
+    /// #stack(
+    ///   dir: ltr,
+    ///   spacing: 8pt,
+    ///   baseline: true,
+    ///   text(20pt)[Big],
+    ///   block(width: 60pt)[small text that wraps],
+    /// )
+    /// ```
+    #[default(false)]
+    pub baseline: bool,
+
     /// The children to stack along the axis.
     #[variadic]
     pub children: Vec<StackChild>,
@@ -96,7 +117,13 @@ fn layout_stack(
     styles: StyleChain,
     regions: Regions,
 ) -> SourceResult<Fragment> {
-    let mut layouter = StackLayouter::new(elem.span(), elem.dir(styles), regions, styles);
+    let mut layouter = StackLayouter::new(
+        elem.span(),
+        elem.dir(styles),
+        elem.baseline(styles),
+        regions,
+        styles,
+    );
     let axis = layouter.dir.axis();
 
     // Spacing to insert before the next block.
@@ -145,6 +172,8 @@ struct StackLayouter<'a> {
     dir: Dir,
     /// The axis of the stacking direction.
     axis: Axis,
+    /// Whether to align the children's first baselines.
+    baseline: bool,
     /// The regions to layout children into.
     regions: Regions<'a>,
     /// The inherited styles.
@@ -179,6 +208,7 @@ impl<'a> StackLayouter<'a> {
     fn new(
         span: Span,
         dir: Dir,
+        baseline: bool,
         mut regions: Regions<'a>,
         styles: StyleChain<'a>,
     ) -> Self {
@@ -192,6 +222,7 @@ impl<'a> StackLayouter<'a> {
             span,
             dir,
             axis,
+            baseline: baseline && axis == Axis::X,
             regions,
             styles,
             expand,
@@ -276,6 +307,22 @@ impl<'a> StackLayouter<'a> {
 
     /// Advance to the next region.
     fn finish_region(&mut self) -> SourceResult<()> {
+        // Find the shared baseline and grow the cross axis so that the frames
+        // shifted onto it still fit.
+        let mut shared = None;
+        if self.baseline {
+            let frames = self.items.iter().filter_map(|item| match item {
+                StackItem::Frame(frame, _) => Some(frame),
+                _ => None,
+            });
+            let baseline = frames.clone().map(first_baseline).fold(Abs::zero(), Abs::max);
+            for frame in frames {
+                let bottom = baseline - first_baseline(frame) + frame.height();
+                self.used.cross.set_max(bottom);
+            }
+            shared = Some(baseline);
+        }
+
         // Determine the size of the stack in this region depending on whether
         // the region expands.
         let mut size = self
@@ -323,9 +370,12 @@ impl<'a> StackLayouter<'a> {
 
                     // Align along the cross axis.
                     let other = self.axis.other();
-                    let cross = align
-                        .get(other)
-                        .position(size.get(other) - frame.size().get(other));
+                    let cross = match shared {
+                        Some(baseline) => baseline - first_baseline(&frame),
+                        None => align
+                            .get(other)
+                            .position(size.get(other) - frame.size().get(other)),
+                    };
 
                     let pos = GenericSize::new(cross, main).to_point(self.axis);
                     cursor += child;
@@ -351,6 +401,16 @@ impl<'a> StackLayouter<'a> {
     }
 }
 
+/// The first baseline of a frame in a stack.
+fn first_baseline(frame: &Frame) -> Abs {
+    if frame.has_baseline() {
+        return frame.baseline();
+    }
+
+    text_baselines(frame, Transform::identity())
+        .map_or(frame.height(), |(first, _)| first)
+}
+
 /// A generic size with main and cross axes. The axes are generic, meaning the
 /// main axis could correspond to either the X or the Y axis.
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
@@ -0,0 +1,97 @@
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{elem, Cast, Content, Packed, Show, StyleChain};
+
+/// Includes content only in some export formats.
+///
+/// The content is laid out as usual, so that the document looks the same in
+/// all formats, but it is removed from the exported output of all formats
+/// that are not listed. This way, interactive hints, crop marks, or answer
+/// keys can live in the same source as the rest of the document.
+///
+/// # Example
+/// ```example
+/// Solve $x + 2 = 5$.
+/// #export-only(("pdf",))[
+///   _Answer:_ $x = 3$
+/// ]
+/// ```
+#[elem(Show)]
+pub struct ExportOnlyElem {
+    /// The formats to include the content in.
+    #[required]
+    pub targets: Vec<ExportTarget>,
+
+    /// The content to include.
+    #[required]
+    pub body: Content,
+
+    /// This style is set on the content contained in the `export-only`
+    /// element.
+    #[internal]
+    #[ghost]
+    pub restriction: Option<Vec<ExportTarget>>,
+}
+
+impl Show for Packed<ExportOnlyElem> {
+    #[typst_macros::time(name = "export-only", span = self.span())]
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        Ok(restrict(self.body().clone(), self.targets().clone(), styles))
+    }
+}
+
+/// Includes content only in formats meant for screens.
+///
+/// Like [`export-only`] with the PNG, SVG, and HTML formats. Useful for hints
+/// and links that only make sense on screen.
+///
+/// # Example
+/// ```example
+/// #screen-only[Click the headings to navigate.]
+/// ```
+#[elem(Show)]
+pub struct ScreenOnlyElem {
+    /// The content to include.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<ScreenOnlyElem> {
+    #[typst_macros::time(name = "screen-only", span = self.span())]
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        Ok(restrict(self.body().clone(), ExportTarget::SCREEN.to_vec(), styles))
+    }
+}
+
+/// Restrict content to the given targets, intersected with the targets of
+/// surrounding restrictions.
+fn restrict(
+    body: Content,
+    mut targets: Vec<ExportTarget>,
+    styles: StyleChain,
+) -> Content {
+    if let Some(outer) = ExportOnlyElem::restriction_in(styles) {
+        targets.retain(|target| outer.contains(target));
+    }
+    body.styled(ExportOnlyElem::set_restriction(Some(targets)))
+}
+
+/// A format a document can be exported to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ExportTarget {
+    /// The Portable Document Format.
+    Pdf,
+    /// Raster images in the PNG format.
+    Png,
+    /// Vector images in the SVG format.
+    Svg,
+    /// Word processor documents in the DOCX format.
+    Docx,
+    /// Web pages in the HTML format.
+    Html,
+}
+
+impl ExportTarget {
+    /// The formats meant for screens, which `screen-only` restricts to.
+    pub const SCREEN: &'static [Self] = &[Self::Png, Self::Svg, Self::Html];
+}
@@ -1,12 +1,17 @@
-use crate::diag::SourceResult;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, At, HintedStrResult, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, Content, NativeElement, Packed, Resolve, Show, StyleChain,
+    elem, func, scope, ty, Content, NativeElement, Packed, Repr, Resolve, Show,
+    StyleChain,
 };
 use crate::layout::{
     Abs, Alignment, Angle, Axes, BlockElem, FixedAlignment, Frame, HAlignment, Length,
-    Point, Ratio, Region, Regions, Rel, Size, VAlignment,
+    Point, Ratio, Region, Regions, Rel, Size, VAlignment, Vec2,
 };
+use crate::syntax::Span;
+use crate::utils::Numeric;
 
 /// Moves content without affecting layout.
 ///
@@ -28,12 +33,39 @@ use crate::layout::{
 /// ```
 #[elem(Show)]
 pub struct MoveElem {
+    /// The displacement of the content as a [vector]($vec2). This is
+    /// shorthand to set `dx` and `dy` at once. Both take precedence over it.
+    ///
+    /// ```example
+    /// #let shift = vec2(4pt, -2pt)
+    /// Hello #move(offset: shift)[World]
+    /// ```
+    #[external]
+    pub offset: Vec2,
+
     /// The horizontal displacement of the content.
+    #[parse(
+        let offset = args.named::<Vec2>("offset")?;
+        args.named("dx")?.or(offset.map(|offset| offset.x.into()))
+    )]
     pub dx: Rel<Length>,
 
     /// The vertical displacement of the content.
+    #[parse(args.named("dy")?.or(offset.map(|offset| offset.y.into())))]
     pub dy: Rel<Length>,
 
+    /// An arbitrary [transform] to apply to the content before displacing it.
+    /// The transform's origin is the top-left corner of the content.
+    ///
+    /// ```example
+    /// #move(
+    ///   transform: transform.skew(ax: -12deg),
+    ///   rect(fill: aqua)[Leaning],
+    /// )
+    /// ```
+    #[default(Transform::identity())]
+    pub transform: Transform,
+
     /// The content to move.
     #[required]
     pub body: Content,
@@ -57,6 +89,10 @@ fn layout_move(
         .body()
         .layout(engine, styles, region.into_regions())?
         .into_frame();
+    let transform = elem.transform(styles);
+    if !transform.is_identity() {
+        frame.transform(transform);
+    }
     let delta = Axes::new(elem.dx(styles), elem.dy(styles)).resolve(styles);
     let delta = delta.zip_map(region.size, Rel::relative_to);
     frame.translate(delta.to_point());
@@ -107,6 +143,15 @@ pub struct RotateElem {
     #[default(HAlignment::Center + VAlignment::Horizon)]
     pub origin: Alignment,
 
+    /// An additional [transform] to apply after the rotation, around the
+    /// same origin.
+    ///
+    /// ```example
+    /// #rotate(20deg, transform: transform.scale(150%, 100%))[Tilted]
+    /// ```
+    #[default(Transform::identity())]
+    pub transform: Transform,
+
     /// Whether the rotation impacts the layout.
     ///
     /// If set to `{false}`, the rotated content will retain the bounding box of
@@ -140,6 +185,7 @@ fn layout_rotate(
 ) -> SourceResult<Frame> {
     let angle = elem.angle(styles);
     let align = elem.origin(styles).resolve(styles);
+    let extra = checked(elem.transform(styles)).at(elem.span())?;
 
     // Compute the new region's approximate size.
     let size = region
@@ -152,10 +198,10 @@ fn layout_rotate(
     measure_and_layout(
         engine,
         region,
-        size,
+        approximate_size(size, extra),
         styles,
         elem.body(),
-        Transform::rotate(angle),
+        extra.pre_concat(Transform::rotate(angle)),
         align,
         elem.reflow(styles),
     )
@@ -200,6 +246,15 @@ pub struct ScaleElem {
     #[default(HAlignment::Center + VAlignment::Horizon)]
     pub origin: Alignment,
 
+    /// An additional [transform] to apply after the scaling, around the
+    /// same origin.
+    ///
+    /// ```example
+    /// #scale(x: -100%, transform: transform.rotate(10deg))[Mirrored]
+    /// ```
+    #[default(Transform::identity())]
+    pub transform: Transform,
+
     /// Whether the scaling impacts the layout.
     ///
     /// If set to `{false}`, the scaled content will be allowed to overlap
@@ -234,6 +289,7 @@ fn layout_scale(
     let sx = elem.x(styles);
     let sy = elem.y(styles);
     let align = elem.origin(styles).resolve(styles);
+    let extra = checked(elem.transform(styles)).at(elem.span())?;
 
     // Compute the new region's approximate size.
     let size = region.size.zip_map(Axes::new(sx, sy), |r, s| s.of(r)).map(Abs::abs);
@@ -241,16 +297,142 @@ fn layout_scale(
     measure_and_layout(
         engine,
         region,
-        size,
+        approximate_size(size, extra),
         styles,
         elem.body(),
-        Transform::scale(sx, sy),
+        extra.pre_concat(Transform::scale(sx, sy)),
         align,
         elem.reflow(styles),
     )
 }
 
-/// A scale-skew-translate transformation.
+/// Skews content.
+///
+/// Slants the content horizontally and/or vertically by the given angles.
+/// Like with [`rotate`] and [`scale`], the layout will act as if the element
+/// was not skewed unless you specify `{reflow: true}`.
+///
+/// # Example
+/// ```example
+/// #skew(x: -12deg)[
+///   This is some fake italic text.
+/// ]
+/// ```
+#[elem(Show)]
+pub struct SkewElem {
+    /// The horizontal skewing angle.
+    ///
+    /// ```example
+    /// #skew(x: 30deg)[Skewed]
+    /// ```
+    #[default(Angle::zero())]
+    pub x: Angle,
+
+    /// The vertical skewing angle.
+    ///
+    /// ```example
+    /// #skew(y: 10deg)[Skewed]
+    /// ```
+    #[default(Angle::zero())]
+    pub y: Angle,
+
+    /// The origin of the skew transformation.
+    ///
+    /// The origin stays fixed while the rest of the content is skewed around
+    /// it.
+    ///
+    /// ```example
+    /// X#box(skew(x: 30deg, origin: bottom)[X])X \
+    /// X#box(skew(x: 30deg, origin: top)[X])X
+    /// ```
+    #[fold]
+    #[default(HAlignment::Center + VAlignment::Horizon)]
+    pub origin: Alignment,
+
+    /// An additional [transform] to apply after the skew, around the
+    /// same origin.
+    ///
+    /// ```example
+    /// #skew(x: -12deg, transform: transform.scale(120%))[Slanted]
+    /// ```
+    #[default(Transform::identity())]
+    pub transform: Transform,
+
+    /// Whether the skew transformation impacts the layout.
+    ///
+    /// If set to `{false}`, the skewed content will retain the bounding box of
+    /// the original content. If set to `{true}`, the bounding box will take the
+    /// transformation of the content into account and adjust the layout
+    /// accordingly.
+    ///
+    /// ```example
+    /// Hello #skew(x: 30deg, reflow: true)[World]!
+    /// ```
+    #[default(false)]
+    pub reflow: bool,
+
+    /// The content to skew.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<SkewElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_skew).pack())
+    }
+}
+
+/// Layout the skewed content.
+#[typst_macros::time(span = elem.span())]
+fn layout_skew(
+    elem: &Packed<SkewElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let transform = checked_skew(elem.x(styles), elem.y(styles)).at(elem.span())?;
+    let align = elem.origin(styles).resolve(styles);
+    let extra = checked(elem.transform(styles)).at(elem.span())?;
+
+    // Compute the new region's approximate size.
+    let size = region
+        .size
+        .to_point()
+        .transform_inf(transform)
+        .map(Abs::abs)
+        .to_size();
+
+    measure_and_layout(
+        engine,
+        region,
+        approximate_size(size, extra),
+        styles,
+        elem.body(),
+        extra.pre_concat(transform),
+        align,
+        elem.reflow(styles),
+    )
+}
+
+/// A two-dimensional affine transformation.
+///
+/// A transform maps a point `(x, y)` to `(sx * x + kx * y + tx, ky * x + sy *
+/// y + ty)`. You can create transforms for translation, rotation, scaling, and
+/// skewing, and combine them with [`then`]($transform.then). Transforms can be
+/// used to [`move`] content and to transform [patterns]($pattern.transform).
+///
+/// # Example
+/// ```example
+/// #let t = transform.rotate(30deg).then(transform.translate(20pt, 0pt))
+/// #move(transform: t, rect(fill: aqua)[Turned])
+/// #t.apply(vec2(10pt, 0pt))
+/// ```
+///
+/// # Fields
+/// - `sx`, `sy`: The scaling factors, as [ratios]($ratio).
+/// - `kx`, `ky`: The skewing factors, as [ratios]($ratio).
+/// - `tx`, `ty`: The translation, as [lengths]($length).
+#[ty(scope)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Transform {
     pub sx: Ratio,
@@ -261,6 +443,201 @@ pub struct Transform {
     pub ty: Abs,
 }
 
+#[scope]
+impl Transform {
+    /// Creates a transform from the entries of its matrix.
+    ///
+    /// ```example
+    /// #transform(sx: 200%, ty: 10pt)
+    /// ```
+    #[func(constructor)]
+    pub fn construct(
+        /// The call site of this function.
+        span: Span,
+        /// The horizontal scaling factor.
+        #[named]
+        #[default(Ratio::one())]
+        sx: Ratio,
+        /// The vertical skewing factor.
+        #[named]
+        #[default(Ratio::zero())]
+        ky: Ratio,
+        /// The horizontal skewing factor.
+        #[named]
+        #[default(Ratio::zero())]
+        kx: Ratio,
+        /// The vertical scaling factor.
+        #[named]
+        #[default(Ratio::one())]
+        sy: Ratio,
+        /// The horizontal translation.
+        #[named]
+        #[default(Length::zero())]
+        tx: Length,
+        /// The vertical translation.
+        #[named]
+        #[default(Length::zero())]
+        ty: Length,
+    ) -> SourceResult<Transform> {
+        let (tx, ty) = absolute(tx, ty).at(span)?;
+        Ok(Self { sx, ky, kx, sy, tx, ty })
+    }
+
+    /// Creates a translation.
+    ///
+    /// ```example
+    /// #transform.translate(5pt, 10pt)
+    /// ```
+    #[func]
+    pub fn translate_(
+        /// The call site of this function.
+        span: Span,
+        /// The horizontal translation.
+        #[default(Length::zero())]
+        dx: Length,
+        /// The vertical translation.
+        #[default(Length::zero())]
+        dy: Length,
+    ) -> SourceResult<Transform> {
+        let (tx, ty) = absolute(dx, dy).at(span)?;
+        Ok(Self::translate(tx, ty))
+    }
+
+    /// Creates a rotation around the origin. Positive angles rotate clockwise.
+    ///
+    /// ```example
+    /// #transform.rotate(90deg)
+    /// ```
+    #[func]
+    pub fn rotate_(
+        /// The angle of the rotation.
+        angle: Angle,
+    ) -> Transform {
+        Self::rotate(angle)
+    }
+
+    /// Creates a scaling. If only one factor is given, it is used for both
+    /// axes.
+    ///
+    /// ```example
+    /// #transform.scale(50%) \
+    /// #transform.scale(-100%, 100%)
+    /// ```
+    #[func]
+    pub fn scale_(
+        /// The horizontal scaling factor.
+        x: Ratio,
+        /// The vertical scaling factor. Defaults to the horizontal one.
+        #[default]
+        y: Option<Ratio>,
+    ) -> Transform {
+        Self::scale(x, y.unwrap_or(x))
+    }
+
+    /// Creates a skew.
+    ///
+    /// ```example
+    /// #transform.skew(ax: 45deg)
+    /// ```
+    #[func]
+    pub fn skew_(
+        /// The call site of this function.
+        span: Span,
+        /// The horizontal skewing angle.
+        #[named]
+        #[default(Angle::zero())]
+        ax: Angle,
+        /// The vertical skewing angle.
+        #[named]
+        #[default(Angle::zero())]
+        ay: Angle,
+    ) -> SourceResult<Transform> {
+        checked_skew(ax, ay).at(span)
+    }
+
+    /// Combines this transform with another one that is applied afterwards.
+    ///
+    /// ```example
+    /// #let t = transform.scale(200%).then(transform.translate(5pt, 0pt))
+    /// #t.apply(vec2(1pt, 1pt))
+    /// ```
+    #[func]
+    pub fn then(
+        self,
+        /// The transform to apply after this one.
+        next: Transform,
+    ) -> Transform {
+        self.post_concat(next)
+    }
+
+    /// Computes the transform that undoes this one.
+    ///
+    /// Fails with an error if the transform collapses the plane onto a line or
+    /// a point, such as a scaling by `{0%}`.
+    ///
+    /// ```example
+    /// #transform.translate(5pt, 0pt).inverse()
+    /// ```
+    #[func]
+    pub fn inverse(self) -> StrResult<Transform> {
+        self.invert()
+            .ok_or_else(|| "cannot invert a transform with a determinant of zero".into())
+    }
+
+    /// Applies this transform to a point.
+    ///
+    /// Fails with an error if the point has non-zero `em` units.
+    ///
+    /// ```example
+    /// #transform.rotate(90deg).apply(vec2(10pt, 0pt))
+    /// ```
+    #[func]
+    pub fn apply(
+        self,
+        /// The point to transform.
+        point: Vec2,
+    ) -> HintedStrResult<Vec2> {
+        if !point.x.em.is_zero() || !point.y.em.is_zero() {
+            bail!(
+                "cannot transform a point with non-zero em units (`{}`)",
+                point.repr();
+                hint: "use `vec.to-absolute()` to resolve its em units first"
+            );
+        }
+        Ok(Point::new(point.x.abs, point.y.abs).transform(self).into())
+    }
+}
+
+/// Creates a skew, failing for angles whose tangent is infinite.
+fn checked_skew(ax: Angle, ay: Angle) -> StrResult<Transform> {
+    if ax.cos().abs() < 1e-12 || ay.cos().abs() < 1e-12 {
+        bail!("skewing angle must not be an odd multiple of 90deg");
+    }
+    let skew = Transform::skew(ax, ay);
+    if skew.is_singular() {
+        bail!("skewing angles must not collapse the content onto a line");
+    }
+    Ok(skew)
+}
+
+/// Ensures that an additional transform can be inverted, which exporters
+/// rely on to map paints into the transformed content.
+fn checked(transform: Transform) -> StrResult<Transform> {
+    if transform.is_singular() {
+        bail!("transform must be invertible");
+    }
+    Ok(transform)
+}
+
+/// Extracts the absolute parts of a translation, failing for font-relative
+/// lengths.
+fn absolute(x: Length, y: Length) -> StrResult<(Abs, Abs)> {
+    if !x.em.is_zero() || !y.em.is_zero() {
+        bail!("transform translation must be absolute");
+    }
+    Ok((x.abs, y.abs))
+}
+
 impl Transform {
     /// The identity transformation.
     pub const fn identity() -> Self {
@@ -297,6 +674,15 @@ impl Transform {
         }
     }
 
+    /// A skew transform.
+    pub fn skew(ax: Angle, ay: Angle) -> Self {
+        Self {
+            kx: Ratio::new(ax.tan()),
+            ky: Ratio::new(ay.tan()),
+            ..Self::identity()
+        }
+    }
+
     /// Whether this is the identity transformation.
     pub fn is_identity(self) -> bool {
         self == Self::identity()
@@ -319,21 +705,27 @@ impl Transform {
         next.pre_concat(self)
     }
 
+    /// Whether the transform collapses the plane onto a line or a point,
+    /// that is, whether its determinant is zero.
+    pub fn is_singular(self) -> bool {
+        (self.sx * self.sy - self.kx * self.ky).get().abs() < 1e-12
+    }
+
     /// Inverts the transformation.
     ///
-    /// Returns `None` if the determinant of the matrix is zero.
+    /// Returns `None` if the transformation is [singular](Self::is_singular).
     pub fn invert(self) -> Option<Self> {
         // Allow the trivial case to be inlined.
         if self.is_identity() {
             return Some(self);
         }
 
+        if self.is_singular() {
+            return None;
+        }
+
         // Fast path for scale-translate-only transforms.
         if self.kx.is_zero() && self.ky.is_zero() {
-            if self.sx.is_zero() || self.sy.is_zero() {
-                return Some(Self::translate(-self.tx, -self.ty));
-            }
-
             let inv_x = 1.0 / self.sx;
             let inv_y = 1.0 / self.sy;
             return Some(Self {
@@ -347,9 +739,6 @@ impl Transform {
         }
 
         let det = self.sx * self.sy - self.kx * self.ky;
-        if det.get().abs() < 1e-12 {
-            return None;
-        }
 
         let inv_det = 1.0 / det;
         Some(Self {
@@ -375,6 +764,21 @@ impl Default for Transform {
     }
 }
 
+impl Repr for Transform {
+    fn repr(&self) -> EcoString {
+        let Self { sx, ky, kx, sy, tx, ty } = self;
+        eco_format!(
+            "transform(sx: {}, ky: {}, kx: {}, sy: {}, tx: {}, ty: {})",
+            sx.repr(),
+            ky.repr(),
+            kx.repr(),
+            sy.repr(),
+            tx.repr(),
+            ty.repr()
+        )
+    }
+}
+
 /// Applies a transformation to a frame, reflowing the layout if necessary.
 #[allow(clippy::too_many_arguments)]
 fn measure_and_layout(
@@ -424,6 +828,20 @@ fn measure_and_layout(
     }
 }
 
+/// Approximates the size of a region after an additional `transform` is
+/// applied by computing the bounding box of the transformed region.
+/// Translations don't affect the size.
+fn approximate_size(size: Size, extra: Transform) -> Size {
+    if extra.is_identity() {
+        return size;
+    }
+
+    Size::new(
+        extra.sx.get().abs() * size.x + extra.kx.get().abs() * size.y,
+        extra.ky.get().abs() * size.x + extra.sy.get().abs() * size.y,
+    )
+}
+
 /// Computes the bounding box and offset of a transformed frame.
 fn compute_bounding_box(frame: &Frame, ts: Transform) -> (Point, Size) {
     let top_left = Point::zero().transform_inf(ts);
@@ -0,0 +1,275 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use comemo::Tracked;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, HintedStrResult, SourceResult};
+use crate::foundations::{
+    array, cast, func, scope, ty, Args, Array, Context, Repr, Resolve, StyleChain,
+};
+use crate::layout::{Abs, Axes, Length, Point, Rel};
+use crate::utils::Numeric;
+
+/// A two-dimensional vector, such as a point or an offset.
+///
+/// Wherever a point is expected, for instance in the vertices of a
+/// [polygon] or the `start` of a [line], you can pass a vector instead of an
+/// array of two lengths.
+///
+/// Vectors can be added to and subtracted from each other and multiplied
+/// with and divided by integers and floats.
+///
+/// # Example
+/// ```example
+/// #let a = vec2(10pt, 0pt)
+/// #let b = vec2(0pt, 20pt)
+/// #let c = a + b
+/// #c.x, #c.y \
+/// #c.length() \
+/// #polygon(fill: teal, (0pt, 0pt), a * 3, b * 2)
+/// ```
+///
+/// # Fields
+/// - `x`: The horizontal component of the vector, as a [length].
+/// - `y`: The vertical component of the vector, as a [length].
+#[ty(scope, cast, title = "Vector")]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Vec2 {
+    /// The horizontal component.
+    pub x: Length,
+    /// The vertical component.
+    pub y: Length,
+}
+
+impl Vec2 {
+    /// Create a new vector from its components.
+    pub const fn new(x: Length, y: Length) -> Self {
+        Self { x, y }
+    }
+
+    /// The absolute components of the vector, if it has no font-relative
+    /// parts.
+    fn try_abs(self) -> HintedStrResult<(Abs, Abs)> {
+        if !self.x.em.is_zero() || !self.y.em.is_zero() {
+            bail!(
+                "cannot compute this for a vector with non-zero em units (`{}`)",
+                self.repr();
+                hint: "use `vec.to-absolute()` to resolve its em units first"
+            );
+        }
+        Ok((self.x.abs, self.y.abs))
+    }
+}
+
+#[scope]
+impl Vec2 {
+    /// Creates a new vector.
+    ///
+    /// ```example
+    /// #vec2(1pt, 2pt) \
+    /// #vec2((3pt, 4pt))
+    /// ```
+    #[func(constructor)]
+    pub fn construct(
+        /// The real arguments (the other arguments are just for the docs, this
+        /// function accepts both an array and two lengths).
+        args: &mut Args,
+        /// The horizontal component. Instead of the two components, you can
+        /// also pass an array of two lengths.
+        #[external]
+        x: Length,
+        /// The vertical component.
+        #[external]
+        y: Length,
+    ) -> SourceResult<Vec2> {
+        if let Some(vec) = args.find::<Vec2>()? {
+            return Ok(vec);
+        }
+        let x = args.expect("x")?;
+        let y = args.expect("y")?;
+        Ok(Self::new(x, y))
+    }
+
+    /// Calculates the dot product of this and another vector, in square
+    /// points.
+    ///
+    /// Fails with an error if one of the vectors has non-zero `em` units.
+    ///
+    /// ```example
+    /// #vec2(3pt, 4pt).dot(vec2(2pt, 1pt))
+    /// ```
+    #[func]
+    pub fn dot(
+        self,
+        /// The other vector.
+        other: Vec2,
+    ) -> HintedStrResult<f64> {
+        let (ax, ay) = self.try_abs()?;
+        let (bx, by) = other.try_abs()?;
+        Ok(ax.to_pt() * bx.to_pt() + ay.to_pt() * by.to_pt())
+    }
+
+    /// Calculates the magnitude of the two-dimensional cross product of this
+    /// and another vector, in square points. It is positive if the other
+    /// vector points clockwise from this one on the page.
+    ///
+    /// Fails with an error if one of the vectors has non-zero `em` units.
+    ///
+    /// ```example
+    /// #vec2(1pt, 0pt).cross(vec2(0pt, 1pt))
+    /// ```
+    #[func]
+    pub fn cross(
+        self,
+        /// The other vector.
+        other: Vec2,
+    ) -> HintedStrResult<f64> {
+        let (ax, ay) = self.try_abs()?;
+        let (bx, by) = other.try_abs()?;
+        Ok(ax.to_pt() * by.to_pt() - ay.to_pt() * bx.to_pt())
+    }
+
+    /// Calculates the length of the vector.
+    ///
+    /// Fails with an error if the vector has non-zero `em` units.
+    ///
+    /// ```example
+    /// #vec2(3pt, 4pt).length()
+    /// ```
+    #[func]
+    pub fn length(self) -> HintedStrResult<Length> {
+        let (x, y) = self.try_abs()?;
+        Ok(Point::new(x, y).hypot().into())
+    }
+
+    /// Scales the vector so that it has a length of `{1pt}`, while keeping its
+    /// direction. Multiply the result to get a vector of any other length.
+    ///
+    /// Fails with an error if the vector has non-zero `em` units or a length
+    /// of zero.
+    ///
+    /// ```example
+    /// #(vec2(3pt, 4pt).normalize() * 10)
+    /// ```
+    #[func]
+    pub fn normalize(self) -> HintedStrResult<Vec2> {
+        let (x, y) = self.try_abs()?;
+        let length = Point::new(x, y).hypot();
+        if length.is_zero() {
+            bail!("cannot normalize a vector of length zero");
+        }
+        let scale = Abs::pt(1.0) / length;
+        Ok(Self::new((x * scale).into(), (y * scale).into()))
+    }
+
+    /// Resolves the `em` units of the vector's components to absolute lengths.
+    ///
+    /// ```example
+    /// #set text(size: 10pt)
+    /// #context vec2(1em, 2pt).to-absolute()
+    /// ```
+    #[func]
+    pub fn to_absolute(self, context: Tracked<Context>) -> HintedStrResult<Vec2> {
+        Ok(self.resolve(context.styles()?).into())
+    }
+
+    /// Converts the vector into an array of its two components.
+    ///
+    /// ```example
+    /// #vec2(1pt, 2pt).to-array()
+    /// ```
+    #[func]
+    pub fn to_array(self) -> Array {
+        array![self.x, self.y]
+    }
+}
+
+impl Debug for Vec2 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Vec2({:?}, {:?})", self.x, self.y)
+    }
+}
+
+impl Repr for Vec2 {
+    fn repr(&self) -> EcoString {
+        eco_format!("vec2({}, {})", self.x.repr(), self.y.repr())
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, other: f64) -> Self {
+        Self::new(self.x * other, self.y * other)
+    }
+}
+
+impl Div<f64> for Vec2 {
+    type Output = Self;
+
+    fn div(self, other: f64) -> Self {
+        Self::new(self.x / other, self.y / other)
+    }
+}
+
+impl Resolve for Vec2 {
+    type Output = Point;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        Point::new(self.x.resolve(styles), self.y.resolve(styles))
+    }
+}
+
+impl From<Point> for Vec2 {
+    fn from(point: Point) -> Self {
+        Self::new(point.x.into(), point.y.into())
+    }
+}
+
+impl From<Vec2> for Axes<Length> {
+    fn from(vec: Vec2) -> Self {
+        Axes::new(vec.x, vec.y)
+    }
+}
+
+impl From<Vec2> for Axes<Rel<Length>> {
+    fn from(vec: Vec2) -> Self {
+        Axes::new(vec.x.into(), vec.y.into())
+    }
+}
+
+cast! {
+    type Vec2,
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), None) => Self::new(a.cast()?, b.cast()?),
+            _ => bail!("vector array must contain exactly two entries"),
+        }
+    },
+}
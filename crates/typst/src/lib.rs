@@ -56,6 +56,7 @@ pub use typst_syntax as syntax;
 #[doc(inline)]
 pub use typst_utils as utils;
 
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::ops::{Deref, Range};
 
@@ -64,19 +65,23 @@ use ecow::{EcoString, EcoVec};
 use typst_timing::{timed, TimingScope};
 
 use crate::diag::{warning, FileResult, SourceDiagnostic, SourceResult};
-use crate::engine::{Engine, Route};
+use crate::engine::{Engine, LogEvent, Route};
 use crate::eval::Tracer;
+use crate::foundations::sys::Locale;
 use crate::foundations::{
     Array, Bytes, Content, Datetime, Dict, Module, Scope, StyleChain, Styles, Value,
 };
 use crate::introspection::{Introspector, Locator};
-use crate::layout::{Alignment, Dir};
-use crate::model::Document;
+use crate::layout::{
+    Abs, Alignment, Axes, Dir, Fragment, Frame, FrameItem, Limits, Page, Regions, Sides,
+    Size,
+};
+use crate::model::{Document, Structure};
 use crate::syntax::package::PackageSpec;
 use crate::syntax::{FileId, Source, Span};
-use crate::text::{Font, FontBook};
+use crate::text::{Font, FontBook, TextElem};
 use crate::utils::LazyHash;
-use crate::visualize::Color;
+use crate::visualize::{Color, ImageKind};
 
 /// Compile a source file into a fully layouted document.
 ///
@@ -88,17 +93,22 @@ use crate::visualize::Color;
 /// `tracer.warnings()` after compilation will return all compiler warnings.
 #[typst_macros::time(name = "compile")]
 pub fn compile(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
+    let result = compile_impl(world, tracer);
+    tracer.apply_suppressions(world);
+    result
+}
+
+/// Evaluate and typeset the main source file.
+fn compile_impl(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document> {
     // Call `track` on the world just once to keep comemo's ID stable.
     let world = world.track();
 
     // Try to evaluate the source file into a module.
-    let module = crate::eval::eval(
-        world,
-        Route::default().track(),
-        tracer.track_mut(),
-        &world.main(),
-    )
-    .map_err(deduplicate)?;
+    let main = world.main();
+    log_main(world, tracer, &main);
+    let module =
+        crate::eval::eval(world, Route::default().track(), tracer.track_mut(), &main)
+            .map_err(deduplicate)?;
 
     // Typeset the module's content, relayouting until convergence.
     typeset(world, tracer, &module.content()).map_err(deduplicate)
@@ -110,23 +120,68 @@ fn typeset(
     tracer: &mut Tracer,
     content: &Content,
 ) -> SourceResult<Document> {
+    let library = world.library();
+    let styles = StyleChain::new(&library.styles);
+
+    let (mut document, introspector) = converge(
+        world,
+        tracer,
+        |engine| content.layout_document(engine, styles),
+        |document| Cow::Borrowed(&document.pages),
+    )?;
+    document.introspector = introspector;
+
+    let mut fonts = vec![];
+    for page in &document.pages {
+        collect_fonts(&mut fonts, &page.frame);
+    }
+    for font in fonts {
+        tracer.log(LogEvent::FontLoaded(font));
+    }
+
+    library.limits.check(document.pages.iter().map(|page| &page.frame))?;
+    document.structure = timed!("build structure", {
+        // Diagnostics were already reported during layout, so the ones that
+        // come up again while realizing the structure are discarded.
+        let mut scratch = Tracer::new();
+        let mut locator = Locator::new();
+        let mut engine = Engine {
+            world,
+            route: Route::default(),
+            tracer: scratch.track_mut(),
+            locator: &mut locator,
+            introspector: document.introspector.track(),
+        };
+        Structure::build(&mut engine, content, styles, &document.pages)
+    });
+    Ok(document)
+}
+
+/// Lay out with `layout` until all introspections stabilize.
+///
+/// The pages that `pages` extracts from a layout are what the next iteration
+/// introspects. If layout doesn't converge within five attempts, we give up
+/// with a warning. Delayed errors of the final iteration are promoted to
+/// fatal ones. Returns the final layout and its introspector.
+fn converge<T>(
+    world: Tracked<dyn World + '_>,
+    tracer: &mut Tracer,
+    mut layout: impl FnMut(&mut Engine) -> SourceResult<T>,
+    pages: impl Fn(&T) -> Cow<'_, [Page]>,
+) -> SourceResult<(T, Introspector)> {
     // The name of the iterations for timing scopes.
     const ITER_NAMES: &[&str] =
         &["typeset (1)", "typeset (2)", "typeset (3)", "typeset (4)", "typeset (5)"];
 
-    let library = world.library();
-    let styles = StyleChain::new(&library.styles);
-
     let mut iter = 0;
-    let mut document = Document::default();
+    let mut introspector = Introspector::default();
 
-    // Relayout until all introspections stabilize.
-    // If that doesn't happen within five attempts, we give up.
-    loop {
+    let output = loop {
         let _scope = TimingScope::new(ITER_NAMES[iter], None);
 
         // Clear delayed errors.
         tracer.delayed();
+        tracer.log(LogEvent::LayoutPass(iter + 1));
 
         let constraint = <Introspector as Validate>::Constraint::new();
         let mut locator = Locator::new();
@@ -135,16 +190,16 @@ fn typeset(
             route: Route::default(),
             tracer: tracer.track_mut(),
             locator: &mut locator,
-            introspector: document.introspector.track_with(&constraint),
+            introspector: introspector.track_with(&constraint),
         };
 
         // Layout!
-        document = content.layout_document(&mut engine, styles)?;
-        document.introspector.rebuild(&document.pages);
+        let output = layout(&mut engine)?;
+        introspector.rebuild(&pages(&output));
         iter += 1;
 
-        if timed!("check stabilized", document.introspector.validate(&constraint)) {
-            break;
+        if timed!("check stabilized", introspector.validate(&constraint)) {
+            break output;
         }
 
         if iter >= 5 {
@@ -152,9 +207,9 @@ fn typeset(
                 Span::detached(), "layout did not converge within 5 attempts";
                 hint: "check if any states or queries are updating themselves"
             ));
-            break;
+            break output;
         }
-    }
+    };
 
     // Promote delayed errors.
     let delayed = tracer.delayed();
@@ -162,7 +217,182 @@ fn typeset(
         return Err(delayed);
     }
 
-    Ok(document)
+    Ok((output, introspector))
+}
+
+/// Collect the distinct fonts used in a frame, including those used by text
+/// in SVG images.
+fn collect_fonts(fonts: &mut Vec<Font>, frame: &Frame) {
+    for (_, item) in frame.items() {
+        let used = match item {
+            FrameItem::Group(group) => {
+                collect_fonts(fonts, &group.frame);
+                continue;
+            }
+            FrameItem::Text(text) => std::slice::from_ref(&text.font),
+            FrameItem::Image(image, ..) => match image.kind() {
+                ImageKind::Svg(svg) => svg.fonts(),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        for font in used {
+            if !fonts.contains(font) {
+                fonts.push(font.clone());
+            }
+        }
+    }
+}
+
+/// Logs that the main file was read.
+fn log_main(world: Tracked<dyn World + '_>, tracer: &mut Tracer, main: &Source) {
+    tracer.log(LogEvent::FileRead(main.id()));
+    if tracer.digesting() {
+        tracer.digest(main.id(), crate::engine::raw(world.file(main.id()), main));
+    }
+}
+
+/// Compile a source file into frames without a full document.
+///
+/// The source file's content is laid out into regions of the given size
+/// instead of into pages. Regions with an infinite height grow with the
+/// content. This is useful to render snippets like formula previews or hover
+/// popups. Page-level constructs like page breaks are not supported.
+///
+/// - Returns `Ok(fragment)` with one frame per region if there were no fatal
+///   errors.
+/// - Returns `Err(errors)` if there were fatal errors.
+///
+/// Like with [`compile`], warnings are reported through the tracer.
+#[typst_macros::time(name = "compile fragment")]
+pub fn compile_fragment(
+    source: &Source,
+    world: &dyn World,
+    size: Size,
+    tracer: &mut Tracer,
+) -> SourceResult<Fragment> {
+    let result = compile_fragment_impl(source, world, size, tracer);
+    tracer.apply_suppressions(world);
+    result
+}
+
+/// Evaluate a source file and lay it out into regions.
+fn compile_fragment_impl(
+    source: &Source,
+    world: &dyn World,
+    size: Size,
+    tracer: &mut Tracer,
+) -> SourceResult<Fragment> {
+    // Call `track` on the world just once to keep comemo's ID stable.
+    let world = world.track();
+
+    // Try to evaluate the source file into a module.
+    let module =
+        crate::eval::eval(world, Route::default().track(), tracer.track_mut(), source)
+            .map_err(deduplicate)?;
+
+    // Lay out the module's content, relayouting until convergence.
+    typeset_fragment(world, tracer, &module.content(), size).map_err(deduplicate)
+}
+
+/// Relayout a fragment until introspection converges.
+///
+/// Each region counts as one page for introspection.
+fn typeset_fragment(
+    world: Tracked<dyn World + '_>,
+    tracer: &mut Tracer,
+    content: &Content,
+    size: Size,
+) -> SourceResult<Fragment> {
+    let library = world.library();
+    let styles = StyleChain::new(&library.styles);
+    let regions = Regions::repeat(size, Axes::splat(false));
+
+    let (fragment, _) = converge(
+        world,
+        tracer,
+        |engine| content.layout(engine, styles, regions),
+        |fragment| {
+            let pages = fragment.iter().enumerate().map(|(i, frame)| Page {
+                frame: frame.clone(),
+                numbering: None,
+                number: i + 1,
+                section: None,
+                lang: TextElem::lang_in(styles),
+                bleed: Abs::zero(),
+                margin: Sides::default(),
+            });
+            Cow::Owned(pages.collect())
+        },
+    )?;
+
+    library.limits.check(&fragment)?;
+    Ok(fragment)
+}
+
+/// Evaluate the main source file without laying it out.
+///
+/// The module's content can then be typeset with different style bundles
+/// through [`compile_with_styles`] without evaluating the source again.
+///
+/// Like with [`compile`], warnings are reported through the tracer.
+#[typst_macros::time(name = "evaluate")]
+pub fn evaluate(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Module> {
+    let main = world.main();
+    let tracked = world.track();
+    log_main(tracked, tracer, &main);
+    let result =
+        crate::eval::eval(tracked, Route::default().track(), tracer.track_mut(), &main)
+            .map_err(deduplicate);
+    tracer.apply_suppressions(world);
+    result
+}
+
+/// Compile a style bundle into styles.
+///
+/// A style bundle is a source file that only consists of set and show rules
+/// (and imports or bindings they need). The resulting styles can be applied
+/// to already evaluated content with [`compile_with_styles`], which allows
+/// to quickly re-theme a document.
+///
+/// Fails if the bundle produces any content or uses a show rule without a
+/// selector, as the latter cannot be expressed as a style. Rules within
+/// blocks stay scoped to their block like in any other file, so they don't
+/// become part of the styles.
+#[typst_macros::time(name = "compile styles")]
+pub fn compile_styles(
+    source: &Source,
+    world: &dyn World,
+    tracer: &mut Tracer,
+) -> SourceResult<Styles> {
+    let result = crate::eval::eval_styles(
+        world.track(),
+        Route::default().track(),
+        tracer.track_mut(),
+        source,
+    )
+    .map_err(deduplicate);
+    tracer.apply_suppressions(world);
+    result
+}
+
+/// Typeset evaluated content with a style bundle applied.
+///
+/// The styles act as if they were applied by a template around the content,
+/// so set and show rules within the content take precedence over them.
+///
+/// Like with [`compile`], warnings are reported through the tracer.
+#[typst_macros::time(name = "compile with styles")]
+pub fn compile_with_styles(
+    world: &dyn World,
+    content: &Content,
+    styles: &Styles,
+    tracer: &mut Tracer,
+) -> SourceResult<Document> {
+    let content = content.clone().styled_with_map(styles.clone());
+    let result = typeset(world.track(), tracer, &content).map_err(deduplicate);
+    tracer.apply_suppressions(world);
+    result
 }
 
 /// Deduplicate diagnostics.
@@ -301,6 +531,13 @@ pub struct Library {
     /// The standard library as a value.
     /// Used to provide the `std` variable.
     pub std: Value,
+    /// Limits on the complexity of each laid out page.
+    pub limits: Limits,
+    /// Whether the whole document is untrusted. If so, it may not read files,
+    /// load plugins, or use absolute placement.
+    pub untrusted: bool,
+    /// The regional defaults of the environment.
+    pub locale: Locale,
 }
 
 impl Library {
@@ -323,6 +560,9 @@ impl Default for Library {
 #[derive(Debug, Clone, Default)]
 pub struct LibraryBuilder {
     inputs: Option<Dict>,
+    limits: Limits,
+    locale: Locale,
+    untrusted: bool,
 }
 
 impl LibraryBuilder {
@@ -332,20 +572,50 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure limits on the complexity of each page, for example to guard
+    /// against untrusted documents.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Configure the regional defaults of the environment, such as the
+    /// default language and paper size.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Mark the whole document as untrusted, for example when compiling
+    /// user-submitted fragments. This applies the same restrictions as the
+    /// `untrusted` function to everything, including evaluation.
+    pub fn with_untrusted(mut self, untrusted: bool) -> Self {
+        self.untrusted = untrusted;
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
         let inputs = self.inputs.unwrap_or_default();
-        let global = global(math.clone(), inputs);
+        let global = global(math.clone(), inputs, self.locale);
         let std = Value::Module(global.clone());
-        Library { global, math, styles: Styles::new(), std }
+        Library {
+            global,
+            math,
+            styles: self.locale.styles(),
+            std,
+            limits: self.limits,
+            untrusted: self.untrusted,
+            locale: self.locale,
+        }
     }
 }
 
 /// Construct the module with global definitions.
-fn global(math: Module, inputs: Dict) -> Module {
+fn global(math: Module, inputs: Dict, locale: Locale) -> Module {
     let mut global = Scope::deduplicating();
-    self::foundations::define(&mut global, inputs);
+    self::foundations::define(&mut global, inputs, locale);
     self::model::define(&mut global);
     self::text::define(&mut global);
     global.reset_category();
@@ -4,7 +4,6 @@ use crate::diag::{At, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{func, scope, Bytes, Value};
 use crate::syntax::Spanned;
-use crate::World;
 
 /// Reads structured data from a CBOR file.
 ///
@@ -26,7 +25,7 @@ pub fn cbor(
 ) -> SourceResult<Value> {
     let Spanned { v: path, span } = path;
     let id = span.resolve_path(&path).at(span)?;
-    let data = engine.world.file(id).at(span)?;
+    let data = engine.file(id).at(span)?;
     cbor::decode(Spanned::new(data, span))
 }
 
@@ -6,7 +6,6 @@ use crate::engine::Engine;
 use crate::foundations::{dict, func, scope, Array, Dict, IntoValue, Str, Value};
 use crate::loading::Readable;
 use crate::syntax::Spanned;
-use crate::World;
 
 /// Reads structured data from an XML file.
 ///
@@ -65,7 +64,7 @@ pub fn xml(
 ) -> SourceResult<Value> {
     let Spanned { v: path, span } = path;
     let id = span.resolve_path(&path).at(span)?;
-    let data = engine.world.file(id).at(span)?;
+    let data = engine.file(id).at(span)?;
     xml::decode(Spanned::new(Readable::Bytes(data), span))
 }
 
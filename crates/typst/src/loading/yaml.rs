@@ -5,7 +5,6 @@ use crate::engine::Engine;
 use crate::foundations::{func, scope, Str, Value};
 use crate::loading::Readable;
 use crate::syntax::Spanned;
-use crate::World;
 
 /// Reads structured data from a YAML file.
 ///
@@ -48,7 +47,7 @@ pub fn yaml(
 ) -> SourceResult<Value> {
     let Spanned { v: path, span } = path;
     let id = span.resolve_path(&path).at(span)?;
-    let data = engine.world.file(id).at(span)?;
+    let data = engine.file(id).at(span)?;
     yaml::decode(Spanned::new(Readable::Bytes(data), span))
 }
 
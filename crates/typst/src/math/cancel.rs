@@ -9,7 +9,7 @@ use crate::layout::{
 };
 use crate::math::{FrameFragment, LayoutMath, MathContext};
 use crate::syntax::Span;
-use crate::text::TextElem;
+use crate::text::deco_paint;
 use crate::visualize::{FixedStroke, Geometry, Stroke};
 
 /// Displays a diagonal line over a part of an equation.
@@ -122,10 +122,9 @@ impl LayoutMath for Packed<CancelElem> {
         let span = self.span();
         let length = self.length(styles).resolve(styles);
 
-        let stroke = self.stroke(styles).unwrap_or(FixedStroke {
-            paint: TextElem::fill_in(styles).as_decoration(),
-            ..Default::default()
-        });
+        let stroke = self
+            .stroke(styles)
+            .unwrap_or(FixedStroke { paint: deco_paint(styles), ..Default::default() });
 
         let invert = self.inverted(styles);
         let cross = self.cross(styles);
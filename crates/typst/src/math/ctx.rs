@@ -12,7 +12,7 @@ use unicode_segmentation::UnicodeSegmentation;
 use crate::diag::SourceResult;
 use crate::engine::Engine;
 use crate::foundations::{Content, Packed, StyleChain};
-use crate::layout::{Abs, Axes, BoxElem, Em, Frame, Regions, Size};
+use crate::layout::{Abs, Axes, BoxElem, Em, Frame, Ratio, Regions, Size, Transform};
 use crate::math::{
     scaled_font_size, styled_char, EquationElem, FrameFragment, GlyphFragment,
     LayoutMath, MathFragment, MathRun, MathSize, THICK,
@@ -190,6 +190,28 @@ impl<'a, 'b, 'v> MathContext<'a, 'b, 'v> {
             .into_frame())
     }
 
+    /// Layout embedded [`Content`] like images or drawings into a [`Frame`].
+    ///
+    /// The content is laid out at the surrounding text size and then scaled
+    /// down in scripts, so that it shrinks along with the formula around it.
+    pub fn layout_embedded(
+        &mut self,
+        content: &Content,
+        styles: StyleChain,
+    ) -> SourceResult<Frame> {
+        let factor = EquationElem::size_in(styles).factor(self);
+        let mut frame = content.layout(self.engine, styles, self.regions)?.into_frame();
+        if factor != 1.0 {
+            let baseline = frame.has_baseline().then(|| frame.baseline() * factor);
+            frame.transform(Transform::scale(Ratio::new(factor), Ratio::new(factor)));
+            frame.set_size(frame.size() * factor);
+            if let Some(baseline) = baseline {
+                frame.set_baseline(baseline);
+            }
+        }
+        Ok(frame)
+    }
+
     /// Layout the given [`TextElem`] into a [`MathFragment`].
     pub fn layout_text(
         &mut self,
@@ -290,7 +312,7 @@ impl<'a, 'b, 'v> MathContext<'a, 'b, 'v> {
         let par = ParElem::new(StyleVec::wrap(eco_vec![text]));
         let frame = Packed::new(par)
             .spanned(span)
-            .layout(self.engine, styles, false, Size::splat(Abs::inf()), false)?
+            .layout(self.engine, styles, false, Size::splat(Abs::inf()), false, None)?
             .into_frame();
 
         Ok(FrameFragment::new(self, styles, frame)
@@ -10,9 +10,9 @@ use crate::foundations::{
 };
 use crate::introspection::{Count, Counter, CounterUpdate, Locatable};
 use crate::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockElem, Em, FixedAlignment, Fragment, Frame,
-    InlineElem, InlineItem, OuterHAlignment, Point, Regions, Size, SpecificAlignment,
-    VAlignment,
+    relate_fills, Abs, AlignElem, Alignment, Axes, BlockElem, Em, FixedAlignment,
+    Fragment, Frame, InlineElem, InlineItem, OuterHAlignment, Point, Regions, Size,
+    SpecificAlignment, VAlignment,
 };
 use crate::math::{
     scaled_font_size, LayoutMath, MathContext, MathRunFrameBuilder, MathSize, MathVariant,
@@ -106,6 +106,26 @@ pub struct EquationElem {
     /// ```
     pub supplement: Smart<Option<Supplement>>,
 
+    /// The counter that numbers block-level equations.
+    ///
+    /// By default, equations have their own counter. When set to another
+    /// counter, for instance the one of a figure kind, numbered equations step
+    /// that counter instead and share a single numbering sequence with the
+    /// elements it counts.
+    ///
+    /// ```example
+    /// #set figure(numbering: "(1)")
+    /// #set math.equation(
+    ///   numbering: "(1)",
+    ///   counter: counter(figure.where(kind: image)),
+    /// )
+    ///
+    /// $ a^2 + b^2 = c^2 $
+    /// #figure(rect[Triangle], caption: [Sides])
+    /// $ c = sqrt(a^2 + b^2) $
+    /// ```
+    pub counter: Smart<Counter>,
+
     /// The contents of the equation.
     #[required]
     pub body: Content,
@@ -196,6 +216,10 @@ impl Count for Packed<EquationElem> {
         (self.block(StyleChain::default()) && self.numbering().is_some())
             .then(|| CounterUpdate::Step(NonZeroUsize::ONE))
     }
+
+    fn shared_counter(&self) -> Option<Counter> {
+        (**self).counter(StyleChain::default()).custom()
+    }
 }
 
 impl LocalName for Packed<EquationElem> {
@@ -212,7 +236,9 @@ impl Refable for Packed<EquationElem> {
     }
 
     fn counter(&self) -> Counter {
-        Counter::of(EquationElem::elem())
+        (**self)
+            .counter(StyleChain::default())
+            .unwrap_or_else(|| Counter::of(EquationElem::elem()))
     }
 
     fn numbering(&self) -> Option<&Numbering> {
@@ -384,15 +410,17 @@ fn layout_equation_block(
     };
 
     let Some(numbering) = (**elem).numbering(styles) else {
-        let frames = equation_builders
+        let mut frames: Vec<_> = equation_builders
             .into_iter()
             .map(MathRunFrameBuilder::build)
             .collect();
+        relate_fills(&mut frames, Abs::zero());
         return Ok(Fragment::frames(frames));
     };
 
     let pod = Regions::one(regions.base(), Axes::splat(false));
-    let number = Counter::of(EquationElem::elem())
+    let number = elem
+        .counter()
         .display_at_loc(engine, elem.location().unwrap(), styles, numbering)?
         .spanned(span)
         .layout(engine, styles, pod)?
@@ -408,7 +436,7 @@ fn layout_equation_block(
     };
 
     // Add equation numbers to each equation region.
-    let frames = equation_builders
+    let mut frames: Vec<_> = equation_builders
         .into_iter()
         .map(|builder| {
             add_equation_number(
@@ -422,10 +450,11 @@ fn layout_equation_block(
         })
         .collect();
 
+    relate_fills(&mut frames, Abs::zero());
     Ok(Fragment::frames(frames))
 }
 
-fn find_math_font(
+pub(crate) fn find_math_font(
     engine: &mut Engine<'_>,
     styles: StyleChain,
     span: Span,
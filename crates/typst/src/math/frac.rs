@@ -6,7 +6,7 @@ use crate::math::{
     GlyphFragment, LayoutMath, MathContext, Scaled, DELIM_SHORT_FALL,
 };
 use crate::syntax::{Span, Spanned};
-use crate::text::TextElem;
+use crate::text::{deco_paint, TextElem};
 use crate::visualize::{FixedStroke, Geometry};
 
 const FRAC_AROUND: Em = Em::new(0.1);
@@ -162,12 +162,8 @@ fn layout(
         frame.push(
             line_pos,
             FrameItem::Shape(
-                Geometry::Line(Point::with_x(line_width)).stroked(
-                    FixedStroke::from_pair(
-                        TextElem::fill_in(styles).as_decoration(),
-                        thickness,
-                    ),
-                ),
+                Geometry::Line(Point::with_x(line_width))
+                    .stroked(FixedStroke::from_pair(deco_paint(styles), thickness)),
                 span,
             ),
         );
@@ -5,9 +5,10 @@ use ttf_parser::gsub::AlternateSet;
 use ttf_parser::{GlyphId, Rect};
 use unicode_math_class::MathClass;
 
-use crate::foundations::StyleChain;
+use crate::foundations::{Smart, StyleChain};
 use crate::layout::{
-    Abs, Corner, Em, Frame, FrameItem, HideElem, Point, Size, VAlignment,
+    Abs, Corner, Em, ExportOnlyElem, ExportTarget, Frame, FrameItem, HideElem, Point,
+    Size, VAlignment,
 };
 use crate::math::{
     scaled_font_size, EquationElem, Limits, MathContext, MathSize, Scaled,
@@ -15,7 +16,7 @@ use crate::math::{
 use crate::model::{Destination, LinkElem};
 use crate::syntax::Span;
 use crate::text::{Font, Glyph, Lang, Region, TextElem, TextItem};
-use crate::visualize::Paint;
+use crate::visualize::{FixedStroke, Paint, RelativeTo};
 
 #[derive(Debug, Clone)]
 pub enum MathFragment {
@@ -209,7 +210,8 @@ pub struct GlyphFragment {
     pub font: Font,
     pub lang: Lang,
     pub region: Option<Region>,
-    pub fill: Paint,
+    pub fill: Option<Paint>,
+    pub stroke: Option<FixedStroke>,
     pub shift: Abs,
     pub width: Abs,
     pub ascent: Abs,
@@ -222,6 +224,7 @@ pub struct GlyphFragment {
     pub span: Span,
     pub dests: SmallVec<[Destination; 1]>,
     pub hidden: bool,
+    pub restriction: Option<Vec<ExportTarget>>,
     pub limits: Limits,
 }
 
@@ -264,7 +267,12 @@ impl GlyphFragment {
             font: ctx.font.clone(),
             lang: TextElem::lang_in(styles),
             region: TextElem::region_in(styles),
-            fill: TextElem::fill_in(styles).as_decoration(),
+            // Self-relative fills are resolved once the equation is laid out.
+            fill: TextElem::fill_in(styles).map(|fill| match fill.relative() {
+                Smart::Custom(RelativeTo::Self_) => fill,
+                _ => fill.as_decoration(),
+            }),
+            stroke: TextElem::stroke_in(styles).map(|stroke| stroke.unwrap_or_default()),
             shift: TextElem::baseline_in(styles),
             font_size: scaled_font_size(ctx, styles),
             math_size: EquationElem::size_in(styles),
@@ -278,6 +286,7 @@ impl GlyphFragment {
             span,
             dests: LinkElem::dests_in(styles),
             hidden: HideElem::hidden_in(styles),
+            restriction: ExportOnlyElem::restriction_in(styles),
         };
         fragment.set_id(ctx, id);
         fragment
@@ -348,7 +357,7 @@ impl GlyphFragment {
             lang: self.lang,
             region: self.region,
             text: self.c.into(),
-            stroke: None,
+            stroke: self.stroke,
             glyphs: vec![Glyph {
                 id: self.id.0,
                 x_advance: Em::from_length(self.width, self.font_size),
@@ -361,7 +370,7 @@ impl GlyphFragment {
         let mut frame = Frame::soft(size);
         frame.set_baseline(self.ascent);
         frame.push(Point::with_y(self.ascent + self.shift), FrameItem::Text(item));
-        frame.post_process_raw(self.dests, self.hidden);
+        frame.post_process_raw(self.dests, self.hidden, self.restriction);
         frame
     }
 
@@ -16,7 +16,7 @@ use crate::math::{
 };
 use crate::symbols::Symbol;
 use crate::syntax::{Span, Spanned};
-use crate::text::TextElem;
+use crate::text::deco_paint;
 use crate::utils::Numeric;
 use crate::visualize::{FixedStroke, Geometry, LineCap, Shape, Stroke};
 
@@ -469,7 +469,7 @@ fn layout_mat_body(
     let default_stroke_thickness = DEFAULT_STROKE_THICKNESS.at(font_size);
     let default_stroke = FixedStroke {
         thickness: default_stroke_thickness,
-        paint: TextElem::fill_in(styles).as_decoration(),
+        paint: deco_paint(styles),
         cap: LineCap::Square,
         ..Default::default()
     };
@@ -598,6 +598,7 @@ fn line_item(length: Abs, vertical: bool, stroke: FixedStroke, span: Span) -> Fr
             geometry: line_geom,
             fill: None,
             stroke: Some(stroke),
+            blur: Abs::zero(),
         },
         span,
     )
@@ -138,6 +138,17 @@ use crate::text::{LinebreakElem, SpaceElem, TextElem};
 ///   x &= 21 - y/3 & "divide by 3" $
 /// ```
 ///
+/// # Embedded content
+/// Equations can contain arbitrary content like images and drawings. Content
+/// without a baseline is vertically centered on the math axis, which makes it
+/// line up with operators like `+` and `=`. In sub- and superscripts, embedded
+/// content shrinks along with the surrounding formula.
+///
+/// ```example
+/// #let cell = rect(width: 8pt, height: 8pt)
+/// $ cell + cell = 2 cell^cell $
+/// ```
+///
 /// # Math fonts
 /// You can set the math font by with a [show-set rule]($styling/#show-rules) as
 /// demonstrated below. Note that only special OpenType math fonts are suitable
@@ -202,6 +213,7 @@ pub fn module() -> Module {
     math.define_func::<inline>();
     math.define_func::<script>();
     math.define_func::<sscript>();
+    math.define_func::<size>();
 
     // Text operators, spacings, and symbols.
     op::define(&mut math);
@@ -307,7 +319,7 @@ impl LayoutMath for Content {
             return elem.layout_math(ctx, styles);
         }
 
-        let mut frame = ctx.layout_content(self, styles)?;
+        let mut frame = ctx.layout_embedded(self, styles)?;
         if !frame.has_baseline() {
             let axis = scaled!(ctx, styles, axis_height);
             frame.set_baseline(frame.height() / 2.0 + axis);
@@ -6,7 +6,7 @@ use crate::math::{
     MathSize, Scaled,
 };
 use crate::syntax::Span;
-use crate::text::TextElem;
+use crate::text::deco_paint;
 use crate::visualize::{FixedStroke, Geometry};
 
 /// A square root.
@@ -133,12 +133,8 @@ fn layout(
     frame.push(
         line_pos,
         FrameItem::Shape(
-            Geometry::Line(Point::with_x(radicand.width())).stroked(
-                FixedStroke::from_pair(
-                    TextElem::fill_in(styles).as_decoration(),
-                    thickness,
-                ),
-            ),
+            Geometry::Line(Point::with_x(radicand.width()))
+                .stroked(FixedStroke::from_pair(deco_paint(styles), thickness)),
             span,
         ),
     );
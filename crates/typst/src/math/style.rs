@@ -1,6 +1,13 @@
-use crate::foundations::{func, Cast, Content, Smart, Style, StyleChain};
-use crate::layout::Abs;
-use crate::math::{EquationElem, MathContext};
+use comemo::Tracked;
+
+use crate::diag::{At, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    dict, func, Cast, Content, Context, Dict, Smart, Style, StyleChain,
+};
+use crate::layout::{Abs, Ratio};
+use crate::math::{find_math_font, EquationElem, MathContext};
+use crate::syntax::Span;
 use crate::text::TextElem;
 use crate::utils::LazyHash;
 
@@ -208,6 +215,58 @@ pub fn sscript(
         .styled(EquationElem::set_cramped(cramped))
 }
 
+/// The math size at the current position in an equation.
+///
+/// Returns a dictionary with two entries:
+///
+/// - `style`: The current size, as set by [`display`]($math.display),
+///   [`inline`]($math.inline), [`script`]($math.script), and
+///   [`sscript`]($math.sscript). One of `{"display"}`, `{"text"}`,
+///   `{"script"}`, or `{"script-script"}`. Sub- and superscripts
+///   automatically use the smaller sizes.
+/// - `scale`: By how much the current size is scaled down relative to the
+///   text size, as determined by the math font. This is `{100%}` for the
+///   display and text sizes.
+///
+/// With this, custom constructs built from layout primitives can scale
+/// themselves like native math elements do.
+///
+/// ```example
+/// #let bead = context {
+///   let size = 6pt * math.size().scale
+///   box(circle(radius: size / 2, fill: blue))
+/// }
+///
+/// $ x bead + x^bead + x^(x^bead) $
+/// ```
+#[func(contextual)]
+pub fn size(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite context.
+    context: Tracked<Context>,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Dict> {
+    let styles = context.styles().at(span)?;
+    let size = EquationElem::size_in(styles);
+    let scale = match size {
+        MathSize::Display | MathSize::Text => 1.0,
+        MathSize::Script | MathSize::ScriptScript => {
+            let font = find_math_font(engine, styles, span)?;
+            let constants =
+                font.ttf().tables().math.and_then(|math| math.constants).unwrap();
+            let percent = if size == MathSize::Script {
+                constants.script_percent_scale_down()
+            } else {
+                constants.script_script_percent_scale_down()
+            };
+            percent as f64 / 100.0
+        }
+    };
+    Ok(dict! { "style" => size, "scale" => Ratio::new(scale) })
+}
+
 /// The size of elements in an equation.
 ///
 /// See the TeXbook p. 141.
@@ -7,7 +7,7 @@ use crate::math::{
     Scaled,
 };
 use crate::syntax::Span;
-use crate::text::TextElem;
+use crate::text::deco_paint;
 use crate::visualize::{FixedStroke, Geometry};
 
 const BRACE_GAP: Em = Em::new(0.25);
@@ -106,7 +106,7 @@ fn layout_underoverline(
         line_pos,
         FrameItem::Shape(
             Geometry::Line(Point::with_x(width)).stroked(FixedStroke {
-                paint: TextElem::fill_in(styles).as_decoration(),
+                paint: deco_paint(styles),
                 thickness: bar_height,
                 ..FixedStroke::default()
             }),
@@ -305,7 +305,7 @@ impl Bibliography {
             .iter()
             .map(|path| {
                 let id = span.resolve_path(path).at(span)?;
-                engine.world.file(id).at(span)
+                engine.file(id).at(span)
             })
             .collect::<SourceResult<Vec<Bytes>>>()?;
 
@@ -445,7 +445,7 @@ impl CslStyle {
 
         if ext == "csl" {
             let id = span.resolve_path(string)?;
-            let data = engine.world.file(id)?;
+            let data = engine.file(id)?;
             CslStyle::from_data(&data)
         } else {
             CslStyle::from_name(string)
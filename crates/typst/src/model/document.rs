@@ -1,14 +1,23 @@
+use std::num::NonZeroUsize;
+
 use ecow::EcoString;
+use indexmap::IndexSet;
 
 use crate::diag::{bail, HintedStrResult, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, Args, Array, Construct, Content, Datetime, Packed, Smart, StyleChain,
-    Value,
+    cast, dict, elem, Args, Array, Construct, Content, Datetime, Dict, Packed, Smart,
+    StyleChain, Value,
 };
 use crate::introspection::{Introspector, ManualPageCounter};
-use crate::layout::{Page, PageElem};
+use crate::layout::{
+    ExportTarget, Frame, FrameItem, Page, PageElem, Point, Size, Transform,
+};
+use crate::model::Structure;
 use crate::realize::StyleVec;
+use crate::syntax::Span;
+use crate::text::Font;
+use crate::visualize::Geometry;
 
 /// The root element of a document and its metadata.
 ///
@@ -58,6 +67,25 @@ pub struct DocumentElem {
     #[ghost]
     pub date: Smart<Option<Datetime>>,
 
+    /// Additional values to export from the document, like a slug or a
+    /// publication status.
+    ///
+    /// These values are not embedded into the PDF. Instead, tools can
+    /// retrieve them from the compiled document together with the title,
+    /// authors, keywords, and date, for instance with
+    /// `typst query --front-matter`, which lists them under the `extra` key.
+    /// This way, static site generators can read a document's front matter
+    /// without querying metadata elements.
+    ///
+    /// ```example
+    /// #set document(
+    ///   title: [Hello],
+    ///   extra: (slug: "hello", draft: false),
+    /// )
+    /// ```
+    #[ghost]
+    pub extra: Dict,
+
     /// The page runs.
     #[internal]
     #[variadic]
@@ -98,10 +126,12 @@ impl Packed<DocumentElem> {
 
         Ok(Document {
             pages,
+            structure: Structure::default(),
             title: DocumentElem::title_in(styles).map(|content| content.plain_text()),
             author: DocumentElem::author_in(styles).0,
             keywords: DocumentElem::keywords_in(styles).0,
             date: DocumentElem::date_in(styles),
+            extra: DocumentElem::extra_in(styles),
             introspector: Introspector::default(),
         })
     }
@@ -134,6 +164,8 @@ cast! {
 pub struct Document {
     /// The document's finished pages.
     pub pages: Vec<Page>,
+    /// The document's semantic structure.
+    pub structure: Structure,
     /// The document's title.
     pub title: Option<EcoString>,
     /// The document's author.
@@ -142,10 +174,148 @@ pub struct Document {
     pub keywords: Vec<EcoString>,
     /// The document's creation date.
     pub date: Smart<Option<Datetime>>,
+    /// Additional values exported from the document.
+    pub extra: Dict,
     /// Provides the ability to execute queries on the document.
     pub introspector: Introspector,
 }
 
+impl Document {
+    /// The document's front matter: Its title, authors, keywords, and creation
+    /// date, along with its extra values under the `extra` key.
+    pub fn front_matter(&self) -> Dict {
+        dict! {
+            "title" => self.title.clone(),
+            "author" => self.author.clone(),
+            "keywords" => self.keywords.clone(),
+            "date" => self.date,
+            "extra" => self.extra.clone(),
+        }
+    }
+
+    /// All fonts used by the document's pages, in order of first use.
+    pub fn fonts(&self) -> Vec<Font> {
+        fn collect(frame: &Frame, fonts: &mut IndexSet<Font>) {
+            for (_, item) in frame.items() {
+                match item {
+                    FrameItem::Group(group) => collect(&group.frame, fonts),
+                    FrameItem::Text(text) => {
+                        fonts.insert(text.font.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut fonts = IndexSet::new();
+        for page in &self.pages {
+            collect(&page.frame, &mut fonts);
+        }
+        fonts.into_iter().collect()
+    }
+
+    /// Remove all content that is restricted to other export targets.
+    ///
+    /// See [`export-only`](crate::layout::ExportOnlyElem).
+    pub fn for_target(&self, target: ExportTarget) -> Document {
+        let mut document = self.clone();
+        for page in &mut document.pages {
+            page.frame = page.frame.for_target(target);
+        }
+        document.structure = self.structure.for_target(target);
+        document
+    }
+
+    /// Find the source span of the topmost item on a page that intersects an
+    /// area.
+    ///
+    /// The area is given by its top-left corner and its size in the page's
+    /// coordinates. Returns `None` if the page doesn't exist or if no item
+    /// that stems from source code intersects the area.
+    pub fn span_at(&self, page: NonZeroUsize, pos: Point, size: Size) -> Option<Span> {
+        let page = self.pages.get(page.get() - 1)?;
+        span_in(&page.frame, pos, pos + size.to_point())
+    }
+}
+
+/// Find the span of the topmost item in a frame that intersects the area
+/// between `min` and `max`.
+fn span_in(frame: &Frame, min: Point, max: Point) -> Option<Span> {
+    let intersects = |from: Point, to: Point| {
+        let (lo, hi) = (from.min(to), from.max(to));
+        lo.x <= max.x && min.x <= hi.x && lo.y <= max.y && min.y <= hi.y
+    };
+
+    for (pos, item) in frame.items().rev() {
+        let span = match item {
+            FrameItem::Group(group) => {
+                // Move the area into the group's coordinate system.
+                let Some(inverse) = Transform::translate(pos.x, pos.y)
+                    .pre_concat(group.transform)
+                    .invert()
+                else {
+                    continue;
+                };
+
+                let corners =
+                    [min, Point::new(max.x, min.y), Point::new(min.x, max.y), max]
+                        .map(|corner| corner.transform(inverse));
+                let lo = corners.into_iter().reduce(Point::min).unwrap();
+                let hi = corners.into_iter().reduce(Point::max).unwrap();
+                span_in(&group.frame, lo, hi)
+            }
+            FrameItem::Text(text) => {
+                let metrics = text.font.metrics();
+                let top = pos.y - metrics.ascender.at(text.size);
+                let bottom = pos.y - metrics.descender.at(text.size);
+
+                let mut x = pos.x;
+                text.glyphs.iter().find_map(|glyph| {
+                    let advance = glyph.x_advance.at(text.size);
+                    let hit =
+                        intersects(Point::new(x, top), Point::new(x + advance, bottom));
+                    x += advance;
+                    hit.then_some(glyph.span.0).filter(|span| !span.is_detached())
+                })
+            }
+            FrameItem::Shape(shape, span) => {
+                let (from, to) = match &shape.geometry {
+                    Geometry::Line(line) => (Point::zero(), *line),
+                    Geometry::Rect(size) => (Point::zero(), size.to_point()),
+                    Geometry::Path(path) => path.bbox().unwrap_or_default(),
+                };
+                intersects(*pos + from, *pos + to).then_some(*span)
+            }
+            FrameItem::Image(_, size, span) => {
+                intersects(*pos, *pos + size.to_point()).then_some(*span)
+            }
+            FrameItem::Link(..) | FrameItem::Tag(_) => None,
+        };
+
+        if let Some(span) = span.filter(|span| !span.is_detached()) {
+            return Some(span);
+        }
+    }
+
+    None
+}
+
+/// An output format that finished documents can be exported to.
+///
+/// This is the interface for implementing additional output formats outside
+/// of Typst. An exporter has access to everything about the finished
+/// [`Document`]: its pages and the fonts used in them, its metadata, and its
+/// introspector, which can look up the elements and their locations.
+///
+/// The PDF, SVG, raster, and DOCX exporters implement this trait.
+pub trait DocumentExporter {
+    /// The exported data, e.g. the bytes of a file.
+    type Output;
+
+    /// Export a document.
+    fn export(&self, document: &Document) -> SourceResult<Self::Output>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,284 @@
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use ecow::eco_vec;
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    cast, elem, scope, Content, Label, NativeElement, Packed, Selector, Show, ShowSet,
+    Smart, StyleChain, Styles,
+};
+use crate::introspection::{Count, CounterUpdate, Locatable};
+use crate::layout::{BlockElem, Em, Length, VElem};
+use crate::model::{
+    show_entry, show_note, FootnoteBody, HeadingElem, Note, Numbering, NumberingPattern,
+    ParbreakElem,
+};
+use crate::text::{LocalName, TextElem};
+use crate::utils::NonZeroExt;
+
+/// An endnote.
+///
+/// Works like a [footnote], but instead of placing the note at the bottom of
+/// the page, it is collected and listed wherever the [`endnotes`] function is
+/// called, typically at the end of a chapter or the document. The endnote
+/// itself is realized as a superscript number that links to its entry, and
+/// each entry links back to its place in the text.
+///
+/// Endnotes are numbered sequentially throughout your document. To restart
+/// the numbering for each chapter, reset the endnote [counter] in a heading
+/// show rule.
+///
+/// # Example
+/// ```example
+/// Typst is a typesetting system.
+/// #endnote[It was first released in 2023.]
+/// It compiles fast.
+/// #endnote[Incrementally, even.]
+///
+/// #endnotes()
+/// ```
+///
+/// Like footnotes, endnotes can be labelled to refer to them multiple times,
+/// either with a reference or by passing the label instead of content.
+///
+/// ```example
+/// Typst has a web app.
+/// #endnote[https://typst.app/app] <app>
+/// It runs in the browser. @app
+///
+/// #endnotes(title: none)
+/// ```
+#[elem(scope, Locatable, Show, Count)]
+pub struct EndnoteElem {
+    /// How to number endnotes.
+    ///
+    /// ```example
+    /// #set endnote(numbering: "i")
+    ///
+    /// Endnotes:
+    /// #endnote[One],
+    /// #endnote[Two]
+    ///
+    /// #endnotes()
+    /// ```
+    #[borrowed]
+    #[default(Numbering::Pattern(NumberingPattern::from_str("1").unwrap()))]
+    pub numbering: Numbering,
+
+    /// The content to put into the endnote. Can also be the label of another
+    /// endnote this one should point to.
+    #[required]
+    pub body: FootnoteBody,
+}
+
+#[scope]
+impl EndnoteElem {
+    #[elem]
+    type EndnoteEntry;
+}
+
+impl EndnoteElem {
+    /// Creates a new endnote that the passed content as its body.
+    pub fn with_content(content: Content) -> Self {
+        Self::new(FootnoteBody::Content(content))
+    }
+
+    /// Creates a new endnote referencing the endnote with the specified label.
+    pub fn with_label(label: Label) -> Self {
+        Self::new(FootnoteBody::Reference(label))
+    }
+
+    /// Tests if this endnote is a reference to another endnote.
+    pub fn is_ref(&self) -> bool {
+        matches!(self.body(), FootnoteBody::Reference(_))
+    }
+}
+
+impl Note for EndnoteElem {
+    const DESCRIPTION: &'static str = "an endnote";
+
+    fn note_body(&self) -> &FootnoteBody {
+        self.body()
+    }
+
+    fn note_numbering<'a>(&'a self, styles: StyleChain<'a>) -> &'a Numbering {
+        self.numbering(styles)
+    }
+}
+
+impl Show for Packed<EndnoteElem> {
+    #[typst_macros::time(name = "endnote", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        show_note(self, engine, styles)
+    }
+}
+
+impl Count for Packed<EndnoteElem> {
+    fn update(&self) -> Option<CounterUpdate> {
+        (!self.is_ref()).then(|| CounterUpdate::Step(NonZeroUsize::ONE))
+    }
+}
+
+cast! {
+    EndnoteElem,
+    v: Content => v.unpack::<Self>().unwrap_or_else(Self::with_content)
+}
+
+/// Lists the endnotes collected since the previous listing.
+///
+/// Each call lists all [endnotes]($endnote) between the previous call (or the
+/// start of the document) and itself. Call it at the end of each chapter to
+/// get per-chapter notes or once at the end of the document to collect all of
+/// them.
+///
+/// ```example
+/// = Introduction
+/// Notes are useful.#endnote[Usually.]
+/// #endnotes(title: [Notes])
+///
+/// = Conclusion
+/// Notes were useful.#endnote[Mostly.]
+/// #endnotes(title: [Notes])
+/// ```
+#[elem(title = "Endnote Listing", Locatable, Show, ShowSet)]
+pub struct EndnotesElem {
+    /// The title of the listing.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($text.lang) will be used. This is the default.
+    /// - When set to `{none}`, the listing will not have a title.
+    /// - A custom title can be set by passing content.
+    ///
+    /// The title is an unnumbered heading, but you can force it to be
+    /// numbered with a show-set rule:
+    /// `{show endnotes: set heading(numbering: "1.")}`
+    pub title: Smart<Option<Content>>,
+
+    /// The gap between endnote entries.
+    ///
+    /// ```example
+    /// #set endnotes(title: none, gap: 1em)
+    ///
+    /// Endnotes:
+    /// #endnote[Spaced],
+    /// #endnote[Apart]
+    ///
+    /// #endnotes()
+    /// ```
+    #[default(Em::new(0.5).into())]
+    pub gap: Length,
+}
+
+impl Show for Packed<EndnotesElem> {
+    #[typst_macros::time(name = "endnotes", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let span = self.span();
+        let Some(location) = self.location() else {
+            bail!(span, "endnote listing must have a location");
+        };
+
+        let mut seq = vec![ParbreakElem::new().pack()];
+        if let Some(title) = self.title(styles).unwrap_or_else(|| {
+            Some(TextElem::packed(Self::local_name_in(styles)).spanned(span))
+        }) {
+            seq.push(
+                HeadingElem::new(title)
+                    .with_level(Smart::Custom(NonZeroUsize::ONE))
+                    .pack()
+                    .spanned(span),
+            );
+        }
+
+        // Collect the notes between the previous listing and this one.
+        let selector = Selector::Or(eco_vec![
+            EndnoteElem::elem().select(),
+            EndnotesElem::elem().select()
+        ]);
+
+        let mut notes = vec![];
+        for elem in engine.introspector.query(&selector) {
+            if elem.location() == Some(location) {
+                break;
+            }
+
+            if elem.is::<EndnotesElem>() {
+                notes.clear();
+            } else if let Some(note) = elem.to_packed::<EndnoteElem>() {
+                if !note.is_ref() {
+                    notes.push(note.clone());
+                }
+            }
+        }
+
+        let mut entries = vec![];
+        for note in notes {
+            entries.push(EndnoteEntry::new(note).pack().spanned(span));
+            entries.push(ParbreakElem::new().pack());
+        }
+
+        let gap = VElem::block_spacing(self.gap(styles).into());
+        seq.push(
+            Content::sequence(entries)
+                .styled(BlockElem::set_above(gap.clone()))
+                .styled(BlockElem::set_below(gap)),
+        );
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+impl ShowSet for Packed<EndnotesElem> {
+    fn show_set(&self, _: StyleChain) -> Styles {
+        let mut out = Styles::new();
+        out.set(HeadingElem::set_numbering(None));
+        out
+    }
+}
+
+impl LocalName for Packed<EndnotesElem> {
+    const KEY: &'static str = "endnotes";
+}
+
+/// An entry in an endnote listing.
+///
+/// This function is not intended to be called directly. Instead, it is used
+/// in set and show rules to customize endnote listings.
+///
+/// ```example
+/// #show endnote.entry: set text(red)
+///
+/// My endnote listing
+/// #endnote[It's at the end]
+/// has red text!
+///
+/// #endnotes(title: none)
+/// ```
+#[elem(name = "entry", title = "Endnote Entry", Show)]
+pub struct EndnoteEntry {
+    /// The endnote for this entry. Its location can be used to determine
+    /// the endnote counter state.
+    #[required]
+    pub note: Packed<EndnoteElem>,
+
+    /// The indent of each endnote entry.
+    ///
+    /// ```example
+    /// #set endnote.entry(indent: 1em)
+    ///
+    /// Endnotes:
+    /// #endnote[Indented]
+    ///
+    /// #endnotes(title: none)
+    /// ```
+    #[default(Em::new(0.0).into())]
+    pub indent: Length,
+}
+
+impl Show for Packed<EndnoteEntry> {
+    #[typst_macros::time(name = "endnote.entry", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        show_entry(self.note(), engine, styles, self.span(), self.indent(styles))
+    }
+}
@@ -9,12 +9,14 @@ use crate::foundations::{
     cast, elem, scope, Array, Content, Context, NativeElement, Packed, Show, Smart,
     StyleChain, Styles,
 };
+use crate::introspection::TagElem;
 use crate::layout::{
     Alignment, Axes, BlockElem, Cell, CellGrid, Em, Fragment, GridLayouter, HAlignment,
     Length, Regions, Sizing, Spacing, VAlignment, VElem,
 };
 use crate::model::{Numbering, NumberingPattern, ParElem};
-use crate::text::TextElem;
+use crate::text::{Lang, TextElem};
+use crate::utils::hash128;
 
 /// A numbered list.
 ///
@@ -72,6 +74,19 @@ use crate::text::TextElem;
 /// Enumeration items can contain multiple paragraphs and other block-level
 /// content. All content that is indented more than an item's marker becomes
 /// part of that item.
+///
+/// # References
+/// Labelled [items]($enum.item) can be [referenced]($ref). A reference shows
+/// the item's full number, including the numbers of all parent items, no
+/// matter whether the enumeration displays its
+/// [full numbering](#parameters-full).
+///
+/// ```example
+/// #set enum(numbering: "1.")
+/// + Prepare
+///   #enum.item(2)[Heat water] <heat>
+/// + Serve after @heat is done.
+/// ```
 #[elem(scope, title = "Numbered List", Show)]
 pub struct EnumElem {
     /// If this is `{false}`, the items are spaced apart with
@@ -103,6 +118,10 @@ pub struct EnumElem {
     /// to nested enums. If given a function, the function receives one argument
     /// if `full` is `{false}` and multiple arguments if `full` is `{true}`.
     ///
+    /// The default pattern `{"1."}` counts with the digits that are native to
+    /// the [text language]($text.lang), for example `"١."` in Arabic and
+    /// `"१."` in Hindi.
+    ///
     /// ```example
     /// #set enum(numbering: "1.a)")
     /// + Different
@@ -235,7 +254,7 @@ fn layout_enum(
     styles: StyleChain,
     regions: Regions,
 ) -> SourceResult<Fragment> {
-    let numbering = elem.numbering(styles);
+    let numbering = resolve_numbering(elem.numbering(styles), styles);
     let indent = elem.indent(styles);
     let body_indent = elem.body_indent(styles);
     let gutter = if elem.tight(styles) {
@@ -267,10 +286,12 @@ fn layout_enum(
             parents.pop();
             content
         } else {
-            match numbering {
-                Numbering::Pattern(pattern) => {
-                    TextElem::packed(pattern.apply_kth(parents.len(), number))
-                }
+            match &numbering {
+                Numbering::Pattern(pattern) => TextElem::packed(pattern.apply_kth(
+                    parents.len(),
+                    number,
+                    TextElem::lang_in(styles),
+                )),
                 other => other.apply(engine, context.track(), &[number])?.display(),
             }
         };
@@ -280,12 +301,25 @@ fn layout_enum(
         let resolved =
             resolved.aligned(number_align).styled(TextElem::set_overhang(false));
 
+        // Make labelled items referenceable by remembering their full number.
+        let mut body = item.body().clone();
+        if item.label().is_some() {
+            let mut target = item.clone();
+            let mut numbers = parents.clone();
+            numbers.push(number);
+            target.push_numbers(numbers);
+            target.push_numbering(numbering.clone());
+            let mut target = target.pack();
+            if target.location().is_none() {
+                target.set_location(engine.locator.locate(hash128(&target)));
+            }
+            body = TagElem::packed(target) + body;
+        }
+
         cells.push(Cell::from(Content::empty()));
         cells.push(Cell::from(resolved));
         cells.push(Cell::from(Content::empty()));
-        cells.push(Cell::from(
-            item.body().clone().styled(EnumElem::set_parents(smallvec![number])),
-        ));
+        cells.push(Cell::from(body.styled(EnumElem::set_parents(smallvec![number]))));
         number = number.saturating_add(1);
     }
 
@@ -304,6 +338,19 @@ fn layout_enum(
     layouter.layout(engine)
 }
 
+/// Resolve the numbering of an enumeration. The default `"1."` pattern counts
+/// with the digits that are native to the text language.
+pub(crate) fn resolve_numbering(numbering: &Numbering, styles: StyleChain) -> Numbering {
+    match numbering {
+        Numbering::Pattern(pattern)
+            if *pattern == NumberingPattern::enumeration(Lang::ENGLISH) =>
+        {
+            NumberingPattern::enumeration(TextElem::lang_in(styles)).into()
+        }
+        other => other.clone(),
+    }
+}
+
 /// An enumeration item.
 #[elem(name = "item", title = "Numbered List Item")]
 pub struct EnumItem {
@@ -314,6 +361,16 @@ pub struct EnumItem {
     /// The item's body.
     #[required]
     pub body: Content,
+
+    /// The numbers of the item and all its parent items.
+    #[internal]
+    #[synthesized]
+    pub numbers: SmallVec<[usize; 4]>,
+
+    /// The numbering of the enumeration the item belongs to.
+    #[internal]
+    #[synthesized]
+    pub numbering: Numbering,
 }
 
 impl Packed<EnumItem> {
@@ -14,12 +14,13 @@ use crate::introspection::{
     Count, Counter, CounterKey, CounterUpdate, Locatable, Location,
 };
 use crate::layout::{
-    AlignElem, Alignment, BlockChild, BlockElem, Em, HAlignment, Length, OuterVAlignment,
-    PlaceElem, VAlignment, VElem,
+    AlignElem, Alignment, Angle, Binding, BlockChild, BlockElem, Dir, Em, HAlignment,
+    Length, OuterVAlignment, PageElem, PlaceElem, RotateElem, Spacing, StackChild,
+    StackElem, VAlignment, VElem,
 };
 use crate::model::{Numbering, NumberingPattern, Outlinable, Refable, Supplement};
 use crate::text::{Lang, Region, TextElem};
-use crate::utils::NonZeroExt;
+use crate::utils::{NonZeroExt, Numeric};
 use crate::visualize::ImageElem;
 
 /// A figure with an optional caption.
@@ -136,6 +137,55 @@ pub struct FigureElem {
     /// The figure's caption.
     pub caption: Option<Packed<FigureCaption>>,
 
+    /// Where to place the caption relative to the figure's body.
+    ///
+    /// - `{auto}`: The caption's [position]($figure.caption.position) is used.
+    /// - `{top}`, `{bottom}`: The caption is placed above or below the body.
+    /// - `{left}`, `{right}`: The caption is placed beside the body.
+    /// - `{"inside"}`, `{"outside"}`: The caption is placed beside the body,
+    ///   on the side of the page's [binding]($page.binding) or on the
+    ///   opposite side, respectively. This way, captions end up on the outer
+    ///   margin side on both verso and recto pages.
+    ///
+    /// ```example
+    /// #figure(
+    ///   rect(width: 60pt, height: 40pt),
+    ///   caption: [Beside],
+    ///   caption-pos: right,
+    /// )
+    /// ```
+    pub caption_pos: Smart<CaptionPos>,
+
+    /// How much to rotate the caption.
+    ///
+    /// The figure makes room for the rotated caption. Together with a
+    /// caption beside the body, this is useful for rotated full-page figures.
+    ///
+    /// ```example
+    /// #figure(
+    ///   rect(width: 60pt, height: 60pt),
+    ///   caption: [Rotated],
+    ///   caption-pos: left,
+    ///   caption-rotate: -90deg,
+    /// )
+    /// ```
+    pub caption_rotate: Angle,
+
+    /// A text describing the figure for assistive technology.
+    ///
+    /// In PDF export, the description is attached to the figure as a whole.
+    /// Images in the figure that have no [alternative text]($image.alt) of
+    /// their own are also described by it.
+    ///
+    /// ```example
+    /// #figure(
+    ///   image("molecular.jpg", width: 60%),
+    ///   alt: "A pipette dropping liquid into test tubes.",
+    ///   caption: [Molecular testing],
+    /// )
+    /// ```
+    pub alt: Option<EcoString>,
+
     /// The kind of figure this is.
     ///
     /// All figures of the same kind share a common counter.
@@ -171,8 +221,9 @@ pub struct FigureElem {
     ///
     /// If set to `{auto}`, the figure will try to automatically determine the
     /// correct supplement based on the `kind` and the active
-    /// [text language]($text.lang). If you are using a custom figure type, you
-    /// will need to manually specify the supplement.
+    /// [text language]($text.lang), taking custom
+    /// [translations]($text.translations) into account. If you are using a
+    /// custom figure type, you will need to manually specify the supplement.
     ///
     /// If a function is specified, it is passed the first descendant of the
     /// specified `kind` (typically, the figure's body) and should return
@@ -246,12 +297,9 @@ impl Synthesize for Packed<FigureElem> {
             Smart::Auto => {
                 // Default to the local name for the kind, if available.
                 let name = match &kind {
-                    FigureKind::Elem(func) => func
-                        .local_name(
-                            TextElem::lang_in(styles),
-                            TextElem::region_in(styles),
-                        )
-                        .map(TextElem::packed),
+                    FigureKind::Elem(func) => {
+                        func.local_name(styles).map(TextElem::packed)
+                    }
                     FigureKind::Name(_) => None,
                 };
 
@@ -304,21 +352,55 @@ impl Synthesize for Packed<FigureElem> {
 
 impl Show for Packed<FigureElem> {
     #[typst_macros::time(name = "figure", span = self.span())]
-    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
         let mut realized = self.body().clone();
 
+        // Describe images without an alternative text of their own.
+        let alt = self.alt(styles);
+        if let Some(alt) = &alt {
+            realized = realized.styled(ImageElem::set_alt(Some(alt.clone())));
+        }
+
         // Build the caption, if any.
         if let Some(caption) = self.caption(styles) {
-            let v = VElem::weak(self.gap(styles).into()).pack();
-            realized = match caption.position(styles) {
-                OuterVAlignment::Top => caption.pack() + v + realized,
-                OuterVAlignment::Bottom => realized + v + caption.pack(),
+            let gap = self.gap(styles);
+            let angle = self.caption_rotate(styles);
+            let pos = match self.caption_pos(styles) {
+                Smart::Auto => match caption.position(styles) {
+                    OuterVAlignment::Top => CaptionPos::Top,
+                    OuterVAlignment::Bottom => CaptionPos::Bottom,
+                },
+                Smart::Custom(pos) => pos,
+            };
+
+            let mut caption = caption.pack();
+            if !angle.is_zero() {
+                caption =
+                    RotateElem::new(caption).with_angle(angle).with_reflow(true).pack();
+            }
+
+            let beside = |first, second| {
+                StackElem::new(vec![
+                    StackChild::Block(first),
+                    StackChild::Spacing(Spacing::Rel(gap.into())),
+                    StackChild::Block(second),
+                ])
+                .with_dir(Dir::LTR)
+                .pack()
+            };
+
+            realized = match pos.resolve(engine, styles, self.location()) {
+                CaptionPos::Top => caption + VElem::weak(gap.into()).pack() + realized,
+                CaptionPos::Bottom => realized + VElem::weak(gap.into()).pack() + caption,
+                CaptionPos::Left => beside(caption, realized),
+                _ => beside(realized, caption),
             };
         }
 
         // Wrap the contents in a block.
         realized = BlockElem::new()
             .with_body(Some(BlockChild::Content(realized)))
+            .with_alt(alt)
             .pack()
             .spanned(self.span());
 
@@ -422,6 +504,79 @@ impl Outlinable for Packed<FigureElem> {
     }
 }
 
+/// Where to place the caption of a figure.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CaptionPos {
+    /// Above the body.
+    Top,
+    /// Below the body.
+    Bottom,
+    /// To the left of the body.
+    Left,
+    /// To the right of the body.
+    Right,
+    /// Beside the body, on the side of the page's binding.
+    Inside,
+    /// Beside the body, on the side opposite of the page's binding.
+    Outside,
+}
+
+impl CaptionPos {
+    /// Resolve inside and outside to left or right, depending on the page the
+    /// figure ends up on.
+    fn resolve(
+        self,
+        engine: &Engine,
+        styles: StyleChain,
+        location: Option<Location>,
+    ) -> Self {
+        if !matches!(self, Self::Inside | Self::Outside) {
+            return self;
+        }
+
+        let binding = PageElem::binding_in(styles).unwrap_or_else(|| {
+            match TextElem::dir_in(styles) {
+                Dir::LTR => Binding::Left,
+                _ => Binding::Right,
+            }
+        });
+
+        let swap =
+            location.is_some_and(|loc| binding.swap(engine.introspector.page(loc)));
+        let inside_left = (binding == Binding::Left) != swap;
+        if (self == Self::Inside) == inside_left {
+            Self::Left
+        } else {
+            Self::Right
+        }
+    }
+}
+
+cast! {
+    CaptionPos,
+    self => match self {
+        Self::Top => Alignment::TOP.into_value(),
+        Self::Bottom => Alignment::BOTTOM.into_value(),
+        Self::Left => Alignment::LEFT.into_value(),
+        Self::Right => Alignment::RIGHT.into_value(),
+        Self::Inside => "inside".into_value(),
+        Self::Outside => "outside".into_value(),
+    },
+    v: Alignment => match v {
+        Alignment::TOP => Self::Top,
+        Alignment::BOTTOM => Self::Bottom,
+        Alignment::LEFT => Self::Left,
+        Alignment::RIGHT => Self::Right,
+        _ => bail!(
+            "expected `top`, `bottom`, `left`, `right`, \"inside\", or \"outside\""
+        ),
+    },
+    /// Beside the body, on the side of the page's binding.
+    "inside" => Self::Inside,
+    /// Beside the body, on the side opposite of the page's binding.
+    "outside" => Self::Outside,
+}
+
 /// The caption of a figure. This element can be used in set and show rules to
 /// customize the appearance of captions for all figures or figures of a
 /// specific kind.
@@ -10,6 +10,7 @@ use crate::foundations::{
 use crate::introspection::{Count, Counter, CounterUpdate, Locatable, Location};
 use crate::layout::{Abs, Em, HElem, Length, Ratio};
 use crate::model::{Destination, Numbering, NumberingPattern, ParElem};
+use crate::syntax::Span;
 use crate::text::{SuperElem, TextElem, TextSize};
 use crate::utils::NonZeroExt;
 use crate::visualize::{LineElem, Stroke};
@@ -110,31 +111,14 @@ impl FootnoteElem {
 impl Packed<FootnoteElem> {
     /// Returns the location of the definition of this footnote.
     pub fn declaration_location(&self, engine: &Engine) -> StrResult<Location> {
-        match self.body() {
-            FootnoteBody::Reference(label) => {
-                let element = engine.introspector.query_label(*label)?;
-                let footnote = element
-                    .to_packed::<FootnoteElem>()
-                    .ok_or("referenced element should be a footnote")?;
-                footnote.declaration_location(engine)
-            }
-            _ => Ok(self.location().unwrap()),
-        }
+        declaration_location(self, engine)
     }
 }
 
 impl Show for Packed<FootnoteElem> {
     #[typst_macros::time(name = "footnote", span = self.span())]
     fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
-        let span = self.span();
-        let loc = self.declaration_location(engine).at(span)?;
-        let numbering = self.numbering(styles);
-        let counter = Counter::of(FootnoteElem::elem());
-        let num = counter.display_at_loc(engine, loc, styles, numbering)?;
-        let sup = SuperElem::new(num).pack().spanned(span);
-        let loc = loc.variant(1);
-        // Add zero-width weak spacing to make the footnote "sticky".
-        Ok(HElem::hole().pack() + sup.linked(Destination::Location(loc)))
+        show_note(self, engine, styles)
     }
 }
 
@@ -162,6 +146,105 @@ cast! {
     v: Label => Self::Reference(v),
 }
 
+/// A note that is numbered and listed in entries, like a footnote or an
+/// endnote.
+pub(crate) trait Note: NativeElement {
+    /// The name of the note in error messages, with its article.
+    const DESCRIPTION: &'static str;
+
+    /// The note's body.
+    fn note_body(&self) -> &FootnoteBody;
+
+    /// How the note is numbered.
+    fn note_numbering<'a>(&'a self, styles: StyleChain<'a>) -> &'a Numbering;
+}
+
+impl Note for FootnoteElem {
+    const DESCRIPTION: &'static str = "a footnote";
+
+    fn note_body(&self) -> &FootnoteBody {
+        self.body()
+    }
+
+    fn note_numbering<'a>(&'a self, styles: StyleChain<'a>) -> &'a Numbering {
+        self.numbering(styles)
+    }
+}
+
+/// Returns the location of the definition of a note, following references to
+/// other notes.
+pub(crate) fn declaration_location<T: Note>(
+    note: &Packed<T>,
+    engine: &Engine,
+) -> StrResult<Location> {
+    match note.note_body() {
+        FootnoteBody::Reference(label) => {
+            let element = engine.introspector.query_label(*label)?;
+            let Some(note) = element.to_packed::<T>() else {
+                bail!("referenced element should be {}", T::DESCRIPTION);
+            };
+            declaration_location(note, engine)
+        }
+        _ => Ok(note.location().unwrap()),
+    }
+}
+
+/// Shows a note as a superscript number that links to its entry.
+pub(crate) fn show_note<T: Note>(
+    note: &Packed<T>,
+    engine: &mut Engine,
+    styles: StyleChain,
+) -> SourceResult<Content> {
+    let span = note.span();
+    let loc = declaration_location(note, engine).at(span)?;
+    let numbering = note.note_numbering(styles);
+    let counter = Counter::of(T::elem());
+    let num = counter.display_at_loc(engine, loc, styles, numbering)?;
+    let sup = SuperElem::new(num).pack().spanned(span);
+    let loc = loc.variant(1);
+    // Add zero-width weak spacing to make the note "sticky".
+    Ok(HElem::hole().pack() + sup.linked(Destination::Location(loc)))
+}
+
+/// Shows the entry of a note: Its indented number, which links back to the
+/// note, followed by its body.
+pub(crate) fn show_entry<T: Note>(
+    note: &Packed<T>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    span: Span,
+    indent: Length,
+) -> SourceResult<Content> {
+    let number_gap = Em::new(0.05);
+    let default = StyleChain::default();
+    let numbering = note.note_numbering(default);
+    let counter = Counter::of(T::elem());
+    let Some(loc) = note.location() else {
+        bail!(
+            span, "{} entry must have a location", T::elem().name();
+            hint: "try using a query or a show rule to customize the {} instead",
+            T::elem().name()
+        );
+    };
+
+    let num = counter.display_at_loc(engine, loc, styles, numbering)?;
+    let sup = SuperElem::new(num)
+        .pack()
+        .spanned(span)
+        .linked(Destination::Location(loc))
+        .backlinked(loc.variant(1));
+    let body = match note.note_body() {
+        FootnoteBody::Content(content) => content.clone(),
+        FootnoteBody::Reference(_) => Content::empty(),
+    };
+    Ok(Content::sequence([
+        HElem::new(indent.into()).pack(),
+        sup,
+        HElem::new(number_gap.into()).with_weak(true).pack(),
+        body,
+    ]))
+}
+
 /// An entry in a footnote list.
 ///
 /// This function is not intended to be called directly. Instead, it is used
@@ -268,31 +351,7 @@ pub struct FootnoteEntry {
 impl Show for Packed<FootnoteEntry> {
     #[typst_macros::time(name = "footnote.entry", span = self.span())]
     fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
-        let span = self.span();
-        let note = self.note();
-        let number_gap = Em::new(0.05);
-        let default = StyleChain::default();
-        let numbering = note.numbering(default);
-        let counter = Counter::of(FootnoteElem::elem());
-        let Some(loc) = note.location() else {
-            bail!(
-                span, "footnote entry must have a location";
-                hint: "try using a query or a show rule to customize the footnote instead"
-            );
-        };
-
-        let num = counter.display_at_loc(engine, loc, styles, numbering)?;
-        let sup = SuperElem::new(num)
-            .pack()
-            .spanned(span)
-            .linked(Destination::Location(loc))
-            .backlinked(loc.variant(1));
-        Ok(Content::sequence([
-            HElem::new(self.indent(styles).into()).pack(),
-            sup,
-            HElem::new(number_gap.into()).with_weak(true).pack(),
-            note.body_content().unwrap().clone(),
-        ]))
+        show_entry(self.note(), engine, styles, self.span(), self.indent(styles))
     }
 }
 
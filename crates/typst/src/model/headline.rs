@@ -0,0 +1,342 @@
+use std::num::NonZeroUsize;
+
+use comemo::Tracked;
+
+use crate::diag::{bail, warning, At, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    dict, elem, func, scope, Cast, Content, Context, Dict, IntoValue, NativeElement,
+    Packed, Resolve, Show, Smart, StyleChain, Styles, Value,
+};
+use crate::layout::{
+    Abs, Axes, BlockElem, Em, Frame, Length, Ratio, Region, Regions, Size,
+};
+use crate::model::ParElem;
+use crate::syntax::Span;
+use crate::text::{Hyphenate, TextElem, TextSize};
+
+/// How often the amount of a gradual strategy is halved in the search for
+/// the least amount that fits.
+const BISECTIONS: usize = 7;
+
+/// A headline that is fitted into a maximum number of lines.
+///
+/// If the body doesn't fit into `max-lines` lines, the headline tries the
+/// given strategies one after another: First, it enables hyphenation, then it
+/// reduces the tracking, and finally it scales the text down. Each strategy
+/// is applied only as far as necessary and keeps what the previous ones did.
+/// If that is not enough, the [alternative wordings]($headline.alternatives)
+/// are tried in the same way.
+///
+/// To find out which strategy a headline needed, use
+/// [`headline.fit`]($headline.fit).
+///
+/// # Example
+/// ```example
+/// #set page(width: 120pt)
+/// #headline(max-lines: 1)[
+///   *Typesetting Made Simple*
+/// ]
+/// ```
+#[elem(scope, Show)]
+pub struct HeadlineElem {
+    /// The maximum number of lines the headline may take.
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub max_lines: NonZeroUsize,
+
+    /// The strategies to fit the headline with, in the order in which they
+    /// are tried.
+    ///
+    /// ```example
+    /// #set page(width: 120pt)
+    /// #headline(max-lines: 1, strategies: ("scale",))[
+    ///   *Typesetting Made Simple*
+    /// ]
+    /// ```
+    #[default(vec![
+        HeadlineStrategy::Hyphenate,
+        HeadlineStrategy::Tracking,
+        HeadlineStrategy::Scale,
+    ])]
+    pub strategies: Vec<HeadlineStrategy>,
+
+    /// How far the tracking may be reduced, as a negative length.
+    #[resolve]
+    #[default(Em::new(-0.05).into())]
+    pub min_tracking: Length,
+
+    /// How far the text may be scaled down.
+    #[default(Ratio::new(0.7))]
+    pub min_scale: Ratio,
+
+    /// Alternative wordings that are tried when the strategies cannot make
+    /// the body fit.
+    ///
+    /// ```example
+    /// #set page(width: 120pt)
+    /// #headline(
+    ///   max-lines: 1,
+    ///   alternatives: ([*Simple Typesetting*],),
+    /// )[*Typesetting Made Remarkably Simple*]
+    /// ```
+    pub alternatives: Vec<Content>,
+
+    /// The headline's text.
+    #[required]
+    pub body: Content,
+}
+
+#[scope]
+impl HeadlineElem {
+    /// Determines how a headline is fitted into its lines.
+    ///
+    /// Returns a dictionary with the following keys:
+    /// - `strategy`: The last strategy that was needed, or `{none}` if the
+    ///   headline fits as is.
+    /// - `alternative`: The index of the alternative wording that was used,
+    ///   or `{none}` if the body was used.
+    /// - `hyphenate`: Whether hyphenation was enabled.
+    /// - `tracking`: By how much the tracking was reduced.
+    /// - `scale`: By how much the text was scaled.
+    /// - `lines`: The number of lines the fitted headline takes.
+    /// - `fits`: Whether the headline fits into its maximum number of lines.
+    ///
+    /// ```example
+    /// #context headline.fit(
+    ///   headline(max-lines: 1)[*Typesetting Made Simple*],
+    ///   100pt,
+    /// )
+    /// ```
+    #[func(contextual)]
+    pub fn fit(
+        /// The engine.
+        engine: &mut Engine,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The callsite span.
+        span: Span,
+        /// The headline to fit.
+        headline: Content,
+        /// The width available to the headline.
+        width: Length,
+    ) -> SourceResult<Dict> {
+        let styles = context.styles().at(span)?;
+        let Some(headline) = headline.to_packed::<HeadlineElem>() else {
+            bail!(span, "expected headline, found {}", headline.elem().name());
+        };
+
+        let width = width.resolve(styles);
+        let fitted = fit(engine, headline, styles, width)?;
+        Ok(dict! {
+            "strategy" => fitted.strategy.map_or(Value::None, |s| s.into_value()),
+            "alternative" => fitted.alternative.map_or(Value::None, |i| i.into_value()),
+            "hyphenate" => fitted.hyphenate,
+            "tracking" => fitted.tracking,
+            "scale" => fitted.scale,
+            "lines" => fitted.lines,
+            "fits" => fitted.fits,
+        })
+    }
+}
+
+impl Show for Packed<HeadlineElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(BlockElem::single_layouter(self.clone(), layout_headline).pack())
+    }
+}
+
+/// Layout the headline, fitting it into its lines.
+#[typst_macros::time(span = elem.span())]
+fn layout_headline(
+    elem: &Packed<HeadlineElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let fitted = fit(engine, elem, styles, region.size.x)?;
+    if !fitted.fits {
+        engine.tracer.warn(warning!(
+            elem.span(),
+            "headline does not fit into {} {}",
+            elem.max_lines(styles),
+            if elem.max_lines(styles).get() == 1 { "line" } else { "lines" };
+            hint: "try allowing more lines or a smaller minimum scale"
+        ));
+    }
+
+    let styles = styles.chain(&fitted.styles);
+    Ok(fitted
+        .body
+        .layout(engine, styles, region.into_regions())?
+        .into_frame())
+}
+
+/// A strategy to fit a headline into its lines.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum HeadlineStrategy {
+    /// Enables hyphenation.
+    Hyphenate,
+    /// Reduces the tracking down to the minimum tracking.
+    Tracking,
+    /// Scales the text down to the minimum scale.
+    Scale,
+}
+
+/// A headline candidate with the styles that fit it.
+struct Fitted {
+    /// The body or alternative wording that was used.
+    body: Content,
+    /// The styles that fit the body.
+    styles: Styles,
+    /// The last strategy that was needed.
+    strategy: Option<HeadlineStrategy>,
+    /// The index of the alternative wording, if any.
+    alternative: Option<usize>,
+    /// Whether hyphenation was enabled.
+    hyphenate: bool,
+    /// By how much the tracking was reduced.
+    tracking: Abs,
+    /// By how much the text was scaled.
+    scale: Ratio,
+    /// The number of lines the fitted headline takes.
+    lines: usize,
+    /// Whether the headline fits.
+    fits: bool,
+}
+
+/// Try the strategies on the body and each alternative until one fits.
+///
+/// If nothing fits, the last attempt is returned.
+fn fit(
+    engine: &mut Engine,
+    elem: &Packed<HeadlineElem>,
+    styles: StyleChain,
+    width: Abs,
+) -> SourceResult<Fitted> {
+    let max_lines = elem.max_lines(styles).get();
+    let strategies = elem.strategies(styles);
+    let min_tracking = elem.min_tracking(styles);
+    let min_scale = elem.min_scale(styles);
+    let tracking = TextElem::tracking_in(styles);
+    let size = TextElem::size_in(styles);
+
+    let candidates = std::iter::once(elem.body().clone())
+        .chain(elem.alternatives(styles))
+        .enumerate();
+
+    // Restyles an attempt and counts the lines it takes.
+    let update = |engine: &mut Engine, attempt: &mut Fitted| {
+        attempt.styles = Styles::new();
+        if attempt.hyphenate {
+            attempt
+                .styles
+                .set(TextElem::set_hyphenate(Hyphenate(Smart::Custom(true))));
+        }
+        attempt
+            .styles
+            .set(TextElem::set_tracking((tracking + attempt.tracking).into()));
+        attempt
+            .styles
+            .set(TextElem::set_size(TextSize(attempt.scale.of(size).into())));
+        attempt.lines = count_lines(engine, styles, attempt, width)?;
+        attempt.fits = attempt.lines <= max_lines;
+        SourceResult::Ok(())
+    };
+
+    let mut fitted = None;
+    for (i, body) in candidates {
+        let mut attempt = Fitted {
+            body,
+            styles: Styles::new(),
+            strategy: None,
+            alternative: i.checked_sub(1),
+            hyphenate: false,
+            tracking: Abs::zero(),
+            scale: Ratio::one(),
+            lines: 0,
+            fits: false,
+        };
+
+        update(engine, &mut attempt)?;
+        for &strategy in &strategies {
+            if attempt.fits {
+                break;
+            }
+
+            attempt.strategy = Some(strategy);
+            if strategy == HeadlineStrategy::Hyphenate {
+                attempt.hyphenate = true;
+                update(engine, &mut attempt)?;
+                continue;
+            }
+
+            let apply = |attempt: &mut Fitted, t: f64| match strategy {
+                HeadlineStrategy::Tracking => attempt.tracking = min_tracking * t,
+                HeadlineStrategy::Scale => {
+                    attempt.scale = Ratio::one() + (min_scale - Ratio::one()) * t;
+                }
+                HeadlineStrategy::Hyphenate => unreachable!(),
+            };
+
+            // Apply the strategy fully. If that is not enough, keep it and
+            // move on to the next one. Otherwise, search for the least amount
+            // that still fits.
+            apply(&mut attempt, 1.0);
+            update(engine, &mut attempt)?;
+            if !attempt.fits {
+                continue;
+            }
+
+            let (mut lo, mut hi) = (0.0, 1.0);
+            for _ in 0..BISECTIONS {
+                let mid = (lo + hi) / 2.0;
+                apply(&mut attempt, mid);
+                update(engine, &mut attempt)?;
+                if attempt.fits {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+
+            if !attempt.fits {
+                apply(&mut attempt, hi);
+                update(engine, &mut attempt)?;
+            }
+        }
+
+        let fits = attempt.fits;
+        fitted = Some(attempt);
+        if fits {
+            break;
+        }
+    }
+
+    Ok(fitted.unwrap())
+}
+
+/// Count the lines a headline candidate takes at the given width.
+///
+/// The lines are derived from the candidate's height compared to the height
+/// of a single line, which is measured by laying it out with infinite width.
+fn count_lines(
+    engine: &mut Engine,
+    styles: StyleChain,
+    attempt: &Fitted,
+    width: Abs,
+) -> SourceResult<usize> {
+    let styles = styles.chain(&attempt.styles);
+    let mut measure = |width: Abs| -> SourceResult<Abs> {
+        let pod = Regions::one(Size::new(width, Abs::inf()), Axes::splat(false));
+        Ok(attempt.body.measure(engine, styles, pod)?.into_frame().height())
+    };
+
+    let line = measure(Abs::inf())?;
+    let height = measure(width)?;
+    if line <= Abs::zero() {
+        return Ok(1);
+    }
+
+    let leading = ParElem::leading_in(styles);
+    Ok(((height + leading) / (line + leading)).round().max(1.0) as usize)
+}
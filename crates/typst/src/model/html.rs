@@ -0,0 +1,149 @@
+//! Embedding of HTML snippets.
+
+use ecow::EcoString;
+
+use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::engine::Engine;
+use crate::foundations::{elem, Content, Module, Packed, Scope, Show, StyleChain};
+use crate::introspection::Locatable;
+use crate::syntax::Spanned;
+
+/// A module with definitions for HTML output.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_elem::<HtmlElem>();
+    Module::new("html", scope)
+}
+
+/// A snippet of HTML that is passed through verbatim to HTML output.
+///
+/// This gives access to features of the web platform that have no Typst
+/// equivalent yet. The PDF, PNG, and SVG exporters ignore the snippet, as
+/// their formats can't display HTML, and show the
+/// [`placeholder`]($html.html.placeholder) instead.
+///
+/// Unlike [`raw`], which displays code, this element inserts the snippet into
+/// the output as markup.
+///
+/// To make sure that a snippet can't break the markup around it, its tags
+/// must be balanced: Every opened tag must be closed in the same snippet,
+/// except for void elements like `<br>` and self-closing tags like
+/// `<path/>`.
+///
+/// # Example
+/// ```example
+/// #html.html(
+///   "<video src=\"intro.mp4\" controls></video>",
+///   placeholder: [_The video is available online._],
+/// )
+/// ```
+#[elem(name = "html", title = "HTML Snippet", Locatable, Show)]
+pub struct HtmlElem {
+    /// The HTML snippet.
+    #[required]
+    #[parse(
+        let Spanned { v: html, span } = args.expect::<Spanned<EcoString>>("html")?;
+        check(&html).at(span)?;
+        html
+    )]
+    pub html: EcoString,
+
+    /// Content to show instead of the snippet in formats that can't display
+    /// HTML. By default, nothing is shown.
+    pub placeholder: Option<Content>,
+}
+
+impl Show for Packed<HtmlElem> {
+    #[typst_macros::time(name = "html.html", span = self.span())]
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        Ok(self.placeholder(styles).unwrap_or_default())
+    }
+}
+
+/// Elements that never have a closing tag.
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is raw text that may contain `<`.
+const RAW_TEXT: &[&str] = &["script", "style"];
+
+/// Check that the tags of an HTML snippet are balanced.
+fn check(html: &str) -> StrResult<()> {
+    let mut open: Vec<&str> = vec![];
+    let mut rest = html;
+
+    while let Some(i) = rest.find('<') {
+        rest = &rest[i + 1..];
+
+        if let Some(after) = rest.strip_prefix("!--") {
+            let Some(end) = after.find("-->") else { bail!("unclosed HTML comment") };
+            rest = &after[end + 3..];
+            continue;
+        }
+
+        // A `<` that doesn't start a tag is just text.
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || "/!?".contains(c)) {
+            continue;
+        }
+
+        let Some(end) = tag_end(rest) else { bail!("unclosed HTML tag") };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with(['!', '?']) {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            match open.pop() {
+                Some(prev) if prev.eq_ignore_ascii_case(name) => {}
+                Some(prev) => bail!("expected closing tag for `{prev}`, found `{name}`"),
+                None => bail!("unexpected closing tag `{name}`"),
+            }
+            continue;
+        }
+
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        let lower = name.to_ascii_lowercase();
+        if tag.ends_with('/') || VOID.contains(&lower.as_str()) {
+            continue;
+        }
+
+        // Skip over the content of raw text elements.
+        if RAW_TEXT.contains(&lower.as_str()) {
+            let closing = format!("</{lower}");
+            let Some(end) = rest.to_ascii_lowercase().find(&closing) else {
+                bail!("unclosed tag `{name}`");
+            };
+            rest = &rest[end..];
+        }
+
+        open.push(name);
+    }
+
+    if let Some(name) = open.pop() {
+        bail!("unclosed tag `{name}`");
+    }
+
+    Ok(())
+}
+
+/// Find the `>` that ends a tag, skipping over quoted attribute values.
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, scope, Content, NativeElement, Packed, Show, ShowSet, Smart, StyleChain, Styles,
+};
+use crate::introspection::{Locatable, Location};
+use crate::layout::{Em, HElem, Length};
+use crate::model::{page_ranges, EmphElem, HeadingElem, ParElem, ParbreakElem};
+use crate::text::{localized_str_in, Lang, LocalName, TextElem};
+use crate::utils::NonZeroExt;
+
+/// A back-of-book index.
+///
+/// Collects all [index entries]($index.entry) in the document and lists them
+/// alphabetically, together with the pages they occur on. Entries with the
+/// same term are merged, subentries are listed below their main entry, and
+/// consecutive pages are folded into ranges.
+///
+/// Terms are sorted case- and accent-insensitively according to the
+/// alphabet of the [text language]($text.lang). In English, "émigré" sorts
+/// next to "emigrant", while in Swedish, "ö" sorts after "z". If a term
+/// should sort differently, for instance a person's name or a symbol, pass an
+/// explicit `key` to its entries.
+///
+/// # Example
+/// ```example
+/// #set page(height: 100pt)
+///
+/// Typst#index.entry("Typst") is a
+/// typesetting system#index.entry("typesetting").
+/// #pagebreak()
+/// Its markup#index.entry("Typst", sub: "markup")
+/// is lightweight.
+/// #index.entry("layout", see: "typesetting")
+///
+/// #index(title: none)
+/// ```
+#[elem(scope, Show, ShowSet, LocalName)]
+pub struct IndexElem {
+    /// The title of the index.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($text.lang) will be used. This is the default.
+    /// - When set to `{none}`, the index will not have a title.
+    /// - A custom title can be set by passing content.
+    ///
+    /// The index's heading will not be numbered by default, but you can
+    /// force it to be with a show-set rule:
+    /// `{show index: set heading(numbering: "1.")}`
+    pub title: Smart<Option<Content>>,
+
+    /// The indent of subentries.
+    #[default(Em::new(1.0).into())]
+    pub indent: Length,
+}
+
+#[scope]
+impl IndexElem {
+    #[elem]
+    type IndexEntry;
+}
+
+impl Show for Packed<IndexElem> {
+    #[typst_macros::time(name = "index", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let span = self.span();
+        let mut seq = vec![ParbreakElem::new().pack()];
+        if let Some(title) = self.title(styles).unwrap_or_else(|| {
+            Some(TextElem::packed(Self::local_name_in(styles)).spanned(span))
+        }) {
+            seq.push(
+                HeadingElem::new(title)
+                    .with_level(Smart::Custom(NonZeroUsize::ONE))
+                    .pack()
+                    .spanned(span),
+            );
+        }
+
+        // Merge the entries by term and subentry.
+        let mut terms = BTreeMap::<EcoString, Group>::new();
+        for elem in engine.introspector.query(&IndexEntry::elem().select()) {
+            let entry = elem.to_packed::<IndexEntry>().unwrap();
+            let mut group = terms.entry(entry.term().clone()).or_default();
+            if let Some(sub) = entry.sub(StyleChain::default()) {
+                group = group.subs.entry(sub.clone()).or_default();
+            }
+
+            if let Some(key) = entry.key(StyleChain::default()) {
+                group.key = Some(key.clone());
+            }
+
+            if let Some(see) = entry.see(StyleChain::default()) {
+                if !group.see.contains(see) {
+                    group.see.push(see.clone());
+                }
+            } else {
+                group.locations.push(elem.location().unwrap());
+            }
+
+            for also in entry.see_also(StyleChain::default()) {
+                if !group.see_also.contains(also) {
+                    group.see_also.push(also.clone());
+                }
+            }
+        }
+
+        let indent = self.indent(styles);
+        let lang = TextElem::lang_in(styles);
+        for (term, group) in sorted(terms, lang) {
+            seq.push(group.display(engine, styles, term)?);
+            seq.push(ParbreakElem::new().pack());
+            for (sub, group) in sorted(group.subs, lang) {
+                seq.push(HElem::new(indent.into()).pack());
+                seq.push(group.display(engine, styles, sub)?);
+                seq.push(ParbreakElem::new().pack());
+            }
+        }
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+impl ShowSet for Packed<IndexElem> {
+    fn show_set(&self, _: StyleChain) -> Styles {
+        let mut out = Styles::new();
+        out.set(HeadingElem::set_numbering(None));
+        out.set(ParElem::set_justify(false));
+        out
+    }
+}
+
+impl LocalName for Packed<IndexElem> {
+    const KEY: &'static str = "index";
+}
+
+/// Marks a term for the index.
+///
+/// The entry itself is invisible. It records the page it occurs on, which is
+/// then listed by the [`index`] function.
+///
+/// ```example
+/// #set page(height: 100pt)
+///
+/// Kerning#index.entry("kerning")
+/// adjusts the space between glyphs.
+/// #index.entry("letter spacing", see: "kerning")
+/// #index.entry("kerning", see-also: ("tracking",))
+///
+/// #index(title: none)
+/// ```
+#[elem(name = "entry", title = "Index Entry", Locatable, Show)]
+pub struct IndexEntry {
+    /// The indexed term.
+    #[required]
+    pub term: EcoString,
+
+    /// A subentry, listed below the term.
+    #[borrowed]
+    pub sub: Option<EcoString>,
+
+    /// The key to sort the term by. Defaults to the term itself.
+    ///
+    /// If the entry has a subentry, the key sorts the subentry.
+    #[borrowed]
+    pub key: Option<EcoString>,
+
+    /// Another term to refer to instead of listing a page.
+    #[borrowed]
+    pub see: Option<EcoString>,
+
+    /// Related terms to refer to in addition to the pages.
+    #[borrowed]
+    pub see_also: Vec<EcoString>,
+}
+
+impl Show for Packed<IndexEntry> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// Merged index entries for a single term or subentry.
+#[derive(Debug, Default)]
+struct Group {
+    key: Option<EcoString>,
+    locations: Vec<Location>,
+    see: Vec<EcoString>,
+    see_also: Vec<EcoString>,
+    subs: BTreeMap<EcoString, Group>,
+}
+
+impl Group {
+    /// Display the term with its page ranges and cross-references.
+    fn display(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        term: EcoString,
+    ) -> SourceResult<Content> {
+        let mut seq = vec![TextElem::packed(term)];
+        let (ranges, _) = page_ranges(engine, styles, &self.locations)?;
+        for range in ranges {
+            seq.push(TextElem::packed(", "));
+            seq.push(range);
+        }
+
+        let refer = |seq: &mut Vec<Content>, key: &str, terms: &[EcoString]| {
+            let terms: Vec<_> = terms.iter().map(|term| term.as_str()).collect();
+            seq.push(
+                EmphElem::new(TextElem::packed(localized_str_in(styles, key))).pack(),
+            );
+            seq.push(TextElem::packed(eco_format!(" {}", terms.join("; "))));
+        };
+
+        if !self.see.is_empty() {
+            seq.push(TextElem::packed(", "));
+            refer(&mut seq, "see", &self.see);
+        }
+
+        if !self.see_also.is_empty() {
+            seq.push(TextElem::packed(". "));
+            refer(&mut seq, "see-also", &self.see_also);
+        }
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+/// Sort groups by their sort key in the alphabet of the given language,
+/// ignoring case and accents.
+fn sorted(terms: BTreeMap<EcoString, Group>, lang: Lang) -> Vec<(EcoString, Group)> {
+    let mut terms: Vec<_> = terms.into_iter().collect();
+    terms.sort_by_cached_key(|(term, group)| {
+        (collate(group.key.as_ref().unwrap_or(term), lang), term.clone())
+    });
+    terms
+}
+
+/// Turn a string into a key that sorts it in the alphabet of a language.
+///
+/// Letters are compared without case and accents, except for the letters
+/// that the language treats as separate letters. Those sort after the letter
+/// they follow in the alphabet and before the next one.
+fn collate(text: &str, lang: Lang) -> String {
+    let letters = letters(lang);
+    let mut key = String::new();
+    for c in text.nfc().flat_map(char::to_lowercase) {
+        match letters.iter().position(|&(letter, _)| letter == c) {
+            Some(i) => {
+                key.push(letters[i].1);
+                key.extend(char::from_u32(SEPARATE + i as u32));
+            }
+            None => key.extend(c.nfd().filter(|&c| !is_combining_mark(c))),
+        }
+    }
+    key
+}
+
+/// The start of the private use characters that mark separate letters in
+/// collation keys. They sort after all other letters.
+const SEPARATE: u32 = 0x100000;
+
+/// The letters that a language treats as separate letters, in alphabetical
+/// order, along with the letter they follow.
+fn letters(lang: Lang) -> &'static [(char, char)] {
+    match lang.as_str() {
+        "cs" | "sk" => &[('č', 'c'), ('ř', 'r'), ('š', 's'), ('ž', 'z')],
+        "da" | "nb" | "nn" | "no" => &[('æ', 'z'), ('ø', 'z'), ('å', 'z')],
+        "es" => &[('ñ', 'n')],
+        "fi" | "sv" => &[('å', 'z'), ('ä', 'z'), ('ö', 'z')],
+        "hu" => &[('ö', 'o'), ('ő', 'o'), ('ü', 'u'), ('ű', 'u')],
+        "pl" => &[
+            ('ą', 'a'),
+            ('ć', 'c'),
+            ('ę', 'e'),
+            ('ł', 'l'),
+            ('ń', 'n'),
+            ('ó', 'o'),
+            ('ś', 's'),
+            ('ź', 'z'),
+            ('ż', 'z'),
+        ],
+        "tr" => &[('ç', 'c'), ('ğ', 'g'), ('ö', 'o'), ('ş', 's'), ('ü', 'u')],
+        _ => &[],
+    }
+}
@@ -4,11 +4,15 @@ mod bibliography;
 mod cite;
 mod document;
 mod emph;
+mod endnote;
 #[path = "enum.rs"]
 mod enum_;
 mod figure;
 mod footnote;
 mod heading;
+mod headline;
+pub mod html;
+mod index;
 mod link;
 mod list;
 #[path = "numbering.rs"]
@@ -18,17 +22,24 @@ mod par;
 mod quote;
 mod reference;
 mod strong;
+mod structure;
 mod table;
+mod template;
 mod terms;
+mod translation;
+mod untrusted;
 
 pub use self::bibliography::*;
 pub use self::cite::*;
 pub use self::document::*;
 pub use self::emph::*;
+pub use self::endnote::*;
 pub use self::enum_::*;
 pub use self::figure::*;
 pub use self::footnote::*;
 pub use self::heading::*;
+pub use self::headline::*;
+pub use self::index::*;
 pub use self::link::*;
 pub use self::list::*;
 pub use self::numbering_::*;
@@ -37,8 +48,12 @@ pub use self::par::*;
 pub use self::quote::*;
 pub use self::reference::*;
 pub use self::strong::*;
+pub use self::structure::*;
 pub use self::table::*;
+pub use self::template::*;
 pub use self::terms::*;
+pub use self::translation::*;
+pub use self::untrusted::*;
 
 use crate::foundations::{category, Category, Scope};
 
@@ -58,18 +73,30 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<LinkElem>();
     global.define_elem::<OutlineElem>();
     global.define_elem::<HeadingElem>();
+    global.define_elem::<HeadlineElem>();
     global.define_elem::<FigureElem>();
     global.define_elem::<FootnoteElem>();
+    global.define_elem::<EndnoteElem>();
+    global.define_elem::<EndnotesElem>();
     global.define_elem::<QuoteElem>();
     global.define_elem::<CiteElem>();
     global.define_elem::<BibliographyElem>();
+    global.define_elem::<IndexElem>();
     global.define_elem::<EnumElem>();
     global.define_elem::<ListElem>();
     global.define_elem::<ParbreakElem>();
     global.define_elem::<ParElem>();
+    global.define_elem::<TabElem>();
     global.define_elem::<TableElem>();
     global.define_elem::<TermsElem>();
+    global.define_elem::<TranslatableElem>();
+    global.define_elem::<TemplateElem>();
+    global.define_elem::<SlotElem>();
+    global.define_elem::<UseTemplateElem>();
     global.define_elem::<EmphElem>();
     global.define_elem::<StrongElem>();
+    global.define_elem::<UntrustedElem>();
     global.define_func::<numbering>();
+    global.define_func::<dependent>();
+    global.define_module(html::module());
 }
@@ -2,13 +2,15 @@ use std::str::FromStr;
 
 use chinese_number::{ChineseCase, ChineseCountMethod, ChineseVariant, NumberToChinese};
 use comemo::Tracked;
-use ecow::{eco_format, EcoString, EcoVec};
+use ecow::{eco_format, eco_vec, EcoString, EcoVec};
 use smallvec::{smallvec, SmallVec};
 
-use crate::diag::SourceResult;
+use crate::diag::{warning, SourceResult};
 use crate::engine::Engine;
-use crate::foundations::{cast, func, Context, Func, Str, Value};
-use crate::text::Case;
+use crate::foundations::{cast, func, ty, Context, Func, IntoValue, Repr, Str, Value};
+use crate::introspection::{Counter, CounterKey};
+use crate::syntax::Span;
+use crate::text::{Case, Lang, TextElem};
 
 /// Applies a numbering to a sequence of numbers.
 ///
@@ -25,6 +27,7 @@ use crate::text::Case;
 /// #numbering("1.1)", 1, 2, 3) \
 /// #numbering("1.a.i", 1, 2) \
 /// #numbering("I – 1", 12, 2) \
+/// #numbering("Chapter {Words}", 3) \
 /// #numbering(
 ///   (..nums) => nums
 ///     .pos()
@@ -39,16 +42,33 @@ pub fn numbering(
     engine: &mut Engine,
     /// The callsite context.
     context: Tracked<Context>,
+    /// The callsite span.
+    span: Span,
     /// Defines how the numbering works.
     ///
     /// **Counting symbols** are `1`, `a`, `A`, `i`, `I`, `一`, `壹`, `あ`, `い`, `ア`, `イ`, `א`, `가`,
-    /// `ㄱ`, and `*`. They are replaced by the number in the sequence, in the
-    /// given case.
+    /// `ㄱ`, `١`, `۱`, `१`, and `*`. They are replaced by the number in the
+    /// sequence, in the given case. The symbols `١`, `۱`, and `१` count with
+    /// Arabic-Indic, Persian, and Devanagari digits, respectively.
     ///
     /// The `*` character means that symbols should be used to count, in the
     /// order of `*`, `†`, `‡`, `§`, `¶`, and `‖`. If there are more than six
     /// items, the number is represented using multiple symbols.
     ///
+    /// The **named counting symbols** `{ordinal}`, `{words}`, and `{Words}`
+    /// display ordinal numbers and numbers as words. They follow the [text
+    /// language]($text.lang): `{ordinal}` yields "2nd" in English, "2e" in
+    /// French, and "2." in German. `{words}` yields "two" in English and
+    /// "zwei" in German, `{Words}` additionally capitalizes the first letter.
+    /// Other languages use English words. Outside of [context], where the
+    /// language is unknown, English is used with a warning.
+    ///
+    /// Every counting symbol can also be written in braces, like `{1}` or
+    /// `{A}`. If a pattern contains a symbol in braces, all characters outside
+    /// of braces are taken literally. This way, `{"Chapter {Words}"}` keeps the
+    /// letters of "Chapter" and `{"Appendix {A}"}` counts with the last `A`
+    /// only.
+    ///
     /// **Suffixes** are all characters after the last counting symbol. They are
     /// repeated as-is at the end of any rendered number.
     ///
@@ -70,6 +90,17 @@ pub fn numbering(
     #[variadic]
     numbers: Vec<usize>,
 ) -> SourceResult<Value> {
+    let pattern = match &numbering {
+        Numbering::Pattern(pattern) => Some(pattern),
+        Numbering::Dependent(dependent) => Some(&dependent.pattern),
+        Numbering::Func(_) => None,
+    };
+    if context.styles().is_err() && pattern.is_some_and(NumberingPattern::is_localized) {
+        engine.tracer.warn(warning!(
+            span, "numbering in English because the text language is unknown";
+            hint: "try wrapping this in a `context` expression"
+        ));
+    }
     numbering.apply(engine, context, &numbers)
 }
 
@@ -80,6 +111,8 @@ pub enum Numbering {
     Pattern(NumberingPattern),
     /// A closure mapping from an item's number to content.
     Func(Func),
+    /// A pattern whose leading numbers come from another counter.
+    Dependent(DependentNumbering),
 }
 
 impl Numbering {
@@ -90,16 +123,22 @@ impl Numbering {
         context: Tracked<Context>,
         numbers: &[usize],
     ) -> SourceResult<Value> {
+        let lang = || context.styles().map(TextElem::lang_in).unwrap_or(Lang::ENGLISH);
         Ok(match self {
-            Self::Pattern(pattern) => Value::Str(pattern.apply(numbers).into()),
+            Self::Pattern(pattern) => Value::Str(pattern.apply(numbers, lang()).into()),
             Self::Func(func) => func.call(engine, context, numbers.iter().copied())?,
+            Self::Dependent(dependent) => {
+                Value::Str(dependent.pattern.apply(numbers, lang()).into())
+            }
         })
     }
 
     /// Trim the prefix suffix if this is a pattern.
     pub fn trimmed(mut self) -> Self {
-        if let Self::Pattern(pattern) = &mut self {
-            pattern.trimmed = true;
+        match &mut self {
+            Self::Pattern(pattern) => pattern.trimmed = true,
+            Self::Dependent(dependent) => dependent.pattern.trimmed = true,
+            Self::Func(_) => {}
         }
         self
     }
@@ -116,21 +155,97 @@ cast! {
     self => match self {
         Self::Pattern(pattern) => pattern.into_value(),
         Self::Func(func) => func.into_value(),
+        Self::Dependent(dependent) => dependent.into_value(),
     },
     v: NumberingPattern => Self::Pattern(v),
     v: Func => Self::Func(v),
+    v: DependentNumbering => Self::Dependent(v),
+}
+
+/// Creates a numbering whose leading numbers come from another counter.
+///
+/// All counting symbols of the pattern but the last display the numbers of
+/// the `parent` counter. The last one displays the count of the numbered
+/// thing since the parent's displayed numbers last changed. This way,
+/// figures can be numbered per chapter without a custom numbering function,
+/// while outlines and references keep working.
+///
+/// Only counters display the parent's numbers. When used with the
+/// [`numbering`] function, the pattern is applied to the given numbers.
+///
+/// # Example
+/// ```example
+/// #set heading(numbering: "1.")
+/// #set figure(numbering: dependent(heading, "1.1"))
+/// #show figure.where(kind: table): set figure(
+///   numbering: dependent(heading, "1-1"),
+/// )
+///
+/// = Introduction
+/// #figure(rect[A], caption: [First])
+/// #figure(rect[B], caption: [Second])
+///
+/// = Results
+/// #figure(rect[C], caption: [Third])
+/// #figure(table[D], caption: [Data])
+/// ```
+#[func]
+pub fn dependent(
+    /// The counter whose numbers come first, for instance `heading`. Takes
+    /// the same values as the key of the [`counter`] constructor.
+    parent: CounterKey,
+    /// The numbering pattern. Its number of counting symbols determines how
+    /// many of the parent's numbers are displayed.
+    pattern: NumberingPattern,
+) -> DependentNumbering {
+    DependentNumbering { parent: Counter::new(parent), pattern }
+}
+
+/// A numbering pattern whose leading numbers come from another counter.
+///
+/// Created with the [`dependent`] function.
+#[ty]
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct DependentNumbering {
+    /// The counter whose numbers come first.
+    pub parent: Counter,
+    /// The pattern to display the numbers with.
+    pub pattern: NumberingPattern,
+}
+
+impl DependentNumbering {
+    /// How many of the parent's numbers are displayed.
+    pub fn depth(&self) -> usize {
+        self.pattern.pieces().saturating_sub(1)
+    }
+}
+
+impl Repr for DependentNumbering {
+    fn repr(&self) -> EcoString {
+        eco_format!(
+            "dependent({}, {})",
+            self.parent.repr(),
+            self.pattern.clone().into_value().repr()
+        )
+    }
 }
 
 /// How to turn a number into text.
 ///
 /// A pattern consists of a prefix, followed by one of
-/// `1`, `a`, `A`, `i`, `I`, `一`, `壹`, `あ`, `い`, `ア`, `イ`, `א`, `가`, `ㄱ`, `*`, `①`, or `⓵`,
-/// and then a suffix.
+/// `1`, `a`, `A`, `i`, `I`, `一`, `壹`, `あ`, `い`, `ア`, `イ`, `א`, `가`, `ㄱ`, `١`, `۱`, `१`, `*`,
+/// `①`, or `⓵`, and then a suffix.
+///
+/// Instead of a single character, the named symbols `{ordinal}`, `{words}`,
+/// and `{Words}` can be used. Any counting symbol can be written in braces,
+/// in which case all characters outside of braces are literal.
 ///
 /// Examples of valid patterns:
 /// - `1)`
 /// - `a.`
 /// - `(I)`
+/// - `Chapter {Words}`
+/// - `Appendix {A}.{1}`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct NumberingPattern {
     pub pieces: EcoVec<(EcoString, NumberingKind, Case)>,
@@ -139,8 +254,8 @@ pub struct NumberingPattern {
 }
 
 impl NumberingPattern {
-    /// Apply the pattern to the given number.
-    pub fn apply(&self, numbers: &[usize]) -> EcoString {
+    /// Apply the pattern to the given numbers, in the given language.
+    pub fn apply(&self, numbers: &[usize], lang: Lang) -> EcoString {
         let mut fmt = EcoString::new();
         let mut numbers = numbers.iter();
 
@@ -150,7 +265,7 @@ impl NumberingPattern {
             if i > 0 || !self.trimmed {
                 fmt.push_str(prefix);
             }
-            fmt.push_str(&kind.apply(n, *case));
+            fmt.push_str(&kind.apply(n, *case, lang));
         }
 
         for ((prefix, kind, case), &n) in
@@ -161,7 +276,7 @@ impl NumberingPattern {
             } else {
                 fmt.push_str(prefix);
             }
-            fmt.push_str(&kind.apply(n, *case));
+            fmt.push_str(&kind.apply(n, *case, lang));
         }
 
         if !self.trimmed {
@@ -171,8 +286,9 @@ impl NumberingPattern {
         fmt
     }
 
-    /// Apply only the k-th segment of the pattern to a number.
-    pub fn apply_kth(&self, k: usize, number: usize) -> EcoString {
+    /// Apply only the k-th segment of the pattern to a number, in the given
+    /// language.
+    pub fn apply_kth(&self, k: usize, number: usize, lang: Lang) -> EcoString {
         let mut fmt = EcoString::new();
         if let Some((prefix, _, _)) = self.pieces.first() {
             fmt.push_str(prefix);
@@ -183,16 +299,34 @@ impl NumberingPattern {
             .chain(self.pieces.last().into_iter().cycle())
             .nth(k)
         {
-            fmt.push_str(&kind.apply(number, *case));
+            fmt.push_str(&kind.apply(number, *case, lang));
         }
         fmt.push_str(&self.suffix);
         fmt
     }
 
+    /// The default pattern for enumerations in the given language, which counts
+    /// with the language's native digits.
+    pub fn enumeration(lang: Lang) -> Self {
+        let kind = NumberingKind::native_digits(lang);
+        Self {
+            pieces: eco_vec![(EcoString::new(), kind, Case::Lower)],
+            suffix: ".".into(),
+            trimmed: false,
+        }
+    }
+
     /// How many counting symbols this pattern has.
     pub fn pieces(&self) -> usize {
         self.pieces.len()
     }
+
+    /// Whether the pattern displays numbers depending on the text language.
+    pub fn is_localized(&self) -> bool {
+        self.pieces.iter().any(|(_, kind, _)| {
+            matches!(kind, NumberingKind::Ordinal | NumberingKind::Words)
+        })
+    }
 }
 
 impl FromStr for NumberingPattern {
@@ -202,16 +336,35 @@ impl FromStr for NumberingPattern {
         let mut pieces = EcoVec::new();
         let mut handled = 0;
 
-        for (i, c) in pattern.char_indices() {
-            let Some(kind) = NumberingKind::from_char(c.to_ascii_lowercase()) else {
+        // With a symbol in braces, everything outside of braces is literal.
+        let braced = pattern
+            .char_indices()
+            .any(|(i, _)| NumberingKind::from_name(&pattern[i..]).is_some());
+
+        let mut i = 0;
+        while let Some(c) = pattern[i..].chars().next() {
+            let (kind, case, len) = if let Some((kind, case, len)) =
+                NumberingKind::from_name(&pattern[i..])
+            {
+                (kind, case, len)
+            } else if let Some(kind) =
+                NumberingKind::from_char(c.to_ascii_lowercase()).filter(|_| !braced)
+            {
+                let case = if c.is_uppercase() || c == '壹' {
+                    Case::Upper
+                } else {
+                    Case::Lower
+                };
+                (kind, case, c.len_utf8())
+            } else {
+                i += c.len_utf8();
                 continue;
             };
 
             let prefix = pattern[handled..i].into();
-            let case =
-                if c.is_uppercase() || c == '壹' { Case::Upper } else { Case::Lower };
             pieces.push((prefix, kind, case));
-            handled = c.len_utf8() + i;
+            i += len;
+            handled = i;
         }
 
         let suffix = pattern[handled..].into();
@@ -226,14 +379,33 @@ impl FromStr for NumberingPattern {
 cast! {
     NumberingPattern,
     self => {
+        // Braces are needed if some characters must be taken literally.
+        let counts = |s: &EcoString| {
+            s.chars()
+                .any(|c| NumberingKind::from_char(c.to_ascii_lowercase()).is_some())
+        };
+        let braced = self.pieces.iter().any(|(prefix, kind, case)| {
+            counts(prefix) || kind.to_name(*case).is_some()
+        }) || counts(&self.suffix);
+
         let mut pat = EcoString::new();
         for (prefix, kind, case) in &self.pieces {
             pat.push_str(prefix);
+            if let Some(name) = kind.to_name(*case) {
+                pat.push_str(name);
+                continue;
+            }
             let mut c = kind.to_char();
             if *case == Case::Upper {
                 c = c.to_ascii_uppercase();
             }
-            pat.push(c);
+            if braced {
+                pat.push('{');
+                pat.push(c);
+                pat.push('}');
+            } else {
+                pat.push(c);
+            }
         }
         pat.push_str(&self.suffix);
         pat.into_value()
@@ -280,6 +452,13 @@ pub enum NumberingKind {
     EasternArabic,
     /// The variant of Eastern Arabic numerals used in Persian and Urdu.
     EasternArabicPersian,
+    /// Devanagari numerals, used in Hindi, Marathi, and Nepali.
+    Devanagari,
+    /// Ordinal numbers in the text language (1st, 2nd, 3rd, etc. in English).
+    Ordinal,
+    /// Numbers spelled out in the text language (one, two, three, etc. in
+    /// English). Uses both cases, where uppercase capitalizes the first letter.
+    Words,
     /// Circled numbers (①, ②, ③, etc.), up to 50.
     CircledNumber,
     /// Double-circled numbers (⓵, ⓶, ⓷, etc.), up to 10.
@@ -304,13 +483,61 @@ impl NumberingKind {
             '가' => NumberingKind::KoreanSyllable,
             '\u{0661}' => NumberingKind::EasternArabic,
             '\u{06F1}' => NumberingKind::EasternArabicPersian,
+            '\u{0967}' => NumberingKind::Devanagari,
             '①' => NumberingKind::CircledNumber,
             '⓵' => NumberingKind::DoubleCircledNumber,
             _ => return None,
         })
     }
 
+    /// The decimal numbering commonly used in the given language.
+    pub fn native_digits(lang: Lang) -> Self {
+        match lang.as_str() {
+            "ar" => Self::EasternArabic,
+            "fa" | "ur" => Self::EasternArabicPersian,
+            "hi" | "mr" | "ne" => Self::Devanagari,
+            _ => Self::Arabic,
+        }
+    }
+
+    /// Create a numbering kind from a named counting symbol or a counting
+    /// symbol in braces at the start of the text, returning its case and the
+    /// length of the symbol.
+    pub fn from_name(text: &str) -> Option<(Self, Case, usize)> {
+        let named = [
+            ("{ordinal}", Self::Ordinal, Case::Lower),
+            ("{words}", Self::Words, Case::Lower),
+            ("{Words}", Self::Words, Case::Upper),
+        ]
+        .into_iter()
+        .find(|(name, _, _)| text.starts_with(name))
+        .map(|(name, kind, case)| (kind, case, name.len()));
+        named.or_else(|| {
+            // A single counting symbol in braces.
+            let mut chars = text.strip_prefix('{')?.chars();
+            let (c, '}') = (chars.next()?, chars.next()?) else { return None };
+            let kind = Self::from_char(c.to_ascii_lowercase())?;
+            let case =
+                if c.is_uppercase() || c == '壹' { Case::Upper } else { Case::Lower };
+            Some((kind, case, c.len_utf8() + 2))
+        })
+    }
+
+    /// The named counting symbol for this numbering kind, if it is written
+    /// as one.
+    pub fn to_name(self, case: Case) -> Option<&'static str> {
+        match (self, case) {
+            (Self::Ordinal, _) => Some("{ordinal}"),
+            (Self::Words, Case::Lower) => Some("{words}"),
+            (Self::Words, Case::Upper) => Some("{Words}"),
+            _ => None,
+        }
+    }
+
     /// The lowercase character for this numbering kind.
+    ///
+    /// Kinds that are written as a [named symbol](Self::to_name) yield the
+    /// character of the numerals they build upon.
     pub fn to_char(self) -> char {
         match self {
             Self::Arabic => '1',
@@ -328,13 +555,15 @@ impl NumberingKind {
             Self::KoreanSyllable => '가',
             Self::EasternArabic => '\u{0661}',
             Self::EasternArabicPersian => '\u{06F1}',
+            Self::Devanagari => '\u{0967}',
+            Self::Ordinal | Self::Words => '1',
             Self::CircledNumber => '①',
             Self::DoubleCircledNumber => '⓵',
         }
     }
 
-    /// Apply the numbering to the given number.
-    pub fn apply(self, mut n: usize, case: Case) -> EcoString {
+    /// Apply the numbering to the given number, in the given language.
+    pub fn apply(self, mut n: usize, case: Case, lang: Lang) -> EcoString {
         match self {
             Self::Arabic => {
                 eco_format!("{n}")
@@ -539,6 +768,15 @@ impl NumberingKind {
             ),
             Self::EasternArabic => decimal('\u{0660}', n),
             Self::EasternArabicPersian => decimal('\u{06F0}', n),
+            Self::Devanagari => decimal('\u{0966}', n),
+            Self::Ordinal => ordinal(n, lang),
+            Self::Words => {
+                let words = words(n, lang);
+                match case {
+                    Case::Lower => words,
+                    Case::Upper => capitalize(&words),
+                }
+            }
             Self::CircledNumber => zeroless::<50>(
                 |x| {
                     [
@@ -613,3 +851,162 @@ fn decimal(start: char, mut n: usize) -> EcoString {
     }
     cs.into_iter().rev().collect()
 }
+
+/// Stringify a number as an ordinal in the given language.
+fn ordinal(n: usize, lang: Lang) -> EcoString {
+    match lang.as_str() {
+        "fr" => eco_format!("{n}{}", if n == 1 { "er" } else { "e" }),
+        "nl" => eco_format!("{n}e"),
+        "es" | "it" | "pt" => eco_format!("{n}º"),
+        "ru" | "uk" => eco_format!("{n}-й"),
+        "sv" => {
+            let suffix = match (n % 10, n % 100) {
+                (1 | 2, 11 | 12) => "e",
+                (1 | 2, _) => "a",
+                _ => "e",
+            };
+            eco_format!("{n}:{suffix}")
+        }
+        "cs" | "da" | "de" | "dsb" | "et" | "fi" | "hr" | "hu" | "nb" | "nn" | "pl"
+        | "sk" | "sl" | "sr" | "tr" => eco_format!("{n}."),
+        _ => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            eco_format!("{n}{suffix}")
+        }
+    }
+}
+
+/// Spell out a number in the given language.
+///
+/// Numbers of a million and more are written with digits.
+fn words(n: usize, lang: Lang) -> EcoString {
+    if n >= 1_000_000 {
+        return eco_format!("{n}");
+    }
+
+    match lang.as_str() {
+        "de" => german_words(n),
+        _ => english_words(n),
+    }
+}
+
+/// Spell out a number below a million in English.
+fn english_words(n: usize) -> EcoString {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+        "ninety",
+    ];
+
+    let (head, unit, rest) = match n {
+        0..=19 => return ONES[n].into(),
+        20..=99 if n % 10 == 0 => return TENS[n / 10].into(),
+        20..=99 => return eco_format!("{}-{}", TENS[n / 10], ONES[n % 10]),
+        100..=999 => (n / 100, "hundred", n % 100),
+        _ => (n / 1000, "thousand", n % 1000),
+    };
+
+    let mut words = eco_format!("{} {unit}", english_words(head));
+    if rest > 0 {
+        words.push(' ');
+        words.push_str(&english_words(rest));
+    }
+    words
+}
+
+/// Spell out a number below a million in German.
+fn german_words(n: usize) -> EcoString {
+    if n == 1 {
+        return "eins".into();
+    }
+
+    let mut words = german_prefix(n);
+    if n % 100 == 1 {
+        // A trailing one is "eins" when it is not followed by anything.
+        words.push('s');
+    }
+    words
+}
+
+/// Spell out a number below a million in German, in the form it takes when
+/// it precedes another word (e.g. "ein" instead of "eins").
+fn german_prefix(n: usize) -> EcoString {
+    const ONES: [&str; 20] = [
+        "null",
+        "ein",
+        "zwei",
+        "drei",
+        "vier",
+        "fünf",
+        "sechs",
+        "sieben",
+        "acht",
+        "neun",
+        "zehn",
+        "elf",
+        "zwölf",
+        "dreizehn",
+        "vierzehn",
+        "fünfzehn",
+        "sechzehn",
+        "siebzehn",
+        "achtzehn",
+        "neunzehn",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig",
+        "achtzig", "neunzig",
+    ];
+
+    let (head, unit, rest) = match n {
+        0..=19 => return ONES[n].into(),
+        20..=99 if n % 10 == 0 => return TENS[n / 10].into(),
+        20..=99 => return eco_format!("{}und{}", ONES[n % 10], TENS[n / 10]),
+        100..=999 => (n / 100, "hundert", n % 100),
+        _ => (n / 1000, "tausend", n % 1000),
+    };
+
+    let mut words = eco_format!("{}{unit}", german_prefix(head));
+    if rest > 0 {
+        words.push_str(&german_prefix(rest));
+    }
+    words
+}
+
+/// Uppercase the first letter of a text.
+fn capitalize(text: &str) -> EcoString {
+    let mut chars = text.chars();
+    let mut capitalized = EcoString::new();
+    if let Some(first) = chars.next() {
+        capitalized.extend(first.to_uppercase());
+    }
+    capitalized.push_str(chars.as_str());
+    capitalized
+}
@@ -7,7 +7,7 @@ use crate::diag::{bail, At, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, scope, select_where, Content, Context, Func, LocatableSelector,
-    NativeElement, Packed, Show, ShowSet, Smart, StyleChain, Styles,
+    NativeElement, Packed, Show, ShowSet, Smart, StyleChain, Styles, Value,
 };
 use crate::introspection::{Counter, CounterKey, Locatable};
 use crate::layout::{BoxElem, Em, Fr, HElem, HideElem, Length, Rel, RepeatElem, Spacing};
@@ -117,6 +117,50 @@ pub struct OutlineElem {
     /// ```
     pub depth: Option<NonZeroUsize>,
 
+    /// A function that decides which of the targeted elements are included in
+    /// the outline. It receives each element and returns a boolean.
+    ///
+    /// ```example
+    /// #outline(
+    ///   filter: it => it.body != [Appendix],
+    /// )
+    ///
+    /// = Introduction
+    /// = Appendix
+    /// ```
+    #[borrowed]
+    pub filter: Option<Func>,
+
+    /// A function that groups the outline's entries. It receives each element
+    /// and returns the group the element belongs to. Whenever the group
+    /// changes between two consecutive entries, the new group is displayed
+    /// above its first entry. Returning `{none}` puts an element into no
+    /// group.
+    ///
+    /// The function is called in the [context] of the element, so it can
+    /// query the document around the element, for instance to find the
+    /// chapter it belongs to.
+    ///
+    /// ```example
+    /// #outline(
+    ///   title: [Figures],
+    ///   target: figure,
+    ///   group: it => {
+    ///     let chapters = query(
+    ///       heading.where(level: 1).before(it.location())
+    ///     )
+    ///     strong(chapters.last().body)
+    ///   },
+    /// )
+    ///
+    /// = Dogs
+    /// #figure(rect[Woof], caption: [Bark])
+    /// = Cats
+    /// #figure(rect[Meow], caption: [Purr])
+    /// ```
+    #[borrowed]
+    pub group: Option<Func>,
+
     /// How to indent the outline's entries.
     ///
     /// - `{none}`: No indent
@@ -207,9 +251,21 @@ impl Show for Packed<OutlineElem> {
         let depth = self.depth(styles).unwrap_or(NonZeroUsize::new(usize::MAX).unwrap());
 
         let mut ancestors: Vec<&Content> = vec![];
+        let mut group = None;
         let elems = engine.introspector.query(&self.target(styles).0);
 
         for elem in &elems {
+            let context = Context::new(elem.location(), Some(styles));
+            if let Some(filter) = self.filter(styles) {
+                if !filter
+                    .call(engine, context.track(), [elem.clone()])?
+                    .cast::<bool>()
+                    .at(filter.span())?
+                {
+                    continue;
+                }
+            }
+
             let Some(entry) = OutlineEntry::from_outlinable(
                 engine,
                 self.span(),
@@ -226,6 +282,18 @@ impl Show for Packed<OutlineElem> {
                 continue;
             }
 
+            // Display the group when it changes.
+            if let Some(func) = self.group(styles) {
+                let value = func.call(engine, context.track(), [elem.clone()])?;
+                if group.as_ref() != Some(&value) {
+                    if value != Value::None {
+                        seq.push(value.clone().display());
+                        seq.push(LinebreakElem::new().pack());
+                    }
+                    group = Some(value);
+                }
+            }
+
             // Deals with the ancestors of the current element.
             // This is only applicable for elements with a hierarchy/level.
             while ancestors
@@ -441,6 +509,28 @@ pub struct OutlineEntry {
     /// numbering set for the referenced page.
     #[required]
     pub page: Content,
+
+    /// The number of the element this entry refers to, formatted with the
+    /// element's numbering, or `{none}` if the element is not numbered. This
+    /// is useful to build custom entries that display the number separately
+    /// from the element's title.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.a")
+    /// #show outline.entry: it => {
+    ///   box(width: 2em, it.numbers)
+    ///   it.element.body
+    ///   box(width: 1fr, it.fill)
+    ///   it.page
+    /// }
+    ///
+    /// #outline(fill: repeat[.])
+    ///
+    /// = Introduction
+    /// == Motivation
+    /// ```
+    #[synthesized]
+    pub numbers: Option<Content>,
 }
 
 impl OutlineEntry {
@@ -477,7 +567,18 @@ impl OutlineEntry {
             &page_numbering,
         )?;
 
-        Ok(Some(Self::new(outlinable.level(), elem, body, fill, page)))
+        let numbers = match outlinable.numbering() {
+            Some(numbering) => Some(
+                outlinable
+                    .counter()
+                    .display_at_loc(engine, location, styles, numbering)?,
+            ),
+            None => None,
+        };
+
+        let mut entry = Self::new(outlinable.level(), elem, body, fill, page);
+        entry.push_numbers(numbers);
+        Ok(Some(entry))
     }
 }
 
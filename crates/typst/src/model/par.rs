@@ -3,11 +3,11 @@ use std::fmt::{self, Debug, Formatter};
 use crate::diag::SourceResult;
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, Args, Cast, Construct, Content, NativeElement, Packed, Set, Smart, StyleChain,
-    Unlabellable,
+    cast, dict, elem, Args, Cast, Construct, Content, Dict, NativeElement, Packed, Set,
+    Smart, StyleChain, Unlabellable, Value,
 };
-use crate::layout::{Em, Fragment, Length, Size};
-use crate::realize::StyleVec;
+use crate::layout::{Abs, Em, Fragment, HAlignment, Length, Point, Rel, Size};
+use crate::realize::{Behave, Behaviour, StyleVec};
 
 /// Arranges text, spacing and inline-level elements into a paragraph.
 ///
@@ -97,6 +97,33 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// The tab stops of the paragraph.
+    ///
+    /// Each [tab]($tab) advances to the next stop on its line. A stop is
+    /// either a length, measured from the start of the line, or a dictionary
+    /// with the following keys:
+    ///
+    /// - `pos`: The position of the stop, measured from the start of the line.
+    /// - `align`: How the text after the tab is aligned at the stop. One of
+    ///   `start`, `left`, `center`, `right`, or `end`. Defaults to `start`.
+    /// - `leader`: Content that is repeated to fill the space before the stop,
+    ///   like the dots in a menu. Defaults to `{none}`.
+    ///
+    /// If there is no further stop on the line, a tab inserts a quarter em of
+    /// space instead.
+    ///
+    /// ```example
+    /// #set par(tabs: (
+    ///   2cm,
+    ///   (pos: 100%, align: end, leader: [.]),
+    /// ))
+    ///
+    /// Soup #tab() Tomato #tab() 4.50 \
+    /// Main #tab() Risotto #tab() 12.00
+    /// ```
+    #[ghost]
+    pub tabs: Vec<TabStop>,
+
     /// Indicates wheter an overflowing line should be shrunk.
     ///
     /// This property is set to `false` on raw blocks, because shrinking a line
@@ -142,6 +169,7 @@ impl Packed<ParElem> {
         consecutive: bool,
         region: Size,
         expand: bool,
+        contour: Option<(&[Point], Abs)>,
     ) -> SourceResult<Fragment> {
         crate::layout::layout_inline(
             &self.children,
@@ -150,6 +178,7 @@ impl Packed<ParElem> {
             consecutive,
             region,
             expand,
+            contour,
         )
     }
 }
@@ -173,6 +202,58 @@ pub enum Linebreaks {
     Optimized,
 }
 
+/// A stop for [tabs]($tab) in a paragraph.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TabStop {
+    /// The position of the stop, relative to the start of the line.
+    pub pos: Rel<Length>,
+    /// How the text after the tab is aligned at the stop.
+    pub align: HAlignment,
+    /// Content that is repeated to fill the space before the stop.
+    pub leader: Option<Content>,
+}
+
+cast! {
+    TabStop,
+    self => dict! {
+        "pos" => self.pos,
+        "align" => self.align,
+        "leader" => self.leader,
+    }.into_value(),
+    pos: Rel<Length> => Self { pos, align: HAlignment::Start, leader: None },
+    mut dict: Dict => {
+        let pos = dict.take("pos")?.cast()?;
+        let align = dict.take("align").ok().map(Value::cast)
+            .transpose()?.unwrap_or(HAlignment::Start);
+        let leader = dict.take("leader").ok().map(Value::cast)
+            .transpose()?.flatten();
+        dict.finish(&["pos", "align", "leader"])?;
+        Self { pos, align, leader }
+    },
+}
+
+/// Advances to the next tab stop of the paragraph.
+///
+/// The stops are configured with the paragraph's [`tabs`]($par.tabs)
+/// property. Together with leaders, they make it easy to set menus, programs,
+/// or tables of contents whose entries are connected by dotted lines.
+///
+/// # Example
+/// ```example
+/// #set par(tabs: ((pos: 100%, align: end, leader: [.]),))
+///
+/// Introduction #tab() 1 \
+/// Methods #tab() 4
+/// ```
+#[elem(Behave)]
+pub struct TabElem {}
+
+impl Behave for Packed<TabElem> {
+    fn behaviour(&self) -> Behaviour {
+        Behaviour::Destructive
+    }
+}
+
 /// A paragraph break.
 ///
 /// This starts a new paragraph. Especially useful when used within code like
@@ -164,6 +164,7 @@ impl Show for Packed<QuoteElem> {
                 TextElem::lang_in(styles),
                 TextElem::region_in(styles),
                 SmartQuoteElem::alternative_in(styles),
+                SmartQuoteElem::nesting_in(styles),
             );
 
             // Alternate between single and double quotes.
@@ -1,18 +1,21 @@
+use std::str::FromStr;
+
 use comemo::Track;
 use ecow::eco_format;
 
 use crate::diag::{bail, At, Hint, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, Content, Context, Func, IntoValue, Label, NativeElement, Packed, Show,
-    Smart, StyleChain, Synthesize,
+    cast, elem, Cast, Content, Context, Func, IntoValue, Label, NativeElement, Packed,
+    Repr, Selector, Show, Smart, StyleChain, Synthesize,
 };
-use crate::introspection::{Counter, Locatable};
+use crate::introspection::{Counter, CounterKey, Locatable, Location};
 use crate::math::EquationElem;
 use crate::model::{
-    BibliographyElem, CiteElem, Destination, Figurable, FootnoteElem, Numbering,
+    BibliographyElem, CiteElem, Destination, EndnoteElem, EnumItem, Figurable,
+    FootnoteElem, Numbering, NumberingPattern,
 };
-use crate::text::TextElem;
+use crate::text::{localized_str_in, TextElem};
 
 /// A reference to a label or bibliography.
 ///
@@ -22,10 +25,11 @@ use crate::text::TextElem;
 /// element. Reference syntax can also be used to [cite] from a bibliography.
 ///
 /// Referenceable elements include [headings]($heading), [figures]($figure),
-/// [equations]($math.equation), and [footnotes]($footnote). To create a custom
-/// referenceable element like a theorem, you can create a figure of a custom
-/// [`kind`]($figure.kind) and write a show rule for it. In the future, there
-/// might be a more direct way to define a custom referenceable element.
+/// [equations]($math.equation), [footnotes]($footnote), and labelled
+/// [enumeration items]($enum.item). To create a custom referenceable element
+/// like a theorem, you can create a figure of a custom [`kind`]($figure.kind)
+/// and write a show rule for it. In the future, there might be a more direct
+/// way to define a custom referenceable element.
 ///
 /// If you just want to link to a labelled element and not get an automatic
 /// textual reference, consider using the [`link`] function instead.
@@ -127,6 +131,23 @@ pub struct RefElem {
     #[borrowed]
     pub supplement: Smart<Option<Supplement>>,
 
+    /// The kind of reference to produce.
+    ///
+    /// ```example
+    /// #set page(height: 80pt)
+    ///
+    /// Typst is a typesetting system.
+    /// #metadata(none) <typesetting>
+    /// #pagebreak()
+    /// Typesetting is fun.
+    /// #metadata(none) <typesetting>
+    /// #pagebreak()
+    /// More about typesetting on
+    /// #ref(<typesetting>, form: "page").
+    /// ```
+    #[default(RefForm::Normal)]
+    pub form: RefForm,
+
     /// A synthesized citation.
     #[synthesized]
     pub citation: Option<Packed<CiteElem>>,
@@ -175,13 +196,24 @@ impl Show for Packed<RefElem> {
             return Ok(to_citation(self, engine, styles)?.pack().spanned(span));
         }
 
-        let elem = elem.at(span)?;
+        if self.form(styles) == RefForm::Page {
+            return show_pages(self, engine, styles);
+        }
+
+        let elem = elem.at(span)?.clone();
 
         if elem.func() == FootnoteElem::elem() {
             return Ok(FootnoteElem::with_label(target).pack().spanned(span));
         }
 
-        let elem = elem.clone();
+        if elem.func() == EndnoteElem::elem() {
+            return Ok(EndnoteElem::with_label(target).pack().spanned(span));
+        }
+
+        if let Some(item) = elem.to_packed::<EnumItem>() {
+            return show_enum_item(self, item, engine, styles);
+        }
+
         let refable = elem
             .with::<dyn Refable>()
             .ok_or_else(|| {
@@ -237,6 +269,144 @@ impl Show for Packed<RefElem> {
     }
 }
 
+/// Show a reference to an enumeration item with the item's full number.
+fn show_enum_item(
+    reference: &Packed<RefElem>,
+    item: &Packed<EnumItem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+) -> SourceResult<Content> {
+    let loc = item.location().unwrap();
+    let (Some(numbers), Some(numbering)) = (item.numbers(), item.numbering()) else {
+        bail!(reference.span(), "cannot reference enum item");
+    };
+
+    let context = Context::new(Some(loc), Some(styles));
+    let numbers = numbering
+        .clone()
+        .trimmed()
+        .apply(engine, context.track(), numbers)?
+        .display();
+
+    let supplement = match reference.supplement(styles).as_ref() {
+        Smart::Auto | Smart::Custom(None) => Content::empty(),
+        Smart::Custom(Some(supplement)) => {
+            supplement.resolve(engine, styles, [item.clone().pack()])?
+        }
+    };
+
+    let mut content = numbers;
+    if !supplement.is_empty() {
+        content = supplement + TextElem::packed("\u{a0}") + content;
+    }
+
+    Ok(content.linked(Destination::Location(loc)))
+}
+
+/// Show a reference to the pages of all elements with the target label.
+fn show_pages(
+    reference: &Packed<RefElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+) -> SourceResult<Content> {
+    let target = *reference.target();
+    let elems = engine.introspector.query(&Selector::Label(target));
+    let Some(first) = elems.first() else {
+        bail!(
+            reference.span(),
+            "label `{}` does not exist in the document",
+            target.repr()
+        );
+    };
+
+    let locations: Vec<_> = elems.iter().filter_map(Content::location).collect();
+    let (ranges, pages) = page_ranges(engine, styles, &locations)?;
+
+    let supplement = match reference.supplement(styles).as_ref() {
+        Smart::Auto => {
+            let key = if pages > 1 { "pages" } else { "page" };
+            TextElem::packed(localized_str_in(styles, key))
+        }
+        Smart::Custom(None) => Content::empty(),
+        Smart::Custom(Some(supplement)) => {
+            supplement.resolve(engine, styles, [first.clone()])?
+        }
+    };
+
+    let mut seq = vec![];
+    if !supplement.is_empty() {
+        seq.push(supplement);
+        seq.push(TextElem::packed("\u{a0}"));
+    }
+
+    for (i, range) in ranges.into_iter().enumerate() {
+        if i > 0 {
+            seq.push(TextElem::packed(", "));
+        }
+        seq.push(range);
+    }
+
+    Ok(Content::sequence(seq))
+}
+
+/// Display the pages spanned by the elements at the given locations, folding
+/// consecutive pages into ranges.
+///
+/// Returns the linked ranges and the total number of pages they cover.
+pub(crate) fn page_ranges(
+    engine: &mut Engine,
+    styles: StyleChain,
+    locations: &[Location],
+) -> SourceResult<(Vec<Content>, usize)> {
+    let counter = Counter::new(CounterKey::Page);
+    let mut spans = vec![];
+    for &loc in locations {
+        // Assumes that the page counter isn't reset within an element.
+        let (start, end) = engine.introspector.page_span(loc);
+        let section = engine.introspector.page_section(loc);
+        let first = counter.at_loc(engine, loc)?.first();
+        let last = first + (end.get() - start.get());
+        spans.push((section, first, last, loc));
+    }
+
+    spans.sort_by_key(|&(section, first, last, _)| (section, first, last));
+
+    // Only pages of the same section are folded into a range.
+    let mut folded: Vec<(Option<usize>, usize, usize, Location)> = vec![];
+    for span in spans {
+        match folded.last_mut() {
+            Some(prev) if span.0 == prev.0 && span.1 <= prev.2 + 1 => {
+                prev.2 = prev.2.max(span.2)
+            }
+            _ => folded.push(span),
+        }
+    }
+
+    let pages = folded.iter().map(|&(_, first, last, _)| last - first + 1).sum();
+    let mut ranges = vec![];
+    for (section, first, last, loc) in folded {
+        let numbering = engine
+            .introspector
+            .page_numbering(loc)
+            .cloned()
+            .unwrap_or_else(|| NumberingPattern::from_str("1").unwrap().into())
+            .trimmed();
+
+        let context = Context::new(Some(loc), Some(styles));
+        let numbers = |page| section.into_iter().chain([page]).collect::<Vec<_>>();
+        let mut range =
+            numbering.apply(engine, context.track(), &numbers(first))?.display();
+        if last > first {
+            range += TextElem::packed(localized_str_in(styles, "page-range"));
+            range += numbering.apply(engine, context.track(), &numbers(last))?.display();
+        }
+
+        ranges.push(range.linked(Destination::Location(loc)));
+    }
+
+    Ok((ranges, pages))
+}
+
 /// Turn a reference into a citation.
 fn to_citation(
     reference: &Packed<RefElem>,
@@ -259,6 +429,17 @@ fn to_citation(
     Ok(elem)
 }
 
+/// The form of a reference.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum RefForm {
+    /// Refers to the element's number, like "Section 1".
+    #[default]
+    Normal,
+    /// Refers to the pages of all elements with the label, folding
+    /// consecutive pages into ranges, like "pp. 12–15, 18".
+    Page,
+}
+
 /// Additional content for a reference.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum Supplement {
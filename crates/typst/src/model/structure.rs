@@ -0,0 +1,801 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
+
+use crate::engine::{Engine, Route};
+use crate::foundations::{
+    Content, NativeElement, Packed, SequenceElem, StyleChain, StyledElem, Styles, Value,
+};
+use crate::introspection::{Counter, TagElem};
+use crate::layout::{
+    Abs, AlignElem, BlockChild, BlockElem, ColbreakElem, ColumnsElem, ExportOnlyElem,
+    ExportTarget, Frame, FrameItem, GridElem, HElem, HideElem, MasonryElem, PadElem,
+    Page, PagebreakElem, PlaceElem, RepeatElem, ScreenOnlyElem, Sides, Size, StackElem,
+    VElem,
+};
+use crate::math::EquationElem;
+use crate::model::{
+    resolve_numbering, EmphElem, EnumElem, EnumItem, FigureElem, FootnoteElem,
+    HeadingElem, ListElem, ListItem, Numbering, ParbreakElem, StrongElem, TableChild,
+    TableElem, TableItem, TermItem, TermsElem,
+};
+use crate::realize::process_keeping;
+use crate::syntax::Span;
+use crate::text::{
+    FontStyle, FontWeight, Lang, LinebreakElem, RawElem, SpaceElem, TextElem,
+};
+use crate::visualize::{Image, ImageElem, Paint};
+use crate::World;
+
+/// The semantic structure of a document.
+///
+/// Exporters to formats with flowing text, like DOCX or speech, need to know
+/// what the document's parts _are_ rather than where they were placed. This
+/// tree is recovered by realizing the content the document was built from:
+/// User-defined show rules, counters, references, and contextual content are
+/// resolved against the final layout, while headings, paragraphs, lists,
+/// tables, figures, formulas, and footnotes keep their meaning instead of
+/// being replaced by how their built-in show rules style them.
+#[derive(Debug, Default, Clone)]
+pub struct Structure {
+    /// The top-level blocks in reading order.
+    pub blocks: Vec<StructBlock>,
+    /// The resolved margins of the first page.
+    pub margin: Sides<Abs>,
+}
+
+/// A block-level part of a document.
+#[derive(Debug, Clone)]
+pub enum StructBlock {
+    /// A heading with its level.
+    Heading(NonZeroUsize, Vec<StructInline>),
+    /// A paragraph.
+    Paragraph(Vec<StructInline>),
+    /// An item of a bullet, numbered, or term list with its marker.
+    ListItem(EcoString, Vec<StructBlock>),
+    /// A table.
+    Table(StructTable),
+    /// A figure with its body and caption.
+    Figure(Vec<StructBlock>, Option<Vec<StructInline>>),
+    /// A formula that is displayed as its own block, with the styles it
+    /// appears in.
+    Equation(Packed<EquationElem>, Styles),
+    /// An image as it was laid out.
+    Image(StructImage),
+    /// Blocks that are only included in some export formats.
+    Only(Vec<ExportTarget>, Vec<StructBlock>),
+}
+
+/// An inline part of a block.
+#[derive(Debug, Clone)]
+pub enum StructInline {
+    /// A run of uniformly styled text.
+    Text(EcoString, StructStyle),
+    /// An inline formula with the styles it appears in.
+    Equation(Packed<EquationElem>, Styles),
+    /// A footnote with its contents.
+    Footnote(Vec<StructBlock>),
+    /// Inline content that is only included in some export formats.
+    Only(Vec<ExportTarget>, Vec<StructInline>),
+}
+
+/// The resolved character formatting of a run of text.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct StructStyle {
+    /// The first font family.
+    pub family: EcoString,
+    /// The font size.
+    pub size: Abs,
+    /// Whether the text is bold, either through its weight or by being
+    /// strongly emphasized.
+    pub bold: bool,
+    /// Whether the text is italic, either through its style or by being
+    /// emphasized.
+    pub italic: bool,
+    /// The text's fill.
+    pub fill: Option<Paint>,
+    /// The text's language.
+    pub lang: Lang,
+}
+
+/// A table whose cells contain blocks.
+#[derive(Debug, Clone)]
+pub struct StructTable {
+    /// The number of columns.
+    pub columns: usize,
+    /// The cells of each row.
+    pub rows: Vec<Vec<StructCell>>,
+}
+
+/// A table cell.
+#[derive(Debug, Clone)]
+pub struct StructCell {
+    /// The number of columns the cell spans.
+    pub colspan: usize,
+    /// The cell's contents.
+    pub blocks: Vec<StructBlock>,
+}
+
+/// An image together with the size it was laid out at.
+#[derive(Debug, Clone)]
+pub struct StructImage {
+    /// The decoded image.
+    pub image: Image,
+    /// The size of the image on the page.
+    pub size: Size,
+}
+
+impl Structure {
+    /// Recover the structure of a document from the content it was laid out
+    /// from.
+    ///
+    /// The engine's introspector should reflect the final layout, whose pages
+    /// provide the decoded images and their sizes.
+    pub(crate) fn build(
+        engine: &mut Engine,
+        content: &Content,
+        styles: StyleChain,
+        pages: &[Page],
+    ) -> Self {
+        let mut images = HashMap::new();
+        for page in pages {
+            collect_images(&page.frame, &mut images);
+        }
+
+        let mut builder = Builder {
+            engine,
+            blocks: vec![],
+            inlines: vec![],
+            images,
+            next_number: None,
+            strong: false,
+            emph: false,
+        };
+
+        builder.content(content, styles);
+        builder.finish_par();
+        Self {
+            blocks: builder.blocks,
+            margin: pages.first().map(|page| page.margin).unwrap_or_default(),
+        }
+    }
+
+    /// Remove all blocks and inlines that are restricted to other export
+    /// targets.
+    pub fn for_target(&self, target: ExportTarget) -> Self {
+        Self {
+            blocks: blocks_for_target(&self.blocks, target),
+            margin: self.margin,
+        }
+    }
+}
+
+impl StructBlock {
+    /// The plain text of the block.
+    pub fn plain_text(&self) -> EcoString {
+        let mut text = EcoString::new();
+        match self {
+            Self::Heading(_, inlines) | Self::Paragraph(inlines) => {
+                text.push_str(&inlines_text(inlines));
+            }
+            Self::ListItem(marker, blocks) => {
+                text.push_str(marker);
+                text.push(' ');
+                text.push_str(&blocks_text(blocks));
+            }
+            Self::Table(table) => {
+                let cells = table.rows.iter().flatten();
+                let texts: Vec<_> = cells.map(|cell| blocks_text(&cell.blocks)).collect();
+                text.push_str(&texts.join(" "));
+            }
+            Self::Figure(blocks, caption) => {
+                text.push_str(&blocks_text(blocks));
+                if let Some(caption) = caption {
+                    text.push(' ');
+                    text.push_str(&inlines_text(caption));
+                }
+            }
+            Self::Equation(equation, _) => {
+                text.push_str(&equation.clone().pack().plain_text())
+            }
+            Self::Image(image) => {
+                text.push_str(image.image.alt().unwrap_or_default());
+            }
+            Self::Only(_, blocks) => text.push_str(&blocks_text(blocks)),
+        }
+        text
+    }
+}
+
+impl StructInline {
+    /// The plain text of the inline content.
+    pub fn plain_text(&self) -> EcoString {
+        match self {
+            Self::Text(text, _) => text.clone(),
+            Self::Equation(equation, _) => equation.clone().pack().plain_text(),
+            Self::Footnote(_) => EcoString::new(),
+            Self::Only(_, inlines) => inlines_text(inlines),
+        }
+    }
+}
+
+/// The plain text of consecutive blocks, separated by spaces.
+fn blocks_text(blocks: &[StructBlock]) -> EcoString {
+    let texts: Vec<_> = blocks.iter().map(StructBlock::plain_text).collect();
+    texts.join(" ").into()
+}
+
+/// The plain text of consecutive inlines.
+fn inlines_text(inlines: &[StructInline]) -> EcoString {
+    inlines.iter().map(StructInline::plain_text).collect()
+}
+
+/// Walks through content, realizes it, and collects blocks.
+struct Builder<'a, 'e> {
+    /// The engine used to realize content.
+    engine: &'a mut Engine<'e>,
+    /// The finished blocks.
+    blocks: Vec<StructBlock>,
+    /// The contents of the paragraph that is currently being built.
+    inlines: Vec<StructInline>,
+    /// The laid-out images that were not yet placed into the tree, by the span
+    /// of their element. One element can produce multiple images, e.g. in a
+    /// loop, which are consumed in order.
+    images: HashMap<Span, Vec<StructImage>>,
+    /// The number of the next item if the last block was an item of a
+    /// numbered list.
+    next_number: Option<usize>,
+    /// Whether the text is strongly emphasized.
+    strong: bool,
+    /// Whether the text is emphasized.
+    emph: bool,
+}
+
+impl Builder<'_, '_> {
+    /// Add a piece of content.
+    fn content(&mut self, content: &Content, styles: StyleChain) {
+        if !content.is::<SequenceElem>() && !content.is::<StyledElem>() {
+            // Errors were already reported during layout, so content that
+            // fails to realize is dropped.
+            let realized = process_keeping(self.engine, content, styles, is_semantic);
+            if let Ok(Some(realized)) = realized {
+                self.engine.route.increase();
+                if !self.engine.route.within(Route::MAX_SHOW_RULE_DEPTH) {
+                    // Content that matches its own show rule is dropped.
+                } else if let Some(heading) = content.to_packed::<HeadingElem>() {
+                    self.realized_heading(heading, &realized, styles);
+                } else {
+                    self.content(&realized, styles);
+                }
+                self.engine.route.decrease();
+                return;
+            }
+        }
+
+        if let Some(sequence) = content.to_packed::<SequenceElem>() {
+            for child in &sequence.children {
+                self.content(child, styles);
+            }
+        } else if let Some(styled) = content.to_packed::<StyledElem>() {
+            self.content(&styled.child, styles.chain(&styled.styles));
+        } else if let Some(elem) = content.to_packed::<TextElem>() {
+            self.text(elem.text(), styles);
+        } else if content.is::<SpaceElem>() || content.is::<LinebreakElem>() {
+            self.space(styles);
+        } else if let Some(elem) = content.to_packed::<RawElem>() {
+            if elem.block(styles) {
+                self.finish_par();
+                self.text(&elem.text().get(), styles);
+                self.finish_par();
+            } else {
+                self.text(&elem.text().get(), styles);
+            }
+        } else if let Some(elem) = content.to_packed::<StrongElem>() {
+            let outer = std::mem::replace(&mut self.strong, true);
+            self.content(elem.body(), styles);
+            self.strong = outer;
+        } else if let Some(elem) = content.to_packed::<EmphElem>() {
+            self.emph = !self.emph;
+            self.content(elem.body(), styles);
+            self.emph = !self.emph;
+        } else if let Some(elem) = content.to_packed::<HeadingElem>() {
+            self.finish_par();
+            let level = elem.resolve_level(styles);
+            let mut body = elem.body().clone();
+            if let (Some(numbering), Some(location)) =
+                ((**elem).numbering(styles).as_ref(), elem.location())
+            {
+                let counter = Counter::of(HeadingElem::elem());
+                if let Ok(numbers) =
+                    counter.display_at_loc(self.engine, location, styles, numbering)
+                {
+                    body = numbers + SpaceElem::new().pack() + body;
+                }
+            }
+            let inlines = self.inlines(&body, styles);
+            self.push(StructBlock::Heading(level, inlines));
+        } else if let Some(elem) = content.to_packed::<EquationElem>() {
+            if elem.block(styles) {
+                self.finish_par();
+                self.push(StructBlock::Equation(elem.clone(), styles.to_map()));
+            } else {
+                let equation = StructInline::Equation(elem.clone(), styles.to_map());
+                self.inlines.push(equation);
+            }
+        } else if let Some(elem) = content.to_packed::<FootnoteElem>() {
+            if let Some(body) = elem.body_content() {
+                let blocks = self.blocks(body, styles);
+                self.inlines.push(StructInline::Footnote(blocks));
+            }
+        } else if let Some(item) = content.to_packed::<ListItem>() {
+            self.finish_par();
+            let blocks = self.blocks(item.body(), styles);
+            self.push(StructBlock::ListItem("•".into(), blocks));
+        } else if let Some(item) = content.to_packed::<EnumItem>() {
+            self.finish_par();
+            let number = item
+                .number(styles)
+                .or(self.next_number)
+                .unwrap_or_else(|| EnumElem::start_in(styles));
+            let numbering = EnumElem::numbering_in(styles);
+            self.enum_item(item, number, numbering, styles);
+        } else if let Some(item) = content.to_packed::<TermItem>() {
+            self.finish_par();
+            self.term_item(item, styles);
+        } else if let Some(elem) = content.to_packed::<ListElem>() {
+            self.finish_par();
+            for item in elem.children() {
+                let blocks = self.blocks(item.body(), styles);
+                self.push(StructBlock::ListItem("•".into(), blocks));
+            }
+        } else if let Some(elem) = content.to_packed::<EnumElem>() {
+            self.finish_par();
+            self.enumeration(elem, styles);
+        } else if let Some(elem) = content.to_packed::<TermsElem>() {
+            self.finish_par();
+            for item in elem.children() {
+                self.term_item(item, styles);
+            }
+        } else if let Some(elem) = content.to_packed::<TableElem>() {
+            self.finish_par();
+            self.table(elem, styles);
+        } else if let Some(elem) = content.to_packed::<FigureElem>() {
+            self.finish_par();
+            let body = self.blocks(elem.body(), styles);
+            // The caption's built-in show rule adds the supplement and number.
+            let caption = elem
+                .caption(styles)
+                .map(|caption| self.inlines(&caption.clone().pack(), styles));
+            self.push(StructBlock::Figure(body, caption));
+        } else if content.is::<ImageElem>() {
+            self.finish_par();
+            let image = self
+                .images
+                .get_mut(&content.span())
+                .and_then(|images| (!images.is_empty()).then(|| images.remove(0)));
+            if let Some(image) = image {
+                self.push(StructBlock::Image(image));
+            }
+        } else if let Some(elem) = content.to_packed::<ExportOnlyElem>() {
+            self.restricted(elem.targets().clone(), elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<ScreenOnlyElem>() {
+            self.restricted(ExportTarget::SCREEN.to_vec(), elem.body(), styles);
+        } else if let Some(elem) = content.to_packed::<BlockElem>() {
+            self.finish_par();
+            if let Some(BlockChild::Content(body)) = elem.body(styles) {
+                self.content(body, styles);
+            }
+            self.finish_par();
+        } else if content.is::<ParbreakElem>()
+            || content.is::<PagebreakElem>()
+            || content.is::<ColbreakElem>()
+            || content.is::<VElem>()
+        {
+            self.finish_par();
+        } else if content.is::<HElem>()
+            || content.is::<TagElem>()
+            || content.is::<HideElem>()
+            || content.is::<RepeatElem>()
+        {
+            // Spacing, introspection tags, and invisible or decorative content
+            // are not part of the structure.
+        } else if content.is::<AlignElem>()
+            || content.is::<PadElem>()
+            || content.is::<PlaceElem>()
+            || content.is::<ColumnsElem>()
+            || content.is::<StackElem>()
+            || content.is::<GridElem>()
+            || content.is::<MasonryElem>()
+        {
+            self.finish_par();
+            self.fields(content, styles);
+            self.finish_par();
+        } else if !self.fields(content, styles) {
+            self.text(&content.plain_text(), styles);
+        }
+    }
+
+    /// Add the realization of a heading, which stays a heading even if a show
+    /// rule replaced it with other content.
+    fn realized_heading(
+        &mut self,
+        heading: &Packed<HeadingElem>,
+        realized: &Content,
+        styles: StyleChain,
+    ) {
+        self.finish_par();
+        let blocks = self.blocks(realized, styles);
+        if blocks.iter().any(|block| matches!(block, StructBlock::Heading(..))) {
+            blocks.into_iter().for_each(|block| self.push(block));
+            return;
+        }
+
+        let mut inlines = vec![];
+        for block in blocks {
+            if !inlines.is_empty() {
+                inlines.push(StructInline::Text(" ".into(), self.style(styles)));
+            }
+            match block {
+                StructBlock::Paragraph(children) => inlines.extend(children),
+                block => inlines
+                    .push(StructInline::Text(block.plain_text(), self.style(styles))),
+            }
+        }
+
+        let level = heading.resolve_level(styles);
+        self.push(StructBlock::Heading(level, inlines));
+    }
+
+    /// Add the content-valued fields of an element, returning whether it had
+    /// any.
+    fn fields(&mut self, content: &Content, styles: StyleChain) -> bool {
+        let mut found = false;
+        for (_, value) in content.fields() {
+            match value {
+                Value::Content(child) => {
+                    found = true;
+                    self.content(&child, styles);
+                }
+                Value::Array(array) => {
+                    for value in array {
+                        if let Value::Content(child) = value {
+                            found = true;
+                            self.content(&child, styles);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        found
+    }
+
+    /// Add a run of text.
+    fn text(&mut self, text: &str, styles: StyleChain) {
+        if text.is_empty() {
+            return;
+        }
+
+        let style = self.style(styles);
+        if let Some(StructInline::Text(prev, prev_style)) = self.inlines.last_mut() {
+            if *prev_style == style {
+                prev.push_str(text);
+                return;
+            }
+        }
+
+        self.inlines.push(StructInline::Text(text.into(), style));
+    }
+
+    /// The character formatting of text in the given styles.
+    fn style(&self, styles: StyleChain) -> StructStyle {
+        // Font families are matched case-insensitively, so the name is taken
+        // from the font book to restore its capitalization.
+        let book = self.engine.world.book();
+        StructStyle {
+            family: TextElem::font_in(styles)
+                .into_iter()
+                .next()
+                .map(|family| {
+                    book.select_family(family.as_str())
+                        .next()
+                        .and_then(|index| book.info(index))
+                        .map_or_else(
+                            || family.as_str().into(),
+                            |info| info.family.as_str().into(),
+                        )
+                })
+                .unwrap_or_default(),
+            size: TextElem::size_in(styles),
+            bold: self.strong || TextElem::weight_in(styles) >= FontWeight::SEMIBOLD,
+            italic: self.emph != (TextElem::style_in(styles) != FontStyle::Normal),
+            fill: TextElem::fill_in(styles),
+            lang: TextElem::lang_in(styles),
+        }
+    }
+
+    /// Add a word space, collapsing it with adjacent spaces.
+    fn space(&mut self, styles: StyleChain) {
+        if let Some(StructInline::Text(prev, _)) = self.inlines.last() {
+            if prev.ends_with(char::is_whitespace) {
+                return;
+            }
+        }
+        self.text(" ", styles);
+    }
+
+    /// Add the items of a numbered list.
+    fn enumeration(&mut self, elem: &Packed<EnumElem>, styles: StyleChain) {
+        let numbering = elem.numbering(styles);
+        let mut number = elem.start(styles);
+        for item in elem.children() {
+            number = item.number(styles).unwrap_or(number);
+            self.enum_item(item, number, numbering, styles);
+            number = number.saturating_add(1);
+        }
+    }
+
+    /// Add an item of a numbered list with the given number.
+    fn enum_item(
+        &mut self,
+        item: &Packed<EnumItem>,
+        number: usize,
+        numbering: &Numbering,
+        styles: StyleChain,
+    ) {
+        let lang = TextElem::lang_in(styles);
+        let marker = match resolve_numbering(numbering, styles) {
+            Numbering::Pattern(pattern) => pattern.apply_kth(0, number, lang),
+            _ => eco_format!("{number}."),
+        };
+        let blocks = self.blocks(item.body(), styles);
+        self.push(StructBlock::ListItem(marker, blocks));
+        self.next_number = Some(number.saturating_add(1));
+    }
+
+    /// Add an item of a term list, using the term as its marker.
+    fn term_item(&mut self, item: &Packed<TermItem>, styles: StyleChain) {
+        let term = self.inlines(item.term(), styles);
+        let marker = term.iter().map(StructInline::plain_text).collect();
+        let blocks = self.blocks(item.description(), styles);
+        self.push(StructBlock::ListItem(marker, blocks));
+    }
+
+    /// Add a table, splitting its cells into rows.
+    fn table(&mut self, elem: &Packed<TableElem>, styles: StyleChain) {
+        let columns = elem.columns(styles).0.len().max(1);
+        let mut rows: Vec<Vec<StructCell>> = vec![];
+        let mut filled = 0;
+        for child in elem.children() {
+            let items = match child {
+                TableChild::Header(header) => header.children().as_slice(),
+                TableChild::Footer(footer) => footer.children().as_slice(),
+                TableChild::Item(item) => std::slice::from_ref(item),
+            };
+
+            for item in items {
+                let TableItem::Cell(cell) = item else { continue };
+                let colspan = cell.colspan(styles).get().min(columns);
+                let blocks = self.blocks(cell.body(), styles);
+                let cell = StructCell { colspan, blocks };
+                match rows.last_mut() {
+                    Some(row) if filled + colspan <= columns => row.push(cell),
+                    _ => {
+                        rows.push(vec![cell]);
+                        filled = 0;
+                    }
+                }
+                filled += colspan;
+            }
+        }
+
+        self.push(StructBlock::Table(StructTable { columns, rows }));
+    }
+
+    /// Add content that is only included in some export formats.
+    fn restricted(
+        &mut self,
+        mut targets: Vec<ExportTarget>,
+        body: &Content,
+        styles: StyleChain,
+    ) {
+        if let Some(outer) = ExportOnlyElem::restriction_in(styles) {
+            targets.retain(|target| outer.contains(target));
+        }
+
+        let outer_blocks = std::mem::take(&mut self.blocks);
+        let outer_inlines = std::mem::take(&mut self.inlines);
+        self.content(body, styles);
+        let inlines = std::mem::replace(&mut self.inlines, outer_inlines);
+        let blocks = std::mem::replace(&mut self.blocks, outer_blocks);
+
+        if !blocks.is_empty() {
+            self.finish_par();
+            self.push(StructBlock::Only(targets.clone(), blocks));
+        }
+        if !inlines.is_empty() {
+            self.inlines.push(StructInline::Only(targets, inlines));
+        }
+    }
+
+    /// Build the blocks of nested content.
+    fn blocks(&mut self, content: &Content, styles: StyleChain) -> Vec<StructBlock> {
+        let outer_blocks = std::mem::take(&mut self.blocks);
+        let outer_inlines = std::mem::take(&mut self.inlines);
+        let outer_number = self.next_number.take();
+        self.content(content, styles);
+        self.finish_par();
+        self.inlines = outer_inlines;
+        self.next_number = outer_number;
+        std::mem::replace(&mut self.blocks, outer_blocks)
+    }
+
+    /// Build the inlines of nested content, dropping any blocks it contains.
+    fn inlines(&mut self, content: &Content, styles: StyleChain) -> Vec<StructInline> {
+        let outer_blocks = std::mem::take(&mut self.blocks);
+        let outer_inlines = std::mem::take(&mut self.inlines);
+        let outer_number = self.next_number.take();
+        self.content(content, styles);
+        self.blocks = outer_blocks;
+        self.next_number = outer_number;
+        let mut inlines = std::mem::replace(&mut self.inlines, outer_inlines);
+        trim(&mut inlines);
+        inlines
+    }
+
+    /// Add a finished block.
+    fn push(&mut self, block: StructBlock) {
+        self.next_number = None;
+        self.blocks.push(block);
+    }
+
+    /// Finish the current paragraph, dropping it if it is blank.
+    fn finish_par(&mut self) {
+        let mut inlines = std::mem::take(&mut self.inlines);
+        trim(&mut inlines);
+        if !inlines.is_empty() {
+            self.push(StructBlock::Paragraph(inlines));
+        }
+    }
+}
+
+/// Whether an element is handled by the builder itself instead of being
+/// realized through its built-in show rule.
+fn is_semantic(content: &Content) -> bool {
+    content.is::<TextElem>()
+        || content.is::<RawElem>()
+        || content.is::<StrongElem>()
+        || content.is::<EmphElem>()
+        || content.is::<HeadingElem>()
+        || content.is::<EquationElem>()
+        || content.is::<FootnoteElem>()
+        || content.is::<ListItem>()
+        || content.is::<EnumItem>()
+        || content.is::<TermItem>()
+        || content.is::<ListElem>()
+        || content.is::<EnumElem>()
+        || content.is::<TermsElem>()
+        || content.is::<TableElem>()
+        || content.is::<FigureElem>()
+        || content.is::<ImageElem>()
+        || content.is::<ExportOnlyElem>()
+        || content.is::<ScreenOnlyElem>()
+        || content.is::<BlockElem>()
+        || content.is::<HideElem>()
+        || content.is::<RepeatElem>()
+        || content.is::<AlignElem>()
+        || content.is::<PadElem>()
+        || content.is::<PlaceElem>()
+        || content.is::<ColumnsElem>()
+        || content.is::<StackElem>()
+        || content.is::<GridElem>()
+        || content.is::<MasonryElem>()
+}
+
+/// Remove leading and trailing whitespace from inline content.
+fn trim(inlines: &mut Vec<StructInline>) {
+    while let Some(StructInline::Text(text, _)) = inlines.first_mut() {
+        let trimmed = text.trim_start();
+        if !trimmed.is_empty() {
+            *text = trimmed.into();
+            break;
+        }
+        inlines.remove(0);
+    }
+
+    while let Some(StructInline::Text(text, _)) = inlines.last_mut() {
+        let trimmed = text.trim_end();
+        if !trimmed.is_empty() {
+            *text = trimmed.into();
+            break;
+        }
+        inlines.pop();
+    }
+}
+
+/// Collect the laid-out images of a frame by the span of their element.
+fn collect_images(frame: &Frame, images: &mut HashMap<Span, Vec<StructImage>>) {
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => collect_images(&group.frame, images),
+            FrameItem::Image(image, size, span) => {
+                let image = StructImage { image: image.clone(), size: *size };
+                images.entry(*span).or_default().push(image);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Remove all blocks that are restricted to other export targets.
+fn blocks_for_target(blocks: &[StructBlock], target: ExportTarget) -> Vec<StructBlock> {
+    let mut out = vec![];
+    for block in blocks {
+        out.push(match block {
+            StructBlock::Heading(level, inlines) => {
+                StructBlock::Heading(*level, inlines_for_target(inlines, target))
+            }
+            StructBlock::Paragraph(inlines) => {
+                let mut inlines = inlines_for_target(inlines, target);
+                trim(&mut inlines);
+                if inlines.is_empty() {
+                    continue;
+                }
+                StructBlock::Paragraph(inlines)
+            }
+            StructBlock::ListItem(marker, blocks) => {
+                StructBlock::ListItem(marker.clone(), blocks_for_target(blocks, target))
+            }
+            StructBlock::Table(table) => StructBlock::Table(StructTable {
+                columns: table.columns,
+                rows: table
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|cell| StructCell {
+                                colspan: cell.colspan,
+                                blocks: blocks_for_target(&cell.blocks, target),
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            }),
+            StructBlock::Figure(body, caption) => StructBlock::Figure(
+                blocks_for_target(body, target),
+                caption.as_ref().map(|caption| inlines_for_target(caption, target)),
+            ),
+            StructBlock::Only(targets, blocks) => {
+                if targets.contains(&target) {
+                    out.extend(blocks_for_target(blocks, target));
+                }
+                continue;
+            }
+            block => block.clone(),
+        });
+    }
+    out
+}
+
+/// Remove all inlines that are restricted to other export targets.
+fn inlines_for_target(
+    inlines: &[StructInline],
+    target: ExportTarget,
+) -> Vec<StructInline> {
+    let mut out = vec![];
+    for inline in inlines {
+        match inline {
+            StructInline::Footnote(blocks) => {
+                out.push(StructInline::Footnote(blocks_for_target(blocks, target)));
+            }
+            StructInline::Only(targets, inlines) => {
+                if targets.contains(&target) {
+                    out.extend(inlines_for_target(inlines, target));
+                }
+            }
+            inline => out.push(inline.clone()),
+        }
+    }
+    out
+}
@@ -1,7 +1,7 @@
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
-use ecow::eco_format;
+use ecow::{eco_format, EcoString};
 
 use crate::diag::{bail, HintedStrResult, HintedString, SourceResult, Trace, Tracepoint};
 use crate::engine::Engine;
@@ -10,9 +10,9 @@ use crate::foundations::{
 };
 use crate::layout::{
     show_grid_cell, Abs, Alignment, Axes, BlockElem, Cell, CellGrid, Celled, Dir,
-    Fragment, GridCell, GridFooter, GridHLine, GridHeader, GridLayouter, GridVLine,
-    Length, LinePosition, OuterHAlignment, OuterVAlignment, Regions, Rel, ResolvableCell,
-    ResolvableGridChild, ResolvableGridItem, Sides, TrackSizings,
+    Fragment, GridAreas, GridCell, GridFooter, GridHLine, GridHeader, GridLayouter,
+    GridVLine, Length, LinePosition, OuterHAlignment, OuterVAlignment, Regions, Rel,
+    ResolvableCell, ResolvableGridChild, ResolvableGridItem, Sides, TrackSizings,
 };
 use crate::model::Figurable;
 use crate::syntax::Span;
@@ -152,6 +152,11 @@ pub struct TableElem {
     #[borrowed]
     pub row_gutter: TrackSizings,
 
+    /// Named areas that cells can be placed in. See the
+    /// [grid documentation]($grid.areas) for more information on areas.
+    #[borrowed]
+    pub areas: GridAreas,
+
     /// How to fill the cells.
     ///
     /// This can be a color or a function that returns a color. The function
@@ -261,8 +266,15 @@ impl TableElem {
 }
 
 impl Show for Packed<TableElem> {
-    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
-        Ok(BlockElem::multi_layouter(self.clone(), layout_table).pack())
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let mut realized = BlockElem::multi_layouter(self.clone(), layout_table).pack();
+
+        // Apply the typographic profile's numbers for tables.
+        if let Smart::Custom(numbers) = TextElem::profile_in(styles).table_numbers {
+            realized = realized.styled(TextElem::set_number_type(Smart::Custom(numbers)));
+        }
+
+        Ok(realized)
     }
 }
 
@@ -276,7 +288,8 @@ fn layout_table(
 ) -> SourceResult<Fragment> {
     let inset = elem.inset(styles);
     let align = elem.align(styles);
-    let columns = elem.columns(styles);
+    let areas = elem.areas(styles);
+    let columns = areas.columns_or(elem.columns(styles));
     let rows = elem.rows(styles);
     let column_gutter = elem.column_gutter(styles);
     let row_gutter = elem.row_gutter(styles);
@@ -309,6 +322,7 @@ fn layout_table(
         align,
         &inset,
         &stroke,
+        areas,
         engine,
         styles,
         elem.span(),
@@ -767,6 +781,12 @@ pub struct TableCell {
     #[default(NonZeroUsize::ONE)]
     rowspan: NonZeroUsize,
 
+    /// The named [area]($table.areas) this cell is placed in.
+    ///
+    /// The cell then spans all columns and rows of the area, regardless of its
+    /// `colspan` and `rowspan`. It must not also specify an `x` or `y`.
+    pub area: Option<EcoString>,
+
     /// The cell's [fill]($table.fill) override.
     pub fill: Smart<Option<Paint>>,
 
@@ -804,6 +824,8 @@ impl ResolvableCell for Packed<TableCell> {
         mut self,
         x: usize,
         y: usize,
+        colspan: NonZeroUsize,
+        rowspan: NonZeroUsize,
         fill: &Option<Paint>,
         align: Smart<Alignment>,
         inset: Sides<Option<Rel<Length>>>,
@@ -812,8 +834,6 @@ impl ResolvableCell for Packed<TableCell> {
         styles: StyleChain,
     ) -> Cell {
         let cell = &mut *self;
-        let colspan = cell.colspan(styles);
-        let rowspan = cell.rowspan(styles);
         let breakable = cell.breakable(styles).unwrap_or(breakable);
         let fill = cell.fill(styles).unwrap_or_else(|| fill.clone());
 
@@ -833,6 +853,8 @@ impl ResolvableCell for Packed<TableCell> {
         let stroke = cell_stroke.fold(stroke).map(Option::flatten);
         cell.push_x(Smart::Custom(x));
         cell.push_y(Smart::Custom(y));
+        cell.push_colspan(colspan);
+        cell.push_rowspan(rowspan);
         cell.push_fill(Smart::Custom(fill.clone()));
         cell.push_align(match align {
             Smart::Custom(align) => {
@@ -887,6 +909,10 @@ impl ResolvableCell for Packed<TableCell> {
         (**self).rowspan(styles)
     }
 
+    fn area(&self, styles: StyleChain) -> Option<EcoString> {
+        (**self).area(styles)
+    }
+
     fn span(&self) -> Span {
         Packed::span(self)
     }
@@ -0,0 +1,180 @@
+use ecow::EcoString;
+
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, Args, Construct, Content, Dict, NativeElement, Packed, Repr, Show, Str,
+    StyleChain,
+};
+use crate::introspection::Locatable;
+
+/// A reusable content blueprint with named slots.
+///
+/// A template is defined once with a name and a body in which
+/// [slots]($slot) mark the places that are filled in when the template is
+/// used. The definition itself is invisible. With
+/// [`use-template`]($use-template), the template's body is inserted with its
+/// slots filled by the named arguments.
+///
+/// # Example
+/// ```example
+/// #template("card", block(
+///   stroke: 0.5pt,
+///   inset: 6pt,
+///   [*#slot("title")* \ #slot("body", [_No description._])],
+/// ))
+///
+/// #use-template("card", title: [Typst], body: [A markup-based
+///   typesetting system.])
+/// #use-template("card", title: [LaTeX])
+/// ```
+///
+/// Compared to a function that takes many content arguments, a template
+/// checks that all required slots are filled and that no unknown slots are
+/// passed. Since templates are looked up in the document, they can be used
+/// before they are defined.
+#[elem(Locatable, Show)]
+pub struct TemplateElem {
+    /// The name under which the template can be used.
+    #[required]
+    pub name: Str,
+
+    /// The blueprint with the template's slots.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<TemplateElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// A named place in a [template]($template) that is filled when the template
+/// is used.
+///
+/// A slot without fallback content must be filled by every use of its
+/// template.
+#[elem(Show)]
+pub struct SlotElem {
+    /// The name of the slot.
+    #[required]
+    pub name: Str,
+
+    /// The content to display when the slot isn't filled.
+    #[positional]
+    pub fallback: Option<Content>,
+
+    /// The contents of the slots of the template that is being used.
+    #[internal]
+    #[ghost]
+    fills: Dict,
+}
+
+impl Show for Packed<SlotElem> {
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        if let Ok(value) = SlotElem::fills_in(styles).get(self.name()) {
+            return Ok(value.clone().display());
+        }
+
+        match self.fallback(styles) {
+            Some(fallback) => Ok(fallback),
+            None => bail!(self.span(), "slot {} is not filled", self.name().repr()),
+        }
+    }
+}
+
+/// Inserts a [template]($template) with its slots filled.
+///
+/// Each named argument fills the slot of the same name.
+///
+/// ```example
+/// #template("note", [*Note:* #slot("body")])
+/// #use-template("note", body: [Slots are filled by name.])
+/// ```
+#[elem(Construct, Show)]
+pub struct UseTemplateElem {
+    /// The name of the template to use.
+    #[required]
+    pub name: Str,
+
+    /// The contents of the slots.
+    #[required]
+    #[internal]
+    pub fills: Dict,
+}
+
+impl Construct for UseTemplateElem {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        let name = args.expect::<Str>("name")?;
+        let mut fills = Dict::new();
+        for arg in std::mem::take(&mut args.items) {
+            let Some(key) = arg.name else {
+                bail!(
+                    arg.span, "unexpected argument";
+                    hint: "slots must be filled with named arguments"
+                );
+            };
+            fills.insert(key, arg.value.v);
+        }
+        Ok(Self::new(name, fills).pack().spanned(args.span))
+    }
+}
+
+impl Show for Packed<UseTemplateElem> {
+    #[typst_macros::time(name = "use-template", span = self.span())]
+    fn show(&self, engine: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        let span = self.span();
+        let name = self.name();
+        let Some(template) = engine
+            .introspector
+            .query(&TemplateElem::elem().select())
+            .into_iter()
+            .find_map(|elem| {
+                let template = elem.to_packed::<TemplateElem>()?;
+                (template.name() == name).then(|| template.body().clone())
+            })
+        else {
+            bail!(span, "template {} does not exist in the document", name.repr());
+        };
+
+        let slots = template.query(SlotElem::elem().select());
+        let fills = self.fills();
+
+        // Every required slot must be filled.
+        let mut missing: Vec<EcoString> = vec![];
+        for slot in &slots {
+            let slot = slot.to_packed::<SlotElem>().unwrap();
+            let required = slot.fallback(StyleChain::default()).is_none();
+            if required
+                && !fills.contains(slot.name())
+                && !missing.contains(&slot.name().repr())
+            {
+                missing.push(slot.name().repr());
+            }
+        }
+
+        if let Some(first) = missing.first() {
+            bail!(
+                span, "missing {} {} for template {}",
+                if missing.len() == 1 { "slot" } else { "slots" },
+                missing.join(", "),
+                name.repr();
+                hint: "fill slots with named arguments, e.g. `{}: [..]`",
+                first.trim_matches('"')
+            );
+        }
+
+        // Every filled slot must exist.
+        for key in fills.iter().map(|(key, _)| key) {
+            let exists = slots.iter().any(|slot| {
+                slot.to_packed::<SlotElem>().is_some_and(|slot| slot.name() == key)
+            });
+            if !exists {
+                bail!(span, "template {} has no slot {}", name.repr(), key.repr());
+            }
+        }
+
+        Ok(template.styled(SlotElem::set_fills(fills.clone())))
+    }
+}
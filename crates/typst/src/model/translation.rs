@@ -0,0 +1,140 @@
+use ecow::EcoString;
+use sha2::{Digest, Sha256};
+
+use crate::diag::{warning, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, Content, Dict, NativeElement, Packed, Selector, Show, StyleChain, Synthesize,
+};
+use crate::introspection::{Introspector, Locatable};
+
+/// Marks text for translation.
+///
+/// Translatable segments can be extracted from a document together with an
+/// ID for each segment, for instance with the `--extract-translations` flag
+/// of the command line interface. The extracted file maps IDs to texts and
+/// can be handed to a standard localization pipeline. Once translated, it can
+/// be passed back to the document through the
+/// [`translations`]($translatable.translations) property, which replaces
+/// each segment with its translation.
+///
+/// # Example
+/// ```example
+/// #set translatable(translations: (
+///   greeting: "Hallo Welt!",
+/// ))
+///
+/// #translatable(id: "greeting")[Hello world!] \
+/// #translatable[Not translated yet.]
+/// ```
+#[elem(Locatable, Synthesize, Show)]
+pub struct TranslatableElem {
+    /// The segment's ID.
+    ///
+    /// If `{none}`, the ID is the SHA-256 digest of the segment's text. Such
+    /// IDs stay the same as long as the text does, but change with every edit
+    /// to it. Give segments explicit IDs to keep their translations across
+    /// edits.
+    pub id: Option<EcoString>,
+
+    /// A dictionary from segment IDs to their translations.
+    ///
+    /// Translations can be strings or content. Segments without a
+    /// translation are shown as they are.
+    ///
+    /// ```example
+    /// #set translatable(translations: json.decode(
+    ///   `{"title": "Einleitung"}`.text
+    /// ))
+    /// #translatable(id: "title")[Introduction]
+    /// ```
+    #[borrowed]
+    pub translations: Dict,
+
+    /// The text to translate.
+    #[required]
+    pub body: Content,
+
+    /// The segment's ID, either the explicit one or one derived from its
+    /// text.
+    #[internal]
+    #[synthesized]
+    pub segment: EcoString,
+}
+
+impl Synthesize for Packed<TranslatableElem> {
+    fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
+        let id = self.id(styles).unwrap_or_else(|| text_id(&self.body().plain_text()));
+        self.push_segment(id);
+        Ok(())
+    }
+}
+
+impl Show for Packed<TranslatableElem> {
+    #[typst_macros::time(name = "translatable", span = self.span())]
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let id = self.segment().unwrap();
+
+        // Segments that share an ID are translated the same, so their texts
+        // should match.
+        let first = engine
+            .introspector
+            .query(&TranslatableElem::elem().select())
+            .into_iter()
+            .filter_map(|elem| elem.into_packed::<TranslatableElem>().ok())
+            .find(|elem| elem.segment() == Some(id));
+        if let Some(first) = first {
+            if first.location() != self.location()
+                && first.body().plain_text() != self.body().plain_text()
+            {
+                engine.tracer.warn(warning!(
+                    self.span(),
+                    "segment `{id}` has a different text than its first occurrence";
+                    hint: "give the segments different IDs"
+                ));
+            }
+        }
+
+        Ok(match self.translations(styles).get(id) {
+            Ok(translation) => translation.clone().display().spanned(self.span()),
+            Err(_) => self.body().clone(),
+        })
+    }
+}
+
+/// A translatable segment of a document.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TranslatableSegment {
+    /// The segment's ID.
+    pub id: EcoString,
+    /// The segment's text in the source language.
+    pub text: EcoString,
+}
+
+/// Extract all translatable segments in document order.
+///
+/// Segments that share an ID are listed once, with the text of their first
+/// occurrence.
+pub fn translatable_segments(introspector: &Introspector) -> Vec<TranslatableSegment> {
+    let selector = Selector::Elem(TranslatableElem::elem(), None);
+    let mut segments: Vec<TranslatableSegment> = vec![];
+    for elem in introspector.query(&selector) {
+        let Some(elem) = elem.to_packed::<TranslatableElem>() else { continue };
+        let Some(id) = elem.segment() else { continue };
+        if segments.iter().all(|segment| segment.id != *id) {
+            let text = elem.body().plain_text();
+            segments.push(TranslatableSegment { id: id.clone(), text });
+        }
+    }
+    segments
+}
+
+/// Derive a segment ID from its text: The hexadecimal SHA-256 digest of it.
+fn text_id(text: &str) -> EcoString {
+    let digest = Sha256::digest(text.as_bytes());
+    digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>()
+        .into()
+}
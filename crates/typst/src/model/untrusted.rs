@@ -0,0 +1,55 @@
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{elem, Content, Packed, Show, StyleChain};
+use crate::World;
+
+/// Restricts what third-party content may do.
+///
+/// Platforms that compile user-submitted fragments into a trusted template can
+/// wrap them in `untrusted` to make sure that they stay within their bounds.
+/// Code evaluated within the wrapper may not read files, which also rules out
+/// [including or importing]($scripting/#modules) other files and loading
+/// images, data, and plugins. It may also not call plugins that were loaded
+/// in trusted code. Functions defined within it, such as show rule
+/// transformations and `context` expressions, stay restricted wherever they
+/// run, and `{show: untrusted}` restricts the rest of the enclosing scope.
+/// Content shown within it may not use absolute [placement]($place). Floating
+/// placement remains allowed. Each violation is reported as an error at the
+/// offending call or element.
+///
+/// To restrict a fragment stored in a separate file, read it in trusted code
+/// and evaluate it within the wrapper, as in
+/// `{untrusted(eval(read("review.typ"), mode: "markup"))}`.
+///
+/// # Example
+/// ```example
+/// #untrusted[
+///   *Reviewer 2:* Looks good to me.
+/// ]
+/// ```
+#[elem(Show)]
+pub struct UntrustedElem {
+    /// The content to restrict.
+    #[required]
+    pub body: Content,
+
+    /// This style is set on the content contained in the `untrusted` element.
+    #[internal]
+    #[ghost]
+    pub active: bool,
+}
+
+impl UntrustedElem {
+    /// Whether content with the given styles is untrusted, either because it
+    /// is wrapped in `untrusted` or because the whole document is.
+    pub fn is_active(engine: &Engine, styles: StyleChain) -> bool {
+        engine.world.library().untrusted || Self::active_in(styles)
+    }
+}
+
+impl Show for Packed<UntrustedElem> {
+    #[typst_macros::time(name = "untrusted", span = self.span())]
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(self.body().clone().styled(UntrustedElem::set_active(true)))
+    }
+}
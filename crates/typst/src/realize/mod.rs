@@ -12,14 +12,14 @@ mod process;
 
 pub use self::arenas::Arenas;
 pub use self::behaviour::{Behave, BehavedBuilder, Behaviour, StyleVec};
-pub use self::process::process;
+pub use self::process::{process, process_keeping};
 
 use std::mem;
 
 use crate::diag::{bail, SourceResult};
 use crate::engine::{Engine, Route};
 use crate::foundations::{
-    Content, NativeElement, Packed, SequenceElem, StyleChain, StyledElem, Styles,
+    Content, Element, NativeElement, Packed, SequenceElem, StyleChain, StyledElem, Styles,
 };
 use crate::introspection::TagElem;
 use crate::layout::{
@@ -29,7 +29,7 @@ use crate::layout::{
 use crate::math::{EquationElem, LayoutMath};
 use crate::model::{
     CiteElem, CiteGroup, DocumentElem, EnumElem, EnumItem, ListElem, ListItem, ParElem,
-    ParbreakElem, TermItem, TermsElem,
+    ParbreakElem, TabElem, TermItem, TermsElem,
 };
 use crate::syntax::Span;
 use crate::text::{LinebreakElem, SmartQuoteElem, SpaceElem, TextElem};
@@ -442,6 +442,7 @@ impl<'a> ParBuilder<'a> {
             || content.is::<SmartQuoteElem>()
             || content.is::<InlineElem>()
             || content.is::<BoxElem>()
+            || content.is::<TabElem>()
         {
             self.0.push(content, styles);
             return true;
@@ -484,14 +485,25 @@ impl<'a> ListBuilder<'a> {
             return true;
         }
 
+        // Labelled enumeration items are preceded by their tag. Keep it in the
+        // list so that the tag doesn't break the list apart. Tags emitted by
+        // the enumeration's layout already carry the item's numbers and belong
+        // to the item's body instead.
+        let is_enum_tag = content.to_packed::<TagElem>().is_some_and(|tag| {
+            tag.elem
+                .to_packed::<EnumItem>()
+                .is_some_and(|item| item.numbers().is_none())
+        });
+
         if (content.is::<ListItem>()
             || content.is::<EnumItem>()
-            || content.is::<TermItem>())
+            || content.is::<TermItem>()
+            || is_enum_tag)
             && self
                 .items
                 .items()
                 .next()
-                .map_or(true, |first| first.func() == content.func())
+                .map_or(true, |first| item_func(first) == item_func(content))
         {
             self.items.push(content, styles);
             self.tight &= self.staged.drain(..).all(|(t, _)| !t.is::<ParbreakElem>());
@@ -514,13 +526,32 @@ impl<'a> ListBuilder<'a> {
                 })
                 .collect();
             ListElem::new(children).with_tight(self.tight).pack().spanned(span)
-        } else if first.is::<EnumItem>() {
-            let children = items
-                .map(|(item, local)| {
-                    item.into_packed::<EnumItem>().unwrap().styled(local)
-                })
-                .collect();
-            EnumElem::new(children).with_tight(self.tight).pack().spanned(span)
+        } else if item_func(first) == EnumItem::elem() {
+            // The enumeration emits the tags of its items itself, so that they
+            // contain the items' full numbers. Only tags without a matching
+            // item are kept.
+            let mut tags = vec![];
+            let mut children = vec![];
+            for (item, local) in items {
+                if item.is::<TagElem>() {
+                    tags.push(item.styled_with_map(local));
+                } else {
+                    let item = item.into_packed::<EnumItem>().unwrap();
+                    if let Some(loc) = item.location() {
+                        tags.retain(|tag| {
+                            let tag = tag.to_packed::<TagElem>();
+                            tag.map_or(true, |tag| tag.elem.location() != Some(loc))
+                        });
+                    }
+                    children.push(item.styled(local));
+                }
+            }
+            if !children.is_empty() {
+                let list =
+                    EnumElem::new(children).with_tight(self.tight).pack().spanned(span);
+                tags.push(list);
+            }
+            Content::sequence(tags)
         } else if first.is::<TermItem>() {
             let children = items
                 .map(|(item, local)| {
@@ -535,6 +566,14 @@ impl<'a> ListBuilder<'a> {
     }
 }
 
+/// The element function of a list item or, for a tag, of its element.
+fn item_func(content: &Content) -> Element {
+    match content.to_packed::<TagElem>() {
+        Some(tag) => tag.elem.func(),
+        None => content.func(),
+    }
+}
+
 impl Default for ListBuilder<'_> {
     fn default() -> Self {
         Self {
@@ -1,6 +1,7 @@
 use std::cell::OnceCell;
 
 use comemo::{Track, Tracked};
+use typst_timing::TimingScope;
 
 use crate::diag::SourceResult;
 use crate::engine::Engine;
@@ -37,11 +38,48 @@ pub fn process(
     target: &Content,
     styles: StyleChain,
 ) -> SourceResult<Option<Content>> {
-    let Some(Verdict { prepared, mut map, step }) = verdict(engine, target, styles)
-    else {
+    let Some(verdict) = verdict(engine, target, styles) else {
         return Ok(None);
     };
 
+    apply(engine, target, verdict, styles)
+}
+
+/// Processes the given `target` element like [`process`], but leaves the
+/// built-in show rules of elements for which `keep` returns `true` unapplied.
+///
+/// This realizes user-defined show rules and everything that is computed
+/// during realization while keeping the elements that carry the document's
+/// meaning. Returns `None` if nothing is left to do for the element.
+pub fn process_keeping(
+    engine: &mut Engine,
+    target: &Content,
+    styles: StyleChain,
+    keep: fn(&Content) -> bool,
+) -> SourceResult<Option<Content>> {
+    let Some(mut verdict) = verdict(engine, target, styles) else {
+        return Ok(None);
+    };
+
+    if matches!(verdict.step, Some(ShowStep::Builtin)) && keep(target) {
+        verdict.step = None;
+    }
+
+    if verdict.prepared && verdict.step.is_none() && verdict.map.is_empty() {
+        return Ok(None);
+    }
+
+    apply(engine, target, verdict, styles)
+}
+
+/// Prepares the `target` element if necessary and applies the verdict's
+/// styles and show rule.
+fn apply(
+    engine: &mut Engine,
+    target: &Content,
+    Verdict { prepared, mut map, step }: Verdict,
+    styles: StyleChain,
+) -> SourceResult<Option<Content>> {
     // Create a fresh copy that we can mutate.
     let mut target = target.clone();
 
@@ -239,6 +277,7 @@ fn show(
     match step {
         // Apply a user-defined show rule.
         ShowStep::Recipe(recipe, guard) => {
+            let _scope = TimingScope::with_span("show rule", recipe.span);
             let context = Context::new(target.location(), Some(styles));
             match &recipe.selector {
                 // If the selector is a regex, the `target` is guaranteed to be a
@@ -0,0 +1,306 @@
+use ecow::EcoString;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::diag::SourceResult;
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, scope, Cast, Content, NativeElement, Packed, Show, StyleChain,
+};
+use crate::text::{StrikeElem, TextElem, UnderlineElem};
+use crate::visualize::Color;
+
+/// Compares two texts and shows the changes between them.
+///
+/// Computes the difference between an old and a new version of a text and
+/// shows it as a redline: Text that only occurs in the old version is shown
+/// as a [deletion]($compare.deletion) and text that only occurs in the new
+/// version as an [insertion]($compare.insertion). The look of both can be
+/// customized with show rules.
+///
+/// # Example
+/// ```example
+/// #compare(
+///   "The tenant shall pay the rent monthly.",
+///   "The tenant shall pay the full rent quarterly.",
+/// )
+/// ```
+#[elem(scope, Show)]
+pub struct CompareElem {
+    /// The old version of the text.
+    #[required]
+    pub before: EcoString,
+
+    /// The new version of the text.
+    #[required]
+    pub after: EcoString,
+
+    /// The units in which the texts are compared.
+    ///
+    /// ```example
+    /// #compare(
+    ///   granularity: "sentence",
+    ///   "Rent is due. Late fees apply.",
+    ///   "Rent is due. Late fees may apply.",
+    /// )
+    /// ```
+    #[default(CompareGranularity::Word)]
+    pub granularity: CompareGranularity,
+}
+
+#[scope]
+impl CompareElem {
+    #[elem]
+    type CompareInsertion;
+
+    #[elem]
+    type CompareDeletion;
+}
+
+impl Show for Packed<CompareElem> {
+    #[typst_macros::time(name = "compare", span = self.span())]
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let span = self.span();
+        let granularity = self.granularity(styles);
+        let old = granularity.split(self.before());
+        let new = granularity.split(self.after());
+
+        // Merge consecutive tokens of the same kind into runs.
+        let mut runs: Vec<(Change, EcoString)> = vec![];
+        for (change, token) in diff(&old, &new) {
+            match runs.last_mut() {
+                Some((prev, run)) if *prev == change => run.push_str(token),
+                _ => runs.push((change, token.into())),
+            }
+        }
+
+        Ok(Content::sequence(runs.into_iter().map(|(change, run)| {
+            let text = TextElem::packed(run).spanned(span);
+            match change {
+                Change::Equal => text,
+                Change::Delete => CompareDeletion::new(text).pack().spanned(span),
+                Change::Insert => CompareInsertion::new(text).pack().spanned(span),
+            }
+        })))
+    }
+}
+
+/// The units in which texts are compared.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum CompareGranularity {
+    /// Compares word by word.
+    #[default]
+    Word,
+    /// Compares whole sentences.
+    Sentence,
+}
+
+impl CompareGranularity {
+    /// Split a text into tokens of this granularity.
+    fn split(self, text: &str) -> Vec<&str> {
+        match self {
+            Self::Word => text.split_word_bounds().collect(),
+            Self::Sentence => text.split_sentence_bounds().collect(),
+        }
+    }
+}
+
+/// Text that was inserted into the new version of a compared text.
+///
+/// This function is not intended to be called directly. Instead, it is used
+/// in show rules to customize how insertions look.
+///
+/// ```example
+/// #show compare.insertion: set text(green)
+/// #compare("A red fox.", "A quick red fox.")
+/// ```
+#[elem(name = "insertion", Show)]
+pub struct CompareInsertion {
+    /// The inserted text.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<CompareInsertion> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(UnderlineElem::new(self.body().clone())
+            .pack()
+            .spanned(self.span())
+            .styled(TextElem::set_fill(Some(Color::BLUE.into()))))
+    }
+}
+
+/// Text that was deleted from the old version of a compared text.
+///
+/// This function is not intended to be called directly. Instead, it is used
+/// in show rules to customize how deletions look.
+///
+/// ```example
+/// #show compare.deletion: none
+/// #compare("A red fox.", "A quick fox.")
+/// ```
+#[elem(name = "deletion", Show)]
+pub struct CompareDeletion {
+    /// The deleted text.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<CompareDeletion> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(StrikeElem::new(self.body().clone())
+            .pack()
+            .spanned(self.span())
+            .styled(TextElem::set_fill(Some(Color::RED.into()))))
+    }
+}
+
+/// How a token changed between two versions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Change {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Compute a shortest edit script between two token lists with Myers'
+/// algorithm. Deletions are ordered before insertions at the same position.
+fn diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Change, &'a str)> {
+    let mut edits = vec![];
+    myers(old, new, &mut edits);
+    for group in edits.split_mut(|(change, _)| *change == Change::Equal) {
+        group.sort_by_key(|(change, _)| *change == Change::Insert);
+    }
+    edits
+}
+
+/// The linear-space variant of Myers' algorithm: Splits the texts at the
+/// middle of a shortest edit script and recurses into both halves.
+fn myers<'a>(old: &[&'a str], new: &[&'a str], edits: &mut Vec<(Change, &'a str)>) {
+    // Strip the common prefix and suffix, which are usually most of a text.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    edits.extend(old[..prefix].iter().map(|&token| (Change::Equal, token)));
+
+    let inner_old = &old[prefix..old.len() - suffix];
+    let inner_new = &new[prefix..new.len() - suffix];
+    match middle(inner_old, inner_new) {
+        Some((x, y)) => {
+            myers(&inner_old[..x], &inner_new[..y], edits);
+            myers(&inner_old[x..], &inner_new[y..], edits);
+        }
+        None => {
+            edits.extend(inner_old.iter().map(|&token| (Change::Delete, token)));
+            edits.extend(inner_new.iter().map(|&token| (Change::Insert, token)));
+        }
+    }
+
+    edits.extend(old[old.len() - suffix..].iter().map(|&token| (Change::Equal, token)));
+}
+
+/// Find a point on a shortest edit script between two token lists, which
+/// start and end with different tokens, by searching from both ends until
+/// the paths meet. Only the furthest reaching paths of the current step are
+/// kept.
+///
+/// Returns `None` if the lists have nothing in common.
+fn middle(old: &[&str], new: &[&str]) -> Option<(usize, usize)> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    if n == 0 || m == 0 {
+        return None;
+    }
+
+    let max = (n + m + 1) / 2;
+    let offset = max;
+    let len = 2 * max as usize + 2;
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+
+    // The furthest reaching x on each diagonal, from the start and from the
+    // end respectively. Diagonals that weren't reached yet are at -1.
+    let mut forward = vec![-1; len];
+    let mut backward = vec![-1; len];
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+
+    // Diagonals that left the edit graph don't need to be searched further.
+    let (mut fstart, mut fend, mut bstart, mut bend) = (0, 0, 0, 0);
+    let at = |v: &[isize], k: isize| v[(offset + k) as usize];
+
+    for d in 0..max {
+        for k in (-d + fstart..=d - fend).step_by(2) {
+            let mut x =
+                if k == -d || (k != d && at(&forward, k - 1) < at(&forward, k + 1)) {
+                    at(&forward, k + 1)
+                } else {
+                    at(&forward, k - 1) + 1
+                };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[(offset + k) as usize] = x;
+
+            if x > n {
+                fend += 2;
+            } else if y > m {
+                fstart += 2;
+            } else if odd {
+                let i = offset + delta - k;
+                if (0..len as isize).contains(&i) && backward[i as usize] != -1 {
+                    // The paths overlap if they cover the same diagonal.
+                    if x >= n - backward[i as usize] {
+                        return split(x, y, n, m);
+                    }
+                }
+            }
+        }
+
+        for k in (-d + bstart..=d - bend).step_by(2) {
+            let mut x =
+                if k == -d || (k != d && at(&backward, k - 1) < at(&backward, k + 1)) {
+                    at(&backward, k + 1)
+                } else {
+                    at(&backward, k - 1) + 1
+                };
+            let mut y = x - k;
+            while x < n && y < m && old[(n - x - 1) as usize] == new[(m - y - 1) as usize]
+            {
+                x += 1;
+                y += 1;
+            }
+            backward[(offset + k) as usize] = x;
+
+            if x > n {
+                bend += 2;
+            } else if y > m {
+                bstart += 2;
+            } else if !odd {
+                let i = offset + delta - k;
+                if (0..len as isize).contains(&i) && forward[i as usize] != -1 {
+                    let fx = forward[i as usize];
+                    let fy = fx - (delta - k);
+                    if fx >= n - x {
+                        return split(fx, fy, n, m);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Turn an overlap of the forward and backward paths into a split point,
+/// rejecting points that wouldn't make the problem smaller.
+fn split(x: isize, y: isize, n: isize, m: isize) -> Option<(usize, usize)> {
+    let trivial = (x == 0 && y == 0) || (x == n && y == m);
+    (!trivial).then_some((x as usize, y as usize))
+}
@@ -417,6 +417,7 @@ pub(crate) fn decorate(
     pos: Point,
 ) {
     let font_metrics = text.font.metrics();
+    let span = text.glyphs.first().map_or(Span::detached(), |glyph| glyph.span.0);
 
     if let DecoLine::Highlight { fill, stroke, top_edge, bottom_edge, radius } =
         &deco.line
@@ -426,9 +427,7 @@ pub(crate) fn decorate(
         let rects = styled_rect(size, radius, fill.clone(), stroke);
         let origin = Point::new(pos.x - deco.extent, pos.y - top - shift);
         frame.prepend_multiple(
-            rects
-                .into_iter()
-                .map(|shape| (origin, FrameItem::Shape(shape, Span::detached()))),
+            rects.into_iter().map(|shape| (origin, FrameItem::Shape(shape, span))),
         );
         return;
     }
@@ -448,7 +447,7 @@ pub(crate) fn decorate(
 
     let offset = offset.unwrap_or(-metrics.position.at(text.size)) - shift;
     let stroke = stroke.clone().unwrap_or(FixedStroke::from_pair(
-        text.fill.as_decoration(),
+        text.deco_paint(),
         metrics.thickness.at(text.size),
     ));
 
@@ -466,9 +465,9 @@ pub(crate) fn decorate(
             let shape = Geometry::Line(target).stroked(stroke.clone());
 
             if prepend {
-                frame.prepend(origin, FrameItem::Shape(shape, Span::detached()));
+                frame.prepend(origin, FrameItem::Shape(shape, span));
             } else {
-                frame.push(origin, FrameItem::Shape(shape, Span::detached()));
+                frame.push(origin, FrameItem::Shape(shape, span));
             }
         }
     };
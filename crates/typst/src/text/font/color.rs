@@ -113,7 +113,7 @@ impl<'f, 't> ttf_parser::colr::Painter<'_> for ColrPainter<'f, 't> {
             FrameItem::Text(TextItem {
                 font: self.font.clone(),
                 size: Abs::pt(self.font.units_per_em()),
-                fill: color.into(),
+                fill: Some(color.into()),
                 stroke: None,
                 lang: Lang::ENGLISH,
                 region: None,
@@ -6,7 +6,7 @@ use ecow::EcoString;
 use crate::layout::{Abs, Em};
 use crate::syntax::Span;
 use crate::text::{Font, Lang, Region};
-use crate::visualize::{FixedStroke, Paint};
+use crate::visualize::{Color, FixedStroke, Paint};
 
 /// A run of shaped text.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -15,8 +15,8 @@ pub struct TextItem {
     pub font: Font,
     /// The font size.
     pub size: Abs,
-    /// Glyph color.
-    pub fill: Paint,
+    /// Glyph color, if the glyphs are filled.
+    pub fill: Option<Paint>,
     /// Glyph stroke.
     pub stroke: Option<FixedStroke>,
     /// The natural language of the text.
@@ -35,6 +35,16 @@ impl TextItem {
     pub fn width(&self) -> Abs {
         self.glyphs.iter().map(|g| g.x_advance).sum::<Em>().at(self.size)
     }
+
+    /// The paint for lines drawn along with the text, like underlines. For
+    /// hollow text, this is the stroke's paint.
+    pub fn deco_paint(&self) -> Paint {
+        self.fill
+            .clone()
+            .or_else(|| self.stroke.as_ref().map(|stroke| stroke.paint.clone()))
+            .unwrap_or(Color::BLACK.into())
+            .as_decoration()
+    }
 }
 
 impl Debug for TextItem {
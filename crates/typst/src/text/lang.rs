@@ -1,10 +1,11 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::diag::Hint;
 use ecow::{eco_format, EcoString};
+use indexmap::IndexMap;
 
-use crate::foundations::{cast, StyleChain};
+use crate::diag::{bail, Hint};
+use crate::foundations::{cast, Dict, Fold, StyleChain};
 use crate::layout::Dir;
 use crate::text::TextElem;
 
@@ -227,12 +228,98 @@ pub trait LocalName {
         localized_str(lang, region, Self::KEY)
     }
 
-    /// Gets the local name from the style chain.
-    fn local_name_in(styles: StyleChain) -> &'static str
+    /// Gets the local name from the style chain, taking user-provided
+    /// [translations](TextElem::translations_in) into account.
+    fn local_name_in(styles: StyleChain) -> EcoString
     where
         Self: Sized,
     {
-        Self::local_name(TextElem::lang_in(styles), TextElem::region_in(styles))
+        localized_str_in(styles, Self::KEY)
+    }
+}
+
+/// User-provided translations of generated strings, organized by language
+/// and region.
+///
+/// Later entries take precedence over earlier ones.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct Translations(pub Vec<(Lang, Option<Region>, EcoString, EcoString)>);
+
+impl Translations {
+    /// Get the translation of a key for the given language and region.
+    ///
+    /// Falls back from the region-specific to the language-wide translation.
+    pub fn get(&self, lang: Lang, region: Option<Region>, key: &str) -> Option<&str> {
+        let find = |region: Option<Region>| {
+            self.0
+                .iter()
+                .rev()
+                .find(|(l, r, k, _)| *l == lang && *r == region && k == key)
+                .map(|(.., v)| v.as_str())
+        };
+        region.and_then(|region| find(Some(region))).or_else(|| find(None))
+    }
+}
+
+cast! {
+    Translations,
+    self => {
+        let mut packs: IndexMap<EcoString, Dict> = IndexMap::new();
+        for (lang, region, key, value) in self.0 {
+            packs
+                .entry(lang_str(lang, region))
+                .or_default()
+                .insert(key.into(), value.into_value());
+        }
+        packs
+            .into_iter()
+            .map(|(code, entries)| (code.into(), entries.into_value()))
+            .collect::<Dict>()
+            .into_value()
+    },
+    values: Dict => {
+        let mut translations = vec![];
+        for (code, entries) in values {
+            let (lang, region): (Lang, Option<Region>) = match code.split_once('-') {
+                Some((lang, region)) => (lang.parse()?, Some(region.parse()?)),
+                None => (code.parse()?, None),
+            };
+            for (key, value) in entries.cast::<Dict>()? {
+                if !is_translation_key(key.as_str()) {
+                    bail!("unknown translation key `{}`", key.as_str());
+                }
+                let value = value.cast::<EcoString>()?;
+                if value.is_empty() {
+                    bail!("translation for `{}` must not be empty", key.as_str());
+                }
+                translations.push((lang, region, key.into(), value));
+            }
+        }
+        Self(translations)
+    },
+}
+
+impl Fold for Translations {
+    fn fold(self, outer: Self) -> Self {
+        Self(self.0.fold(outer.0))
+    }
+}
+
+/// Whether a key can be translated, that is, whether it has a built-in English
+/// translation or is a default [datetime](crate::foundations::Datetime) format.
+fn is_translation_key(key: &str) -> bool {
+    matches!(key, "date" | "time" | "datetime")
+        || parse_language_bundle(Lang::ENGLISH, None).unwrap().contains_key(key)
+}
+
+/// Retrieves the localized string for a key from the style chain.
+///
+/// Prefers user-provided translations and falls back to the built-in ones.
+pub fn localized_str_in(styles: StyleChain, key: &str) -> EcoString {
+    let (lang, region) = (TextElem::lang_in(styles), TextElem::region_in(styles));
+    match TextElem::translations_in(styles).get(lang, region, key) {
+        Some(custom) => custom.into(),
+        None => localized_str(lang, region, key).into(),
     }
 }
 
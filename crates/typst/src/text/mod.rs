@@ -1,6 +1,7 @@
 //! Text handling.
 
 mod case;
+mod compare;
 mod deco;
 mod font;
 mod item;
@@ -14,8 +15,10 @@ mod shift;
 mod smallcaps_;
 mod smartquote;
 mod space;
+pub mod transcribe;
 
 pub use self::case::*;
+pub use self::compare::*;
 pub use self::deco::*;
 pub use self::font::*;
 pub use self::item::*;
@@ -38,11 +41,11 @@ use ttf_parser::{Rect, Tag};
 use crate::diag::{bail, warning, HintedStrResult, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, category, dict, elem, Args, Array, Cast, Category, Construct, Content, Dict,
-    Fold, NativeElement, Never, Packed, PlainText, Repr, Resolve, Scope, Set, Smart,
-    StyleChain,
+    cast, category, dict, elem, func, scope, Args, Array, Cast, Category, Construct,
+    Content, Dict, Fold, Func, NativeElement, Never, NoneValue, Packed, PlainText, Regex,
+    Repr, Resolve, Scope, Set, Smart, StyleChain, Value,
 };
-use crate::layout::{Abs, Axis, Dir, Em, Length, Ratio, Rel};
+use crate::layout::{Abs, Angle, Axis, Dir, Em, Length, Ratio, Rel};
 use crate::model::ParElem;
 use crate::syntax::Spanned;
 use crate::visualize::{Color, Paint, RelativeTo, Stroke};
@@ -66,11 +69,13 @@ pub(super) fn define(global: &mut Scope) {
     global.define_elem::<OverlineElem>();
     global.define_elem::<StrikeElem>();
     global.define_elem::<HighlightElem>();
+    global.define_elem::<CompareElem>();
     global.define_elem::<SmallcapsElem>();
     global.define_elem::<RawElem>();
     global.define_func::<lower>();
     global.define_func::<upper>();
     global.define_func::<lorem>();
+    global.define_module(transcribe::module());
 }
 
 /// Customizes the look and layout of text in a variety of ways.
@@ -88,7 +93,7 @@ pub(super) fn define(global: &mut Scope) {
 ///   With a function call.
 /// ])
 /// ```
-#[elem(Debug, Construct, PlainText, Repr)]
+#[elem(scope, Debug, Construct, PlainText, Repr)]
 pub struct TextElem {
     /// A font family name or priority list of font family names.
     ///
@@ -244,26 +249,44 @@ pub struct TextElem {
 
     /// The glyph fill paint.
     ///
+    /// If `{none}`, the glyphs aren't filled. Together with a
+    /// [`stroke`]($text.stroke), this produces outlined, hollow text.
+    ///
     /// ```example
     /// #set text(fill: red)
     /// This text is red.
+    ///
+    /// #text(fill: none, stroke: 0.5pt + black)[Hollow]
+    /// ```
+    ///
+    /// Gradients and patterns that are relative to `{"self"}` span all text
+    /// with the same fill in a paragraph, even across line breaks.
+    ///
+    /// ```example
+    /// #set text(fill: gradient.linear(
+    ///   red, blue, relative: "self",
+    /// ))
+    /// #lorem(15)
     /// ```
     #[parse({
-        let paint: Option<Spanned<Paint>> = args.named_or_find("fill")?;
-        if let Some(paint) = &paint {
-            if paint.v.relative() == Smart::Custom(RelativeTo::Self_) {
+        let paint: Option<Spanned<Option<Paint>>> = match args.named("fill")? {
+            Some(paint) => Some(paint),
+            None => args.find::<Spanned<Paint>>()?.map(|paint| paint.map(Some)),
+        };
+        if let Some(Spanned { v: Some(paint), span }) = &paint {
+            if paint.relative() == Smart::Custom(RelativeTo::Stroke) {
                 bail!(
-                    paint.span,
-                    "gradients and patterns on text must be relative to the parent";
+                    *span,
+                    "gradients and patterns on text cannot be relative to the stroke";
                     hint: "make sure to set `relative: auto` on your text fill"
                 );
             }
         }
         paint.map(|paint| paint.v)
     })]
-    #[default(Color::BLACK.into())]
+    #[default(Some(Color::BLACK.into()))]
     #[ghost]
-    pub fill: Paint,
+    pub fill: Option<Paint>,
 
     /// How to stroke the text.
     ///
@@ -284,6 +307,30 @@ pub struct TextElem {
     #[ghost]
     pub tracking: Length,
 
+    /// Additional tracking for runs of capital letters.
+    ///
+    /// Acronyms and other runs of two or more capital letters look cramped
+    /// when set with the tracking of lowercase text. When this is set to a
+    /// length, that amount of space is added between the letters of such runs
+    /// in addition to the regular [`tracking`]($text.tracking).
+    ///
+    /// For finer control, a dictionary with the following keys can be given:
+    /// - `tracking`: The additional tracking.
+    /// - `size`: The size of the capitals relative to the text size. Defaults
+    ///   to `{100%}`.
+    /// - `pattern`: A [regular expression]($regex) that finds the text to
+    ///   track instead of runs of capital letters.
+    ///
+    /// ```example
+    /// #set text(track-caps: 0.05em)
+    /// The NASA and ESA missions.
+    ///
+    /// #set text(track-caps: (tracking: 0.05em, size: 90%))
+    /// The NASA and ESA missions.
+    /// ```
+    #[ghost]
+    pub track_caps: Option<TrackCaps>,
+
     /// The amount of space between words.
     ///
     /// Can be given as an absolute length, but also relative to the width of
@@ -404,6 +451,36 @@ pub struct TextElem {
     #[ghost]
     pub region: Option<Region>,
 
+    /// Custom translations for generated strings, such as the supplements of
+    /// figures or the title of the outline.
+    ///
+    /// Expects a dictionary that maps language codes (optionally with a region,
+    /// like `{"pt-BR"}`) to dictionaries of translated strings. These take
+    /// precedence over the built-in translations and can also provide strings
+    /// for languages that Typst does not know. Missing strings fall back to
+    /// the built-in ones and, ultimately, to English.
+    ///
+    /// The available keys are `figure`, `table`, `equation`, `bibliography`,
+    /// `heading`, `outline`, `raw`, `endnotes`, `index`, `see`, `see-also`,
+    /// `page`, `pages`, and `page-range`. The last three are the prefixes of
+    /// references to a single page and to several pages and the dash between
+    /// the first and last page of a range. Additionally, `date`, `time`, and
+    /// `datetime` define the default [format]($datetime/#format) of
+    /// [`datetime.display`]($datetime.display) within [context]. Other keys
+    /// are an error.
+    ///
+    /// ```example
+    /// #set text(lang: "eo", translations: (
+    ///   eo: (figure: "Figuro", outline: "Enhavo"),
+    /// ))
+    ///
+    /// #outline()
+    /// #figure(rect(), caption: [Kvadrato])
+    /// ```
+    #[fold]
+    #[ghost]
+    pub translations: Translations,
+
     /// The OpenType writing script.
     ///
     /// The combination of `{lang}` and `{script}` determine how font features,
@@ -680,6 +757,49 @@ pub struct TextElem {
     #[ghost]
     pub features: FontFeatures,
 
+    /// Default OpenType features for specific font families.
+    ///
+    /// Maps font family names to features, which are given like for the
+    /// [`features`]($text.features) parameter. The features apply to all text
+    /// set in a font of the family, unless other text properties configure
+    /// the same features. This way, a font's preferred features must only be
+    /// declared once.
+    ///
+    /// ```example
+    /// #set text(family-features: (
+    ///   "linux libertine": ("onum",),
+    /// ))
+    /// Founded in 1887.
+    /// ```
+    #[fold]
+    #[ghost]
+    pub family_features: FamilyFeatures,
+
+    /// A typographic profile that configures features across the document.
+    ///
+    /// A profile is a dictionary with the following optional keys:
+    /// - `numbers`: The [number type]($text.number-type) of running text,
+    ///   which applies where the number type is `{auto}`.
+    /// - `table-numbers`: The number type in [tables]($table).
+    /// - `acronyms`: Whether runs of two or more capital letters are set in
+    ///   true small capitals. This enables the OpenType `c2sc` font feature
+    ///   for them.
+    ///
+    /// The profile `{"book"}` is a shorthand for old-style numbers in running
+    /// text, lining numbers in tables, and small capitals for acronyms.
+    ///
+    /// ```example
+    /// #set text(profile: "book")
+    /// The NASA budget of 1969:
+    ///
+    /// #table(
+    ///   columns: 2,
+    ///   [Year], [1969],
+    /// )
+    /// ```
+    #[ghost]
+    pub profile: TypographicProfile,
+
     /// Content in which all text is styled according to the other arguments.
     #[external]
     #[required]
@@ -724,6 +844,12 @@ pub struct TextElem {
     #[default(false)]
     #[ghost]
     pub smallcaps: bool,
+
+    /// A function that styles each glyph, set by
+    /// [`text.glyphs`]($text.glyphs).
+    #[internal]
+    #[ghost]
+    pub glyph_styler: Option<Func>,
 }
 
 impl TextElem {
@@ -733,6 +859,46 @@ impl TextElem {
     }
 }
 
+#[scope]
+impl TextElem {
+    /// Styles each glyph of a text individually.
+    ///
+    /// The function is called for each grapheme cluster of the text, that is
+    /// each user-perceived character, with the cluster's index and its text.
+    /// It can return `{none}` to leave the cluster as it is or a dictionary
+    /// with any of the following keys:
+    ///
+    /// - `fill`: The paint to fill the cluster's glyphs with.
+    /// - `dx`: The horizontal offset of the glyphs.
+    /// - `dy`: The vertical offset of the glyphs. Positive values move them
+    ///   down.
+    /// - `rotate`: The angle to rotate the glyphs by around the middle of
+    ///   their baseline.
+    ///
+    /// The text is shaped as a whole, so kerning and ligatures are kept and
+    /// the glyphs stay in their usual places unless they are offset. Styling
+    /// doesn't affect the layout of surrounding content.
+    ///
+    /// ```example
+    /// #text.glyphs(
+    ///   (i, c) => (
+    ///     fill: color.map.rainbow.at(i * 20),
+    ///     dy: calc.sin(i * 0.8) * 3pt,
+    ///     rotate: calc.sin(i * 0.8) * 10deg,
+    ///   ),
+    /// )[Kinetic typography]
+    /// ```
+    #[func]
+    pub fn glyphs(
+        /// The function to call for each grapheme cluster.
+        styler: Func,
+        /// The text to style.
+        body: Content,
+    ) -> Content {
+        body.styled(TextElem::set_glyph_styler(Some(styler)))
+    }
+}
+
 impl Debug for TextElem {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Text({})", self.text)
@@ -762,6 +928,33 @@ impl PlainText for Packed<TextElem> {
     }
 }
 
+/// How to style the glyphs of a grapheme cluster, as returned by the
+/// function passed to [`text.glyphs`]($text.glyphs).
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct GlyphStyle {
+    /// The paint to fill the glyphs with.
+    pub fill: Option<Paint>,
+    /// The horizontal offset of the glyphs.
+    pub dx: Length,
+    /// The vertical offset of the glyphs.
+    pub dy: Length,
+    /// The angle to rotate the glyphs by.
+    pub rotate: Angle,
+}
+
+cast! {
+    GlyphStyle,
+    mut dict: Dict => {
+        let mut take = |key| dict.take(key).ok();
+        let fill = take("fill").map(Value::cast).transpose()?;
+        let dx = take("dx").map(Value::cast).transpose()?.unwrap_or_default();
+        let dy = take("dy").map(Value::cast).transpose()?.unwrap_or_default();
+        let rotate = take("rotate").map(Value::cast).transpose()?.unwrap_or_default();
+        dict.finish(&["fill", "dx", "dy", "rotate"])?;
+        Self { fill, dx, dy, rotate }
+    },
+}
+
 /// A lowercased font family like "arial".
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct FontFamily(EcoString);
@@ -831,6 +1024,15 @@ pub(crate) fn families(styles: StyleChain) -> impl Iterator<Item = &str> + Clone
         .chain(tail.iter().copied())
 }
 
+/// Resolve the paint for lines drawn along with text, like fraction lines in
+/// math. For hollow text, this is the stroke's paint.
+pub(crate) fn deco_paint(styles: StyleChain) -> Paint {
+    TextElem::fill_in(styles)
+        .or_else(|| TextElem::stroke_in(styles).and_then(|stroke| stroke.paint.custom()))
+        .unwrap_or(Color::BLACK.into())
+        .as_decoration()
+}
+
 /// Resolve the font variant.
 pub(crate) fn variant(styles: StyleChain) -> FontVariant {
     let mut variant = FontVariant::new(
@@ -1147,6 +1349,152 @@ impl Fold for FontFeatures {
     }
 }
 
+/// Default OpenType features for font families.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct FamilyFeatures(pub Vec<(FontFamily, FontFeatures)>);
+
+impl FamilyFeatures {
+    /// The features for a font family. Later features take precedence.
+    pub fn get<'a>(
+        &'a self,
+        family: &'a FontFamily,
+    ) -> impl Iterator<Item = (Tag, u32)> + 'a {
+        self.0
+            .iter()
+            .filter(move |(f, _)| f == family)
+            .flat_map(|(_, features)| features.0.iter().copied())
+    }
+}
+
+cast! {
+    FamilyFeatures,
+    self => self.0
+        .into_iter()
+        .map(|(family, features)| (family.as_str().into(), features.into_value()))
+        .collect::<Dict>()
+        .into_value(),
+    values: Dict => Self(values
+        .into_iter()
+        .map(|(k, v)| Ok((FontFamily::new(&k), v.cast()?)))
+        .collect::<HintedStrResult<_>>()?),
+}
+
+impl Fold for FamilyFeatures {
+    fn fold(self, outer: Self) -> Self {
+        Self(self.0.fold(outer.0))
+    }
+}
+
+/// A typographic profile.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TypographicProfile {
+    /// The number type of running text.
+    pub numbers: Smart<NumberType>,
+    /// The number type in tables.
+    pub table_numbers: Smart<NumberType>,
+    /// Whether acronyms are set in small capitals.
+    pub acronyms: bool,
+}
+
+impl TypographicProfile {
+    /// Old-style numbers in running text, lining numbers in tables, and small
+    /// capitals for acronyms.
+    pub const BOOK: Self = Self {
+        numbers: Smart::Custom(NumberType::OldStyle),
+        table_numbers: Smart::Custom(NumberType::Lining),
+        acronyms: true,
+    };
+}
+
+cast! {
+    TypographicProfile,
+    self => dict! {
+        "numbers" => self.numbers,
+        "table-numbers" => self.table_numbers,
+        "acronyms" => self.acronyms,
+    }.into_value(),
+    _: NoneValue => Self::default(),
+    "book" => Self::BOOK,
+    mut dict: Dict => {
+        let numbers = dict.take("numbers").ok().map(Value::cast).transpose()?;
+        let table_numbers = dict.take("table-numbers").ok().map(Value::cast).transpose()?;
+        let acronyms = dict.take("acronyms").ok().map(Value::cast).transpose()?;
+        dict.finish(&["numbers", "table-numbers", "acronyms"])?;
+        Self {
+            numbers: numbers.unwrap_or_default(),
+            table_numbers: table_numbers.unwrap_or_default(),
+            acronyms: acronyms.unwrap_or_default(),
+        }
+    },
+}
+
+/// Tracking and size reduction for runs of capital letters.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TrackCaps {
+    /// The additional tracking.
+    pub tracking: Length,
+    /// The size of the capitals relative to the text size.
+    pub size: Ratio,
+    /// The pattern that finds the text to track, if not runs of capitals.
+    pub pattern: Option<Regex>,
+}
+
+impl TrackCaps {
+    /// Find the byte ranges of the text to track.
+    pub(crate) fn find(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+        match &self.pattern {
+            Some(regex) => regex.find_iter(text).map(|m| m.range()).collect(),
+            None => caps_runs(text),
+        }
+    }
+}
+
+cast! {
+    TrackCaps,
+    self => dict! {
+        "tracking" => self.tracking,
+        "size" => self.size,
+        "pattern" => self.pattern,
+    }.into_value(),
+    tracking: Length => Self { tracking, size: Ratio::one(), pattern: None },
+    mut dict: Dict => {
+        let tracking = dict.take("tracking").ok().map(Value::cast).transpose()?;
+        let size: Option<Ratio> = dict.take("size").ok().map(Value::cast).transpose()?;
+        let pattern = dict.take("pattern").ok().map(Value::cast).transpose()?;
+        dict.finish(&["tracking", "size", "pattern"])?;
+        if size.is_some_and(|size| size.get() <= 0.0) {
+            bail!("size must be positive");
+        }
+        Self {
+            tracking: tracking.unwrap_or_default(),
+            size: size.unwrap_or(Ratio::one()),
+            pattern: pattern.flatten(),
+        }
+    },
+}
+
+/// Find the byte ranges of runs of two or more capital letters.
+pub(crate) fn caps_runs(text: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = None;
+    let mut count = 0;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        if c.is_uppercase() {
+            start.get_or_insert(i);
+            count += 1;
+            continue;
+        }
+
+        if let Some(start) = start.take() {
+            if count >= 2 {
+                ranges.push(start..i);
+            }
+        }
+        count = 0;
+    }
+    ranges
+}
+
 /// Collect the OpenType features to apply.
 pub(crate) fn features(styles: StyleChain) -> Vec<Feature> {
     let mut tags = vec![];
@@ -1187,7 +1535,8 @@ pub(crate) fn features(styles: StyleChain) -> Vec<Feature> {
         feat(b"hilg", 1);
     }
 
-    match TextElem::number_type_in(styles) {
+    let profile = TextElem::profile_in(styles);
+    match TextElem::number_type_in(styles).or(profile.numbers) {
         Smart::Auto => {}
         Smart::Custom(NumberType::Lining) => feat(b"lnum", 1),
         Smart::Custom(NumberType::OldStyle) => feat(b"onum", 1),
@@ -2,6 +2,7 @@ use std::hash::Hash;
 use std::ops::Range;
 use std::sync::Arc;
 
+use comemo::Track;
 use ecow::{eco_format, EcoString, EcoVec};
 use once_cell::sync::Lazy;
 use once_cell::unsync::Lazy as UnsyncLazy;
@@ -10,21 +11,22 @@ use syntect::parsing::{SyntaxDefinition, SyntaxSet, SyntaxSetBuilder};
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::Lang;
-use crate::diag::{At, FileError, HintedStrResult, SourceResult, StrResult};
+use crate::diag::{bail, At, FileError, HintedStrResult, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, scope, Args, Array, Bytes, Content, Fold, NativeElement, Packed,
-    PlainText, Show, ShowSet, Smart, StyleChain, Styles, Synthesize, Value,
+    cast, elem, scope, Args, Array, Bytes, Content, Context, Dict, Fold, Func, IntoValue,
+    NativeElement, Packed, PlainText, Show, ShowSet, Smart, StyleChain, Styles,
+    Synthesize, Value,
 };
 use crate::layout::{BlockChild, BlockElem, Em, HAlignment};
 use crate::model::{Figurable, ParElem};
+use crate::syntax;
 use crate::syntax::{split_newlines, LinkedNode, Span, Spanned};
 use crate::text::{
     FontFamily, FontList, Hyphenate, LinebreakElem, LocalName, SmartQuoteElem, TextElem,
     TextSize,
 };
-use crate::visualize::Color;
-use crate::{syntax, World};
+use crate::visualize::{Color, Paint};
 
 // Shorthand for highlighter closures.
 type StyleFn<'a> =
@@ -247,6 +249,36 @@ pub struct RawElem {
     #[borrowed]
     pub theme_data: Option<Bytes>,
 
+    /// A function that highlights raw text instead of the built-in syntax
+    /// definitions.
+    ///
+    /// The function receives the raw text and its [language]($raw.lang) and
+    /// returns an array of tokens that, put together, reproduce the text. A
+    /// token is either a string, which is left unstyled, or a dictionary with
+    /// the token's `text` and, optionally, its `fill`, and whether it is
+    /// `bold`, `italic`, or `underline`d. If the function returns `{none}`,
+    /// the text is highlighted as usual. This way, highlighters produced by a
+    /// plugin or computed in script can handle the languages they know.
+    ///
+    /// ````example
+    /// #set raw(highlighter: (text, lang) => {
+    ///   if lang != "calc" { return none }
+    ///   text.matches(regex("\d+|\D+")).map(m => {
+    ///     if m.text.first() in "0123456789" {
+    ///       (text: m.text, fill: blue, bold: true)
+    ///     } else {
+    ///       m.text
+    ///     }
+    ///   })
+    /// })
+    ///
+    /// ```calc
+    /// 12 * (3 + 4) = 84
+    /// ```
+    /// ````
+    #[borrowed]
+    pub highlighter: Option<Func>,
+
     /// The size for a tab stop in spaces. A tab is replaced with enough spaces to
     /// align with the next multiple of the size.
     ///
@@ -294,8 +326,15 @@ impl RawElem {
 }
 
 impl Synthesize for Packed<RawElem> {
-    fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
-        let seq = self.highlight(styles);
+    fn synthesize(
+        &mut self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<()> {
+        let seq = match self.highlight_custom(engine, styles)? {
+            Some(seq) => seq,
+            None => self.highlight(styles),
+        };
         self.push_lines(seq);
         Ok(())
     }
@@ -426,6 +465,74 @@ impl Packed<RawElem> {
     }
 }
 
+impl Packed<RawElem> {
+    /// Highlight the text with the user-defined highlighter, if any.
+    fn highlight_custom(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Option<Vec<Packed<RawLine>>>> {
+        let Some(func) = self.highlighter(styles) else { return Ok(None) };
+
+        let lines = preprocess(self.text(), styles, self.span());
+        let text = lines.iter().map(|(s, _)| s.clone()).collect::<Vec<_>>().join("\n");
+        let lang = self.lang(styles).clone();
+        let tokens = func
+            .call(
+                engine,
+                Context::new(None, Some(styles)).track(),
+                [text.clone().into_value(), lang.into_value()],
+            )?
+            .cast::<Option<Vec<RawToken>>>()
+            .at(func.span())?;
+        let Some(tokens) = tokens else { return Ok(None) };
+
+        let joined: String = tokens.iter().map(|token| token.text.as_str()).collect();
+        if joined != text {
+            bail!(
+                self.span(), "highlighter returned tokens that do not match the text";
+                hint: "the texts of the tokens must add up to the raw text"
+            );
+        }
+
+        // Distribute the tokens over the lines.
+        let count = lines.len() as i64;
+        let mut body = vec![];
+        let mut seq = vec![];
+        let mut offset = 0;
+        let mut finish = |body: &mut Vec<Content>, i: usize| {
+            let (line, span) = &lines[i];
+            seq.push(
+                Packed::new(RawLine::new(
+                    i as i64 + 1,
+                    count,
+                    line.clone(),
+                    Content::sequence(body.drain(..)),
+                ))
+                .spanned(*span),
+            );
+        };
+
+        let mut i = 0;
+        for token in &tokens {
+            for (k, piece) in token.text.split('\n').enumerate() {
+                if k > 0 {
+                    finish(&mut body, i);
+                    i += 1;
+                    offset = 0;
+                }
+                if !piece.is_empty() {
+                    body.push(token.styled(piece, lines[i].1, offset));
+                    offset += piece.len();
+                }
+            }
+        }
+        finish(&mut body, i);
+
+        Ok(Some(seq))
+    }
+}
+
 impl Show for Packed<RawElem> {
     #[typst_macros::time(name = "raw", span = self.span())]
     fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
@@ -493,7 +600,7 @@ pub enum RawContent {
 
 impl RawContent {
     /// Returns or synthesizes the text content of the raw text.
-    fn get(&self) -> EcoString {
+    pub(crate) fn get(&self) -> EcoString {
         match self.clone() {
             RawContent::Text(text) => text,
             RawContent::Lines(lines) => {
@@ -693,7 +800,7 @@ fn styled(
     }
 
     if style.foreground != foreground {
-        body = body.styled(TextElem::set_fill(to_typst(style.foreground).into()));
+        body = body.styled(TextElem::set_fill(Some(to_typst(style.foreground).into())));
     }
 
     if style.font_style.contains(synt::FontStyle::BOLD) {
@@ -720,6 +827,76 @@ fn to_syn(color: Color) -> synt::Color {
     synt::Color { r, g, b, a }
 }
 
+/// A piece of raw text produced by a user-defined highlighter.
+#[derive(Debug, Clone, PartialEq, Hash)]
+struct RawToken {
+    /// The text of the token.
+    text: EcoString,
+    /// The fill of the token's text, if it differs from the surrounding text.
+    fill: Option<Paint>,
+    /// Whether the token is bold.
+    bold: bool,
+    /// Whether the token is italic.
+    italic: bool,
+    /// Whether the token is underlined.
+    underline: bool,
+}
+
+impl RawToken {
+    /// Style a piece of the token's text.
+    fn styled(&self, piece: &str, span: Span, span_offset: usize) -> Content {
+        let mut body = TextElem::packed(piece).spanned(span);
+
+        if span_offset > 0 {
+            body = body.styled(TextElem::set_span_offset(span_offset));
+        }
+
+        if let Some(fill) = &self.fill {
+            body = body.styled(TextElem::set_fill(Some(fill.clone())));
+        }
+
+        if self.bold {
+            body = body.strong().spanned(span);
+        }
+
+        if self.italic {
+            body = body.emph().spanned(span);
+        }
+
+        if self.underline {
+            body = body.underlined().spanned(span);
+        }
+
+        body
+    }
+}
+
+cast! {
+    RawToken,
+    text: EcoString => Self {
+        text,
+        fill: None,
+        bold: false,
+        italic: false,
+        underline: false,
+    },
+    mut dict: Dict => {
+        let text = dict.take("text")?.cast()?;
+        let fill = dict.take("fill").ok().map(Value::cast).transpose()?;
+        let bold = dict.take("bold").ok().map(Value::cast).transpose()?;
+        let italic = dict.take("italic").ok().map(Value::cast).transpose()?;
+        let underline = dict.take("underline").ok().map(Value::cast).transpose()?;
+        dict.finish(&["text", "fill", "bold", "italic", "underline"])?;
+        Self {
+            text,
+            fill,
+            bold: bold.unwrap_or(false),
+            italic: italic.unwrap_or(false),
+            underline: underline.unwrap_or(false),
+        }
+    },
+}
+
 /// A list of raw syntax file paths.
 #[derive(Debug, Default, Clone, PartialEq, Hash)]
 pub struct SyntaxPaths(Vec<EcoString>);
@@ -772,7 +949,7 @@ fn parse_syntaxes(
         .iter()
         .map(|path| {
             let id = span.resolve_path(path).at(span)?;
-            engine.world.file(id).at(span)
+            engine.file(id).at(span)
         })
         .collect::<SourceResult<Vec<Bytes>>>()?;
 
@@ -816,7 +993,7 @@ fn parse_theme(
 
     // Load theme file.
     let id = span.resolve_path(&path).at(span)?;
-    let data = engine.world.file(id).at(span)?;
+    let data = engine.file(id).at(span)?;
 
     // Check that parsing works.
     let _ = load_theme(&path, &data).at(span)?;
@@ -3,7 +3,7 @@ use unicode_segmentation::UnicodeSegmentation;
 
 use crate::diag::{bail, HintedStrResult, StrResult};
 use crate::foundations::{
-    array, cast, dict, elem, Array, Dict, FromValue, Packed, PlainText, Smart, Str,
+    array, cast, dict, elem, Array, Cast, Dict, FromValue, Packed, PlainText, Smart, Str,
 };
 use crate::layout::Dir;
 use crate::syntax::is_newline;
@@ -73,6 +73,11 @@ pub struct SmartQuoteElem {
     ///   - [dictionary]: an array containing the double and single quotes, each
     ///     specified as either `{auto}`, string, or array
     ///
+    /// A dictionary may additionally map language codes (optionally with a
+    /// region, like `{"de-CH"}`) to quotes in any of the forms above. These
+    /// quotes are only used for text in that language and take precedence
+    /// over the general ones.
+    ///
     /// ```example
     /// #set text(lang: "de")
     /// 'Das sind normale Anführungszeichen.'
@@ -82,9 +87,46 @@ pub struct SmartQuoteElem {
     ///
     /// #set smartquote(quotes: (single: ("[[", "]]"),  double: auto))
     /// 'Das sind eigene Anführungszeichen.'
+    ///
+    /// #set smartquote(quotes: (fi: (double: "»»", single: "››")))
+    /// #set text(lang: "fi")
+    /// "Nämä ovat 'sisäkkäisiä' lainausmerkkejä."
     /// ```
     #[borrowed]
     pub quotes: Smart<SmartQuoteDict>,
+
+    /// How nested quotes are chosen.
+    ///
+    /// ```example
+    /// #set smartquote(nesting: "alternate")
+    /// "Outer "inner "innermost" inner" outer"
+    /// ```
+    #[default(SmartQuoteNesting::Typed)]
+    pub nesting: SmartQuoteNesting,
+
+    /// Words that begin with an apostrophe.
+    ///
+    /// A straight single quote at the start of a word normally opens a quote.
+    /// If the word is listed here, it becomes an apostrophe instead. Matching
+    /// is case-insensitive and only considers whole words.
+    ///
+    /// ```example
+    /// #set smartquote(elisions: ("'tis", "'90s"))
+    /// 'Tis the season of the '90s, 'they say'.
+    /// ```
+    #[borrowed]
+    pub elisions: Vec<EcoString>,
+}
+
+/// How nested smart quotes are chosen.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum SmartQuoteNesting {
+    /// Double and single quotes are used as they are typed.
+    Typed,
+    /// Quotes alternate between double and single quotes with each level of
+    /// nesting, regardless of which quote character was typed. This way,
+    /// quotes can be nested arbitrarily deep.
+    Alternate,
 }
 
 impl PlainText for Packed<SmartQuoteElem> {
@@ -100,8 +142,10 @@ impl PlainText for Packed<SmartQuoteElem> {
 /// State machine for smart quote substitution.
 #[derive(Debug, Clone)]
 pub struct SmartQuoter {
-    /// How many quotes have been opened.
-    quote_depth: usize,
+    /// The quotes that are currently open, from the outermost to the
+    /// innermost. For each, whether a double quote was typed and whether a
+    /// double quote was substituted.
+    open: Vec<(bool, bool)>,
     /// Whether an opening quote might follow.
     expect_opening: bool,
     /// Whether the last character was numeric.
@@ -114,7 +158,7 @@ impl SmartQuoter {
     /// Start quoting.
     pub fn new() -> Self {
         Self {
-            quote_depth: 0,
+            open: vec![],
             expect_opening: true,
             last_num: false,
             prev_quote_type: None,
@@ -130,6 +174,23 @@ impl SmartQuoter {
         }
     }
 
+    /// Whether a single quote followed by `text` is an apostrophe that starts
+    /// one of the given elided words rather than an opening quote.
+    pub fn is_elision(&self, elisions: &[EcoString], double: bool, text: &str) -> bool {
+        if double || !self.expect_opening {
+            return false;
+        }
+
+        elisions.iter().any(|elision| {
+            let word = elision.trim_start_matches(['\'', '’']);
+            text.get(..word.len()).is_some_and(|head| {
+                !word.is_empty()
+                    && head.eq_ignore_ascii_case(word)
+                    && !text[word.len()..].starts_with(char::is_alphanumeric)
+            })
+        })
+    }
+
     /// Process and substitute a quote.
     pub fn quote<'a>(
         &mut self,
@@ -146,20 +207,40 @@ impl SmartQuoter {
         }
 
         if expect_opening {
-            self.quote_depth += 1;
+            let kind = match quotes.nesting {
+                SmartQuoteNesting::Typed => double,
+                SmartQuoteNesting::Alternate => self.open.len() % 2 == 0,
+            };
+            self.open.push((double, kind));
             self.prev_quote_type = Some(double);
-            quotes.open(double)
-        } else if self.quote_depth > 0
+            quotes.open(kind)
+        } else if self.closes(quotes.nesting, double)
             && (peeked.is_ascii_punctuation() || is_ignorable(peeked))
         {
-            self.quote_depth -= 1;
-            quotes.close(double)
+            let (_, kind) = self.open.pop().unwrap_or((double, double));
+            match quotes.nesting {
+                SmartQuoteNesting::Typed => quotes.close(double),
+                SmartQuoteNesting::Alternate => quotes.close(kind),
+            }
         } else if self.last_num {
             quotes.prime(double)
         } else {
             quotes.fallback(double)
         }
     }
+
+    /// Whether a typed quote can close the innermost open quote.
+    ///
+    /// When alternating, only the quote character that opened a quote can
+    /// close it. Otherwise, an apostrophe at the end of a word (like in
+    /// "the dogs' bowls") would close the surrounding double quote.
+    fn closes(&self, nesting: SmartQuoteNesting, double: bool) -> bool {
+        match (nesting, self.open.last()) {
+            (_, None) => false,
+            (SmartQuoteNesting::Typed, Some(_)) => true,
+            (SmartQuoteNesting::Alternate, Some(&(typed, _))) => typed == double,
+        }
+    }
 }
 
 impl Default for SmartQuoter {
@@ -178,6 +259,8 @@ fn is_opening_bracket(c: char) -> bool {
 
 /// Decides which quotes to substitute smart quotes with.
 pub struct SmartQuotes<'s> {
+    /// How nested quotes are chosen.
+    pub nesting: SmartQuoteNesting,
     /// The opening single quote.
     pub single_open: &'s str,
     /// The closing single quote.
@@ -202,12 +285,18 @@ impl<'s> SmartQuotes<'s> {
     /// Norwegian.
     ///
     /// For unknown languages, the English quotes are used as fallback.
+    ///
+    /// Custom quotes for the specific language take precedence over general
+    /// custom quotes, which in turn take precedence over the defaults.
     pub fn new(
         quotes: &'s Smart<SmartQuoteDict>,
         lang: Lang,
         region: Option<Region>,
         alternative: bool,
+        nesting: SmartQuoteNesting,
     ) -> Self {
+        let custom = quotes.as_ref().custom();
+        let specific = custom.and_then(|dict| dict.get(lang, region));
         let region = region.as_ref().map(Region::as_str);
 
         let default = ("‘", "’", "“", "”");
@@ -235,25 +324,24 @@ impl<'s> SmartQuotes<'s> {
         };
 
         fn inner_or_default<'s>(
-            quotes: Smart<&'s SmartQuoteDict>,
-            f: impl FnOnce(&'s SmartQuoteDict) -> Smart<&'s SmartQuoteSet>,
+            quotes: [Option<&'s SmartQuoteDict>; 2],
+            f: impl Fn(&'s SmartQuoteDict) -> Smart<&'s SmartQuoteSet>,
             default: [&'s str; 2],
         ) -> [&'s str; 2] {
-            match quotes.and_then(f) {
-                Smart::Auto => default,
-                Smart::Custom(SmartQuoteSet { open, close }) => {
-                    [open, close].map(|s| s.as_str())
-                }
+            match quotes.into_iter().flatten().find_map(|q| f(q).custom()) {
+                None => default,
+                Some(SmartQuoteSet { open, close }) => [open, close].map(|s| s.as_str()),
             }
         }
 
-        let quotes = quotes.as_ref();
+        let quotes = [specific, custom];
         let [single_open, single_close] =
             inner_or_default(quotes, |q| q.single.as_ref(), [single_open, single_close]);
         let [double_open, double_close] =
             inner_or_default(quotes, |q| q.double.as_ref(), [double_open, double_close]);
 
         Self {
+            nesting,
             single_open,
             single_close,
             double_open,
@@ -348,16 +436,44 @@ fn array_to_set(value: Array) -> HintedStrResult<[EcoString; 2]> {
     Ok([open, close])
 }
 
-/// A dict of single and double quotes.
+/// A dict of single and double quotes, optionally with quotes for specific
+/// languages.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct SmartQuoteDict {
     double: Smart<SmartQuoteSet>,
     single: Smart<SmartQuoteSet>,
+    langs: Vec<(Lang, Option<Region>, SmartQuoteDict)>,
+}
+
+impl SmartQuoteDict {
+    /// The quotes for the given language and region, if any.
+    ///
+    /// Falls back from the region-specific to the language-wide quotes.
+    fn get(&self, lang: Lang, region: Option<Region>) -> Option<&Self> {
+        let find = |region: Option<Region>| {
+            self.langs
+                .iter()
+                .find(|(l, r, _)| *l == lang && *r == region)
+                .map(|(.., quotes)| quotes)
+        };
+        region.and_then(|region| find(Some(region))).or_else(|| find(None))
+    }
 }
 
 cast! {
     SmartQuoteDict,
-    self => dict! { "double" => self.double, "single" => self.single }.into_value(),
+    self => {
+        let mut dict = dict! { "double" => self.double, "single" => self.single };
+        for (lang, region, quotes) in self.langs {
+            let mut code = EcoString::from(lang.as_str());
+            if let Some(region) = region {
+                code.push('-');
+                code.push_str(region.as_str());
+            }
+            dict.insert(code.into(), quotes.into_value());
+        }
+        dict.into_value()
+    },
     mut value: Dict => {
         let keys = ["double", "single"];
 
@@ -374,12 +490,30 @@ cast! {
             .transpose()?
             .unwrap_or(Smart::Auto);
 
+        let mut langs = vec![];
+        for (code, quotes) in value.clone() {
+            let (lang, region) = match code.split_once('-') {
+                Some((lang, region)) => {
+                    (lang.parse::<Lang>(), region.parse::<Region>().map(Some))
+                }
+                None => (code.parse::<Lang>(), Ok(None)),
+            };
+            let (Ok(lang), Ok(region)) = (lang, region) else { continue };
+            let quotes: Self = quotes.cast()?;
+            if !quotes.langs.is_empty() {
+                bail!("quotes for a specific language cannot be nested");
+            }
+            langs.push((lang, region, quotes));
+            value.take(&code)?;
+        }
+
         value.finish(&keys)?;
 
-        Self { single, double }
+        Self { single, double, langs }
     },
     value: SmartQuoteSet => Self {
         double: Smart::Custom(value),
         single: Smart::Auto,
+        langs: vec![],
     },
 }
@@ -0,0 +1,312 @@
+//! Transcription of text into other writing systems.
+
+use ecow::EcoString;
+
+use crate::diag::{bail, SourceResult, StrResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, func, Content, Module, Packed, Scope, Show, Str, StyleChain,
+};
+use crate::text::{FontFamily, FontList, TextElem};
+
+/// A module with transcription definitions.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_func::<to_braille>();
+    scope.define_func::<respell>();
+    scope.define_elem::<PhoneticElem>();
+    Module::new("transcribe", scope)
+}
+
+/// Transcribes text into Unicode braille.
+///
+/// Letters, digits, and common punctuation are transcribed according to
+/// Unified English Braille. Capital letters and words get capital
+/// indicators and numbers get numeric indicators. Characters without a
+/// braille equivalent are kept as they are.
+///
+/// ```example
+/// #transcribe.to-braille("Hello, World!") \
+/// #transcribe.to-braille("Call 911 at 3.5 km.")
+/// ```
+#[func]
+pub fn to_braille(
+    /// The text to transcribe.
+    text: Str,
+    /// The braille grade.
+    ///
+    /// Only grade 1, that is uncontracted braille, is supported.
+    #[named]
+    #[default(1)]
+    grade: u8,
+) -> StrResult<Str> {
+    if grade != 1 {
+        bail!("only grade 1 braille is supported");
+    }
+    Ok(braille(&text).into())
+}
+
+/// Converts a phonetic transcription in X-SAMPA to the International Phonetic
+/// Alphabet.
+///
+/// X-SAMPA represents IPA symbols with ASCII characters, which makes
+/// transcriptions easy to type. Unknown characters are kept as they are.
+///
+/// ```example
+/// #transcribe.respell("h@\"l@U") \
+/// #transcribe.respell("\"TINk")
+/// ```
+#[func]
+pub fn respell(
+    /// The transcription in X-SAMPA.
+    text: Str,
+) -> Str {
+    x_sampa(&text).into()
+}
+
+/// Typesets a phonetic transcription in a font with good coverage of the
+/// International Phonetic Alphabet.
+///
+/// The fonts Charis SIL, Doulos SIL, Gentium Plus, and New Computer Modern,
+/// which ships with Typst, are tried in order before the fonts that are
+/// already set.
+///
+/// ```example
+/// The word is pronounced
+/// #transcribe.phonetic[/#transcribe.respell("h@\"l@U")/].
+/// ```
+#[elem(Show)]
+pub struct PhoneticElem {
+    /// The transcription.
+    #[required]
+    pub body: Content,
+}
+
+impl Show for Packed<PhoneticElem> {
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let families =
+            ["Charis SIL", "Doulos SIL", "Gentium Plus", "New Computer Modern"];
+        let list = FontList(
+            families
+                .into_iter()
+                .map(FontFamily::new)
+                .chain(TextElem::font_in(styles).into_iter().cloned())
+                .collect(),
+        );
+        Ok(self.body().clone().styled(TextElem::set_font(list)))
+    }
+}
+
+/// Transcribe text into uncontracted Unified English Braille.
+fn braille(text: &str) -> EcoString {
+    const CAPITAL: char = '⠠';
+    const NUMERIC: char = '⠼';
+    const GRADE_1: char = '⠰';
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = EcoString::new();
+    let mut numeric = false;
+    let mut capital_word = false;
+    let mut double_quote_open = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        let next = chars.get(i + 1).copied();
+
+        if let Some(digit) = c.to_digit(10) {
+            if !numeric {
+                out.push(NUMERIC);
+                numeric = true;
+            }
+            out.push(letter(b"jabcdefghi"[digit as usize] as char).unwrap());
+            continue;
+        }
+
+        // Decimal points and digit group separators continue a number.
+        if numeric && matches!(c, '.' | ',') && next.is_some_and(|n| n.is_ascii_digit()) {
+            out.push(if c == '.' { '⠲' } else { '⠂' });
+            continue;
+        }
+
+        if let Some(cell) = letter(c.to_ascii_lowercase()) {
+            // Letters a to j would read as digits right after a number.
+            if numeric && ('a'..='j').contains(&c.to_ascii_lowercase()) {
+                out.push(GRADE_1);
+            }
+            numeric = false;
+
+            if c.is_ascii_uppercase() && !capital_word {
+                let word_end = chars[i..]
+                    .iter()
+                    .position(|c| !c.is_ascii_alphabetic())
+                    .map_or(chars.len(), |n| i + n);
+                let word = &chars[i..word_end];
+                if word.len() > 1 && word.iter().all(char::is_ascii_uppercase) {
+                    out.push(CAPITAL);
+                    out.push(CAPITAL);
+                    capital_word = true;
+                } else {
+                    out.push(CAPITAL);
+                }
+            }
+
+            out.push(cell);
+            continue;
+        }
+
+        numeric = false;
+        capital_word = false;
+        match c {
+            '"' => {
+                out.push(if double_quote_open { '⠴' } else { '⠦' });
+                double_quote_open = !double_quote_open;
+            }
+            '“' => out.push_str("⠘⠦"),
+            '”' => out.push_str("⠘⠴"),
+            '(' => out.push_str("⠐⠣"),
+            ')' => out.push_str("⠐⠜"),
+            '/' => out.push_str("⠸⠌"),
+            _ => out.push(punctuation(c).unwrap_or(c)),
+        }
+    }
+
+    out
+}
+
+/// The braille cell for a lowercase ASCII letter.
+fn letter(c: char) -> Option<char> {
+    const CELLS: [char; 26] = [
+        '⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊', '⠚', '⠅', '⠇', '⠍', '⠝', '⠕', '⠏',
+        '⠟', '⠗', '⠎', '⠞', '⠥', '⠧', '⠺', '⠭', '⠽', '⠵',
+    ];
+    c.is_ascii_lowercase().then(|| CELLS[(c as u8 - b'a') as usize])
+}
+
+/// The braille cell for a punctuation mark that takes a single cell.
+fn punctuation(c: char) -> Option<char> {
+    Some(match c {
+        ',' => '⠂',
+        ';' => '⠆',
+        ':' => '⠒',
+        '.' => '⠲',
+        '!' => '⠖',
+        '?' => '⠦',
+        '\'' | '’' => '⠄',
+        '-' => '⠤',
+        _ => return None,
+    })
+}
+
+/// Convert X-SAMPA to IPA.
+fn x_sampa(text: &str) -> EcoString {
+    let mut out = EcoString::new();
+    let mut rest = text;
+    'outer: while let Some(c) = rest.chars().next() {
+        // Try the longest sequences first.
+        for len in [2, 1] {
+            let Some(key) = rest.get(..len) else { continue };
+            if let Some(ipa) = x_sampa_symbol(key) {
+                out.push_str(ipa);
+                rest = &rest[len..];
+                continue 'outer;
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// The IPA equivalent of an X-SAMPA symbol.
+fn x_sampa_symbol(key: &str) -> Option<&'static str> {
+    Some(match key {
+        // Symbols with diacritics and modifiers.
+        "r\\" => "ɹ",
+        "R\\" => "ʀ",
+        "l\\" => "ɺ",
+        "h\\" => "ɦ",
+        "j\\" => "ʝ",
+        "x\\" => "ɧ",
+        "s\\" => "ɕ",
+        "z\\" => "ʑ",
+        "p\\" => "ɸ",
+        "v\\" => "ʋ",
+        "B\\" => "ʙ",
+        "G\\" => "ɢ",
+        "H\\" => "ʜ",
+        "J\\" => "ɟ",
+        "K\\" => "ɮ",
+        "L\\" => "ʟ",
+        "M\\" => "ɰ",
+        "N\\" => "ɴ",
+        "O\\" => "ʘ",
+        "X\\" => "ħ",
+        "I\\" => "ᵻ",
+        "U\\" => "ᵿ",
+        "?\\" => "ʕ",
+        "@\\" => "ɘ",
+        "3\\" => "ɞ",
+        ":\\" => "ˑ",
+        "r`" => "ɽ",
+        "t`" => "ʈ",
+        "d`" => "ɖ",
+        "n`" => "ɳ",
+        "s`" => "ʂ",
+        "z`" => "ʐ",
+        "l`" => "ɭ",
+        "_h" => "ʰ",
+        "_w" => "ʷ",
+        "_j" => "ʲ",
+        "_0" => "\u{325}",
+        "_~" => "\u{303}",
+        "||" => "‖",
+        // Single characters.
+        "A" => "ɑ",
+        "B" => "β",
+        "C" => "ç",
+        "D" => "ð",
+        "E" => "ɛ",
+        "F" => "ɱ",
+        "G" => "ɣ",
+        "H" => "ɥ",
+        "I" => "ɪ",
+        "J" => "ɲ",
+        "K" => "ɬ",
+        "L" => "ʎ",
+        "M" => "ɯ",
+        "N" => "ŋ",
+        "O" => "ɔ",
+        "P" => "ʋ",
+        "Q" => "ɒ",
+        "R" => "ʁ",
+        "S" => "ʃ",
+        "T" => "θ",
+        "U" => "ʊ",
+        "V" => "ʌ",
+        "W" => "ʍ",
+        "X" => "χ",
+        "Y" => "ʏ",
+        "Z" => "ʒ",
+        "g" => "ɡ",
+        "@" => "ə",
+        "{" => "æ",
+        "}" => "ʉ",
+        "1" => "ɨ",
+        "2" => "ø",
+        "3" => "ɜ",
+        "4" => "ɾ",
+        "5" => "ɫ",
+        "6" => "ɐ",
+        "7" => "ɤ",
+        "8" => "ɵ",
+        "9" => "œ",
+        "&" => "ɶ",
+        "?" => "ʔ",
+        "\"" => "ˈ",
+        "%" => "ˌ",
+        ":" => "ː",
+        "'" => "ʲ",
+        "~" => "\u{303}",
+        "=" => "\u{329}",
+        _ => return None,
+    })
+}
@@ -0,0 +1,47 @@
+use crate::foundations::Cast;
+
+/// How the colors of content are combined with the colors beneath it.
+///
+/// The modes follow the blend modes of the [PDF](https://www.adobe.com/go/pdfreference)
+/// and [CSS](https://www.w3.org/TR/compositing-1/#blending) specifications.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum BlendMode {
+    /// The content is painted over the content beneath it.
+    #[default]
+    Normal,
+    /// Multiplies the colors, which always results in a darker color.
+    Multiply,
+    /// Multiplies the complements of the colors, which always results in a
+    /// lighter color.
+    Screen,
+    /// Multiplies or screens the colors, depending on the color beneath.
+    Overlay,
+    /// Keeps the darker of the two colors.
+    Darken,
+    /// Keeps the lighter of the two colors.
+    Lighten,
+    /// Brightens the color beneath to reflect the content's color.
+    ColorDodge,
+    /// Darkens the color beneath to reflect the content's color.
+    ColorBurn,
+    /// Multiplies or screens the colors, depending on the content's color.
+    HardLight,
+    /// Darkens or lightens the colors, depending on the content's color.
+    SoftLight,
+    /// Subtracts the darker from the lighter color.
+    Difference,
+    /// Like `difference`, but with lower contrast.
+    Exclusion,
+    /// Uses the hue of the content with the saturation and luminosity of the
+    /// color beneath.
+    Hue,
+    /// Uses the saturation of the content with the hue and luminosity of the
+    /// color beneath.
+    Saturation,
+    /// Uses the hue and saturation of the content with the luminosity of the
+    /// color beneath.
+    Color,
+    /// Uses the luminosity of the content with the hue and saturation of the
+    /// color beneath.
+    Luminosity,
+}
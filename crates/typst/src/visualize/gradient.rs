@@ -3,29 +3,35 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::Hash;
 use std::sync::Arc;
 
+use comemo::Tracked;
 use ecow::EcoString;
 use kurbo::Vec2;
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, At, SourceResult};
+use crate::engine::Engine;
 use crate::foundations::{
-    array, cast, func, scope, ty, Args, Array, Cast, Func, IntoValue, Repr, Smart,
+    array, cast, func, scope, ty, Args, Array, Cast, Context, Func, IntoValue, Repr,
+    Smart,
 };
-use crate::layout::{Angle, Axes, Dir, Quadrant, Ratio};
+use crate::layout::{Angle, Axes, Dir, Quadrant, Ratio, Transform};
 use crate::syntax::{Span, Spanned};
-use crate::visualize::{Color, ColorSpace, WeightedColor};
+use crate::visualize::{Color, ColorSpace, MeshGradient, MeshPoint, WeightedColor};
 
 /// A color gradient.
 ///
 /// Typst supports linear gradients through the
 /// [`gradient.linear` function]($gradient.linear), radial gradients through
-/// the [`gradient.radial` function]($gradient.radial), and conic gradients
-/// through the [`gradient.conic` function]($gradient.conic).
+/// the [`gradient.radial` function]($gradient.radial), conic gradients
+/// through the [`gradient.conic` function]($gradient.conic), and mesh
+/// gradients through the [`gradient.mesh` function]($gradient.mesh).
 ///
 /// A gradient can be used for the following purposes:
 /// - As a fill to paint the interior of a shape:
 ///   `{rect(fill: gradient.linear(..))}`
 /// - As a stroke to paint the outline of a shape:
 ///   `{rect(stroke: 1pt + gradient.linear(..))}`
+/// - As a stroke that follows the direction of a path:
+///   `{line(stroke: 4pt + gradient.linear(.., relative: "stroke"))}`
 /// - As the fill of text:
 ///   `{set text(fill: gradient.linear(..))}`
 /// - As a color map you can [sample]($gradient.sample) from:
@@ -43,11 +49,12 @@ use crate::visualize::{Color, ColorSpace, WeightedColor};
 /// )
 /// ```
 ///
-/// Gradients are also supported on text, but only when setting the
-/// [relativeness]($gradient.relative) to either `{auto}` (the default value) or
-/// `{"parent"}`. To create word-by-word or glyph-by-glyph gradients, you can
-/// wrap the words or characters of your text in [boxes]($box) manually or
-/// through a [show rule]($styling/#show-rules).
+/// Gradients are also supported on text. By default, they are relative to the
+/// text's parent container. With a [relativeness]($gradient.relative) of
+/// `{"self"}`, a gradient instead spans the text it is applied to, across all
+/// lines of a paragraph. To create word-by-word or glyph-by-glyph gradients,
+/// you can wrap the words or characters of your text in [boxes]($box)
+/// manually or through a [show rule]($styling/#show-rules).
 ///
 /// ```example
 /// >>> #set page(width: auto, height: auto, margin: 12pt)
@@ -79,6 +86,27 @@ use crate::visualize::{Color, ColorSpace, WeightedColor};
 /// applied on text, in which case they are relative to the closest ancestor
 /// container.
 ///
+/// Gradients used as strokes can also be made relative to the stroke itself
+/// by setting `relative` to `{"stroke"}`. In this case, the gradient is not
+/// laid out across a container, but progresses along the stroked path: the
+/// `{0%}` stop is at the start of the path and the `{100%}` stop at its end.
+/// Only the stops of the gradient are relevant in this mode, its geometry
+/// (angle, center, etc.) is ignored. When such a gradient is used as a fill,
+/// it behaves as if it were relative to `{"self"}`.
+///
+/// ```example
+/// #path(
+///   stroke: 4pt + gradient.linear(
+///     ..color.map.rainbow,
+///     relative: "stroke",
+///   ),
+///   (0pt, 40pt),
+///   ((40pt, 0pt), (-15pt, 0pt)),
+///   ((80pt, 40pt), (-15pt, 0pt)),
+///   (120pt, 0pt),
+/// )
+/// ```
+///
 /// Typst determines the ancestor container as follows:
 /// - For shapes that are placed at the root/top level of the document, the
 ///   closest ancestor is the page itself.
@@ -180,6 +208,7 @@ pub enum Gradient {
     Linear(Arc<LinearGradient>),
     Radial(Arc<RadialGradient>),
     Conic(Arc<ConicGradient>),
+    Mesh(Arc<MeshGradient>),
 }
 
 #[scope]
@@ -222,6 +251,12 @@ impl Gradient {
         #[named]
         #[default(Smart::Auto)]
         relative: Smart<RelativeTo>,
+        /// A [transform] to apply to the gradient. Its origin is the origin
+        /// of the element or parent the gradient is relative to. It must be
+        /// invertible.
+        #[named]
+        #[default(Spanned::new(Transform::identity(), Span::detached()))]
+        transform: Spanned<Transform>,
         /// The direction of the gradient.
         #[external]
         #[default(Dir::LTR)]
@@ -255,6 +290,7 @@ impl Gradient {
             angle,
             space,
             relative,
+            transform: check_transform(transform)?,
             anti_alias: true,
         })))
     }
@@ -311,6 +347,25 @@ impl Gradient {
         #[named]
         #[default(Smart::Auto)]
         relative: Smart<RelativeTo>,
+        /// A [transform] to apply to the gradient, for instance to rotate the
+        /// ellipse of a radial gradient. The transform's origin is the origin
+        /// of the element or parent the gradient is relative to. It must be
+        /// invertible.
+        ///
+        /// ```example
+        /// #rect(
+        ///   width: 100%,
+        ///   height: 40pt,
+        ///   fill: gradient.radial(
+        ///     red, blue,
+        ///     radius: 30%,
+        ///     transform: transform.rotate(20deg),
+        ///   ),
+        /// )
+        /// ```
+        #[named]
+        #[default(Spanned::new(Transform::identity(), Span::detached()))]
+        transform: Spanned<Transform>,
         /// The center of the end circle of the gradient.
         ///
         /// A value of `{(50%, 50%)}` means that the end circle is
@@ -380,6 +435,7 @@ impl Gradient {
             focal_radius: focal_radius.v,
             space,
             relative,
+            transform: check_transform(transform)?,
             anti_alias: true,
         })))
     }
@@ -430,6 +486,12 @@ impl Gradient {
         #[named]
         #[default(Smart::Auto)]
         relative: Smart<RelativeTo>,
+        /// A [transform] to apply to the gradient. Its origin is the origin
+        /// of the element or parent the gradient is relative to. It must be
+        /// invertible.
+        #[named]
+        #[default(Spanned::new(Transform::identity(), Span::detached()))]
+        transform: Spanned<Transform>,
         /// The center of the last circle of the gradient.
         ///
         /// A value of `{(50%, 50%)}` means that the end circle is
@@ -451,10 +513,238 @@ impl Gradient {
             center: center.map(From::from),
             space,
             relative,
+            transform: check_transform(transform)?,
             anti_alias: true,
         })))
     }
 
+    /// Creates a new mesh gradient, in which colors are smoothly interpolated
+    /// between the points of a grid.
+    ///
+    /// The gradient is defined by a list of rows, each of which is an array of
+    /// colors. All rows must have the same number of colors. The colors are
+    /// placed on an evenly spaced grid spanning the container: the first color
+    /// of the first row sits in the top-left corner and the last color of the
+    /// last row in the bottom-right corner. Each cell of the grid is a
+    /// [Coons patch](https://en.wikipedia.org/wiki/Coons_patch) whose colors
+    /// are interpolated between its four corners.
+    ///
+    /// ```example
+    /// #rect(
+    ///   width: 100%,
+    ///   height: 60pt,
+    ///   fill: gradient.mesh(
+    ///     (red, yellow, green),
+    ///     (purple, white, aqua),
+    ///     (blue, black, orange),
+    ///   ),
+    /// )
+    /// ```
+    ///
+    /// Instead of a color, a point of the grid can also be given as an array
+    /// of a color and its position `(x, y)` in the container, with both
+    /// coordinates between `{0%}` and `{100%}`. The edges of the patches run
+    /// along smooth curves through the rows and columns of points, so moving
+    /// a point bends the patches around it. Points on the edge of the grid
+    /// can only move along the container's edge, so that the mesh always
+    /// covers the whole container.
+    ///
+    /// ```example
+    /// #rect(
+    ///   width: 100%,
+    ///   height: 60pt,
+    ///   fill: gradient.mesh(
+    ///     (red, yellow, green),
+    ///     (purple, (white, (70%, 20%)), aqua),
+    ///     (blue, black, orange),
+    ///   ),
+    /// )
+    /// ```
+    ///
+    /// When sampled with [`sample`]($gradient.sample), a mesh gradient is
+    /// sampled along the diagonal from its top-left to its bottom-right
+    /// corner.
+    #[func]
+    pub fn mesh(
+        /// The call site of this function.
+        span: Span,
+        /// The rows of points of the mesh. Each point is either a color or
+        /// an array of a color and a position.
+        #[variadic]
+        rows: Vec<Spanned<Vec<MeshPoint>>>,
+        /// The color space in which to interpolate the gradient.
+        ///
+        /// Defaults to a perceptually uniform color space called
+        /// [Oklab]($color.oklab).
+        #[named]
+        #[default(ColorSpace::Oklab)]
+        space: ColorSpace,
+        /// The [relative placement](#relativeness) of the gradient.
+        ///
+        /// For an element placed at the root/top level of the document, the parent
+        /// is the page itself. For other elements, the parent is the innermost block,
+        /// box, column, grid, or stack that contains the element.
+        #[named]
+        #[default(Smart::Auto)]
+        relative: Smart<RelativeTo>,
+        /// A [transform] to apply to the gradient. Its origin is the origin
+        /// of the element or parent the gradient is relative to. It must be
+        /// invertible.
+        #[named]
+        #[default(Spanned::new(Transform::identity(), Span::detached()))]
+        transform: Spanned<Transform>,
+    ) -> SourceResult<Gradient> {
+        if rows.len() < 2 {
+            bail!(
+                span, "a mesh gradient must have at least two rows";
+                hint: "try using a linear gradient instead"
+            );
+        }
+
+        let columns = rows[0].v.len();
+        for row in &rows {
+            if row.v.len() < 2 {
+                bail!(
+                    row.span,
+                    "each row of a mesh gradient must have at least two colors"
+                );
+            }
+
+            if row.v.len() != columns {
+                bail!(
+                    row.span,
+                    "all rows of a mesh gradient must have the same number of colors";
+                    hint: "expected {columns} colors, found {}", row.v.len()
+                );
+            }
+        }
+
+        let last = Axes::new(columns - 1, rows.len() - 1);
+        for (j, row) in rows.iter().enumerate() {
+            for (i, point) in row.v.iter().enumerate() {
+                let Some(position) = point.position else { continue };
+                let within = |r: Ratio| (0.0..=1.0).contains(&r.get());
+                if !within(position.x) || !within(position.y) {
+                    bail!(row.span, "mesh point positions must be between 0% and 100%");
+                }
+
+                let pinned = |index: usize, last: usize, r: Ratio| {
+                    (index != 0 || r.is_zero()) && (index != last || r.is_one())
+                };
+                if !pinned(i, last.x, position.x) || !pinned(j, last.y, position.y) {
+                    bail!(
+                        row.span,
+                        "points on the edge of a mesh gradient must stay on that edge";
+                        hint: "points on the edge can only move along it"
+                    );
+                }
+            }
+        }
+
+        Ok(Gradient::Mesh(Arc::new(MeshGradient::new(
+            rows.into_iter().map(|row| row.v).collect(),
+            space,
+            relative,
+            check_transform(transform)?,
+        ))))
+    }
+
+    /// Creates a procedural gradient, in which a function determines the
+    /// color at each point.
+    ///
+    /// The function receives the horizontal and vertical position within the
+    /// container as [floats]($float) between `{0.0}` and `{1.0}` and must
+    /// return a [color]. It is evaluated on an evenly spaced grid of sample
+    /// points, between which the colors are smoothly interpolated.
+    ///
+    /// This is a preset for a [mesh gradient]($gradient.mesh): the function is
+    /// called once per sample point when the gradient is created, not when it
+    /// is drawn. The result is a mesh gradient with the sampled colors, so its
+    /// [kind]($gradient.kind) is `{gradient.mesh}` and its
+    /// [stops]($gradient.stops) are the sampled colors. More samples capture
+    /// finer detail at the cost of larger output files.
+    ///
+    /// ```example
+    /// #rect(
+    ///   width: 100%,
+    ///   height: 60pt,
+    ///   fill: gradient.procedural(
+    ///     (x, y) => {
+    ///       let wave = calc.sin(x * 12) * calc.cos(y * 8)
+    ///       color.mix((blue, 50% + 50% * wave), (yellow, 50% - 50% * wave))
+    ///     },
+    ///     samples: (24, 8),
+    ///   ),
+    /// )
+    /// ```
+    #[func]
+    pub fn procedural(
+        /// The engine.
+        engine: &mut Engine,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The call site of this function.
+        span: Span,
+        /// The function that maps a position to a color.
+        func: Func,
+        /// The number of sample points along each axis.
+        ///
+        /// Either a single integer for both axes or an array of the number of
+        /// horizontal and vertical samples. Must be between 2 and 256.
+        #[named]
+        #[default(Spanned::new(SampleGrid(Axes::splat(16)), Span::detached()))]
+        samples: Spanned<SampleGrid>,
+        /// The color space in which to interpolate the gradient.
+        ///
+        /// Defaults to a perceptually uniform color space called
+        /// [Oklab]($color.oklab).
+        #[named]
+        #[default(ColorSpace::Oklab)]
+        space: ColorSpace,
+        /// The [relative placement](#relativeness) of the gradient.
+        ///
+        /// For an element placed at the root/top level of the document, the parent
+        /// is the page itself. For other elements, the parent is the innermost block,
+        /// box, column, grid, or stack that contains the element.
+        #[named]
+        #[default(Smart::Auto)]
+        relative: Smart<RelativeTo>,
+        /// A [transform] to apply to the gradient. Its origin is the origin
+        /// of the element or parent the gradient is relative to. It must be
+        /// invertible.
+        #[named]
+        #[default(Spanned::new(Transform::identity(), Span::detached()))]
+        transform: Spanned<Transform>,
+    ) -> SourceResult<Gradient> {
+        let Axes { x: columns, y: rows } = samples.v.0;
+        if !(2..=256).contains(&columns) || !(2..=256).contains(&rows) {
+            bail!(
+                samples.span,
+                "the number of samples must be between 2 and 256 per axis"
+            );
+        }
+
+        let mut grid = Vec::with_capacity(rows);
+        for j in 0..rows {
+            let y = j as f64 / (rows - 1) as f64;
+            let mut row = Vec::with_capacity(columns);
+            for i in 0..columns {
+                let x = i as f64 / (columns - 1) as f64;
+                let color =
+                    func.call(engine, context, [x, y])?.cast::<Color>().at(span)?;
+                row.push(MeshPoint { color, position: None });
+            }
+            grid.push(row);
+        }
+
+        Ok(Gradient::Mesh(Arc::new(MeshGradient::new(
+            grid,
+            space,
+            relative,
+            check_transform(transform)?,
+        ))))
+    }
+
     /// Creates a sharp version of this gradient.
     ///
     /// Sharp gradients have discrete jumps between colors, instead of a
@@ -529,6 +819,7 @@ impl Gradient {
                 angle: linear.angle,
                 space: linear.space,
                 relative: linear.relative,
+                transform: linear.transform,
                 anti_alias: false,
             })),
             Self::Radial(radial) => Self::Radial(Arc::new(RadialGradient {
@@ -539,6 +830,7 @@ impl Gradient {
                 focal_radius: radial.focal_radius,
                 space: radial.space,
                 relative: radial.relative,
+                transform: radial.transform,
                 anti_alias: false,
             })),
             Self::Conic(conic) => Self::Conic(Arc::new(ConicGradient {
@@ -547,8 +839,10 @@ impl Gradient {
                 center: conic.center,
                 space: conic.space,
                 relative: conic.relative,
+                transform: conic.transform,
                 anti_alias: false,
             })),
+            Self::Mesh(_) => bail!(steps.span, "mesh gradients cannot be made sharp"),
         })
     }
 
@@ -611,6 +905,7 @@ impl Gradient {
                 angle: linear.angle,
                 space: linear.space,
                 relative: linear.relative,
+                transform: linear.transform,
                 anti_alias: linear.anti_alias,
             })),
             Self::Radial(radial) => Self::Radial(Arc::new(RadialGradient {
@@ -621,6 +916,7 @@ impl Gradient {
                 focal_radius: radial.focal_radius,
                 space: radial.space,
                 relative: radial.relative,
+                transform: radial.transform,
                 anti_alias: radial.anti_alias,
             })),
             Self::Conic(conic) => Self::Conic(Arc::new(ConicGradient {
@@ -629,8 +925,12 @@ impl Gradient {
                 center: conic.center,
                 space: conic.space,
                 relative: conic.relative,
+                transform: conic.transform,
                 anti_alias: conic.anti_alias,
             })),
+            Self::Mesh(_) => {
+                bail!(repetitions.span, "mesh gradients cannot be repeated")
+            }
         })
     }
 
@@ -641,10 +941,14 @@ impl Gradient {
             Self::Linear(_) => Self::linear_data().into(),
             Self::Radial(_) => Self::radial_data().into(),
             Self::Conic(_) => Self::conic_data().into(),
+            Self::Mesh(_) => Self::mesh_data().into(),
         }
     }
 
     /// Returns the stops of this gradient.
+    ///
+    /// For mesh gradients, this returns the colors of all points of the mesh
+    /// in row-major order, without offsets.
     #[func]
     pub fn stops(&self) -> Vec<GradientStop> {
         match self {
@@ -672,6 +976,12 @@ impl Gradient {
                     offset: Some(*offset),
                 })
                 .collect(),
+            Self::Mesh(mesh) => mesh
+                .grid
+                .iter()
+                .flatten()
+                .map(|point| GradientStop { color: point.color, offset: None })
+                .collect(),
         }
     }
 
@@ -682,6 +992,7 @@ impl Gradient {
             Self::Linear(linear) => linear.space,
             Self::Radial(radial) => radial.space,
             Self::Conic(conic) => conic.space,
+            Self::Mesh(mesh) => mesh.space,
         }
     }
 
@@ -692,6 +1003,18 @@ impl Gradient {
             Self::Linear(linear) => linear.relative,
             Self::Radial(radial) => radial.relative,
             Self::Conic(conic) => conic.relative,
+            Self::Mesh(mesh) => mesh.relative,
+        }
+    }
+
+    /// Returns the transform applied to this gradient.
+    #[func]
+    pub fn transform(&self) -> Transform {
+        match self {
+            Self::Linear(linear) => linear.transform,
+            Self::Radial(radial) => radial.transform,
+            Self::Conic(conic) => conic.transform,
+            Self::Mesh(mesh) => mesh.transform,
         }
     }
 
@@ -700,7 +1023,7 @@ impl Gradient {
     pub fn angle(&self) -> Option<Angle> {
         match self {
             Self::Linear(linear) => Some(linear.angle),
-            Self::Radial(_) => None,
+            Self::Radial(_) | Self::Mesh(_) => None,
             Self::Conic(conic) => Some(conic.angle),
         }
     }
@@ -722,6 +1045,7 @@ impl Gradient {
             Self::Linear(linear) => sample_stops(&linear.stops, linear.space, value),
             Self::Radial(radial) => sample_stops(&radial.stops, radial.space, value),
             Self::Conic(conic) => sample_stops(&conic.stops, conic.space, value),
+            Self::Mesh(mesh) => mesh.sample(value, value),
         }
     }
 
@@ -751,16 +1075,43 @@ impl Gradient {
             Self::Conic(conic) => {
                 Arc::make_mut(conic).relative = Smart::Custom(relative);
             }
+            Self::Mesh(mesh) => {
+                Arc::make_mut(mesh).relative = Smart::Custom(relative);
+            }
         }
 
         self
     }
+
+    /// Clones this gradient, but with all of its colors mapped through a
+    /// function.
+    pub fn map_colors(mut self, f: impl Fn(Color) -> Color) -> Self {
+        let map = |stops: &mut Vec<(Color, Ratio)>| {
+            for (color, _) in stops {
+                *color = f(*color);
+            }
+        };
+
+        match &mut self {
+            Self::Linear(linear) => map(&mut Arc::make_mut(linear).stops),
+            Self::Radial(radial) => map(&mut Arc::make_mut(radial).stops),
+            Self::Conic(conic) => map(&mut Arc::make_mut(conic).stops),
+            Self::Mesh(mesh) => Arc::make_mut(mesh).map_colors(&f),
+        }
+
+        self
+    }
+
     /// Returns a reference to the stops of this gradient.
+    ///
+    /// Mesh gradients do not have stops, so this returns an empty slice for
+    /// them.
     pub fn stops_ref(&self) -> &[(Color, Ratio)] {
         match self {
             Gradient::Linear(linear) => &linear.stops,
             Gradient::Radial(radial) => &radial.stops,
             Gradient::Conic(conic) => &conic.stops,
+            Gradient::Mesh(_) => &[],
         }
     }
 
@@ -821,6 +1172,7 @@ impl Gradient {
                 );
                 ((-y.atan2(x) + PI + angle.to_rad()) % TAU) / TAU
             }
+            Self::Mesh(mesh) => return mesh.sample(x as f64, y as f64),
         };
 
         self.sample(RatioOrAngle::Ratio(Ratio::new(t.clamp(0.0, 1.0))))
@@ -832,6 +1184,7 @@ impl Gradient {
             Self::Linear(linear) => linear.anti_alias,
             Self::Radial(radial) => radial.anti_alias,
             Self::Conic(conic) => conic.anti_alias,
+            Self::Mesh(_) => true,
         }
     }
 
@@ -868,6 +1221,7 @@ impl Debug for Gradient {
             Self::Linear(v) => v.fmt(f),
             Self::Radial(v) => v.fmt(f),
             Self::Conic(v) => v.fmt(f),
+            Self::Mesh(v) => v.fmt(f),
         }
     }
 }
@@ -878,10 +1232,28 @@ impl Repr for Gradient {
             Self::Radial(radial) => radial.repr(),
             Self::Linear(linear) => linear.repr(),
             Self::Conic(conic) => conic.repr(),
+            Self::Mesh(mesh) => mesh.repr(),
         }
     }
 }
 
+/// The number of sample points of a procedural gradient along each axis.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SampleGrid(pub Axes<usize>);
+
+cast! {
+    SampleGrid,
+    self => array![self.0.x, self.0.y].into_value(),
+    v: usize => Self(Axes::splat(v)),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), None) => Self(Axes::new(a.cast()?, b.cast()?)),
+            _ => bail!("sample array must contain exactly two entries"),
+        }
+    },
+}
+
 /// A gradient that interpolates between two colors along an axis.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct LinearGradient {
@@ -893,6 +1265,8 @@ pub struct LinearGradient {
     pub space: ColorSpace,
     /// The relative placement of the gradient.
     pub relative: Smart<RelativeTo>,
+    /// The transform applied to the gradient.
+    pub transform: Transform,
     /// Whether to anti-alias the gradient (used for sharp gradients).
     pub anti_alias: bool,
 }
@@ -928,6 +1302,12 @@ impl Repr for LinearGradient {
             r.push_str(", ");
         }
 
+        if !self.transform.is_identity() {
+            r.push_str("transform: ");
+            r.push_str(&self.transform.repr());
+            r.push_str(", ");
+        }
+
         for (i, (color, offset)) in self.stops.iter().enumerate() {
             r.push('(');
             r.push_str(&color.repr());
@@ -961,6 +1341,8 @@ pub struct RadialGradient {
     pub space: ColorSpace,
     /// The relative placement of the gradient.
     pub relative: Smart<RelativeTo>,
+    /// The transform applied to the gradient.
+    pub transform: Transform,
     /// Whether to anti-alias the gradient (used for sharp gradients).
     pub anti_alias: bool,
 }
@@ -1009,6 +1391,12 @@ impl Repr for RadialGradient {
             r.push_str(", ");
         }
 
+        if !self.transform.is_identity() {
+            r.push_str("transform: ");
+            r.push_str(&self.transform.repr());
+            r.push_str(", ");
+        }
+
         for (i, (color, offset)) in self.stops.iter().enumerate() {
             r.push('(');
             r.push_str(&color.repr());
@@ -1039,6 +1427,8 @@ pub struct ConicGradient {
     pub space: ColorSpace,
     /// The relative placement of the gradient.
     pub relative: Smart<RelativeTo>,
+    /// The transform applied to the gradient.
+    pub transform: Transform,
     /// Whether to anti-alias the gradient (used for sharp gradients).
     pub anti_alias: bool,
 }
@@ -1074,6 +1464,12 @@ impl Repr for ConicGradient {
             r.push_str(", ");
         }
 
+        if !self.transform.is_identity() {
+            r.push_str("transform: ");
+            r.push_str(&self.transform.repr());
+            r.push_str(", ");
+        }
+
         for (i, (color, offset)) in self.stops.iter().enumerate() {
             r.push('(');
             r.push_str(&color.repr());
@@ -1097,6 +1493,9 @@ pub enum RelativeTo {
     Self_,
     /// The gradient is relative to its parent (the parent's bounding box).
     Parent,
+    /// The gradient progresses along the stroked path. Only applicable to
+    /// gradients used as strokes.
+    Stroke,
 }
 
 /// A color stop.
@@ -1162,6 +1561,15 @@ cast! {
     angle: Angle => Self::Angle(angle),
 }
 
+/// Ensures that a gradient's transform can be inverted, which is needed to
+/// sample it.
+fn check_transform(transform: Spanned<Transform>) -> SourceResult<Transform> {
+    if transform.v.is_singular() {
+        bail!(transform.span, "gradient transform must be invertible");
+    }
+    Ok(transform.v)
+}
+
 /// Pre-processes the stops, checking that they are valid and computing the
 /// offsets if necessary.
 ///
@@ -1,44 +1,52 @@
 //! Image handling.
 
+mod pdf;
 mod raster;
 mod svg;
 
+pub use self::pdf::PdfImage;
 pub use self::raster::{RasterFormat, RasterImage};
 pub use self::svg::SvgImage;
 
 use std::ffi::OsStr;
 use std::fmt::{self, Debug, Formatter};
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use comemo::Tracked;
 use ecow::EcoString;
 
-use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::diag::{bail, warning, At, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, func, scope, Bytes, Cast, Content, NativeElement, Packed, Show, Smart,
-    StyleChain,
+    cast, elem, func, scope, Args, Bytes, Cast, Content, NativeElement, Packed, Show,
+    Smart, StyleChain,
 };
 use crate::layout::{
-    Abs, Axes, BlockElem, FixedAlignment, Frame, FrameItem, Length, Point, Region, Rel,
-    Size,
+    Abs, Axes, BlockElem, FixedAlignment, Frame, FrameItem, Length, Point, Ratio, Region,
+    Rel, Size,
 };
 use crate::loading::Readable;
 use crate::model::Figurable;
 use crate::syntax::{Span, Spanned};
 use crate::text::{families, LocalName};
-use crate::utils::LazyHash;
+use crate::utils::{LazyHash, NonZeroExt, Numeric, Scalar};
 use crate::visualize::Path;
 use crate::World;
 
 /// A raster or vector graphic.
 ///
-/// Supported formats are PNG, JPEG, GIF and SVG.
+/// Supported formats are PNG, JPEG, GIF, SVG and PDF.
 ///
 /// _Note:_ Work on SVG export is ongoing and there might be visual inaccuracies
 /// in the resulting PDF. Make sure to double-check embedded SVG images. If you
 /// have an issue, also feel free to report it on [GitHub][gh-svg].
 ///
+/// _Note:_ Pages of PDF documents are embedded as-is in PDF export. For PNG
+/// and SVG export, they are converted on a best-effort basis: Vector paths,
+/// text in TrueType, OpenType, and CFF fonts, and JPEG and 8-bit raster images
+/// are drawn, while content in other formats, like Type 3 fonts, is left out.
+///
 /// # Example
 /// ```example
 /// #figure(
@@ -59,7 +67,7 @@ pub struct ImageElem {
         let Spanned { v: path, span } =
             args.expect::<Spanned<EcoString>>("path to image file")?;
         let id = span.resolve_path(&path).at(span)?;
-        let data = engine.world.file(id).at(span)?;
+        let data = engine.file(id).at(span)?;
         path
     )]
     #[borrowed]
@@ -83,6 +91,11 @@ pub struct ImageElem {
     /// A text describing the image.
     pub alt: Option<EcoString>,
 
+    /// The page of a PDF document to embed, starting at one. Has no effect
+    /// for other image formats.
+    #[default(NonZeroUsize::ONE)]
+    pub page: NonZeroUsize,
+
     /// How the image should adjust itself to a given area (the area is defined
     /// by the `width` and `height` fields). Note that `fit` doesn't visually
     /// change anything if the area's aspect ratio is the same as the image's
@@ -96,6 +109,63 @@ pub struct ImageElem {
     /// ```
     #[default(ImageFit::Cover)]
     pub fit: ImageFit,
+
+    /// The point of the image that stays visible when it is cropped, given
+    /// as fractions of the image's width and height.
+    ///
+    /// With the default of `{(50%, 50%)}`, the image is cropped evenly on
+    /// both sides. With `{(50%, 0%)}`, its top edge stays visible. When the
+    /// image doesn't fill its area, the focus also determines where it is
+    /// placed in the area.
+    ///
+    /// ```example
+    /// #set page(width: 300pt, height: 50pt, margin: 10pt)
+    /// #image("tiger.jpg", width: 100%, focus: (50%, 0%))
+    /// #image("tiger.jpg", width: 100%, focus: (50%, 100%))
+    /// ```
+    #[default(Axes::splat(Ratio::new(0.5)))]
+    pub focus: Axes<Ratio>,
+
+    /// The resolution in dots per inch above which an image that is shrunk by
+    /// `{fit: "natural-max"}` triggers a warning.
+    ///
+    /// An image that must be shrunk a lot to fit into its container, like a
+    /// large screenshot, may end up with illegibly small details. Set this to
+    /// get notified when that happens. Defaults to `{none}`, which never
+    /// warns.
+    ///
+    /// ```example
+    /// #set image(fit: "natural-max", max-dpi: 600)
+    /// #image("tiger.jpg")
+    /// ```
+    pub max_dpi: Option<Scalar>,
+
+    /// The ICC color profile of a raster image.
+    ///
+    /// - `{auto}`: Use the profile embedded in the image, if any.
+    /// - `{none}`: Discard any embedded profile and treat the image as sRGB
+    ///   (or gray).
+    /// - A path to an ICC profile file: Use this profile instead of the
+    ///   embedded one. It must match the image's color space.
+    ///
+    /// Color profiles are preserved on PDF export. They have no effect on
+    /// vector graphics.
+    ///
+    /// ```example
+    /// #image("tiger.jpg", icc: none)
+    /// ```
+    #[parse(
+        let (icc_path, icc_data) = parse_icc(engine, args)?;
+        icc_path
+    )]
+    #[borrowed]
+    pub icc: Smart<Option<EcoString>>,
+
+    /// The raw file buffer of the ICC profile.
+    #[internal]
+    #[parse(icc_data.map(Some))]
+    #[borrowed]
+    pub icc_data: Option<Bytes>,
 }
 
 #[scope]
@@ -113,6 +183,7 @@ impl ImageElem {
     /// #image.decode(changed)
     /// ```
     #[func(title = "Decode Image")]
+    #[allow(clippy::too_many_arguments)]
     pub fn decode(
         /// The call span of this function.
         span: Span,
@@ -130,9 +201,15 @@ impl ImageElem {
         /// A text describing the image.
         #[named]
         alt: Option<Option<EcoString>>,
+        /// The page of a PDF document to embed.
+        #[named]
+        page: Option<NonZeroUsize>,
         /// How the image should adjust itself to a given area.
         #[named]
         fit: Option<ImageFit>,
+        /// Which point of the image to keep in view when it is cropped.
+        #[named]
+        focus: Option<Axes<Ratio>>,
     ) -> StrResult<Content> {
         let mut elem = ImageElem::new(EcoString::new(), data);
         if let Some(format) = format {
@@ -147,9 +224,15 @@ impl ImageElem {
         if let Some(alt) = alt {
             elem.push_alt(alt);
         }
+        if let Some(page) = page {
+            elem.push_page(page);
+        }
         if let Some(fit) = fit {
             elem.push_fit(fit);
         }
+        if let Some(focus) = focus {
+            elem.push_focus(focus);
+        }
         Ok(elem.pack().spanned(span))
     }
 }
@@ -187,16 +270,37 @@ fn layout_image(
         Smart::Auto => determine_format(elem.path().as_str(), data).at(span)?,
     };
 
+    // Find the color profile override.
+    let icc = elem
+        .icc(styles)
+        .as_ref()
+        .map(|path| path.as_ref().and(elem.icc_data(styles).clone()));
+
     // Construct the image itself.
     let image = Image::with_fonts(
         data.clone().into(),
         format,
         elem.alt(styles),
+        icc,
+        elem.page(styles),
         engine.world,
         &families(styles).map(|s| s.into()).collect::<Vec<_>>(),
     )
     .at(span)?;
 
+    // Exporters that cannot embed PDF pages convert them and drop content
+    // in formats they cannot decode.
+    if let ImageKind::Pdf(pdf) = image.kind() {
+        if pdf.is_lossy() {
+            engine.tracer.warn(warning!(
+                span,
+                "PDF page contains images or fonts in unsupported formats, \
+                 which are not shown in PNG and SVG exports";
+                hint: "export to PDF to include the complete page"
+            ));
+        }
+    }
+
     // Determine the image's pixel aspect ratio.
     let pxw = image.width();
     let pxh = image.height();
@@ -229,8 +333,13 @@ fn layout_image(
         )
     };
 
-    // Compute the actual size of the fitted image.
+    // Natural-max sizing ignores the usual target and fitting rules.
     let fit = elem.fit(styles);
+    if fit == ImageFit::NaturalMax {
+        return Ok(layout_natural_max(elem, engine, styles, region, image));
+    }
+
+    // Compute the actual size of the fitted image.
     let fitted = match fit {
         ImageFit::Cover | ImageFit::Contain => {
             if wide == (fit == ImageFit::Contain) {
@@ -239,15 +348,17 @@ fn layout_image(
                 Size::new(target.y * px_ratio, target.y)
             }
         }
-        ImageFit::Stretch => target,
+        ImageFit::Stretch | ImageFit::NaturalMax => target,
     };
 
     // First, place the image in a frame of exactly its size and then resize
-    // the frame to the target size, center aligning the image in the
+    // the frame to the target size, aligning the image's focus in the
     // process.
     let mut frame = Frame::soft(fitted);
     frame.push(Point::zero(), FrameItem::Image(image, fitted, span));
-    frame.resize(target, Axes::splat(FixedAlignment::Center));
+    let offset = (target - fitted).zip_map(elem.focus(styles), |d, r| r.of(d));
+    frame.set_size(target);
+    frame.translate(offset.to_point());
 
     // Create a clipping group if only part of the image should be visible.
     if fit == ImageFit::Cover && !target.fits(fitted) {
@@ -257,6 +368,81 @@ fn layout_image(
     Ok(frame)
 }
 
+/// Layout an image at its natural size, shrunk to the region's width if
+/// necessary.
+fn layout_natural_max(
+    elem: &Packed<ImageElem>,
+    engine: &mut Engine,
+    styles: StyleChain,
+    region: Region,
+    image: Image,
+) -> Frame {
+    let span = elem.span();
+    let dpi = image.dpi().unwrap_or(Image::DEFAULT_DPI);
+    let natural =
+        Size::new(Abs::inches(image.width() / dpi), Abs::inches(image.height() / dpi));
+
+    // Only ever shrink the image, never enlarge it.
+    let scale = if region.size.x.is_finite() && natural.x > region.size.x {
+        region.size.x / natural.x
+    } else {
+        1.0
+    };
+
+    if scale < 1.0 {
+        let effective = dpi / scale;
+        if let Some(max) = elem.max_dpi(styles).filter(|&max| effective > max.get()) {
+            engine.tracer.warn(warning!(
+                span,
+                "image was shrunk to {}dpi, which exceeds the maximum of {}dpi",
+                effective.round(),
+                max.get();
+                hint: "try giving the image more space or cropping it"
+            ));
+        }
+    }
+
+    let fitted = natural * scale;
+    let target = Size::new(
+        if region.expand.x { region.size.x } else { fitted.x },
+        if region.expand.y { region.size.y } else { fitted.y },
+    );
+
+    let mut frame = Frame::soft(fitted);
+    frame.push(Point::zero(), FrameItem::Image(image, fitted, span));
+    frame.resize(target, Axes::splat(FixedAlignment::Center));
+    frame
+}
+
+/// The value of the `icc` argument, if given, and the referenced profile.
+type ParsedIcc = (Option<Smart<Option<EcoString>>>, Option<Bytes>);
+
+/// Parses the `icc` argument and loads the referenced profile.
+fn parse_icc(engine: &mut Engine, args: &mut Args) -> SourceResult<ParsedIcc> {
+    let Some(Spanned { v: icc, span }) =
+        args.named::<Spanned<Smart<Option<EcoString>>>>("icc")?
+    else {
+        // Argument `icc` not found.
+        return Ok((None, None));
+    };
+
+    let Smart::Custom(Some(path)) = icc else {
+        // Argument `icc` is `auto` or `none`.
+        return Ok((Some(icc), None));
+    };
+
+    // Load profile file.
+    let id = span.resolve_path(&path).at(span)?;
+    let data = engine.file(id).at(span)?;
+
+    // Check for the profile file signature.
+    if data.get(36..40) != Some(b"acsp".as_slice()) {
+        bail!(span, "file `{path}` is not an ICC profile");
+    }
+
+    Ok((Some(Smart::Custom(Some(path))), Some(data)))
+}
+
 /// Determine the image format based on path and data.
 fn determine_format(path: &str, data: &Readable) -> StrResult<ImageFormat> {
     let ext = std::path::Path::new(path)
@@ -270,8 +456,12 @@ fn determine_format(path: &str, data: &Readable) -> StrResult<ImageFormat> {
         "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
         "gif" => ImageFormat::Raster(RasterFormat::Gif),
         "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
+        "pdf" => ImageFormat::Vector(VectorFormat::Pdf),
         _ => match &data {
             Readable::Str(_) => ImageFormat::Vector(VectorFormat::Svg),
+            Readable::Bytes(bytes) if bytes.starts_with(b"%PDF-") => {
+                ImageFormat::Vector(VectorFormat::Pdf)
+            }
             Readable::Bytes(bytes) => match RasterFormat::detect(bytes) {
                 Some(f) => ImageFormat::Raster(f),
                 None => bail!("unknown image format"),
@@ -295,6 +485,11 @@ pub enum ImageFit {
     /// this means that the image will be distorted (doesn't preserve aspect
     /// ratio and doesn't crop the image).
     Stretch,
+    /// The image should be shown at its natural size, but shrunk to fit the
+    /// width of its container if it is too wide (preserves aspect ratio;
+    /// doesn't crop the image). The `width` and `height` of the image only
+    /// define the space it is centered in.
+    NaturalMax,
 }
 
 /// A loaded raster or vector image.
@@ -319,6 +514,8 @@ pub enum ImageKind {
     Raster(RasterImage),
     /// An SVG image.
     Svg(SvgImage),
+    /// A page of a PDF document.
+    Pdf(PdfImage),
 }
 
 impl Image {
@@ -341,28 +538,39 @@ impl Image {
             ImageFormat::Vector(VectorFormat::Svg) => {
                 ImageKind::Svg(SvgImage::new(data)?)
             }
+            ImageFormat::Vector(VectorFormat::Pdf) => {
+                ImageKind::Pdf(PdfImage::new(data, NonZeroUsize::ONE)?)
+            }
         };
 
         Ok(Self(Arc::new(LazyHash::new(Repr { kind, alt }))))
     }
 
     /// Create a possibly font-dependant image from a buffer and a format.
+    ///
+    /// For raster images, `icc` can override the embedded color profile. For
+    /// PDF documents, `page` selects the page to embed.
     #[comemo::memoize]
     #[typst_macros::time(name = "load image")]
     pub fn with_fonts(
         data: Bytes,
         format: ImageFormat,
         alt: Option<EcoString>,
+        icc: Smart<Option<Bytes>>,
+        page: NonZeroUsize,
         world: Tracked<dyn World + '_>,
         families: &[String],
     ) -> StrResult<Image> {
         let kind = match format {
             ImageFormat::Raster(format) => {
-                ImageKind::Raster(RasterImage::new(data, format)?)
+                ImageKind::Raster(RasterImage::with_icc(data, format, icc)?)
             }
             ImageFormat::Vector(VectorFormat::Svg) => {
                 ImageKind::Svg(SvgImage::with_fonts(data, world, families)?)
             }
+            ImageFormat::Vector(VectorFormat::Pdf) => {
+                ImageKind::Pdf(PdfImage::with_fonts(data, page, world, families)?)
+            }
         };
 
         Ok(Self(Arc::new(LazyHash::new(Repr { kind, alt }))))
@@ -373,6 +581,7 @@ impl Image {
         match &self.0.kind {
             ImageKind::Raster(raster) => raster.data(),
             ImageKind::Svg(svg) => svg.data(),
+            ImageKind::Pdf(pdf) => pdf.data(),
         }
     }
 
@@ -381,6 +590,7 @@ impl Image {
         match &self.0.kind {
             ImageKind::Raster(raster) => raster.format().into(),
             ImageKind::Svg(_) => VectorFormat::Svg.into(),
+            ImageKind::Pdf(_) => VectorFormat::Pdf.into(),
         }
     }
 
@@ -389,6 +599,7 @@ impl Image {
         match &self.0.kind {
             ImageKind::Raster(raster) => raster.width() as f64,
             ImageKind::Svg(svg) => svg.width(),
+            ImageKind::Pdf(pdf) => pdf.width(),
         }
     }
 
@@ -397,6 +608,7 @@ impl Image {
         match &self.0.kind {
             ImageKind::Raster(raster) => raster.height() as f64,
             ImageKind::Svg(svg) => svg.height(),
+            ImageKind::Pdf(pdf) => pdf.height(),
         }
     }
 
@@ -404,7 +616,7 @@ impl Image {
     pub fn dpi(&self) -> Option<f64> {
         match &self.0.kind {
             ImageKind::Raster(raster) => raster.dpi(),
-            ImageKind::Svg(_) => None,
+            ImageKind::Svg(_) | ImageKind::Pdf(_) => None,
         }
     }
 
@@ -444,6 +656,8 @@ pub enum ImageFormat {
 pub enum VectorFormat {
     /// The vector graphics format of the web.
     Svg,
+    /// The Portable Document Format.
+    Pdf,
 }
 
 impl From<RasterFormat> for ImageFormat {
@@ -0,0 +1,877 @@
+//! Conversion of PDF pages into frames.
+
+use std::collections::HashMap;
+use std::io;
+use std::rc::Rc;
+
+use image::{DynamicImage, RgbaImage};
+use lopdf::content::{Content, Operation};
+use lopdf::{Dictionary, Document, Object, Stream};
+
+use super::document::{self, number, resolve};
+use super::font::PdfFont;
+use super::PdfImage;
+use crate::layout::{Abs, Frame, FrameItem, GroupItem, Point, Ratio, Size, Transform};
+use crate::syntax::Span;
+use crate::text::Font;
+use crate::utils::Scalar;
+use crate::visualize::{
+    Cmyk, Color, DashPattern, FixedStroke, Geometry, Image, LineCap, LineJoin, Luma,
+    Paint, Path, RasterFormat, Rgb, Shape,
+};
+
+/// Convert a page into a frame and determine whether content was dropped.
+pub fn page(pdf: &PdfImage) -> (Frame, bool) {
+    let size = pdf.0.size;
+    let Ok((document, id)) = document::parse(&pdf.0.data, pdf.0.page) else {
+        return (Frame::soft(size), true);
+    };
+
+    let mut converter = Converter {
+        document: &document,
+        fallbacks: &pdf.0.fallbacks,
+        frame: Frame::soft(size),
+        state: GraphicsState::new(pdf.0.transform),
+        stack: vec![],
+        path: Path::new(),
+        current: Point::zero(),
+        start: Point::zero(),
+        clip: false,
+        text_matrix: Transform::identity(),
+        line_matrix: Transform::identity(),
+        fonts: HashMap::new(),
+        depth: 0,
+        lossy: false,
+    };
+
+    match document.get_page_content(id) {
+        Ok(content) => converter.run(&content, document::resources(&document, id)),
+        Err(_) => converter.lossy = true,
+    }
+
+    (converter.frame, converter.lossy)
+}
+
+/// The maximum nesting depth of form XObjects that is followed.
+const MAX_FORM_DEPTH: usize = 16;
+
+/// Interprets content streams and collects the painted paths, text, and
+/// images into a frame.
+struct Converter<'a> {
+    document: &'a Document,
+    /// Fonts that stand in for fonts that are not embedded.
+    fallbacks: &'a [Font],
+    frame: Frame,
+    state: GraphicsState<'a>,
+    stack: Vec<GraphicsState<'a>>,
+    /// The path under construction, in frame coordinates.
+    path: Path,
+    /// The current point of the path, in frame coordinates.
+    current: Point,
+    /// The start of the current subpath, in frame coordinates.
+    start: Point,
+    /// Whether the path should become the clip path once it is painted.
+    clip: bool,
+    /// The text matrix, mapping text space into user space.
+    text_matrix: Transform,
+    /// The text matrix at the start of the current line.
+    line_matrix: Transform,
+    /// The fonts loaded so far, keyed by the address of their dictionary.
+    fonts: HashMap<usize, Option<Rc<PdfFont<'a>>>>,
+    depth: usize,
+    /// Whether content in an unsupported format was skipped.
+    lossy: bool,
+}
+
+/// The parts of the PDF graphics state that are converted.
+#[derive(Clone)]
+struct GraphicsState<'a> {
+    ctm: Transform,
+    fill: Color,
+    stroke: Color,
+    fill_alpha: f32,
+    stroke_alpha: f32,
+    line_width: f64,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f64,
+    dash: Option<(Vec<f64>, f64)>,
+    /// The clip path, in frame coordinates.
+    clip: Option<Path>,
+    text: TextState<'a>,
+}
+
+/// The text parameters of the graphics state.
+#[derive(Clone)]
+struct TextState<'a> {
+    font: Option<Rc<PdfFont<'a>>>,
+    size: f64,
+    char_spacing: f64,
+    word_spacing: f64,
+    horizontal_scale: f64,
+    leading: f64,
+    rise: f64,
+    mode: i64,
+}
+
+impl GraphicsState<'_> {
+    fn new(ctm: Transform) -> Self {
+        Self {
+            ctm,
+            fill: Color::BLACK,
+            stroke: Color::BLACK,
+            fill_alpha: 1.0,
+            stroke_alpha: 1.0,
+            line_width: 1.0,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 10.0,
+            dash: None,
+            clip: None,
+            text: TextState {
+                font: None,
+                size: 0.0,
+                char_spacing: 0.0,
+                word_spacing: 0.0,
+                horizontal_scale: 1.0,
+                leading: 0.0,
+                rise: 0.0,
+                mode: 0,
+            },
+        }
+    }
+}
+
+impl<'a> Converter<'a> {
+    /// Execute a content stream.
+    fn run(&mut self, content: &[u8], resources: Option<&'a Dictionary>) {
+        let Ok(content) = Content::decode(content) else {
+            self.lossy = true;
+            return;
+        };
+        for operation in &content.operations {
+            self.execute(operation, resources);
+        }
+    }
+
+    /// Execute a single operation.
+    fn execute(&mut self, operation: &Operation, resources: Option<&'a Dictionary>) {
+        let operands: Vec<f64> = operation
+            .operands
+            .iter()
+            .filter_map(|object| number(self.document, object))
+            .collect();
+
+        match (operation.operator.as_str(), operands.as_slice()) {
+            ("q", _) => self.stack.push(self.state.clone()),
+            ("Q", _) => {
+                if let Some(state) = self.stack.pop() {
+                    self.state = state;
+                }
+            }
+            ("cm", &[a, b, c, d, e, f]) => {
+                self.state.ctm = self.state.ctm.pre_concat(matrix([a, b, c, d, e, f]));
+            }
+
+            ("w", &[width]) => self.state.line_width = width,
+            ("J", &[cap]) => self.state.cap = line_cap(cap),
+            ("j", &[join]) => self.state.join = line_join(join),
+            ("M", &[limit]) => self.state.miter_limit = limit,
+            ("d", _) => self.state.dash = dash(self.document, &operation.operands),
+            ("gs", _) => self.apply_ext_g_state(&operation.operands, resources),
+
+            ("g" | "rg" | "k" | "sc" | "scn", operands) => {
+                if let Some(color) = color(operands) {
+                    self.state.fill = color;
+                }
+            }
+            ("G" | "RG" | "K" | "SC" | "SCN", operands) => {
+                if let Some(color) = color(operands) {
+                    self.state.stroke = color;
+                }
+            }
+            ("cs", _) => self.state.fill = Color::BLACK,
+            ("CS", _) => self.state.stroke = Color::BLACK,
+
+            ("m", &[x, y]) => {
+                let p = self.point(x, y);
+                self.path.move_to(p);
+                self.current = p;
+                self.start = p;
+            }
+            ("l", &[x, y]) => {
+                let p = self.point(x, y);
+                self.path.line_to(p);
+                self.current = p;
+            }
+            ("c", &[x1, y1, x2, y2, x3, y3]) => {
+                let p3 = self.point(x3, y3);
+                self.path.cubic_to(self.point(x1, y1), self.point(x2, y2), p3);
+                self.current = p3;
+            }
+            ("v", &[x2, y2, x3, y3]) => {
+                let p3 = self.point(x3, y3);
+                self.path.cubic_to(self.current, self.point(x2, y2), p3);
+                self.current = p3;
+            }
+            ("y", &[x1, y1, x3, y3]) => {
+                let p3 = self.point(x3, y3);
+                self.path.cubic_to(self.point(x1, y1), p3, p3);
+                self.current = p3;
+            }
+            ("h", _) => self.close(),
+            ("re", &[x, y, w, h]) => {
+                let p = self.point(x, y);
+                self.path.move_to(p);
+                self.path.line_to(self.point(x + w, y));
+                self.path.line_to(self.point(x + w, y + h));
+                self.path.line_to(self.point(x, y + h));
+                self.path.close_path();
+                self.current = p;
+                self.start = p;
+            }
+
+            ("W" | "W*", _) => self.clip = true,
+            ("f" | "F" | "f*", _) => self.paint(true, false),
+            ("S", _) => self.paint(false, true),
+            ("s", _) => {
+                self.close();
+                self.paint(false, true);
+            }
+            ("B" | "B*", _) => self.paint(true, true),
+            ("b" | "b*", _) => {
+                self.close();
+                self.paint(true, true);
+            }
+            ("n", _) => self.paint(false, false),
+
+            ("BT", _) => {
+                self.text_matrix = Transform::identity();
+                self.line_matrix = Transform::identity();
+            }
+            ("Tc", &[spacing]) => self.state.text.char_spacing = spacing,
+            ("Tw", &[spacing]) => self.state.text.word_spacing = spacing,
+            ("Tz", &[scale]) => self.state.text.horizontal_scale = scale / 100.0,
+            ("TL", &[leading]) => self.state.text.leading = leading,
+            ("Ts", &[rise]) => self.state.text.rise = rise,
+            ("Tr", &[mode]) => self.state.text.mode = mode as i64,
+            ("Tf", &[size]) => {
+                self.state.text.font = self
+                    .named_resource(b"Font", &operation.operands, resources)
+                    .and_then(|object| object.as_dict().ok())
+                    .and_then(|dict| self.load_font(dict));
+                self.state.text.size = size;
+            }
+            ("Td", &[x, y]) => self.next_line(x, y),
+            ("TD", &[x, y]) => {
+                self.state.text.leading = -y;
+                self.next_line(x, y);
+            }
+            ("Tm", &[a, b, c, d, e, f]) => {
+                self.text_matrix = matrix([a, b, c, d, e, f]);
+                self.line_matrix = self.text_matrix;
+            }
+            ("T*", _) => self.next_line(0.0, -self.state.text.leading),
+            ("Tj", _) => self.show_operands(&operation.operands),
+            ("'", _) => {
+                self.next_line(0.0, -self.state.text.leading);
+                self.show_operands(&operation.operands);
+            }
+            ("\"", &[word_spacing, char_spacing]) => {
+                self.state.text.word_spacing = word_spacing;
+                self.state.text.char_spacing = char_spacing;
+                self.next_line(0.0, -self.state.text.leading);
+                self.show_operands(&operation.operands);
+            }
+            ("TJ", _) => {
+                let Some(array) = operation
+                    .operands
+                    .first()
+                    .and_then(|object| resolve(self.document, object).as_array().ok())
+                else {
+                    return;
+                };
+                for item in array {
+                    match resolve(self.document, item) {
+                        Object::String(bytes, _) => self.show(bytes),
+                        other => {
+                            if let Some(adjustment) = number(self.document, other) {
+                                let text = &self.state.text;
+                                let tx = -adjustment / 1000.0
+                                    * text.size
+                                    * text.horizontal_scale;
+                                self.advance(tx);
+                            }
+                        }
+                    }
+                }
+            }
+
+            ("Do", _) => self.draw_x_object(&operation.operands, resources),
+            ("BI", _) => self.lossy = true,
+            _ => {}
+        }
+    }
+
+    /// Transform a point from user space into frame coordinates.
+    fn point(&self, x: f64, y: f64) -> Point {
+        Point::new(Abs::pt(x), Abs::pt(y)).transform(self.state.ctm)
+    }
+
+    /// Close the current subpath.
+    fn close(&mut self) {
+        self.path.close_path();
+        self.current = self.start;
+    }
+
+    /// Paint the current path and possibly make it the clip path.
+    fn paint(&mut self, fill: bool, stroke: bool) {
+        let path = std::mem::take(&mut self.path);
+        if std::mem::take(&mut self.clip) {
+            self.state.clip = Some(path.clone());
+        }
+        self.draw(path, fill, stroke);
+    }
+
+    /// Fill and/or stroke a path with the current graphics state.
+    fn draw(&mut self, path: Path, fill: bool, stroke: bool) {
+        if path.0.is_empty() || (!fill && !stroke) {
+            return;
+        }
+
+        let state = &self.state;
+        let fill = fill.then(|| Paint::from(with_alpha(state.fill, state.fill_alpha)));
+        let stroke = stroke.then(|| {
+            // Line widths are given in user space, so they are scaled by the
+            // current transformation. A width of zero denotes the thinnest
+            // line that can be rendered.
+            let scale = scale(state.ctm);
+            let thickness = match state.line_width * scale {
+                width if width > 0.0 => Abs::pt(width),
+                _ => Abs::pt(0.25),
+            };
+
+            FixedStroke {
+                paint: with_alpha(state.stroke, state.stroke_alpha).into(),
+                thickness,
+                cap: state.cap,
+                join: state.join,
+                dash: state.dash.as_ref().map(|(array, phase)| DashPattern {
+                    array: array.iter().map(|v| Abs::pt(v * scale)).collect(),
+                    phase: Abs::pt(phase * scale),
+                }),
+                miter_limit: Scalar::new(state.miter_limit),
+            }
+        });
+
+        let shape = Shape {
+            geometry: Geometry::Path(path),
+            fill,
+            stroke,
+            blur: Abs::zero(),
+        };
+
+        self.push(FrameItem::Shape(shape, Span::detached()));
+    }
+
+    /// Add an item to the frame, clipped to the current clip path.
+    fn push(&mut self, item: FrameItem) {
+        match &self.state.clip {
+            Some(clip) => {
+                let mut clipped = Frame::soft(self.frame.size());
+                clipped.push(Point::zero(), item);
+                clipped.clip(clip.clone());
+                self.frame.push_frame(Point::zero(), clipped);
+            }
+            None => self.frame.push(Point::zero(), item),
+        }
+    }
+
+    /// Apply the parameters of a named graphics state dictionary.
+    fn apply_ext_g_state(
+        &mut self,
+        operands: &[Object],
+        resources: Option<&'a Dictionary>,
+    ) {
+        let Some(dict) = self
+            .named_resource(b"ExtGState", operands, resources)
+            .and_then(|object| object.as_dict().ok())
+        else {
+            return;
+        };
+
+        let document = self.document;
+        let get = |key: &[u8]| dict.get(key).ok().and_then(|v| number(document, v));
+        if let Some(alpha) = get(b"CA") {
+            self.state.stroke_alpha = alpha.clamp(0.0, 1.0) as f32;
+        }
+        if let Some(alpha) = get(b"ca") {
+            self.state.fill_alpha = alpha.clamp(0.0, 1.0) as f32;
+        }
+        if let Some(width) = get(b"LW") {
+            self.state.line_width = width;
+        }
+        if let Some(cap) = get(b"LC") {
+            self.state.cap = line_cap(cap);
+        }
+        if let Some(join) = get(b"LJ") {
+            self.state.join = line_join(join);
+        }
+        if let Some(limit) = get(b"ML") {
+            self.state.miter_limit = limit;
+        }
+        if let Ok(object) = dict.get(b"D") {
+            if let Ok(array) = resolve(document, object).as_array() {
+                self.state.dash = dash(document, array);
+            }
+        }
+        if let Ok(array) = dict.get(b"Font").and_then(|v| resolve(document, v).as_array())
+        {
+            if let [font, size] = array.as_slice() {
+                let font = resolve(document, font).as_dict().ok();
+                self.state.text.font = font.and_then(|dict| self.load_font(dict));
+                self.state.text.size = number(document, size).unwrap_or(0.0);
+            }
+        }
+    }
+
+    /// Draw a form or image XObject.
+    fn draw_x_object(&mut self, operands: &[Object], resources: Option<&'a Dictionary>) {
+        let Some(Object::Stream(stream)) =
+            self.named_resource(b"XObject", operands, resources)
+        else {
+            return;
+        };
+
+        let subtype = stream.dict.get(b"Subtype").and_then(Object::as_name).ok();
+        match subtype {
+            Some(b"Image") => self.draw_image(stream),
+            Some(b"Form") if self.depth < MAX_FORM_DEPTH => {
+                let content = stream
+                    .decompressed_content()
+                    .unwrap_or_else(|_| stream.content.clone());
+
+                let form_resources = stream
+                    .dict
+                    .get(b"Resources")
+                    .ok()
+                    .and_then(|object| resolve(self.document, object).as_dict().ok())
+                    .or(resources);
+
+                let transform = stream
+                    .dict
+                    .get(b"Matrix")
+                    .ok()
+                    .and_then(|object| self.matrix(object))
+                    .unwrap_or_else(Transform::identity);
+
+                let ctm = self.state.ctm.pre_concat(transform);
+                self.run_nested(&content, form_resources, ctm);
+            }
+            _ => {}
+        }
+    }
+
+    /// Execute a form content stream with its own transformation
+    /// and restore the state afterwards.
+    fn run_nested(
+        &mut self,
+        content: &[u8],
+        resources: Option<&'a Dictionary>,
+        ctm: Transform,
+    ) {
+        let (path, current, start, clip) = (
+            std::mem::take(&mut self.path),
+            self.current,
+            self.start,
+            std::mem::take(&mut self.clip),
+        );
+        let (text_matrix, line_matrix) = (self.text_matrix, self.line_matrix);
+        let stack = std::mem::take(&mut self.stack);
+        let saved = self.state.clone();
+
+        self.state.ctm = ctm;
+        self.depth += 1;
+        self.run(content, resources);
+        self.depth -= 1;
+
+        self.state = saved;
+        self.stack = stack;
+        (self.text_matrix, self.line_matrix) = (text_matrix, line_matrix);
+        (self.path, self.current, self.start, self.clip) = (path, current, start, clip);
+    }
+
+    /// Draw an image XObject into the unit square of user space.
+    fn draw_image(&mut self, stream: &'a Stream) {
+        let Some(image) = decode_image(self.document, stream) else {
+            self.lossy = true;
+            return;
+        };
+
+        // Images are painted upside down into the unit square.
+        let unit = Size::splat(Abs::pt(1.0));
+        let flip = matrix([1.0, 0.0, 0.0, -1.0, 0.0, 1.0]);
+        let mut frame = Frame::soft(unit);
+        frame.push(Point::zero(), FrameItem::Image(image, unit, Span::detached()));
+        frame.transform(self.state.ctm.pre_concat(flip));
+        self.push(FrameItem::Group(GroupItem::new(frame)));
+    }
+
+    /// Look up a resource by the name given in the operands.
+    fn named_resource(
+        &self,
+        category: &[u8],
+        operands: &[Object],
+        resources: Option<&'a Dictionary>,
+    ) -> Option<&'a Object> {
+        let name = operands.first()?.as_name().ok()?;
+        let category = resolve(self.document, resources?.get(category).ok()?);
+        let object = category.as_dict().ok()?.get(name).ok()?;
+        Some(resolve(self.document, object))
+    }
+
+    /// Read a matrix from an array.
+    fn matrix(&self, object: &Object) -> Option<Transform> {
+        let array = resolve(self.document, object).as_array().ok()?;
+        let values: Vec<f64> =
+            array.iter().filter_map(|v| number(self.document, v)).collect();
+        Some(matrix(values.try_into().ok()?))
+    }
+
+    /// Move to the start of the next line, offset from the start of the
+    /// current one.
+    fn next_line(&mut self, x: f64, y: f64) {
+        self.line_matrix =
+            self.line_matrix.pre_concat(matrix([1.0, 0.0, 0.0, 1.0, x, y]));
+        self.text_matrix = self.line_matrix;
+    }
+
+    /// Move the text position horizontally by the given distance in text
+    /// space.
+    fn advance(&mut self, tx: f64) {
+        self.text_matrix =
+            self.text_matrix.pre_concat(matrix([1.0, 0.0, 0.0, 1.0, tx, 0.0]));
+    }
+
+    /// Show the string in the last operand.
+    fn show_operands(&mut self, operands: &[Object]) {
+        if let Some(Object::String(bytes, _)) =
+            operands.last().map(|v| resolve(self.document, v))
+        {
+            self.show(bytes);
+        }
+    }
+
+    /// Show a string of character codes in the current font.
+    fn show(&mut self, bytes: &[u8]) {
+        let Some(font) = self.state.text.font.clone() else {
+            self.lossy = true;
+            return;
+        };
+
+        let text = self.state.text.clone();
+        let fill = matches!(text.mode, 0 | 2 | 4 | 6);
+        let stroke = matches!(text.mode, 1 | 2 | 5 | 6);
+        let params = matrix([
+            text.size * text.horizontal_scale,
+            0.0,
+            0.0,
+            text.size,
+            0.0,
+            text.rise,
+        ]);
+
+        let mut path = Path::new();
+        for code in font.codes(bytes) {
+            let transform =
+                self.state.ctm.pre_concat(self.text_matrix).pre_concat(params);
+            if fill || stroke {
+                match font.glyph(code) {
+                    Some(outline) => outline.build(&mut path, transform),
+                    None => self.lossy |= !font.is_empty(code),
+                }
+            }
+
+            let mut tx = font.advance(code) * text.size + text.char_spacing;
+            if code == 32 && !font.wide {
+                tx += text.word_spacing;
+            }
+            self.advance(tx * text.horizontal_scale);
+        }
+
+        self.draw(path, fill, stroke);
+    }
+
+    /// Load a font from its dictionary.
+    fn load_font(&mut self, dict: &'a Dictionary) -> Option<Rc<PdfFont<'a>>> {
+        let key = dict as *const Dictionary as usize;
+        if let Some(font) = self.fonts.get(&key) {
+            return font.clone();
+        }
+        let font = PdfFont::load(self.document, dict, self.fallbacks).map(Rc::new);
+        if font.is_none() {
+            self.lossy = true;
+        }
+        self.fonts.insert(key, font.clone());
+        font
+    }
+}
+
+/// A color space of an image.
+enum ColorSpace {
+    Gray,
+    Rgb,
+    Cmyk,
+}
+
+impl ColorSpace {
+    /// Read a color space.
+    fn read(document: &Document, object: &Object) -> Option<Self> {
+        let object = resolve(document, object);
+        if let Ok(name) = object.as_name() {
+            return match name {
+                b"DeviceGray" | b"CalGray" | b"G" => Some(Self::Gray),
+                b"DeviceRGB" | b"CalRGB" | b"RGB" => Some(Self::Rgb),
+                b"DeviceCMYK" | b"CMYK" => Some(Self::Cmyk),
+                _ => None,
+            };
+        }
+
+        let array = object.as_array().ok()?;
+        let family = resolve(document, array.first()?).as_name().ok()?;
+        match family {
+            b"CalGray" => Some(Self::Gray),
+            b"CalRGB" => Some(Self::Rgb),
+            b"ICCBased" => {
+                let Object::Stream(stream) = resolve(document, array.get(1)?) else {
+                    return None;
+                };
+                let alternate = stream.dict.get(b"Alternate").ok();
+                let n = stream.dict.get(b"N").ok().map(|n| resolve(document, n));
+                match n.and_then(|n| n.as_i64().ok()) {
+                    Some(1) => Some(Self::Gray),
+                    Some(3) => Some(Self::Rgb),
+                    Some(4) => Some(Self::Cmyk),
+                    _ => Self::read(document, alternate?),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The number of components per sample.
+    fn components(&self) -> usize {
+        match self {
+            Self::Gray => 1,
+            Self::Rgb => 3,
+            Self::Cmyk => 4,
+        }
+    }
+
+    /// Convert components in the range from zero to one into RGB.
+    fn to_rgb(&self, c: &[f64]) -> [u8; 3] {
+        let byte = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        match self {
+            Self::Gray => [byte(c[0]); 3],
+            Self::Rgb => [byte(c[0]), byte(c[1]), byte(c[2])],
+            Self::Cmyk => {
+                let k = 1.0 - c[3];
+                [byte((1.0 - c[0]) * k), byte((1.0 - c[1]) * k), byte((1.0 - c[2]) * k)]
+            }
+        }
+    }
+}
+
+/// Decode an image XObject into an image.
+///
+/// Supports JPEG images and uncompressed or deflated images with eight bits
+/// per component, with an optional soft mask.
+fn decode_image(document: &Document, stream: &Stream) -> Option<Image> {
+    let rgba = decode_samples(document, stream, true)?;
+    let mut data = vec![];
+    DynamicImage::ImageRgba8(rgba)
+        .write_to(&mut io::Cursor::new(&mut data), image::ImageFormat::Png)
+        .ok()?;
+    Image::new(data.into(), RasterFormat::Png.into(), None).ok()
+}
+
+/// Decode the samples of an image XObject, including its soft mask if
+/// `with_mask` is set.
+///
+/// A soft mask is decoded without its own soft mask, so that masks referring
+/// to themselves or to further masks can't recurse endlessly.
+fn decode_samples(
+    document: &Document,
+    stream: &Stream,
+    with_mask: bool,
+) -> Option<RgbaImage> {
+    let dict = &stream.dict;
+    let get = |key: &[u8]| dict.get(key).ok().map(|object| resolve(document, object));
+    let width = get(b"Width").and_then(|v| number(document, v))? as u32;
+    let height = get(b"Height").and_then(|v| number(document, v))? as u32;
+    if width == 0 || height == 0 || u64::from(width) * u64::from(height) > 1 << 26 {
+        return None;
+    }
+
+    let mut rgba = if filters(document, dict).last() == Some(&b"DCTDecode".as_slice()) {
+        let data = decompress(stream, true)?;
+        let image = image::load_from_memory_with_format(&data, image::ImageFormat::Jpeg);
+        image.ok()?.to_rgba8()
+    } else {
+        let space = ColorSpace::read(document, get(b"ColorSpace")?)?;
+        let bits = get(b"BitsPerComponent").and_then(|v| number(document, v))?;
+        if bits != 8.0 {
+            return None;
+        }
+
+        let data = decompress(stream, false)?;
+        let n = space.components();
+        if data.len() < width as usize * height as usize * n {
+            return None;
+        }
+
+        let mut components = vec![0.0; n];
+        RgbaImage::from_fn(width, height, |x, y| {
+            let start = (y as usize * width as usize + x as usize) * n;
+            for (i, component) in components.iter_mut().enumerate() {
+                *component = f64::from(data[start + i]) / 255.0;
+            }
+            let [r, g, b] = space.to_rgb(&components);
+            image::Rgba([r, g, b, 255])
+        })
+    };
+
+    if let Some(Object::Stream(mask)) = get(b"SMask").filter(|_| with_mask) {
+        let mut alpha =
+            image::imageops::grayscale(&decode_samples(document, mask, false)?);
+        if alpha.dimensions() != rgba.dimensions() {
+            // JPEG images take their size from the encoded data, which may
+            // differ from the dictionary's.
+            let (width, height) = rgba.dimensions();
+            alpha = image::imageops::resize(
+                &alpha,
+                width,
+                height,
+                image::imageops::FilterType::Triangle,
+            );
+        }
+        for (pixel, mask) in rgba.pixels_mut().zip(alpha.pixels()) {
+            pixel.0[3] = mask.0[0];
+        }
+    }
+
+    Some(rgba)
+}
+
+/// The names of a stream's filters, in decoding order.
+fn filters<'a>(document: &'a Document, dict: &'a Dictionary) -> Vec<&'a [u8]> {
+    match dict.get(b"Filter").map(|v| resolve(document, v)) {
+        Ok(Object::Name(name)) => vec![name.as_slice()],
+        Ok(Object::Array(array)) => array
+            .iter()
+            .filter_map(|v| resolve(document, v).as_name().ok())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Apply a stream's filters, except for a final `DCTDecode` filter if
+/// `keep_dct` is set. Returns `None` for unsupported filters.
+fn decompress(stream: &Stream, keep_dct: bool) -> Option<Vec<u8>> {
+    let mut stream = stream.clone();
+    if keep_dct {
+        let mut names: Vec<Object> = match stream.dict.get(b"Filter") {
+            Ok(Object::Array(array)) => array.clone(),
+            Ok(name) => vec![name.clone()],
+            Err(_) => vec![],
+        };
+        names.pop();
+        stream.dict.set("Filter", Object::Array(names));
+    }
+
+    let has_filters = match stream.dict.get(b"Filter") {
+        Ok(Object::Array(array)) => !array.is_empty(),
+        Ok(_) => true,
+        Err(_) => false,
+    };
+    if !has_filters {
+        return Some(stream.content);
+    }
+
+    // Image streams are not decompressed by the parser, so present the
+    // stream as a plain one.
+    stream.dict.remove(b"Subtype");
+    stream.decompressed_content().ok()
+}
+
+/// The factor by which a transform scales lengths on average.
+fn scale(ts: Transform) -> f64 {
+    (ts.sx.get() * ts.sy.get() - ts.kx.get() * ts.ky.get()).abs().sqrt()
+}
+
+/// Create a transform from the six entries of a PDF matrix.
+pub fn matrix([a, b, c, d, e, f]: [f64; 6]) -> Transform {
+    Transform {
+        sx: Ratio::new(a),
+        ky: Ratio::new(b),
+        kx: Ratio::new(c),
+        sy: Ratio::new(d),
+        tx: Abs::pt(e),
+        ty: Abs::pt(f),
+    }
+}
+
+/// Create a color from its components, guessing the color space from the
+/// number of components.
+fn color(components: &[f64]) -> Option<Color> {
+    let c = |i: usize| components[i].clamp(0.0, 1.0) as f32;
+    Some(match components.len() {
+        1 => Color::Luma(Luma::new(c(0), 1.0)),
+        3 => Color::Rgb(Rgb::new(c(0), c(1), c(2), 1.0)),
+        4 => Color::Cmyk(Cmyk { c: c(0), m: c(1), y: c(2), k: c(3) }),
+        _ => return None,
+    })
+}
+
+/// Apply an opacity to a color. CMYK colors are converted to RGB for
+/// translucency since they have no alpha channel.
+fn with_alpha(color: Color, alpha: f32) -> Color {
+    match color {
+        _ if alpha >= 1.0 => color,
+        Color::Cmyk(_) => color.to_rgb().with_alpha(alpha),
+        _ => color.with_alpha(alpha),
+    }
+}
+
+/// Convert a PDF line cap style.
+fn line_cap(value: f64) -> LineCap {
+    match value as i64 {
+        1 => LineCap::Round,
+        2 => LineCap::Square,
+        _ => LineCap::Butt,
+    }
+}
+
+/// Convert a PDF line join style.
+fn line_join(value: f64) -> LineJoin {
+    match value as i64 {
+        1 => LineJoin::Round,
+        2 => LineJoin::Bevel,
+        _ => LineJoin::Miter,
+    }
+}
+
+/// Read a dash pattern from an array and a phase.
+fn dash(document: &Document, operands: &[Object]) -> Option<(Vec<f64>, f64)> {
+    let [array, phase] = operands else { return None };
+    let array: Vec<f64> = resolve(document, array)
+        .as_array()
+        .ok()?
+        .iter()
+        .filter_map(|v| number(document, v))
+        .collect();
+    if array.is_empty() || array.iter().all(|&v| v <= 0.0) {
+        return None;
+    }
+    Some((array, number(document, phase).unwrap_or(0.0)))
+}
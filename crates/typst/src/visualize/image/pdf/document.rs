@@ -0,0 +1,156 @@
+//! Reading the page tree of a PDF document.
+
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+
+use ecow::{eco_format, EcoString};
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+use crate::diag::{bail, StrResult};
+use crate::layout::{Abs, Ratio, Size, Transform};
+
+/// Parse a document and find the object ID of a page in it.
+pub fn parse(data: &[u8], page: NonZeroUsize) -> StrResult<(Document, ObjectId)> {
+    let document = Document::load_mem(data)
+        .map_err(|err| eco_format!("failed to parse PDF ({err})"))?;
+    if document.is_encrypted() {
+        bail!("encrypted PDFs are not supported");
+    }
+
+    let pages = document.get_pages();
+    let Some(&id) = pages.get(&(page.get() as u32)) else {
+        return Err(format_page_error(page, pages.len()));
+    };
+
+    Ok((document, id))
+}
+
+/// Determine the visible area of a page, its displayed size, and the
+/// transform from its coordinate system into the top-left based coordinate
+/// system of the image.
+///
+/// Also checks that the page can be embedded as is.
+pub fn geometry(
+    data: &[u8],
+    page: NonZeroUsize,
+) -> StrResult<([f64; 4], Size, Transform)> {
+    let (document, id) = parse(data, page)?;
+
+    let bbox = inherited(&document, id, b"CropBox")
+        .or_else(|| inherited(&document, id, b"MediaBox"))
+        .and_then(|object| rect(&document, object))
+        .ok_or("PDF page has no valid media box")?;
+
+    let rotation = inherited(&document, id, b"Rotate")
+        .and_then(|object| object.as_i64().ok())
+        .unwrap_or(0)
+        .rem_euclid(360);
+
+    let (size, transform) = orient(bbox, rotation);
+    if !size.is_finite() || size.x <= Abs::zero() || size.y <= Abs::zero() {
+        bail!("PDF page has an empty media box");
+    }
+
+    check_integers(&document, resources(&document, id))?;
+    Ok((bbox, size, transform))
+}
+
+/// The resource dictionary of a page, if any.
+pub fn resources(document: &Document, id: ObjectId) -> Option<&Dictionary> {
+    inherited(document, id, b"Resources").and_then(|object| object.as_dict().ok())
+}
+
+/// Follow a reference to the object it points to.
+pub fn resolve<'a>(document: &'a Document, mut object: &'a Object) -> &'a Object {
+    for _ in 0..32 {
+        let Object::Reference(id) = object else { break };
+        let Ok(target) = document.get_object(*id) else { break };
+        object = target;
+    }
+    object
+}
+
+/// Read a number from an object.
+pub fn number(document: &Document, object: &Object) -> Option<f64> {
+    resolve(document, object).as_float().ok().map(f64::from)
+}
+
+/// Look up a page attribute, which may be inherited from the page tree.
+fn inherited<'a>(
+    document: &'a Document,
+    mut id: ObjectId,
+    key: &[u8],
+) -> Option<&'a Object> {
+    for _ in 0..64 {
+        let dict = document.get_dictionary(id).ok()?;
+        if let Ok(object) = dict.get(key) {
+            return Some(resolve(document, object));
+        }
+        id = dict.get(b"Parent").and_then(Object::as_reference).ok()?;
+    }
+    None
+}
+
+/// Read a rectangle from an array, normalized to `[left, bottom, right, top]`.
+fn rect(document: &Document, object: &Object) -> Option<[f64; 4]> {
+    let array = resolve(document, object).as_array().ok()?;
+    let [x0, y0, x1, y1] = array.as_slice() else { return None };
+    let [x0, y0, x1, y1] = [x0, y0, x1, y1].map(|v| number(document, v));
+    let (x0, y0, x1, y1) = (x0?, y0?, x1?, y1?);
+    Some([x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)])
+}
+
+/// Compute the displayed size of a page and the transform from its
+/// coordinate system into the top-left based coordinate system of the image.
+fn orient([x0, y0, x1, y1]: [f64; 4], rotation: i64) -> (Size, Transform) {
+    let (w, h) = (x1 - x0, y1 - y0);
+    let matrix = |sx, ky, kx, sy, tx: f64, ty: f64| Transform {
+        sx: Ratio::new(sx),
+        ky: Ratio::new(ky),
+        kx: Ratio::new(kx),
+        sy: Ratio::new(sy),
+        tx: Abs::pt(tx),
+        ty: Abs::pt(ty),
+    };
+
+    match rotation {
+        90 => (Size::new(Abs::pt(h), Abs::pt(w)), matrix(0.0, 1.0, 1.0, 0.0, -y0, -x0)),
+        180 => (Size::new(Abs::pt(w), Abs::pt(h)), matrix(-1.0, 0.0, 0.0, 1.0, x1, -y0)),
+        270 => (Size::new(Abs::pt(h), Abs::pt(w)), matrix(0.0, -1.0, -1.0, 0.0, y1, x1)),
+        _ => (Size::new(Abs::pt(w), Abs::pt(h)), matrix(1.0, 0.0, 0.0, -1.0, -x0, y1)),
+    }
+}
+
+/// Check that all integers the page's resources depend on fit into 32 bits,
+/// which is the range exporters can write.
+fn check_integers(document: &Document, resources: Option<&Dictionary>) -> StrResult<()> {
+    let mut seen = HashSet::new();
+    let mut stack: Vec<&Object> = resources
+        .iter()
+        .flat_map(|dict| dict.iter())
+        .map(|(_, v)| v)
+        .collect();
+    while let Some(object) = stack.pop() {
+        match object {
+            Object::Integer(v) if i32::try_from(*v).is_err() => {
+                bail!("PDF page contains an integer that is out of range ({v})");
+            }
+            Object::Reference(id) if seen.insert(*id) => {
+                if let Ok(target) = document.get_object(*id) {
+                    stack.push(target);
+                }
+            }
+            Object::Array(items) => stack.extend(items),
+            Object::Dictionary(dict) => stack.extend(dict.iter().map(|(_, v)| v)),
+            Object::Stream(stream) => stack.extend(stream.dict.iter().map(|(_, v)| v)),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Format the error for a page number that is out of range.
+fn format_page_error(page: NonZeroUsize, count: usize) -> EcoString {
+    let noun = if count == 1 { "page" } else { "pages" };
+    eco_format!("page {page} does not exist (the document has {count} {noun})")
+}
@@ -0,0 +1,487 @@
+//! Fonts of PDF documents.
+
+use std::collections::HashMap;
+
+use lopdf::{Dictionary, Document, Object, Stream};
+
+use super::convert::matrix;
+use super::document::{number, resolve};
+use crate::layout::{Abs, Point, Transform};
+use crate::text::Font;
+use crate::visualize::Path;
+
+/// A font on the page, as far as it is needed to draw its glyphs.
+///
+/// Glyphs come from embedded TrueType, OpenType, and CFF font programs.
+/// Fonts that are not embedded or whose programs are in the Type 1 format
+/// are drawn with a fallback font. Type 3 fonts are not supported.
+pub struct PdfFont<'a> {
+    /// Where the glyphs come from.
+    program: Program,
+    /// Whether character codes are two bytes wide.
+    pub wide: bool,
+    /// Whether the font uses its own encoding instead of a standard one.
+    symbolic: bool,
+    /// The name of the standard encoding the codes are based on, if any.
+    base_encoding: Option<&'a str>,
+    /// Glyph names for character codes that differ from the base encoding.
+    differences: HashMap<u32, &'a str>,
+    /// Maps CIDs to glyph IDs, if not the identity.
+    cid_to_gid: Option<Vec<u16>>,
+    /// The advance widths of character codes, in thousandths of text space
+    /// units.
+    widths: HashMap<u32, f64>,
+    /// The width of codes without an explicit width.
+    default_width: Option<f64>,
+}
+
+/// The source of a font's glyphs.
+enum Program {
+    /// A TrueType or OpenType font program.
+    Sfnt(Vec<u8>),
+    /// A bare CFF font program.
+    Cff(Vec<u8>),
+    /// A font that stands in for a font that is not embedded.
+    Fallback(Font),
+}
+
+/// A glyph outline in a font program.
+pub enum Outline<'f> {
+    Sfnt(Box<ttf_parser::Face<'f>>, ttf_parser::GlyphId),
+    Cff(Box<ttf_parser::cff::Table<'f>>, ttf_parser::GlyphId),
+}
+
+impl<'a> PdfFont<'a> {
+    /// Load a font from its dictionary.
+    ///
+    /// Returns `None` if the font is in an unsupported format.
+    pub fn load(
+        document: &'a Document,
+        dict: &'a Dictionary,
+        fallbacks: &[Font],
+    ) -> Option<Self> {
+        let get = |dict: &'a Dictionary, key: &[u8]| {
+            dict.get(key).ok().map(|object| resolve(document, object))
+        };
+        let name = |dict: &'a Dictionary, key: &[u8]| {
+            get(dict, key).and_then(|object| object.as_name().ok())
+        };
+
+        let subtype = name(dict, b"Subtype")?;
+        if subtype == b"Type3" {
+            return None;
+        }
+
+        let wide = subtype == b"Type0";
+        let mut widths = HashMap::new();
+        let mut default_width = None;
+        let mut cid_to_gid = None;
+        let mut base_encoding = None;
+        let mut differences = HashMap::new();
+
+        // Composite fonts describe their glyphs in a descendant font.
+        let described = if wide {
+            // Only the identity encodings map codes directly to CIDs.
+            let encoding = name(dict, b"Encoding")?;
+            if encoding != b"Identity-H" && encoding != b"Identity-V" {
+                return None;
+            }
+
+            let descendant = get(dict, b"DescendantFonts")
+                .and_then(|object| object.as_array().ok())
+                .and_then(|array| array.first())
+                .and_then(|object| resolve(document, object).as_dict().ok())?;
+            default_width = get(descendant, b"DW")
+                .and_then(|v| number(document, v))
+                .or(Some(1000.0));
+            if let Some(array) = get(descendant, b"W").and_then(|v| v.as_array().ok()) {
+                widths = cid_widths(document, array);
+            }
+            if let Some(Object::Stream(stream)) = get(descendant, b"CIDToGIDMap") {
+                if let Ok(data) = stream.decompressed_content() {
+                    cid_to_gid = Some(
+                        data.chunks_exact(2)
+                            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                            .collect(),
+                    );
+                }
+            }
+            descendant
+        } else {
+            let first = get(dict, b"FirstChar").and_then(|v| number(document, v));
+            let array = get(dict, b"Widths").and_then(|v| v.as_array().ok());
+            if let (Some(first), Some(array)) = (first, array) {
+                for (i, width) in array.iter().enumerate() {
+                    if let Some(width) = number(document, width) {
+                        widths.insert(first as u32 + i as u32, width);
+                    }
+                }
+            }
+
+            match get(dict, b"Encoding") {
+                Some(Object::Name(name)) => {
+                    base_encoding = std::str::from_utf8(name).ok();
+                }
+                Some(Object::Dictionary(encoding)) => {
+                    base_encoding = name(encoding, b"BaseEncoding")
+                        .and_then(|name| std::str::from_utf8(name).ok());
+                    if let Some(array) =
+                        get(encoding, b"Differences").and_then(|v| v.as_array().ok())
+                    {
+                        differences = read_differences(document, array);
+                    }
+                }
+                _ => {}
+            }
+            dict
+        };
+
+        let descriptor = get(described, b"FontDescriptor").and_then(|v| v.as_dict().ok());
+        let flags = descriptor
+            .and_then(|d| get(d, b"Flags"))
+            .and_then(|v| v.as_i64().ok());
+        if default_width.is_none() {
+            default_width = descriptor
+                .and_then(|d| get(d, b"MissingWidth"))
+                .and_then(|v| number(document, v));
+        }
+
+        let file = |key: &[u8]| match descriptor.and_then(|d| get(d, key)) {
+            Some(Object::Stream(stream)) => Some(stream),
+            _ => None,
+        };
+        let decompressed = |stream: &Stream| {
+            stream
+                .decompressed_content()
+                .unwrap_or_else(|_| stream.content.clone())
+        };
+
+        let program = if let Some(stream) = file(b"FontFile2") {
+            Program::Sfnt(decompressed(stream))
+        } else if let Some(stream) = file(b"FontFile3") {
+            match name(&stream.dict, b"Subtype") {
+                Some(b"OpenType") => Program::Sfnt(decompressed(stream)),
+                _ => Program::Cff(decompressed(stream)),
+            }
+        } else {
+            let base = name(dict, b"BaseFont").unwrap_or_default();
+            let fallback =
+                fallbacks.get(fallback_index(base)).or(fallbacks.first())?.clone();
+            Program::Fallback(fallback)
+        };
+
+        Some(Self {
+            program,
+            wide,
+            symbolic: flags.is_some_and(|flags| flags & 4 != 0),
+            base_encoding,
+            differences,
+            cid_to_gid,
+            widths,
+            default_width,
+        })
+    }
+
+    /// Split a string into character codes.
+    pub fn codes<'s>(&self, bytes: &'s [u8]) -> impl Iterator<Item = u32> + 's {
+        let size = if self.wide { 2 } else { 1 };
+        bytes
+            .chunks(size)
+            .map(|chunk| chunk.iter().fold(0, |code, &byte| code << 8 | u32::from(byte)))
+    }
+
+    /// Whether a code has no glyph on purpose, like a space.
+    pub fn is_empty(&self, code: u32) -> bool {
+        self.unicode(code).map_or(true, char::is_whitespace)
+    }
+
+    /// The advance width of a code in text space units.
+    pub fn advance(&self, code: u32) -> f64 {
+        if let Some(width) = self.widths.get(&code).copied().or(self.default_width) {
+            return width / 1000.0;
+        }
+
+        match &self.program {
+            Program::Fallback(font) => self
+                .unicode(code)
+                .and_then(|c| font.ttf().glyph_index(c))
+                .and_then(|id| font.ttf().glyph_hor_advance(id))
+                .map_or(0.0, |advance| font.to_em(advance).get()),
+            Program::Sfnt(data) => ttf_parser::Face::parse(data, 0)
+                .ok()
+                .and_then(|face| {
+                    let Outline::Sfnt(face, id) = self.sfnt_glyph(face, code)? else {
+                        return None;
+                    };
+                    let advance = face.glyph_hor_advance(id)?;
+                    Some(f64::from(advance) / f64::from(face.units_per_em()))
+                })
+                .unwrap_or(0.0),
+            Program::Cff(data) => ttf_parser::cff::Table::parse(data)
+                .and_then(|table| {
+                    let Outline::Cff(table, id) = self.cff_glyph(table, code)? else {
+                        return None;
+                    };
+                    Some(f64::from(table.glyph_width(id)?) * table.matrix().sx as f64)
+                })
+                .unwrap_or(0.0),
+        }
+    }
+
+    /// Find the glyph outline for a code.
+    pub fn glyph(&self, code: u32) -> Option<Outline<'_>> {
+        match &self.program {
+            Program::Sfnt(data) => {
+                let face = ttf_parser::Face::parse(data, 0).ok()?;
+                self.sfnt_glyph(face, code)
+            }
+            Program::Cff(data) => {
+                let table = ttf_parser::cff::Table::parse(data)?;
+                self.cff_glyph(table, code)
+            }
+            Program::Fallback(font) => {
+                let c = self.unicode(code)?;
+                let face = font.ttf().clone();
+                let id = face.glyph_index(c)?;
+                Some(Outline::Sfnt(Box::new(face), id))
+            }
+        }
+    }
+
+    /// Find the glyph for a code in a TrueType or OpenType font program.
+    fn sfnt_glyph<'f>(
+        &self,
+        face: ttf_parser::Face<'f>,
+        code: u32,
+    ) -> Option<Outline<'f>> {
+        use ttf_parser::{GlyphId, PlatformId};
+
+        let id = if self.wide {
+            Some(GlyphId(self.cid_to_gid(code)))
+        } else if let Some(name) = self.differences.get(&code) {
+            face.glyph_index_by_name(name)
+                .or_else(|| glyph_name_to_unicode(name).and_then(|c| face.glyph_index(c)))
+        } else {
+            let unicode = (!self.symbolic)
+                .then(|| self.unicode(code))
+                .flatten()
+                .and_then(|c| face.glyph_index(c));
+            unicode.or_else(|| {
+                // Symbolic fonts map codes directly through their cmap.
+                let subtables = face.tables().cmap?.subtables;
+                subtables.into_iter().find_map(|subtable| {
+                    match (subtable.platform_id, subtable.encoding_id) {
+                        (PlatformId::Windows, 0) => subtable
+                            .glyph_index(0xF000 + code)
+                            .or_else(|| subtable.glyph_index(code)),
+                        (PlatformId::Macintosh, 0) => subtable.glyph_index(code),
+                        _ => None,
+                    }
+                })
+            })
+        }?;
+        Some(Outline::Sfnt(Box::new(face), id))
+    }
+
+    /// Find the glyph for a code in a CFF font program.
+    fn cff_glyph<'f>(
+        &self,
+        table: ttf_parser::cff::Table<'f>,
+        code: u32,
+    ) -> Option<Outline<'f>> {
+        use ttf_parser::GlyphId;
+
+        let id = if self.wide {
+            // CID-keyed fonts map CIDs to glyphs through their charset.
+            let cid = self.cid_to_gid(code);
+            (0..table.number_of_glyphs())
+                .map(GlyphId)
+                .find(|&id| table.glyph_cid(id) == Some(cid))
+                .or(Some(GlyphId(cid)))
+        } else if let Some(name) = self.differences.get(&code) {
+            table.glyph_index_by_name(name)
+        } else {
+            table.glyph_index(u8::try_from(code).ok()?)
+        }?;
+        Some(Outline::Cff(Box::new(table), id))
+    }
+
+    /// Map a CID to a glyph ID.
+    fn cid_to_gid(&self, cid: u32) -> u16 {
+        match &self.cid_to_gid {
+            Some(map) => map.get(cid as usize).copied().unwrap_or(0),
+            None => cid as u16,
+        }
+    }
+
+    /// The Unicode character of a code of a simple font.
+    fn unicode(&self, code: u32) -> Option<char> {
+        if self.wide {
+            return None;
+        }
+        if let Some(name) = self.differences.get(&code) {
+            return glyph_name_to_unicode(name);
+        }
+        let encoding = self.base_encoding.unwrap_or("StandardEncoding");
+        Document::decode_text(Some(encoding), &[u8::try_from(code).ok()?])
+            .chars()
+            .next()
+    }
+}
+
+impl Outline<'_> {
+    /// The transform from glyph space into text space.
+    pub fn matrix(&self) -> Transform {
+        match self {
+            Self::Sfnt(face, _) => {
+                let scale = 1.0 / f64::from(face.units_per_em());
+                matrix([scale, 0.0, 0.0, scale, 0.0, 0.0])
+            }
+            Self::Cff(table, _) => {
+                let m = table.matrix();
+                matrix([m.sx, m.ky, m.kx, m.sy, m.tx, m.ty].map(f64::from))
+            }
+        }
+    }
+
+    /// Build the outline into a path, transforming it from text space into
+    /// frame coordinates.
+    pub fn build(&self, path: &mut Path, transform: Transform) {
+        let transform = transform.pre_concat(self.matrix());
+        let mut builder = GlyphBuilder { path, transform, last: Point::zero() };
+        match self {
+            Self::Sfnt(face, id) => {
+                face.outline_glyph(*id, &mut builder);
+            }
+            Self::Cff(table, id) => {
+                let _ = table.outline(*id, &mut builder);
+            }
+        }
+    }
+}
+
+/// Builds glyph outlines into a path in frame coordinates.
+struct GlyphBuilder<'p> {
+    path: &'p mut Path,
+    transform: Transform,
+    last: Point,
+}
+
+impl GlyphBuilder<'_> {
+    fn point(&self, x: f32, y: f32) -> Point {
+        Point::new(Abs::pt(x.into()), Abs::pt(y.into())).transform(self.transform)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphBuilder<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.last = self.point(x, y);
+        self.path.move_to(self.last);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.last = self.point(x, y);
+        self.path.line_to(self.last);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.point(x1, y1);
+        let end = self.point(x, y);
+        let c1 = self.last + (control - self.last) * (2.0 / 3.0);
+        let c2 = end + (control - end) * (2.0 / 3.0);
+        self.path.cubic_to(c1, c2, end);
+        self.last = end;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let end = self.point(x, y);
+        self.path.cubic_to(self.point(x1, y1), self.point(x2, y2), end);
+        self.last = end;
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
+}
+
+/// The index of the fallback font for a font with the given name, which
+/// hints at its weight and style.
+fn fallback_index(name: &[u8]) -> usize {
+    let contains = |needle: &[u8]| name.windows(needle.len()).any(|w| w == needle);
+    let bold = contains(b"Bold") || contains(b"Black") || contains(b"Heavy");
+    let italic = contains(b"Italic") || contains(b"Oblique");
+    usize::from(bold) | usize::from(italic) << 1
+}
+
+/// Read the widths of a composite font from its `W` array.
+fn cid_widths(document: &Document, array: &[Object]) -> HashMap<u32, f64> {
+    let mut widths = HashMap::new();
+    let mut items = array.iter().map(|object| resolve(document, object)).peekable();
+    while let Some(first) = items.next().and_then(|v| number(document, v)) {
+        match items.next() {
+            Some(Object::Array(list)) => {
+                for (i, width) in list.iter().enumerate() {
+                    if let Some(width) = number(document, width) {
+                        widths.insert(first as u32 + i as u32, width);
+                    }
+                }
+            }
+            Some(last) => {
+                let last = number(document, last).unwrap_or(first);
+                let width = items.next().and_then(|v| number(document, v));
+                if let Some(width) = width {
+                    // Guard against huge ranges in malformed fonts.
+                    for cid in (first as u32..=last as u32).take(u16::MAX as usize) {
+                        widths.insert(cid, width);
+                    }
+                }
+            }
+            None => break,
+        }
+    }
+    widths
+}
+
+/// Read the glyph names of an encoding's `Differences` array.
+fn read_differences<'a>(
+    document: &'a Document,
+    array: &'a [Object],
+) -> HashMap<u32, &'a str> {
+    let mut names = HashMap::new();
+    let mut code = 0;
+    for object in array {
+        match resolve(document, object) {
+            Object::Name(name) => {
+                if let Ok(name) = std::str::from_utf8(name) {
+                    names.insert(code, name);
+                }
+                code += 1;
+            }
+            other => {
+                if let Some(start) = number(document, other) {
+                    code = start as u32;
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Determine the character a glyph name stands for.
+///
+/// Covers single characters and the `uniXXXX` and `uXXXX` conventions.
+fn glyph_name_to_unicode(name: &str) -> Option<char> {
+    let hex =
+        |digits: &str| u32::from_str_radix(digits, 16).ok().and_then(char::from_u32);
+    if let Some(digits) = name.strip_prefix("uni").filter(|d| d.len() == 4) {
+        return hex(digits);
+    }
+    if let Some(digits) = name.strip_prefix('u').filter(|d| (4..=6).contains(&d.len())) {
+        return hex(digits);
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
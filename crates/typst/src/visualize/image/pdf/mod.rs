@@ -0,0 +1,198 @@
+//! Pages of PDF documents.
+//!
+//! Reading PDF documents requires the `pdf-images` feature. Without it, PDF
+//! images fail to load.
+
+#[cfg(feature = "pdf-images")]
+mod convert;
+#[cfg(feature = "pdf-images")]
+mod document;
+#[cfg(feature = "pdf-images")]
+mod font;
+
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use comemo::Tracked;
+
+use crate::diag::StrResult;
+use crate::foundations::Bytes;
+use crate::layout::{Frame, FrameItem, GroupItem, Size, Transform};
+use crate::syntax::Span;
+use crate::text::{Font, FontStretch, FontStyle, FontVariant, FontWeight};
+use crate::World;
+
+/// A page of a PDF document.
+#[derive(Clone, Hash)]
+pub struct PdfImage(Arc<Repr>);
+
+/// The internal representation.
+struct Repr {
+    data: Bytes,
+    page: NonZeroUsize,
+    bbox: [f64; 4],
+    size: Size,
+    transform: Transform,
+    /// Fonts that stand in for fonts that are not embedded in the document,
+    /// in the order regular, bold, italic, and bold italic.
+    fallbacks: Vec<Font>,
+}
+
+impl PdfImage {
+    /// Load a page of a PDF document.
+    #[comemo::memoize]
+    pub fn new(data: Bytes, page: NonZeroUsize) -> StrResult<PdfImage> {
+        Self::load(data, page, vec![])
+    }
+
+    /// Load a page of a PDF document with access to fonts.
+    ///
+    /// Text in fonts that are not embedded in the document is drawn with the
+    /// first of the given families that is available.
+    #[comemo::memoize]
+    pub fn with_fonts(
+        data: Bytes,
+        page: NonZeroUsize,
+        world: Tracked<dyn World + '_>,
+        families: &[String],
+    ) -> StrResult<PdfImage> {
+        let book = world.book();
+        let fallbacks = families
+            .iter()
+            .find(|family| book.contains_family(family))
+            .map(|family| {
+                let variants = [
+                    (FontStyle::Normal, FontWeight::REGULAR),
+                    (FontStyle::Normal, FontWeight::BOLD),
+                    (FontStyle::Italic, FontWeight::REGULAR),
+                    (FontStyle::Italic, FontWeight::BOLD),
+                ];
+                variants
+                    .into_iter()
+                    .filter_map(|(style, weight)| {
+                        let variant =
+                            FontVariant::new(style, weight, FontStretch::NORMAL);
+                        world.font(book.select(family, variant)?)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self::load(data, page, fallbacks)
+    }
+
+    /// Load a page of a PDF document with the given fallback fonts.
+    ///
+    /// Only the page's geometry is kept. The document is parsed again when
+    /// the page is [converted](Self::frame).
+    #[cfg(feature = "pdf-images")]
+    fn load(
+        data: Bytes,
+        page: NonZeroUsize,
+        fallbacks: Vec<Font>,
+    ) -> StrResult<PdfImage> {
+        let (bbox, size, transform) = document::geometry(&data, page)?;
+        Ok(Self(Arc::new(Repr { data, page, bbox, size, transform, fallbacks })))
+    }
+
+    /// Load a page of a PDF document with the given fallback fonts.
+    #[cfg(not(feature = "pdf-images"))]
+    fn load(_: Bytes, _: NonZeroUsize, _: Vec<Font>) -> StrResult<PdfImage> {
+        crate::diag::bail!("PDF images are not supported in this build of Typst")
+    }
+
+    /// The raw image data.
+    pub fn data(&self) -> &Bytes {
+        &self.0.data
+    }
+
+    /// The number of the embedded page, starting at one.
+    pub fn page(&self) -> NonZeroUsize {
+        self.0.page
+    }
+
+    /// The page's width in points.
+    pub fn width(&self) -> f64 {
+        self.0.size.x.to_pt()
+    }
+
+    /// The page's height in points.
+    pub fn height(&self) -> f64 {
+        self.0.size.y.to_pt()
+    }
+
+    /// The visible area of the page in its own coordinate system, as
+    /// `[left, bottom, right, top]`.
+    pub fn bbox(&self) -> [f64; 4] {
+        self.0.bbox
+    }
+
+    /// The transform from the page's coordinate system into a coordinate
+    /// system with its origin in the top-left corner of the visible page and
+    /// the y-axis pointing downwards. Accounts for the page's rotation.
+    pub fn transform(&self) -> Transform {
+        self.0.transform
+    }
+
+    /// Converts the page into a frame for exporters that cannot embed PDF
+    /// content directly.
+    ///
+    /// The conversion covers vector paths, text in TrueType, OpenType, and
+    /// CFF fonts, and raster images. Text in fonts that are not embedded is
+    /// drawn with a fallback font if one is available. The converted content
+    /// originates from the given span, which should be the one of the image.
+    pub fn frame(&self, span: Span) -> Frame {
+        respan(&convert(self).0, span)
+    }
+
+    /// Whether [converting](Self::frame) the page into a frame drops content
+    /// because it is in an unsupported format.
+    pub fn is_lossy(&self) -> bool {
+        convert(self).1
+    }
+}
+
+impl Hash for Repr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The image is fully defined by the data, the page number, and the
+        // fallback fonts.
+        self.data.hash(state);
+        self.page.hash(state);
+        self.fallbacks.hash(state);
+    }
+}
+
+/// Convert a page into a frame and determine whether content was dropped.
+#[comemo::memoize]
+fn convert(pdf: &PdfImage) -> (Frame, bool) {
+    #[cfg(feature = "pdf-images")]
+    {
+        convert::page(pdf)
+    }
+
+    // Pages can't be loaded without the feature, so this is never reached.
+    #[cfg(not(feature = "pdf-images"))]
+    {
+        (Frame::soft(pdf.0.size), true)
+    }
+}
+
+/// Let all shapes and images in a converted frame originate from a span.
+fn respan(frame: &Frame, span: Span) -> Frame {
+    let mut output = Frame::new(frame.size(), frame.kind());
+    for (pos, item) in frame.items() {
+        let item = match item {
+            FrameItem::Shape(shape, _) => FrameItem::Shape(shape.clone(), span),
+            FrameItem::Image(image, size, _) => {
+                FrameItem::Image(image.clone(), *size, span)
+            }
+            FrameItem::Group(group) => FrameItem::Group(GroupItem {
+                frame: respan(&group.frame, span),
+                ..group.clone()
+            }),
+            item => item.clone(),
+        };
+        output.push(*pos, item);
+    }
+    output
+}
@@ -11,7 +11,7 @@ use image::io::Limits;
 use image::{guess_format, DynamicImage, ImageDecoder, ImageResult};
 
 use crate::diag::{bail, StrResult};
-use crate::foundations::{Bytes, Cast};
+use crate::foundations::{Bytes, Cast, Smart};
 
 /// A decoded raster image.
 #[derive(Clone, Hash)]
@@ -28,8 +28,21 @@ struct Repr {
 
 impl RasterImage {
     /// Decode a raster image.
-    #[comemo::memoize]
     pub fn new(data: Bytes, format: RasterFormat) -> StrResult<RasterImage> {
+        Self::with_icc(data, format, Smart::Auto)
+    }
+
+    /// Decode a raster image, overriding its color profile.
+    ///
+    /// With `Smart::Auto`, the ICC profile embedded in the image is used if it
+    /// matches the image's color space. With `None`, any embedded profile is
+    /// discarded and the image is treated as sRGB (or gray).
+    #[comemo::memoize]
+    pub fn with_icc(
+        data: Bytes,
+        format: RasterFormat,
+        icc: Smart<Option<Bytes>>,
+    ) -> StrResult<RasterImage> {
         fn decode_with<'a, T: ImageDecoder<'a>>(
             decoder: ImageResult<T>,
         ) -> ImageResult<(image::DynamicImage, Option<Vec<u8>>)> {
@@ -41,7 +54,7 @@ impl RasterImage {
         }
 
         let cursor = io::Cursor::new(&data);
-        let (mut dynamic, icc) = match format {
+        let (mut dynamic, embedded) = match format {
             RasterFormat::Jpg => decode_with(JpegDecoder::new(cursor)),
             RasterFormat::Png => decode_with(PngDecoder::new(cursor)),
             RasterFormat::Gif => decode_with(GifDecoder::new(cursor)),
@@ -60,6 +73,19 @@ impl RasterImage {
         // Extract pixel density.
         let dpi = determine_dpi(&data, exif.as_ref());
 
+        // Determine the color profile. Decoders convert some color spaces
+        // (like CMYK) to RGB, so an embedded profile may not apply anymore.
+        let icc = match icc {
+            Smart::Auto => embedded.filter(|icc| icc_matches(icc, &dynamic)),
+            Smart::Custom(None) => None,
+            Smart::Custom(Some(icc)) => {
+                if !icc_matches(&icc, &dynamic) {
+                    bail!("ICC profile does not match the color space of the image");
+                }
+                Some(icc.to_vec())
+            }
+        };
+
         Ok(Self(Arc::new(Repr { data, format, dynamic, icc, dpi })))
     }
 
@@ -101,9 +127,10 @@ impl RasterImage {
 
 impl Hash for Repr {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        // The image is fully defined by data and format.
+        // The image is fully defined by data, format, and color profile.
         self.data.hash(state);
         self.format.hash(state);
+        self.icc.hash(state);
     }
 }
 
@@ -148,6 +175,16 @@ impl TryFrom<image::ImageFormat> for RasterFormat {
     }
 }
 
+/// Whether an ICC profile describes the color space of a decoded image.
+fn icc_matches(icc: &[u8], image: &DynamicImage) -> bool {
+    // The data color space signature is at offset 16 of the profile header.
+    let Some(space) = icc.get(16..20) else { return false };
+    match image.color().channel_count() {
+        1 | 2 => space == b"GRAY",
+        _ => space == b"RGB ",
+    }
+}
+
 /// Try to get the rotation from the EXIF metadata.
 fn exif_rotation(exif: &exif::Exif) -> Option<u32> {
     exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
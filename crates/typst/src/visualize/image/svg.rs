@@ -24,6 +24,7 @@ struct Repr {
     data: Bytes,
     size: Axes<f64>,
     font_hash: u128,
+    fonts: Vec<Font>,
     tree: usvg::Tree,
 }
 
@@ -33,7 +34,13 @@ impl SvgImage {
     pub fn new(data: Bytes) -> StrResult<SvgImage> {
         let tree =
             usvg::Tree::from_data(&data, &base_options()).map_err(format_usvg_error)?;
-        Ok(Self(Arc::new(Repr { data, size: tree_size(&tree), font_hash: 0, tree })))
+        Ok(Self(Arc::new(Repr {
+            data,
+            size: tree_size(&tree),
+            font_hash: 0,
+            fonts: vec![],
+            tree,
+        })))
     }
 
     /// Decode an SVG image with access to fonts.
@@ -60,8 +67,14 @@ impl SvgImage {
             },
         )
         .map_err(format_usvg_error)?;
-        let font_hash = resolver.into_inner().unwrap().finish();
-        Ok(Self(Arc::new(Repr { data, size: tree_size(&tree), font_hash, tree })))
+        let (font_hash, fonts) = resolver.into_inner().unwrap().finish();
+        Ok(Self(Arc::new(Repr {
+            data,
+            size: tree_size(&tree),
+            font_hash,
+            fonts,
+            tree,
+        })))
     }
 
     /// The raw image data.
@@ -79,6 +92,11 @@ impl SvgImage {
         self.0.size.y
     }
 
+    /// The fonts used by text in the SVG, in the order they were loaded.
+    pub fn fonts(&self) -> &[Font] {
+        &self.0.fonts
+    }
+
     /// Accesses the usvg tree.
     pub fn tree(&self) -> &usvg::Tree {
         &self.0.tree
@@ -147,6 +165,8 @@ struct FontResolver<'a> {
     to_id: HashMap<usize, Option<fontdb::ID>>,
     /// The reverse mapping.
     from_id: HashMap<fontdb::ID, Font>,
+    /// The loaded fonts, in order.
+    fonts: Vec<Font>,
     /// Accumulates a hash of all used fonts.
     hasher: SipHasher13,
 }
@@ -164,13 +184,14 @@ impl<'a> FontResolver<'a> {
             families,
             to_id: HashMap::new(),
             from_id: HashMap::new(),
+            fonts: vec![],
             hasher: SipHasher13::new(),
         }
     }
 
-    /// Returns a hash of all used fonts.
-    fn finish(self) -> u128 {
-        self.hasher.finish128().as_u128()
+    /// Returns a hash of all used fonts and the fonts themselves.
+    fn finish(self) -> (u128, Vec<Font>) {
+        (self.hasher.finish128().as_u128(), self.fonts)
     }
 }
 
@@ -282,7 +303,8 @@ impl FontResolver<'_> {
         font.hash(&mut self.hasher);
 
         self.to_id.insert(index, Some(id));
-        self.from_id.insert(id, font);
+        self.from_id.insert(id, font.clone());
+        self.fonts.push(font);
 
         Some(id)
     }
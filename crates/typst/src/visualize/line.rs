@@ -4,8 +4,9 @@ use crate::foundations::{elem, Content, NativeElement, Packed, Show, StyleChain}
 use crate::layout::{
     Abs, Angle, Axes, BlockElem, Frame, FrameItem, Length, Region, Rel, Size,
 };
+use crate::syntax::Spanned;
 use crate::utils::Numeric;
-use crate::visualize::{Geometry, Stroke};
+use crate::visualize::{advance, Geometry, Markers, Stroke};
 
 /// A line from one point to another.
 ///
@@ -56,6 +57,40 @@ pub struct LineElem {
     #[resolve]
     #[fold]
     pub stroke: Stroke,
+
+    /// Markers at the start and end of the line.
+    ///
+    /// Can be set to a dictionary with the keys `start` and `end`. Each
+    /// marker can be one of the predefined shapes below, which are scaled
+    /// with the stroke's thickness and take its paint, or arbitrary content.
+    /// Markers are rotated to follow the line's direction. Content markers are
+    /// centered on the line's end and should point to the right. To put
+    /// markers on curves, use a [path's markers]($path.marker) instead.
+    ///
+    /// - `{">"}`: An arrowhead.
+    /// - `{"<"}`: An arrowhead pointing back onto the line.
+    /// - `{"|"}`: A bar across the line.
+    /// - `{"o"}`: An outlined circle.
+    /// - `{"*"}`: A filled circle.
+    /// - `{"<>"}`: A diamond.
+    ///
+    /// ```example
+    /// #set line(length: 100%)
+    /// #stack(
+    ///   spacing: 1em,
+    ///   line(marker: (end: ">")),
+    ///   line(stroke: 2pt + blue, marker: (start: "*", end: "o")),
+    ///   line(marker: (start: "|", end: text(8pt)[▶])),
+    /// )
+    /// ```
+    #[parse(match args.named::<Spanned<Markers>>("marker")? {
+        Some(Spanned { v, span }) if v.mid.is_some() => bail!(
+            span, "unexpected key \"mid\", valid keys are \"start\" and \"end\"";
+            hint: "lines have no inner vertices, use a path for markers at vertices",
+        ),
+        markers => markers.map(|markers| markers.v),
+    })]
+    pub marker: Markers,
 }
 
 impl Show for Packed<LineElem> {
@@ -68,7 +103,7 @@ impl Show for Packed<LineElem> {
 #[typst_macros::time(span = elem.span())]
 fn layout_line(
     elem: &Packed<LineElem>,
-    _: &mut Engine,
+    engine: &mut Engine,
     styles: StyleChain,
     region: Region,
 ) -> SourceResult<Frame> {
@@ -90,7 +125,36 @@ fn layout_line(
     }
 
     let mut frame = Frame::soft(size);
-    let shape = Geometry::Line(delta.to_point()).stroked(stroke);
-    frame.push(start.to_point(), FrameItem::Shape(shape, elem.span()));
+    let markers = elem.marker(styles);
+    if markers.is_empty() {
+        let shape = Geometry::Line(delta.to_point()).stroked(stroke);
+        frame.push(start.to_point(), FrameItem::Shape(shape, elem.span()));
+        return Ok(frame);
+    }
+
+    // Pull back the ends of the line so that they don't stick out of the
+    // markers, as long as the line is long enough.
+    let dir = delta.to_point();
+    let (from, to) = (start.to_point(), start.to_point() + dir);
+    let (start_inset, end_inset) = markers.insets(&stroke);
+    let (a, b) = if start_inset + end_inset < dir.hypot() {
+        (advance(from, dir, start_inset), advance(to, -dir, end_inset))
+    } else {
+        (from, to)
+    };
+
+    let span = elem.span();
+    let shape = Geometry::Line(b - a).stroked(stroke.clone());
+    frame.push(a, FrameItem::Shape(shape, span));
+    markers.layout(
+        engine,
+        styles,
+        &mut frame,
+        &stroke,
+        Some((from, dir)),
+        &[],
+        Some((to, dir)),
+        span,
+    )?;
     Ok(frame)
 }
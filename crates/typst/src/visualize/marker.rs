@@ -0,0 +1,291 @@
+use crate::diag::{HintedStrResult, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{cast, Content, Dict, NoneValue, StyleChain, Value};
+use crate::layout::{
+    Abs, Angle, Axes, Frame, FrameItem, GroupItem, Point, Region, Size, Transform,
+};
+use crate::syntax::Span;
+use crate::utils::Numeric;
+use crate::visualize::{ellipse, FixedStroke, Geometry, Path, PathItem, Shape};
+
+/// Markers at the start, the inner vertices, and the end of a line or path.
+///
+/// Curves are drawn with paths, so they use the same markers. Can be given as
+/// a dictionary with the keys `start`, `mid`, and `end`.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct Markers {
+    /// The marker at the start.
+    pub start: Option<Marker>,
+    /// The marker at each inner vertex.
+    pub mid: Option<Marker>,
+    /// The marker at the end.
+    pub end: Option<Marker>,
+}
+
+impl Markers {
+    /// Whether no markers are set.
+    pub fn is_empty(&self) -> bool {
+        self.start.is_none() && self.mid.is_none() && self.end.is_none()
+    }
+
+    /// By how much the stroke should be pulled back at the start and the end
+    /// so that it doesn't stick out of the markers.
+    pub fn insets(&self, stroke: &FixedStroke) -> (Abs, Abs) {
+        let inset = |marker: &Option<Marker>| match marker {
+            Some(Marker::Shape(shape)) => shape.inset() * stroke.thickness,
+            _ => Abs::zero(),
+        };
+        (inset(&self.start), inset(&self.end))
+    }
+
+    /// Lay out the markers into a frame.
+    ///
+    /// Each site is a position along with the direction in which the line or
+    /// path passes through it. The start marker points backwards, away from
+    /// the line, while all other markers point forwards.
+    #[allow(clippy::too_many_arguments)]
+    pub fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        frame: &mut Frame,
+        stroke: &FixedStroke,
+        start: Option<(Point, Point)>,
+        mids: &[(Point, Point)],
+        end: Option<(Point, Point)>,
+        span: Span,
+    ) -> SourceResult<()> {
+        if let (Some(marker), Some((pos, dir))) = (&self.start, start) {
+            marker.layout(engine, styles, frame, stroke, pos, -dir, span)?;
+        }
+        if let Some(marker) = &self.mid {
+            for &(pos, dir) in mids {
+                marker.layout(engine, styles, frame, stroke, pos, dir, span)?;
+            }
+        }
+        if let (Some(marker), Some((pos, dir))) = (&self.end, end) {
+            marker.layout(engine, styles, frame, stroke, pos, dir, span)?;
+        }
+        Ok(())
+    }
+}
+
+cast! {
+    Markers,
+    self => {
+        let mut dict = Dict::new();
+        let mut insert = |key: &str, marker: Option<Marker>| {
+            if let Some(marker) = marker {
+                dict.insert(key.into(), marker.into_value());
+            }
+        };
+        insert("start", self.start);
+        insert("mid", self.mid);
+        insert("end", self.end);
+        dict.into_value()
+    },
+    _: NoneValue => Self::default(),
+    mut dict: Dict => {
+        let mut take = |key: &str| -> HintedStrResult<Option<Marker>> {
+            let marker = dict.take(key).ok().map(Value::cast::<Option<Marker>>);
+            Ok(marker.transpose()?.flatten())
+        };
+        let start = take("start")?;
+        let mid = take("mid")?;
+        let end = take("end")?;
+        dict.finish(&["start", "mid", "end"])?;
+        Self { start, mid, end }
+    },
+}
+
+/// A marker on a line or path.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Marker {
+    /// A predefined shape that scales with the stroke's thickness.
+    Shape(MarkerShape),
+    /// Arbitrary content, centered on the vertex. Content pointing to the
+    /// right is rotated to point along the line or path.
+    Content(Content),
+}
+
+impl Marker {
+    /// Lay out the marker at a position, pointing in a direction.
+    #[allow(clippy::too_many_arguments)]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        frame: &mut Frame,
+        stroke: &FixedStroke,
+        pos: Point,
+        dir: Point,
+        span: Span,
+    ) -> SourceResult<()> {
+        let angle = Angle::rad(dir.y.to_raw().atan2(dir.x.to_raw()));
+        let ts = Transform::translate(pos.x, pos.y).pre_concat(Transform::rotate(angle));
+
+        match self {
+            Self::Shape(shape) => {
+                let mut shape = shape.shape(stroke);
+                if let Geometry::Path(path) = &mut shape.geometry {
+                    *path = transform_path(path, ts);
+                }
+                frame.push(Point::zero(), FrameItem::Shape(shape, span));
+            }
+            Self::Content(content) => {
+                let pod = Region::new(Size::splat(Abs::inf()), Axes::splat(false));
+                let marker =
+                    content.layout(engine, styles, pod.into_regions())?.into_frame();
+                let center =
+                    Transform::translate(-marker.width() / 2.0, -marker.height() / 2.0);
+                let mut group = GroupItem::new(marker);
+                group.transform = ts.pre_concat(center);
+                frame.push(Point::zero(), FrameItem::Group(group));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+cast! {
+    Marker,
+    self => match self {
+        Self::Shape(shape) => shape.into_value(),
+        Self::Content(content) => content.into_value(),
+    },
+    v: MarkerShape => Self::Shape(v),
+    v: Content => Self::Content(v),
+}
+
+/// A predefined marker shape.
+///
+/// All shapes point to the right and are sized relative to the thickness of
+/// the stroke they are attached to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MarkerShape {
+    /// A filled arrowhead whose tip sits on the vertex.
+    Arrow,
+    /// A filled arrowhead that points back onto the line.
+    ReverseArrow,
+    /// A short bar across the line.
+    Bar,
+    /// An outlined circle around the vertex.
+    Circle,
+    /// A filled circle on the vertex.
+    Dot,
+    /// A filled diamond on the vertex.
+    Diamond,
+}
+
+impl MarkerShape {
+    /// By how much the stroke is pulled back, in multiples of its thickness.
+    fn inset(self) -> f64 {
+        match self {
+            Self::Arrow => 2.5,
+            Self::Circle => 2.0,
+            Self::ReverseArrow | Self::Bar | Self::Dot | Self::Diamond => 0.0,
+        }
+    }
+
+    /// Create the shape for a stroke, pointing to the right from the origin.
+    fn shape(self, stroke: &FixedStroke) -> Shape {
+        let u = stroke.thickness;
+        let point = |x: f64, y: f64| Point::new(x * u, y * u);
+        let polygon = |points: &[Point]| {
+            let mut path = Path::new();
+            path.move_to(points[0]);
+            for &p in &points[1..] {
+                path.line_to(p);
+            }
+            path.close_path();
+            path
+        };
+
+        let outline = FixedStroke { dash: None, ..stroke.clone() };
+        let circle = |fill, stroke| {
+            let radius = 2.0 * u;
+            let mut shape = ellipse(Size::splat(2.0 * radius), fill, stroke);
+            if let Geometry::Path(path) = &mut shape.geometry {
+                *path = transform_path(path, Transform::translate(-radius, -radius));
+            }
+            shape
+        };
+
+        match self {
+            Self::Arrow => Geometry::Path(polygon(&[
+                point(0.0, 0.0),
+                point(-5.0, -2.5),
+                point(-5.0, 2.5),
+            ]))
+            .filled(stroke.paint.clone()),
+            Self::ReverseArrow => Geometry::Path(polygon(&[
+                point(0.0, -2.5),
+                point(0.0, 2.5),
+                point(-5.0, 0.0),
+            ]))
+            .filled(stroke.paint.clone()),
+            Self::Bar => {
+                let mut path = Path::new();
+                path.move_to(point(0.0, -2.5));
+                path.line_to(point(0.0, 2.5));
+                Geometry::Path(path).stroked(outline)
+            }
+            Self::Circle => circle(None, Some(outline)),
+            Self::Dot => circle(Some(stroke.paint.clone()), None),
+            Self::Diamond => Geometry::Path(polygon(&[
+                point(3.0, 0.0),
+                point(0.0, -2.0),
+                point(-3.0, 0.0),
+                point(0.0, 2.0),
+            ]))
+            .filled(stroke.paint.clone()),
+        }
+    }
+}
+
+cast! {
+    MarkerShape,
+    self => match self {
+        Self::Arrow => ">",
+        Self::ReverseArrow => "<",
+        Self::Bar => "|",
+        Self::Circle => "o",
+        Self::Dot => "*",
+        Self::Diamond => "<>",
+    }.into_value(),
+    ">" => Self::Arrow,
+    "<" => Self::ReverseArrow,
+    "|" => Self::Bar,
+    "o" => Self::Circle,
+    "*" => Self::Dot,
+    "<>" => Self::Diamond,
+}
+
+/// Apply a transform to all points of a path.
+fn transform_path(path: &Path, ts: Transform) -> Path {
+    Path(
+        path.0
+            .iter()
+            .map(|item| match *item {
+                PathItem::MoveTo(p) => PathItem::MoveTo(p.transform(ts)),
+                PathItem::LineTo(p) => PathItem::LineTo(p.transform(ts)),
+                PathItem::CubicTo(p1, p2, p3) => PathItem::CubicTo(
+                    p1.transform(ts),
+                    p2.transform(ts),
+                    p3.transform(ts),
+                ),
+                PathItem::ClosePath => PathItem::ClosePath,
+            })
+            .collect(),
+    )
+}
+
+/// Move a point along a direction by a distance.
+pub(crate) fn advance(point: Point, dir: Point, distance: Abs) -> Point {
+    let length = dir.hypot();
+    if length.is_zero() || distance.is_zero() {
+        return point;
+    }
+    point + dir * (distance / length)
+}
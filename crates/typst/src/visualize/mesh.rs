@@ -0,0 +1,484 @@
+use ecow::EcoString;
+use kurbo::Vec2;
+
+use crate::diag::bail;
+use crate::foundations::{array, cast, Array, IntoValue, Repr, Smart};
+use crate::layout::{Axes, Ratio, Transform};
+use crate::utils::Scalar;
+use crate::visualize::{Color, ColorSpace, RelativeTo, WeightedColor};
+
+/// The parameters from which the inversion of a patch starts. Newton's method
+/// only converges near a solution, so we try several spots of the patch.
+const STARTS: [(f64, f64); 5] =
+    [(0.5, 0.5), (0.1, 0.1), (0.9, 0.1), (0.1, 0.9), (0.9, 0.9)];
+
+/// A gradient that interpolates between colors across a mesh of patches.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MeshGradient {
+    /// The rows of points the gradient was created from. All rows have the
+    /// same length and there are at least two rows with at least two points
+    /// each.
+    ///
+    /// Empty for meshes that are assembled from patches directly, like the
+    /// ones that draw gradients along a stroke.
+    pub grid: Vec<Vec<MeshPoint>>,
+    /// The patches of the mesh, in the unit square. Where patches overlap,
+    /// later ones are drawn on top of earlier ones.
+    pub patches: Vec<MeshPatch>,
+    /// The color space in which to interpolate the gradient.
+    pub space: ColorSpace,
+    /// The relative placement of the gradient.
+    pub relative: Smart<RelativeTo>,
+    /// The transform applied to the gradient.
+    pub transform: Transform,
+}
+
+impl MeshGradient {
+    /// Creates a mesh from a grid of points.
+    ///
+    /// Each cell of the grid becomes a Coons patch. Its edges are the
+    /// segments of the Catmull-Rom splines that run through the rows and
+    /// columns of points, so they are curved if the points are not evenly
+    /// spaced.
+    pub fn new(
+        grid: Vec<Vec<MeshPoint>>,
+        space: ColorSpace,
+        relative: Smart<RelativeTo>,
+        transform: Transform,
+    ) -> Self {
+        let rows = grid.len();
+        let columns = grid[0].len();
+        let position = |j: usize, i: usize| {
+            let default = Axes::new(
+                Ratio::new(i as f64 / (columns - 1) as f64),
+                Ratio::new(j as f64 / (rows - 1) as f64),
+            );
+            let position = grid[j][i].position.unwrap_or(default);
+            Vec2::new(position.x.get(), position.y.get())
+        };
+
+        let lines: Vec<Vec<Vec2>> = (0..rows)
+            .map(|j| (0..columns).map(|i| position(j, i)).collect())
+            .collect();
+        let verticals: Vec<Vec<Vec2>> = (0..columns)
+            .map(|i| (0..rows).map(|j| position(j, i)).collect())
+            .collect();
+
+        let mut patches = Vec::with_capacity((rows - 1) * (columns - 1));
+        for j in 0..rows - 1 {
+            for i in 0..columns - 1 {
+                let [top1, top2] = spline(&lines[j], i);
+                let [bottom1, bottom2] = spline(&lines[j + 1], i);
+                let [left1, left2] = spline(&verticals[i], j);
+                let [right1, right2] = spline(&verticals[i + 1], j);
+
+                let mut points = [[Vec2::ZERO; 4]; 4];
+                points[0] = [lines[j][i], left1, left2, lines[j + 1][i]];
+                points[3] = [lines[j][i + 1], right1, right2, lines[j + 1][i + 1]];
+                points[1][0] = top1;
+                points[2][0] = top2;
+                points[1][3] = bottom1;
+                points[2][3] = bottom2;
+
+                let colors = [
+                    [grid[j][i].color, grid[j + 1][i].color],
+                    [grid[j][i + 1].color, grid[j + 1][i + 1].color],
+                ];
+
+                patches.push(MeshPatch::coons(points, colors));
+            }
+        }
+
+        Self { grid, patches, space, relative, transform }
+    }
+
+    /// Creates a mesh from patches, relative to the element it is drawn on.
+    pub fn from_patches(patches: Vec<MeshPatch>, space: ColorSpace) -> Self {
+        Self {
+            grid: vec![],
+            patches,
+            space,
+            relative: Smart::Custom(RelativeTo::Self_),
+            transform: Transform::identity(),
+        }
+    }
+
+    /// The number of rows of points in the mesh's grid.
+    pub fn rows(&self) -> usize {
+        self.grid.len()
+    }
+
+    /// The number of columns of points in the mesh's grid.
+    pub fn columns(&self) -> usize {
+        self.grid.first().map_or(0, Vec::len)
+    }
+
+    /// Samples the mesh at a position in the unit square.
+    ///
+    /// Positions that no patch covers take the color of the closest spot of
+    /// the patch whose bounds are nearest to them.
+    pub fn sample(&self, x: f64, y: f64) -> Color {
+        let p = Vec2::new(x, y);
+        let mut nearest: Option<(&MeshPatch, f64)> = None;
+        for patch in self.patches.iter().rev() {
+            let distance = patch.bounds_distance(p);
+            if distance == 0.0 {
+                if let Some((u, v)) = patch.locate(p) {
+                    return patch.color(u, v, self.space);
+                }
+            }
+
+            if nearest.map_or(true, |(_, d)| distance < d) {
+                nearest = Some((patch, distance));
+            }
+        }
+
+        let Some((patch, _)) = nearest else { return Color::BLACK };
+        let (u, v) = patch.approach(p);
+        patch.color(u, v, self.space)
+    }
+
+    /// Maps all colors of the mesh through a function.
+    pub fn map_colors(&mut self, f: impl Fn(Color) -> Color) {
+        for point in self.grid.iter_mut().flatten() {
+            point.color = f(point.color);
+        }
+
+        for patch in &mut self.patches {
+            for color in patch.colors.iter_mut().flatten() {
+                *color = f(*color);
+            }
+        }
+    }
+}
+
+impl Repr for MeshGradient {
+    fn repr(&self) -> EcoString {
+        let mut r = EcoString::from("gradient.mesh(");
+
+        if self.space != ColorSpace::Oklab {
+            r.push_str("space: ");
+            r.push_str(&self.space.into_value().repr());
+            r.push_str(", ");
+        }
+
+        if self.relative.is_custom() {
+            r.push_str("relative: ");
+            r.push_str(&self.relative.into_value().repr());
+            r.push_str(", ");
+        }
+
+        if !self.transform.is_identity() {
+            r.push_str("transform: ");
+            r.push_str(&self.transform.repr());
+            r.push_str(", ");
+        }
+
+        if self.grid.is_empty() {
+            r.push_str("..");
+        }
+
+        for (i, row) in self.grid.iter().enumerate() {
+            r.push('(');
+            for (k, point) in row.iter().enumerate() {
+                r.push_str(&(*point).into_value().repr());
+                if k != row.len() - 1 {
+                    r.push_str(", ");
+                }
+            }
+            r.push(')');
+            if i != self.grid.len() - 1 {
+                r.push_str(", ");
+            }
+        }
+
+        r.push(')');
+        r
+    }
+}
+
+/// A point of a mesh gradient's grid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MeshPoint {
+    /// The color at the point.
+    pub color: Color,
+    /// The position of the point in the unit square. If absent, the point
+    /// sits at its place on an evenly spaced grid.
+    pub position: Option<Axes<Ratio>>,
+}
+
+cast! {
+    MeshPoint,
+    self => if let Some(position) = self.position {
+        array![self.color.into_value(), position.into_value()].into_value()
+    } else {
+        self.color.into_value()
+    },
+    color: Color => Self { color, position: None },
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(a), Some(b), None) => Self {
+                color: a.cast()?,
+                position: Some(b.cast()?),
+            },
+            _ => bail!("a mesh point must contain exactly two entries"),
+        }
+    }
+}
+
+/// A patch of a mesh gradient.
+///
+/// The patch is a bicubic tensor-product surface, which is the form that PDF
+/// uses for its patch meshes. Its colors are interpolated bilinearly between
+/// the colors at its four corners.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MeshPatch {
+    /// The sixteen control points of the surface, indexed by their position
+    /// along the `u` and the `v` parameter of the patch.
+    pub points: [[Axes<Scalar>; 4]; 4],
+    /// The colors at the corners, indexed like the points, but from zero to
+    /// one. The color `colors[1][0]` belongs to the point `points[3][0]`.
+    pub colors: [[Color; 2]; 2],
+}
+
+impl MeshPatch {
+    /// Creates a Coons patch from the twelve control points of its boundary.
+    /// The four inner points are ignored.
+    pub(crate) fn coons(mut p: [[Vec2; 4]; 4], colors: [[Color; 2]; 2]) -> Self {
+        // The inner points of the tensor-product patch that is equivalent to
+        // the Coons patch, as given by the PDF specification.
+        p[1][1] = (-4.0 * p[0][0] + 6.0 * (p[0][1] + p[1][0])
+            - 2.0 * (p[0][3] + p[3][0])
+            + 3.0 * (p[3][1] + p[1][3])
+            - p[3][3])
+            / 9.0;
+        p[1][2] = (-4.0 * p[0][3] + 6.0 * (p[0][2] + p[1][3])
+            - 2.0 * (p[0][0] + p[3][3])
+            + 3.0 * (p[3][2] + p[1][0])
+            - p[3][0])
+            / 9.0;
+        p[2][2] = (-4.0 * p[3][3] + 6.0 * (p[3][2] + p[2][3])
+            - 2.0 * (p[3][0] + p[0][3])
+            + 3.0 * (p[2][0] + p[0][2])
+            - p[0][0])
+            / 9.0;
+        p[2][1] = (-4.0 * p[3][0] + 6.0 * (p[3][1] + p[2][0])
+            - 2.0 * (p[3][3] + p[0][0])
+            + 3.0 * (p[2][3] + p[0][1])
+            - p[0][3])
+            / 9.0;
+
+        Self::tensor(p, colors)
+    }
+
+    /// Creates a patch with straight edges from its corners, indexed like
+    /// the colors.
+    pub(crate) fn quad(corners: [[Vec2; 2]; 2], colors: [[Color; 2]; 2]) -> Self {
+        let mut points = [[Vec2::ZERO; 4]; 4];
+        for (i, column) in points.iter_mut().enumerate() {
+            for (j, point) in column.iter_mut().enumerate() {
+                let (u, v) = (i as f64 / 3.0, j as f64 / 3.0);
+                let top = corners[0][0].lerp(corners[1][0], u);
+                let bottom = corners[0][1].lerp(corners[1][1], u);
+                *point = top.lerp(bottom, v);
+            }
+        }
+        Self::tensor(points, colors)
+    }
+
+    /// Creates a patch from all of its control points.
+    fn tensor(points: [[Vec2; 4]; 4], colors: [[Color; 2]; 2]) -> Self {
+        let points = points
+            .map(|column| column.map(|p| Axes::new(Scalar::new(p.x), Scalar::new(p.y))));
+        Self { points, colors }
+    }
+
+    /// The control point with the given indices.
+    fn at(&self, i: usize, j: usize) -> Vec2 {
+        let point = self.points[i][j];
+        Vec2::new(point.x.get(), point.y.get())
+    }
+
+    /// The color of the surface at the given parameters.
+    pub fn color(&self, u: f64, v: f64, space: ColorSpace) -> Color {
+        // Hue-based spaces can only mix two colors at once, so we interpolate
+        // along `u` first and then along `v`.
+        let mix = |a: Color, b: Color, t: f64| {
+            Color::mix_iter(
+                [WeightedColor::new(a, 1.0 - t), WeightedColor::new(b, t)],
+                space,
+            )
+            .unwrap()
+        };
+
+        let [[c00, c01], [c10, c11]] = self.colors;
+        mix(mix(c00, c10, u), mix(c01, c11, u), v)
+    }
+
+    /// Splits the patch into `n` by `n` smaller patches that together form
+    /// the same surface. The colors at their corners are interpolated in the
+    /// given color space.
+    pub fn split(&self, n: usize, space: ColorSpace) -> Vec<MeshPatch> {
+        let mut patches = Vec::with_capacity(n * n);
+        for a in 0..n {
+            let (u0, u1) = (a as f64 / n as f64, (a + 1) as f64 / n as f64);
+
+            // Restrict the curves along `u` first.
+            let columns: [[Vec2; 4]; 4] = std::array::from_fn(|j| {
+                restrict(std::array::from_fn(|i| self.at(i, j)), u0, u1)
+            });
+
+            for b in 0..n {
+                let (v0, v1) = (b as f64 / n as f64, (b + 1) as f64 / n as f64);
+                let points = std::array::from_fn(|i| {
+                    restrict(std::array::from_fn(|j| columns[j][i]), v0, v1)
+                });
+                let colors = [
+                    [self.color(u0, v0, space), self.color(u0, v1, space)],
+                    [self.color(u1, v0, space), self.color(u1, v1, space)],
+                ];
+                patches.push(Self::tensor(points, colors));
+            }
+        }
+        patches
+    }
+
+    /// Evaluates the surface and its partial derivatives at the given
+    /// parameters.
+    fn eval(&self, u: f64, v: f64) -> (Vec2, Vec2, Vec2) {
+        let (bu, du) = (bernstein(u), bernstein_derivative(u));
+        let (bv, dv) = (bernstein(v), bernstein_derivative(v));
+        let mut point = Vec2::ZERO;
+        let mut along_u = Vec2::ZERO;
+        let mut along_v = Vec2::ZERO;
+        for i in 0..4 {
+            for j in 0..4 {
+                let p = self.at(i, j);
+                point += p * (bu[i] * bv[j]);
+                along_u += p * (du[i] * bv[j]);
+                along_v += p * (bu[i] * dv[j]);
+            }
+        }
+        (point, along_u, along_v)
+    }
+
+    /// The distance of a point from the bounding box of the control points,
+    /// which contains the whole surface.
+    fn bounds_distance(&self, p: Vec2) -> f64 {
+        let mut min = Vec2::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vec2::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for i in 0..4 {
+            for j in 0..4 {
+                let q = self.at(i, j);
+                min = Vec2::new(min.x.min(q.x), min.y.min(q.y));
+                max = Vec2::new(max.x.max(q.x), max.y.max(q.y));
+            }
+        }
+
+        let dx = (min.x - p.x).max(p.x - max.x).max(0.0);
+        let dy = (min.y - p.y).max(p.y - max.y).max(0.0);
+        dx.hypot(dy)
+    }
+
+    /// Finds the parameters at which the surface passes through a point, if
+    /// it does.
+    fn locate(&self, p: Vec2) -> Option<(f64, f64)> {
+        const EPS: f64 = 1e-6;
+        STARTS.into_iter().find_map(|start| {
+            let (u, v, residual) = self.newton(p, start, (-0.5, 1.5));
+            let inside = |t: f64| (-EPS..=1.0 + EPS).contains(&t);
+            (residual < EPS && inside(u) && inside(v))
+                .then(|| (u.clamp(0.0, 1.0), v.clamp(0.0, 1.0)))
+        })
+    }
+
+    /// Finds the parameters at which the surface comes close to a point that
+    /// it does not pass through.
+    fn approach(&self, p: Vec2) -> (f64, f64) {
+        STARTS
+            .into_iter()
+            .map(|start| self.newton(p, start, (0.0, 1.0)))
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .map_or((0.5, 0.5), |(u, v, _)| (u, v))
+    }
+
+    /// Solves `S(u, v) = p` with Newton's method, keeping the parameters in
+    /// the given range. Returns the parameters and the remaining distance.
+    fn newton(
+        &self,
+        p: Vec2,
+        (mut u, mut v): (f64, f64),
+        range: (f64, f64),
+    ) -> (f64, f64, f64) {
+        let mut residual = f64::INFINITY;
+        for _ in 0..16 {
+            let (s, su, sv) = self.eval(u, v);
+            let d = s - p;
+            residual = d.hypot();
+            if residual < 1e-9 {
+                break;
+            }
+
+            let det = su.x * sv.y - sv.x * su.y;
+            if det.abs() < 1e-12 {
+                break;
+            }
+
+            u = (u - (d.x * sv.y - sv.x * d.y) / det).clamp(range.0, range.1);
+            v = (v - (su.x * d.y - d.x * su.y) / det).clamp(range.0, range.1);
+        }
+        (u, v, residual)
+    }
+}
+
+/// The inner control points of the cubic Bézier segment between the `k`-th
+/// and the next of the given points on the Catmull-Rom spline through all of
+/// them.
+///
+/// The spline is extended beyond its ends by mirroring, so that evenly spaced
+/// points yield straight segments with evenly spaced control points.
+fn spline(points: &[Vec2], k: usize) -> [Vec2; 2] {
+    let n = points.len() as isize;
+    let at = |i: isize| {
+        if i < 0 {
+            2.0 * points[0] - points[1]
+        } else if i >= n {
+            2.0 * points[n as usize - 1] - points[n as usize - 2]
+        } else {
+            points[i as usize]
+        }
+    };
+
+    let k = k as isize;
+    [at(k) + (at(k + 1) - at(k - 1)) / 6.0, at(k + 1) - (at(k + 2) - at(k)) / 6.0]
+}
+
+/// Restricts a cubic Bézier curve to the parameters between `a` and `b`.
+fn restrict(p: [Vec2; 4], a: f64, b: f64) -> [Vec2; 4] {
+    let (head, _) = subdivide(p, b);
+    subdivide(head, a / b).1
+}
+
+/// Splits a cubic Bézier curve at a parameter with de Casteljau's algorithm.
+fn subdivide(p: [Vec2; 4], t: f64) -> ([Vec2; 4], [Vec2; 4]) {
+    let p01 = p[0].lerp(p[1], t);
+    let p12 = p[1].lerp(p[2], t);
+    let p23 = p[2].lerp(p[3], t);
+    let p012 = p01.lerp(p12, t);
+    let p123 = p12.lerp(p23, t);
+    let mid = p012.lerp(p123, t);
+    ([p[0], p01, p012, mid], [mid, p123, p23, p[3]])
+}
+
+/// The cubic Bernstein polynomials at `t`.
+fn bernstein(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+    [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t]
+}
+
+/// The derivatives of the cubic Bernstein polynomials at `t`.
+fn bernstein_derivative(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+    [-3.0 * s * s, 3.0 * s * s - 6.0 * s * t, 6.0 * s * t - 3.0 * t * t, 3.0 * t * t]
+}
@@ -1,24 +1,34 @@
 //! Drawing and visualization.
 
+mod blend;
 mod color;
 mod gradient;
 mod image;
 mod line;
+mod marker;
+mod mesh;
 mod paint;
 mod path;
 mod pattern;
 mod polygon;
+mod recolor;
+mod shadow;
 mod shape;
 mod stroke;
 
+pub use self::blend::*;
 pub use self::color::*;
 pub use self::gradient::*;
 pub use self::image::*;
 pub use self::line::*;
+pub use self::marker::*;
+pub use self::mesh::*;
 pub use self::paint::*;
 pub use self::path::*;
 pub use self::pattern::*;
 pub use self::polygon::*;
+pub use self::recolor::*;
+pub use self::shadow::*;
 pub use self::shape::*;
 pub use self::stroke::*;
 
@@ -39,6 +49,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define_type::<Gradient>();
     global.define_type::<Pattern>();
     global.define_type::<Stroke>();
+    global.define_type::<Shadow>();
     global.define_elem::<ImageElem>();
     global.define_elem::<LineElem>();
     global.define_elem::<RectElem>();
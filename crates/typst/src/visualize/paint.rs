@@ -34,6 +34,16 @@ impl Paint {
         }
     }
 
+    /// Sets the relative placement of a gradient or pattern. Solid colors
+    /// are unaffected.
+    pub fn with_relative(self, relative: RelativeTo) -> Self {
+        match self {
+            Self::Solid(color) => Self::Solid(color),
+            Self::Gradient(gradient) => Self::Gradient(gradient.with_relative(relative)),
+            Self::Pattern(pattern) => Self::Pattern(pattern.with_relative(relative)),
+        }
+    }
+
     /// Turns this paint into a paint for a text decoration.
     ///
     /// If this paint is a gradient, it will be converted to a gradient with
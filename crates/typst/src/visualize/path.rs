@@ -1,15 +1,19 @@
-use kurbo::{CubicBez, ParamCurveExtrema};
+use comemo::Tracked;
+use kurbo::{CubicBez, ParamCurve, ParamCurveArclen, ParamCurveExtrema};
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, HintedStrResult, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    array, cast, elem, Array, Content, NativeElement, Packed, Reflect, Resolve, Show,
-    Smart, StyleChain,
+    array, cast, dict, elem, func, scope, Array, Content, Context, Dict, NativeElement,
+    Packed, Reflect, Resolve, Show, Smart, StyleChain,
 };
 use crate::layout::{
-    Abs, Axes, BlockElem, Frame, FrameItem, Length, Point, Region, Rel, Size,
+    Abs, Axes, BlockElem, Frame, FrameItem, Length, Point, Ratio, Region, Rel, Size, Vec2,
+};
+use crate::utils::Numeric;
+use crate::visualize::{
+    advance, FixedStroke, Geometry, Markers, Paint, Shadow, Shape, Stroke,
 };
-use crate::visualize::{FixedStroke, Geometry, Paint, Shape, Stroke};
 
 use PathVertex::{AllControlPoints, MirroredControlPoint, Vertex};
 
@@ -26,7 +30,7 @@ use PathVertex::{AllControlPoints, MirroredControlPoint, Vertex};
 ///   ((50%, 0pt), (40pt, 0pt)),
 /// )
 /// ```
-#[elem(Show)]
+#[elem(scope, Show)]
 pub struct PathElem {
     /// How to fill the path.
     ///
@@ -52,6 +56,35 @@ pub struct PathElem {
     #[default(false)]
     pub closed: bool,
 
+    /// How much to blur the path. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// A drop shadow behind the path's fill. See the [shadow] type for more
+    /// details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// Markers at the start, the inner vertices, and the end of the path.
+    ///
+    /// Can be set to a dictionary with the keys `start`, `mid`, and `end`.
+    /// The markers are the same as for [lines]($line.marker) and follow the
+    /// tangent of the path. Closed paths have no start or end, so only their
+    /// `mid` markers are drawn, at every vertex. Markers are only drawn if the
+    /// path has a stroke.
+    ///
+    /// ```example
+    /// #path(
+    ///   stroke: 1.5pt,
+    ///   marker: (mid: "*", end: ">"),
+    ///   (0pt, 30pt),
+    ///   ((40pt, 0pt), (-20pt, 0pt)),
+    ///   ((80pt, 30pt), (-20pt, 0pt)),
+    /// )
+    /// ```
+    pub marker: Markers,
+
     /// The vertices of the path.
     ///
     /// Each vertex can be defined in 3 ways:
@@ -70,6 +103,127 @@ pub struct PathElem {
     pub vertices: Vec<PathVertex>,
 }
 
+#[scope]
+impl PathElem {
+    /// Computes the bounding box of a path's geometry, ignoring its stroke.
+    ///
+    /// Returns a dictionary with the keys `x` and `y` for the top-left corner
+    /// of the box and `width` and `height` for its size, all relative to the
+    /// path's origin. This is useful to size a container to fit a drawing.
+    ///
+    /// Relative coordinates can't be measured, as they depend on the region
+    /// the path is laid out in. Coordinates with `em` units can only be
+    /// measured within a [context].
+    ///
+    /// ```example
+    /// #let p = path(
+    ///   closed: true,
+    ///   (0pt, 20pt),
+    ///   ((40pt, 20pt), (0pt, -30pt)),
+    /// )
+    /// #let b = path.bounds(p)
+    /// #box(width: b.width, height: b.height, stroke: 0.5pt + gray, p)
+    /// ```
+    #[func]
+    pub fn bounds(
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The path to measure.
+        path: Packed<PathElem>,
+    ) -> HintedStrResult<Dict> {
+        let (min, max) = path.measurable(context)?.bbox().unwrap_or_default();
+        Ok(dict! {
+            "x" => Length::from(min.x),
+            "y" => Length::from(min.y),
+            "width" => Length::from(max.x - min.x),
+            "height" => Length::from(max.y - min.y),
+        })
+    }
+
+    /// Computes the length of a path, that is, the distance travelled when
+    /// following all of its segments.
+    ///
+    /// The same restrictions as for [`path.bounds`]($path.bounds) apply.
+    ///
+    /// ```example
+    /// #path.length(path((0pt, 0pt), (30pt, 40pt)))
+    /// ```
+    #[func]
+    pub fn length(
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The path to measure.
+        path: Packed<PathElem>,
+    ) -> HintedStrResult<Length> {
+        Ok(path.measurable(context)?.length().into())
+    }
+
+    /// Computes the point at a fraction of the way along a path, measured by
+    /// length. The result is a [vector]($vec2) relative to the path's origin.
+    ///
+    /// The same restrictions as for [`path.bounds`]($path.bounds) apply.
+    ///
+    /// ```example
+    /// #let p = path(
+    ///   stroke: blue,
+    ///   (0pt, 40pt),
+    ///   ((80pt, 40pt), (0pt, -40pt)),
+    /// )
+    /// #let m = path.point-at(p, 50%)
+    /// #box(p + place(dx: m.x, dy: m.y, circle(radius: 2pt, fill: red)))
+    /// ```
+    #[func]
+    pub fn point_at(
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The path to follow.
+        path: Packed<PathElem>,
+        /// How far to go along the path, from `{0%}` at its start to `{100%}`
+        /// at its end.
+        t: Ratio,
+    ) -> HintedStrResult<Vec2> {
+        if t < Ratio::zero() || t > Ratio::one() {
+            bail!("position must be between 0% and 100%");
+        }
+        let path = path.measurable(context)?;
+        let Some(point) = path.point_at(t) else {
+            bail!("cannot find a point on an empty path");
+        };
+        Ok(point.into())
+    }
+}
+
+cast! {
+    PathElem,
+    v: Content => v.unpack::<Self>().map_err(|_| "expected path")?,
+}
+
+impl Packed<PathElem> {
+    /// Builds the path's geometry for measurement outside of layout.
+    fn measurable(&self, context: Tracked<Context>) -> HintedStrResult<Path> {
+        if self.vertices().iter().any(PathVertex::is_relative) {
+            bail!(
+                "cannot measure a path with relative coordinates";
+                hint: "use absolute lengths for the path's vertices"
+            );
+        }
+
+        let has_em = self.vertices().iter().any(|v| {
+            [v.vertex(), v.control_point_from(), v.control_point_to()]
+                .iter()
+                .any(|axes| !axes.x.abs.em.is_zero() || !axes.y.abs.em.is_zero())
+        });
+
+        let styles = match context.styles() {
+            Ok(styles) => styles,
+            Err(_) if !has_em => StyleChain::default(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(build_path(self, styles, Size::zero()).0)
+    }
+}
+
 impl Show for Packed<PathElem> {
     fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
         Ok(BlockElem::single_layouter(self.clone(), layout_path).pack())
@@ -80,12 +234,180 @@ impl Show for Packed<PathElem> {
 #[typst_macros::time(span = elem.span())]
 fn layout_path(
     elem: &Packed<PathElem>,
-    _: &mut Engine,
+    engine: &mut Engine,
     styles: StyleChain,
     region: Region,
 ) -> SourceResult<Frame> {
+    let (mut path, size) = build_path(elem, styles, region.size);
+    if path.0.is_empty() {
+        return Ok(Frame::soft(size));
+    }
+
+    // Prepare fill and stroke.
+    let fill = elem.fill(styles);
+    let stroke = match elem.stroke(styles) {
+        Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
+        Smart::Auto => None,
+        Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
+    };
+
+    let mut frame = Frame::soft(size);
+
+    // Add the shadow below the path.
+    if let Some(shadow) = elem.shadow(styles) {
+        let mut shape = Geometry::Path(path.clone()).filled(Paint::Solid(shadow.color));
+        shape.blur = shadow.blur;
+        let pos = Point::new(shadow.dx, shadow.dy);
+        frame.push(pos, FrameItem::Shape(shape, elem.span()));
+    }
+
+    // Find the marker positions and pull back the ends of unfilled open paths
+    // so that they don't stick out of the markers.
+    let markers = elem.marker(styles);
+    let closed = elem.closed(styles);
+    let sites = match &stroke {
+        Some(stroke) if !markers.is_empty() => {
+            let sites = marker_sites(&path, closed);
+            if !closed && fill.is_none() {
+                shorten(&mut path, &sites, markers.insets(stroke));
+            }
+            Some(sites)
+        }
+        _ => None,
+    };
+
+    let blur = elem.blur(styles);
+    let shape = Shape {
+        geometry: Geometry::Path(path),
+        stroke: stroke.clone(),
+        fill,
+        blur,
+    };
+    frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
+
+    if let (Some((start, mids, end)), Some(stroke)) = (sites, &stroke) {
+        markers.layout(
+            engine,
+            styles,
+            &mut frame,
+            stroke,
+            start,
+            &mids,
+            end,
+            elem.span(),
+        )?;
+    }
+
+    Ok(frame)
+}
+
+/// A position on a path along with the direction in which the path passes
+/// through it.
+type MarkerSite = (Point, Point);
+
+/// Finds the sites of the start, inner, and end markers of a path.
+///
+/// Inner vertices point along the bisector of their incoming and outgoing
+/// tangents. For closed paths, all vertices are inner vertices.
+fn marker_sites(
+    path: &Path,
+    closed: bool,
+) -> (Option<MarkerSite>, Vec<MarkerSite>, Option<MarkerSite>) {
+    // Collect the segments as cubic curves.
+    let mut segments = vec![];
+    let mut cursor = Point::zero();
+    for item in &path.0 {
+        match *item {
+            PathItem::MoveTo(p) => cursor = p,
+            PathItem::LineTo(p) => {
+                segments.push([cursor, cursor, p, p]);
+                cursor = p;
+            }
+            PathItem::CubicTo(c1, c2, p) => {
+                segments.push([cursor, c1, c2, p]);
+                cursor = p;
+            }
+            PathItem::ClosePath => {}
+        }
+    }
+
+    let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+        return (None, vec![], None);
+    };
+
+    // The tangents at the start and end of a segment. Control points that
+    // coincide with their vertex don't determine the tangent.
+    let nonzero = |candidates: [Point; 3]| {
+        candidates
+            .into_iter()
+            .find(|p| !p.hypot().is_zero())
+            .unwrap_or_default()
+    };
+    let start_dir = |[p0, c1, c2, p3]: [Point; 4]| nonzero([c1 - p0, c2 - p0, p3 - p0]);
+    let end_dir = |[p0, c1, c2, p3]: [Point; 4]| nonzero([p3 - c2, p3 - c1, p3 - p0]);
+    let unit = |p: Point| if p.hypot().is_zero() { p } else { p / p.hypot().to_raw() };
+    let bisect = |from: [Point; 4], to: [Point; 4]| {
+        let incoming = unit(end_dir(from));
+        let bisector = incoming + unit(start_dir(to));
+        if bisector.hypot().is_zero() {
+            incoming
+        } else {
+            bisector
+        }
+    };
+
+    let mut mids: Vec<MarkerSite> = segments
+        .windows(2)
+        .map(|pair| (pair[0][3], bisect(pair[0], pair[1])))
+        .collect();
+
+    if closed {
+        mids.insert(0, (first[0], bisect(*last, *first)));
+        return (None, mids, None);
+    }
+
+    (Some((first[0], start_dir(*first))), mids, Some((last[3], end_dir(*last))))
+}
+
+/// Pull back the ends of an open path along its tangents.
+///
+/// The control points next to the ends move along with them so that curved
+/// ends keep their shape.
+fn shorten(
+    path: &mut Path,
+    (start, _, end): &(Option<MarkerSite>, Vec<MarkerSite>, Option<MarkerSite>),
+    (start_inset, end_inset): (Abs, Abs),
+) {
+    if start_inset + end_inset >= path.length() {
+        return;
+    }
+
+    if let (Some((_, dir)), [PathItem::MoveTo(p), rest @ ..]) = (start, &mut path.0[..]) {
+        let delta = advance(*p, *dir, start_inset) - *p;
+        *p += delta;
+        if let Some(PathItem::CubicTo(c1, _, _)) = rest.first_mut() {
+            *c1 += delta;
+        }
+    }
+
+    if let (Some((_, dir)), Some(item)) = (end, path.0.last_mut()) {
+        match item {
+            PathItem::LineTo(p) => *p = advance(*p, -*dir, end_inset),
+            PathItem::CubicTo(_, c2, p) => {
+                let delta = advance(*p, -*dir, end_inset) - *p;
+                *c2 += delta;
+                *p += delta;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Builds the geometry of a path element, along with the size of the frame it
+/// is laid out in.
+fn build_path(elem: &Packed<PathElem>, styles: StyleChain, region: Size) -> (Path, Size) {
     let resolve = |axes: Axes<Rel<Length>>| {
-        axes.resolve(styles).zip_map(region.size, Rel::relative_to).to_point()
+        axes.resolve(styles).zip_map(region, Rel::relative_to).to_point()
     };
 
     let vertices = elem.vertices();
@@ -93,7 +415,7 @@ fn layout_path(
 
     let mut size = Size::zero();
     if points.is_empty() {
-        return Ok(Frame::soft(size));
+        return (Path::new(), size);
     }
 
     // Only create a path if there are more than zero points.
@@ -141,18 +463,7 @@ fn layout_path(
         path.close_path();
     }
 
-    // Prepare fill and stroke.
-    let fill = elem.fill(styles);
-    let stroke = match elem.stroke(styles) {
-        Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
-        Smart::Auto => None,
-        Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
-    };
-
-    let mut frame = Frame::soft(size);
-    let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
-    frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
-    Ok(frame)
+    (path, size)
 }
 
 /// A component used for path creation.
@@ -187,6 +498,14 @@ impl PathVertex {
             AllControlPoints(_, a, _) => *a,
         }
     }
+
+    /// Whether any of the vertex's coordinates has a non-zero ratio. Zero
+    /// ratios resolve to zero in any region and thus count as absolute.
+    pub fn is_relative(&self) -> bool {
+        [self.vertex(), self.control_point_from(), self.control_point_to()]
+            .iter()
+            .any(|axes| !axes.x.rel.is_zero() || !axes.y.rel.is_zero())
+    }
 }
 
 cast! {
@@ -314,4 +633,161 @@ impl Path {
 
         Size::new(max_x - min_x, max_y - min_y)
     }
+
+    /// Approximates the path with straight line segments.
+    ///
+    /// Returns one polyline per subpath. The polyline of a closed subpath ends
+    /// in its start point. Subpaths without any segments are skipped.
+    pub fn flatten(&self) -> Vec<Vec<Point>> {
+        let mut polylines = vec![];
+        let mut current: Vec<Point> = vec![];
+        let mut start = Point::zero();
+        let mut cursor = Point::zero();
+
+        for item in &self.0 {
+            match *item {
+                PathItem::MoveTo(to) => {
+                    if current.len() > 1 {
+                        polylines.push(std::mem::take(&mut current));
+                    }
+                    current.clear();
+                    current.push(to);
+                    start = to;
+                    cursor = to;
+                }
+                PathItem::LineTo(to) => {
+                    if current.is_empty() {
+                        current.push(cursor);
+                    }
+                    current.push(to);
+                    cursor = to;
+                }
+                PathItem::CubicTo(c0, c1, end) => {
+                    if current.is_empty() {
+                        current.push(cursor);
+                    }
+
+                    // Subdivide proportionally to the length of the control
+                    // polygon, which bounds the length of the curve.
+                    let bound =
+                        (c0 - cursor).hypot() + (c1 - c0).hypot() + (end - c1).hypot();
+                    let n = bound.to_pt().ceil().clamp(1.0, 64.0) as usize;
+                    for i in 1..=n {
+                        let t = i as f64 / n as f64;
+                        current.push(cubic_point(cursor, c0, c1, end, t));
+                    }
+                    cursor = end;
+                }
+                PathItem::ClosePath => {
+                    if !current.is_empty() && cursor != start {
+                        current.push(start);
+                    }
+                    if current.len() > 1 {
+                        polylines.push(std::mem::take(&mut current));
+                    }
+                    current.clear();
+                    cursor = start;
+                }
+            }
+        }
+
+        if current.len() > 1 {
+            polylines.push(current);
+        }
+
+        polylines
+    }
+
+    /// Computes the exact bounding box of the path as its top-left and
+    /// bottom-right corners, or `None` if the path has no segments.
+    pub fn bbox(&self) -> Option<(Point, Point)> {
+        self.segments()
+            .map(|seg| seg.bounding_box())
+            .reduce(|a, b| a.union(b))
+            .map(|rect| {
+                (
+                    Point::new(Abs::pt(rect.x0), Abs::pt(rect.y0)),
+                    Point::new(Abs::pt(rect.x1), Abs::pt(rect.y1)),
+                )
+            })
+    }
+
+    /// Computes the arc length of the path.
+    pub fn length(&self) -> Abs {
+        Abs::pt(self.segments().map(|seg| seg.arclen(ARCLEN_ACCURACY)).sum())
+    }
+
+    /// Finds the point at the given fraction of the path's arc length, or
+    /// `None` if the path has no segments.
+    pub fn point_at(&self, t: Ratio) -> Option<Point> {
+        let mut remaining = self.length().to_pt() * t.get();
+        let mut last = None;
+        for seg in self.segments() {
+            let length = seg.arclen(ARCLEN_ACCURACY);
+            if remaining <= length {
+                let param = seg.inv_arclen(remaining, ARCLEN_ACCURACY);
+                return Some(from_kurbo(seg.eval(param)));
+            }
+            remaining -= length;
+            last = Some(seg.end());
+        }
+        last.map(from_kurbo)
+    }
+
+    /// The segments of the path, including the closing lines of closed
+    /// subpaths.
+    fn segments(&self) -> impl Iterator<Item = kurbo::PathSeg> + '_ {
+        let mut start = Point::zero();
+        let mut cursor = Point::zero();
+        self.0.iter().filter_map(move |item| match *item {
+            PathItem::MoveTo(to) => {
+                start = to;
+                cursor = to;
+                None
+            }
+            PathItem::LineTo(to) => {
+                let seg = kurbo::Line::new(to_kurbo(cursor), to_kurbo(to));
+                cursor = to;
+                Some(kurbo::PathSeg::Line(seg))
+            }
+            PathItem::CubicTo(c0, c1, end) => {
+                let seg = CubicBez::new(
+                    to_kurbo(cursor),
+                    to_kurbo(c0),
+                    to_kurbo(c1),
+                    to_kurbo(end),
+                );
+                cursor = end;
+                Some(kurbo::PathSeg::Cubic(seg))
+            }
+            PathItem::ClosePath => {
+                let seg = (cursor != start)
+                    .then(|| kurbo::Line::new(to_kurbo(cursor), to_kurbo(start)));
+                cursor = start;
+                seg.map(kurbo::PathSeg::Line)
+            }
+        })
+    }
+}
+
+/// The accuracy, in points, of arc length computations.
+const ARCLEN_ACCURACY: f64 = 1e-3;
+
+/// Converts a point into a kurbo point.
+fn to_kurbo(point: Point) -> kurbo::Point {
+    kurbo::Point::new(point.x.to_pt(), point.y.to_pt())
+}
+
+/// Converts a kurbo point into a point.
+fn from_kurbo(point: kurbo::Point) -> Point {
+    Point::new(Abs::pt(point.x), Abs::pt(point.y))
+}
+
+/// Evaluates a cubic bezier curve at the parameter `t`.
+fn cubic_point(p0: Point, p1: Point, p2: Point, p3: Point, t: f64) -> Point {
+    let mt = 1.0 - t;
+    p0 * (mt * mt * mt)
+        + p1 * (3.0 * mt * mt * t)
+        + p2 * (3.0 * mt * t * t)
+        + p3 * (t * t * t)
 }
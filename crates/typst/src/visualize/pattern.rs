@@ -6,7 +6,7 @@ use ecow::{eco_format, EcoString};
 use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{func, repr, scope, ty, Content, Smart, StyleChain};
-use crate::layout::{Abs, Axes, Frame, Length, Regions, Size};
+use crate::layout::{Abs, Axes, Frame, Length, Regions, Size, Transform};
 use crate::syntax::{Span, Spanned};
 use crate::utils::{LazyHash, Numeric};
 use crate::visualize::RelativeTo;
@@ -31,11 +31,12 @@ use crate::World;
 /// #rect(fill: pat, width: 100%, height: 60pt, stroke: 1pt)
 /// ```
 ///
-/// Patterns are also supported on text, but only when setting the
-/// [relativeness]($pattern.relative) to either `{auto}` (the default value) or
-/// `{"parent"}`. To create word-by-word or glyph-by-glyph patterns, you can
-/// wrap the words or characters of your text in [boxes]($box) manually or
-/// through a [show rule]($styling/#show-rules).
+/// Patterns are also supported on text. By default, they are relative to the
+/// text's parent container. With a [relativeness]($pattern.relative) of
+/// `{"self"}`, a pattern instead spans the text it is applied to, across all
+/// lines of a paragraph. To create word-by-word or glyph-by-glyph patterns,
+/// you can wrap the words or characters of your text in [boxes]($box)
+/// manually or through a [show rule]($styling/#show-rules).
 ///
 /// ```example
 /// #let pat = pattern(
@@ -108,6 +109,8 @@ struct Repr {
     spacing: Size,
     /// The pattern's relative transform.
     relative: Smart<RelativeTo>,
+    /// The transform applied to the pattern's tiling.
+    transform: Transform,
 }
 
 #[scope]
@@ -152,9 +155,32 @@ impl Pattern {
         #[named]
         #[default(Smart::Auto)]
         relative: Smart<RelativeTo>,
+        /// A [transform] to apply to the tiling of the pattern, for instance
+        /// to rotate its cells. The transform's origin is the origin of the
+        /// element or parent the pattern is relative to.
+        ///
+        /// ```example
+        /// #let pat = pattern(
+        ///   size: (10pt, 10pt),
+        ///   transform: transform.rotate(30deg),
+        ///   line(start: (0pt, 5pt), end: (10pt, 5pt)),
+        /// )
+        ///
+        /// #rect(width: 100%, height: 40pt, fill: pat)
+        /// ```
+        #[named]
+        #[default(Transform::identity())]
+        transform: Transform,
         /// The content of each cell of the pattern.
         body: Content,
     ) -> SourceResult<Pattern> {
+        if relative == Smart::Custom(RelativeTo::Stroke) {
+            bail!(
+                span, "patterns cannot be relative to the stroke";
+                hint: "try using `relative: \"self\"` instead"
+            );
+        }
+
         let size_span = size.span;
         if let Smart::Custom(size) = size.v {
             // Ensure that sizes are absolute.
@@ -211,6 +237,7 @@ impl Pattern {
             frame: LazyHash::new(frame),
             spacing: spacing.v.map(|l| l.abs),
             relative,
+            transform,
         })))
     }
 }
@@ -230,6 +257,13 @@ impl Pattern {
         self
     }
 
+    /// Clones this pattern, but with its frame mapped through a function.
+    pub fn map_frame(mut self, f: impl FnOnce(&Frame) -> Frame) -> Self {
+        let frame = f(&self.0.frame);
+        Arc::make_mut(&mut self.0).frame = LazyHash::new(frame);
+        self
+    }
+
     /// Return the frame of the pattern.
     pub fn frame(&self) -> &Frame {
         &self.0.frame
@@ -250,6 +284,11 @@ impl Pattern {
         self.0.relative
     }
 
+    /// Return the transform applied to the pattern's tiling.
+    pub fn transform(&self) -> Transform {
+        self.0.transform
+    }
+
     /// Returns the relative placement of the pattern.
     pub fn unwrap_relative(&self, on_text: bool) -> RelativeTo {
         self.0.relative.unwrap_or_else(|| {
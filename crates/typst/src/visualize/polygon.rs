@@ -8,7 +8,7 @@ use crate::foundations::{
 use crate::layout::{Axes, BlockElem, Em, Frame, FrameItem, Length, Point, Region, Rel};
 use crate::syntax::Span;
 use crate::utils::Numeric;
-use crate::visualize::{FixedStroke, Geometry, Paint, Path, Shape, Stroke};
+use crate::visualize::{FixedStroke, Geometry, Paint, Path, Shadow, Shape, Stroke};
 
 /// A closed polygon.
 ///
@@ -44,6 +44,16 @@ pub struct PolygonElem {
     #[fold]
     pub stroke: Smart<Option<Stroke>>,
 
+    /// How much to blur the polygon. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// A drop shadow behind the polygon. See the [shadow] type for more
+    /// details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The vertices of the polygon. Each point is specified as an array of two
     /// [relative lengths]($relative).
     #[variadic]
@@ -171,7 +181,16 @@ fn layout_polygon(
     }
     path.close_path();
 
-    let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
+    // Add the shadow below the polygon.
+    if let Some(shadow) = elem.shadow(styles) {
+        let mut shape = Geometry::Path(path.clone()).filled(Paint::Solid(shadow.color));
+        shape.blur = shadow.blur;
+        let pos = Point::new(shadow.dx, shadow.dy);
+        frame.push(pos, FrameItem::Shape(shape, elem.span()));
+    }
+
+    let blur = elem.blur(styles);
+    let shape = Shape { geometry: Geometry::Path(path), stroke, fill, blur };
     frame.push(Point::zero(), FrameItem::Shape(shape, elem.span()));
     Ok(frame)
 }
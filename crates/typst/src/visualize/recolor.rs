@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::Cursor;
+
+use image::{DynamicImage, Rgba};
+
+use crate::foundations::Bytes;
+use crate::layout::{Frame, FrameItem, GroupItem, Ratio, Size, Transform};
+use crate::model::Document;
+use crate::syntax::Span;
+use crate::text::TextItem;
+use crate::visualize::{
+    Color, FixedStroke, Image, ImageKind, Luma, Paint, PdfImage, RasterFormat,
+    RasterImage, Shape, SvgImage,
+};
+
+/// A conversion of all colors in a document.
+///
+/// Applied to a finished document before exporting it, for instance to print
+/// cheaply in black and white or to target an e-ink display with a limited
+/// palette. The conversion applies to text, shapes, gradients, patterns, and
+/// images. SVG images are rewritten with their text turned into paths, but
+/// raster images embedded in them keep their colors. Pages of PDF documents
+/// are [converted into frames](super::PdfImage::frame) first.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Recolor {
+    /// Converts all colors to shades of gray with the same luminance.
+    Grayscale,
+    /// Replaces all colors with the perceptually closest color of a palette.
+    /// The palette must not be empty.
+    Palette(Vec<Color>),
+    /// Shows how much of one process ink a color needs as a shade of gray,
+    /// like the printing plate for the ink would. Grays are printed with
+    /// black ink alone and CMYK colors as they are. Other colors are
+    /// converted to CMYK first.
+    Separation(Ink),
+}
+
+/// One of the four process inks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Ink {
+    /// The cyan ink.
+    Cyan,
+    /// The magenta ink.
+    Magenta,
+    /// The yellow ink.
+    Yellow,
+    /// The black ink, also called key.
+    Black,
+}
+
+impl Recolor {
+    /// Convert all colors in a document.
+    #[typst_macros::time(name = "recolor")]
+    pub fn document(&self, document: &Document) -> Document {
+        let mut document = document.clone();
+        for page in &mut document.pages {
+            page.frame = self.frame(&page.frame);
+        }
+        document
+    }
+
+    /// Convert all colors in a frame.
+    pub fn frame(&self, frame: &Frame) -> Frame {
+        let mut output = frame.clone();
+        output.clear();
+        for (pos, item) in frame.items() {
+            output.push(*pos, self.item(item));
+        }
+        output
+    }
+
+    /// Convert a single color.
+    pub fn color(&self, color: Color) -> Color {
+        let alpha = color.alpha().unwrap_or(1.0);
+        match self {
+            Self::Grayscale => color.to_luma(),
+            Self::Palette(palette) => {
+                let [l, a, b, _] = color.to_oklab().to_vec4();
+                let distance = |candidate: &Color| {
+                    let [cl, ca, cb, _] = candidate.to_oklab().to_vec4();
+                    (l - cl).powi(2) + (a - ca).powi(2) + (b - cb).powi(2)
+                };
+                palette
+                    .iter()
+                    .copied()
+                    .min_by(|x, y| distance(x).total_cmp(&distance(y)))
+                    .unwrap_or(color)
+                    .with_alpha(alpha)
+            }
+            Self::Separation(ink) => {
+                let inks = match color {
+                    Color::Luma(luma) => [0.0, 0.0, 0.0, 1.0 - luma.luma],
+                    _ => color.to_cmyk().to_vec4(),
+                };
+                Color::Luma(Luma::new(1.0 - inks[*ink as usize], alpha))
+            }
+        }
+    }
+
+    /// Convert the colors of a frame item.
+    fn item(&self, item: &FrameItem) -> FrameItem {
+        match item {
+            FrameItem::Group(group) => FrameItem::Group(GroupItem {
+                frame: self.frame(&group.frame),
+                ..group.clone()
+            }),
+            FrameItem::Text(text) => FrameItem::Text(TextItem {
+                fill: text.fill.as_ref().map(|fill| self.paint(fill)),
+                stroke: text.stroke.as_ref().map(|stroke| self.stroke(stroke)),
+                ..text.clone()
+            }),
+            FrameItem::Shape(shape, span) => FrameItem::Shape(
+                Shape {
+                    fill: shape.fill.as_ref().map(|fill| self.paint(fill)),
+                    stroke: shape.stroke.as_ref().map(|stroke| self.stroke(stroke)),
+                    ..shape.clone()
+                },
+                *span,
+            ),
+            FrameItem::Image(image, size, span) => self.image(image, *size, *span),
+            FrameItem::Link(..) | FrameItem::Tag(_) => item.clone(),
+        }
+    }
+
+    /// Convert the colors of a paint.
+    fn paint(&self, paint: &Paint) -> Paint {
+        match paint {
+            Paint::Solid(color) => Paint::Solid(self.color(*color)),
+            Paint::Gradient(gradient) => {
+                Paint::Gradient(gradient.clone().map_colors(|color| self.color(color)))
+            }
+            Paint::Pattern(pattern) => {
+                Paint::Pattern(pattern.clone().map_frame(|frame| self.frame(frame)))
+            }
+        }
+    }
+
+    /// Convert the colors of a stroke.
+    fn stroke(&self, stroke: &FixedStroke) -> FixedStroke {
+        FixedStroke { paint: self.paint(&stroke.paint), ..stroke.clone() }
+    }
+
+    /// Convert the colors of an image.
+    fn image(&self, image: &Image, size: Size, span: Span) -> FrameItem {
+        let ImageKind::Pdf(pdf) = image.kind() else {
+            return FrameItem::Image(convert_image(self, image), size, span);
+        };
+
+        let frame = convert_pdf(self, pdf);
+        let transform = Transform::scale(
+            Ratio::new(size.x / frame.width()),
+            Ratio::new(size.y / frame.height()),
+        );
+        FrameItem::Group(GroupItem {
+            transform,
+            alt: image.alt().map(Into::into),
+            ..GroupItem::new(frame)
+        })
+    }
+
+    /// Convert the pixels of a raster image and encode the result in the
+    /// image's original format.
+    fn raster(&self, raster: &RasterImage) -> Option<Bytes> {
+        let mut buffer = raster.dynamic().to_rgba8();
+        let mut cache = HashMap::new();
+        for pixel in buffer.pixels_mut() {
+            let [r, g, b, a] = pixel.0;
+            *pixel = *cache.entry(pixel.0).or_insert_with(|| {
+                Rgba(self.color(Color::from_u8(r, g, b, a)).to_rgb().to_vec4_u8())
+            });
+        }
+
+        // JPEGs can't have an alpha channel.
+        let dynamic = match raster.format() {
+            RasterFormat::Jpg => {
+                DynamicImage::ImageRgb8(DynamicImage::from(buffer).to_rgb8())
+            }
+            _ => DynamicImage::ImageRgba8(buffer),
+        };
+
+        let mut data = vec![];
+        dynamic
+            .write_to(
+                &mut Cursor::new(&mut data),
+                image::ImageFormat::from(raster.format()),
+            )
+            .ok()?;
+        Some(data.into())
+    }
+
+    /// Convert the colors of an SVG image.
+    ///
+    /// The image is written back from its parsed tree, which spells out all
+    /// colors as hexadecimal values of a few presentation attributes.
+    fn svg(&self, svg: &SvgImage) -> Bytes {
+        const ATTRS: &[&str] =
+            &[" fill", " stroke", " stop-color", " flood-color", " lighting-color"];
+
+        let written = svg.tree().to_string(&usvg::WriteOptions::default());
+        let mut output = String::with_capacity(written.len());
+        let mut rest = written.as_str();
+        while let Some(i) = rest.find("=\"#") {
+            let (head, tail) = rest.split_at(i + 3);
+            output.push_str(head);
+            rest = tail;
+
+            let is_color = ATTRS.iter().any(|attr| head[..i].ends_with(attr));
+            let Some(hex) = tail.get(..6).filter(|hex| {
+                is_color && hex.bytes().all(|byte| byte.is_ascii_hexdigit())
+            }) else {
+                continue;
+            };
+
+            let rgb = u32::from_str_radix(hex, 16).unwrap();
+            let [r, g, b, _] =
+                self.color(Color::from_u32(rgb << 8 | 0xFF)).to_rgb().to_vec4_u8();
+            write!(output, "{r:02x}{g:02x}{b:02x}").unwrap();
+            rest = &tail[6..];
+        }
+        output.push_str(rest);
+        Bytes::from(output.into_bytes())
+    }
+}
+
+/// Convert the colors of a raster or SVG image, keeping its format.
+#[comemo::memoize]
+fn convert_image(recolor: &Recolor, image: &Image) -> Image {
+    let data = match image.kind() {
+        ImageKind::Raster(raster) => recolor.raster(raster),
+        ImageKind::Svg(svg) => Some(recolor.svg(svg)),
+        ImageKind::Pdf(_) => None,
+    };
+    data.and_then(|data| {
+        Image::new(data, image.format(), image.alt().map(Into::into)).ok()
+    })
+    .unwrap_or_else(|| image.clone())
+}
+
+/// Convert a page of a PDF document into a frame with converted colors.
+#[comemo::memoize]
+fn convert_pdf(recolor: &Recolor, pdf: &PdfImage) -> Frame {
+    recolor.frame(&pdf.frame(Span::detached()))
+}
@@ -0,0 +1,159 @@
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, HintedStrResult, SourceResult};
+use crate::foundations::{
+    cast, func, scope, ty, Dict, FromValue, Repr, Resolve, StyleChain,
+};
+use crate::layout::{Abs, Length};
+use crate::syntax::Span;
+use crate::utils::Numeric;
+use crate::visualize::Color;
+
+/// A blurred drop shadow behind a container or shape.
+///
+/// A shadow is a copy of the element's outline, filled with a solid color,
+/// displaced by an offset, and softened with a gaussian blur. Boxes, blocks,
+/// and all shapes accept a shadow through their `shadow` property.
+///
+/// # Example
+/// ```example
+/// #set rect(fill: white, stroke: 0.5pt + gray)
+/// #stack(
+///   dir: ltr,
+///   spacing: 1fr,
+///   rect(shadow: shadow()),
+///   rect(radius: 4pt, shadow: (dx: 3pt, dy: 3pt, blur: 0pt)),
+///   circle(shadow: (blur: 8pt, color: blue.transparentize(50%))),
+/// )
+/// ```
+///
+/// # Blur radius
+/// Like in CSS, the `blur` of a shadow is a radius: The shadow fades out
+/// over roughly this distance on each side of the outline. The standard
+/// deviation of the underlying gaussian is half the radius. A radius of
+/// `{0pt}` results in a hard shadow.
+///
+/// # Fields
+/// On a shadow object, you can access any of the fields listed in the
+/// constructor function. For example, `{shadow(dx: 2pt).dx}` is `{2pt}`.
+#[ty(scope, cast)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Shadow<T: Numeric = Length> {
+    /// The horizontal offset of the shadow.
+    pub dx: T,
+    /// The vertical offset of the shadow.
+    pub dy: T,
+    /// The blur radius of the shadow.
+    pub blur: T,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self {
+            dx: Abs::zero().into(),
+            dy: Abs::pt(2.0).into(),
+            blur: Abs::pt(4.0).into(),
+            color: Color::BLACK.with_alpha(0.4),
+        }
+    }
+}
+
+#[scope]
+impl Shadow {
+    /// Constructs a new shadow.
+    ///
+    /// Instead of calling this function, you can also pass a dictionary with
+    /// any of the parameters below or just a color to any `shadow` property.
+    ///
+    /// ```example
+    /// #let s = shadow(dy: 4pt)
+    /// #box(inset: 6pt, fill: white, shadow: s)[Lifted]
+    /// ```
+    #[func(constructor)]
+    pub fn construct(
+        /// The call site of this function.
+        span: Span,
+        /// The horizontal offset of the shadow.
+        #[named]
+        #[default(Abs::zero().into())]
+        dx: Length,
+        /// The vertical offset of the shadow.
+        #[named]
+        #[default(Abs::pt(2.0).into())]
+        dy: Length,
+        /// The blur radius of the shadow. Must not be negative.
+        #[named]
+        #[default(Abs::pt(4.0).into())]
+        blur: Length,
+        /// The color of the shadow.
+        #[named]
+        #[default(Color::BLACK.with_alpha(0.4))]
+        color: Color,
+    ) -> SourceResult<Shadow> {
+        if blur.abs < Abs::zero() || blur.em.get() < 0.0 {
+            bail!(span, "shadow blur radius must not be negative");
+        }
+        Ok(Self { dx, dy, blur, color })
+    }
+}
+
+impl Resolve for Shadow {
+    type Output = Shadow<Abs>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        Shadow {
+            dx: self.dx.resolve(styles),
+            dy: self.dy.resolve(styles),
+            blur: self.blur.resolve(styles).max(Abs::zero()),
+            color: self.color,
+        }
+    }
+}
+
+impl<T: Numeric + Repr> Repr for Shadow<T> {
+    fn repr(&self) -> EcoString {
+        let Self { dx, dy, blur, color } = self;
+        eco_format!(
+            "shadow(dx: {}, dy: {}, blur: {}, color: {})",
+            dx.repr(),
+            dy.repr(),
+            blur.repr(),
+            color.repr()
+        )
+    }
+}
+
+cast! {
+    type Shadow,
+    color: Color => Self { color, ..Default::default() },
+    mut dict: Dict => {
+        fn take<T: FromValue>(dict: &mut Dict, key: &str) -> HintedStrResult<Option<T>> {
+            dict.take(key).ok().map(T::from_value).transpose()
+        }
+
+        let default = Self::default();
+        let dx = take::<Length>(&mut dict, "dx")?.unwrap_or(default.dx);
+        let dy = take::<Length>(&mut dict, "dy")?.unwrap_or(default.dy);
+        let blur = take::<Length>(&mut dict, "blur")?.unwrap_or(default.blur);
+        let color = take::<Color>(&mut dict, "color")?.unwrap_or(default.color);
+        dict.finish(&["dx", "dy", "blur", "color"])?;
+
+        if blur.abs < Abs::zero() || blur.em.get() < 0.0 {
+            Err("shadow blur radius must not be negative")?;
+        }
+
+        Self { dx, dy, blur, color }
+    },
+}
+
+cast! {
+    Shadow<Abs>,
+    self => Shadow {
+        dx: Length::from(self.dx),
+        dy: Length::from(self.dy),
+        blur: Length::from(self.blur),
+        color: self.color,
+    }.into_value(),
+}
@@ -1,4 +1,7 @@
 use std::f64::consts::SQRT_2;
+use std::sync::Arc;
+
+use kurbo::Vec2;
 
 use crate::diag::SourceResult;
 use crate::engine::Engine;
@@ -9,7 +12,10 @@ use crate::layout::{
 };
 use crate::syntax::Span;
 use crate::utils::Get;
-use crate::visualize::{FixedStroke, Paint, Path, Stroke};
+use crate::visualize::{
+    FixedStroke, Gradient, LineJoin, MeshGradient, MeshPatch, Paint, Path, RatioOrAngle,
+    RelativeTo, Shadow, Stroke,
+};
 
 /// A rectangle with optional content.
 ///
@@ -123,6 +129,27 @@ pub struct RectElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// How much to blur the rectangle.
+    ///
+    /// The blur is a gaussian blur applied to the fill and stroke. Like a
+    /// [shadow's blur]($shadow), it is given as a radius. The standard
+    /// deviation of the gaussian is half of it.
+    ///
+    /// ```example
+    /// #rect(fill: blue, blur: 4pt)
+    /// ```
+    #[resolve]
+    pub blur: Length,
+
+    /// A drop shadow behind the rectangle. See the [shadow] type for more
+    /// details.
+    ///
+    /// ```example
+    /// #rect(fill: white, stroke: gray, shadow: (dy: 3pt, blur: 6pt))
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the rectangle.
     ///
     /// When this is omitted, the rectangle takes on a default size of at most
@@ -145,6 +172,8 @@ impl Show for Packed<RectElem> {
                 elem.stroke(styles),
                 elem.inset(styles),
                 elem.outset(styles),
+                elem.blur(styles),
+                elem.shadow(styles),
                 elem.radius(styles),
                 elem.span(),
             )
@@ -227,6 +256,15 @@ pub struct SquareElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// How much to blur the square. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// A drop shadow behind the square. See the [shadow] type for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the square. The square expands to fit this
     /// content, keeping the 1-1 aspect ratio.
     ///
@@ -250,6 +288,8 @@ impl Show for Packed<SquareElem> {
                 elem.stroke(styles),
                 elem.inset(styles),
                 elem.outset(styles),
+                elem.blur(styles),
+                elem.shadow(styles),
                 elem.radius(styles),
                 elem.span(),
             )
@@ -305,6 +345,15 @@ pub struct EllipseElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// How much to blur the ellipse. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// A drop shadow behind the ellipse. See the [shadow] type for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the ellipse.
     ///
     /// When this is omitted, the ellipse takes on a default size of at most
@@ -327,6 +376,8 @@ impl Show for Packed<EllipseElem> {
                 elem.stroke(styles).map(|s| Sides::splat(Some(s))),
                 elem.inset(styles),
                 elem.outset(styles),
+                elem.blur(styles),
+                elem.shadow(styles),
                 Corners::splat(None),
                 elem.span(),
             )
@@ -409,6 +460,15 @@ pub struct CircleElem {
     #[fold]
     pub outset: Sides<Option<Rel<Length>>>,
 
+    /// How much to blur the circle. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// A drop shadow behind the circle. See the [shadow] type for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
     /// The content to place into the circle. The circle expands to fit this
     /// content, keeping the 1-1 aspect ratio.
     #[positional]
@@ -429,6 +489,8 @@ impl Show for Packed<CircleElem> {
                 elem.stroke(styles).map(|s| Sides::splat(Some(s))),
                 elem.inset(styles),
                 elem.outset(styles),
+                elem.blur(styles),
+                elem.shadow(styles),
                 Corners::splat(None),
                 elem.span(),
             )
@@ -452,6 +514,8 @@ fn layout_shape(
     stroke: Smart<Sides<Option<Option<Stroke<Abs>>>>>,
     inset: Sides<Option<Rel<Abs>>>,
     outset: Sides<Option<Rel<Abs>>>,
+    blur: Abs,
+    shadow: Option<Shadow<Abs>>,
     radius: Corners<Option<Rel<Abs>>>,
     span: Span,
 ) -> SourceResult<Frame> {
@@ -505,23 +569,37 @@ fn layout_shape(
         }
     };
 
+    let outset = outset.unwrap_or_default().relative_to(frame.size());
+    let size = frame.size() + outset.sum_by_axis();
+    let pos = Point::new(-outset.left, -outset.top);
+    let radius = radius.unwrap_or_default();
+
     // Add fill and/or stroke.
     if fill.is_some() || stroke.iter().any(Option::is_some) {
-        if kind.is_round() {
-            let outset = outset.unwrap_or_default().relative_to(frame.size());
-            let size = frame.size() + outset.sum_by_axis();
-            let pos = Point::new(-outset.left, -outset.top);
-            let shape = ellipse(size, fill, stroke.left);
-            frame.prepend(pos, FrameItem::Shape(shape, span));
+        let shapes = if kind.is_round() {
+            vec![ellipse(size, fill, stroke.left)]
         } else {
-            frame.fill_and_stroke(
-                fill,
-                &stroke,
-                &outset.unwrap_or_default(),
-                &radius.unwrap_or_default(),
-                span,
-            );
-        }
+            styled_rect(size, &radius, fill, &stroke)
+        };
+        frame.prepend_multiple(shapes.into_iter().map(|mut shape| {
+            shape.blur = blur;
+            (pos, FrameItem::Shape(shape, span))
+        }));
+    }
+
+    // Add the shadow below everything else.
+    if let Some(shadow) = shadow {
+        let fill = Some(Paint::Solid(shadow.color));
+        let shapes = if kind.is_round() {
+            vec![ellipse(size, fill, None)]
+        } else {
+            styled_rect(size, &radius, fill, &Sides::splat(None))
+        };
+        let pos = pos + Point::new(shadow.dx, shadow.dy);
+        frame.prepend_multiple(shapes.into_iter().map(|mut shape| {
+            shape.blur = shadow.blur;
+            (pos, FrameItem::Shape(shape, span))
+        }));
     }
 
     Ok(frame)
@@ -561,6 +639,9 @@ pub struct Shape {
     pub fill: Option<Paint>,
     /// The shape's border stroke.
     pub stroke: Option<FixedStroke>,
+    /// The radius of a gaussian blur applied to the whole shape. The shape is
+    /// drawn sharp if this is zero.
+    pub blur: Abs,
 }
 
 /// A shape's geometry.
@@ -577,12 +658,22 @@ pub enum Geometry {
 impl Geometry {
     /// Fill the geometry without a stroke.
     pub fn filled(self, fill: Paint) -> Shape {
-        Shape { geometry: self, fill: Some(fill), stroke: None }
+        Shape {
+            geometry: self,
+            fill: Some(fill),
+            stroke: None,
+            blur: Abs::zero(),
+        }
     }
 
     /// Stroke the geometry without a fill.
     pub fn stroked(self, stroke: FixedStroke) -> Shape {
-        Shape { geometry: self, fill: None, stroke: Some(stroke) }
+        Shape {
+            geometry: self,
+            fill: None,
+            stroke: Some(stroke),
+            blur: Abs::zero(),
+        }
     }
 
     /// The bounding box of the geometry.
@@ -593,6 +684,207 @@ impl Geometry {
             Self::Path(p) => p.bbox_size(),
         }
     }
+
+    /// Approximates the outline of the geometry with straight line segments.
+    ///
+    /// Returns one polyline per subpath, see [`Path::flatten`].
+    pub fn flatten(&self) -> Vec<Vec<Point>> {
+        match self {
+            Self::Line(line) => vec![vec![Point::zero(), *line]],
+            Self::Rect(size) => Path::rect(*size).flatten(),
+            Self::Path(path) => path.flatten(),
+        }
+    }
+}
+
+impl Shape {
+    /// The region that a blurred rendition of the shape can cover, relative
+    /// to the shape's origin. Returns its top-left and bottom-right corners.
+    ///
+    /// The region includes the stroke and extends three standard deviations
+    /// of the blur in each direction, beyond which the blur is invisible.
+    pub fn blur_bounds(&self) -> (Point, Point) {
+        let mut min = Point::splat(Abs::inf());
+        let mut max = Point::splat(-Abs::inf());
+        for point in self.geometry.flatten().into_iter().flatten() {
+            min = min.min(point);
+            max = max.max(point);
+        }
+
+        if min.x > max.x || min.y > max.y {
+            return (Point::zero(), Point::zero());
+        }
+
+        let stroke = self.stroke.as_ref().map_or(Abs::zero(), |stroke| {
+            stroke.thickness / 2.0 * stroke.miter_limit.get().max(1.0)
+        });
+        let margin = stroke + self.blur * 1.5;
+        (min - Point::splat(margin), max + Point::splat(margin))
+    }
+
+    /// Resolves a stroke that is painted with a gradient which is
+    /// [relative](RelativeTo::Stroke) to the stroke.
+    ///
+    /// Returns a copy of the stroke whose paint is a mesh gradient with
+    /// patches that follow the stroked path, along with the rectangle that
+    /// the gradient spans: its top-left corner relative to the shape's origin
+    /// and its size. Exporters draw the stroke as usual, but lay out its
+    /// gradient in this rectangle. The mesh covers a band around the path
+    /// that is wider than the stroke, so that the stroke, including its caps,
+    /// joins, and dashes, only shows the mesh's interior.
+    ///
+    /// Returns `None` if the stroke can be drawn as-is.
+    pub fn stroke_along_path(&self) -> Option<(FixedStroke, Point, Size)> {
+        let stroke = self.stroke.as_ref()?;
+        let Paint::Gradient(gradient) = &stroke.paint else { return None };
+        if gradient.relative() != Smart::Custom(RelativeTo::Stroke)
+            || stroke.thickness <= Abs::zero()
+        {
+            return None;
+        }
+
+        let mut polylines: Vec<Vec<Vec2>> = self
+            .geometry
+            .flatten()
+            .into_iter()
+            .map(|polyline| {
+                let mut points: Vec<Vec2> = polyline
+                    .into_iter()
+                    .map(|p| Vec2::new(p.x.to_pt(), p.y.to_pt()))
+                    .collect();
+                points.dedup();
+                points
+            })
+            .collect();
+        polylines.retain(|polyline| polyline.len() > 1);
+
+        let total: f64 = polylines
+            .iter()
+            .flat_map(|polyline| polyline.windows(2))
+            .map(|w| (w[1] - w[0]).hypot())
+            .sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        // The colors are interpolated linearly between the ends of each
+        // piece of the band, which matches the gradient exactly if the pieces
+        // are split at its stops. Mesh gradients have no stops, so we split
+        // them evenly.
+        let breaks: Vec<f64> = match gradient.stops_ref() {
+            [] => (1..64).map(|i| total * i as f64 / 64.0).collect(),
+            stops => stops.iter().map(|(_, offset)| total * offset.get()).collect(),
+        };
+        let color = |at: f64| {
+            let t = at.clamp(0.0, total) / total;
+            gradient.sample(RatioOrAngle::Ratio(Ratio::new(t)))
+        };
+
+        let width = stroke.thickness.to_pt();
+        let half = width;
+        let miter = match stroke.join {
+            LineJoin::Miter => stroke.miter_limit.get(),
+            LineJoin::Round | LineJoin::Bevel => 0.0,
+        };
+
+        let mut quads = vec![];
+        let mut travelled = 0.0;
+        for polyline in &polylines {
+            let closed = polyline.len() > 2 && polyline.first() == polyline.last();
+            let edges = polyline.len() - 1;
+            for (k, window) in polyline.windows(2).enumerate() {
+                let (a, b) = (window[0], window[1]);
+                let length = (b - a).hypot();
+                let dir = (b - a) / length;
+                let normal = Vec2::new(-dir.y, dir.x) * half;
+
+                // Open ends are extended to cover the caps.
+                let mut cuts = vec![if !closed && k == 0 { -half } else { 0.0 }];
+                cuts.extend(
+                    breaks
+                        .iter()
+                        .map(|&at| at - travelled)
+                        .filter(|&at| at > 0.0 && at < length),
+                );
+                cuts.push(if !closed && k + 1 == edges { length + half } else { length });
+
+                for cut in cuts.windows(2) {
+                    let (p0, p1) = (a + dir * cut[0], a + dir * cut[1]);
+                    let c0 = color(travelled + cut[0]);
+                    let c1 = color(travelled + cut[1]);
+                    quads.push((
+                        [[p0 - normal, p0 + normal], [p1 - normal, p1 + normal]],
+                        [[c0, c0], [c1, c1]],
+                    ));
+                }
+
+                // The outer side of a join is covered by two triangles that
+                // reach beyond any round or mitered corner.
+                let next = match polyline.get(k + 2) {
+                    Some(&next) => Some(next),
+                    None if closed => Some(polyline[1]),
+                    None => None,
+                };
+                if let Some(next) = next.filter(|&next| next != b) {
+                    let turn = (next - b).normalize();
+                    let cos = dir.dot(turn).clamp(-1.0, 1.0);
+                    if cos < 1.0 - 1e-9 {
+                        let side = if dir.cross(turn) > 0.0 { -1.0 } else { 1.0 };
+                        let outer0 = Vec2::new(-dir.y, dir.x) * half * side;
+                        let outer1 = Vec2::new(-turn.y, turn.x) * half * side;
+                        let sum = outer0 + outer1;
+                        let bisector =
+                            if sum.hypot() > 1e-9 { sum / sum.hypot() } else { dir };
+                        let ratio = 1.0 / ((1.0 + cos) / 2.0).sqrt();
+                        let reach = if ratio <= miter {
+                            half.max(width / 2.0 * ratio)
+                        } else {
+                            half
+                        };
+                        let tip = b + bisector * reach;
+                        let c = color(travelled + length);
+                        for (from, to) in [(b + outer0, tip), (tip, b + outer1)] {
+                            quads.push(([[b, b], [from, to]], [[c, c], [c, c]]));
+                        }
+                    }
+                }
+
+                travelled += length;
+            }
+        }
+
+        let mut min = Vec2::new(f64::INFINITY, f64::INFINITY);
+        let mut max = Vec2::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for p in quads.iter().flat_map(|(corners, _)| corners.iter().flatten()) {
+            min = Vec2::new(min.x.min(p.x), min.y.min(p.y));
+            max = Vec2::new(max.x.max(p.x), max.y.max(p.y));
+        }
+
+        let size = max - min;
+        let patches = quads
+            .into_iter()
+            .map(|(corners, colors)| {
+                let unit = corners.map(|column| {
+                    column.map(|p| {
+                        Vec2::new((p.x - min.x) / size.x, (p.y - min.y) / size.y)
+                    })
+                });
+                MeshPatch::quad(unit, colors)
+            })
+            .collect();
+
+        let mesh = MeshGradient::from_patches(patches, gradient.space());
+        let stroke = FixedStroke {
+            paint: Paint::Gradient(Gradient::Mesh(Arc::new(mesh))),
+            ..stroke.clone()
+        };
+
+        Some((
+            stroke,
+            Point::new(Abs::pt(min.x), Abs::pt(min.y)),
+            Size::new(Abs::pt(size.x), Abs::pt(size.y)),
+        ))
+    }
 }
 
 /// Produce a shape that approximates an axis-aligned ellipse.
@@ -617,7 +909,12 @@ pub(crate) fn ellipse(
     path.cubic_to(point(rx, my), point(mx, ry), point(z, ry));
     path.cubic_to(point(-mx, ry), point(-rx, my), point(-rx, z));
 
-    Shape { geometry: Geometry::Path(path), stroke, fill }
+    Shape {
+        geometry: Geometry::Path(path),
+        stroke,
+        fill,
+        blur: Abs::zero(),
+    }
 }
 
 /// Creates a new rectangle as a path.
@@ -680,7 +977,12 @@ fn simple_rect(
     fill: Option<Paint>,
     stroke: Option<FixedStroke>,
 ) -> Vec<Shape> {
-    vec![Shape { geometry: Geometry::Rect(size), fill, stroke }]
+    vec![Shape {
+        geometry: Geometry::Rect(size),
+        fill,
+        stroke,
+        blur: Abs::zero(),
+    }]
 }
 
 fn corners_control_points(
@@ -756,6 +1058,7 @@ fn segmented_rect(
             geometry: Geometry::Path(path),
             fill: Some(fill),
             stroke: None,
+            blur: Abs::zero(),
         });
         stroke_insert += 1;
     }
@@ -892,6 +1195,7 @@ fn stroke_segment(
         geometry: Geometry::Path(path),
         stroke: Some(stroke),
         fill: None,
+        blur: Abs::zero(),
     }
 }
 
@@ -990,6 +1294,7 @@ fn fill_segment(
         geometry: Geometry::Path(path),
         stroke: None,
         fill: Some(stroke.paint.clone()),
+        blur: Abs::zero(),
     }
 }
 
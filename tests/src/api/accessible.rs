@@ -0,0 +1,65 @@
+use crate::compile;
+
+#[test]
+fn test_speech() {
+    let document = compile(
+        "= Intro\n\
+         The area is $pi r^2$.\n\n\
+         - Item\n\n\
+         $ a + b $",
+    );
+    assert_eq!(
+        typst_accessible::speech(&document),
+        "Heading level 1: Intro\n\n\
+         The area is pi r Superscript 2 Baseline.\n\n\
+         • Item\n\n\
+         a plus b\n",
+    );
+}
+
+#[test]
+fn test_braille() {
+    let document = compile(
+        "#set document(title: \"Doc\")\n\
+         #set text(lang: \"de\")\n\
+         #show heading: it => emph(it.body)\n\
+         == Teil\n\
+         Hallo $x$ Welt.#footnote[Notiz]",
+    );
+    assert_eq!(
+        typst_accessible::braille(&document),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\
+         <document xml:lang=\"de\" title=\"Doc\">\
+         <heading level=\"2\">Teil</heading>\
+         <paragraph>Hallo <math speech=\"x\">x</math> Welt.</paragraph>\
+         <paragraph>Footnote: Notiz</paragraph>\
+         </document>",
+    );
+}
+
+#[test]
+fn test_speech_limits() {
+    let document = compile(
+        "Inline $sum_(i=1)^n i$ and\n\
+         $ sum_(i=1)^n i + x_1^2 $",
+    );
+    assert_eq!(
+        typst_accessible::speech(&document),
+        "Inline sigma-summation Subscript i equals 1 Superscript n Baseline i and\n\n\
+         sigma-summation Underscript i equals 1 Overscript n Endscripts i plus \
+         x Subscript 1 Superscript 2 Baseline\n",
+    );
+}
+
+#[test]
+fn test_linear_attachments() {
+    let document = compile(
+        "$x_1^2, f'_n, attach(x, b: 1, br: 2), limits(A)_1^2$\n\
+         $ sum_(i=1)^n, attach(sum, tr: n) $",
+    );
+    let braille = typst_accessible::braille(&document);
+    assert!(
+        braille.contains(">x_1^2, f'_n, attach(x, b: 1, br: 2), limits(A)_1^2</math>")
+    );
+    assert!(braille.contains(">∑_(i=1)^n, attach(∑, tr: n)</equation>"));
+}
@@ -0,0 +1,45 @@
+use typst::model::Document;
+use typst::visualize::Image;
+use typst_svg::svg_merged;
+
+use crate::compile;
+use crate::frames::{group_alts, images};
+
+/// A small SVG image.
+const SVG: &str = r#"#let img(..args) = image.decode(
+  "<svg xmlns='http://www.w3.org/2000/svg' width='10' height='10'/>",
+  width: 10pt,
+  ..args,
+)
+"#;
+
+/// Compile a document with the image helper.
+fn document(text: &str) -> Document {
+    compile(&format!("{SVG}{text}"))
+}
+
+#[test]
+fn test_alt_figure_group() {
+    let document = document("#figure(img(), alt: \"A square\", caption: [Square])");
+    assert_eq!(group_alts(&document.pages[0].frame), ["A square"]);
+    assert!(svg_merged(&document, Default::default())
+        .contains(r#"role="img" aria-label="A square""#));
+}
+
+#[test]
+fn test_alt_figure_describes_images() {
+    let document = document(
+        "#figure(alt: \"Two squares\", stack(img(), img(alt: \"Second\")))\n\
+         #figure(img())",
+    );
+    let found = images(&document.pages[0].frame);
+    let alts: Vec<_> = found.iter().map(Image::alt).collect();
+    assert_eq!(alts, [Some("Two squares"), Some("Second"), None]);
+}
+
+#[test]
+fn test_alt_without_figure() {
+    let document = document("#img(alt: \"Alone\")");
+    assert!(group_alts(&document.pages[0].frame).is_empty());
+    assert_eq!(images(&document.pages[0].frame)[0].alt(), Some("Alone"));
+}
@@ -0,0 +1,37 @@
+use typst::foundations::{dict, Datetime, IntoValue, Smart, Value};
+
+use crate::compile;
+
+#[test]
+fn test_document_front_matter() {
+    let document = compile(
+        "#set document(\n\
+           title: [Hello],\n\
+           author: (\"Jane\", \"John\"),\n\
+           keywords: \"greeting\",\n\
+           date: datetime(year: 2024, month: 5, day: 17),\n\
+           extra: (title: \"Slug\", draft: false),\n\
+         )\n\
+         Hello",
+    );
+
+    let date = Datetime::from_ymd(2024, 5, 17).unwrap();
+    assert_eq!(
+        document.front_matter(),
+        dict! {
+            "title" => "Hello",
+            "author" => vec!["Jane", "John"],
+            "keywords" => vec!["greeting"],
+            "date" => Smart::Custom(Some(date)),
+            "extra" => dict! { "title" => "Slug", "draft" => false },
+        }
+    );
+}
+
+#[test]
+fn test_document_front_matter_defaults() {
+    let front_matter = compile("Hello").front_matter();
+    assert_eq!(front_matter.get("title").unwrap(), &Value::None);
+    assert_eq!(front_matter.get("date").unwrap(), &Smart::<Datetime>::Auto.into_value());
+    assert_eq!(front_matter.get("extra").unwrap(), &dict! {}.into_value());
+}
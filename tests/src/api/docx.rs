@@ -0,0 +1,156 @@
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use crate::compile;
+
+/// The directory where the expected DOCX parts are stored.
+const DOCX_REF_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/ref/docx");
+
+/// Export a document into DOCX and compare a part of the package with the
+/// reference file `{name}.xml`.
+///
+/// Missing references are created from the output. Each paragraph and table
+/// is put on its own line to keep the references readable.
+#[track_caller]
+fn check(name: &str, part: &str, text: &str) {
+    let xml = export(part, text)
+        .replace("</w:p>", "</w:p>\n")
+        .replace("</w:tbl>", "</w:tbl>\n")
+        .replace("</w:footnote>", "</w:footnote>\n");
+
+    let path = Path::new(DOCX_REF_PATH).join(format!("{name}.xml"));
+    match std::fs::read_to_string(&path) {
+        Ok(expected) => assert_eq!(xml, expected, "{name} does not match its reference"),
+        Err(_) => {
+            std::fs::create_dir_all(DOCX_REF_PATH).unwrap();
+            std::fs::write(&path, xml).unwrap();
+            panic!("created missing reference {}", path.display());
+        }
+    }
+}
+
+/// Export a document into DOCX and extract a part of the package.
+#[track_caller]
+fn export(part: &str, text: &str) -> String {
+    let document = compile(text);
+    let data = typst_docx::docx(&document);
+    let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+    let mut xml = String::new();
+    archive.by_name(part).unwrap().read_to_string(&mut xml).unwrap();
+    xml
+}
+
+#[test]
+fn test_docx_paragraphs() {
+    check(
+        "paragraphs",
+        "word/document.xml",
+        "= Introduction\n\
+         Some *strong* and _emphasized_ text\n\
+         that spans two lines.\n\n\
+         #text(red)[A second] paragraph.",
+    );
+}
+
+#[test]
+fn test_docx_heading_show_rule() {
+    // The heading is still exported as a heading even though a show rule
+    // replaces how it looks.
+    check(
+        "heading-show-rule",
+        "word/document.xml",
+        "#show heading: it => text(blue, it.body)\n= Styled\nBody",
+    );
+}
+
+#[test]
+fn test_docx_lists() {
+    check(
+        "lists",
+        "word/document.xml",
+        "- Apple\n- Banana\n\n+ First\n+ Second\n\nBetween\n\n+ Again\n\n/ Term: Description",
+    );
+}
+
+#[test]
+fn test_docx_tables() {
+    check(
+        "tables",
+        "word/document.xml",
+        "#table(columns: 2, table.cell(colspan: 2)[Head], [A], [B], [C])\n\
+         #figure(table(columns: 1, [Inside]), caption: [Caption])",
+    );
+}
+
+#[test]
+fn test_docx_footnotes() {
+    check(
+        "footnotes",
+        "word/footnotes.xml",
+        "Text#footnote[The *first* note.] and#footnote[Another.]",
+    );
+}
+
+#[test]
+fn test_docx_export_only() {
+    check(
+        "export-only",
+        "word/document.xml",
+        "#export-only((\"docx\",))[For Word] #export-only((\"pdf\",))[For PDF]\n\n\
+         #screen-only[On screen]",
+    );
+}
+
+#[test]
+fn test_docx_realized() {
+    // Show rules, counters, and references are resolved against the final
+    // layout.
+    check(
+        "realized",
+        "word/document.xml",
+        "#set heading(numbering: \"1.\")\n\
+         #show \"Typst\": [*Typst*]\n\
+         = Intro <intro>\n\
+         Typst in @intro on page #context counter(page).display().\n\
+         #figure(rect(), caption: [A box])",
+    );
+}
+
+#[test]
+fn test_docx_table_formatting() {
+    check(
+        "table-formatting",
+        "word/document.xml",
+        "#table(columns: 2, [*Bold* cell], [- One\n- Two])",
+    );
+}
+
+#[test]
+fn test_docx_numbering() {
+    check("numbering", "word/numbering.xml", "- A\n  - B\n+ C");
+}
+
+#[test]
+fn test_docx_nested_lists() {
+    let xml = export("word/document.xml", "- A\n\n  More\n  - B\n- C");
+    let level = |ilvl: usize| {
+        format!("<w:numPr><w:ilvl w:val=\"{ilvl}\"/><w:numId w:val=\"1\"/></w:numPr>")
+    };
+    assert_eq!(xml.matches(&level(0)).count(), 2);
+    assert_eq!(xml.matches(&level(1)).count(), 1);
+    assert!(xml.contains("<w:ind w:left=\"720\" w:firstLine=\"0\"/>"));
+}
+
+#[test]
+fn test_docx_margins() {
+    let xml = export(
+        "word/document.xml",
+        "#set page(margin: (left: 1cm, right: 2cm, top: 20pt, bottom: 30pt))\nText",
+    );
+    assert!(xml.contains(
+        "<w:pgMar w:top=\"400\" w:right=\"1134\" w:bottom=\"600\" w:left=\"567\" \
+         w:header=\"200\" w:footer=\"300\" w:gutter=\"0\"/>"
+    ));
+}
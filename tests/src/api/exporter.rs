@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use typst::diag::SourceResult;
+use typst::foundations::{Label, Selector};
+use typst::layout::Abs;
+use typst::model::{Document, DocumentExporter};
+use typst::visualize::Color;
+use typst_accessible::SpeechExporter;
+use typst_docx::DocxExporter;
+use typst_pdf::PdfOptions;
+use typst_render::RasterExporter;
+use typst_svg::SvgExporter;
+
+use crate::compile;
+
+/// An exporter outside of Typst, which lists what it can find out about a
+/// document.
+struct Summary;
+
+impl DocumentExporter for Summary {
+    type Output = String;
+
+    fn export(&self, document: &Document) -> SourceResult<Self::Output> {
+        let mut out = String::new();
+        writeln!(out, "title: {}", document.title.as_deref().unwrap_or("-")).unwrap();
+        writeln!(out, "pages: {}", document.pages.len()).unwrap();
+        for font in document.fonts() {
+            writeln!(out, "font: {}", font.info().family).unwrap();
+        }
+
+        let selector = Selector::Label(Label::new("intro"));
+        for elem in document.introspector.query(&selector) {
+            let location = elem.location().unwrap();
+            let page = document.introspector.page(location);
+            writeln!(out, "<intro>: page {page}").unwrap();
+        }
+
+        Ok(out)
+    }
+}
+
+/// Export a document with any exporter.
+fn export<E: DocumentExporter>(exporter: &E, document: &Document) -> E::Output {
+    exporter.export(document).unwrap()
+}
+
+#[test]
+fn test_exporter_third_party() {
+    let document = compile(
+        "#set document(title: [Report])\n\
+         Hello #pagebreak() #text(font: \"DejaVu Sans Mono\")[World] <intro>",
+    );
+    assert_eq!(
+        export(&Summary, &document),
+        "title: Report\n\
+         pages: 2\n\
+         font: Linux Libertine\n\
+         font: DejaVu Sans Mono\n\
+         <intro>: page 2\n"
+    );
+}
+
+#[test]
+fn test_exporter_built_in() {
+    let document = compile("= Hello\nWorld");
+    assert!(export(&PdfOptions::default(), &document).starts_with(b"%PDF"));
+    assert!(export(&DocxExporter, &document).starts_with(b"PK"));
+    assert!(export(&SvgExporter::default(), &document).starts_with("<svg"));
+    assert_eq!(export(&SpeechExporter, &document), typst_accessible::speech(&document));
+
+    let raster = RasterExporter {
+        pixel_per_pt: 1.0,
+        fill: Color::WHITE,
+        gap: Abs::zero(),
+        gap_fill: Color::WHITE,
+        preview: false,
+    };
+    let pixmap = export(&raster, &document);
+    assert_eq!(pixmap.width() as f64, document.pages[0].frame.width().to_pt().ceil());
+}
+
+#[test]
+fn test_document_fonts_in_order_of_first_use() {
+    let document = compile(
+        "#set text(font: \"DejaVu Sans Mono\")\n\
+         A #box(text(font: \"Linux Libertine\")[B]) C $x$ D",
+    );
+    let families: Vec<_> = document
+        .fonts()
+        .iter()
+        .map(|font| font.info().family.clone())
+        .collect();
+    assert_eq!(
+        families,
+        ["DejaVu Sans Mono", "Linux Libertine", "New Computer Modern Math"]
+    );
+}
+
+#[test]
+fn test_document_fonts_empty() {
+    assert!(compile("").fonts().is_empty());
+}
@@ -0,0 +1,41 @@
+use typst::diag::SourceResult;
+use typst::eval::Tracer;
+use typst::layout::{Abs, Fragment, Size};
+use typst::syntax::Source;
+
+use crate::frames::text;
+use crate::world::TestWorld;
+
+/// Compile a snippet into regions of the given size.
+fn fragment(text: &str, size: Size) -> SourceResult<Fragment> {
+    let source = Source::detached(text);
+    let world = TestWorld::new(source.clone());
+    typst::compile_fragment(&source, &world, size, &mut Tracer::new())
+}
+
+#[test]
+fn test_fragment_one_frame_per_region() {
+    let size = Size::new(Abs::pt(100.0), Abs::pt(30.0));
+    let fragment = fragment("#lorem(40)", size).unwrap();
+    assert!(fragment.len() > 1);
+    assert!(fragment.iter().all(|frame| frame.width() <= size.x));
+    assert!(fragment.iter().all(|frame| frame.height() <= size.y));
+}
+
+#[test]
+fn test_fragment_converges_like_a_document() {
+    // The final value of the counter is only known after a second pass.
+    let source = r#"#context counter("c").final().first() #counter("c").update(7)"#;
+    let size = Size::new(Abs::pt(100.0), Abs::inf());
+    let fragment = fragment(source, size).unwrap();
+    assert_eq!(fragment.len(), 1);
+    assert_eq!(text(&fragment.into_frame()), "7");
+}
+
+#[test]
+fn test_fragment_promotes_delayed_errors() {
+    let size = Size::new(Abs::pt(100.0), Abs::inf());
+    let errors = fragment("See @missing.", size).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "label `<missing>` does not exist in the document");
+}
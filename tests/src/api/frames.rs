@@ -0,0 +1,59 @@
+//! Helpers that collect items from laid-out frames.
+
+use ecow::EcoString;
+use typst::layout::{Frame, FrameItem};
+use typst::visualize::{Image, Paint};
+
+/// The alternative texts of the groups in a frame, in order.
+pub fn group_alts(frame: &Frame) -> Vec<String> {
+    let mut output = vec![];
+    for (_, item) in frame.items() {
+        if let FrameItem::Group(group) = item {
+            if let Some(alt) = &group.alt {
+                output.push(alt.to_string());
+            }
+            output.extend(group_alts(&group.frame));
+        }
+    }
+    output
+}
+
+/// The images in a frame, in order.
+pub fn images(frame: &Frame) -> Vec<Image> {
+    let mut output = vec![];
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => output.extend(images(&group.frame)),
+            FrameItem::Image(image, ..) => output.push(image.clone()),
+            _ => {}
+        }
+    }
+    output
+}
+
+/// The fills of all shapes and text in a frame, in order.
+pub fn paints(frame: &Frame) -> Vec<Paint> {
+    let mut output = vec![];
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => output.extend(paints(&group.frame)),
+            FrameItem::Text(text) => output.extend(text.fill.clone()),
+            FrameItem::Shape(shape, _) => output.extend(shape.fill.clone()),
+            _ => {}
+        }
+    }
+    output
+}
+
+/// The text in a frame, in order.
+pub fn text(frame: &Frame) -> EcoString {
+    let mut output = EcoString::new();
+    for (_, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => output.push_str(&text(&group.frame)),
+            FrameItem::Text(item) => output.push_str(&item.text),
+            _ => {}
+        }
+    }
+    output
+}
@@ -0,0 +1,59 @@
+use typst::layout::Limits;
+use typst::syntax::Source;
+use typst::Library;
+
+use crate::{compile, try_compile_with};
+
+/// Compile a document with limits, returning the error messages and the text
+/// they point at.
+fn errors(limits: Limits, text: &str) -> Vec<(String, String)> {
+    let library = Library::builder().with_limits(limits).build();
+    let (_, errors) = try_compile_with(library, text);
+    let source = Source::detached(text);
+    errors
+        .into_iter()
+        .map(|error| {
+            let range = source.range(error.span).unwrap_or_default();
+            (error.message.into(), text[range].into())
+        })
+        .collect()
+}
+
+#[test]
+fn test_limits_within() {
+    let limits = Limits { path_segments: Some(4), ..Limits::default() };
+    assert!(errors(limits, "#rect()").is_empty());
+}
+
+#[test]
+fn test_limits_exceeded_at_page() {
+    let limits = Limits { path_segments: Some(6), ..Limits::default() };
+    let text = "Hello #rect() #rect() #pagebreak() World #rect()";
+    assert_eq!(
+        errors(limits, text),
+        [(
+            "page 1 contains 8 path segments, which exceeds the limit of 6".into(),
+            "Hello".into(),
+        )]
+    );
+}
+
+#[test]
+fn test_limits_close_path_is_not_a_segment() {
+    // Only the two explicit sides of a triangle count.
+    let text = "#polygon((0pt, 0pt), (10pt, 0pt), (0pt, 10pt))";
+    let limits = Limits { path_segments: Some(2), ..Limits::default() };
+    assert!(errors(limits, text).is_empty());
+    let limits = Limits { path_segments: Some(1), ..Limits::default() };
+    assert_eq!(errors(limits, text).len(), 1);
+}
+
+#[test]
+fn test_limits_check_frames() {
+    let document = compile("#for i in range(5) { rect() }");
+    let frames = document.pages.iter().map(|page| &page.frame);
+    let limits = Limits { frame_items: Some(2), ..Limits::default() };
+    let errors = limits.check(frames).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].message.starts_with("page 1 contains"));
+}
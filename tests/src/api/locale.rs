@@ -0,0 +1,61 @@
+use typst::foundations::sys::Locale;
+use typst::layout::Paper;
+use typst::model::Document;
+use typst::text::Lang;
+use typst::Library;
+
+use crate::frames::text;
+use crate::try_compile_with;
+
+/// Compile a document with a locale.
+fn document(locale: Locale, text: &str) -> Document {
+    let library = Library::builder().with_locale(locale).build();
+    let (document, errors) = try_compile_with(library, text);
+    if !errors.is_empty() {
+        panic!("failed to compile: {errors:?}");
+    }
+    document.unwrap()
+}
+
+#[test]
+fn test_locale_decimal_separator_in_markup() {
+    let locale = Locale { decimal_separator: Some(','), ..Locale::default() };
+    let document = document(locale, "#3.5\n\n$#2.25$\n\n#(1.5 + 1)");
+    let text = text(&document.pages[0].frame);
+    assert!(text.contains("3,5"));
+    assert!(text.contains("2,25"));
+    assert!(text.contains("2,5"));
+    assert!(!text.contains('.'));
+}
+
+#[test]
+fn test_locale_decimal_separator_not_in_strings() {
+    let locale = Locale { decimal_separator: Some(','), ..Locale::default() };
+    let document = document(locale, "#str(3.5) #repr(0.5) #sys.locale.decimal-separator");
+    let text = text(&document.pages[0].frame);
+    assert!(text.contains("3.5"));
+    assert!(text.contains("0.5"));
+    assert!(text.contains(','));
+}
+
+#[test]
+fn test_locale_decimal_separator_default() {
+    let document = document(Locale::default(), "#3.5");
+    assert_eq!(text(&document.pages[0].frame), "3.5");
+}
+
+#[test]
+fn test_locale_lang() {
+    let locale = Locale { lang: Some(Lang::GERMAN), ..Locale::default() };
+    let document = document(locale, "#context text.lang #sys.locale.lang");
+    assert_eq!(text(&document.pages[0].frame).matches("de").count(), 2);
+}
+
+#[test]
+fn test_locale_paper() {
+    let locale = Locale { paper: Some(Paper::US_LETTER), ..Locale::default() };
+    let document = document(locale, "Hello");
+    let page = &document.pages[0].frame;
+    assert_eq!(page.width(), Paper::US_LETTER.width());
+    assert_eq!(page.height(), Paper::US_LETTER.height());
+}
@@ -0,0 +1,117 @@
+use std::sync::{Arc, Mutex};
+
+use sha2::{Digest, Sha256};
+use typst::engine::{LogEvent, Manifest};
+use typst::eval::Tracer;
+use typst::syntax::{FileId, Source, VirtualPath};
+
+use crate::world::TestWorld;
+
+/// A document that reads a file.
+const TEXT: &str = "#let data = read(\"/suite/playground.typ\")\nHello";
+
+/// Compile a document with a tracer that collects the logged events.
+fn compile_logged(text: &str) -> (Tracer, Vec<LogEvent>) {
+    let world = TestWorld::new(Source::detached(text));
+    let received = Arc::new(Mutex::new(vec![]));
+    let sink = received.clone();
+    let mut tracer =
+        Tracer::new().with_log(move |event| sink.lock().unwrap().push(event.clone()));
+    assert!(tracer.logging());
+    typst::compile(&world, &mut tracer).unwrap();
+    let events = received.lock().unwrap().clone();
+    (tracer, events)
+}
+
+#[test]
+fn test_log_events() {
+    let (tracer, events) = compile_logged(TEXT);
+    assert_eq!(tracer.events(), events.as_slice());
+
+    let read = FileId::new(None, VirtualPath::new("/suite/playground.typ"));
+    assert!(events.contains(&LogEvent::FileRead(read)));
+    assert!(events.contains(&LogEvent::LayoutPass(1)));
+    assert!(events.iter().any(|event| matches!(event, LogEvent::FontLoaded(_))));
+}
+
+#[test]
+fn test_log_events_are_distinct() {
+    let (_, events) = compile_logged(
+        "#read(\"/suite/playground.typ\")\n#read(\"/suite/playground.typ\")",
+    );
+    let read = FileId::new(None, VirtualPath::new("/suite/playground.typ"));
+    let count = events
+        .iter()
+        .filter(|&event| *event == LogEvent::FileRead(read))
+        .count();
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_log_manifest() {
+    let world = TestWorld::new(Source::detached(TEXT));
+    let mut tracer = Tracer::new().with_digests();
+    typst::compile(&world, &mut tracer).unwrap();
+    let manifest = Manifest::new(&tracer).unwrap();
+    assert!(manifest.packages.is_empty());
+
+    let fonts: Vec<_> = manifest
+        .fonts
+        .iter()
+        .map(|(font, _)| font.info().family.as_str())
+        .collect();
+    assert_eq!(fonts, ["Linux Libertine"]);
+
+    let read = FileId::new(None, VirtualPath::new("/suite/playground.typ"));
+    let data = std::fs::read("suite/playground.typ").unwrap();
+    let digest: [u8; 32] = Sha256::digest(data).into();
+    assert!(manifest.files.contains(&(read, digest)));
+
+    let json = manifest.to_json();
+    assert!(json.contains("\"path\": \"suite/playground.typ\""));
+    assert!(json.contains("\"family\": \"Linux Libertine\""));
+    assert!(json.contains("\"sha256\""));
+}
+
+#[test]
+fn test_log_manifest_digests_sources_as_bytes() {
+    // The file is both imported as a source and read as bytes.
+    let world = TestWorld::new(Source::detached(
+        "#import \"/suite/playground.typ\"\n#read(\"/suite/playground.typ\")",
+    ));
+    let mut tracer = Tracer::new().with_digests();
+    typst::compile(&world, &mut tracer).unwrap();
+    let manifest = Manifest::new(&tracer).unwrap();
+
+    let read = FileId::new(None, VirtualPath::new("/suite/playground.typ"));
+    let data = std::fs::read("suite/playground.typ").unwrap();
+    let digest: [u8; 32] = Sha256::digest(data).into();
+    let digests: Vec<_> = manifest.files.iter().filter(|(id, _)| *id == read).collect();
+    assert_eq!(digests, [&(read, digest)]);
+}
+
+#[test]
+fn test_log_manifest_requires_digests() {
+    let world = TestWorld::new(Source::detached(TEXT));
+    let mut tracer = Tracer::new();
+    typst::compile(&world, &mut tracer).unwrap();
+    assert!(tracer.digests().is_none());
+    assert_eq!(Manifest::new(&tracer), None);
+}
+
+#[test]
+fn test_log_fonts_in_svg() {
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="20"><text x="0" y="15" font-family="DejaVu Sans Mono">Hi</text></svg>"#;
+    let (_, events) = compile_logged(&format!(
+        "#image.decode(\"{}\", format: \"svg\")",
+        svg.replace('"', "\\\"")
+    ));
+    let fonts: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            LogEvent::FontLoaded(font) => Some(font.info().family.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fonts, ["DejaVu Sans Mono"]);
+}
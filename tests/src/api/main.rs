@@ -0,0 +1,109 @@
+//! Tests of Typst's Rust API and of the exporters whose output can't be
+//! compared against reference images.
+
+#[path = "../world.rs"]
+#[allow(dead_code)]
+mod world;
+
+mod accessible;
+mod alt;
+mod document;
+mod docx;
+mod exporter;
+mod fragment;
+mod frames;
+mod limits;
+mod locale;
+mod log;
+mod pdf;
+mod recolor;
+mod render;
+mod span;
+mod styles;
+mod target;
+mod text;
+mod timing;
+mod translation;
+mod untrusted;
+
+use typst::diag::{FileResult, SourceDiagnostic};
+use typst::eval::Tracer;
+use typst::foundations::{Bytes, Datetime};
+use typst::model::Document;
+use typst::syntax::{FileId, Source};
+use typst::text::{Font, FontBook};
+use typst::utils::LazyHash;
+use typst::{Library, World};
+
+use crate::world::TestWorld;
+
+/// Compile a document, panicking if it fails.
+fn compile(text: &str) -> Document {
+    let (document, errors) = try_compile(text);
+    if !errors.is_empty() {
+        panic!("failed to compile: {errors:?}");
+    }
+    document.unwrap()
+}
+
+/// Compile a document, returning its errors.
+fn try_compile(text: &str) -> (Option<Document>, Vec<SourceDiagnostic>) {
+    compile_in(&TestWorld::new(Source::detached(text)))
+}
+
+/// Compile a document with a custom standard library, returning its errors.
+fn try_compile_with(
+    library: Library,
+    text: &str,
+) -> (Option<Document>, Vec<SourceDiagnostic>) {
+    let world = LibraryWorld {
+        world: TestWorld::new(Source::detached(text)),
+        library: LazyHash::new(library),
+    };
+    compile_in(&world)
+}
+
+/// Compile the main file of a world.
+fn compile_in(world: &dyn World) -> (Option<Document>, Vec<SourceDiagnostic>) {
+    let mut tracer = Tracer::new();
+    match typst::compile(world, &mut tracer) {
+        Ok(document) => (Some(document), vec![]),
+        Err(errors) => (None, errors.into_iter().collect()),
+    }
+}
+
+/// A test world with a different standard library.
+struct LibraryWorld {
+    world: TestWorld,
+    library: LazyHash<Library>,
+}
+
+impl World for LibraryWorld {
+    fn library(&self) -> &LazyHash<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &LazyHash<FontBook> {
+        self.world.book()
+    }
+
+    fn main(&self) -> Source {
+        self.world.main()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        self.world.source(id)
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        self.world.file(id)
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.world.font(index)
+    }
+
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        self.world.today(offset)
+    }
+}
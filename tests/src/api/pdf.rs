@@ -0,0 +1,206 @@
+use typst::foundations::{Bytes, Datetime, Smart};
+use typst::layout::{Frame, FrameItem, GroupItem, Point, Size};
+use typst::model::Document;
+use typst::syntax::Source;
+use typst::text::{Font, TextItem};
+use typst_pdf::{FontPolicy, PdfAttachment, PdfOptions};
+
+use crate::compile;
+
+/// Export a document into PDF with the given font policy.
+fn pdf(document: &Document, font_policy: FontPolicy) -> Vec<u8> {
+    let options = PdfOptions { font_policy, ..PdfOptions::default() };
+    typst_pdf::pdf(document, &options).unwrap()
+}
+
+/// Whether the PDF contains a font subset.
+fn has_subset(pdf: &[u8]) -> bool {
+    !subset_tags(pdf).is_empty()
+}
+
+/// The six-letter tags that prefix the names of the font subsets in a PDF.
+fn subset_tags(pdf: &[u8]) -> Vec<String> {
+    let needle = b"/BaseFont /";
+    pdf.windows(needle.len() + 7)
+        .filter(|window| {
+            window.starts_with(needle)
+                && window[needle.len()..needle.len() + 6]
+                    .iter()
+                    .all(u8::is_ascii_uppercase)
+                && window[needle.len() + 6] == b'+'
+        })
+        .map(|window| {
+            String::from_utf8_lossy(&window[needle.len()..needle.len() + 6]).into()
+        })
+        .collect()
+}
+
+/// Compile and export a document the way `--reproducible` does, with an
+/// optional identifier like `--pdf-ident`.
+fn reproducible(text: &str, ident: Option<&str>) -> Vec<u8> {
+    let ident = ident.map_or(Smart::Auto, Smart::Custom);
+    let options = PdfOptions { ident, timestamp: None, ..PdfOptions::default() };
+    typst_pdf::pdf(&compile(text), &options).unwrap()
+}
+
+/// Whether a byte string occurs in a PDF.
+fn contains(pdf: &[u8], needle: &[u8]) -> bool {
+    pdf.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Mark a font's license as forbidding embedding by setting the `fsType`
+/// field of its `OS/2` table to "Restricted License embedding".
+fn restrict(font: &Font) -> Font {
+    let mut data = font.data().to_vec();
+    let count = u16::from_be_bytes([data[4], data[5]]) as usize;
+    for i in 0..count {
+        let record = 12 + 16 * i;
+        if &data[record..record + 4] == b"OS/2" {
+            let offset =
+                u32::from_be_bytes(data[record + 8..record + 12].try_into().unwrap());
+            let fs_type = offset as usize + 8;
+            data[fs_type..fs_type + 2].copy_from_slice(&2u16.to_be_bytes());
+        }
+    }
+    Font::new(Bytes::from(data), font.index()).unwrap()
+}
+
+#[test]
+fn test_pdf_subset_fonts_by_default() {
+    let document = compile("Hello");
+    assert!(has_subset(&pdf(&document, FontPolicy::default())));
+}
+
+#[test]
+fn test_pdf_full_fonts() {
+    let document = compile("Hello");
+    let subset = pdf(&document, FontPolicy::default());
+    let full = pdf(&document, FontPolicy { full: true, ..FontPolicy::default() });
+    assert!(!has_subset(&full));
+    assert!(full.len() > 2 * subset.len());
+}
+
+#[test]
+fn test_pdf_cid_fonts() {
+    // Libertinus Serif has CFF outlines and DejaVu Sans Mono TrueType ones.
+    let document = compile("Hello #text(font: \"DejaVu Sans Mono\")[World]");
+    for policy in
+        [FontPolicy::default(), FontPolicy { full: true, ..FontPolicy::default() }]
+    {
+        let pdf = pdf(&document, policy);
+        assert!(contains(&pdf, b"/Subtype /Type0"));
+        assert!(contains(&pdf, b"/Subtype /CIDFontType0"));
+        assert!(contains(&pdf, b"/Subtype /CIDFontType2"));
+        assert!(!contains(&pdf, b"/Subtype /Type1"));
+        assert!(!contains(&pdf, b"/Subtype /TrueType"));
+    }
+}
+
+#[test]
+fn test_pdf_font_license_diagnostic() {
+    let text = "Hello #text(font: \"DejaVu Sans Mono\")[World]";
+    let mut document = compile(text);
+    for page in &mut document.pages {
+        page.frame.map_text_runs(
+            &mut |item| item.font.info().family.starts_with("DejaVu").then_some(()),
+            &mut |run| {
+                let mut group = Frame::soft(Size::zero());
+                for (pos, item) in run {
+                    let font = restrict(&item.font);
+                    group.push(pos, FrameItem::Text(TextItem { font, ..item }));
+                }
+                (Point::zero(), FrameItem::Group(GroupItem::new(group)))
+            },
+        );
+    }
+
+    // Without checks, the font is embedded anyway.
+    pdf(&document, FontPolicy::default());
+
+    let policy = FontPolicy { respect_licenses: true, ..FontPolicy::default() };
+    let options = PdfOptions { font_policy: policy, ..PdfOptions::default() };
+    let errors = typst_pdf::pdf(&document, &options).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "the license of font `DejaVu Sans Mono` does not allow embedding it"
+    );
+
+    // The error points to the first text set in the font.
+    let source = Source::detached(text);
+    let range = source.range(errors[0].span).unwrap();
+    assert_eq!(&text[range], "World");
+}
+
+#[test]
+fn test_pdf_reproducible() {
+    let text = "#set document(title: [Report])\n= Results\nHello *World*";
+    let first = reproducible(text, None);
+    assert_eq!(first, reproducible(text, None));
+    assert!(!contains(&first, b"/CreationDate"));
+
+    // With a timestamp, the date is embedded.
+    let timestamp = Datetime::from_ymd_hms(2024, 1, 1, 12, 0, 0);
+    let options = PdfOptions { timestamp, ..PdfOptions::default() };
+    let dated = typst_pdf::pdf(&compile(text), &options).unwrap();
+    assert!(contains(&dated, b"/CreationDate"));
+}
+
+#[test]
+fn test_pdf_reproducible_with_ident() {
+    let text = "Hello";
+    let first = reproducible(text, Some("report"));
+    assert_eq!(first, reproducible(text, Some("report")));
+    assert_ne!(first, reproducible(text, Some("letter")));
+    assert_ne!(first, reproducible(text, None));
+}
+
+#[test]
+fn test_pdf_subset_tags_are_deterministic() {
+    let tags = subset_tags(&reproducible("Hello", None));
+    assert!(!tags.is_empty());
+    assert_eq!(tags, subset_tags(&reproducible("Hello", None)));
+
+    // The tag depends on the glyphs in the subset.
+    assert_ne!(tags, subset_tags(&reproducible("World", None)));
+}
+
+#[test]
+fn test_pdf_attachments() {
+    let attachment = PdfAttachment {
+        name: "manifest.json".into(),
+        mime_type: Some("application/json".into()),
+        description: Some("Resources used by the document".into()),
+        data: Bytes::from_static(b"{\"files\": []}"),
+    };
+    let options = PdfOptions {
+        attachments: vec![attachment],
+        ..PdfOptions::default()
+    };
+    let pdf = typst_pdf::pdf(&compile("Hello"), &options).unwrap();
+    assert!(contains(&pdf, b"/EmbeddedFiles"));
+    assert!(contains(&pdf, b"(manifest.json)"));
+    assert!(contains(&pdf, b"/Subtype /application#2Fjson"));
+
+    // Without attachments, there is no name tree for them.
+    let plain = typst_pdf::pdf(&compile("Hello"), &PdfOptions::default()).unwrap();
+    assert!(!contains(&plain, b"/EmbeddedFiles"));
+}
+
+#[test]
+fn test_pdf_page_labels_sectioned() {
+    let text = "#set page(numbering: \"1-1\", section: counter(\"chapter\"))\n\
+                #counter(\"chapter\").step()\nA\n#pagebreak()\nB\n\
+                #pagebreak()\n#counter(\"chapter\").step()\nC";
+    let pdf = pdf(&compile(text), FontPolicy::default());
+    assert!(contains(&pdf, b"/PageLabels"));
+    assert!(contains(&pdf, b"/P (1-)"));
+    assert!(contains(&pdf, b"/P (2-)"));
+}
+
+#[test]
+fn test_pdf_page_labels_localized() {
+    let text = "#set text(lang: \"de\")\n#set page(numbering: \"{words}\")\nA";
+    let pdf = pdf(&compile(text), FontPolicy::default());
+    assert!(contains(&pdf, b"/P (eins)"));
+}
@@ -0,0 +1,122 @@
+use typst::foundations::Bytes;
+use typst::layout::{Abs, Frame, FrameItem, Point, Size};
+use typst::syntax::Span;
+use typst::visualize::{
+    Color, Image, ImageFormat, ImageKind, Paint, RasterFormat, Recolor, VectorFormat,
+};
+
+use crate::compile;
+use crate::frames::{images, paints};
+
+/// A frame with an image from the test assets.
+fn image_frame(name: &str, format: impl Into<ImageFormat>) -> Frame {
+    let data = std::fs::read(format!("assets/{name}")).unwrap();
+    let image = Image::new(Bytes::from(data), format.into(), None).unwrap();
+    let size = Size::new(Abs::pt(100.0), Abs::pt(100.0));
+    let mut frame = Frame::hard(size);
+    frame.push(Point::zero(), FrameItem::Image(image, size, Span::detached()));
+    frame
+}
+
+/// Whether a color is a shade of gray.
+fn is_gray(color: Color) -> bool {
+    let [r, g, b, _] = color.to_rgb().to_vec4_u8();
+    r == g && g == b
+}
+
+#[test]
+fn test_recolor_grayscale_color() {
+    let gray = Recolor::Grayscale.color(Color::RED);
+    assert!(is_gray(gray));
+    assert_eq!(Recolor::Grayscale.color(Color::BLACK), Color::BLACK.to_luma());
+}
+
+#[test]
+fn test_recolor_palette_picks_closest_color() {
+    let palette = Recolor::Palette(vec![Color::BLACK, Color::WHITE, Color::RED]);
+    assert_eq!(palette.color(Color::from_u8(250, 60, 50, 255)), Color::RED);
+    assert_eq!(palette.color(Color::from_u8(20, 20, 30, 255)), Color::BLACK);
+
+    // The alpha of the original color is kept.
+    let translucent = palette.color(Color::from_u8(240, 240, 240, 128));
+    assert_eq!(translucent, Color::WHITE.with_alpha(translucent.alpha().unwrap()));
+    assert!((translucent.alpha().unwrap() - 128.0 / 255.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_recolor_document() {
+    let document = compile(
+        "#text(fill: blue)[Hello]\n\
+         #rect(fill: red)\n\
+         #rect(fill: gradient.linear(red, green))",
+    );
+    let recolored = Recolor::Grayscale.document(&document);
+    let converted = paints(&recolored.pages[0].frame);
+    assert_eq!(converted.len(), 3);
+    for paint in converted {
+        match paint {
+            Paint::Solid(color) => assert!(is_gray(color)),
+            Paint::Gradient(gradient) => {
+                assert!(gradient.stops_ref().iter().all(|&(color, _)| is_gray(color)))
+            }
+            Paint::Pattern(_) => panic!("unexpected pattern"),
+        }
+    }
+
+    // The original document is unchanged.
+    assert!(paints(&document.pages[0].frame)
+        .iter()
+        .any(|paint| matches!(paint, Paint::Solid(color) if !is_gray(*color))));
+}
+
+#[test]
+fn test_recolor_raster_keeps_format() {
+    let recolored =
+        Recolor::Grayscale.frame(&image_frame("colors.jpg", RasterFormat::Jpg));
+    let [image] = images(&recolored).try_into().unwrap();
+    assert_eq!(image.format(), RasterFormat::Jpg.into());
+
+    // JPEG compression can shift the channels a little.
+    let ImageKind::Raster(raster) = image.kind() else { panic!("expected raster") };
+    assert!(raster.dynamic().to_rgb8().pixels().all(|pixel| {
+        let [r, g, b] = pixel.0.map(i16::from);
+        (r - g).abs() <= 8 && (g - b).abs() <= 8
+    }));
+}
+
+#[test]
+fn test_recolor_svg() {
+    let document = compile(
+        "#image.decode(\"<svg xmlns='http://www.w3.org/2000/svg' width='20' height='10'>\
+         <rect width='10' height='10' fill='red' stroke='blue'/>\
+         <text y='10' fill='green'>A</text></svg>\", format: \"svg\")",
+    );
+    let recolored = Recolor::Grayscale.document(&document);
+    let [image] = images(&recolored.pages[0].frame).try_into().unwrap();
+    assert_eq!(image.format(), VectorFormat::Svg.into());
+
+    let ImageKind::Svg(svg) = image.kind() else { panic!("expected SVG") };
+    let text = std::str::from_utf8(svg.data()).unwrap();
+    let colors: Vec<_> =
+        text.match_indices("=\"#").map(|(i, _)| &text[i + 3..i + 9]).collect();
+    assert_eq!(colors.len(), 3);
+    for hex in colors {
+        let rgb = u32::from_str_radix(hex, 16).unwrap();
+        assert!(is_gray(Color::from_u32(rgb << 8 | 0xFF)), "{hex} is not gray");
+    }
+}
+
+#[test]
+fn test_recolor_pdf() {
+    let frame =
+        &Recolor::Grayscale.frame(&image_frame("text-and-images.pdf", VectorFormat::Pdf));
+    assert!(images(frame)
+        .iter()
+        .all(|image| image.format() != VectorFormat::Pdf.into()));
+
+    let converted = paints(frame);
+    assert!(!converted.is_empty());
+    assert!(converted
+        .iter()
+        .all(|paint| matches!(paint, Paint::Solid(color) if is_gray(*color))));
+}
@@ -0,0 +1,88 @@
+use typst::layout::Abs;
+use typst::model::DocumentExporter;
+use typst::visualize::Color;
+use typst_render::RasterExporter;
+
+use crate::compile;
+
+/// A page filled with a horizontal gradient.
+const GRADIENT: &str = "#set page(width: 40pt, height: 20pt, margin: 0pt)\n\
+     #rect(width: 100%, height: 100%, fill: gradient.linear(red, blue))";
+
+/// Whether all pixels of a pixmap have the same color.
+fn is_uniform(pixmap: &tiny_skia::Pixmap) -> bool {
+    let first = pixmap.pixels()[0];
+    pixmap.pixels().iter().all(|pixel| *pixel == first)
+}
+
+#[test]
+fn test_render_thumbnail_fits_longer_side() {
+    let document = compile("#set page(width: 200pt, height: 100pt)\nHello");
+    let frame = &document.pages[0].frame;
+    let thumbnail = typst_render::render_thumbnail(frame, 64, Color::WHITE);
+    assert_eq!((thumbnail.width(), thumbnail.height()), (64, 32));
+
+    let document = compile("#set page(width: 100pt, height: 200pt)\nHello");
+    let frame = &document.pages[0].frame;
+    let thumbnail = typst_render::render_thumbnail(frame, 64, Color::WHITE);
+    assert_eq!((thumbnail.width(), thumbnail.height()), (32, 64));
+}
+
+#[test]
+fn test_render_preview_approximates_gradients() {
+    let document = compile(GRADIENT);
+    let frame = &document.pages[0].frame;
+    let full = typst_render::render(frame, 2.0, Color::WHITE);
+    let preview = typst_render::render_preview(frame, 2.0, Color::WHITE);
+    assert_eq!((full.width(), full.height()), (preview.width(), preview.height()));
+    assert!(!is_uniform(&full));
+    assert!(is_uniform(&preview));
+}
+
+#[test]
+fn test_render_exporter_preview() {
+    let document = compile(GRADIENT);
+    let exporter = |preview| RasterExporter {
+        pixel_per_pt: 1.0,
+        fill: Color::WHITE,
+        gap: Abs::zero(),
+        gap_fill: Color::WHITE,
+        preview,
+    };
+    assert!(!is_uniform(&exporter(false).export(&document).unwrap()));
+    assert!(is_uniform(&exporter(true).export(&document).unwrap()));
+}
+
+#[test]
+fn test_render_ink_coverage() {
+    let blank = compile("#set page(width: 20pt, height: 20pt)");
+    let coverage = typst_render::ink_coverage(&blank.pages[0].frame, 1.0);
+    assert_eq!(coverage.total(), 0.0);
+
+    // The left half of the page is black.
+    let black = compile(
+        "#set page(width: 20pt, height: 20pt, margin: 0pt)\n\
+         #rect(width: 50%, height: 100%, fill: black)",
+    );
+    let coverage = typst_render::ink_coverage(&black.pages[0].frame, 1.0);
+    assert!((coverage.black - 0.5).abs() < 0.05);
+    assert!(coverage.cyan < 0.01 && coverage.magenta < 0.01 && coverage.yellow < 0.01);
+    assert!((coverage.max_total - 1.0).abs() < 0.01);
+
+    // Cyan covers the whole page.
+    let cyan =
+        compile("#set page(width: 20pt, height: 20pt, fill: cmyk(100%, 0%, 0%, 0%))");
+    let coverage = typst_render::ink_coverage(&cyan.pages[0].frame, 1.0);
+    assert!(coverage.cyan > 0.99);
+    assert!(coverage.magenta < 0.01 && coverage.yellow < 0.01 && coverage.black < 0.01);
+
+    // A rich black keeps all of its inks instead of turning into plain black.
+    let rich =
+        compile("#set page(width: 20pt, height: 20pt, fill: cmyk(60%, 40%, 40%, 100%))");
+    let coverage = typst_render::ink_coverage(&rich.pages[0].frame, 1.0);
+    assert!((coverage.cyan - 0.6).abs() < 0.01);
+    assert!((coverage.magenta - 0.4).abs() < 0.01);
+    assert!((coverage.yellow - 0.4).abs() < 0.01);
+    assert!((coverage.black - 1.0).abs() < 0.01);
+    assert!((coverage.max_total - 2.4).abs() < 0.02);
+}
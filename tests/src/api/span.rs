@@ -0,0 +1,75 @@
+use std::num::NonZeroUsize;
+
+use typst::layout::{Abs, Point, Size};
+use typst::syntax::Source;
+use typst::utils::NonZeroExt;
+
+use crate::compile;
+
+/// Compile a document and find the source text behind an area of a page,
+/// given in points.
+fn text_at(text: &str, page: usize, pos: (f64, f64), size: (f64, f64)) -> Option<String> {
+    let document = compile(text);
+    let span = document.span_at(
+        NonZeroUsize::new(page)?,
+        Point::new(Abs::pt(pos.0), Abs::pt(pos.1)),
+        Size::new(Abs::pt(size.0), Abs::pt(size.1)),
+    )?;
+    let range = Source::detached(text).range(span)?;
+    Some(text[range].into())
+}
+
+/// The page setup of the tests: Text starts at (10pt, 10pt).
+const SETUP: &str = "#set page(width: 100pt, height: 100pt, margin: 10pt)\n";
+
+#[test]
+fn test_span_at_text() {
+    let text = format!("{SETUP}Hello *World*");
+    assert_eq!(text_at(&text, 1, (11.0, 12.0), (1.0, 1.0)).as_deref(), Some("Hello"));
+}
+
+#[test]
+fn test_span_at_nothing() {
+    let text = format!("{SETUP}Hello");
+    assert_eq!(text_at(&text, 1, (2.0, 90.0), (1.0, 1.0)), None);
+    assert_eq!(text_at(&text, 2, (11.0, 12.0), (1.0, 1.0)), None);
+}
+
+#[test]
+fn test_span_at_show_rule_shape() {
+    let text = format!("{SETUP}#show \"X\": rect(width: 20pt, height: 20pt)\nX");
+    assert_eq!(
+        text_at(&text, 1, (15.0, 15.0), (1.0, 1.0)).as_deref(),
+        Some("rect(width: 20pt, height: 20pt)")
+    );
+}
+
+#[test]
+fn test_span_at_highlight() {
+    // The highlight behind the text points at the highlighted text.
+    let text = format!("{SETUP}#highlight[Hi]");
+    let document = compile(&text);
+    let span = document
+        .span_at(NonZeroUsize::ONE, Point::splat(Abs::pt(10.5)), Size::zero())
+        .unwrap();
+    let range = Source::detached(&text).range(span).unwrap();
+    assert_eq!(&text[range], "Hi");
+}
+
+#[test]
+fn test_span_at_page_fill() {
+    let text = "#set page(width: 100pt, height: 100pt, fill: aqua)\nHello";
+    assert!(text_at(text, 1, (2.0, 90.0), (1.0, 1.0)).is_some());
+}
+
+#[test]
+fn test_span_at_transformed() {
+    // The area is mapped into the rotated group, so that the rectangle
+    // extends to the left instead of downwards.
+    let text = format!(
+        "{SETUP}#rotate(90deg, origin: top + left, \
+         box(rect(width: 10pt, height: 30pt, fill: red)))"
+    );
+    assert_eq!(text_at(&text, 1, (12.0, 30.0), (1.0, 1.0)), None);
+    assert!(text_at(&text, 1, (5.0, 15.0), (1.0, 1.0)).is_some());
+}
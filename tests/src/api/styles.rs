@@ -0,0 +1,103 @@
+use ecow::EcoString;
+use typst::diag::SourceDiagnostic;
+use typst::eval::Tracer;
+use typst::layout::{Frame, FrameItem};
+use typst::model::Document;
+use typst::syntax::Source;
+use typst::visualize::{Color, Paint};
+
+use crate::world::TestWorld;
+
+/// Evaluate a document once and typeset it with a style bundle.
+fn restyle(bundle: &str, text: &str) -> Result<Document, Vec<SourceDiagnostic>> {
+    let world = TestWorld::new(Source::detached(text));
+    let mut tracer = Tracer::new();
+    let module = typst::evaluate(&world, &mut tracer).unwrap();
+    let styles = typst::compile_styles(&Source::detached(bundle), &world, &mut tracer)
+        .map_err(|errors| errors.into_iter().collect::<Vec<_>>())?;
+    let document =
+        typst::compile_with_styles(&world, &module.content(), &styles, &mut tracer)
+            .unwrap();
+    Ok(document)
+}
+
+/// The error messages and hints of a bundle, along with the text they
+/// point at.
+fn errors(bundle: &str) -> Vec<(EcoString, Vec<EcoString>, String)> {
+    let source = Source::detached(bundle);
+    restyle(bundle, "Hello")
+        .unwrap_err()
+        .into_iter()
+        .map(|error| {
+            let range = source.range(error.span).unwrap_or_default();
+            (error.message, error.hints.to_vec(), bundle[range].into())
+        })
+        .collect()
+}
+
+/// The texts in a document's first page along with their fills.
+fn texts(document: &Document) -> Vec<(EcoString, Option<Paint>)> {
+    fn visit(frame: &Frame, out: &mut Vec<(EcoString, Option<Paint>)>) {
+        for (_, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => visit(&group.frame, out),
+                FrameItem::Text(text) => out.push((text.text.clone(), text.fill.clone())),
+                _ => {}
+            }
+        }
+    }
+
+    let mut out = vec![];
+    visit(&document.pages[0].frame, &mut out);
+    out
+}
+
+#[test]
+fn test_styles_set_and_show_rules() {
+    let bundle = "#let color = red\n#set text(fill: color)\n#show \"Hi\": \"Yo\"";
+    let document = restyle(bundle, "Hi").unwrap();
+    assert_eq!(texts(&document), [("Yo".into(), Some(Paint::Solid(Color::RED)))]);
+}
+
+#[test]
+fn test_styles_document_rules_take_precedence() {
+    let document = restyle("#set text(fill: red)", "#set text(fill: blue)\nHi").unwrap();
+    assert_eq!(texts(&document), [("Hi".into(), Some(Paint::Solid(Color::BLUE)))]);
+}
+
+#[test]
+fn test_styles_show_set_without_selector() {
+    let document = restyle("#show: set text(fill: red)", "Hi").unwrap();
+    assert_eq!(texts(&document), [("Hi".into(), Some(Paint::Solid(Color::RED)))]);
+}
+
+#[test]
+fn test_styles_scoped_rules_do_not_leak() {
+    let bundle = "#[#set text(fill: red)]\n#{ set text(fill: blue) }";
+    let document = restyle(bundle, "Hi").unwrap();
+    assert_eq!(texts(&document), [("Hi".into(), Some(Paint::Solid(Color::BLACK)))]);
+}
+
+#[test]
+fn test_styles_show_without_selector() {
+    assert_eq!(
+        errors("#set text(fill: red)\n#show: block"),
+        [(
+            "style bundle cannot contain show rules without a selector".into(),
+            vec!["these rules transform the whole document instead of styling it".into()],
+            "show: block".into(),
+        )]
+    );
+}
+
+#[test]
+fn test_styles_content() {
+    assert_eq!(
+        errors("#set text(fill: red)\nHello"),
+        [(
+            "style bundle must only contain set and show rules".into(),
+            vec![],
+            "Hello".into(),
+        )]
+    );
+}
@@ -0,0 +1,57 @@
+use typst::model::Document;
+use typst::visualize::Color;
+use typst_pdf::PdfOptions;
+
+use crate::compile;
+
+/// Whether the PDF export of a document contains any text.
+fn pdf_has_text(document: &Document) -> bool {
+    let pdf = typst_pdf::pdf(document, &PdfOptions::default()).unwrap();
+    pdf.windows(b"/BaseFont".len()).any(|window| window == b"/BaseFont")
+}
+
+/// Whether the SVG export of a document contains any text.
+fn svg_has_text(document: &Document) -> bool {
+    typst_svg::svg_merged(document, Default::default()).contains("typst-text")
+}
+
+/// Whether the PNG export of a document contains any ink.
+fn png_has_ink(document: &Document) -> bool {
+    let pixmap = typst_render::render(&document.pages[0].frame, 1.0, Color::WHITE);
+    pixmap.pixels().iter().any(|pixel| pixel.red() != 255)
+}
+
+#[test]
+fn test_export_only_is_excluded_from_other_formats() {
+    let pdf = compile("#export-only((\"pdf\",))[Answer]");
+    assert!(pdf_has_text(&pdf));
+    assert!(!svg_has_text(&pdf));
+    assert!(!png_has_ink(&pdf));
+
+    let png = compile("#export-only((\"png\",))[Answer]");
+    assert!(!pdf_has_text(&png));
+    assert!(!svg_has_text(&png));
+    assert!(png_has_ink(&png));
+
+    let svg = compile("#export-only((\"svg\",))[Answer]");
+    assert!(!pdf_has_text(&svg));
+    assert!(svg_has_text(&svg));
+    assert!(!png_has_ink(&svg));
+}
+
+#[test]
+fn test_screen_only_is_excluded_from_pdf() {
+    let document = compile("#screen-only[Click here]");
+    assert!(!pdf_has_text(&document));
+    assert!(svg_has_text(&document));
+    assert!(png_has_ink(&document));
+}
+
+#[test]
+fn test_html_only_is_excluded_from_other_formats() {
+    // Nested restrictions intersect, so only HTML is left.
+    let document = compile("#export-only((\"pdf\", \"html\"))[#screen-only[Video]]");
+    assert!(!pdf_has_text(&document));
+    assert!(!svg_has_text(&document));
+    assert!(!png_has_ink(&document));
+}
@@ -0,0 +1,45 @@
+use crate::compile;
+
+/// Text that is stroked, but not filled.
+const HOLLOW: &str = "#text(fill: none, stroke: 0.5pt + black)[Hollow]";
+
+/// The contents of the compressed streams in a PDF.
+fn content_streams(pdf: &[u8]) -> Vec<String> {
+    let mut streams = vec![];
+    let mut rest = pdf;
+    while let Some(start) = find(rest, b"stream\n") {
+        rest = &rest[start + 7..];
+        let Some(end) = find(rest, b"\nendstream") else { break };
+        if let Ok(data) = miniz_oxide::inflate::decompress_to_vec_zlib(&rest[..end]) {
+            streams.push(String::from_utf8_lossy(&data).into_owned());
+        }
+        rest = &rest[end + 10..];
+    }
+    streams
+}
+
+/// The position of a byte string in another one.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+#[test]
+fn test_text_hollow_pdf() {
+    let pdf = typst_pdf::pdf(&compile(HOLLOW), &Default::default()).unwrap();
+    let content = content_streams(&pdf).concat();
+    assert!(content.contains("1 Tr"));
+    assert!(!content.contains("0 Tr") && !content.contains("2 Tr"));
+
+    // Filled and stroked text uses both.
+    let text = "#text(stroke: 0.5pt + red)[Outlined]";
+    let pdf = typst_pdf::pdf(&compile(text), &Default::default()).unwrap();
+    assert!(content_streams(&pdf).concat().contains("2 Tr"));
+}
+
+#[test]
+fn test_text_hollow_svg() {
+    let svg = typst_svg::svg(&compile(HOLLOW).pages[0].frame);
+    assert!(svg.contains("<use xlink:href=\"#"));
+    assert!(svg.contains("fill=\"none\""));
+    assert!(svg.contains("stroke=\"#000000\""));
+}
@@ -0,0 +1,63 @@
+use typst::syntax::Span;
+
+use crate::compile;
+
+/// Enables timing while alive and disables it again when dropped, so that
+/// other tests don't record events.
+struct Enabled;
+
+impl Enabled {
+    fn new() -> Self {
+        typst_timing::enable();
+        Self
+    }
+}
+
+impl Drop for Enabled {
+    fn drop(&mut self) {
+        typst_timing::disable();
+        typst_timing::clear();
+    }
+}
+
+/// Resolves spans to a stand-in location made up of their number.
+fn source(span: Span) -> (String, u32) {
+    ("main".into(), span.number() as u32)
+}
+
+#[test]
+fn test_timing_summary_and_folded_stacks() {
+    let _enabled = Enabled::new();
+
+    // Layouts that are cached don't show up, so the document is unique to
+    // this test.
+    compile(
+        "#show heading: it => it.body\n\
+         #show emph: it => it.body\n\
+         = Profiled\n\
+         Timed *paragraph* with _emphasis_",
+    );
+
+    let mut summary = vec![];
+    typst_timing::export_summary(&mut summary, source).unwrap();
+    let summary = String::from_utf8(summary).unwrap();
+    for name in ["compile", "show rule", "strong", "layout paragraph"] {
+        assert!(summary.contains(&format!("\"name\": \"{name}\"")), "{name} missing");
+    }
+
+    // Each show rule has its own entry, but built-in show rules are only
+    // recorded once. Tests running at the same time may add more rules.
+    assert!(summary.matches("\"name\": \"show rule\"").count() >= 2);
+    assert_eq!(summary.matches("\"name\": \"strong\"").count(), 1);
+
+    let mut folded = vec![];
+    typst_timing::export_folded(&mut folded, source).unwrap();
+    let folded = String::from_utf8(folded).unwrap();
+    let line = folded
+        .lines()
+        .find(|line| line.starts_with("compile;") && line.contains(";show rule (main:"))
+        .unwrap();
+    let (_, micros) = line.rsplit_once(' ').unwrap();
+    micros.parse::<u128>().unwrap();
+    assert!(!folded.contains(";strong;strong"));
+}
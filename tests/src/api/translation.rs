@@ -0,0 +1,35 @@
+use sha2::{Digest, Sha256};
+use typst::model::{translatable_segments, TranslatableSegment};
+
+use crate::compile;
+
+#[test]
+fn test_translatable_segments() {
+    let document = compile(
+        "#translatable(id: \"title\")[Introduction]\n\
+         #translatable[Hello *world*!]\n\
+         #translatable(id: \"title\")[Introduction]",
+    );
+
+    let digest: String = Sha256::digest(b"Hello world!")
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    assert_eq!(
+        translatable_segments(&document.introspector),
+        [
+            TranslatableSegment { id: "title".into(), text: "Introduction".into() },
+            TranslatableSegment { id: digest.into(), text: "Hello world!".into() },
+        ]
+    );
+}
+
+#[test]
+fn test_translatable_segments_derived_ids_are_stable() {
+    let document = compile("#translatable[]");
+    let segments = translatable_segments(&document.introspector);
+    assert_eq!(
+        segments[0].id,
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
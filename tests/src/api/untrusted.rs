@@ -0,0 +1,49 @@
+use typst::syntax::Source;
+use typst::Library;
+
+use crate::try_compile_with;
+
+/// Compile a document with the given trust, returning the error messages and
+/// the text they point at.
+fn errors(untrusted: bool, text: &str) -> Vec<(String, String)> {
+    let library = Library::builder().with_untrusted(untrusted).build();
+    let (_, errors) = try_compile_with(library, text);
+    let source = Source::detached(text);
+    errors
+        .into_iter()
+        .map(|error| {
+            let range = source.range(error.span).unwrap_or_default();
+            (error.message.into(), text[range].into())
+        })
+        .collect()
+}
+
+#[test]
+fn test_untrusted_library_allows_plain_content() {
+    assert!(errors(true, "= Hello\n#place(top, float: true)[World]").is_empty());
+}
+
+#[test]
+fn test_untrusted_library_restricts_files() {
+    let message = "failed to load file (untrusted content may not read files)";
+    for (text, path) in [
+        ("#read(\"/suite/playground.typ\")", "\"/suite/playground.typ\""),
+        ("#include \"/suite/playground.typ\"", "\"/suite/playground.typ\""),
+        ("#import \"/suite/playground.typ\"", "\"/suite/playground.typ\""),
+        ("#context read(\"/suite/playground.typ\")", "\"/suite/playground.typ\""),
+    ] {
+        assert!(errors(false, text).is_empty(), "{text}");
+        assert_eq!(errors(true, text), [(message.into(), path.into())], "{text}");
+    }
+}
+
+#[test]
+fn test_untrusted_library_restricts_placement() {
+    assert_eq!(
+        errors(true, "#place(top)[A]"),
+        [(
+            "untrusted content may not use absolute placement".into(),
+            "place(top)[A]".into()
+        )]
+    );
+}
@@ -0,0 +1,8 @@
+--- sys-locale-default ---
+#test(sys.locale, (
+  lang: "en",
+  region: none,
+  paper: "a4",
+  decimal-separator: ".",
+))
+#context test(text.lang, sys.locale.lang)
@@ -0,0 +1,29 @@
+--- warn ---
+// Warning: 2-28 the title is empty
+#warn("the title is empty")
+
+--- warn-hints ---
+// Warning: 2-51 deprecated
+// Hint: 2-51 use `new` instead
+#warn("deprecated", hints: ("use `new` instead",))
+
+--- suppress-warnings ---
+#suppress-warnings[#warn("anything")]
+#suppress-warnings(only: "custom")[#warn("custom warning")]
+#suppress-warnings(only: ("a", "b"))[#warn("b")]
+
+--- suppress-warnings-other ---
+// Warning: 37-50 other
+#suppress-warnings(only: "custom")[#warn("other")]
+
+--- suppress-warnings-font ---
+#suppress-warnings(only: "unknown font family")[
+  #set text(font: ("Fancy Font", "Libertinus Serif"))
+  Falls back silently.
+]
+
+--- suppress-warnings-outside-region ---
+// Only warnings that stem from code within the call are suppressed.
+// Warning: 30-42 unknown font family: fancy font
+#let fancy = text.with(font: "Fancy Font")
+#suppress-warnings(only: "unknown font family")[#fancy[Hi]]
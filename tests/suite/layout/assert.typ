@@ -0,0 +1,27 @@
+// Test layout assertions.
+
+--- assert-fits-ok ---
+#assert-fits(width: 50pt, box(width: 40pt))
+
+--- assert-fits-too-wide ---
+// Error: 2-44 content is 60pt wide, but at most 50pt fit
+#assert-fits(width: 50pt, box(width: 60pt))
+
+--- assert-fits-too-tall ---
+// Error: 2-48 content is 20pt tall, but at most 10pt fit
+#assert-fits(height: 10pt, block(height: 20pt))
+
+--- assert-layout-ok ---
+#assert-layout(max-pages: 1)
+
+--- assert-layout-zero-pages ---
+// Error: 27-28 number must be positive
+#assert-layout(max-pages: 0)
+
+--- assert-layout-too-many-pages ---
+// Error: 2-29 document has 2 pages, but at most 1 is allowed
+// Hint: 2-29 try shortening the content or reducing its size
+#assert-layout(max-pages: 1)
+First
+#pagebreak()
+Second
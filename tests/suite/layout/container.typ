@@ -188,3 +188,92 @@ First!
 // Test box in 100% width block.
 #block(width: 100%, fill: red, box("a box"))
 #block(width: 100%, fill: red, [#box("a box") #box()])
+
+--- container-opacity-blend-mode-fields ---
+#test(box(opacity: 50%).opacity, 50%)
+#test(block(blend-mode: "multiply").blend-mode, "multiply")
+
+--- container-blend-mode-invalid ---
+// Error: 18-24 expected "normal", "multiply", "screen", "overlay", "darken", "lighten", "color-dodge", "color-burn", "hard-light", "soft-light", "difference", "exclusion", "hue", "saturation", "color", or "luminosity"
+#box(blend-mode: "glow")
+
+--- container-ratio-derived-size ---
+#context {
+  test(measure(box(width: 32pt, ratio: 2)).height, 16pt)
+  test(measure(box(height: 10pt, ratio: 1.5)).width, 15pt)
+  test(measure(block(height: 10pt, ratio: 2)).width, 20pt)
+  test(measure(block(width: 30pt, height: 5pt, ratio: 2)).height, 5pt)
+}
+
+--- container-ratio-invalid ---
+// Error: 13-15 aspect ratio must be positive
+#box(ratio: -1)
+
+--- block-contour-polygon ---
+// Test pouring text into a triangle.
+#let shape = polygon.with((50%, 0%), (100%, 100%), (0%, 100%))
+#block(
+  width: 100%,
+  height: 90pt,
+  contour: shape(),
+)[
+  #place(shape(stroke: 0.5pt + red))
+  #set align(center)
+  #lorem(16)
+]
+
+--- block-contour-path ---
+// Test a circular contour with inset. The curves are approximated and the
+// lines below the contour use the full width.
+#let circle = path.with(
+  closed: true,
+  ((50%, 0pt), (-27.6pt, 0pt)),
+  ((100%, 50pt), (0pt, -27.6pt)),
+  ((50%, 100pt), (27.6pt, 0pt)),
+  ((0%, 50pt), (0pt, 27.6pt)),
+)
+#block(
+  width: 120pt,
+  inset: 10pt,
+  stroke: 0.5pt,
+  contour: circle(),
+)[
+  #place(circle(stroke: 0.5pt + red))
+  #set align(center)
+  #lorem(36)
+]
+
+--- block-contour-diagonal ---
+// Test a diagonal cut.
+#block(
+  width: 100%,
+  height: 70pt,
+  contour: polygon((0%, 0%), (100%, 0%), (40%, 100%), (0%, 100%)),
+  lorem(9),
+)
+
+--- block-contour-invalid ---
+// Error: 17-23 expected polygon or path
+#block(contour: rect())[Hi]
+
+--- container-ratio-full-width-grid ---
+// A full-width container takes the width of its grid cell.
+#set page(width: 120pt, height: auto)
+#grid(
+  columns: (1fr, 2fr),
+  gutter: 6pt,
+  block(width: 100%, ratio: 1, fill: aqua),
+  block(width: 100%, ratio: 2, fill: teal),
+  box(width: 100%, ratio: 4, fill: eastern),
+  block(ratio: 4, fill: olive),
+)
+
+--- container-ratio-full-width-columns ---
+// A full-width container takes the width of its column.
+#set page(width: 120pt, height: 100pt)
+#columns(2, gutter: 10pt)[
+  #block(width: 100%, ratio: 2, fill: aqua)
+  #block(ratio: 1, fill: teal)
+  #colbreak()
+  #box(width: 100%, ratio: 3, fill: eastern)
+]
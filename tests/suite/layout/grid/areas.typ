@@ -0,0 +1,77 @@
+// Test named grid areas.
+
+--- grid-areas ---
+#grid(
+  areas: (
+    ("head", "head"),
+    ("nav", "main"),
+  ),
+  columns: (3em, 1fr),
+  inset: 4pt,
+  fill: luma(230),
+  gutter: 2pt,
+  grid.cell(area: "main")[Main],
+  grid.cell(area: "nav")[Nav],
+  grid.cell(area: "head")[Head],
+)
+
+--- grid-areas-span-rows ---
+// Test an area spanning rows and columns, with unnamed positions filled by
+// automatically placed cells. The columns are inferred from the areas.
+#grid(
+  areas: (
+    ("side", "top", none),
+    ("side", "big", "big"),
+    ("side", "big", "big"),
+  ),
+  inset: 4pt,
+  stroke: 0.5pt,
+  grid.cell(area: "big", fill: aqua)[Big],
+  grid.cell(area: "side", fill: yellow)[Side],
+  grid.cell(area: "top")[Top],
+  [A],
+)
+
+--- table-areas ---
+#table(
+  areas: (
+    ("title", "title", "title"),
+    (none, none, none),
+  ),
+  table.cell(area: "title", align: center)[*Title*],
+  [A], [B], [C],
+)
+
+--- grid-areas-unknown ---
+// Error: 3:3-3:29 grid area "side" does not exist
+#grid(
+  areas: (("a", "b"),),
+  grid.cell(area: "side")[A],
+)
+
+--- grid-areas-not-rectangular ---
+// Error: 2:10-2:34 grid area "b" must be a rectangle
+#grid(
+  areas: (("a", "b"), ("b", "b")),
+)
+
+--- grid-areas-uneven-rows ---
+// Error: 2:10-2:30 all rows of the grid areas must have the same length
+#grid(
+  areas: (("a", "b"), ("c",)),
+)
+
+--- grid-areas-cell-with-position ---
+// Error: 3:3-3:32 cell cannot be placed in an area and at a position
+// Hint: 3:3-3:32 remove either the `area` or the `x` and `y` fields
+#grid(
+  areas: (("a", "b"),),
+  grid.cell(area: "a", x: 1)[A],
+)
+
+--- table-areas-unknown ---
+// Error: 3:3-3:30 grid area "side" does not exist
+#table(
+  areas: (("a", "b"),),
+  table.cell(area: "side")[A],
+)
@@ -15,3 +15,55 @@ Hey #box(baseline: 40%, image("/assets/images/tiger.jpg", width: 1.5cm)) there!
 // The math content should also be affected by the TextElem baseline.
 hello #text(baseline: -5pt)[123 #sym.WW\orld]\
 hello #text(baseline: -5pt)[$123 WW#text[or]$ld]\
+
+--- baseline-box-alignment-field ---
+#test(box(baseline: top).baseline, top)
+#test(box(baseline: 40%).baseline, 40%)
+
+--- baseline-box-horizontal-alignment ---
+// Error: 16-20 expected `top`, `horizon`, or `bottom`, found left
+#box(baseline: left)[A]
+
+--- baseline-box-top-bottom ---
+#set page(width: 100pt, height: auto, margin: 0pt)
+A#metadata(none)<line>
+#box(baseline: top, stack(spacing: 5pt, [B#metadata(none)<first>], [C]))
+#box(baseline: bottom, stack(spacing: 5pt, [D], [E#metadata(none)<last>]))
+#context {
+  let y(label) = locate(label).position().y
+  test(y(<first>), y(<line>))
+  test(y(<last>), y(<line>))
+}
+
+--- baseline-box-top-without-text ---
+// Without text, the default baseline at the bottom edge is kept.
+#set page(width: 100pt, height: auto, margin: 0pt)
+A#metadata(none)<line> #box(baseline: top, block(width: 5pt, height: 20pt, fill: aqua))
+#context test(locate(<line>).position().y, 20pt)
+
+--- baseline-box-horizon ---
+// Fonts without a math table use half of their x-height as the axis.
+#set page(width: 100pt, height: auto, margin: 0pt)
+#set text(font: "DejaVu Sans Mono", size: 10pt)
+A#metadata(none)<line> #box(baseline: horizon, block(width: 5pt, height: 20pt, fill: aqua))
+#context {
+  let x-height = measure(text(top-edge: "x-height", bottom-edge: "baseline")[x]).height
+  let axis = locate(<line>).position().y - 10pt
+  test(calc.round(axis.pt(), digits: 3), calc.round(x-height.pt() / 2, digits: 3))
+}
+
+--- baseline-box-horizon-math-font ---
+// Math fonts use the axis from their math table, which lies above the
+// baseline.
+#set page(width: 100pt, height: auto, margin: 0pt)
+#set text(font: "New Computer Modern Math", size: 10pt)
+A#metadata(none)<line> #box(baseline: horizon, block(width: 5pt, height: 20pt, fill: aqua))
+#context {
+  let axis = locate(<line>).position().y - 10pt
+  assert(axis > 0pt and axis < 5pt)
+}
+
+--- baseline-box-shift ---
+#set page(width: 100pt, height: auto, margin: 0pt)
+A#metadata(none)<line> #box(baseline: 5pt, block(width: 5pt, height: 20pt, fill: aqua))
+#context test(locate(<line>).position().y, 15pt)
@@ -58,6 +58,43 @@ fi vs. #text(features: (liga: 0))[No fi]
 // Error: 21-35 expected string, found boolean
 #set text(features: ("tag", false))
 
+--- text-family-features ---
+// Test default features for a font family.
+1887 \
+#set text(family-features: ("linux libertine": ("onum",)))
+1887 \
+#text(font: "New Computer Modern")[1887] \
+#text(number-type: "lining")[1887]
+
+--- text-profile-book ---
+// Test the book profile.
+#set text(profile: "book")
+The NASA budget of 1969.
+
+#table(
+  columns: 2,
+  [Year], [1969],
+)
+
+--- text-profile-acronyms ---
+// Test small capitals for acronyms only.
+#set text(profile: (acronyms: true))
+NASA and the ESA, but not A or Hello.
+
+--- text-profile-table-numbers ---
+// Test the number type in tables.
+#set text(profile: (table-numbers: "old-style"))
+1969
+#table(columns: 2, [1969], [2024])
+
+--- text-family-features-bad ---
+// Error: 28-55 expected array or dictionary, found boolean
+#set text(family-features: ("libertinus serif": false))
+
+--- text-profile-bad-key ---
+// Error: 20-38 unexpected key "digits", valid keys are "numbers", "table-numbers", and "acronyms"
+#set text(profile: (digits: "lining"))
+
 --- text-tracking-negative ---
 // Test tracking.
 #set text(tracking: -0.01em)
@@ -78,6 +115,49 @@ I'm in#text(tracking: 0.15em + 1.5pt)[ spaace]!
 #set text(tracking: 0.3em)
 النص
 
+--- text-track-caps ---
+// Test that only runs of capitals are tracked.
+#context {
+  let plain = measure[NASA and Nasa].width
+  let tracked = measure(text(track-caps: 1pt)[NASA and Nasa]).width
+  assert(calc.abs((tracked - plain - 3pt).pt()) < 0.01)
+}
+
+--- text-track-caps-size ---
+// Test that tracked capitals are scaled.
+#context {
+  let plain = measure[NASA].width
+  let scaled = measure(text(track-caps: (size: 50%))[NASA]).width
+  assert(calc.abs((scaled - plain / 2).pt()) < 0.01)
+}
+
+#set text(track-caps: (tracking: 0.05em, size: 80%))
+The NASA and ESA missions.
+
+--- text-track-caps-pattern ---
+// Test that a custom pattern replaces the runs of capitals.
+#context {
+  let plain = measure[NASA 555 1234].width
+  let tracked = measure(
+    text(track-caps: (tracking: 1pt, pattern: regex("\d+")))[NASA 555 1234]
+  ).width
+  assert(calc.abs((tracked - plain - 5pt).pt()) < 0.01)
+}
+
+--- text-track-caps-pattern-line-break ---
+// Test that a match is tracked the same on both sides of a line break.
+#set page(width: 80pt)
+#set text(track-caps: (tracking: 3pt, pattern: regex("[A-Z]+-[A-Z]+")))
+Read the ABCD-EFGH manual.
+
+--- text-track-caps-invalid-size ---
+// Error: 23-33 size must be positive
+#set text(track-caps: (size: 0%))
+
+--- text-track-caps-bad-key ---
+// Error: 23-53 unexpected key "case", valid keys are "tracking", "size", and "pattern"
+#set text(track-caps: (tracking: 1pt, case: "upper"))
+
 --- text-spacing ---
 // Test word spacing.
 #set text(spacing: 1em)
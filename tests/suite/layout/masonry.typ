@@ -0,0 +1,30 @@
+// Test masonry layouts.
+
+--- masonry-shortest ---
+#let item(h) = rect(width: 10pt, height: h)
+#context {
+  let size = measure(masonry(gutter: 2pt, item(30pt), item(10pt), item(10pt)))
+  test(size.width, 22pt)
+  test(size.height, 30pt)
+}
+
+--- masonry-sequential ---
+#let item(h) = rect(width: 10pt, height: h)
+#context {
+  let size = measure(masonry(
+    gutter: 2pt,
+    order: "sequential",
+    item(30pt),
+    item(10pt),
+    item(10pt),
+  ))
+  test(size.height, 42pt)
+}
+
+--- masonry-zero-columns ---
+// Error: 19-20 number must be positive
+#masonry(columns: 0)
+
+--- masonry-order-invalid ---
+// Error: 17-25 expected "shortest" or "sequential"
+#masonry(order: "random")
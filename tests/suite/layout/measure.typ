@@ -20,3 +20,56 @@
   assert(d2.width < 400pt)
   assert(d2.height > 50pt)
 }
+
+--- measure-candidates ---
+// Test measuring multiple candidates at once.
+#context {
+  let sizes = measure(([A], text(20pt)[A], [AA]))
+  test(sizes.len(), 3)
+  test(sizes.at(0), measure[A])
+  test(sizes.at(1), measure(text(20pt)[A]))
+  assert(sizes.at(2).width > sizes.at(0).width)
+}
+
+--- measure-candidates-empty ---
+#context test(measure(()), ())
+
+--- measure-candidates-bad-type ---
+// Error: 18-24 expected content, found integer
+#context measure((1, 2))
+
+--- measure-styles ---
+#let big = styles[#set text(20pt)]
+#context {
+  let (normal, large) = (measure[A], measure([A], big))
+  test(large, measure(text(20pt)[A]))
+  assert(large.width > normal.width)
+  box(fill: aqua, width: normal.width, height: normal.height)
+  box(fill: eastern, width: large.width, height: large.height)
+}
+
+--- measure-styles-folded ---
+// Styles from `style` already contain the context's styles and must not be
+// applied twice. Emphasis toggles italics, so that would cancel it.
+_#style(s => context {
+  test(measure([Affine], s), measure[Affine])
+})_
+
+--- measure-styles-candidates ---
+#let fit = sizes => {
+  let candidates = sizes.map(size => styles[#set text(size)])
+  context {
+    let widths = candidates.map(s => measure([Fit], s).width)
+    let i = widths.position(w => w <= 30pt)
+    text(sizes.at(i))[Fit]
+  }
+}
+#fit((20pt, 16pt, 12pt, 8pt))
+
+--- styles-type ---
+#test(type(styles[]), styles)
+#test(type(styles[#set text(red)]), styles)
+
+--- styles-not-only-rules ---
+// Error: 2-28 expected content consisting only of set and show rules
+#styles[#set text(red); Hi]
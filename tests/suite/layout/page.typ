@@ -258,3 +258,146 @@ Default page numbers now.
 Hi
 #pagebreak()
 = Second
+
+--- page-marginal-fit-shrink ---
+#set page(
+  width: 80pt,
+  height: 60pt,
+  margin: (x: 5pt, top: 20pt, bottom: 5pt),
+  marginal-fit: "shrink",
+  header: rect(width: 100%, height: 16pt, fill: aqua),
+)
+#rect(width: 100%, height: 100%, fill: eastern)
+
+--- page-marginal-fit-grow ---
+#set page(
+  width: 80pt,
+  height: 60pt,
+  margin: (x: 5pt, top: 15pt, bottom: 5pt),
+  marginal-fit: "grow",
+  header: context {
+    let n = counter(page).get().first()
+    rect(width: 100%, height: 10pt * n, fill: aqua)
+  },
+)
+#lorem(20)
+
+--- page-marginal-fit-overflow ---
+// Marginals overflow into the body by default.
+#set page(
+  width: 80pt,
+  height: 60pt,
+  margin: (x: 5pt, top: 15pt, bottom: 5pt),
+  header: rect(width: 100%, height: 25pt, fill: aqua.transparentize(50%)),
+)
+#rect(width: 100%, height: 100%, fill: eastern)
+
+--- page-marginal-fit-invalid ---
+// Error: 25-34 expected "overflow", "shrink", or "grow"
+#set page(marginal-fit: "stretch")
+
+--- page-justify-bad-type ---
+// Error: 20-25 expected boolean, found string
+#set page(justify: "yes")
+
+--- page-section-restart ---
+#set page(width: 60pt, height: 30pt, numbering: "1.1", section: counter("chapter"))
+#counter("chapter").step()
+#context test(counter(page).get(), (1,))
+#pagebreak()
+#counter("chapter").step()
+#context test(counter(page).get(), (1,))
+#context test(counter(page).display(), "2.1")
+#pagebreak()
+#context test(counter(page).get(), (2,))
+#context test(counter(page).final(), (2,))
+#context test(counter(page).display("I.a"), "II.b")
+
+--- page-section-outline-and-ref ---
+#set page(width: 120pt, height: 80pt, numbering: "1-1", section: counter(heading))
+#set heading(numbering: "1.")
+#outline(title: none)
+= Intro <intro>
+#pagebreak()
+= Method <method>
+#lorem(8)
+See #ref(<intro>, form: "page") and #ref(<method>, form: "page").
+
+--- page-section-page-counter ---
+// Error: 2-37 pages cannot be sectioned by the page counter
+#page(section: counter(page))[Hello]
+
+--- page-bleed ---
+#set page(width: 100pt, height: 100pt, margin: 10pt, bleed: 5pt, fill: aqua)
+#place(rect(width: 100%, height: 100%, stroke: red))
+#context test(here().position(), (page: 1, x: 15pt, y: 15pt))
+#context test(page.bleed, 5pt)
+
+--- page-justify ---
+// The weak spacing on the full first page stretches, while the last page is
+// left as it is.
+#set page(height: 100pt, justify: true)
+= Heading
+#lorem(5)
+
+#lorem(8)
+
+#v(4pt, weak: true)
+#lorem(12)
+
+--- page-justify-baseline ---
+// The last baselines of full pages line up with the bottom of the region even
+// if their last lines extend below the baseline differently.
+#set page(
+  height: 70pt,
+  justify: true,
+  background: place(dy: 60pt, line(length: 100%, stroke: 0.2pt + red)),
+)
+#set text(bottom-edge: "bounds")
+First
+
+Group of pages
+
+Second
+
+Third
+
+Fourth
+
+Jumping
+
+Last
+
+--- page-background-image ---
+#let picture = ```
+<svg xmlns="http://www.w3.org/2000/svg" width="80" height="80">
+  <rect width="80" height="20" fill="red"/>
+  <rect y="20" width="80" height="40" fill="green"/>
+  <rect y="60" width="80" height="20" fill="blue"/>
+</svg>
+```.text
+#set page(
+  width: 100pt,
+  height: 60pt,
+  background-image: image.decode(picture),
+)
+#text(white)[*Covered*]
+
+--- page-background-image-bleed ---
+// The image covers the bleed, keeps its top edge in view, and stays behind
+// the background.
+#let picture = ```
+<svg xmlns="http://www.w3.org/2000/svg" width="80" height="80">
+  <rect width="80" height="20" fill="red"/>
+  <rect y="20" width="80" height="40" fill="green"/>
+  <rect y="60" width="80" height="20" fill="blue"/>
+</svg>
+```.text
+#set page(
+  width: 100pt,
+  height: 60pt,
+  bleed: 10pt,
+  background-image: image.decode(picture, focus: (50%, 0%)),
+  background: rect(width: 100%, height: 100%, stroke: 2pt + white),
+)
+#text(white)[*Covered*]
@@ -58,3 +58,18 @@ This is the first line\ #h(2cm, weak: true) A new line
 
 // non-spacing, on the other hand, is not removed.
 This is the first line\ #h(2cm, weak: false) A new line
+
+--- spacing-h-weight ---
+// Only the fractions with the highest weight receive space.
+#set page(width: 120pt, margin: 10pt)
+#h(1fr) #context test(here().position().x, 60pt) #h(1fr)
+#linebreak()
+#h(1fr) #context test(here().position().x, 10pt) #h(1fr, weight: 2)
+#linebreak()
+A #h(1fr) B #h(1fr) C \
+A #h(1fr) B #h(1fr, weight: 2) C
+
+--- spacing-h-fill ---
+// A fill takes the space of its spacing.
+#set page(width: 120pt, margin: 10pt)
+A #h(1fr, fill: line(length: 100%, stroke: 0.5pt)) B
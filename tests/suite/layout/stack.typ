@@ -89,3 +89,30 @@ World! 🌍
   // Error: 3-40 stack spacing is infinite
   stack(spacing: infinite-length)[A][B]
 })
+
+--- stack-baseline ---
+#context {
+  let a = text(20pt)[Big]
+  let b = box(width: 40pt)[small text that wraps]
+  let aligned = measure(stack(dir: ltr, baseline: true, a, b))
+  let top = measure(stack(dir: ltr, a, b))
+  test(aligned.width, top.width)
+  test(aligned.height > top.height, true)
+}
+
+--- stack-baseline-render ---
+#set page(width: 160pt)
+#let guide(body) = box(stroke: 0.5pt + aqua, body)
+#stack(
+  dir: ltr,
+  spacing: 6pt,
+  baseline: true,
+  guide(text(20pt)[Big]),
+  guide(block(width: 50pt)[small text that wraps]),
+  guide(box(width: 20pt, height: 10pt, fill: red)),
+  guide(rect(width: 15pt, height: 15pt, fill: blue)),
+)
+
+--- stack-baseline-vertical ---
+// Has no effect in vertical stacks.
+#stack(baseline: true, text(20pt)[Big], [small])
@@ -0,0 +1,27 @@
+// Test the `export-only` and `screen-only` functions.
+
+--- export-only-keeps-space ---
+#context test(
+  measure(export-only(("pdf",))[Answer: 42]),
+  measure[Answer: 42],
+)
+
+--- screen-only-keeps-space ---
+#context test(
+  measure(screen-only[Click here]),
+  measure[Click here],
+)
+
+--- export-only-bad-target ---
+// Error: 14-22 expected "pdf", "png", "svg", "docx", or "html"
+#export-only(("odt",))[A]
+
+--- export-only-render ---
+// The reference images are PNGs, so only content for PNG is visible.
+PNG: #export-only(("png",))[shown] \
+PDF and SVG: #export-only(("pdf", "svg"))[hidden] \
+Screen: #screen-only[shown] \
+PDF and HTML: #export-only(("pdf", "html"))[#screen-only[hidden]]
+
+--- export-only-render-math ---
+$ x + #export-only(("pdf",))[$y$] + #screen-only[$z$] $
@@ -104,3 +104,82 @@ Hello #scaled[World]!\
 
 #set scale(reflow: true)
 Hello #scaled[World]!
+
+--- transform-matrix-constructor ---
+#let t = transform(sx: 200%, ty: 10pt)
+#test(t.sx, 200%)
+#test(t.kx, 0%)
+#test(t.ty, 10pt)
+#test(repr(t), "transform(sx: 200%, ky: 0%, kx: 0%, sy: 100%, tx: 0pt, ty: 10pt)")
+#test(transform(), transform.scale(100%))
+#test(transform.scale(50%), transform(sx: 50%, sy: 50%))
+
+--- transform-matrix-then-apply ---
+#let t = transform.scale(200%).then(transform.translate(5pt, 0pt))
+#test(t.apply(vec2(1pt, 1pt)), vec2(7pt, 2pt))
+#test(t.inverse().apply(vec2(7pt, 2pt)), vec2(1pt, 1pt))
+#let p = transform.rotate(90deg).apply(vec2(10pt, 0pt))
+#test((calc.round(p.x.pt()), calc.round(p.y.pt())), (0.0, 10.0))
+
+--- transform-matrix-inverse-singular ---
+// Error: 2-31 cannot invert a transform with a determinant of zero
+#transform.scale(0%).inverse()
+
+--- transform-matrix-inverse-singular-axis ---
+// Error: 2-29 cannot invert a transform with a determinant of zero
+#transform(sx: 0%).inverse()
+
+--- transform-matrix-inverse-singular-skew ---
+// Error: 2-61 cannot invert a transform with a determinant of zero
+#transform(sx: 100%, ky: 100%, kx: 100%, sy: 100%).inverse()
+
+--- transform-matrix-skew-90deg ---
+// Error: 2-27 skewing angle must not be an odd multiple of 90deg
+#transform.skew(ax: 90deg)
+
+--- transform-matrix-em-translation ---
+// Error: 2-26 transform translation must be absolute
+#transform.translate(1em)
+
+--- transform-matrix-apply-em ---
+// Error: 2-35 cannot transform a point with non-zero em units (`vec2(1em, 0pt)`)
+// Hint: 2-35 use `vec.to-absolute()` to resolve its em units first
+#transform().apply(vec2(1em, 0pt))
+
+--- transform-skew-reflow ---
+#context {
+  let body = box(width: 10pt, height: 10pt)
+  let size = measure(skew(x: 45deg, reflow: true, body))
+  test(calc.round(size.width.pt()), 20.0)
+  test(calc.round(size.height.pt()), 10.0)
+  test(measure(skew(y: 45deg, body)), measure(body))
+}
+
+--- transform-skew-90deg ---
+// Error: 2-19 skewing angle must not be an odd multiple of 90deg
+#skew(x: 90deg)[A]
+
+--- transform-skew-collapsed ---
+// Error: 2-29 skewing angles must not collapse the content onto a line
+#skew(x: 45deg, y: 45deg)[A]
+
+--- transform-rotate-extra-transform ---
+#set page(width: 80pt, height: 60pt)
+#set align(center + horizon)
+#rotate(30deg, transform: transform.scale(150%, 100%), rect(fill: aqua)[A])
+
+--- transform-scale-extra-transform-reflow ---
+#set page(width: 60pt, height: 60pt)
+A#scale(
+  x: 200%,
+  reflow: true,
+  transform: transform.rotate(90deg),
+  box(width: 10pt, height: 20pt, fill: aqua),
+)B
+
+--- transform-skew-extra-transform ---
+#skew(x: -12deg, transform: transform.translate(10pt, 0pt))[Slanted]
+
+--- transform-extra-transform-singular ---
+// Error: 2-50 transform must be invertible
+#rotate(10deg, transform: transform.scale(0%))[A]
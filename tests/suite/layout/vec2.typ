@@ -0,0 +1,59 @@
+--- vec2-constructor ---
+#test(vec2(1pt, 2pt), vec2((1pt, 2pt)))
+#test(vec2(1pt, 2pt).x, 1pt)
+#test(vec2(1pt, 2em).y, 2em)
+#test(repr(vec2(1pt, 2em)), "vec2(1pt, 2em)")
+#test(vec2(1pt, 2pt).to-array(), (1pt, 2pt))
+
+--- vec2-arithmetic ---
+#let a = vec2(1pt, 2pt)
+#let b = vec2(3pt, -1pt)
+#test(a + b, vec2(4pt, 1pt))
+#test(a - b, vec2(-2pt, 3pt))
+#test(-a, vec2(-1pt, -2pt))
+#test(a * 2, vec2(2pt, 4pt))
+#test(0.5 * a, vec2(0.5pt, 1pt))
+#test(b / 2, vec2(1.5pt, -0.5pt))
+
+--- vec2-products ---
+#test(vec2(3pt, 4pt).dot(vec2(2pt, 1pt)), 10.0)
+#test(vec2(1pt, 0pt).cross(vec2(0pt, 1pt)), 1.0)
+#test(vec2(3pt, 4pt).length(), 5pt)
+#test(vec2(0pt, 4pt).normalize(), vec2(0pt, 1pt))
+
+--- vec2-to-absolute ---
+#set text(size: 10pt)
+#context test(vec2(1em, 2pt).to-absolute(), vec2(10pt, 2pt))
+
+--- vec2-as-point ---
+#test(line(start: vec2(1pt, 2pt)).start, (1pt, 2pt))
+
+--- vec2-array-wrong-length ---
+// Error: 7-22 vector array must contain exactly two entries
+#vec2((1pt, 2pt, 3pt))
+
+--- vec2-length-em ---
+// Error: 2-25 cannot compute this for a vector with non-zero em units (`vec2(1em, 0pt)`)
+// Hint: 2-25 use `vec.to-absolute()` to resolve its em units first
+#vec2(1em, 0pt).length()
+
+--- vec2-normalize-zero ---
+// Error: 2-28 cannot normalize a vector of length zero
+#vec2(0pt, 0pt).normalize()
+
+--- vec2-add-length ---
+// Error: 3-23 cannot add vector and length
+#(vec2(1pt, 2pt) + 1pt)
+
+--- vec2-place-offset ---
+#set page(width: 100pt, height: 50pt, margin: 0pt)
+#place(offset: vec2(10pt, 20pt), block(width: 5pt, height: 5pt, fill: aqua)[#metadata(none) <a>])
+#place(offset: vec2(10pt, 20pt), dy: 5pt, block(width: 5pt, height: 5pt, fill: aqua)[#metadata(none) <b>])
+#context test(locate(<a>).position(), (page: 1, x: 10pt, y: 20pt))
+#context test(locate(<b>).position(), (page: 1, x: 10pt, y: 5pt))
+#test(place(offset: vec2(1pt, 2pt))[].dx, 1pt + 0%)
+
+--- vec2-move-offset ---
+#set page(width: 100pt, height: 50pt, margin: 0pt)
+#move(offset: vec2(7pt, 3pt), block(width: 5pt, height: 5pt, fill: aqua)[#metadata(none) <a>])
+#context test(locate(<a>).position(), (page: 1, x: 7pt, y: 3pt))
@@ -284,3 +284,27 @@ Looks at the @quadratic formula.
 #set page(width: 150pt)
 #set text(lang: "he")
 תהא סדרה $a_n$: $[a_n: 1, 1/2, 1/3, dots]$
+
+--- math-equation-shared-counter ---
+#set figure(numbering: "1")
+#set math.equation(numbering: "(1)", counter: counter(figure.where(kind: image)))
+#place(hide[
+  #figure(image("/assets/images/tiger.jpg", width: 1pt))
+  $ a = b $ <shared>
+  #figure(image("/assets/images/tiger.jpg", width: 1pt))
+])
+#context {
+  test(counter(figure.where(kind: image)).final(), (3,))
+  test(counter(math.equation).final(), (0,))
+  test(counter(figure.where(kind: image)).at(<shared>), (2,))
+}
+
+--- math-equation-shared-counter-display ---
+// Equations and figures are numbered in one sequence.
+#set page(width: 150pt)
+#set figure(numbering: "(1)")
+#set math.equation(numbering: "(1)", counter: counter(figure.where(kind: image)))
+$ a + b = c $ <first>
+#figure(rect(width: 20pt, height: 10pt), caption: [Box])
+$ c - b = a $
+See @first and the heading counter, which is unaffected: #context counter(heading).get().
@@ -114,3 +114,11 @@ Inline $2 baz(x,y,baz(u, v))$.
 $ 2 foo(alpha, (M+foo(a, b))) $
 $ 2 bar(alpha, (M+foo(a, b))) $
 $ 2 baz(x,y,baz(u, v)) $
+
+--- math-embedded-script-size ---
+// Test that embedded content shrinks along with scripts.
+#let cell = rect(width: 10pt, height: 10pt)
+#let width(body) = calc.round(measure(body).width.pt(), digits: 2)
+#context test(width($cell$), 10.0)
+#context test(width($script(cell)$), 7.0)
+#context test(width($sscript(cell)$), 5.0)
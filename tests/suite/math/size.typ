@@ -7,3 +7,18 @@ $a/b, display(a/b), display(a)/display(b), inline(a/b), script(a/b), sscript(a/b
 --- issue-3658-math-size ---
 $ #rect[$1/2$] $
 $#rect[$1/2$]$
+
+--- math-size-context ---
+#context test(math.size(), (style: "text", scale: 100%))
+
+#let probe = context metadata(math.size())
+#place(hide($ probe^(probe^probe) $))
+
+#context test(
+  query(metadata).map(it => it.value.style).sorted(),
+  ("display", "script", "script-script"),
+)
+#context test(
+  query(metadata).map(it => it.value.scale).sorted(),
+  (50%, 70%, 100%),
+)
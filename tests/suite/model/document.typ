@@ -9,6 +9,14 @@ What's up?
 // This, too.
 #set document(author: ("A", "B"), date: datetime.today())
 
+--- document-set-extra ---
+#set document(extra: (slug: "hello", draft: false))
+#context test(document.extra, (slug: "hello", draft: false))
+
+--- document-extra-bad ---
+// Error: 22-28 expected dictionary, found string
+#set document(extra: "slug")
+
 --- document-date-bad ---
 // Error: 21-28 expected datetime, none, or auto, found string
 #set document(date: "today")
@@ -0,0 +1,54 @@
+// Test endnotes.
+
+--- endnote-counter ---
+#place(hide[
+  A#endnote[One] <first>
+  B#endnote[Two]
+  C#endnote(<first>) @first
+  #endnotes()
+])
+#context test(counter(endnote).final(), (2,))
+
+--- endnote-listing-per-chapter ---
+#show endnote.entry: it => [#metadata(it.note.body) <entry>]
+#place(hide[
+  A#endnote[One]
+  #endnotes(title: none)
+  B#endnote[Two]
+  C#endnote[Three]
+  #endnotes(title: none)
+])
+#context test(query(<entry>).map(it => it.value), ([One], [Two], [Three]))
+
+--- endnote-listing ---
+#set page(height: auto)
+Typst is a typesetting system.#endnote[It was first released in 2023.]
+It compiles fast.#endnote[Incrementally, even.] <fast>
+Really fast. @fast
+
+#endnotes()
+
+--- endnote-listing-styled ---
+#set page(height: auto)
+#set endnote(numbering: "i")
+#set endnote.entry(indent: 1em)
+#set endnotes(gap: 1em)
+#show endnote.entry: set text(red)
+= Introduction
+Notes are useful.#endnote[Usually.]
+#endnotes(title: [Notes])
+
+= Conclusion
+Notes were useful.#endnote[Mostly.]#endnote[Almost always.]
+#endnotes(title: none)
+
+--- endnote-listing-lang ---
+#set page(height: auto)
+#set text(lang: "de")
+Ein Satz.#endnote[Eine Anmerkung.]
+#endnotes()
+
+--- endnote-entry-without-location ---
+// Error: 2-30 endnote entry must have a location
+// Hint: 2-30 try using a query or a show rule to customize the endnote instead
+#endnote.entry(endnote[Note])
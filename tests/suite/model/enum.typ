@@ -101,6 +101,16 @@ a + 0.
 // Error: 22-28 invalid numbering pattern
 #set enum(numbering: "(())")
 
+--- enum-numbering-lang ---
+// Test that the default markers count with the language's native digits. The
+// test fonts lack these digits, so they are shown by their code points.
+#show regex("[\u{660}-\u{669}\u{966}-\u{96F}]"): it => {
+  text(eastern, str(str.to-unicode(it.text), base: 16))
+}
+#text(lang: "ar", enum[One][Two])
+#text(lang: "hi", enum[One][Two])
+#text(lang: "hi", enum(numbering: "1)")[Explicit])
+
 --- enum-number-align-unaffected ---
 // Alignment shouldn't affect number
 #set align(horizon)
@@ -154,3 +164,16 @@ a + 0.
 // Enum item (pre-emptive)
 #enum.item(none)[Hello]
 #enum.item(17)[Hello]
+
+--- enum-item-ref ---
+// Test referencing a labelled nested enum item.
+#set enum(numbering: "1.a.")
+#place(hide[
+  + First
+  + Second
+    + Nested
+    #enum.item(3)[Third] <third>
+])
+
+#context test(query(<third>).len(), 1)
+#context test(measure[@third].width, measure[2.c].width)
@@ -226,3 +226,48 @@ HI#footnote.entry(clearance: 2.5em)[There]
 // Test that figure caption separator is synthesized correctly.
 #show figure.caption: c => test(c.separator, [#": "])
 #figure(table[], caption: [This is a test caption])
+
+--- figure-caption-pos-invalid ---
+// Error: 26-33 expected `top`, `bottom`, `left`, `right`, "inside", or "outside"
+#figure([], caption-pos: horizon)
+
+--- figure-caption-pos-beside ---
+#set page(width: 160pt)
+#figure(
+  rect(width: 40pt, height: 30pt),
+  caption: [Left],
+  caption-pos: left,
+)
+#figure(
+  rect(width: 40pt, height: 30pt),
+  caption: [Right],
+  caption-pos: right,
+)
+
+--- figure-caption-pos-inside-outside ---
+// Inside and outside captions switch sides between verso and recto pages.
+#set page(width: 160pt, height: 100pt, binding: left)
+#let fig(pos) = figure(
+  rect(width: 40pt, height: 30pt),
+  caption: [#pos],
+  caption-pos: pos,
+)
+#fig("inside")
+#fig("outside")
+#pagebreak()
+#fig("inside")
+#fig("outside")
+
+--- figure-caption-rotate ---
+#set page(width: 160pt)
+#figure(
+  rect(width: 40pt, height: 60pt),
+  caption: [Rotated],
+  caption-pos: left,
+  caption-rotate: -90deg,
+)
+#figure(
+  rect(width: 60pt, height: 20pt),
+  caption: [Upside down],
+  caption-rotate: 180deg,
+)
@@ -0,0 +1,82 @@
+// Test headline fitting.
+
+--- headline-fit-as-is ---
+#context {
+  let fitted = headline.fit(headline[Short], 200pt)
+  test(fitted.strategy, none)
+  test(fitted.alternative, none)
+  test(fitted.lines, 1)
+  test(fitted.fits, true)
+}
+
+--- headline-fit-tracking ---
+#context {
+  let body = [Typesetting Made Simple]
+  let width = measure(body).width * 0.99
+  let fitted = headline.fit(headline(max-lines: 1, body), width)
+  test(fitted.strategy, "tracking")
+  test(fitted.scale, 100%)
+  test(fitted.fits, true)
+}
+
+--- headline-fit-scale ---
+#context {
+  let body = [Typesetting Made Simple]
+  let width = measure(body).width * 0.85
+  let fitted = headline.fit(headline(max-lines: 1, strategies: ("scale",), body), width)
+  test(fitted.strategy, "scale")
+  test(fitted.tracking, 0pt)
+  test(fitted.scale < 100%, true)
+  test(fitted.fits, true)
+}
+
+--- headline-fit-alternative ---
+#context {
+  let fitted = headline.fit(
+    headline(max-lines: 1, alternatives: ([A], [B]))[#lorem(10)],
+    20pt,
+  )
+  test(fitted.alternative, 0)
+  test(fitted.fits, true)
+}
+
+--- headline-fit-wrong-element ---
+// Error: 10-40 expected headline, found strong
+#context headline.fit(strong[Hi], 10pt)
+
+--- headline-fit-hyphenate ---
+#context {
+  let body = [Extraordinarily Unconventional Typesetting]
+  let fitted = headline.fit(headline(max-lines: 2, body), 110pt)
+  test(fitted.strategy, "hyphenate")
+  test(fitted.hyphenate, true)
+  test(fitted.tracking, 0pt)
+  test(fitted.fits, true)
+}
+
+--- headline-fit-least-amount ---
+#context {
+  let body = [Typesetting Made Simple]
+  let width = measure(body).width * 0.99
+  let fitted = headline.fit(
+    headline(max-lines: 1, strategies: ("tracking",), min-tracking: -0.1em, body),
+    width,
+  )
+  test(fitted.fits, true)
+  test(fitted.tracking < 0pt, true)
+  test(fitted.tracking > -0.1em.to-absolute(), true)
+}
+
+--- headline-render ---
+#set page(width: 120pt)
+#headline(max-lines: 1)[*Typesetting Made Simple*]
+#headline(max-lines: 1, strategies: ("scale",))[*Typesetting Made Simple*]
+#headline(max-lines: 1, alternatives: ([*Simple Typesetting*],))[
+  *Typesetting Made Remarkably Simple*
+]
+
+--- headline-does-not-fit ---
+#set page(width: 60pt)
+// Warning: 2-41 headline does not fit into 1 line
+// Hint: 2-41 try allowing more lines or a smaller minimum scale
+#headline(max-lines: 1)[*Breaking News*]
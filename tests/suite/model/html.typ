@@ -0,0 +1,19 @@
+// Test the `html` module.
+
+--- html-snippet-hidden ---
+#html.html("<div class=\"note\"><br><img src=\"a.png\"/></div>")
+#html.html("<script>if (a < b) {}</script><!-- <p> -->")
+#context test(query(html.html).len(), 2)
+
+--- html-snippet-placeholder ---
+#let elem = html.html("<video></video>", placeholder: [Video])
+#test(elem.placeholder, [Video])
+#test(elem.html, "<video></video>")
+
+--- html-snippet-unclosed ---
+// Error: 12-19 unclosed tag `div`
+#html.html("<div>")
+
+--- html-snippet-mismatched ---
+// Error: 12-28 expected closing tag for `b`, found `i`
+#html.html("<p><b>bold</i>")
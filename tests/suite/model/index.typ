@@ -0,0 +1,43 @@
+// Test the back-of-book index.
+
+--- index-page-ranges ---
+#set page(width: 80pt, height: auto)
+#index.entry("Typst")
+#pagebreak()
+#index.entry("Typst")
+#pagebreak()
+#index.entry("Typst", sub: "markup")
+#index.entry("Typst")
+#index(title: none)
+
+--- index-see ---
+#index.entry("layout", see: "typesetting")
+#index.entry("layout", see: "typesetting")
+#context test(
+  measure(index(title: none)).width,
+  measure[layout, _see_ typesetting].width,
+)
+
+--- index-sort-lang ---
+#set page(width: 80pt, height: auto)
+#set text(lang: "sv")
+#for term in ("öl", "zebra", "ål", "äpple", "apa", "Émile") {
+  index.entry(term)
+}
+#index(title: none)
+
+--- index-sort-lang-spanish ---
+#set page(width: 80pt, height: auto)
+#set text(lang: "es")
+#for term in ("ñandú", "nube", "oso", "Nación") {
+  index.entry(term)
+}
+#index(title: none)
+
+--- index-sub-key ---
+#set page(width: 80pt, height: auto)
+#index.entry("Typst", sub: "markup")
+#index.entry("Typst", sub: "10 reasons", key: "ten reasons")
+#index.entry("Typst", sub: "code")
+#index.entry("LaTeX")
+#index(title: none)
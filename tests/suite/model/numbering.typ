@@ -102,6 +102,62 @@
 #assert.eq(numbering("\u{0661}", 1475), "١٤٧٥")
 #assert.eq(numbering("\u{06F1}", 1475), "۱۴۷۵")
 
+--- numbering-devanagari ---
+#assert.eq(numbering("\u{0967}", 1475), "१४७५")
+#assert.eq(numbering("(१)", 0), "(०)")
+
+--- numbering-ordinal ---
+#context assert.eq(numbering("{ordinal}", 1), "1st")
+#context assert.eq(numbering("{ordinal}", 12), "12th")
+#context assert.eq(numbering("{ordinal}", 23), "23rd")
+
+--- numbering-ordinal-lang ---
+#set text(lang: "fr")
+#context assert.eq(numbering("{ordinal}", 1), "1er")
+#context assert.eq(numbering("{ordinal}", 2), "2e")
+#set text(lang: "de")
+#context assert.eq(numbering("{ordinal} Kapitel", 3), "3. Kapitel")
+#set text(lang: "uk")
+#context assert.eq(numbering("{ordinal}", 3), "3-й")
+
+--- numbering-words ---
+#context assert.eq(numbering("Chapter {Words}", 1), "Chapter One")
+#context assert.eq(numbering("{words}", 42), "forty-two")
+#context assert.eq(numbering("{words}", 1905), "one thousand nine hundred five")
+#context assert.eq(numbering("{words}.{ordinal}", 2, 3), "two.3rd")
+
+--- numbering-words-lang ---
+#set text(lang: "de")
+#context assert.eq(numbering("{Words}", 1), "Eins")
+#context assert.eq(numbering("{words}", 21), "einundzwanzig")
+#context assert.eq(numbering("{words}", 101), "einhunderteins")
+
+--- numbering-words-without-context ---
+// Warning: 12-43 numbering in English because the text language is unknown
+// Hint: 12-43 try wrapping this in a `context` expression
+#assert.eq(numbering("Chapter {Words}", 3), "Chapter Three")
+
+--- numbering-braced ---
+#assert.eq(numbering("Appendix {A}", 2), "Appendix B")
+#assert.eq(numbering("Appendix {A}.{1}", 2, 3), "Appendix B.3")
+#assert.eq(numbering("{i}", 4), "iv")
+#assert.eq(numbering("{1}", 5), "5")
+#assert.eq(numbering("1 {", 5), "5 {")
+#assert.eq(numbering("{I}{x}", 4), "IV{x}")
+
+--- numbering-braced-repr ---
+#set heading(numbering: "Appendix {A}.{1}")
+#context test(heading.numbering, "Appendix {A}.{1}")
+#set heading(numbering: "1.a")
+#context test(heading.numbering, "1.a")
+
+--- numbering-braced-heading ---
+#set heading(numbering: "Chapter {Words}")
+#set text(lang: "de")
+= Einleitung
+#set heading(numbering: "Part {I}:")
+= Analyse
+
 --- numbering-negative ---
 // Error: 17-19 number must be at least zero
 #numbering("1", -1)
@@ -112,4 +168,55 @@
 
 --- numbering-double-circled-number ---
 #assert.eq(numbering("⓵", 1), "⓵")
-#assert.eq(numbering("⓵", 10), "⓾")
\ No newline at end of file
+#assert.eq(numbering("⓵", 10), "⓾")
+
+--- numbering-dependent ---
+#let fig = counter(figure.where(kind: image))
+#let show-fig = () => fig.display(dependent(heading, "1.a"))
+#counter(heading).step()
+#fig.step()
+#fig.step()
+#context test(show-fig(), "1.b")
+#counter(heading).step(level: 2)
+#fig.step()
+#context test(show-fig(), "1.c")
+#counter(heading).step()
+#fig.step()
+#context test(show-fig(), "2.a")
+#context test(numbering(dependent(heading, "1-1"), 3, 4), "3-4")
+
+--- numbering-dependent-before-parent ---
+#let fig = counter(figure.where(kind: image))
+#fig.step()
+#context test(fig.display(dependent(heading, "1.1")), "0.1")
+
+--- numbering-dependent-figures ---
+#set page(width: 160pt)
+#set heading(numbering: "1.")
+#set figure(numbering: dependent(heading, "1.1"))
+#show figure.where(kind: table): set figure(numbering: dependent(heading, "1-1"))
+
+#outline(title: none, target: figure)
+
+= Introduction
+#figure(rect(height: 8pt), caption: [First]) <first>
+#figure(table[A], caption: [Data])
+
+= Results
+#figure(rect(height: 8pt), caption: [Second]) <second>
+#figure(table[B], caption: [More data]) <data>
+
+See @first, @second, and @data.
+
+--- numbering-dependent-query ---
+#set heading(numbering: "1.")
+#set figure(numbering: dependent(heading, "1.a"))
+= A
+#figure([], caption: [X])
+= B
+#figure([], caption: [Y])
+#figure([], caption: [Z]) <z>
+#context {
+  let fig = query(<z>).first()
+  test(fig.counter.display(fig.numbering), "2.b")
+}
@@ -174,3 +174,48 @@ Ok ...
 // Error: 2-55 heading must have a location
 // Hint: 2-55 try using a query or a show rule to customize the outline.entry instead
 #outline.entry(1, heading[Hello], [World!], none, [1])
+
+--- outline-filter-and-group ---
+#show heading: none
+#show outline.entry: it => metadata(it.element.body)
+#place(hide(outline(
+  title: none,
+  target: heading.where(level: 2),
+  filter: it => it.body != [Skip],
+  group: it => {
+    let chapters = query(heading.where(level: 1).before(it.location()))
+    metadata(chapters.last().body)
+  },
+)))
+
+= One
+== A
+== Skip
+== B
+= Two
+== C
+
+#context test(
+  query(metadata).map(it => it.value),
+  ([One], [A], [B], [Two], [C]),
+)
+
+--- outline-entry-numbers ---
+#set heading(numbering: "1.a")
+#show heading: none
+#show outline.entry: it => metadata(it.numbers)
+#place(hide(outline(title: none)))
+
+= One
+== Two
+#heading(numbering: none)[Three]
+
+#context test(
+  query(metadata).map(it => if it.value != none { it.value.text }),
+  ("1", "1.a", none),
+)
+
+--- outline-filter-bad-return ---
+// Error: 18-20 expected boolean, found integer
+#outline(filter: it => 1)
+= Heading
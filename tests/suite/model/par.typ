@@ -76,3 +76,35 @@ Welcome \ here. Does this work well?
 #set text(dir: rtl)
 لآن وقد أظلم الليل وبدأت النجوم
 تنضخ وجه الطبيعة التي أعْيَتْ من طول ما انبعثت في النهار
+
+--- par-tabs ---
+// Test that tabs advance to their stops.
+#set par(tabs: (2cm, (pos: 3cm, align: end, leader: [.])))
+#let width(body) = calc.round(measure(body).width.pt(), digits: 2)
+#context test(width[A #tab() B], width[#h(2cm)B])
+#context test(width[A #tab() B #tab() C], calc.round(3cm.pt(), digits: 2))
+
+--- par-tabs-past-last-stop ---
+// A tab past the last stop only inserts a small space.
+#set par(tabs: (1cm,))
+#let width(body) = calc.round(measure(body).width.pt(), digits: 2)
+#context test(width[A #tab() B #tab() C], width[#h(1cm)B#h(0.25em)C])
+
+--- par-tabs-leader ---
+// Test that leaders fill the space up to the stop.
+#set page(width: 120pt, height: auto)
+#set par(tabs: ((pos: 100%, align: end, leader: [.]),))
+Introduction #tab() 1 \
+Tabs and leaders #tab() 12 \
+#set par(tabs: ((pos: 60pt, leader: line(length: 100%)),))
+Name #tab() Value
+
+--- par-tabs-break ---
+// Test that tabs count towards the line width when breaking.
+#set page(width: 120pt, height: auto)
+#set par(tabs: (50pt,))
+Alpha #tab() Beta Gamma Delta
+
+--- par-tabs-missing-pos ---
+// Error: 16-31 dictionary does not contain key "pos"
+#set par(tabs: ((align: end),))
@@ -54,3 +54,38 @@ $ A = 1 $ <eq2>
 // Error: 1-7 label occurs in the document and its bibliography
 @arrgh
 #bibliography("/assets/bib/works.bib")
+
+--- ref-form-page ---
+#set page(width: 80pt, height: 30pt, margin: 5pt)
+#metadata(none) <a>
+#pagebreak()
+#metadata(none) <a>
+#pagebreak()
+#metadata(none) <a>
+#pagebreak()
+#pagebreak()
+#metadata(none) <a>
+#metadata(none) <b>
+#ref(<a>, form: "page") \
+#ref(<b>, form: "page")
+
+--- ref-form-page-spanning ---
+#set page(width: 80pt, height: 40pt, margin: 5pt)
+#block(breakable: true, width: 100%, height: 45pt, fill: aqua) <tall>
+#ref(<tall>, form: "page")
+
+--- ref-form-page-spanning-counter ---
+#set page(width: 80pt, height: 40pt, margin: (rest: 5pt, bottom: 12pt), numbering: "1")
+#counter(page).update(5)
+#block(breakable: true, width: 100%, height: 40pt, fill: aqua) <tall>
+#ref(<tall>, form: "page")
+
+--- ref-form-page-range-lang ---
+#set page(width: 80pt, height: 40pt, margin: 5pt)
+#set text(lang: "eo", translations: (eo: (pages: "pp.", page-range: " ĝis ")))
+#block(breakable: true, width: 100%, height: 45pt, fill: aqua) <tall>
+#ref(<tall>, form: "page")
+
+--- ref-form-page-missing ---
+// Error: 2-30 label `<missing>` does not exist in the document
+#ref(<missing>, form: "page")
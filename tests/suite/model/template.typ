@@ -0,0 +1,36 @@
+// Test templates with slots.
+
+--- template-fill ---
+#template("greet", [Hello #slot("name", [World])!])
+#context test(
+  measure(use-template("greet", name: [Typst])).width,
+  measure[Hello Typst!].width,
+)
+#context test(
+  measure(use-template("greet")).width,
+  measure[Hello World!].width,
+)
+
+--- template-missing-slots ---
+#template("card", [#slot("title") #slot("body") #slot("title")])
+// Error: 2-22 missing slots "title", "body" for template "card"
+// Hint: 2-22 fill slots with named arguments, e.g. `title: [..]`
+#use-template("card")
+
+--- template-unknown-slot ---
+#template("card", [#slot("title") #slot("body")])
+// Error: 2-58 template "card" has no slot "footer"
+#use-template("card", title: [A], body: [B], footer: [C])
+
+--- template-positional-slot ---
+// Error: 23-26 unexpected argument
+// Hint: 23-26 slots must be filled with named arguments
+#use-template("card", [A])
+
+--- template-does-not-exist ---
+// Error: 2-25 template "missing" does not exist in the document
+#use-template("missing")
+
+--- template-slot-outside ---
+// Error: 2-15 slot "title" is not filled
+#slot("title")
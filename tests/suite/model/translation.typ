@@ -0,0 +1,23 @@
+// Test translatable segments.
+
+--- translatable-translated ---
+#set translatable(translations: (greeting: "Hallo"))
+#context test(
+  measure(translatable(id: "greeting")[Hello]),
+  measure[Hallo],
+)
+
+--- translatable-untranslated ---
+#set translatable(translations: (other: "Andere"))
+#context test(measure(translatable[Hello]), measure[Hello])
+
+--- translatable-query ---
+#translatable(id: "a")[One] #translatable[Two]
+#context test(query(translatable).map(it => it.body), ([One], [Two]))
+
+--- translatable-duplicate-id ---
+#translatable(id: "a")[One]
+// Warning: 2-28 segment `a` has a different text than its first occurrence
+// Hint: 2-28 give the segments different IDs
+#translatable(id: "a")[Two]
+#translatable(id: "a")[One]
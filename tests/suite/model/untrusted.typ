@@ -0,0 +1,69 @@
+// Test restrictions on untrusted content.
+
+--- untrusted-place ---
+// Error: 12-25 untrusted content may not use absolute placement
+#untrusted(place(top)[A])
+
+--- untrusted-place-float ---
+#set page(height: 60pt)
+#untrusted(place(top, float: true)[A])
+B
+
+--- untrusted-read ---
+// Error: 18-33 failed to load file (untrusted content may not read files)
+#untrusted[#read("untrusted.typ")]
+
+--- untrusted-read-in-eval ---
+// Error: 17-43 failed to load file (untrusted content may not read files)
+#untrusted(eval("#read(\"untrusted.typ\")", mode: "markup"))
+
+--- untrusted-read-in-function ---
+// Error: 20-35 failed to load file (untrusted content may not read files)
+#let load() = read("untrusted.typ")
+#untrusted[#load()]
+
+--- untrusted-read-outside ---
+#let before = read("untrusted.typ")
+#untrusted[Trusted reads around untrusted content are fine.]
+#test(read("untrusted.typ"), before)
+
+--- untrusted-include ---
+// Error: 21-57 failed to load file (untrusted content may not read files)
+#untrusted[#include "/suite/scripting/modules/chap1.typ"]
+
+--- untrusted-import ---
+// Error: 20-56 failed to load file (untrusted content may not read files)
+#untrusted[#import "/suite/scripting/modules/chap1.typ"]
+
+--- untrusted-context ---
+// Error: 26-41 failed to load file (untrusted content may not read files)
+#untrusted[#context read("untrusted.typ")]
+
+--- untrusted-show-rule ---
+// Error: 34-49 failed to load file (untrusted content may not read files)
+#untrusted[#show "A": it => read("untrusted.typ"); A]
+
+--- untrusted-with ---
+#let restrict = untrusted.with()
+// Error: 17-32 failed to load file (untrusted content may not read files)
+#restrict[#read("untrusted.typ")]
+
+--- untrusted-show-everything ---
+#show: untrusted
+// Error: 7-22 failed to load file (untrusted content may not read files)
+#read("untrusted.typ")
+
+--- untrusted-plugin ---
+// Error: 20-48 failed to load file (untrusted content may not read files)
+#untrusted[#plugin("/assets/plugins/hello.wasm")]
+
+--- untrusted-plugin-call ---
+#let p = plugin("/assets/plugins/hello.wasm")
+// Error: 13-22 untrusted content may not call plugins
+#untrusted[#p.hello()]
+
+--- untrusted-plugin-call-in-function ---
+#let p = plugin("/assets/plugins/hello.wasm")
+// Error: 16-25 untrusted content may not call plugins
+#let hello() = p.hello()
+#untrusted[#hello()]
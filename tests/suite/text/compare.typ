@@ -0,0 +1,34 @@
+// Test text comparison.
+
+--- compare-word ---
+#show compare.deletion: it => [-#it.body]
+#show compare.insertion: it => [+#it.body]
+#context test(
+  measure(compare("the cat sat", "the dog sat on it")).width,
+  measure[the -cat+dog sat+ on it].width,
+)
+
+--- compare-sentence ---
+#show compare.deletion: it => [-#it.body]
+#show compare.insertion: it => [+#it.body]
+#context test(
+  measure(compare(granularity: "sentence", "One. Two. Three.", "One. Too. Three.")).width,
+  measure[One. -Two. +Too. Three.].width,
+)
+
+--- compare-equal ---
+#context test(
+  measure(compare("No changes here.", "No changes here.")).width,
+  measure[No changes here.].width,
+)
+
+--- compare-default-style ---
+#compare(
+  "The tenant shall pay the rent monthly.",
+  "The tenant shall pay the full rent quarterly.",
+)
+
+--- compare-scattered ---
+#show compare.deletion: it => [-#it.body]
+#show compare.insertion: it => [+#it.body]
+#compare("a b c a b b a", "c b a b a c")
@@ -0,0 +1,20 @@
+// Test per-glyph styling.
+
+--- text-glyphs-styled ---
+#text.glyphs(
+  (i, c) => if c != " " { (fill: red, dx: i * 0.1pt, dy: 2pt, rotate: 10deg) },
+)[Hello world, fi]
+
+--- text-glyphs-across-runs ---
+// Clusters are counted across differently styled text.
+#text.glyphs(
+  (i, c) => (fill: if calc.even(i) { red } else { blue }),
+)[Ab *cd* _ef_]
+
+--- text-glyphs-bad-return ---
+// Error: 14-20 expected dictionary or none, found integer
+#text.glyphs((i, c) => 5)[Hi]
+
+--- text-glyphs-bad-key ---
+// Error: 14-20 unexpected key "size", valid keys are "fill", "dx", "dy", and "rotate"
+#text.glyphs((i, c) => (size: 2pt))[Hi]
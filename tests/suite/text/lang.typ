@@ -83,3 +83,40 @@
 // Error: 17-24 expected two or three letter language code (ISO 639-1/2/3)
 // Hint: 17-24 you should leave only "en" in the `lang` parameter and specify "gb" in the `region` parameter
 #set text(lang: "en-gb")
+
+--- text-translations ---
+#set text(lang: "eo", translations: (eo: (figure: "Figuro")))
+#show figure: it => test(it.supplement, [Figuro])
+#figure(rect())
+
+--- text-translations-region ---
+#set text(
+  lang: "pt",
+  region: "BR",
+  translations: (pt: (table: "Tabela"), "pt-BR": (table: "Quadro")),
+)
+#show figure: it => test(it.supplement, [Quadro])
+#figure(table[A])
+
+--- text-translations-fallback ---
+#set text(lang: "de", translations: (de: (outline: "Übersicht")))
+#show figure: it => test(it.supplement, [Abbildung])
+#figure(rect())
+
+--- text-translations-date ---
+#let date = datetime(year: 2024, month: 1, day: 2)
+#set text(translations: (en: (date: "[day].[month].[year]")))
+#context test(date.display(), "02.01.2024")
+#test(date.display(), "2024-01-02")
+
+--- text-translations-bad-lang ---
+// Error: 25-52 expected two or three letter language code (ISO 639-1/2/3)
+#set text(translations: (english: (figure: "Fig.")))
+
+--- text-translations-empty ---
+// Error: 25-43 translation for `figure` must not be empty
+#set text(translations: (en: (figure: "")))
+
+--- text-translations-unknown-key ---
+// Error: 25-46 unknown translation key `figur`
+#set text(translations: (en: (figur: "Fig.")))
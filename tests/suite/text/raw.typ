@@ -654,6 +654,17 @@ fn main() {
 #let foo = "bar"
 ```
 
+--- raw-highlighter-mismatch ---
+#set raw(highlighter: (text, lang) => ("nope",))
+// Error: 1-7 highlighter returned tokens that do not match the text
+// Hint: 1-7 the texts of the tokens must add up to the raw text
+`code`
+
+--- raw-highlighter-bad-key ---
+// Error: 23-35 unexpected key "color", valid keys are "text", "fill", "bold", "italic", and "underline"
+#set raw(highlighter: (text, lang) => ((text: text, color: red),))
+`code`
+
 --- raw-unclosed ---
 // Test unterminated raw text.
 //
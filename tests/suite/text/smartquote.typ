@@ -97,6 +97,49 @@ Some people's thought on this would be #[#set smartquote(enabled: false); "stran
 // Error: 25-45 expected 2 quotes, found 4 quotes
 #set smartquote(quotes: (single: ("'",) * 4))
 
+--- smartquote-custom-lang ---
+#set smartquote(quotes: (single: "‹›", "de-CH": "«»"))
+#context test(smartquote.quotes, (
+  double: auto,
+  single: ("‹", "›"),
+  "de-CH": (double: ("«", "»"), single: auto),
+))
+
+--- smartquote-custom-lang-nested ---
+// Error: 25-41 quotes for a specific language cannot be nested
+#set smartquote(quotes: (de: (fr: "«»")))
+
+--- smartquote-custom-lang-render ---
+// Test that quotes for a specific language take precedence.
+#set smartquote(quotes: (single: "‹›", fi: (double: "»»", single: "››")))
+"Default and 'custom single' quotes" \
+#text(lang: "fi")["Finnish and 'nested' quotes"] \
+#text(lang: "de")["German and 'custom single' quotes"]
+
+--- smartquote-nesting-alternate ---
+// Test that quotes alternate with each level of nesting.
+#set smartquote(nesting: "alternate")
+"Outer "inner "innermost" inner" outer" \
+'Outer 'inner 'innermost' inner' outer' \
+#text(lang: "de")["Außen "innen "ganz innen" innen" außen"]
+
+--- smartquote-nesting-alternate-apostrophe ---
+// Test that apostrophes don't close alternating quotes.
+#set smartquote(nesting: "alternate")
+"The dogs' bowls don't "fit" here." \
+"Rock and roll, "the kids' 'music'", they said."
+
+--- smartquote-elisions ---
+// Test that elided words start with an apostrophe.
+#set smartquote(elisions: ("'tis", "'90s", "’em"))
+'Tis the season of the '90s, 'they say'. \
+Get 'em, 'tisane' is not elided. \
+"'TIS" is case-insensitive.
+
+--- smartquote-nesting-bad ---
+// Error: 26-32 expected "typed" or "alternate"
+#set smartquote(nesting: "deep")
+
 --- issue-3662-pdf-smartquotes ---
 // Smart quotes were not appearing in the PDF outline, because they didn't
 // implement `PlainText`.
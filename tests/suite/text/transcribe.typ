@@ -0,0 +1,31 @@
+// Test the `transcribe` module.
+
+--- transcribe-braille ---
+#test(transcribe.to-braille("abc xyz"), "⠁⠃⠉ ⠭⠽⠵")
+#test(transcribe.to-braille("Hello, World!"), "⠠⠓⠑⠇⠇⠕⠂ ⠠⠺⠕⠗⠇⠙⠖")
+#test(transcribe.to-braille("NASA"), "⠠⠠⠝⠁⠎⠁")
+
+--- transcribe-braille-numbers ---
+#test(transcribe.to-braille("911"), "⠼⠊⠁⠁")
+#test(transcribe.to-braille("3.5"), "⠼⠉⠲⠑")
+#test(transcribe.to-braille("2b"), "⠼⠃⠰⠃")
+
+--- transcribe-braille-grade ---
+// Error: 2-42 only grade 1 braille is supported
+#transcribe.to-braille("hello", grade: 2)
+
+--- transcribe-respell ---
+#test(transcribe.respell("h@\"l@U"), "həˈləʊ")
+#test(transcribe.respell("\"TINk"), "ˈθɪŋk")
+#test(transcribe.respell("r\\Ed"), "ɹɛd")
+
+--- transcribe-phonetic ---
+// The phonetic fonts are tried before the ones that are already set.
+#set text(font: "DejaVu Sans Mono")
+#transcribe.phonetic[
+  /#transcribe.respell("h@\"l@U")/
+  #context test(
+    text.font,
+    ("charis sil", "doulos sil", "gentium plus", "new computer modern", "dejavu sans mono"),
+  )
+]
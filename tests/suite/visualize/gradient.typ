@@ -372,10 +372,26 @@
 #lorem(30)
 
 --- gradient-text-bad-relative ---
-// Make sure they don't work when `relative: "self"`.
-// Hint: 17-61 make sure to set `relative: auto` on your text fill
-// Error: 17-61 gradients and patterns on text must be relative to the parent
+// Make sure they don't work when `relative: "stroke"`.
+// Hint: 17-63 make sure to set `relative: auto` on your text fill
+// Error: 17-63 gradients and patterns on text cannot be relative to the stroke
+#set text(fill: gradient.linear(red, blue, relative: "stroke"))
+
+--- gradient-text-self-across-lines ---
+// Self-relative gradients span the text across line breaks.
+#set page(width: 100pt)
 #set text(fill: gradient.linear(red, blue, relative: "self"))
+#lorem(20)
+
+--- gradient-text-self-equation-and-transformed ---
+// Self-relative gradients span the text of block equations and of
+// transformed content.
+#set page(width: 160pt, height: auto)
+#set text(16pt)
+#let g = gradient.linear(red, blue, relative: "self")
+$ text(fill: #g, x + y = z) $
+#rotate(20deg, text(fill: g)[Rotated text])
+#scale(x: 80%, text(fill: g)[Scaled text])
 
 --- gradient-text-global ---
 // Test that gradient fills on text work for globally defined gradients.
@@ -579,6 +595,7 @@ $ A = mat(
 #test(gradient.linear(red, green, relative: "self").relative(), "self")
 #test(gradient.linear(red, green, relative: "parent").relative(), "parent")
 #test(gradient.linear(red, green).relative(), auto)
+#test(gradient.linear(red, green, relative: "stroke").relative(), "stroke")
 
 --- gradient-angle ---
 #test(gradient.linear(red, green).angle(), 0deg)
@@ -597,6 +614,108 @@ $ A = mat(
   ((red, 0%), (green, 25%), (blue, 50%), (green, 75%), (red, 100%))
 )
 
+--- gradient-mesh ---
+#let mesh = gradient.mesh((red, blue), (green, aqua), space: rgb)
+#test(mesh.kind(), gradient.mesh)
+#test(mesh.stops(), (red, blue, green, aqua))
+#test(mesh.space(), rgb)
+#test(mesh.angle(), none)
+#test(mesh.sample(0%), red)
+#test(mesh.sample(100%), aqua)
+
+--- gradient-mesh-too-few-rows ---
+// Error: 2-28 a mesh gradient must have at least two rows
+// Hint: 2-28 try using a linear gradient instead
+#gradient.mesh((red, blue))
+
+--- gradient-mesh-short-row ---
+// Error: 29-37 each row of a mesh gradient must have at least two colors
+#gradient.mesh((red, blue), (green,))
+
+--- gradient-mesh-unequal-rows ---
+// Error: 29-47 all rows of a mesh gradient must have the same number of colors
+// Hint: 29-47 expected 2 colors, found 3
+#gradient.mesh((red, blue), (green, red, blue))
+
+--- gradient-mesh-sharp ---
+// Error: 51-52 mesh gradients cannot be made sharp
+#gradient.mesh((red, blue), (green, white)).sharp(5)
+
+--- gradient-mesh-fill ---
+// A mesh with a moved inner point bends its patches.
+#set page(width: 140pt, height: 90pt, margin: 5pt)
+#rect(
+  width: 100%,
+  height: 100%,
+  fill: gradient.mesh(
+    (red, yellow, green),
+    (purple, (white, (70%, 20%)), aqua),
+    (blue, (orange, (30%, 100%)), teal),
+  ),
+)
+
+--- gradient-mesh-position-out-of-range ---
+// Error: 29-59 mesh point positions must be between 0% and 100%
+#gradient.mesh((red, blue), (green, (white, (100%, 120%))))
+
+--- gradient-mesh-position-off-edge ---
+// Error: 36-70 points on the edge of a mesh gradient must stay on that edge
+// Hint: 36-70 points on the edge can only move along it
+#gradient.mesh((red, blue, green), (aqua, (white, (50%, 80%)), black))
+
+--- gradient-stroke-relative-path ---
+// Stroke-relative gradients follow the path, around joins and caps.
+#set page(width: 120pt, height: 120pt)
+#let grad = gradient.linear(red, yellow, blue, relative: "stroke")
+#path(
+  stroke: (paint: grad, thickness: 8pt, join: "round", cap: "round"),
+  (10pt, 50pt),
+  (35pt, 10pt),
+  (60pt, 50pt),
+  ((100pt, 50pt), (0pt, -20pt)),
+)
+#line(
+  length: 100%,
+  stroke: (paint: grad, thickness: 6pt, dash: "dashed"),
+)
+#circle(
+  radius: 20pt,
+  stroke: 6pt + gradient.mesh((red, blue), (green, white), relative: "stroke"),
+)
+
+--- gradient-procedural ---
+#let grad = gradient.procedural(
+  (x, y) => if x < 0.5 { red } else if y < 0.5 { green } else { blue },
+  samples: (3, 2),
+  space: rgb,
+)
+#test(grad.kind(), gradient.mesh)
+#test(grad.stops(), (red, green, green, red, blue, blue))
+#test(grad.space(), rgb)
+
+--- gradient-procedural-fill ---
+// The function is sampled on a grid and drawn as a mesh.
+#set page(width: 140pt, height: 80pt, margin: 5pt)
+#rect(
+  width: 100%,
+  height: 100%,
+  fill: gradient.procedural(
+    (x, y) => {
+      let wave = calc.sin(x * 12) * calc.cos(y * 8)
+      color.mix((blue, 50% + 50% * wave), (yellow, 50% - 50% * wave))
+    },
+    samples: (24, 8),
+  ),
+)
+
+--- gradient-procedural-too-few-samples ---
+// Error: 46-47 the number of samples must be between 2 and 256 per axis
+#gradient.procedural((x, y) => red, samples: 1)
+
+--- gradient-procedural-bad-return ---
+// Error: 2-34 expected color, found integer
+#gradient.procedural((x, y) => 1)
+
 --- gradient-repr ---
 // Gradients
 #set page(width: 400pt)
@@ -651,3 +770,35 @@ $ A = mat(
   height: 10pt,
   fill: gradient.linear(violet, blue, space: cmyk)
 )
+
+--- gradient-transform ---
+#set page(width: 100pt, height: auto, margin: 5pt)
+#set block(spacing: 5pt)
+#rect(width: 100%, height: 30pt, fill: gradient.linear(
+  red, blue,
+  transform: transform.skew(ax: 30deg),
+))
+#rect(width: 100%, height: 30pt, fill: gradient.radial(
+  red, blue,
+  radius: 30%,
+  transform: transform.scale(100%, 50%),
+))
+#block(width: 100%, height: 30pt, rect(
+  width: 60%,
+  height: 100%,
+  fill: gradient.conic(
+    ..color.map.rainbow,
+    relative: "parent",
+    transform: transform.translate(20pt, 0pt),
+  ),
+))
+
+--- gradient-transform-repr ---
+#test(
+  repr(gradient.linear(red, blue, transform: transform.rotate(90deg)).sharp(2).transform()),
+  repr(transform.rotate(90deg)),
+)
+
+--- gradient-transform-singular ---
+// Error: 40-59 gradient transform must be invertible
+#gradient.linear(red, blue, transform: transform.scale(0%))
@@ -120,3 +120,96 @@ A #box(image("/assets/images/tiger.jpg", height: 1cm, width: 80%)) B
 --- issue-2051-new-cm-svg ---
 #set text(font: "New Computer Modern")
 #image("/assets/images/diagram.svg")
+
+--- image-icc-not-a-profile ---
+// Error: 41-67 file `/assets/images/rhino.png` is not an ICC profile
+#image("/assets/images/tiger.jpg", icc: "/assets/images/rhino.png")
+
+--- image-icc-not-found ---
+// Error: 41-54 file not found (searched at tests/suite/visualize/missing.icc)
+#image("/assets/images/tiger.jpg", icc: "missing.icc")
+
+--- image-pdf-page-zero ---
+// Error: 42-43 number must be positive
+#image("/assets/images/tiger.jpg", page: 0)
+
+--- image-pdf-multiple-pages ---
+// The PDF has a blue 20x10pt page and a red 10x30pt page.
+#context {
+  let size(page) = measure(image("/tests/assets/two-pages.pdf", page: page))
+  test(size(1), (width: 20pt, height: 10pt))
+  test(size(2), (width: 10pt, height: 30pt))
+}
+
+--- image-pdf-page-out-of-range ---
+// Error: 2-47 page 3 does not exist (the document has 2 pages)
+#image("/tests/assets/two-pages.pdf", page: 3)
+
+--- image-pdf-integer-out-of-range ---
+// Error: 2-41 PDF page contains an integer that is out of range (9999999999)
+#image("/tests/assets/huge-integer.pdf")
+
+--- image-pdf-text-fallback ---
+// Text in a font that is not embedded is drawn with the image's font.
+#image("/tests/assets/text.pdf", width: 40pt)
+
+--- image-pdf-text-and-images ---
+// Embedded fonts, a JPEG, and a PNG with transparency.
+#image("/tests/assets/text-and-images.pdf", width: 100pt)
+
+--- image-pdf-self-smask ---
+// An image whose soft mask is the image itself.
+#image("/tests/assets/self-smask.pdf")
+
+--- image-pdf-unsupported-warning ---
+// Warning: 2-32 PDF page contains images or fonts in unsupported formats, which are not shown in PNG and SVG exports
+// Hint: 2-32 export to PDF to include the complete page
+#image("/tests/assets/jpx.pdf")
+
+--- image-fit-natural-max ---
+// Test that natural-max images shrink to the available width only.
+#context {
+  let path = "/assets/images/tiger.jpg"
+  test(measure(image(path, fit: "natural-max")), (width: 1024pt, height: 670pt))
+  test(
+    measure(image(path, fit: "natural-max"), width: 512pt),
+    (width: 512pt, height: 335pt),
+  )
+}
+
+--- image-fit-natural-max-dpi-warning ---
+#context {
+  // Warning: 19-86 image was shrunk to 737dpi, which exceeds the maximum of 300dpi
+  // Hint: 19-86 try giving the image more space or cropping it
+  let _ = measure(image("/assets/images/tiger.jpg", fit: "natural-max", max-dpi: 300), width: 100pt)
+}
+
+--- image-focus ---
+#let picture = ```
+<svg xmlns="http://www.w3.org/2000/svg" width="80" height="80">
+  <rect width="80" height="20" fill="red"/>
+  <rect y="20" width="80" height="40" fill="green"/>
+  <rect y="60" width="80" height="20" fill="blue"/>
+</svg>
+```.text
+#set page(width: 120pt, height: auto)
+#image.decode(picture, width: 100%, height: 20pt, focus: (50%, 0%))
+#image.decode(picture, width: 100%, height: 20pt)
+#image.decode(picture, width: 100%, height: 20pt, focus: (50%, 100%))
+
+--- image-focus-contain ---
+// When the image doesn't fill its area, the focus places it.
+#let picture = ```
+<svg xmlns="http://www.w3.org/2000/svg" width="80" height="80">
+  <rect width="80" height="20" fill="red"/>
+  <rect y="20" width="80" height="40" fill="green"/>
+  <rect y="60" width="80" height="20" fill="blue"/>
+</svg>
+```.text
+#set page(width: 120pt, height: auto)
+#let framed(focus) = rect(
+  inset: 0pt,
+  image.decode(picture, width: 100%, height: 30pt, fit: "contain", focus: focus),
+)
+#framed((0%, 50%))
+#framed((100%, 50%))
@@ -90,3 +90,36 @@
 --- line-bad-point-component-type ---
 // Error: 14-26 expected relative length, found angle
 #line(start: (3deg, 10pt), length: 5cm)
+
+--- line-marker-field ---
+#test(line(marker: (end: ">")).marker, (end: ">"))
+#test(line(marker: (start: "*", end: [x])).marker.end, [x])
+#test(line(marker: none).marker, (:))
+
+--- line-marker-invalid-key ---
+// Error: 15-25 unexpected key "tip", valid keys are "start", "mid", and "end"
+#line(marker: (tip: ">"))
+
+--- line-marker-mid ---
+// Error: 15-35 unexpected key "mid", valid keys are "start" and "end"
+// Hint: 15-35 lines have no inner vertices, use a path for markers at vertices
+#line(marker: (mid: "*", end: ">"))
+
+--- line-marker-shapes ---
+#set page(width: 80pt, height: auto, margin: 5pt)
+#set line(length: 100%, stroke: 1.5pt)
+#stack(
+  spacing: 8pt,
+  line(marker: (end: ">")),
+  line(marker: (start: "<", end: "|")),
+  line(marker: (start: "o", end: "*")),
+  line(stroke: 1pt + eastern, marker: (start: "<>", end: ">")),
+  line(marker: (end: text(8pt, fill: red)[▶])),
+)
+
+--- line-marker-rotation ---
+#set page(width: 60pt, height: 60pt, margin: 5pt)
+#place(line(start: (25pt, 25pt), end: (50pt, 0pt), marker: (end: ">")))
+#place(line(start: (25pt, 25pt), end: (50pt, 50pt), marker: (end: ">")))
+#place(line(start: (25pt, 25pt), end: (0pt, 50pt), marker: (end: ">")))
+#place(line(start: (25pt, 25pt), end: (0pt, 0pt), marker: (end: [▶])))
@@ -63,3 +63,65 @@
     (10pt, 10pt),
   ),
 )
+
+--- path-measure ---
+#let round(v) = calc.round(v / 1pt, digits: 2)
+#let p = path((0pt, 0pt), (30pt, 0pt), (30pt, 40pt))
+#test(round(path.length(p)), 70.0)
+#let b = path.bounds(p)
+#test((b.x, b.y, b.width, b.height).map(round), (0.0, 0.0, 30.0, 40.0))
+#test(path.point-at(p, 0%).to-array().map(round), (0.0, 0.0))
+#test(path.point-at(p, 50%).to-array().map(round), (30.0, 5.0))
+#test(path.point-at(p, 100%).to-array().map(round), (30.0, 40.0))
+
+--- path-measure-closed ---
+#let round(v) = calc.round(v / 1pt, digits: 2)
+#let p = path(closed: true, (0pt, 0pt), (30pt, 0pt), (30pt, 40pt))
+#test(round(path.length(p)), 120.0)
+
+--- path-measure-em-in-context ---
+#set text(size: 10pt)
+#context test(calc.round(path.length(path((0pt, 0pt), (2em, 0pt))) / 1pt), 20.0)
+
+--- path-measure-zero-ratio ---
+#test(path.length(path((0pt, 0pt), (0% + 10pt, 0pt))), 10pt)
+
+--- path-measure-relative ---
+// Error: 2-31 cannot measure a path with relative coordinates
+// Hint: 2-31 use absolute lengths for the path's vertices
+#path.length(path((10%, 0pt)))
+
+--- path-measure-relative-control-point ---
+// Error: 2-45 cannot measure a path with relative coordinates
+// Hint: 2-45 use absolute lengths for the path's vertices
+#path.bounds(path(((0pt, 0pt), (10%, 5pt))))
+
+--- path-measure-em-no-context ---
+// Error: 2-31 can only be used when context is known
+// Hint: 2-31 try wrapping this in a `context` expression
+// Hint: 2-31 the `context` expression should wrap everything that depends on this function
+#path.length(path((1em, 0pt)))
+
+--- path-point-at-out-of-range ---
+// Error: 2-39 position must be between 0% and 100%
+#path.point-at(path((0pt, 0pt)), 150%)
+
+--- path-point-at-empty ---
+// Error: 2-38 cannot find a point on an empty path
+#path.point-at(path((0pt, 0pt)), 50%)
+
+--- path-marker-curve ---
+#set page(width: 80pt, height: 50pt, margin: 5pt)
+#path(
+  stroke: 2pt,
+  marker: (start: "o", mid: "*", end: ">"),
+  ((0pt, 40pt), (0pt, -20pt)),
+  ((35pt, 0pt), (-10pt, 0pt), (10pt, 0pt)),
+  ((70pt, 40pt), (0pt, -20pt)),
+)
+
+--- path-marker-shorten ---
+// The pulled back ends should keep the curve's shape.
+#set page(width: 80pt, height: 50pt, margin: 5pt)
+#path(stroke: 4pt + eastern, marker: (start: ">", end: ">"), ((0pt, 0pt), (0pt, 30pt)), ((70pt, 40pt), (0pt, 30pt)))
+#place(top + left, path(stroke: 0.5pt + red, ((0pt, 0pt), (0pt, 30pt)), ((70pt, 40pt), (0pt, 30pt))))
@@ -129,3 +129,8 @@
   #set text(fill: pat)
   #lorem(10)
 ]))
+
+--- pattern-relative-stroke ---
+// Error: 2-31 patterns cannot be relative to the stroke
+// Hint: 2-31 try using `relative: "self"` instead
+#pattern(relative: "stroke")[]
@@ -0,0 +1,76 @@
+--- shadow-fields ---
+#let s = shadow(dx: 1pt, dy: 2pt, blur: 3pt, color: red)
+#test(s.dx, 1pt)
+#test(s.dy, 2pt)
+#test(s.blur, 3pt)
+#test(s.color, red)
+#test(shadow().blur, 4pt)
+
+--- shadow-repr ---
+#test(repr(shadow(dx: 1pt, color: red)), "shadow(dx: 1pt, dy: 2pt, blur: 4pt, color: rgb(\"#ff4136\"))")
+
+--- shadow-negative-blur ---
+// Error: 2-20 shadow blur radius must not be negative
+#shadow(blur: -1pt)
+
+--- shadow-dict-unknown-key ---
+// Error: 15-28 unexpected key "offset", valid keys are "dx", "dy", "blur", and "color"
+#rect(shadow: (offset: 2pt))
+
+--- shadow-dict-negative-blur ---
+// Error: 15-27 shadow blur radius must not be negative
+#rect(shadow: (blur: -2pt))
+
+--- shadow-rect ---
+#set rect(width: 30pt, height: 20pt, fill: white, stroke: 0.5pt + gray)
+#stack(
+  dir: ltr,
+  spacing: 10pt,
+  rect(shadow: shadow()),
+  rect(radius: 4pt, shadow: (dx: 3pt, dy: 3pt, blur: 0pt)),
+  rect(outset: 2pt, shadow: (blur: 8pt, color: blue.transparentize(50%))),
+)
+
+--- shadow-shapes ---
+#set page(width: auto)
+#let s = shadow(dx: 2pt, dy: 2pt, blur: 3pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  square(size: 20pt, fill: aqua, shadow: s),
+  circle(radius: 10pt, fill: aqua, shadow: s),
+  ellipse(width: 30pt, height: 20pt, fill: aqua, shadow: s),
+  polygon(fill: aqua, shadow: s, (0pt, 20pt), (10pt, 0pt), (20pt, 20pt)),
+)
+
+--- shadow-box-and-block ---
+#set page(height: 80pt)
+Text with #box(inset: 2pt, fill: white, shadow: (dy: 2pt, blur: 2pt))[a box].
+#block(width: 100%, height: 90pt, inset: 4pt, fill: white, breakable: true,
+  shadow: (dy: 3pt, blur: 4pt))
+
+--- shadow-does-not-affect-layout ---
+#let body = [Shadowed]
+#context test(
+  measure(box(shadow: (dx: 5pt, dy: 5pt, blur: 10pt), body)),
+  measure(box(body)),
+)
+#context test(
+  measure(rect(width: 20pt, height: 10pt, blur: 6pt, shadow: shadow())),
+  measure(rect(width: 20pt, height: 10pt)),
+)
+
+--- blur-shapes ---
+#set page(width: auto)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(width: 20pt, height: 20pt, fill: blue),
+  rect(width: 20pt, height: 20pt, fill: blue, blur: 2pt),
+  rect(width: 20pt, height: 20pt, fill: blue, blur: 6pt),
+  circle(radius: 10pt, stroke: 2pt + red, blur: 3pt),
+)
+
+--- blur-fields ---
+#test(rect(blur: 3pt).blur, 3pt)
+#test(circle(shadow: red).shadow, shadow(color: red))
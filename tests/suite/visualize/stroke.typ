@@ -169,3 +169,13 @@
   height: 10pt,
   stroke: (left: rgb("46b3c2") + 16.0mm),
 )
+
+--- stroke-text-hollow ---
+#set text(fill: none, stroke: 0.3pt + black)
+#context test(text.fill, none)
+
+--- stroke-text-hollow-render ---
+// Decorations and lines in math take the stroke's paint.
+#set text(size: 16pt, fill: none, stroke: 0.4pt + blue)
+Hollow #underline[text] $1/2$ \
+#text(fill: red.lighten(60%))[Filled]